@@ -17,7 +17,7 @@ use zksync_config::{
     configs::{
         chain::NetworkConfig, wallets::Wallets, BasicWitnessInputProducerConfig, DatabaseSecrets,
         GatewayChainConfig, GeneralConfig, L1Secrets, ObservabilityConfig,
-        ProtectiveReadsWriterConfig,
+        ProtectiveReadsWriterConfig, StateDiffExporterConfig,
     },
     ContractsConfig, DBConfig, EthConfig, GenesisConfig, PostgresConfig,
 };
@@ -187,6 +187,11 @@ async fn main() -> anyhow::Result<()> {
         None => BasicWitnessInputProducerConfig::from_env()
             .context("BasicWitnessInputProducerConfig::from_env()")?,
     };
+    // The state diff exporter is optional, so its config may be missing.
+    let state_diff_exporter_config = match &general_config {
+        Some(general_config) => general_config.state_diff_exporter_config.clone(),
+        None => StateDiffExporterConfig::from_env().ok(),
+    };
     let contracts = match opts.contracts_config_path {
         Some(path) => read_yaml_repr::<proto::contracts::Contracts>(&path)
             .context("failed decoding contracts YAML config")?,
@@ -426,6 +431,19 @@ async fn main() -> anyhow::Result<()> {
                         basic_witness_input_producer_config.db_path,
                     );
                 }
+
+                if let Some(config) = state_diff_exporter_config {
+                    let cache_exists =
+                        fs::try_exists(&config.db_path).await.with_context(|| {
+                            format!(
+                                "cannot check whether storage cache path `{}` exists",
+                                config.db_path
+                            )
+                        })?;
+                    if cache_exists {
+                        block_reverter.add_rocksdb_storage_path_to_rollback(config.db_path);
+                    }
+                }
             }
 
             block_reverter
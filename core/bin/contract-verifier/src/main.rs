@@ -4,7 +4,10 @@ use anyhow::Context as _;
 use clap::Parser;
 use tokio::sync::watch;
 use zksync_config::configs::{ContractVerifierSecrets, DatabaseSecrets, PrometheusConfig};
-use zksync_contract_verifier_lib::{etherscan::EtherscanVerifier, ContractVerifier};
+use zksync_contract_verifier_lib::{
+    etherscan::EtherscanVerifier, inheritance::VerificationInheritanceTask,
+    proxies::ProxyTrackingTask, ContractVerifier,
+};
 use zksync_core_leftovers::temp_config_store::{load_general_config, read_yaml_repr};
 use zksync_dal::{ConnectionPool, Core, CoreDal};
 use zksync_env_config::FromEnv;
@@ -116,10 +119,14 @@ async fn main() -> anyhow::Result<()> {
     .await
     .context("failed initializing contract verifier")?;
     let update_task = contract_verifier.sync_compiler_versions_task();
+    let inheritance_task = VerificationInheritanceTask::new(pool.clone(), stop_receiver.clone());
+    let proxy_tracking_task = ProxyTrackingTask::new(pool.clone(), stop_receiver.clone());
 
     let mut tasks = vec![
         tokio::spawn(update_task),
         tokio::spawn(contract_verifier.run(stop_receiver.clone(), opt.jobs_number)),
+        tokio::spawn(inheritance_task.run()),
+        tokio::spawn(proxy_tracking_task.run()),
         tokio::spawn(
             PrometheusExporterConfig::pull(prometheus_config.listener_port)
                 .run(stop_receiver.clone()),
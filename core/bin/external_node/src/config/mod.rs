@@ -426,6 +426,9 @@ pub(crate) struct OptionalENConfig {
     /// Enables the stale keys repair task for the Merkle tree.
     #[serde(default)]
     pub merkle_tree_repair_stale_keys: bool,
+    /// Path to the RocksDB directory for the experimental ZK OS Merkle tree. If not set, the tree is not built.
+    #[serde(default)]
+    pub zk_os_merkle_tree_path: Option<String>,
 
     // Postgres config (new parameters)
     /// Threshold in milliseconds for the DB connection lifetime to denote it as long-living and log its details.
@@ -493,6 +496,17 @@ pub(crate) struct OptionalENConfig {
     /// If set to 0, L1 batches will not be retained based on their timestamp. The default value is 7 days.
     #[serde(default = "OptionalENConfig::default_pruning_data_retention_sec")]
     pruning_data_retention_sec: u64,
+    /// Events emitted by these contracts are retained when the containing L1 batches are pruned, so that they
+    /// can still be queried via `eth_getLogs`.
+    #[serde(default)]
+    pub pruning_retained_event_addresses: Vec<Address>,
+    /// Transactions initiated by or sent to these accounts are retained when the containing L1 batches are pruned,
+    /// so that their receipts can still be queried.
+    #[serde(default)]
+    pub pruning_retained_transaction_accounts: Vec<Address>,
+    /// Whether call traces of retained transactions are retained as well. Disabled by default.
+    #[serde(default)]
+    pub pruning_retain_call_traces: bool,
     /// Gateway RPC URL, needed for operating during migration.
     pub gateway_url: Option<SensitiveUrl>,
     /// Interval for bridge addresses refreshing in seconds.
@@ -500,6 +514,10 @@ pub(crate) struct OptionalENConfig {
     /// Minimum time between current block.timestamp and the end of the asserted range for TimestampAsserter
     #[serde(default = "OptionalENConfig::default_timestamp_asserter_min_time_till_end_sec")]
     pub timestamp_asserter_min_time_till_end_sec: u32,
+    /// Enables retrieval of DA blobs by the data availability fetcher. The pubdata in each blob is compared
+    /// with the pubdata produced by the node itself, and a mismatch is reported via the health check.
+    #[serde(default)]
+    pub da_blob_verification_enabled: bool,
 }
 
 impl OptionalENConfig {
@@ -666,6 +684,10 @@ impl OptionalENConfig {
                 .map_or(false, |config| {
                     config.experimental.merkle_tree_repair_stale_keys
                 }),
+            zk_os_merkle_tree_path: general_config
+                .db_config
+                .as_ref()
+                .and_then(|config| config.experimental.zk_os_merkle_tree_path.clone()),
             database_long_connection_threshold_ms: load_config!(
                 general_config.postgres_config,
                 long_connection_threshold_ms
@@ -714,6 +736,21 @@ impl OptionalENConfig {
                 data_retention_sec,
                 default_pruning_data_retention_sec
             ),
+            pruning_retained_event_addresses: general_config
+                .pruning
+                .as_ref()
+                .map(|a| a.retained_event_addresses.clone())
+                .unwrap_or_default(),
+            pruning_retained_transaction_accounts: general_config
+                .pruning
+                .as_ref()
+                .map(|a| a.retained_transaction_accounts.clone())
+                .unwrap_or_default(),
+            pruning_retain_call_traces: general_config
+                .pruning
+                .as_ref()
+                .map(|a| a.retain_call_traces)
+                .unwrap_or_default(),
             protective_reads_persistence_enabled: general_config
                 .db_config
                 .as_ref()
@@ -749,6 +786,7 @@ impl OptionalENConfig {
                 .as_ref()
                 .map(|x| x.min_time_till_end_sec)
                 .unwrap_or_else(Self::default_timestamp_asserter_min_time_till_end_sec),
+            da_blob_verification_enabled: enconfig.da_blob_verification_enabled.unwrap_or_default(),
         })
     }
 
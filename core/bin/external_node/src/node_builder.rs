@@ -15,6 +15,7 @@ use zksync_metadata_calculator::{
     MerkleTreeReaderConfig, MetadataCalculatorConfig, MetadataCalculatorRecoveryConfig,
 };
 use zksync_node_api_server::web3::Namespace;
+use zksync_node_db_pruner::RetentionRules;
 use zksync_node_framework::{
     implementations::layers::{
         batch_status_updater::BatchStatusUpdaterLayer,
@@ -272,11 +273,25 @@ impl ExternalNodeBuilder {
 
     fn add_pruning_layer(mut self) -> anyhow::Result<Self> {
         if self.config.optional.pruning_enabled {
+            let retention_rules = RetentionRules {
+                event_addresses: self
+                    .config
+                    .optional
+                    .pruning_retained_event_addresses
+                    .clone(),
+                transaction_accounts: self
+                    .config
+                    .optional
+                    .pruning_retained_transaction_accounts
+                    .clone(),
+                retain_call_traces: self.config.optional.pruning_retain_call_traces,
+            };
             let layer = PruningLayer::new(
                 self.config.optional.pruning_removal_delay(),
                 self.config.optional.pruning_chunk_size,
                 self.config.optional.pruning_data_retention(),
-            );
+            )
+            .with_retention_rules(retention_rules);
             self.node.add_layer(layer);
         } else {
             tracing::info!("Pruning is disabled");
@@ -378,7 +393,9 @@ impl ExternalNodeBuilder {
     }
 
     fn add_data_availability_fetcher_layer(mut self) -> anyhow::Result<Self> {
-        self.node.add_layer(DataAvailabilityFetcherLayer);
+        self.node.add_layer(DataAvailabilityFetcherLayer::new(
+            self.config.optional.da_blob_verification_enabled,
+        ));
 
         Ok(self)
     }
@@ -437,6 +454,11 @@ impl ExternalNodeBuilder {
             layer = layer.with_stale_keys_repair();
         }
 
+        // Add the experimental ZK OS tree if requested.
+        if let Some(path) = &self.config.optional.zk_os_merkle_tree_path {
+            layer = layer.with_zk_os_tree(path.clone());
+        }
+
         // Add tree pruning if needed.
         if self.config.optional.pruning_enabled {
             layer = layer.with_pruning_config(self.config.optional.pruning_removal_delay());
@@ -2,6 +2,7 @@ use std::{path::Path, time::Instant};
 
 use anyhow::Context as _;
 use clap::Parser;
+use zk_os_merkle_tree::{MerkleTree as ZkOsTree, RocksDBWrapper as ZkOsRocksDBWrapper};
 use zksync_config::{configs::ObservabilityConfig, DBConfig};
 use zksync_env_config::FromEnv;
 use zksync_merkle_tree::domain::ZkSyncTree;
@@ -20,10 +21,22 @@ struct Cli {
     /// applied to it last. If not specified, the latest tree version is checked.
     #[arg(long = "l1-batch")]
     l1_batch: Option<u32>,
+    /// Checks the experimental ZK OS Merkle tree instead of the main tree. The tree path is taken
+    /// from the experimental DB config.
+    #[arg(long)]
+    zk_os: bool,
+    /// Removes bogus stale keys from the ZK OS Merkle tree after checking it. Bogus stale keys point
+    /// to nodes still used by the tree, so they would break the tree on pruning.
+    #[arg(long, requires = "zk_os")]
+    repair_stale_keys: bool,
 }
 
 impl Cli {
     fn run(self, config: &DBConfig) -> anyhow::Result<()> {
+        if self.zk_os {
+            return self.run_for_zk_os_tree(config);
+        }
+
         let db_path = &config.merkle_tree.path;
         tracing::info!("Verifying consistency of Merkle tree at {db_path}");
         let start = Instant::now();
@@ -49,6 +62,49 @@ impl Cli {
         tracing::info!("Merkle tree verified in {:?}", start.elapsed());
         Ok(())
     }
+
+    fn run_for_zk_os_tree(self, config: &DBConfig) -> anyhow::Result<()> {
+        let db_path = config
+            .experimental
+            .zk_os_merkle_tree_path
+            .as_deref()
+            .context("ZK OS Merkle tree path is not configured")?;
+        tracing::info!("Verifying consistency of ZK OS Merkle tree at {db_path}");
+        let start = Instant::now();
+        let db = RocksDB::new(Path::new(db_path))
+            .context("failed initializing ZK OS Merkle tree RocksDB")?;
+        let mut tree = ZkOsTree::new(ZkOsRocksDBWrapper::from(db))
+            .context("cannot initialize ZK OS Merkle tree")?;
+
+        let version = if let Some(number) = self.l1_batch {
+            number.into()
+        } else {
+            let Some(latest_version) = tree.latest_version()? else {
+                tracing::info!("ZK OS Merkle tree is empty, skipping");
+                return Ok(());
+            };
+            latest_version
+        };
+
+        tracing::info!("L1 batch number to check: {version}");
+        tree.verify_consistency(version)
+            .context("ZK OS Merkle tree is inconsistent")?;
+        tracing::info!("ZK OS Merkle tree verified in {:?}", start.elapsed());
+
+        let bogus_stale_keys = tree.bogus_stale_keys(version)?;
+        if self.repair_stale_keys {
+            let stats = tree.repair_stale_keys()?;
+            tracing::info!("Repaired stale keys in ZK OS Merkle tree: {stats:?}");
+        } else {
+            anyhow::ensure!(
+                bogus_stale_keys.is_empty(),
+                "ZK OS Merkle tree contains {} bogus stale keys for version {version}; \
+                 run with `--repair-stale-keys` to remove them",
+                bogus_stale_keys.len()
+            );
+        }
+        Ok(())
+    }
 }
 
 fn main() -> anyhow::Result<()> {
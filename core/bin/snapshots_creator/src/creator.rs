@@ -1,12 +1,13 @@
 //! [`SnapshotCreator`] and tightly related types.
 
-use std::{sync::Arc, time::Duration};
+use std::{path::Path, sync::Arc, time::Duration};
 
 use anyhow::Context as _;
-use tokio::sync::Semaphore;
+use tokio::sync::{watch, Semaphore};
 use zksync_config::SnapshotsCreatorConfig;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalResult};
 use zksync_object_store::{ObjectStore, StoredObject};
+use zksync_state::{RocksdbSnapshotExport, RocksdbStorage};
 use zksync_types::{
     snapshots::{
         uniform_hashed_keys_chunk, SnapshotFactoryDependencies, SnapshotFactoryDependency,
@@ -137,14 +138,102 @@ impl SnapshotCreator {
                     .await?
             }
         };
+        self.save_storage_logs_chunk_filepath(progress, chunk_id, &output_filepath, latency)
+            .await
+    }
+
+    /// Synchronizes the RocksDB cache at `path` with Postgres up to the snapshot L1 batch and starts
+    /// exporting the snapshot from it.
+    async fn export_from_rocksdb_cache(
+        &self,
+        path: &Path,
+        progress: &SnapshotProgress,
+    ) -> anyhow::Result<RocksdbSnapshotExport> {
+        anyhow::ensure!(
+            progress.version == SnapshotVersion::Version1,
+            "Creating snapshots from RocksDB cache is only supported for version 1 snapshots, \
+             while the snapshot has {:?}",
+            progress.version
+        );
+
+        tracing::info!(
+            "Synchronizing RocksDB cache at {path:?} with Postgres up to L1 batch #{}",
+            progress.l1_batch_number
+        );
+        let mut conn = self.connect_to_replica().await?;
+        // The creator is never interrupted, so the stop signal is never sent.
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let storage = RocksdbStorage::builder(path)
+            .await?
+            .synchronize(&mut conn, &stop_receiver, Some(progress.l1_batch_number))
+            .await?
+            .context("RocksDB cache synchronization was unexpectedly interrupted")?;
+        drop(conn);
+
+        let export = storage.export_snapshot(progress.chunk_count).await?;
+        anyhow::ensure!(
+            export.l1_batch_number() == progress.l1_batch_number,
+            "RocksDB cache at {path:?} corresponds to L1 batch #{}, while the snapshot is created for L1 batch #{}",
+            export.l1_batch_number(),
+            progress.l1_batch_number
+        );
+        Ok(export)
+    }
+
+    async fn process_storage_logs_from_rocksdb(
+        &self,
+        mut export: RocksdbSnapshotExport,
+        progress: &SnapshotProgress,
+    ) -> anyhow::Result<()> {
+        // Initial writes for the exported keys are loaded from Postgres.
+        let mut conn = self.connect_to_replica().await?;
+        loop {
+            let latency = METRICS.storage_logs_processing_duration
+                [&StorageChunkStage::LoadFromRocksdb]
+                .start();
+            let Some((chunk_id, chunk)) = export.next_chunk(&mut conn).await? else {
+                return Ok(());
+            };
+            if !progress.remaining_chunk_ids.contains(&chunk_id) {
+                continue; // The chunk is already persisted
+            }
+            #[cfg(test)]
+            if self.event_listener.on_chunk_started().should_exit() {
+                return Ok(());
+            }
+
+            let latency = latency.observe();
+            tracing::info!(
+                "Loaded chunk {chunk_id} ({} logs) from RocksDB cache in {latency:?}",
+                chunk.storage_logs.len()
+            );
+            let (output_filepath, latency) = self
+                .store_storage_logs_chunk(progress.l1_batch_number, chunk_id, chunk.storage_logs)
+                .await?;
+            self.save_storage_logs_chunk_filepath(progress, chunk_id, &output_filepath, latency)
+                .await?;
+        }
+    }
 
+    async fn save_storage_logs_chunk_filepath(
+        &self,
+        progress: &SnapshotProgress,
+        chunk_id: u64,
+        output_filepath: &str,
+        latency: Duration,
+    ) -> anyhow::Result<()> {
+        let chunk_count = progress.chunk_count;
         let mut master_conn = self
             .master_pool
             .connection_tagged("snapshots_creator")
             .await?;
         master_conn
             .snapshots_dal()
-            .add_storage_logs_filepath_for_snapshot(l1_batch_number, chunk_id, &output_filepath)
+            .add_storage_logs_filepath_for_snapshot(
+                progress.l1_batch_number,
+                chunk_id,
+                output_filepath,
+            )
             .await?;
         #[cfg(test)]
         self.event_listener.on_chunk_saved();
@@ -204,9 +293,6 @@ impl SnapshotCreator {
         let latency = latency.observe();
         tracing::info!("Loaded {} factory deps in {latency:?}", factory_deps.len());
 
-        tracing::info!("Saving factory deps to GCS...");
-        let latency =
-            METRICS.factory_deps_processing_duration[&FactoryDepsStage::SaveToGcs].start();
         let factory_deps = factory_deps
             .into_iter()
             .map(|(_, bytecode)| SnapshotFactoryDependency {
@@ -214,6 +300,17 @@ impl SnapshotCreator {
             })
             .collect();
         let factory_deps = SnapshotFactoryDependencies { factory_deps };
+        self.store_factory_deps(l1_batch_number, factory_deps).await
+    }
+
+    async fn store_factory_deps(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        factory_deps: SnapshotFactoryDependencies,
+    ) -> anyhow::Result<String> {
+        tracing::info!("Saving factory deps to GCS...");
+        let latency =
+            METRICS.factory_deps_processing_duration[&FactoryDepsStage::SaveToGcs].start();
         let filename = self
             .blob_store
             .put(l1_batch_number, &factory_deps)
@@ -377,10 +474,26 @@ impl SnapshotCreator {
             progress.l1_batch_number
         );
 
+        let mut rocksdb_export = if let Some(path) = &config.rocksdb_cache_path {
+            Some(
+                self.export_from_rocksdb_cache(Path::new(path), &progress)
+                    .await?,
+            )
+        } else {
+            None
+        };
+
         if progress.is_new_snapshot {
-            let factory_deps_output_file = self
-                .process_factory_deps(last_l2_block_number_in_batch, progress.l1_batch_number)
-                .await?;
+            let factory_deps_output_file = if let Some(export) = &mut rocksdb_export {
+                let factory_deps = export
+                    .take_factory_deps()
+                    .context("factory deps were already taken from RocksDB export")?;
+                self.store_factory_deps(progress.l1_batch_number, factory_deps)
+                    .await?
+            } else {
+                self.process_factory_deps(last_l2_block_number_in_batch, progress.l1_batch_number)
+                    .await?
+            };
 
             let mut master_conn = self
                 .master_pool
@@ -400,20 +513,26 @@ impl SnapshotCreator {
         METRICS
             .storage_logs_chunks_left_to_process
             .set(progress.remaining_chunk_ids.len());
-        let semaphore = Semaphore::new(config.concurrent_queries_count as usize);
-        let tasks = progress
-            .remaining_chunk_ids
-            .iter()
-            .copied()
-            .map(|chunk_id| {
-                self.process_storage_logs_single_chunk(
-                    &semaphore,
-                    &progress,
-                    last_l2_block_number_in_batch,
-                    chunk_id,
-                )
-            });
-        futures::future::try_join_all(tasks).await?;
+        if let Some(export) = rocksdb_export {
+            // Chunks are exported from RocksDB sequentially, so there's no point in processing them concurrently.
+            self.process_storage_logs_from_rocksdb(export, &progress)
+                .await?;
+        } else {
+            let semaphore = Semaphore::new(config.concurrent_queries_count as usize);
+            let tasks = progress
+                .remaining_chunk_ids
+                .iter()
+                .copied()
+                .map(|chunk_id| {
+                    self.process_storage_logs_single_chunk(
+                        &semaphore,
+                        &progress,
+                        last_l2_block_number_in_batch,
+                        chunk_id,
+                    )
+                });
+            futures::future::try_join_all(tasks).await?;
+        }
 
         METRICS
             .snapshot_l1_batch
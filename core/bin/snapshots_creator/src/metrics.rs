@@ -15,6 +15,7 @@ pub(crate) enum FactoryDepsStage {
 #[metrics(label = "stage", rename_all = "snake_case")]
 pub(crate) enum StorageChunkStage {
     LoadFromPostgres,
+    LoadFromRocksdb,
     SaveToGcs,
 }
 
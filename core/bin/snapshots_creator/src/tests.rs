@@ -10,6 +10,7 @@ use std::{
 };
 
 use rand::{thread_rng, Rng};
+use tempfile::TempDir;
 use test_casing::test_casing;
 use zksync_config::SnapshotsCreatorConfig;
 use zksync_dal::{Connection, CoreDal};
@@ -31,6 +32,7 @@ const TEST_CONFIG: SnapshotsCreatorConfig = SnapshotsCreatorConfig {
     l1_batch_number: None,
     storage_logs_chunk_size: 1_000_000,
     concurrent_queries_count: 10,
+    rocksdb_cache_path: None,
     object_store: None,
 };
 const SEQUENTIAL_TEST_CONFIG: SnapshotsCreatorConfig = SnapshotsCreatorConfig {
@@ -616,6 +618,117 @@ async fn recovery_workflow_with_varying_chunk_size() {
     assert_storage_logs(&*object_store, snapshot_l1_batch_number, &expected_outputs).await;
 }
 
+async fn load_storage_logs_chunks(
+    object_store: &dyn ObjectStore,
+    snapshot_l1_batch_number: L1BatchNumber,
+) -> Vec<Vec<SnapshotStorageLog>> {
+    let mut chunks = vec![];
+    for chunk_id in 0..MIN_CHUNK_COUNT {
+        let key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: snapshot_l1_batch_number,
+            chunk_id,
+        };
+        let chunk: SnapshotStorageLogsChunk = object_store.get(key).await.unwrap();
+        let mut logs = chunk.storage_logs;
+        logs.sort_unstable_by_key(|log| log.key);
+        chunks.push(logs);
+    }
+    chunks
+}
+
+#[test_casing(2, [false, true])]
+#[tokio::test]
+async fn creating_snapshot_from_rocksdb_cache(interrupt: bool) {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let mut conn = pool.connection().await.unwrap();
+    let expected_outputs = prepare_postgres(&mut rng, &mut conn, 10).await;
+    let snapshot_l1_batch_number = L1BatchNumber(8);
+
+    // Create a reference snapshot from Postgres and remove its metadata, so that it's created anew.
+    let postgres_object_store = MockObjectStore::arc();
+    SnapshotCreator::for_tests(postgres_object_store.clone(), pool.clone())
+        .run(TEST_CONFIG, MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+    conn.snapshots_dal()
+        .delete_snapshots_after(snapshot_l1_batch_number - 1)
+        .await
+        .unwrap();
+
+    let temp_dir = TempDir::new().unwrap();
+    let config = SnapshotsCreatorConfig {
+        rocksdb_cache_path: Some(temp_dir.path().to_str().unwrap().to_owned()),
+        ..SEQUENTIAL_TEST_CONFIG
+    };
+    let object_store = MockObjectStore::arc();
+    if interrupt {
+        SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+            .stop_after_chunk_count(3)
+            .run(config.clone(), MIN_CHUNK_COUNT)
+            .await
+            .unwrap();
+        let snapshot_metadata = conn
+            .snapshots_dal()
+            .get_snapshot_metadata(snapshot_l1_batch_number)
+            .await
+            .unwrap()
+            .expect("No snapshot metadata");
+        assert_eq!(
+            snapshot_metadata
+                .storage_logs_filepaths
+                .iter()
+                .flatten()
+                .count(),
+            3
+        );
+    }
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(config, MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+
+    let snapshot_metadata = conn
+        .snapshots_dal()
+        .get_snapshot_metadata(snapshot_l1_batch_number)
+        .await
+        .unwrap()
+        .expect("No snapshot metadata");
+    assert!(snapshot_metadata.is_complete(), "{snapshot_metadata:#?}");
+
+    let SnapshotFactoryDependencies { factory_deps } =
+        object_store.get(snapshot_l1_batch_number).await.unwrap();
+    let actual_deps: HashSet<_> = factory_deps.into_iter().collect();
+    assert_eq!(actual_deps, expected_outputs.deps);
+
+    let chunks = load_storage_logs_chunks(&*object_store, snapshot_l1_batch_number).await;
+    let expected_chunks =
+        load_storage_logs_chunks(&*postgres_object_store, snapshot_l1_batch_number).await;
+    assert_eq!(chunks, expected_chunks);
+    assert_storage_logs(&*object_store, snapshot_l1_batch_number, &expected_outputs).await;
+}
+
+#[tokio::test]
+async fn creating_v0_snapshot_from_rocksdb_cache_is_not_supported() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let mut conn = pool.connection().await.unwrap();
+    prepare_postgres(&mut rng, &mut conn, 10).await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let config = SnapshotsCreatorConfig {
+        version: 0,
+        rocksdb_cache_path: Some(temp_dir.path().to_str().unwrap().to_owned()),
+        ..SEQUENTIAL_TEST_CONFIG
+    };
+    let err = SnapshotCreator::for_tests(MockObjectStore::arc(), pool.clone())
+        .panic_on_chunk_start()
+        .run(config, MIN_CHUNK_COUNT)
+        .await
+        .unwrap_err();
+    assert!(format!("{err:#}").contains("version 1"), "{err:#}");
+}
+
 #[tokio::test]
 async fn creator_fails_if_specified_l1_batch_is_missing() {
     let pool = ConnectionPool::<Core>::test_pool().await;
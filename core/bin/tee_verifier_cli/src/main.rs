@@ -0,0 +1,126 @@
+//! Re-verifies TEE verifier inputs outside of TEEs, e.g. to investigate disputed TEE proofs.
+
+use std::{path::PathBuf, process::ExitCode};
+
+use anyhow::Context as _;
+use clap::{Parser, ValueEnum};
+use serde::Serialize;
+use zksync_config::ObjectStoreConfig;
+use zksync_env_config::FromEnv;
+use zksync_object_store::{Bucket, ObjectStoreFactory, StoredObject};
+use zksync_prover_interface::inputs::TeeVerifierInput;
+use zksync_tee_verifier::{VerificationError, Verify};
+use zksync_types::{L1BatchNumber, H256};
+
+/// Object store bucket to fetch the input from.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum InputBucket {
+    TeeVerifierInputs,
+    ProofsTee,
+}
+
+impl From<InputBucket> for Bucket {
+    fn from(bucket: InputBucket) -> Self {
+        match bucket {
+            InputBucket::TeeVerifierInputs => Bucket::TeeVerifierInput,
+            InputBucket::ProofsTee => Bucket::ProofsTee,
+        }
+    }
+}
+
+#[derive(Debug, Parser)]
+#[command(
+    author = "Matter Labs",
+    version,
+    about = "Re-verifies TEE verifier inputs outside of TEEs",
+    long_about = None
+)]
+struct Cli {
+    /// L1 batch to fetch the input for. The object store is configured via `OBJECT_STORE_*` env variables.
+    #[arg(long = "l1-batch", required_unless_present = "input_file")]
+    l1_batch: Option<u32>,
+    /// Object store bucket to fetch the input from.
+    #[arg(long, value_enum, default_value_t = InputBucket::TeeVerifierInputs)]
+    bucket: InputBucket,
+    /// Path to a bincode-serialized input to verify instead of fetching it from the object store.
+    #[arg(long, conflicts_with = "l1_batch")]
+    input_file: Option<PathBuf>,
+    /// Root hash expected after the L1 batch, e.g. the one signed in the disputed TEE proof.
+    #[arg(long)]
+    expected_root_hash: Option<H256>,
+}
+
+/// Verification report printed as JSON.
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum Report {
+    Verified {
+        l1_batch_number: L1BatchNumber,
+        root_hash: H256,
+    },
+    Failed {
+        l1_batch_number: L1BatchNumber,
+        /// Structured difference between the input and the re-executed batch, if the failure is attributable to one.
+        diff: Option<VerificationError>,
+        error: String,
+    },
+}
+
+impl Cli {
+    async fn load_input(&self) -> anyhow::Result<TeeVerifierInput> {
+        let bytes = if let Some(path) = &self.input_file {
+            std::fs::read(path).with_context(|| format!("failed reading input from {path:?}"))?
+        } else {
+            let l1_batch_number =
+                L1BatchNumber(self.l1_batch.context("L1 batch is not specified")?);
+            let config = ObjectStoreConfig::from_env().context("ObjectStoreConfig::from_env()")?;
+            let blob_store = ObjectStoreFactory::new(config).create_store().await?;
+            let bucket = Bucket::from(self.bucket);
+            let key = TeeVerifierInput::encode_key(l1_batch_number);
+            blob_store
+                .get_raw(bucket, &key)
+                .await
+                .with_context(|| format!("failed fetching `{key}` from bucket `{bucket}`"))?
+        };
+        TeeVerifierInput::deserialize(bytes)
+            .map_err(|err| anyhow::anyhow!("failed deserializing input: {err}"))
+    }
+
+    fn verify(&self, input: TeeVerifierInput) -> anyhow::Result<Report> {
+        let TeeVerifierInput::V1(input) = input else {
+            anyhow::bail!("only V1 TEE verifier inputs are supported");
+        };
+        let l1_batch_number = input.l1_batch_env.number;
+
+        let result = input.verify().and_then(|result| {
+            if let Some(expected) = self.expected_root_hash {
+                result.check_root_hash(expected)?;
+            }
+            Ok(result)
+        });
+        Ok(match result {
+            Ok(result) => Report::Verified {
+                l1_batch_number,
+                root_hash: result.value_hash,
+            },
+            Err(err) => Report::Failed {
+                l1_batch_number,
+                diff: err.downcast_ref::<VerificationError>().cloned(),
+                error: format!("{err:#}"),
+            },
+        })
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<ExitCode> {
+    let cli = Cli::parse();
+    let input = cli.load_input().await?;
+    let report = cli.verify(input)?;
+
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(match report {
+        Report::Verified { .. } => ExitCode::SUCCESS,
+        Report::Failed { .. } => ExitCode::FAILURE,
+    })
+}
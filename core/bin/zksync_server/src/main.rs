@@ -16,7 +16,7 @@ use zksync_config::{
         ExternalPriceApiClientConfig, FriProofCompressorConfig, FriProverConfig,
         FriProverGatewayConfig, FriWitnessGeneratorConfig, FriWitnessVectorGeneratorConfig,
         L1Secrets, ObservabilityConfig, PrometheusConfig, ProofDataHandlerConfig,
-        ProtectiveReadsWriterConfig, Secrets,
+        ProtectiveReadsWriterConfig, Secrets, StateDiffExporterConfig,
     },
     ApiConfig, BaseTokenAdjusterConfig, ContractVerifierConfig, DAClientConfig, DADispatcherConfig,
     DBConfig, EthConfig, EthWatchConfig, ExternalProofIntegrationApiConfig, GasAdjusterConfig,
@@ -26,7 +26,12 @@ use zksync_core_leftovers::{
     temp_config_store::{read_yaml_repr, TempConfigStore},
     Component, Components,
 };
-use zksync_env_config::FromEnv;
+use zksync_env_config::{
+    da_client::{
+        da_client_config_from_env, da_client_secrets_from_env, SECONDARY_DA_CLIENT_ENV_PREFIX,
+    },
+    FromEnv,
+};
 
 use crate::node_builder::MainNodeBuilder;
 
@@ -127,6 +132,8 @@ fn main() -> anyhow::Result<()> {
             database: DatabaseSecrets::from_env().ok(),
             l1: L1Secrets::from_env().ok(),
             data_availability: DataAvailabilitySecrets::from_env().ok(),
+            secondary_data_availability: da_client_secrets_from_env(SECONDARY_DA_CLIENT_ENV_PREFIX)
+                .ok(),
             contract_verifier: ContractVerifierSecrets::from_env().ok(),
         },
     };
@@ -222,9 +229,11 @@ fn load_env_config() -> anyhow::Result<TempConfigStore> {
         observability: ObservabilityConfig::from_env().ok(),
         snapshot_creator: SnapshotsCreatorConfig::from_env().ok(),
         da_client_config: DAClientConfig::from_env().ok(),
+        secondary_da_client_config: da_client_config_from_env(SECONDARY_DA_CLIENT_ENV_PREFIX).ok(),
         da_dispatcher_config: DADispatcherConfig::from_env().ok(),
         protective_reads_writer_config: ProtectiveReadsWriterConfig::from_env().ok(),
         basic_witness_input_producer_config: BasicWitnessInputProducerConfig::from_env().ok(),
+        state_diff_exporter_config: StateDiffExporterConfig::from_env().ok(),
         core_object_store: ObjectStoreConfig::from_env().ok(),
         base_token_adjuster_config: BaseTokenAdjusterConfig::from_env().ok(),
         commitment_generator: None,
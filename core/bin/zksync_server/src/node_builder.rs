@@ -30,6 +30,7 @@ use zksync_node_framework::{
         da_clients::{
             avail::AvailWiringLayer, celestia::CelestiaWiringLayer, eigen::EigenWiringLayer,
             no_da::NoDAClientWiringLayer, object_store::ObjectStorageClientWiringLayer,
+            secondary::SecondaryDAClientWiringLayer,
         },
         da_dispatcher::DataAvailabilityDispatcherLayer,
         eth_sender::{EthTxAggregatorLayer, EthTxManagerLayer},
@@ -60,6 +61,7 @@ use zksync_node_framework::{
         vm_runner::{
             bwip::BasicWitnessInputProducerLayer, playground::VmPlaygroundLayer,
             protective_reads::ProtectiveReadsWriterLayer,
+            state_diff_exporter::StateDiffExporterLayer,
         },
         web3_api::{
             caches::MempoolCacheLayer,
@@ -216,7 +218,15 @@ impl MainNodeBuilder {
                 .add_layer(BaseTokenRatioProviderLayer::new(base_token_adjuster_config));
         }
         let state_keeper_config = try_load_config!(self.configs.state_keeper_config);
-        let l1_gas_layer = L1GasLayer::new(&state_keeper_config);
+        let mut l1_gas_layer = L1GasLayer::new(&state_keeper_config);
+        if self
+            .configs
+            .da_dispatcher_config
+            .as_ref()
+            .is_some_and(|config| config.pubdata_price_feedback_enabled())
+        {
+            l1_gas_layer = l1_gas_layer.with_da_pubdata_price_feedback();
+        }
         self.node.add_layer(l1_gas_layer);
         Ok(self)
     }
@@ -238,7 +248,8 @@ impl MainNodeBuilder {
     }
 
     fn add_metadata_calculator_layer(mut self, with_tree_api: bool) -> anyhow::Result<Self> {
-        let merkle_tree_env_config = try_load_config!(self.configs.db_config).merkle_tree;
+        let db_config = try_load_config!(self.configs.db_config);
+        let merkle_tree_env_config = db_config.merkle_tree;
         let operations_manager_env_config =
             try_load_config!(self.configs.operations_manager_config);
         let state_keeper_env_config = try_load_config!(self.configs.state_keeper_config);
@@ -252,6 +263,9 @@ impl MainNodeBuilder {
             let merkle_tree_api_config = try_load_config!(self.configs.api_config).merkle_tree;
             layer = layer.with_tree_api_config(merkle_tree_api_config);
         }
+        if let Some(path) = db_config.experimental.zk_os_merkle_tree_path {
+            layer = layer.with_zk_os_tree(path);
+        }
         self.node.add_layer(layer);
         Ok(self)
     }
@@ -529,7 +543,10 @@ impl MainNodeBuilder {
 
     fn add_contract_verification_api_layer(mut self) -> anyhow::Result<Self> {
         let config = try_load_config!(self.configs.contract_verifier);
-        self.node.add_layer(ContractVerificationApiLayer(config));
+        self.node.add_layer(ContractVerificationApiLayer::new(
+            config,
+            self.genesis_config.l2_chain_id,
+        ));
         Ok(self)
     }
 
@@ -599,6 +616,29 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    fn add_secondary_da_client_layer(mut self) -> anyhow::Result<Self> {
+        let Some(mut da_client_config) = self.configs.secondary_da_client_config.clone() else {
+            return Ok(self);
+        };
+        let eth_sender_config = try_load_config!(self.configs.eth);
+        if let Some(sender_config) = eth_sender_config.sender {
+            if sender_config.pubdata_sending_mode != PubdataSendingMode::Custom {
+                return Ok(self);
+            }
+        }
+
+        if let DAClientConfig::Eigen(config) = &mut da_client_config {
+            if config.eigenda_eth_rpc.is_none() {
+                let l1_secrets = try_load_config!(self.secrets.l1);
+                config.eigenda_eth_rpc = Some(l1_secrets.l1_rpc_url);
+            }
+        }
+        let secrets = self.secrets.secondary_data_availability.clone();
+        self.node
+            .add_layer(SecondaryDAClientWiringLayer::new(da_client_config, secrets));
+        Ok(self)
+    }
+
     fn add_da_dispatcher_layer(mut self) -> anyhow::Result<Self> {
         let eth_sender_config = try_load_config!(self.configs.eth);
         if let Some(sender_config) = eth_sender_config.sender {
@@ -610,6 +650,10 @@ impl MainNodeBuilder {
 
         let state_keeper_config = try_load_config!(self.configs.state_keeper_config);
         let da_config = try_load_config!(self.configs.da_dispatcher_config);
+        if da_config.fee_token_address.is_some() {
+            // The price API client is used to convert DA fees to the base token.
+            self = self.add_external_api_client_layer()?;
+        }
         self.node.add_layer(DataAvailabilityDispatcherLayer::new(
             state_keeper_config,
             da_config,
@@ -648,6 +692,16 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    fn add_vm_runner_state_diff_exporter_layer(mut self) -> anyhow::Result<Self> {
+        let state_diff_exporter_config = try_load_config!(self.configs.state_diff_exporter_config);
+        self.node.add_layer(StateDiffExporterLayer::new(
+            state_diff_exporter_config,
+            self.genesis_config.l2_chain_id,
+        ));
+
+        Ok(self)
+    }
+
     fn add_vm_playground_layer(mut self) -> anyhow::Result<Self> {
         let vm_config = self
             .configs
@@ -820,7 +874,10 @@ impl MainNodeBuilder {
                     self = self.add_commitment_generator_layer()?;
                 }
                 Component::DADispatcher => {
-                    self = self.add_da_client_layer()?.add_da_dispatcher_layer()?;
+                    self = self
+                        .add_da_client_layer()?
+                        .add_secondary_da_client_layer()?
+                        .add_da_dispatcher_layer()?;
                 }
                 Component::VmRunnerProtectiveReads => {
                     self = self.add_vm_runner_protective_reads_layer()?;
@@ -837,6 +894,9 @@ impl MainNodeBuilder {
                 Component::VmPlayground => {
                     self = self.add_vm_playground_layer()?;
                 }
+                Component::VmRunnerStateDiffExporter => {
+                    self = self.add_vm_runner_state_diff_exporter_layer()?;
+                }
                 Component::ExternalProofIntegrationApi => {
                     self = self.add_external_proof_integration_api_layer()?;
                 }
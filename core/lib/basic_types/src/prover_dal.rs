@@ -7,6 +7,7 @@ use strum::{Display, EnumString};
 
 use crate::{
     basic_fri_types::AggregationRound, protocol_version::ProtocolVersionId, L1BatchNumber,
+    L2ChainId,
 };
 
 #[derive(Debug, Clone, Copy)]
@@ -81,6 +82,23 @@ pub struct StuckJobs {
     pub error: Option<String>,
 }
 
+/// L1 batch of a chain served by a shared prover subsystem. Batches of all chains are proven under
+/// prover-local batch numbers, which are mapped to chain batches in `chain_batches_fri`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChainBatch {
+    pub chain_id: L2ChainId,
+    pub l1_batch_number: L1BatchNumber,
+}
+
+/// L1 batch which is not proven yet and is close to (or past) its SLA deadline.
+#[derive(Debug, Clone)]
+pub struct BatchSlaStatus {
+    pub l1_batch_number: L1BatchNumber,
+    pub deadline: NaiveDateTime,
+    /// Whether the deadline has already passed.
+    pub missed: bool,
+}
+
 // TODO (PLA-774): Redundant structure, should be replaced with `std::net::SocketAddr`.
 #[derive(Debug, Clone)]
 pub struct SocketAddress {
@@ -285,6 +303,8 @@ pub struct ProverJobFriInfo {
     pub proof_blob_url: Option<String>,
     pub protocol_version: Option<ProtocolVersionId>,
     pub picked_by: Option<String>,
+    /// Time the witness vector was generated and the job was handed over to the GPU prover.
+    pub witness_vector_generated_at: Option<NaiveDateTime>,
 }
 
 pub trait Stallable {
@@ -1,10 +1,12 @@
 //! Types related to data availability.
 
+use std::str::FromStr;
+
 use chrono::{DateTime, Utc};
 use num_enum::TryFromPrimitive;
 use serde::{Deserialize, Serialize};
 
-use crate::{commitment::PubdataType, Address, L1BatchNumber};
+use crate::{commitment::PubdataType, Address, L1BatchNumber, U256};
 
 /// Enum holding the current values used for DA Layers.
 #[repr(u8)]
@@ -21,11 +23,42 @@ pub enum PubdataSendingMode {
     RelayedL2Calldata,
 }
 
+/// DA layer holding a blob, in case the DA dispatcher is configured with a secondary layer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DataAvailabilityLayer {
+    #[default]
+    Primary,
+    Secondary,
+}
+
+impl DataAvailabilityLayer {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Primary => "primary",
+            Self::Secondary => "secondary",
+        }
+    }
+}
+
+impl FromStr for DataAvailabilityLayer {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "primary" => Ok(Self::Primary),
+            "secondary" => Ok(Self::Secondary),
+            _ => Err("Incorrect DA layer; expected one of `primary`, `secondary`"),
+        }
+    }
+}
+
 /// Represents a blob in the data availability layer.
 #[derive(Debug, Clone)]
 pub struct DataAvailabilityBlob {
     pub l1_batch_number: L1BatchNumber,
     pub blob_id: String,
+    /// DA layer holding the blob; inclusion data must be fetched from this layer.
+    pub layer: DataAvailabilityLayer,
     pub inclusion_data: Option<Vec<u8>>,
     pub sent_at: DateTime<Utc>,
 }
@@ -38,4 +71,12 @@ pub struct DataAvailabilityDetails {
     pub inclusion_data: Option<Vec<u8>>,
     pub sent_at: DateTime<Utc>,
     pub l2_da_validator: Option<Address>,
+    /// DA layer holding the blob identified by `blob_id`.
+    pub layer: DataAvailabilityLayer,
+    /// ID of the blob copy in the secondary DA layer, if the blob was posted to both layers.
+    pub secondary_blob_id: Option<String>,
+    /// Share of the blob fee attributed to the batch, in the smallest units of the DA layer's native token.
+    pub fee: Option<U256>,
+    /// `fee` converted to the base token.
+    pub fee_in_base_token: Option<U256>,
 }
@@ -1,4 +1,4 @@
-use std::fmt;
+use std::{fmt, str::FromStr};
 
 use serde::{Deserialize, Serialize};
 
@@ -6,17 +6,36 @@ use serde::{Deserialize, Serialize};
 #[serde(rename_all = "lowercase")]
 #[non_exhaustive]
 pub enum TeeType {
+    /// Intel Software Guard Extensions.
     Sgx,
+    /// Intel Trust Domain Extensions.
+    Tdx,
+}
+
+impl TeeType {
+    pub const ALL: [Self; 2] = [Self::Sgx, Self::Tdx];
 }
 
 impl fmt::Display for TeeType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             TeeType::Sgx => write!(f, "sgx"),
+            TeeType::Tdx => write!(f, "tdx"),
         }
     }
 }
 
+impl FromStr for TeeType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|tee_type| tee_type.to_string() == s)
+            .ok_or_else(|| anyhow::anyhow!("unknown TEE type: {s}"))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json;
@@ -28,8 +47,10 @@ mod tests {
         let json_str = "\"sgx\"";
         let tee_type: TeeType = serde_json::from_str(json_str).unwrap();
         assert_eq!(tee_type, TeeType::Sgx);
+        let tee_type: TeeType = serde_json::from_str("\"tdx\"").unwrap();
+        assert_eq!(tee_type, TeeType::Tdx);
 
-        for json_str in &["\"Sgx\"", "\"SGX\""] {
+        for json_str in &["\"Sgx\"", "\"SGX\"", "\"TDX\""] {
             let result: Result<TeeType, _> = serde_json::from_str(json_str);
             assert!(result.is_err());
         }
@@ -45,5 +66,14 @@ mod tests {
     #[test]
     fn test_display_teetype() {
         assert_eq!(TeeType::Sgx.to_string(), "sgx");
+        assert_eq!(TeeType::Tdx.to_string(), "tdx");
+    }
+
+    #[test]
+    fn test_parse_teetype() {
+        for tee_type in TeeType::ALL {
+            assert_eq!(tee_type.to_string().parse::<TeeType>().unwrap(), tee_type);
+        }
+        assert!("Sgx".parse::<TeeType>().is_err());
     }
 }
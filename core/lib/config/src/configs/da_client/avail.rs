@@ -33,6 +33,10 @@ pub struct AvailDefaultConfig {
 pub struct AvailGasRelayConfig {
     pub gas_relay_api_url: String,
     pub max_retries: usize,
+    /// Delay between submitting data to the gas relay and querying the submission status.
+    /// Defaults to 60 seconds.
+    #[serde(default)]
+    pub inclusion_delay_ms: Option<u64>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
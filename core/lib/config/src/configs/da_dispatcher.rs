@@ -1,6 +1,7 @@
 use std::time::Duration;
 
 use serde::Deserialize;
+use zksync_basic_types::Address;
 
 /// The default interval between the `da_dispatcher's` iterations.
 pub const DEFAULT_POLLING_INTERVAL_MS: u32 = 5000;
@@ -13,6 +14,28 @@ pub const DEFAULT_MAX_RETRIES: u16 = 5;
 pub const DEFAULT_USE_DUMMY_INCLUSION_DATA: bool = false;
 /// The default value for the inclusion_verification_transition_enabled flag.
 pub const DEFAULT_INCLUSION_VERIFICATION_TRANSITION_ENABLED: bool = false;
+/// The default number of consecutive failed dispatches to the primary DA layer before switching over.
+pub const DEFAULT_FALLBACK_AFTER_FAILURES: u32 = 1;
+/// The default interval after which the primary DA layer is tried again after switching over.
+pub const DEFAULT_PRIMARY_LAYER_RECHECK_INTERVAL_MS: u64 = 10 * 60 * 1_000;
+/// The default maximum number of L1 batches packed into a single blob.
+pub const DEFAULT_MAX_BATCHES_PER_BLOB: u32 = 1;
+/// The default value for the compress_blobs flag.
+pub const DEFAULT_COMPRESS_BLOBS: bool = false;
+/// The default number of decimals of the DA layer's native token.
+pub const DEFAULT_FEE_TOKEN_DECIMALS: u32 = 18;
+/// The default value for the pubdata_price_feedback_enabled flag.
+pub const DEFAULT_PUBDATA_PRICE_FEEDBACK_ENABLED: bool = false;
+
+/// Policy of using the secondary DA layer by the dispatcher.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum SecondaryDALayerPolicy {
+    /// Blobs are dispatched to the secondary layer only if the primary layer is unavailable.
+    #[default]
+    Fallback,
+    /// Blobs are dispatched to both layers. Inclusion data is always taken from the primary layer.
+    DualPosting,
+}
 
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct DADispatcherConfig {
@@ -28,6 +51,26 @@ pub struct DADispatcherConfig {
     /// It will make the dispatcher stop polling for inclusion data and ensure all the old batches
     /// have at least dummy inclusion data.
     pub inclusion_verification_transition_enabled: Option<bool>,
+    /// Policy of using the secondary DA layer. Ignored if the secondary DA client is not configured.
+    pub secondary_layer_policy: Option<SecondaryDALayerPolicy>,
+    /// The number of consecutive failed dispatches (each exhausting `max_retries`) to the primary DA layer
+    /// after which the dispatcher switches over to the secondary layer. Only used with the fallback policy.
+    pub fallback_after_failures: Option<u32>,
+    /// The interval after which the dispatcher tries the primary DA layer again after switching over
+    /// to the secondary layer.
+    pub primary_layer_recheck_interval_ms: Option<u64>,
+    /// The maximum number of consecutive L1 batches packed into a single blob. Only batches fetched in
+    /// the same iteration are packed together, so this value is effectively capped by `max_rows_to_dispatch`.
+    pub max_batches_per_blob: Option<u32>,
+    /// Compress the blobs before dispatching them.
+    pub compress_blobs: Option<bool>,
+    /// L1 address of the primary DA layer's native token. If set, the token price is fetched via the external
+    /// price API, and the DA fees are converted to the base token. Otherwise, the fees are only recorded in native units.
+    pub fee_token_address: Option<Address>,
+    /// The number of decimals of the primary DA layer's native token.
+    pub fee_token_decimals: Option<u32>,
+    /// Use the DA fees recorded by the dispatcher as the pubdata price in the fee model.
+    pub pubdata_price_feedback_enabled: Option<bool>,
 }
 
 impl DADispatcherConfig {
@@ -40,6 +83,14 @@ impl DADispatcherConfig {
             inclusion_verification_transition_enabled: Some(
                 DEFAULT_INCLUSION_VERIFICATION_TRANSITION_ENABLED,
             ),
+            secondary_layer_policy: None,
+            fallback_after_failures: Some(DEFAULT_FALLBACK_AFTER_FAILURES),
+            primary_layer_recheck_interval_ms: Some(DEFAULT_PRIMARY_LAYER_RECHECK_INTERVAL_MS),
+            max_batches_per_blob: Some(DEFAULT_MAX_BATCHES_PER_BLOB),
+            compress_blobs: Some(DEFAULT_COMPRESS_BLOBS),
+            fee_token_address: None,
+            fee_token_decimals: Some(DEFAULT_FEE_TOKEN_DECIMALS),
+            pubdata_price_feedback_enabled: Some(DEFAULT_PUBDATA_PRICE_FEEDBACK_ENABLED),
         }
     }
 
@@ -68,4 +119,45 @@ impl DADispatcherConfig {
         self.inclusion_verification_transition_enabled
             .unwrap_or(DEFAULT_INCLUSION_VERIFICATION_TRANSITION_ENABLED)
     }
+
+    pub fn secondary_layer_policy(&self) -> SecondaryDALayerPolicy {
+        self.secondary_layer_policy.unwrap_or_default()
+    }
+
+    pub fn fallback_after_failures(&self) -> u32 {
+        self.fallback_after_failures
+            .unwrap_or(DEFAULT_FALLBACK_AFTER_FAILURES)
+    }
+
+    pub fn primary_layer_recheck_interval(&self) -> Duration {
+        Duration::from_millis(
+            self.primary_layer_recheck_interval_ms
+                .unwrap_or(DEFAULT_PRIMARY_LAYER_RECHECK_INTERVAL_MS),
+        )
+    }
+
+    pub fn max_batches_per_blob(&self) -> u32 {
+        self.max_batches_per_blob
+            .unwrap_or(DEFAULT_MAX_BATCHES_PER_BLOB)
+            .max(1)
+    }
+
+    pub fn compress_blobs(&self) -> bool {
+        self.compress_blobs.unwrap_or(DEFAULT_COMPRESS_BLOBS)
+    }
+
+    pub fn fee_token_decimals(&self) -> u32 {
+        self.fee_token_decimals
+            .unwrap_or(DEFAULT_FEE_TOKEN_DECIMALS)
+    }
+
+    pub fn pubdata_price_feedback_enabled(&self) -> bool {
+        self.pubdata_price_feedback_enabled
+            .unwrap_or(DEFAULT_PUBDATA_PRICE_FEEDBACK_ENABLED)
+    }
+
+    /// Returns `true` if blobs are dispatched in the packed format rather than as the raw pubdata.
+    pub fn packing_enabled(&self) -> bool {
+        self.max_batches_per_blob() > 1 || self.compress_blobs()
+    }
 }
@@ -20,4 +20,7 @@ pub struct ENConfig {
     pub bridge_addresses_refresh_interval_sec: Option<NonZeroU64>,
 
     pub gateway_chain_id: Option<SLChainId>,
+
+    /// Whether to retrieve DA blobs and verify them against the locally produced pubdata.
+    pub da_blob_verification_enabled: Option<bool>,
 }
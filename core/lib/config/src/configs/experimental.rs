@@ -32,6 +32,10 @@ pub struct ExperimentalDBConfig {
     /// Enables the stale keys repair task for the Merkle tree.
     #[serde(default)]
     pub merkle_tree_repair_stale_keys: bool,
+    /// Path to the RocksDB directory for the experimental ZK OS Merkle tree. If set, the tree will be built
+    /// in parallel with the main Merkle tree. The tree doesn't influence L1 batch commitments; it cannot replace
+    /// the main tree.
+    pub zk_os_merkle_tree_path: Option<String>,
 }
 
 impl Default for ExperimentalDBConfig {
@@ -44,6 +48,7 @@ impl Default for ExperimentalDBConfig {
             processing_delay_ms: Self::default_merkle_tree_processing_delay_ms(),
             include_indices_and_filters_in_block_cache: false,
             merkle_tree_repair_stale_keys: false,
+            zk_os_merkle_tree_path: None,
         }
     }
 }
@@ -1,6 +1,27 @@
+use std::time::Duration;
+
 use serde::Deserialize;
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct ExternalProofIntegrationApiConfig {
     pub http_port: u16,
+    /// Path to the directory with SNARK wrapper verification keys (`verification_snark_key.json`
+    /// and, optionally, `fflonk_verification_snark_key.json`) used to verify proofs submitted
+    /// to the proof marketplace. If not set, the marketplace endpoints are disabled.
+    #[serde(default)]
+    pub verification_keys_path: Option<String>,
+    /// Time in seconds an external prover holds a claimed batch. Unless the lease is renewed,
+    /// the batch is reassigned to another prover after it expires.
+    #[serde(default = "ExternalProofIntegrationApiConfig::default_lease_timeout_in_secs")]
+    pub lease_timeout_in_secs: u32,
+}
+
+impl ExternalProofIntegrationApiConfig {
+    pub fn default_lease_timeout_in_secs() -> u32 {
+        3600
+    }
+
+    pub fn lease_timeout(&self) -> Duration {
+        Duration::from_secs(self.lease_timeout_in_secs.into())
+    }
 }
@@ -1,11 +1,16 @@
 use std::time::Duration;
 
 use serde::Deserialize;
+use zksync_basic_types::L2ChainId;
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct FriProverGatewayConfig {
     pub api_url: String,
     pub api_poll_duration_secs: u16,
+    /// Chains served by the prover subsystem. If empty, batches are fetched from and proofs are
+    /// submitted to `api_url` only.
+    #[serde(default)]
+    pub chains: Vec<ProverGatewayChainConfig>,
 
     /// Configurations for prometheus
     pub prometheus_listener_port: u16,
@@ -18,3 +23,20 @@ impl FriProverGatewayConfig {
         Duration::from_secs(self.api_poll_duration_secs as u64)
     }
 }
+
+/// Proof data handler of a single chain.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ProverGatewayChainConfig {
+    pub chain_id: L2ChainId,
+    pub api_url: String,
+    /// Share of proof generation data fetches given to the chain relative to other chains
+    /// with pending batches.
+    #[serde(default = "ProverGatewayChainConfig::default_weight")]
+    pub weight: u32,
+}
+
+impl ProverGatewayChainConfig {
+    pub const fn default_weight() -> u32 {
+        1
+    }
+}
@@ -15,7 +15,7 @@ pub use self::{
     external_proof_integration_api::ExternalProofIntegrationApiConfig,
     fri_proof_compressor::FriProofCompressorConfig,
     fri_prover::FriProverConfig,
-    fri_prover_gateway::FriProverGatewayConfig,
+    fri_prover_gateway::{FriProverGatewayConfig, ProverGatewayChainConfig},
     fri_witness_generator::FriWitnessGeneratorConfig,
     fri_witness_vector_generator::FriWitnessVectorGeneratorConfig,
     gateway::{GatewayChainConfig, GatewayConfig},
@@ -23,7 +23,7 @@ pub use self::{
     genesis::GenesisConfig,
     object_store::ObjectStoreConfig,
     observability::{ObservabilityConfig, OpentelemetryConfig},
-    proof_data_handler::{ProofDataHandlerConfig, TeeConfig},
+    proof_data_handler::{ProofDataHandlerConfig, TdxMeasurementPolicy, TeeConfig},
     prover_job_monitor::ProverJobMonitorConfig,
     pruning::PruningConfig,
     secrets::{
@@ -32,7 +32,9 @@ pub use self::{
     snapshot_recovery::SnapshotRecoveryConfig,
     snapshots_creator::SnapshotsCreatorConfig,
     utils::PrometheusConfig,
-    vm_runner::{BasicWitnessInputProducerConfig, ProtectiveReadsWriterConfig},
+    vm_runner::{
+        BasicWitnessInputProducerConfig, ProtectiveReadsWriterConfig, StateDiffExporterConfig,
+    },
 };
 
 pub mod api;
@@ -1,7 +1,17 @@
 use std::time::Duration;
 
 use serde::Deserialize;
-use zksync_basic_types::L1BatchNumber;
+use zksync_basic_types::{tee_types::TeeType, L1BatchNumber, H256};
+
+/// Expected measurement registers of a TDX trust domain. All values are 48 bytes long.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct TdxMeasurementPolicy {
+    /// Measurement of the initial contents of the trust domain.
+    pub mr_td: Vec<u8>,
+    /// Expected values of runtime measurement registers RTMR0..RTMR3; `None` values are not checked.
+    #[serde(default)]
+    pub rtmrs: [Option<Vec<u8>>; 4],
+}
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct TeeConfig {
@@ -9,12 +19,40 @@ pub struct TeeConfig {
     pub tee_support: bool,
     /// All batches before this one are considered to be processed.
     pub first_tee_processed_batch: L1BatchNumber,
+    /// TEE types proofs are generated for. A batch is considered TEE-verified once it is proven
+    /// by each of these TEE types.
+    #[serde(default = "TeeConfig::default_tee_types")]
+    pub tee_types: Vec<TeeType>,
     /// Timeout in seconds for retrying the preparation of input for TEE proof generation if it
     /// previously failed (e.g., due to a transient network issue) or if it was picked by a TEE
     /// prover but the TEE proof was not submitted within that time.
     pub tee_proof_generation_timeout_in_secs: u16,
     /// Timeout in hours after which a batch will be permanently ignored if repeated retries failed.
     pub tee_batch_permanently_ignored_timeout_in_hours: u16,
+    /// If true, DCAP quotes submitted by TEE provers are verified, and proofs signed by keys
+    /// without a successfully verified attestation are rejected.
+    #[serde(default)]
+    pub tee_attestation_verification: bool,
+    /// MRENCLAVE values of enclaves allowed to register attestations. A quote is accepted if either its
+    /// MRENCLAVE or its MRSIGNER is allowed.
+    #[serde(default)]
+    pub tee_allowed_mrenclaves: Vec<H256>,
+    /// MRSIGNER values of enclave signers allowed to register attestations.
+    #[serde(default)]
+    pub tee_allowed_mrsigners: Vec<H256>,
+    /// Measurement policies for TDX quotes. A quote is accepted if it satisfies any of the policies.
+    #[serde(default)]
+    pub tee_tdx_policies: Vec<TdxMeasurementPolicy>,
+    /// Path to the PEM-encoded root CA certificate the PCK certificate chains in quotes must chain to
+    /// (normally, Intel SGX Root CA). Required if attestation verification is enabled.
+    #[serde(default)]
+    pub tee_attestation_root_ca_path: Option<String>,
+    /// URL of the Intel PCS or a PCCS instance used to fetch the quote verification collateral (PCK CRLs).
+    #[serde(default = "TeeConfig::default_tee_pccs_url")]
+    pub tee_pccs_url: String,
+    /// Time in seconds the fetched collateral is cached for.
+    #[serde(default = "TeeConfig::default_tee_collateral_cache_ttl_in_secs")]
+    pub tee_collateral_cache_ttl_in_secs: u32,
 }
 
 impl Default for TeeConfig {
@@ -22,10 +60,18 @@ impl Default for TeeConfig {
         TeeConfig {
             tee_support: Self::default_tee_support(),
             first_tee_processed_batch: Self::default_first_tee_processed_batch(),
+            tee_types: Self::default_tee_types(),
             tee_proof_generation_timeout_in_secs:
                 Self::default_tee_proof_generation_timeout_in_secs(),
             tee_batch_permanently_ignored_timeout_in_hours:
                 Self::default_tee_batch_permanently_ignored_timeout_in_hours(),
+            tee_attestation_verification: false,
+            tee_allowed_mrenclaves: vec![],
+            tee_allowed_mrsigners: vec![],
+            tee_tdx_policies: vec![],
+            tee_attestation_root_ca_path: None,
+            tee_pccs_url: Self::default_tee_pccs_url(),
+            tee_collateral_cache_ttl_in_secs: Self::default_tee_collateral_cache_ttl_in_secs(),
         }
     }
 }
@@ -39,6 +85,10 @@ impl TeeConfig {
         L1BatchNumber(0)
     }
 
+    pub fn default_tee_types() -> Vec<TeeType> {
+        vec![TeeType::Sgx]
+    }
+
     pub fn default_tee_proof_generation_timeout_in_secs() -> u16 {
         60
     }
@@ -47,6 +97,14 @@ impl TeeConfig {
         10 * 24
     }
 
+    pub fn default_tee_pccs_url() -> String {
+        "https://api.trustedservices.intel.com".to_owned()
+    }
+
+    pub fn default_tee_collateral_cache_ttl_in_secs() -> u32 {
+        3600
+    }
+
     pub fn tee_proof_generation_timeout(&self) -> Duration {
         Duration::from_secs(self.tee_proof_generation_timeout_in_secs.into())
     }
@@ -54,6 +112,10 @@ impl TeeConfig {
     pub fn tee_batch_permanently_ignored_timeout(&self) -> Duration {
         Duration::from_secs(3600 * u64::from(self.tee_batch_permanently_ignored_timeout_in_hours))
     }
+
+    pub fn tee_collateral_cache_ttl(&self) -> Duration {
+        Duration::from_secs(self.tee_collateral_cache_ttl_in_secs.into())
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
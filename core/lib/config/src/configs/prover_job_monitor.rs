@@ -63,6 +63,18 @@ pub struct ProverJobMonitorConfig {
     pub witness_job_queuer_run_interval_ms: u64,
     /// HTTP port of the ProverJobMonitor to send requests to.
     pub http_port: u16,
+    /// The time a batch is expected to be proven in after its witness inputs are saved, i.e. the SLA deadline.
+    #[serde(default = "ProverJobMonitorConfig::default_batch_sla_ms")]
+    pub batch_sla_ms: u64,
+    /// The interval between runs for Batch SLA Monitor.
+    #[serde(default = "ProverJobMonitorConfig::default_sla_monitor_run_interval_ms")]
+    pub sla_monitor_run_interval_ms: u64,
+    /// Unproven batches with the deadline closer than this are considered at risk of missing the SLA.
+    #[serde(default = "ProverJobMonitorConfig::default_sla_at_risk_threshold_ms")]
+    pub sla_at_risk_threshold_ms: u64,
+    /// Minimum priority to boost jobs of SLA-at-risk batches to. 0 disables boosting.
+    #[serde(default)]
+    pub sla_at_risk_min_priority: u32,
 }
 
 impl ProverJobMonitorConfig {
@@ -185,6 +197,36 @@ impl ProverJobMonitorConfig {
         10_000
     }
 
+    /// The time a batch is expected to be proven in.
+    pub fn batch_sla(&self) -> Duration {
+        Duration::from_millis(self.batch_sla_ms)
+    }
+
+    /// Default batch_sla_ms -- 3 hours
+    pub fn default_batch_sla_ms() -> u64 {
+        10_800_000
+    }
+
+    /// The interval between runs for Batch SLA Monitor.
+    pub fn sla_monitor_run_interval(&self) -> Duration {
+        Duration::from_millis(self.sla_monitor_run_interval_ms)
+    }
+
+    /// Default sla_monitor_run_interval_ms -- 10 seconds
+    pub fn default_sla_monitor_run_interval_ms() -> u64 {
+        10_000
+    }
+
+    /// Unproven batches with the deadline closer than this are considered at risk.
+    pub fn sla_at_risk_threshold(&self) -> Duration {
+        Duration::from_millis(self.sla_at_risk_threshold_ms)
+    }
+
+    /// Default sla_at_risk_threshold_ms -- 30 minutes
+    pub fn default_sla_at_risk_threshold_ms() -> u64 {
+        1_800_000
+    }
+
     /// Default attempts reporter run interval -- 10 seconds
     pub fn default_attempts_reporter_run_interval_ms() -> u64 {
         10_000
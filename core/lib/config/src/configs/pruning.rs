@@ -1,6 +1,7 @@
 use std::num::NonZeroU64;
 
 use serde::Deserialize;
+use zksync_basic_types::Address;
 
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct PruningConfig {
@@ -16,4 +17,14 @@ pub struct PruningConfig {
     /// the retention period greater than that implicitly imposed by other criteria (e.g., 7 or 30 days).
     /// If set to 0, L1 batches will not be retained based on their timestamp. The default value is 1 hour.
     pub data_retention_sec: Option<u64>,
+    /// Events emitted by these contracts are retained after the containing L1 batches are pruned.
+    #[serde(default)]
+    pub retained_event_addresses: Vec<Address>,
+    /// Transactions initiated by or sent to these accounts are retained after the containing L1 batches are pruned,
+    /// together with their receipts.
+    #[serde(default)]
+    pub retained_transaction_accounts: Vec<Address>,
+    /// Whether call traces of retained transactions are retained as well. Defaults to `false`.
+    #[serde(default)]
+    pub retain_call_traces: bool,
 }
@@ -39,6 +39,7 @@ pub struct Secrets {
     pub database: Option<DatabaseSecrets>,
     pub l1: Option<L1Secrets>,
     pub data_availability: Option<DataAvailabilitySecrets>,
+    pub secondary_data_availability: Option<DataAvailabilitySecrets>,
     pub contract_verifier: Option<ContractVerifierSecrets>,
 }
 
@@ -20,6 +20,11 @@ pub struct SnapshotsCreatorConfig {
     pub storage_logs_chunk_size: u64,
     #[serde(default = "SnapshotsCreatorConfig::concurrent_queries_count")]
     pub concurrent_queries_count: u32,
+    /// Path to a RocksDB state cache to create snapshots from. If set, storage logs and factory deps
+    /// are exported from this cache instead of being loaded from Postgres; the cache is synchronized with Postgres
+    /// up to the snapshot L1 batch beforehand. The cache must not be used by other components (e.g., the state keeper)
+    /// and must not be ahead of the snapshot L1 batch. Only supported for version 1 snapshots.
+    pub rocksdb_cache_path: Option<String>,
     pub object_store: Option<ObjectStoreConfig>,
 }
 
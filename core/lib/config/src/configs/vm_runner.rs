@@ -34,3 +34,32 @@ impl BasicWitnessInputProducerConfig {
         "./db/basic_witness_input_producer".to_owned()
     }
 }
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct StateDiffExporterConfig {
+    /// Path to the RocksDB data directory that serves state cache.
+    #[serde(default = "StateDiffExporterConfig::default_db_path")]
+    pub db_path: String,
+    /// How many max batches should be processed at the same time. Batches are guaranteed to be exported
+    /// in order only if this is set to 1.
+    pub window_size: u32,
+    /// All batches before this one (inclusive) are always considered to be processed.
+    pub first_processed_batch: L1BatchNumber,
+    /// Directory to write JSON Lines files with diffs to (one file per L1 batch). If not set, the file sink is disabled.
+    pub file_sink_path: Option<String>,
+    /// URL of the Kafka REST proxy to produce diffs to. If not set, the Kafka sink is disabled.
+    pub kafka_rest_url: Option<String>,
+    /// Kafka topic to produce diffs to.
+    #[serde(default = "StateDiffExporterConfig::default_kafka_topic")]
+    pub kafka_topic: String,
+}
+
+impl StateDiffExporterConfig {
+    fn default_db_path() -> String {
+        "./db/state_diff_exporter".to_owned()
+    }
+
+    fn default_kafka_topic() -> String {
+        "state_diffs".to_owned()
+    }
+}
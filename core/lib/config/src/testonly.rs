@@ -8,6 +8,7 @@ use zksync_basic_types::{
     protocol_version::{ProtocolSemanticVersion, ProtocolVersionId, VersionPatch},
     pubdata_da::PubdataSendingMode,
     secrets::{APIKey, SeedPhrase},
+    tee_types::TeeType,
     vm::FastVmMode,
     L1BatchNumber, L1ChainId, L2ChainId, SLChainId,
 };
@@ -309,6 +310,7 @@ impl Distribution<configs::ExperimentalDBConfig> for EncodeDist {
             processing_delay_ms: self.sample(rng),
             include_indices_and_filters_in_block_cache: self.sample(rng),
             merkle_tree_repair_stale_keys: self.sample(rng),
+            zk_os_merkle_tree_path: self.sample(rng),
         }
     }
 }
@@ -518,6 +520,7 @@ impl Distribution<configs::FriProverGatewayConfig> for EncodeDist {
         configs::FriProverGatewayConfig {
             api_url: self.sample(rng),
             api_poll_duration_secs: self.sample(rng),
+            chains: self.sample_range(rng).map(|_| self.sample(rng)).collect(),
             prometheus_listener_port: self.sample(rng),
             prometheus_pushgateway_url: self.sample(rng),
             prometheus_push_interval_ms: self.sample(rng),
@@ -525,6 +528,16 @@ impl Distribution<configs::FriProverGatewayConfig> for EncodeDist {
     }
 }
 
+impl Distribution<configs::ProverGatewayChainConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::ProverGatewayChainConfig {
+        configs::ProverGatewayChainConfig {
+            chain_id: L2ChainId::from(rng.gen::<u32>()),
+            api_url: self.sample(rng),
+            weight: self.sample(rng),
+        }
+    }
+}
+
 impl Sample for CircuitIdRoundTuple {
     fn sample(rng: &mut (impl Rng + ?Sized)) -> CircuitIdRoundTuple {
         CircuitIdRoundTuple {
@@ -683,13 +696,31 @@ impl Distribution<configs::ProofDataHandlerConfig> for EncodeDist {
             tee_config: configs::TeeConfig {
                 tee_support: self.sample(rng),
                 first_tee_processed_batch: L1BatchNumber(rng.gen()),
+                tee_types: TeeType::ALL[..rng.gen_range(1..=TeeType::ALL.len())].to_vec(),
                 tee_proof_generation_timeout_in_secs: self.sample(rng),
                 tee_batch_permanently_ignored_timeout_in_hours: self.sample(rng),
+                tee_attestation_verification: self.sample(rng),
+                tee_allowed_mrenclaves: self.sample_range(rng).map(|_| rng.gen()).collect(),
+                tee_allowed_mrsigners: self.sample_range(rng).map(|_| rng.gen()).collect(),
+                tee_tdx_policies: self.sample_collect(rng),
+                tee_attestation_root_ca_path: self.sample_opt(|| self.sample(rng)),
+                tee_pccs_url: self.sample(rng),
+                tee_collateral_cache_ttl_in_secs: self.sample(rng),
             },
         }
     }
 }
 
+impl Distribution<configs::TdxMeasurementPolicy> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::TdxMeasurementPolicy {
+        let mut sample_register = || (0..48).map(|_| rng.gen()).collect::<Vec<u8>>();
+        configs::TdxMeasurementPolicy {
+            mr_td: sample_register(),
+            rtmrs: [(); 4].map(|()| self.sample_opt(&mut sample_register)),
+        }
+    }
+}
+
 impl Distribution<configs::SnapshotsCreatorConfig> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::SnapshotsCreatorConfig {
         configs::SnapshotsCreatorConfig {
@@ -697,6 +728,7 @@ impl Distribution<configs::SnapshotsCreatorConfig> for EncodeDist {
             version: if rng.gen() { 0 } else { 1 },
             storage_logs_chunk_size: self.sample(rng),
             concurrent_queries_count: self.sample(rng),
+            rocksdb_cache_path: self.sample_opt(|| self.sample(rng)),
             object_store: self.sample(rng),
         }
     }
@@ -883,6 +915,7 @@ impl Distribution<configs::secrets::Secrets> for EncodeDist {
             database: self.sample_opt(|| self.sample(rng)),
             l1: self.sample_opt(|| self.sample(rng)),
             data_availability: self.sample_opt(|| self.sample(rng)),
+            secondary_data_availability: self.sample_opt(|| self.sample(rng)),
             contract_verifier: self.sample_opt(|| self.sample(rng)),
         }
     }
@@ -948,6 +981,7 @@ impl Distribution<configs::en_config::ENConfig> for EncodeDist {
             main_node_rate_limit_rps: self.sample_opt(|| rng.gen()),
             bridge_addresses_refresh_interval_sec: self.sample_opt(|| rng.gen()),
             gateway_chain_id: self.sample_opt(|| SLChainId(rng.gen())),
+            da_blob_verification_enabled: self.sample(rng),
         }
     }
 }
@@ -975,6 +1009,19 @@ impl Distribution<configs::secrets::DataAvailabilitySecrets> for EncodeDist {
     }
 }
 
+impl Distribution<configs::da_dispatcher::SecondaryDALayerPolicy> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> configs::da_dispatcher::SecondaryDALayerPolicy {
+        type T = configs::da_dispatcher::SecondaryDALayerPolicy;
+        match rng.gen_range(0..2) {
+            0 => T::Fallback,
+            _ => T::DualPosting,
+        }
+    }
+}
+
 impl Distribution<configs::da_dispatcher::DADispatcherConfig> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::da_dispatcher::DADispatcherConfig {
         configs::da_dispatcher::DADispatcherConfig {
@@ -983,6 +1030,14 @@ impl Distribution<configs::da_dispatcher::DADispatcherConfig> for EncodeDist {
             max_retries: self.sample(rng),
             use_dummy_inclusion_data: self.sample(rng),
             inclusion_verification_transition_enabled: self.sample(rng),
+            secondary_layer_policy: self.sample_opt(|| self.sample(rng)),
+            fallback_after_failures: self.sample(rng),
+            primary_layer_recheck_interval_ms: self.sample(rng),
+            max_batches_per_blob: self.sample(rng),
+            compress_blobs: self.sample(rng),
+            fee_token_address: self.sample_opt(|| rng.gen()),
+            fee_token_decimals: self.sample(rng),
+            pubdata_price_feedback_enabled: self.sample(rng),
         }
     }
 }
@@ -1013,6 +1068,19 @@ impl Distribution<configs::vm_runner::BasicWitnessInputProducerConfig> for Encod
     }
 }
 
+impl Distribution<configs::vm_runner::StateDiffExporterConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::vm_runner::StateDiffExporterConfig {
+        configs::vm_runner::StateDiffExporterConfig {
+            db_path: self.sample(rng),
+            window_size: self.sample(rng),
+            first_processed_batch: L1BatchNumber(rng.gen()),
+            file_sink_path: self.sample(rng),
+            kafka_rest_url: self.sample(rng),
+            kafka_topic: self.sample(rng),
+        }
+    }
+}
+
 impl Distribution<configs::CommitmentGeneratorConfig> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::CommitmentGeneratorConfig {
         configs::CommitmentGeneratorConfig {
@@ -1069,6 +1137,9 @@ impl Distribution<configs::pruning::PruningConfig> for EncodeDist {
             chunk_size: self.sample(rng),
             removal_delay_sec: self.sample_opt(|| rng.gen()),
             data_retention_sec: self.sample(rng),
+            retained_event_addresses: self.sample_range(rng).map(|_| rng.gen()).collect(),
+            retained_transaction_accounts: self.sample_range(rng).map(|_| rng.gen()).collect(),
+            retain_call_traces: self.sample(rng),
         }
     }
 }
@@ -1105,6 +1176,8 @@ impl Distribution<configs::external_proof_integration_api::ExternalProofIntegrat
     ) -> configs::external_proof_integration_api::ExternalProofIntegrationApiConfig {
         configs::external_proof_integration_api::ExternalProofIntegrationApiConfig {
             http_port: self.sample(rng),
+            verification_keys_path: self.sample_opt(|| self.sample(rng)),
+            lease_timeout_in_secs: self.sample(rng),
         }
     }
 }
@@ -1150,6 +1223,10 @@ impl Distribution<configs::prover_job_monitor::ProverJobMonitorConfig> for Encod
             witness_generator_queue_reporter_run_interval_ms: self.sample(rng),
             witness_job_queuer_run_interval_ms: self.sample(rng),
             http_port: self.sample(rng),
+            batch_sla_ms: self.sample(rng),
+            sla_monitor_run_interval_ms: self.sample(rng),
+            sla_at_risk_threshold_ms: self.sample(rng),
+            sla_at_risk_min_priority: self.sample(rng),
         }
     }
 }
@@ -1178,9 +1255,11 @@ impl Distribution<configs::GeneralConfig> for EncodeDist {
             snapshot_creator: self.sample(rng),
             observability: self.sample(rng),
             da_client_config: self.sample(rng),
+            secondary_da_client_config: self.sample(rng),
             da_dispatcher_config: self.sample(rng),
             protective_reads_writer_config: self.sample(rng),
             basic_witness_input_producer_config: self.sample(rng),
+            state_diff_exporter_config: self.sample(rng),
             commitment_generator: self.sample(rng),
             snapshot_recovery: self.sample(rng),
             pruning: self.sample(rng),
@@ -0,0 +1,115 @@
+//! Propagation of verification info to contracts deployed with the same bytecode as a fully verified contract.
+
+use std::time::Duration;
+
+use anyhow::Context as _;
+use tokio::sync::watch;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_types::L2BlockNumber;
+
+use crate::metrics::API_CONTRACT_VERIFIER_METRICS;
+
+/// Background task making contracts deployed in new L2 blocks inherit verification info from fully verified
+/// contracts with the same bytecode hash. Contracts deployed before their bytecode was verified inherit
+/// the info when the verification is saved by [`ContractVerifier`](crate::ContractVerifier).
+#[derive(Debug)]
+pub struct VerificationInheritanceTask {
+    connection_pool: ConnectionPool<Core>,
+    poll_interval: Duration,
+    max_l2_blocks_per_iteration: u32,
+    stop_receiver: watch::Receiver<bool>,
+}
+
+impl VerificationInheritanceTask {
+    const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+    const DEFAULT_MAX_L2_BLOCKS_PER_ITERATION: u32 = 1_000;
+
+    pub fn new(
+        connection_pool: ConnectionPool<Core>,
+        stop_receiver: watch::Receiver<bool>,
+    ) -> Self {
+        Self {
+            connection_pool,
+            poll_interval: Self::DEFAULT_POLL_INTERVAL,
+            max_l2_blocks_per_iteration: Self::DEFAULT_MAX_L2_BLOCKS_PER_ITERATION,
+            stop_receiver,
+        }
+    }
+
+    /// Processes a chunk of L2 blocks after the last processed one. Returns `false` if there are no L2 blocks to process.
+    async fn process_new_l2_blocks(&self) -> anyhow::Result<bool> {
+        let mut storage = self
+            .connection_pool
+            .connection_tagged("contract_verifier")
+            .await?;
+        let last_processed = storage
+            .contract_verification_dal()
+            .get_last_inheritance_l2_block()
+            .await?;
+        let Some(last_sealed) = storage.blocks_dal().get_sealed_l2_block_number().await? else {
+            return Ok(false);
+        };
+
+        let next_l2_block = last_processed.map_or(L2BlockNumber(0), |number| number + 1);
+        if next_l2_block > last_sealed {
+            return Ok(false);
+        }
+        let last_l2_block = last_sealed.min(next_l2_block + (self.max_l2_blocks_per_iteration - 1));
+
+        let mut transaction = storage.start_transaction().await?;
+        let inherited_count = transaction
+            .contract_verification_dal()
+            .inherit_verification_info_for_l2_blocks(next_l2_block..=last_l2_block)
+            .await?;
+        transaction
+            .contract_verification_dal()
+            .set_last_inheritance_l2_block(last_l2_block)
+            .await?;
+        transaction.commit().await?;
+
+        if inherited_count > 0 {
+            tracing::info!(
+                "{inherited_count} contracts deployed in L2 blocks #{next_l2_block}..=#{last_l2_block} have inherited \
+                 verification info"
+            );
+        }
+        API_CONTRACT_VERIFIER_METRICS
+            .inherited_verifications
+            .inc_by(inherited_count as u64);
+        API_CONTRACT_VERIFIER_METRICS
+            .last_inheritance_l2_block
+            .set(last_l2_block.0.into());
+        Ok(true)
+    }
+
+    pub async fn run(mut self) -> anyhow::Result<()> {
+        let mut storage = self
+            .connection_pool
+            .connection_tagged("contract_verifier")
+            .await?;
+        let filled_count = storage
+            .contract_verification_dal()
+            .fill_missing_verified_bytecode_hashes()
+            .await
+            .context("failed filling bytecode hashes for verified contracts")?;
+        drop(storage);
+        if filled_count > 0 {
+            tracing::info!("Filled bytecode hashes for {filled_count} verified contracts");
+        }
+
+        while !*self.stop_receiver.borrow_and_update() {
+            if self.process_new_l2_blocks().await? {
+                continue; // There may be more L2 blocks to process
+            }
+
+            if tokio::time::timeout(self.poll_interval, self.stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received, verification inheritance task is shutting down");
+        Ok(())
+    }
+}
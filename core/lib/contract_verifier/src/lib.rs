@@ -18,7 +18,7 @@ use zksync_types::{
     contract_verification::{
         api::{
             self as api, CompilationArtifacts, VerificationIncomingRequest, VerificationInfo,
-            VerificationProblem, VerificationRequest,
+            VerificationLevel, VerificationProblem, VerificationRequest,
         },
         contract_identifier::{ContractIdentifier, Match},
     },
@@ -35,7 +35,9 @@ use crate::{
 mod compilers;
 pub mod error;
 pub mod etherscan;
+pub mod inheritance;
 mod metrics;
+pub mod proxies;
 mod resolver;
 #[cfg(test)]
 mod tests;
@@ -281,10 +283,13 @@ impl ContractVerifier {
             .context("invalid stored EVM bytecode")?,
         };
 
+        let mut verification_level = VerificationLevel::Full;
         let mut verification_problems = Vec::new();
 
         match identifier.matches(deployed_bytecode) {
             Match::Full => {}
+            // Partial matches are persisted with the corresponding verification level, so that the contract
+            // can be re-verified later to upgrade the level.
             Match::Partial => {
                 tracing::trace!(
                     request_id = request.id,
@@ -292,6 +297,7 @@ impl ContractVerifier {
                     compiled = hex::encode(artifacts.deployed_bytecode()),
                     "Partial bytecode match",
                 );
+                verification_level = VerificationLevel::Partial;
                 verification_problems.push(VerificationProblem::IncorrectMetadata);
             }
             Match::None => {
@@ -333,7 +339,9 @@ impl ContractVerifier {
             request,
             artifacts,
             verified_at,
+            verification_level: Some(verification_level),
             verification_problems,
+            inherited_from: None,
         };
         Ok((info, identifier))
     }
@@ -589,6 +597,8 @@ impl ContractVerifier {
             .await?;
         match verification_result {
             Ok((info, identifier)) => {
+                let address = info.request.req.contract_address;
+                let is_perfect_match = info.is_perfect_match();
                 let mut transaction = storage.start_transaction().await?;
                 transaction
                     .contract_verification_dal()
@@ -598,7 +608,27 @@ impl ContractVerifier {
                         identifier.bytecode_without_metadata_keccak256,
                     )
                     .await?;
-                if self.etherscan_verifier_enabled {
+                if is_perfect_match {
+                    let inherited_count = transaction
+                        .contract_verification_dal()
+                        .inherit_verification_info(address)
+                        .await?;
+                    tracing::debug!(
+                        "Verification info for {address:?} was inherited by {inherited_count} contracts \
+                         with the same bytecode"
+                    );
+                    API_CONTRACT_VERIFIER_METRICS
+                        .inherited_verifications
+                        .inc_by(inherited_count as u64);
+                }
+                // Partially verified contracts are not forwarded to Etherscan; they will be forwarded
+                // once re-verified with the full match.
+                if self.etherscan_verifier_enabled && !is_perfect_match {
+                    tracing::debug!(
+                        "Skipped etherscan verification for request with id = {request_id} \
+                         since the contract is only partially verified"
+                    );
+                } else if self.etherscan_verifier_enabled {
                     tracing::debug!(
                         "Created etherscan verification request with id = {request_id}"
                     );
@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use vise::{Buckets, Counter, Histogram, LabeledFamily, Metrics};
+use vise::{Buckets, Counter, Gauge, Histogram, LabeledFamily, Metrics};
 
 // Starting bucket from 5 sec as there is a 5 second pause between
 // the verification request and the time verification status is checked for the first time.
@@ -19,6 +19,14 @@ pub(crate) struct ApiContractVerifierMetrics {
     pub failed_verifications: LabeledFamily<&'static str, Counter, 1>,
     #[metrics(labels = ["service_name"])]
     pub successful_verifications: LabeledFamily<&'static str, Counter, 1>,
+    /// Number of contracts that have inherited verification info from a contract with the same bytecode.
+    pub inherited_verifications: Counter,
+    /// Last L2 block processed by the verification inheritance task.
+    pub last_inheritance_l2_block: Gauge<u64>,
+    /// Number of created or updated links between proxy contracts and their implementations.
+    pub updated_proxy_links: Counter,
+    /// Last L2 block processed by the proxy tracking task.
+    pub last_proxy_tracking_l2_block: Gauge<u64>,
 }
 
 #[vise::register]
@@ -0,0 +1,121 @@
+//! Tracking of links between proxy contracts and their implementations.
+
+use std::time::Duration;
+
+use tokio::sync::watch;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_types::{contract_verification::proxy::ProxyLink, L2BlockNumber};
+
+use crate::metrics::API_CONTRACT_VERIFIER_METRICS;
+
+/// Background task tracking proxy-to-implementation links. Links are detected from writes to the standard proxy
+/// storage slots (see [`ProxyKind`](zksync_types::contract_verification::proxy::ProxyKind)) in new L2 blocks,
+/// so that upgrades are reflected once the corresponding L2 block is processed.
+#[derive(Debug)]
+pub struct ProxyTrackingTask {
+    connection_pool: ConnectionPool<Core>,
+    poll_interval: Duration,
+    max_l2_blocks_per_iteration: u32,
+    stop_receiver: watch::Receiver<bool>,
+}
+
+impl ProxyTrackingTask {
+    const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+    const DEFAULT_MAX_L2_BLOCKS_PER_ITERATION: u32 = 1_000;
+
+    pub fn new(
+        connection_pool: ConnectionPool<Core>,
+        stop_receiver: watch::Receiver<bool>,
+    ) -> Self {
+        Self {
+            connection_pool,
+            poll_interval: Self::DEFAULT_POLL_INTERVAL,
+            max_l2_blocks_per_iteration: Self::DEFAULT_MAX_L2_BLOCKS_PER_ITERATION,
+            stop_receiver,
+        }
+    }
+
+    /// Processes a chunk of L2 blocks after the last processed one. Returns `false` if there are no L2 blocks to process.
+    async fn process_new_l2_blocks(&self) -> anyhow::Result<bool> {
+        let mut storage = self
+            .connection_pool
+            .connection_tagged("contract_verifier")
+            .await?;
+        let last_processed = storage
+            .contract_verification_dal()
+            .get_last_proxy_tracking_l2_block()
+            .await?;
+        let Some(last_sealed) = storage.blocks_dal().get_sealed_l2_block_number().await? else {
+            return Ok(false);
+        };
+
+        let next_l2_block = last_processed.map_or(L2BlockNumber(0), |number| number + 1);
+        if next_l2_block > last_sealed {
+            return Ok(false);
+        }
+        let last_l2_block = last_sealed.min(next_l2_block + (self.max_l2_blocks_per_iteration - 1));
+
+        let mut transaction = storage.start_transaction().await?;
+        let updates = transaction
+            .contract_verification_dal()
+            .get_proxy_slot_updates(next_l2_block..=last_l2_block)
+            .await?;
+        let mut links = vec![];
+        for (proxy_address, kind, slot_value, l2_block_number) in updates {
+            if let Some(target_address) = ProxyLink::parse_target_address(slot_value) {
+                links.push(ProxyLink {
+                    proxy_address,
+                    kind,
+                    target_address,
+                    l2_block_number,
+                });
+            } else {
+                // The slot was reset or is used for something else.
+                transaction
+                    .contract_verification_dal()
+                    .remove_proxy_link(proxy_address, kind)
+                    .await?;
+            }
+        }
+        transaction
+            .contract_verification_dal()
+            .save_proxy_links(&links)
+            .await?;
+        transaction
+            .contract_verification_dal()
+            .set_last_proxy_tracking_l2_block(last_l2_block)
+            .await?;
+        transaction.commit().await?;
+
+        if !links.is_empty() {
+            tracing::info!(
+                "Updated {} proxy links in L2 blocks #{next_l2_block}..=#{last_l2_block}",
+                links.len()
+            );
+        }
+        API_CONTRACT_VERIFIER_METRICS
+            .updated_proxy_links
+            .inc_by(links.len() as u64);
+        API_CONTRACT_VERIFIER_METRICS
+            .last_proxy_tracking_l2_block
+            .set(last_l2_block.0.into());
+        Ok(true)
+    }
+
+    pub async fn run(mut self) -> anyhow::Result<()> {
+        while !*self.stop_receiver.borrow_and_update() {
+            if self.process_new_l2_blocks().await? {
+                continue; // There may be more L2 blocks to process
+            }
+
+            if tokio::time::timeout(self.poll_interval, self.stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received, proxy tracking task is shutting down");
+        Ok(())
+    }
+}
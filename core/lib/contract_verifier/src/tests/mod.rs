@@ -9,18 +9,23 @@ use zksync_node_test_utils::{create_l1_batch, create_l2_block};
 use zksync_types::{
     address_to_h256,
     bytecode::{pad_evm_bytecode, BytecodeHash},
-    contract_verification::api::{CompilerVersions, SourceCodeData, VerificationIncomingRequest},
+    contract_verification::{
+        api::{CompilerVersions, SourceCodeData, VerificationIncomingRequest},
+        proxy::ProxyKind,
+    },
     get_code_key, get_known_code_key,
     l2::L2Tx,
     tx::IncludedTxLocation,
-    Execute, L1BatchNumber, L2BlockNumber, ProtocolVersion, StorageLog, CONTRACT_DEPLOYER_ADDRESS,
-    H256, U256,
+    AccountTreeId, Execute, L1BatchNumber, L2BlockNumber, ProtocolVersion, StorageKey, StorageLog,
+    CONTRACT_DEPLOYER_ADDRESS, H256, U256,
 };
 use zksync_vm_interface::{tracer::ValidationTraces, TransactionExecutionMetrics, VmEvent};
 
 use super::*;
 use crate::{
     compilers::{SolcInput, VyperInput, ZkSolcInput},
+    inheritance::VerificationInheritanceTask,
+    proxies::ProxyTrackingTask,
     resolver::{Compiler, SupportedCompilerVersions},
 };
 
@@ -471,6 +476,10 @@ async fn assert_request_success(
         &verification_info.verification_problems,
         verification_problems
     );
+    assert_eq!(
+        verification_info.is_perfect_match(),
+        verification_problems.is_empty()
+    );
 
     verification_info
 }
@@ -552,6 +561,326 @@ async fn verifying_evm_bytecode(contract: TestContract) {
     assert_request_success(&mut storage, request_id, address, &creation_bytecode, &[]).await;
 }
 
+async fn save_deploy_event(
+    storage: &mut Connection<'_, Core>,
+    l2_block_number: L2BlockNumber,
+    address: Address,
+    bytecode_hash: H256,
+) {
+    let location = IncludedTxLocation {
+        tx_hash: H256::from_low_u64_be(address.to_low_u64_be()),
+        tx_index_in_l2_block: 0,
+    };
+    let deploy_event = VmEvent {
+        location: (L1BatchNumber(0), 0),
+        address: CONTRACT_DEPLOYER_ADDRESS,
+        indexed_topics: vec![
+            VmEvent::DEPLOY_EVENT_SIGNATURE,
+            H256::zero(),
+            bytecode_hash,
+            address_to_h256(&address),
+        ],
+        value: vec![],
+    };
+    storage
+        .events_dal()
+        .save_events(l2_block_number, &[(location, vec![&deploy_event])])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn inheriting_verification_info_for_identical_bytecode() {
+    let pool = ConnectionPool::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    prepare_storage(&mut storage).await;
+
+    let address = Address::repeat_byte(1);
+    let bytecode = vec![0_u8; 32];
+    let bytecode_hash = BytecodeHash::for_bytecode(&bytecode).value();
+    mock_deployment(&mut storage, address, bytecode, &[]).await;
+    // Another contract deployed with the same bytecode before the verification.
+    let identical_address = Address::repeat_byte(2);
+    storage
+        .blocks_dal()
+        .insert_l2_block(&create_l2_block(1))
+        .await
+        .unwrap();
+    save_deploy_event(
+        &mut storage,
+        L2BlockNumber(1),
+        identical_address,
+        bytecode_hash,
+    )
+    .await;
+
+    let req = test_request(address, COUNTER_CONTRACT);
+    storage
+        .contract_verification_dal()
+        .add_contract_verification_request(&req)
+        .await
+        .unwrap();
+    let mock_resolver = MockCompilerResolver::zksolc(|_| CompilationArtifacts {
+        bytecode: vec![0; 32],
+        deployed_bytecode: None,
+        abi: counter_contract_abi(),
+    });
+    let verifier = ContractVerifier::with_resolver(
+        Duration::from_secs(60),
+        pool.clone(),
+        Arc::new(mock_resolver),
+        false,
+    )
+    .await
+    .unwrap();
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    verifier.run(stop_receiver.clone(), Some(1)).await.unwrap();
+
+    let info = storage
+        .contract_verification_dal()
+        .get_inherited_verification_info(identical_address)
+        .await
+        .unwrap()
+        .expect("no inherited info");
+    assert_eq!(info.inherited_from, Some(address));
+    assert_eq!(info.request.req.contract_address, address);
+
+    // Contracts deployed after the verification should be picked up by the background task.
+    let new_address = Address::repeat_byte(3);
+    storage
+        .blocks_dal()
+        .insert_l2_block(&create_l2_block(2))
+        .await
+        .unwrap();
+    save_deploy_event(&mut storage, L2BlockNumber(2), new_address, bytecode_hash).await;
+
+    let task = VerificationInheritanceTask::new(pool.clone(), stop_receiver);
+    let task_handle = tokio::spawn(task.run());
+    loop {
+        let last_processed = storage
+            .contract_verification_dal()
+            .get_last_inheritance_l2_block()
+            .await
+            .unwrap();
+        if last_processed == Some(L2BlockNumber(2)) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    stop_sender.send_replace(true);
+    task_handle.await.unwrap().unwrap();
+
+    let info = storage
+        .contract_verification_dal()
+        .get_inherited_verification_info(new_address)
+        .await
+        .unwrap()
+        .expect("no inherited info");
+    assert_eq!(info.inherited_from, Some(address));
+}
+
+async fn wait_for_proxy_tracking(
+    storage: &mut Connection<'_, Core>,
+    l2_block_number: L2BlockNumber,
+) {
+    loop {
+        let last_processed = storage
+            .contract_verification_dal()
+            .get_last_proxy_tracking_l2_block()
+            .await
+            .unwrap();
+        if last_processed == Some(l2_block_number) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+}
+
+#[tokio::test]
+async fn tracking_proxy_upgrades() {
+    let pool = ConnectionPool::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    prepare_storage(&mut storage).await;
+
+    let proxy_address = Address::repeat_byte(1);
+    let implementation_address = Address::repeat_byte(2);
+    let new_implementation_address = Address::repeat_byte(3);
+    let slot_key = StorageKey::new(
+        AccountTreeId::new(proxy_address),
+        ProxyKind::Eip1967.storage_slot(),
+    );
+
+    for (number, implementation) in [(1, implementation_address), (2, new_implementation_address)] {
+        storage
+            .blocks_dal()
+            .insert_l2_block(&create_l2_block(number))
+            .await
+            .unwrap();
+        let log = StorageLog::new_write_log(slot_key, address_to_h256(&implementation));
+        storage
+            .storage_logs_dal()
+            .append_storage_logs(L2BlockNumber(number), &[log])
+            .await
+            .unwrap();
+    }
+
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let task = ProxyTrackingTask::new(pool.clone(), stop_receiver.clone());
+    let task_handle = tokio::spawn(task.run());
+    wait_for_proxy_tracking(&mut storage, L2BlockNumber(2)).await;
+
+    let link = storage
+        .contract_verification_dal()
+        .get_proxy_link(proxy_address)
+        .await
+        .unwrap()
+        .expect("no proxy link");
+    assert_eq!(link.kind, ProxyKind::Eip1967);
+    assert_eq!(link.target_address, new_implementation_address);
+    assert_eq!(link.l2_block_number, L2BlockNumber(2));
+
+    // Resetting the implementation should remove the link.
+    storage
+        .blocks_dal()
+        .insert_l2_block(&create_l2_block(3))
+        .await
+        .unwrap();
+    let log = StorageLog::new_write_log(slot_key, H256::zero());
+    storage
+        .storage_logs_dal()
+        .append_storage_logs(L2BlockNumber(3), &[log])
+        .await
+        .unwrap();
+    wait_for_proxy_tracking(&mut storage, L2BlockNumber(3)).await;
+    stop_sender.send_replace(true);
+    task_handle.await.unwrap().unwrap();
+
+    let link = storage
+        .contract_verification_dal()
+        .get_proxy_link(proxy_address)
+        .await
+        .unwrap();
+    assert_eq!(link, None);
+}
+
+#[tokio::test]
+async fn tracking_proxy_with_multiple_slots_written() {
+    let pool = ConnectionPool::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    prepare_storage(&mut storage).await;
+
+    let proxy_address = Address::repeat_byte(1);
+    let implementation_address = Address::repeat_byte(2);
+    let beacon_address = Address::repeat_byte(3);
+    for (number, kind, target) in [
+        (1, ProxyKind::Eip1967, implementation_address),
+        (2, ProxyKind::Eip1967Beacon, beacon_address),
+    ] {
+        storage
+            .blocks_dal()
+            .insert_l2_block(&create_l2_block(number))
+            .await
+            .unwrap();
+        let slot_key = StorageKey::new(AccountTreeId::new(proxy_address), kind.storage_slot());
+        let log = StorageLog::new_write_log(slot_key, address_to_h256(&target));
+        storage
+            .storage_logs_dal()
+            .append_storage_logs(L2BlockNumber(number), &[log])
+            .await
+            .unwrap();
+    }
+
+    // Both slots are processed in a single iteration, so the task must save only one link for the proxy.
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let task = ProxyTrackingTask::new(pool.clone(), stop_receiver.clone());
+    let task_handle = tokio::spawn(task.run());
+    wait_for_proxy_tracking(&mut storage, L2BlockNumber(2)).await;
+    stop_sender.send_replace(true);
+    task_handle.await.unwrap().unwrap();
+
+    let link = storage
+        .contract_verification_dal()
+        .get_proxy_link(proxy_address)
+        .await
+        .unwrap()
+        .expect("no proxy link");
+    assert_eq!(link.kind, ProxyKind::Eip1967Beacon);
+    assert_eq!(link.target_address, beacon_address);
+    assert_eq!(link.l2_block_number, L2BlockNumber(2));
+}
+
+#[tokio::test]
+async fn partial_match_is_upgraded_to_full_match() {
+    let pool = ConnectionPool::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    prepare_storage(&mut storage).await;
+
+    // Bytecodes differ only in the last word, which is treated as the metadata hash.
+    let deployed_bytecode = [[1_u8; 32], [1; 32], [0xaa; 32]].concat();
+    let mut partial_bytecode = deployed_bytecode.clone();
+    partial_bytecode[64..].copy_from_slice(&[0xbb; 32]);
+
+    let address = Address::repeat_byte(1);
+    mock_deployment(&mut storage, address, deployed_bytecode.clone(), &[]).await;
+    let req = test_request(address, COUNTER_CONTRACT);
+
+    for (bytecode, expected_level) in [
+        (partial_bytecode, VerificationLevel::Partial),
+        (deployed_bytecode, VerificationLevel::Full),
+    ] {
+        let request_id = storage
+            .contract_verification_dal()
+            .add_contract_verification_request(&req)
+            .await
+            .unwrap();
+        let expected_bytecode = bytecode.clone();
+        let mock_resolver = MockCompilerResolver::zksolc(move |_| CompilationArtifacts {
+            bytecode: bytecode.clone(),
+            deployed_bytecode: None,
+            abi: counter_contract_abi(),
+        });
+        let verifier = ContractVerifier::with_resolver(
+            Duration::from_secs(60),
+            pool.clone(),
+            Arc::new(mock_resolver),
+            true,
+        )
+        .await
+        .unwrap();
+
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        verifier.run(stop_receiver, Some(1)).await.unwrap();
+
+        let expected_problems = match expected_level {
+            VerificationLevel::Full => vec![],
+            VerificationLevel::Partial => vec![VerificationProblem::IncorrectMetadata],
+        };
+        let info = assert_request_success(
+            &mut storage,
+            request_id,
+            address,
+            &expected_bytecode,
+            &expected_problems,
+        )
+        .await;
+        assert_eq!(info.verification_level, Some(expected_level));
+
+        // Only full matches should be forwarded to Etherscan.
+        let etherscan_request = storage
+            .etherscan_verification_dal()
+            .get_next_queued_verification_request(Duration::from_secs(60))
+            .await
+            .unwrap();
+        match expected_level {
+            VerificationLevel::Full => {
+                let (etherscan_request, _) = etherscan_request.unwrap();
+                assert_eq!(etherscan_request.id, request_id);
+            }
+            VerificationLevel::Partial => assert!(etherscan_request.is_none()),
+        }
+    }
+}
+
 #[tokio::test]
 async fn bytecode_mismatch_error() {
     let pool = ConnectionPool::test_pool().await;
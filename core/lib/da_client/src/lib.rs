@@ -1,3 +1,4 @@
+pub mod packing;
 pub mod types;
 
 use std::fmt;
@@ -20,6 +21,10 @@ pub trait DataAvailabilityClient: Sync + Send + fmt::Debug {
     /// Fetches the inclusion data for a given blob_id.
     async fn get_inclusion_data(&self, blob_id: &str) -> Result<Option<InclusionData>, DAError>;
 
+    /// Retrieves the contents of the blob with a given blob_id from the DA layer. Returns `None` if the blob
+    /// is not available yet. Clients that don't support retrieval return a non-retriable error.
+    async fn fetch_blob(&self, blob_id: &str) -> Result<Option<Vec<u8>>, DAError>;
+
     /// Clones the client and wraps it in a Box.
     fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient>;
 
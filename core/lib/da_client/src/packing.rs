@@ -0,0 +1,215 @@
+//! Framing format for DA blobs holding pubdata of several consecutive L1 batches.
+//!
+//! A packed blob has the following layout (all integers are big-endian):
+//!
+//! - 4 bytes: [`PACKED_BLOB_MAGIC`];
+//! - 1 byte: format version (currently, 1);
+//! - 1 byte: [`BlobCompression`] of the body;
+//! - body, possibly compressed:
+//!   - 4 bytes: number of frames;
+//!   - for each frame: 4 bytes of the L1 batch number, 4 bytes of the pubdata length, and the pubdata itself.
+//!
+//! Blobs without the magic prefix are treated as the raw pubdata of a single L1 batch, which is how blobs
+//! are dispatched if packing is disabled.
+
+use std::io::{Read, Write};
+
+use anyhow::Context as _;
+use flate2::{read::ZlibDecoder, write::ZlibEncoder, Compression};
+
+/// Prefix distinguishing packed blobs from the raw pubdata.
+pub const PACKED_BLOB_MAGIC: [u8; 4] = *b"zkDA";
+const PACKED_BLOB_VERSION: u8 = 1;
+const HEADER_LEN: usize = PACKED_BLOB_MAGIC.len() + 2;
+
+/// Compression applied to the body of a packed blob.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlobCompression {
+    None,
+    Zlib,
+}
+
+impl BlobCompression {
+    fn to_byte(self) -> u8 {
+        match self {
+            Self::None => 0,
+            Self::Zlib => 1,
+        }
+    }
+
+    fn from_byte(byte: u8) -> anyhow::Result<Self> {
+        Ok(match byte {
+            0 => Self::None,
+            1 => Self::Zlib,
+            _ => anyhow::bail!("unknown blob compression: {byte}"),
+        })
+    }
+}
+
+/// Pubdata of a single L1 batch within a packed blob.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchFrame {
+    pub l1_batch_number: u32,
+    pub pubdata: Vec<u8>,
+}
+
+/// Returns `true` if the blob is in the packed format.
+pub fn is_packed_blob(blob: &[u8]) -> bool {
+    blob.starts_with(&PACKED_BLOB_MAGIC)
+}
+
+/// Packs pubdata of the provided L1 batches into a single blob.
+pub fn pack_blob(frames: &[BatchFrame], compression: BlobCompression) -> anyhow::Result<Vec<u8>> {
+    let frame_count = u32::try_from(frames.len()).context("too many frames")?;
+    let body_len = 4 + frames
+        .iter()
+        .map(|frame| 8 + frame.pubdata.len())
+        .sum::<usize>();
+    let mut body = Vec::with_capacity(body_len);
+    body.extend_from_slice(&frame_count.to_be_bytes());
+    for frame in frames {
+        let pubdata_len = u32::try_from(frame.pubdata.len()).context("pubdata is too large")?;
+        body.extend_from_slice(&frame.l1_batch_number.to_be_bytes());
+        body.extend_from_slice(&pubdata_len.to_be_bytes());
+        body.extend_from_slice(&frame.pubdata);
+    }
+
+    let mut blob = Vec::with_capacity(HEADER_LEN + body.len());
+    blob.extend_from_slice(&PACKED_BLOB_MAGIC);
+    blob.push(PACKED_BLOB_VERSION);
+    blob.push(compression.to_byte());
+    match compression {
+        BlobCompression::None => blob.extend_from_slice(&body),
+        BlobCompression::Zlib => {
+            let mut encoder = ZlibEncoder::new(blob, Compression::best());
+            encoder.write_all(&body)?;
+            blob = encoder.finish().context("failed compressing blob")?;
+        }
+    }
+    Ok(blob)
+}
+
+/// Unpacks a blob produced by [`pack_blob()`].
+pub fn unpack_blob(blob: &[u8]) -> anyhow::Result<Vec<BatchFrame>> {
+    anyhow::ensure!(is_packed_blob(blob), "blob is not packed");
+    anyhow::ensure!(blob.len() >= HEADER_LEN, "blob header is truncated");
+    let version = blob[PACKED_BLOB_MAGIC.len()];
+    anyhow::ensure!(
+        version == PACKED_BLOB_VERSION,
+        "unsupported packed blob version: {version}"
+    );
+    let compression = BlobCompression::from_byte(blob[PACKED_BLOB_MAGIC.len() + 1])?;
+
+    let body = &blob[HEADER_LEN..];
+    let body = match compression {
+        BlobCompression::None => body.to_vec(),
+        BlobCompression::Zlib => {
+            let mut decompressed = vec![];
+            ZlibDecoder::new(body)
+                .read_to_end(&mut decompressed)
+                .context("failed decompressing blob")?;
+            decompressed
+        }
+    };
+
+    let mut reader = body.as_slice();
+    let frame_count = read_u32(&mut reader).context("frame count")?;
+    let mut frames = Vec::with_capacity(frame_count.min(1_024) as usize);
+    for i in 0..frame_count {
+        let l1_batch_number = read_u32(&mut reader).with_context(|| format!("frame #{i}"))?;
+        let pubdata_len = read_u32(&mut reader).with_context(|| format!("frame #{i}"))? as usize;
+        anyhow::ensure!(
+            reader.len() >= pubdata_len,
+            "pubdata in frame #{i} is truncated"
+        );
+        let (pubdata, rest) = reader.split_at(pubdata_len);
+        frames.push(BatchFrame {
+            l1_batch_number,
+            pubdata: pubdata.to_vec(),
+        });
+        reader = rest;
+    }
+    anyhow::ensure!(reader.is_empty(), "unexpected data after the last frame");
+    Ok(frames)
+}
+
+/// Extracts the pubdata of the specified L1 batch from a blob, which may be either packed or contain
+/// raw pubdata of a single batch.
+pub fn extract_batch_pubdata(blob: &[u8], l1_batch_number: u32) -> anyhow::Result<Vec<u8>> {
+    if !is_packed_blob(blob) {
+        return Ok(blob.to_vec());
+    }
+    unpack_blob(blob)?
+        .into_iter()
+        .find(|frame| frame.l1_batch_number == l1_batch_number)
+        .map(|frame| frame.pubdata)
+        .with_context(|| format!("L1 batch #{l1_batch_number} is not present in the blob"))
+}
+
+fn read_u32(reader: &mut &[u8]) -> anyhow::Result<u32> {
+    anyhow::ensure!(reader.len() >= 4, "unexpected end of blob");
+    let (bytes, rest) = reader.split_at(4);
+    *reader = rest;
+    Ok(u32::from_be_bytes(bytes.try_into().unwrap()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frames() -> Vec<BatchFrame> {
+        vec![
+            BatchFrame {
+                l1_batch_number: 1,
+                pubdata: vec![1; 100],
+            },
+            BatchFrame {
+                l1_batch_number: 2,
+                pubdata: vec![],
+            },
+            BatchFrame {
+                l1_batch_number: 3,
+                pubdata: (0..=255).collect(),
+            },
+        ]
+    }
+
+    #[test]
+    fn packing_roundtrip() {
+        for compression in [BlobCompression::None, BlobCompression::Zlib] {
+            let blob = pack_blob(&frames(), compression).unwrap();
+            assert!(is_packed_blob(&blob));
+            assert_eq!(unpack_blob(&blob).unwrap(), frames());
+            assert_eq!(
+                extract_batch_pubdata(&blob, 3).unwrap(),
+                frames()[2].pubdata
+            );
+            extract_batch_pubdata(&blob, 4).unwrap_err();
+        }
+    }
+
+    #[test]
+    fn compression_reduces_blob_size() {
+        let uncompressed = pack_blob(&frames(), BlobCompression::None).unwrap();
+        let compressed = pack_blob(&frames(), BlobCompression::Zlib).unwrap();
+        assert!(compressed.len() < uncompressed.len());
+    }
+
+    #[test]
+    fn raw_pubdata_is_extracted_as_is() {
+        let pubdata = vec![0, 0, 0, 1, 2, 3];
+        assert!(!is_packed_blob(&pubdata));
+        assert_eq!(extract_batch_pubdata(&pubdata, 1).unwrap(), pubdata);
+    }
+
+    #[test]
+    fn malformed_blobs_are_rejected() {
+        let blob = pack_blob(&frames(), BlobCompression::None).unwrap();
+        unpack_blob(&blob[..blob.len() - 1]).unwrap_err();
+        unpack_blob(&[blob.as_slice(), &[0]].concat()).unwrap_err();
+
+        let mut blob_with_unknown_version = blob.clone();
+        blob_with_unknown_version[PACKED_BLOB_MAGIC.len()] = 2;
+        unpack_blob(&blob_with_unknown_version).unwrap_err();
+    }
+}
@@ -33,11 +33,14 @@ impl error::Error for DAError {}
 pub struct DispatchResponse {
     /// The blob_id is needed to fetch the inclusion data.
     pub blob_id: String,
+    /// Fee paid for the blob to the DA layer, in the smallest units of its native token. `None` if the client
+    /// doesn't know the fee.
+    pub fee: Option<u64>,
 }
 
 impl From<String> for DispatchResponse {
     fn from(blob_id: String) -> Self {
-        DispatchResponse { blob_id }
+        DispatchResponse { blob_id, fee: None }
     }
 }
 
@@ -0,0 +1,11 @@
+-- Minimal headers of hard-pruned L2 blocks that contain data retained according to pruning retention rules
+-- (e.g., events emitted by specific contracts). Used to serve `eth_getLogs` and transaction receipts for retained data.
+CREATE TABLE IF NOT EXISTS pruning_retained_miniblocks
+(
+    number          BIGINT PRIMARY KEY,
+    l1_batch_number BIGINT NOT NULL,
+    hash            BYTEA  NOT NULL,
+    timestamp       BIGINT NOT NULL,
+
+    created_at      TIMESTAMP NOT NULL
+);
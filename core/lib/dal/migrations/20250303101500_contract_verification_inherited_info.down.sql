@@ -0,0 +1,4 @@
+DROP TABLE IF EXISTS contract_verification_inheritance_progress;
+DROP TABLE IF EXISTS contract_verification_inherited_info;
+DROP INDEX IF EXISTS contract_verification_info_v2_bytecode_hash_idx;
+ALTER TABLE contract_verification_info_v2 DROP COLUMN IF EXISTS bytecode_hash;
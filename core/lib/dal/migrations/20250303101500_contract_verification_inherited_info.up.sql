@@ -0,0 +1,21 @@
+-- Versioned hash of the deployed bytecode of the verified contract. May be NULL for contracts verified before
+-- the column was introduced; such values are filled by the contract verifier.
+ALTER TABLE contract_verification_info_v2 ADD COLUMN IF NOT EXISTS bytecode_hash BYTEA;
+CREATE INDEX IF NOT EXISTS contract_verification_info_v2_bytecode_hash_idx ON contract_verification_info_v2 (bytecode_hash);
+
+-- Contracts inheriting verification info from a fully verified contract with the same bytecode hash.
+CREATE TABLE IF NOT EXISTS contract_verification_inherited_info (
+    contract_address BYTEA NOT NULL PRIMARY KEY,
+    verified_contract_address BYTEA NOT NULL REFERENCES contract_verification_info_v2 (initial_contract_addr) ON DELETE CASCADE,
+    bytecode_hash BYTEA NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+CREATE INDEX IF NOT EXISTS contract_verification_inherited_info_verified_contract_address_idx
+    ON contract_verification_inherited_info (verified_contract_address);
+
+-- Progress of propagating verification info to new deployments. Contains at most one row.
+CREATE TABLE IF NOT EXISTS contract_verification_inheritance_progress (
+    id BOOLEAN NOT NULL PRIMARY KEY DEFAULT TRUE CHECK (id),
+    last_processed_l2_block BIGINT NOT NULL,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,2 @@
+UPDATE contract_verification_info_v2
+SET verification_info = verification_info - 'verificationLevel';
@@ -0,0 +1,11 @@
+-- Persist the verification level explicitly for infos saved before it was introduced.
+UPDATE contract_verification_info_v2
+SET verification_info = JSONB_SET(
+    verification_info,
+    '{verificationLevel}',
+    CASE
+        WHEN COALESCE(JSONB_ARRAY_LENGTH(verification_info -> 'verificationProblems'), 0) = 0 THEN '"full"'::JSONB
+        ELSE '"partial"'::JSONB
+    END
+)
+WHERE NOT verification_info ? 'verificationLevel';
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS contract_verification_proxy_tracking_progress;
+DROP TABLE IF EXISTS contract_verification_proxies;
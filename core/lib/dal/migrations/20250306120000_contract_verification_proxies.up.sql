@@ -0,0 +1,19 @@
+-- Links between proxy contracts and their targets (implementations, or beacons for beacon proxies) detected
+-- from writes to the standard proxy storage slots.
+CREATE TABLE IF NOT EXISTS contract_verification_proxies (
+    proxy_address BYTEA NOT NULL PRIMARY KEY,
+    proxy_kind TEXT NOT NULL,
+    target_address BYTEA NOT NULL,
+    l2_block_number BIGINT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+CREATE INDEX IF NOT EXISTS contract_verification_proxies_target_address_idx
+    ON contract_verification_proxies (target_address);
+
+-- Progress of tracking proxy links. Contains at most one row.
+CREATE TABLE IF NOT EXISTS contract_verification_proxy_tracking_progress (
+    id BOOLEAN NOT NULL PRIMARY KEY DEFAULT TRUE CHECK (id),
+    last_processed_l2_block BIGINT NOT NULL,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,2 @@
+ALTER TABLE data_availability DROP COLUMN IF EXISTS secondary_blob_id;
+ALTER TABLE data_availability DROP COLUMN IF EXISTS da_layer;
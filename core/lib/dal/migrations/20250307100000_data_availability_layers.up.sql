@@ -0,0 +1,2 @@
+ALTER TABLE data_availability ADD COLUMN da_layer TEXT NOT NULL DEFAULT 'primary';
+ALTER TABLE data_availability ADD COLUMN secondary_blob_id TEXT;
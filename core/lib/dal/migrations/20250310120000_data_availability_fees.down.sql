@@ -0,0 +1,2 @@
+ALTER TABLE data_availability DROP COLUMN IF EXISTS fee_in_base_token;
+ALTER TABLE data_availability DROP COLUMN IF EXISTS fee;
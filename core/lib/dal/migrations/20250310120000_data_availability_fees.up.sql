@@ -0,0 +1,2 @@
+ALTER TABLE data_availability ADD COLUMN fee NUMERIC(80);
+ALTER TABLE data_availability ADD COLUMN fee_in_base_token NUMERIC(80);
@@ -0,0 +1,6 @@
+ALTER TABLE tee_attestations
+    DROP COLUMN IF EXISTS status,
+    DROP COLUMN IF EXISTS mr_enclave,
+    DROP COLUMN IF EXISTS mr_signer,
+    DROP COLUMN IF EXISTS verification_error,
+    DROP COLUMN IF EXISTS updated_at;
@@ -0,0 +1,6 @@
+ALTER TABLE tee_attestations
+    ADD COLUMN IF NOT EXISTS status TEXT NOT NULL DEFAULT 'unverified',
+    ADD COLUMN IF NOT EXISTS mr_enclave BYTEA,
+    ADD COLUMN IF NOT EXISTS mr_signer BYTEA,
+    ADD COLUMN IF NOT EXISTS verification_error TEXT,
+    ADD COLUMN IF NOT EXISTS updated_at TIMESTAMP;
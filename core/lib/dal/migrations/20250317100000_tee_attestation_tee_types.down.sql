@@ -0,0 +1,4 @@
+ALTER TABLE tee_attestations
+    DROP COLUMN IF EXISTS tee_type,
+    DROP COLUMN IF EXISTS mr_td,
+    DROP COLUMN IF EXISTS rtmrs;
@@ -0,0 +1,4 @@
+ALTER TABLE tee_attestations
+    ADD COLUMN IF NOT EXISTS tee_type TEXT,
+    ADD COLUMN IF NOT EXISTS mr_td BYTEA,
+    ADD COLUMN IF NOT EXISTS rtmrs BYTEA[];
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS external_proof_leases;
+DROP TABLE IF EXISTS external_provers;
@@ -0,0 +1,27 @@
+CREATE TABLE IF NOT EXISTS external_provers
+(
+    id               BIGSERIAL PRIMARY KEY,
+    name             TEXT      NOT NULL UNIQUE,
+    api_key_hash     BYTEA     NOT NULL UNIQUE,
+    is_active        BOOLEAN   NOT NULL DEFAULT TRUE,
+    claimed_batches  BIGINT    NOT NULL DEFAULT 0,
+    accepted_proofs  BIGINT    NOT NULL DEFAULT 0,
+    rejected_proofs  BIGINT    NOT NULL DEFAULT 0,
+    released_batches BIGINT    NOT NULL DEFAULT 0,
+    expired_leases   BIGINT    NOT NULL DEFAULT 0,
+    proving_time_sec BIGINT    NOT NULL DEFAULT 0,
+    created_at       TIMESTAMP NOT NULL,
+    updated_at       TIMESTAMP NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS external_proof_leases
+(
+    l1_batch_number  BIGINT PRIMARY KEY REFERENCES proof_generation_details (l1_batch_number) ON DELETE CASCADE,
+    prover_id        BIGINT    NOT NULL REFERENCES external_provers (id),
+    lease_expires_at TIMESTAMP NOT NULL,
+    created_at       TIMESTAMP NOT NULL,
+    updated_at       TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_external_proof_leases_prover_id
+    ON external_proof_leases (prover_id);
@@ -1,7 +1,9 @@
 #![doc = include_str!("../doc/ContractVerificationDal.md")]
 
 use std::{
+    collections::HashMap,
     fmt::{Display, Formatter},
+    ops::RangeInclusive,
     time::Duration,
 };
 
@@ -19,8 +21,9 @@ use zksync_types::{
             VerificationRequestStatus,
         },
         contract_identifier::ContractIdentifier,
+        proxy::{ProxyKind, ProxyLink},
     },
-    web3, Address, CONTRACT_DEPLOYER_ADDRESS, H256,
+    web3, Address, L2BlockNumber, CONTRACT_DEPLOYER_ADDRESS, H256,
 };
 use zksync_vm_interface::VmEvent;
 
@@ -256,21 +259,43 @@ impl ContractVerificationDal<'_, '_> {
                 initial_contract_addr,
                 bytecode_keccak256,
                 bytecode_without_metadata_keccak256,
-                verification_info
+                verification_info,
+                bytecode_hash
             )
             VALUES
-            ($1, $2, $3, $4)
+            (
+                $1,
+                $2,
+                $3,
+                $4,
+                (
+                    SELECT
+                        topic3
+                    FROM
+                        events
+                    WHERE
+                        address = $5
+                        AND topic1 = $6
+                        AND topic4 = $7
+                    LIMIT
+                        1
+                )
+            )
             ON CONFLICT (initial_contract_addr) DO
             UPDATE
             SET
             bytecode_keccak256 = $2,
             bytecode_without_metadata_keccak256 = $3,
-            verification_info = $4
+            verification_info = $4,
+            bytecode_hash = excluded.bytecode_hash
             "#,
             address.as_bytes(),
             bytecode_keccak256.as_bytes(),
             bytecode_without_metadata_keccak256.as_bytes(),
-            &verification_info_json
+            &verification_info_json,
+            CONTRACT_DEPLOYER_ADDRESS.as_bytes(),
+            VmEvent::DEPLOY_EVENT_SIGNATURE.as_bytes(),
+            address_to_h256(&address).as_bytes(),
         )
         .instrument("save_verification_info#insert")
         .with_arg("id", &id)
@@ -680,6 +705,415 @@ impl ContractVerificationDal<'_, '_> {
         .await
     }
 
+    /// Returns verification info inherited by the specified contract from a fully verified contract
+    /// with the same bytecode hash. Sets [`VerificationInfo::inherited_from`] in the returned info.
+    pub async fn get_inherited_verification_info(
+        &mut self,
+        address: Address,
+    ) -> DalResult<Option<VerificationInfo>> {
+        let info = sqlx::query!(
+            r#"
+            SELECT
+                verified.verification_info
+            FROM
+                contract_verification_inherited_info inherited
+            JOIN contract_verification_info_v2 verified
+                ON verified.initial_contract_addr = inherited.verified_contract_address
+            WHERE
+                inherited.contract_address = $1
+            "#,
+            address.as_bytes(),
+        )
+        .try_map(|row| {
+            serde_json::from_value::<VerificationInfo>(row.verification_info)
+                .decode_column("verification_info")
+        })
+        .instrument("get_inherited_verification_info")
+        .with_arg("address", &address)
+        .fetch_optional(self.storage)
+        .await?;
+
+        // The source contract may have been re-verified with problems after the info was inherited.
+        Ok(info
+            .filter(VerificationInfo::is_perfect_match)
+            .map(|mut info| {
+                info.inherited_from = Some(info.request.req.contract_address);
+                info
+            }))
+    }
+
+    /// Makes all contracts deployed with the same bytecode hash as the specified verified contract inherit
+    /// its verification info. Does nothing if the contract is not fully verified, or its bytecode hash is unknown.
+    /// Returns the number of contracts that have inherited the info.
+    pub async fn inherit_verification_info(
+        &mut self,
+        verified_address: Address,
+    ) -> DalResult<usize> {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO
+            contract_verification_inherited_info (
+                contract_address, verified_contract_address, bytecode_hash
+            )
+            SELECT
+                SUBSTRING(deploy_event.topic4 FROM 13),
+                verified.initial_contract_addr,
+                verified.bytecode_hash
+            FROM
+                contract_verification_info_v2 verified
+            JOIN events deploy_event
+                ON
+                    deploy_event.topic3 = verified.bytecode_hash
+                    AND deploy_event.address = $2
+                    AND deploy_event.topic1 = $3
+            WHERE
+                verified.initial_contract_addr = $1
+                AND verified.verification_info ->> 'verificationLevel' = 'full'
+                AND SUBSTRING(deploy_event.topic4 FROM 13) != verified.initial_contract_addr
+            ON CONFLICT (contract_address) DO NOTHING
+            "#,
+            verified_address.as_bytes(),
+            CONTRACT_DEPLOYER_ADDRESS.as_bytes(),
+            VmEvent::DEPLOY_EVENT_SIGNATURE.as_bytes(),
+        )
+        .instrument("inherit_verification_info")
+        .with_arg("verified_address", &verified_address)
+        .execute(self.storage)
+        .await?;
+        Ok(result.rows_affected() as usize)
+    }
+
+    /// Makes contracts deployed in the specified L2 blocks inherit verification info from fully verified contracts
+    /// with the same bytecode hash. Returns the number of contracts that have inherited the info.
+    pub async fn inherit_verification_info_for_l2_blocks(
+        &mut self,
+        l2_blocks: RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<usize> {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO
+            contract_verification_inherited_info (
+                contract_address, verified_contract_address, bytecode_hash
+            )
+            SELECT DISTINCT
+            ON (deployed.address)
+                deployed.address,
+                verified.initial_contract_addr,
+                verified.bytecode_hash
+            FROM
+                (
+                    SELECT
+                        SUBSTRING(topic4 FROM 13) AS address,
+                        topic3 AS bytecode_hash
+                    FROM
+                        events
+                    WHERE
+                        miniblock_number BETWEEN $1 AND $2
+                        AND address = $3
+                        AND topic1 = $4
+                ) deployed
+            JOIN contract_verification_info_v2 verified
+                ON verified.bytecode_hash = deployed.bytecode_hash
+            WHERE
+                verified.verification_info ->> 'verificationLevel' = 'full'
+                AND deployed.address != verified.initial_contract_addr
+            ORDER BY
+                deployed.address,
+                verified.created_at
+            ON CONFLICT (contract_address) DO NOTHING
+            "#,
+            i64::from(l2_blocks.start().0),
+            i64::from(l2_blocks.end().0),
+            CONTRACT_DEPLOYER_ADDRESS.as_bytes(),
+            VmEvent::DEPLOY_EVENT_SIGNATURE.as_bytes(),
+        )
+        .instrument("inherit_verification_info_for_l2_blocks")
+        .with_arg("l2_blocks", &l2_blocks)
+        .execute(self.storage)
+        .await?;
+        Ok(result.rows_affected() as usize)
+    }
+
+    /// Fills bytecode hashes for verified contracts that don't have them (e.g., ones verified before bytecode hashes
+    /// were persisted). Returns the number of updated contracts.
+    pub async fn fill_missing_verified_bytecode_hashes(&mut self) -> DalResult<usize> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE contract_verification_info_v2
+            SET
+                bytecode_hash = deploy_event.topic3
+            FROM
+                events deploy_event
+            WHERE
+                contract_verification_info_v2.bytecode_hash IS NULL
+                AND deploy_event.address = $1
+                AND deploy_event.topic1 = $2
+                AND deploy_event.topic4 = '\x000000000000000000000000'::BYTEA
+                || contract_verification_info_v2.initial_contract_addr
+            "#,
+            CONTRACT_DEPLOYER_ADDRESS.as_bytes(),
+            VmEvent::DEPLOY_EVENT_SIGNATURE.as_bytes(),
+        )
+        .instrument("fill_missing_verified_bytecode_hashes")
+        .execute(self.storage)
+        .await?;
+        Ok(result.rows_affected() as usize)
+    }
+
+    /// Returns the last L2 block processed by [`Self::inherit_verification_info_for_l2_blocks()`], as recorded
+    /// by [`Self::set_last_inheritance_l2_block()`].
+    pub async fn get_last_inheritance_l2_block(&mut self) -> DalResult<Option<L2BlockNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                last_processed_l2_block
+            FROM
+                contract_verification_inheritance_progress
+            "#
+        )
+        .instrument("get_last_inheritance_l2_block")
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(row.map(|row| L2BlockNumber(row.last_processed_l2_block as u32)))
+    }
+
+    pub async fn set_last_inheritance_l2_block(
+        &mut self,
+        l2_block_number: L2BlockNumber,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            contract_verification_inheritance_progress (id, last_processed_l2_block, updated_at)
+            VALUES
+            (TRUE, $1, NOW())
+            ON CONFLICT (id) DO
+            UPDATE
+            SET
+            last_processed_l2_block = excluded.last_processed_l2_block,
+            updated_at = NOW()
+            "#,
+            i64::from(l2_block_number.0),
+        )
+        .instrument("set_last_inheritance_l2_block")
+        .with_arg("l2_block_number", &l2_block_number)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the latest values written to the standard proxy storage slots in the specified L2 blocks,
+    /// together with the L2 block of the write. Only storage logs with known key preimages are considered.
+    pub async fn get_proxy_slot_updates(
+        &mut self,
+        l2_blocks: RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<Vec<(Address, ProxyKind, H256, L2BlockNumber)>> {
+        let slots: Vec<_> = ProxyKind::ALL
+            .iter()
+            .map(|kind| kind.storage_slot().as_bytes().to_vec())
+            .collect();
+        sqlx::query!(
+            r#"
+            SELECT DISTINCT
+            ON (address, key)
+                address AS "address!",
+                key AS "key!",
+                value,
+                miniblock_number
+            FROM
+                storage_logs
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+                AND key = ANY($3)
+                AND address IS NOT NULL
+            ORDER BY
+                address,
+                key,
+                miniblock_number DESC,
+                operation_number DESC
+            "#,
+            i64::from(l2_blocks.start().0),
+            i64::from(l2_blocks.end().0),
+            &slots,
+        )
+        .try_map(|row| {
+            let kind = ProxyKind::from_storage_slot(H256::from_slice(&row.key))
+                .ok_or("unexpected storage slot")
+                .decode_column("key")?;
+            Ok((
+                Address::from_slice(&row.address),
+                kind,
+                H256::from_slice(&row.value),
+                L2BlockNumber(row.miniblock_number as u32),
+            ))
+        })
+        .instrument("get_proxy_slot_updates")
+        .with_arg("l2_blocks", &l2_blocks)
+        .fetch_all(self.storage)
+        .await
+    }
+
+    /// Inserts or updates links for the specified proxy contracts. If multiple links are specified for the same proxy
+    /// (e.g., several proxy slots were written in the processed L2 blocks), only the link from the latest L2 block is saved.
+    pub async fn save_proxy_links(&mut self, links: &[ProxyLink]) -> DalResult<()> {
+        // Postgres doesn't allow to update the same row twice in a single `INSERT ... ON CONFLICT` statement.
+        let mut latest_links = HashMap::<_, &ProxyLink>::with_capacity(links.len());
+        for link in links {
+            latest_links
+                .entry(link.proxy_address)
+                .and_modify(|latest_link| {
+                    if link.l2_block_number > latest_link.l2_block_number {
+                        *latest_link = link;
+                    }
+                })
+                .or_insert(link);
+        }
+        let links: Vec<_> = latest_links.into_values().collect();
+
+        let proxy_addresses: Vec<_> = links
+            .iter()
+            .map(|link| link.proxy_address.as_bytes().to_vec())
+            .collect();
+        let kinds: Vec<_> = links.iter().map(|link| link.kind.as_str()).collect();
+        let target_addresses: Vec<_> = links
+            .iter()
+            .map(|link| link.target_address.as_bytes().to_vec())
+            .collect();
+        let l2_block_numbers: Vec<_> = links
+            .iter()
+            .map(|link| i64::from(link.l2_block_number.0))
+            .collect();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            contract_verification_proxies (
+                proxy_address, proxy_kind, target_address, l2_block_number, created_at, updated_at
+            )
+            SELECT
+                u.proxy_address,
+                u.proxy_kind,
+                u.target_address,
+                u.l2_block_number,
+                NOW(),
+                NOW()
+            FROM
+                UNNEST($1::BYTEA [], $2::TEXT [], $3::BYTEA [], $4::BIGINT [])
+                AS u (proxy_address, proxy_kind, target_address, l2_block_number)
+            ON CONFLICT (proxy_address) DO
+            UPDATE
+            SET
+            proxy_kind = excluded.proxy_kind,
+            target_address = excluded.target_address,
+            l2_block_number = excluded.l2_block_number,
+            updated_at = NOW()
+            "#,
+            &proxy_addresses,
+            &kinds as &[&str],
+            &target_addresses,
+            &l2_block_numbers,
+        )
+        .instrument("save_proxy_links")
+        .with_arg("links.len", &links.len())
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Removes the link for the specified proxy contract if it has the specified kind (e.g., if the implementation
+    /// address was reset).
+    pub async fn remove_proxy_link(
+        &mut self,
+        proxy_address: Address,
+        kind: ProxyKind,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM contract_verification_proxies
+            WHERE
+                proxy_address = $1
+                AND proxy_kind = $2
+            "#,
+            proxy_address.as_bytes(),
+            kind.as_str(),
+        )
+        .instrument("remove_proxy_link")
+        .with_arg("proxy_address", &proxy_address)
+        .with_arg("kind", &kind)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_proxy_link(&mut self, proxy_address: Address) -> DalResult<Option<ProxyLink>> {
+        sqlx::query!(
+            r#"
+            SELECT
+                proxy_kind,
+                target_address,
+                l2_block_number
+            FROM
+                contract_verification_proxies
+            WHERE
+                proxy_address = $1
+            "#,
+            proxy_address.as_bytes(),
+        )
+        .try_map(|row| {
+            Ok(ProxyLink {
+                proxy_address,
+                kind: row.proxy_kind.parse().decode_column("proxy_kind")?,
+                target_address: Address::from_slice(&row.target_address),
+                l2_block_number: L2BlockNumber(row.l2_block_number as u32),
+            })
+        })
+        .instrument("get_proxy_link")
+        .with_arg("proxy_address", &proxy_address)
+        .fetch_optional(self.storage)
+        .await
+    }
+
+    /// Returns the last L2 block processed by proxy tracking, as recorded by [`Self::set_last_proxy_tracking_l2_block()`].
+    pub async fn get_last_proxy_tracking_l2_block(&mut self) -> DalResult<Option<L2BlockNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                last_processed_l2_block
+            FROM
+                contract_verification_proxy_tracking_progress
+            "#
+        )
+        .instrument("get_last_proxy_tracking_l2_block")
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(row.map(|row| L2BlockNumber(row.last_processed_l2_block as u32)))
+    }
+
+    pub async fn set_last_proxy_tracking_l2_block(
+        &mut self,
+        l2_block_number: L2BlockNumber,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            contract_verification_proxy_tracking_progress (id, last_processed_l2_block, updated_at)
+            VALUES
+            (TRUE, $1, NOW())
+            ON CONFLICT (id) DO
+            UPDATE
+            SET
+            last_processed_l2_block = excluded.last_processed_l2_block,
+            updated_at = NOW()
+            "#,
+            i64::from(l2_block_number.0),
+        )
+        .instrument("set_last_proxy_tracking_l2_block")
+        .with_arg("l2_block_number", &l2_block_number)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
     /// Checks if migration from `contracts_verification_info` to `contract_verification_info_v2` is performed
     /// by checking if the latter has more or equal number of rows.
     pub async fn is_verification_info_migration_performed(&mut self) -> DalResult<bool> {
@@ -847,9 +1281,12 @@ mod tests {
 
     use zksync_types::{
         bytecode::BytecodeHash,
-        contract_verification::api::{CompilerVersions, SourceCodeData},
+        contract_verification::api::{
+            CompilationArtifacts, CompilerVersions, SourceCodeData, VerificationLevel,
+        },
         tx::IncludedTxLocation,
-        Execute, L1BatchNumber, L2BlockNumber, ProtocolVersion,
+        AccountTreeId, Execute, L1BatchNumber, L2BlockNumber, ProtocolVersion, StorageKey,
+        StorageLog,
     };
     use zksync_vm_interface::{tracer::ValidationTraces, TransactionExecutionMetrics};
 
@@ -983,6 +1420,331 @@ mod tests {
         assert!(maybe_req.is_none());
     }
 
+    async fn save_deploy_events(
+        conn: &mut Connection<'_, Core>,
+        l2_block_number: L2BlockNumber,
+        deployments: &[(Address, H256)],
+    ) {
+        conn.blocks_dal()
+            .insert_l2_block(&create_l2_block_header(l2_block_number.0))
+            .await
+            .unwrap();
+        let deploy_events: Vec<_> = deployments
+            .iter()
+            .map(|(address, bytecode_hash)| VmEvent {
+                location: (L1BatchNumber(0), 0),
+                address: CONTRACT_DEPLOYER_ADDRESS,
+                indexed_topics: vec![
+                    VmEvent::DEPLOY_EVENT_SIGNATURE,
+                    H256::zero(),
+                    *bytecode_hash,
+                    address_to_h256(address),
+                ],
+                value: vec![],
+            })
+            .collect();
+        let location = IncludedTxLocation {
+            tx_hash: H256::repeat_byte(l2_block_number.0 as u8),
+            tx_index_in_l2_block: 0,
+        };
+        conn.events_dal()
+            .save_events(
+                l2_block_number,
+                &[(location, deploy_events.iter().collect())],
+            )
+            .await
+            .unwrap();
+    }
+
+    fn mock_verification_info(address: Address) -> VerificationInfo {
+        VerificationInfo {
+            request: VerificationRequest {
+                id: 1,
+                req: VerificationIncomingRequest {
+                    contract_address: address,
+                    source_code_data: SourceCodeData::SolSingleFile("contract Test {}".to_owned()),
+                    contract_name: "Test".to_owned(),
+                    compiler_versions: CompilerVersions::Solc {
+                        compiler_zksolc_version: Some("1.5.7".to_owned()),
+                        compiler_solc_version: "0.8.27".to_owned(),
+                    },
+                    optimization_used: true,
+                    optimizer_mode: None,
+                    constructor_arguments: web3::Bytes::default(),
+                    is_system: false,
+                    force_evmla: false,
+                    evm_specific: Default::default(),
+                },
+            },
+            artifacts: CompilationArtifacts {
+                bytecode: vec![0; 32],
+                deployed_bytecode: None,
+                abi: serde_json::json!([]),
+            },
+            verified_at: Default::default(),
+            verification_level: Some(VerificationLevel::Full),
+            verification_problems: vec![],
+            inherited_from: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn inheriting_verification_info() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+
+        let bytecode_hash = H256::repeat_byte(1);
+        let verified_address = Address::repeat_byte(1);
+        let identical_address = Address::repeat_byte(2);
+        let other_address = Address::repeat_byte(3);
+        save_deploy_events(
+            &mut conn,
+            L2BlockNumber(0),
+            &[
+                (verified_address, bytecode_hash),
+                (identical_address, bytecode_hash),
+                (other_address, H256::repeat_byte(2)),
+            ],
+        )
+        .await;
+
+        conn.contract_verification_dal()
+            .save_verification_info(
+                mock_verification_info(verified_address),
+                H256::repeat_byte(0x11),
+                H256::repeat_byte(0x22),
+            )
+            .await
+            .unwrap();
+        let inherited_count = conn
+            .contract_verification_dal()
+            .inherit_verification_info(verified_address)
+            .await
+            .unwrap();
+        assert_eq!(inherited_count, 1);
+
+        let info = conn
+            .contract_verification_dal()
+            .get_inherited_verification_info(identical_address)
+            .await
+            .unwrap()
+            .expect("no inherited info");
+        assert_eq!(info.request.req.contract_address, verified_address);
+        assert_eq!(info.inherited_from, Some(verified_address));
+        for address in [verified_address, other_address] {
+            let info = conn
+                .contract_verification_dal()
+                .get_inherited_verification_info(address)
+                .await
+                .unwrap();
+            assert!(info.is_none(), "{info:?}");
+        }
+
+        // Process a new deployment with the same bytecode hash.
+        let new_address = Address::repeat_byte(4);
+        save_deploy_events(&mut conn, L2BlockNumber(1), &[(new_address, bytecode_hash)]).await;
+        let inherited_count = conn
+            .contract_verification_dal()
+            .inherit_verification_info_for_l2_blocks(L2BlockNumber(1)..=L2BlockNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(inherited_count, 1);
+        let info = conn
+            .contract_verification_dal()
+            .get_inherited_verification_info(new_address)
+            .await
+            .unwrap()
+            .expect("no inherited info");
+        assert_eq!(info.inherited_from, Some(verified_address));
+
+        // Info should not be inherited from a partially verified contract.
+        let mut partial_info = mock_verification_info(verified_address);
+        partial_info.mark_as_partial_match();
+        conn.contract_verification_dal()
+            .save_verification_info(
+                partial_info,
+                H256::repeat_byte(0x11),
+                H256::repeat_byte(0x22),
+            )
+            .await
+            .unwrap();
+        let info = conn
+            .contract_verification_dal()
+            .get_inherited_verification_info(new_address)
+            .await
+            .unwrap();
+        assert!(info.is_none(), "{info:?}");
+
+        assert_eq!(
+            conn.contract_verification_dal()
+                .get_last_inheritance_l2_block()
+                .await
+                .unwrap(),
+            None
+        );
+        conn.contract_verification_dal()
+            .set_last_inheritance_l2_block(L2BlockNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(
+            conn.contract_verification_dal()
+                .get_last_inheritance_l2_block()
+                .await
+                .unwrap(),
+            Some(L2BlockNumber(1))
+        );
+    }
+
+    #[tokio::test]
+    async fn tracking_proxy_links() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+
+        let proxy_address = Address::repeat_byte(0x23);
+        let beacon_proxy_address = Address::repeat_byte(0x24);
+        let implementation_address = Address::repeat_byte(0x25);
+        let new_implementation_address = Address::repeat_byte(0x26);
+        let slot_log = |address: Address, kind: ProxyKind, target: Address| {
+            StorageLog::new_write_log(
+                StorageKey::new(AccountTreeId::new(address), kind.storage_slot()),
+                address_to_h256(&target),
+            )
+        };
+        let logs = [
+            slot_log(proxy_address, ProxyKind::Eip1967, implementation_address),
+            slot_log(
+                proxy_address,
+                ProxyKind::Eip1967,
+                new_implementation_address,
+            ),
+            slot_log(
+                beacon_proxy_address,
+                ProxyKind::Eip1967Beacon,
+                implementation_address,
+            ),
+            // Unrelated write
+            StorageLog::new_write_log(
+                StorageKey::new(AccountTreeId::new(proxy_address), H256::zero()),
+                H256::repeat_byte(1),
+            ),
+        ];
+        conn.storage_logs_dal()
+            .append_storage_logs(L2BlockNumber(1), &logs)
+            .await
+            .unwrap();
+
+        let mut updates = conn
+            .contract_verification_dal()
+            .get_proxy_slot_updates(L2BlockNumber(0)..=L2BlockNumber(1))
+            .await
+            .unwrap();
+        updates.sort_unstable_by_key(|(address, ..)| *address);
+        assert_eq!(
+            updates,
+            [
+                (
+                    proxy_address,
+                    ProxyKind::Eip1967,
+                    address_to_h256(&new_implementation_address),
+                    L2BlockNumber(1)
+                ),
+                (
+                    beacon_proxy_address,
+                    ProxyKind::Eip1967Beacon,
+                    address_to_h256(&implementation_address),
+                    L2BlockNumber(1)
+                ),
+            ]
+        );
+        let updates = conn
+            .contract_verification_dal()
+            .get_proxy_slot_updates(L2BlockNumber(2)..=L2BlockNumber(10))
+            .await
+            .unwrap();
+        assert!(updates.is_empty(), "{updates:?}");
+
+        let link = ProxyLink {
+            proxy_address,
+            kind: ProxyKind::Eip1967,
+            target_address: implementation_address,
+            l2_block_number: L2BlockNumber(1),
+        };
+        conn.contract_verification_dal()
+            .save_proxy_links(&[link])
+            .await
+            .unwrap();
+        let new_link = ProxyLink {
+            target_address: new_implementation_address,
+            l2_block_number: L2BlockNumber(2),
+            ..link
+        };
+        conn.contract_verification_dal()
+            .save_proxy_links(&[new_link])
+            .await
+            .unwrap();
+        let loaded_link = conn
+            .contract_verification_dal()
+            .get_proxy_link(proxy_address)
+            .await
+            .unwrap();
+        assert_eq!(loaded_link, Some(new_link));
+
+        // The link should not be removed if the kind doesn't match.
+        conn.contract_verification_dal()
+            .remove_proxy_link(proxy_address, ProxyKind::ZeppelinOs)
+            .await
+            .unwrap();
+        let loaded_link = conn
+            .contract_verification_dal()
+            .get_proxy_link(proxy_address)
+            .await
+            .unwrap();
+        assert_eq!(loaded_link, Some(new_link));
+        conn.contract_verification_dal()
+            .remove_proxy_link(proxy_address, ProxyKind::Eip1967)
+            .await
+            .unwrap();
+        let loaded_link = conn
+            .contract_verification_dal()
+            .get_proxy_link(proxy_address)
+            .await
+            .unwrap();
+        assert_eq!(loaded_link, None);
+
+        // Multiple links for the same proxy in a single call (e.g., if several proxy slots were written).
+        let other_proxy_address = Address::repeat_byte(0x44);
+        let beacon_link = ProxyLink {
+            kind: ProxyKind::Eip1967Beacon,
+            target_address: Address::repeat_byte(0x55),
+            l2_block_number: L2BlockNumber(3),
+            ..new_link
+        };
+        let other_link = ProxyLink {
+            proxy_address: other_proxy_address,
+            ..link
+        };
+        conn.contract_verification_dal()
+            .save_proxy_links(&[beacon_link, other_link, new_link])
+            .await
+            .unwrap();
+        let loaded_link = conn
+            .contract_verification_dal()
+            .get_proxy_link(proxy_address)
+            .await
+            .unwrap();
+        assert_eq!(loaded_link, Some(beacon_link));
+        let loaded_link = conn
+            .contract_verification_dal()
+            .get_proxy_link(other_proxy_address)
+            .await
+            .unwrap();
+        assert_eq!(loaded_link, Some(other_link));
+    }
+
     #[tokio::test]
     async fn working_with_verification_requests() {
         test_working_with_verification_requests(None).await;
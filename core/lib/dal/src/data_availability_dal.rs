@@ -6,12 +6,16 @@ use zksync_db_connection::{
 use zksync_types::{
     commitment::PubdataType,
     l2_to_l1_log::L2ToL1Log,
-    pubdata_da::{DataAvailabilityBlob, DataAvailabilityDetails},
-    Address, L1BatchNumber,
+    pubdata_da::{DataAvailabilityBlob, DataAvailabilityDetails, DataAvailabilityLayer},
+    Address, L1BatchNumber, U256,
 };
 
 use crate::{
-    models::storage_data_availability::{L1BatchDA, StorageDABlob, StorageDADetails},
+    models::{
+        bigdecimal_to_u256,
+        storage_data_availability::{L1BatchDA, StorageDABlob, StorageDADetails},
+        u256_to_big_decimal,
+    },
     Core,
 };
 
@@ -103,6 +107,136 @@ impl DataAvailabilityDal<'_, '_> {
         Ok(())
     }
 
+    /// Records the DA layer holding the blob for the given L1 batch, and the ID of the blob copy
+    /// in the secondary layer (if any). Must be called after [`Self::insert_l1_batch_da()`].
+    pub async fn save_l1_batch_da_layer(
+        &mut self,
+        number: L1BatchNumber,
+        layer: DataAvailabilityLayer,
+        secondary_blob_id: Option<&str>,
+    ) -> DalResult<()> {
+        let instrumentation = Instrumented::new("save_l1_batch_da_layer")
+            .with_arg("number", &number)
+            .with_arg("layer", &layer)
+            .with_arg("secondary_blob_id", &secondary_blob_id);
+        let query = sqlx::query!(
+            r#"
+            UPDATE data_availability
+            SET
+                da_layer = $1,
+                secondary_blob_id = $2,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $3
+            "#,
+            layer.as_str(),
+            secondary_blob_id,
+            i64::from(number.0),
+        );
+        let result = instrumentation
+            .clone()
+            .with(query)
+            .execute(self.storage)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            let err = instrumentation.constraint_error(anyhow::anyhow!(
+                "DA blob for L1 batch #{number} is not present"
+            ));
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// Saves the share of the DA blob fee attributed to the given L1 batch, both in the native units
+    /// of the DA layer and in the base token.
+    pub async fn save_l1_batch_da_fee(
+        &mut self,
+        number: L1BatchNumber,
+        fee: U256,
+        fee_in_base_token: Option<U256>,
+    ) -> DalResult<()> {
+        let instrumentation = Instrumented::new("save_l1_batch_da_fee")
+            .with_arg("number", &number)
+            .with_arg("fee", &fee)
+            .with_arg("fee_in_base_token", &fee_in_base_token);
+        let query = sqlx::query!(
+            r#"
+            UPDATE data_availability
+            SET
+                fee = $1,
+                fee_in_base_token = $2,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $3
+            "#,
+            u256_to_big_decimal(fee),
+            fee_in_base_token.map(u256_to_big_decimal),
+            i64::from(number.0),
+        );
+        let result = instrumentation
+            .clone()
+            .with(query)
+            .execute(self.storage)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            let err = instrumentation.constraint_error(anyhow::anyhow!(
+                "DA blob for L1 batch #{number} is not present"
+            ));
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// Returns the average DA fee per byte of pubdata (in the base token, rounded up) over the latest
+    /// `window` L1 batches with a known fee. Returns `None` if there are no such batches.
+    pub async fn get_recent_da_fee_per_pubdata_byte(
+        &mut self,
+        window: usize,
+    ) -> DalResult<Option<u64>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                SUM(recent_batches.fee_in_base_token) AS "total_fee",
+                SUM(recent_batches.pubdata_size)::BIGINT AS "total_pubdata_size"
+            FROM
+                (
+                    SELECT
+                        data_availability.fee_in_base_token,
+                        OCTET_LENGTH(l1_batches.pubdata_input) AS pubdata_size
+                    FROM
+                        data_availability
+                    JOIN l1_batches ON l1_batches.number = data_availability.l1_batch_number
+                    WHERE
+                        data_availability.fee_in_base_token IS NOT NULL
+                    ORDER BY
+                        data_availability.l1_batch_number DESC
+                    LIMIT
+                        $1
+                ) AS recent_batches
+            "#,
+            window as i64,
+        )
+        .instrument("get_recent_da_fee_per_pubdata_byte")
+        .with_arg("window", &window)
+        .report_latency()
+        .fetch_one(self.storage)
+        .await?;
+
+        let (Some(total_fee), Some(total_pubdata_size)) = (row.total_fee, row.total_pubdata_size)
+        else {
+            return Ok(None);
+        };
+        if total_pubdata_size <= 0 {
+            return Ok(None);
+        }
+        let total_fee = bigdecimal_to_u256(total_fee);
+        let total_pubdata_size = U256::from(total_pubdata_size as u64);
+        let fee_per_byte = (total_fee + total_pubdata_size - 1) / total_pubdata_size;
+        Ok(Some(fee_per_byte.try_into().unwrap_or(u64::MAX)))
+    }
+
     /// Saves the inclusion data for the given L1 batch. If the inclusion data is already present,
     /// verifies that it matches the one provided in the function arguments
     /// (meaning that the inclusion data corresponds to the same DA blob)
@@ -167,6 +301,35 @@ impl DataAvailabilityDal<'_, '_> {
         Ok(())
     }
 
+    /// Saves the inclusion data for all L1 batches packed into the blob with the given ID which don't have
+    /// inclusion data yet. Returns the number of updated L1 batches.
+    pub async fn save_inclusion_data_for_blob(
+        &mut self,
+        blob_id: &str,
+        da_inclusion_data: &[u8],
+    ) -> DalResult<u64> {
+        let update_result = sqlx::query!(
+            r#"
+            UPDATE data_availability
+            SET
+                inclusion_data = $1,
+                updated_at = NOW()
+            WHERE
+                blob_id = $2
+                AND inclusion_data IS NULL
+            "#,
+            da_inclusion_data,
+            blob_id,
+        )
+        .instrument("save_inclusion_data_for_blob")
+        .with_arg("blob_id", &blob_id)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+
+        Ok(update_result.rows_affected())
+    }
+
     /// Assumes that the L1 batches are sorted by number, and returns the first one that is ready for DA dispatch.
     pub async fn get_first_da_blob_awaiting_inclusion(
         &mut self,
@@ -177,6 +340,7 @@ impl DataAvailabilityDal<'_, '_> {
             SELECT
                 l1_batch_number,
                 blob_id,
+                da_layer,
                 inclusion_data,
                 sent_at
             FROM
@@ -258,7 +422,11 @@ impl DataAvailabilityDal<'_, '_> {
                 client_type,
                 inclusion_data,
                 sent_at,
-                l2_da_validator_address
+                l2_da_validator_address,
+                da_layer,
+                secondary_blob_id,
+                fee,
+                fee_in_base_token
             FROM
                 data_availability
             WHERE
@@ -72,7 +72,7 @@ impl EventsWeb3Dal<'_, '_> {
         Ok(log.map(|row| L2BlockNumber(row.get::<i64, _>("miniblock_number") as u32)))
     }
 
-    /// Returns logs for given filter.
+    /// Returns logs for given filter. Logs retained after hard pruning (see `RetentionRules`) are returned as well.
     #[allow(clippy::type_complexity)]
     pub async fn get_logs(&mut self, filter: GetLogsFilter, limit: usize) -> DalResult<Vec<Log>> {
         let (where_sql, arg_index) = self.build_get_logs_where_clause(&filter);
@@ -88,10 +88,16 @@ impl EventsWeb3Dal<'_, '_> {
                 ORDER BY miniblock_number ASC, event_index_in_block ASC
                 LIMIT ${}
             )
-            SELECT miniblocks.hash as "block_hash", miniblocks.l1_batch_number as "l1_batch_number",
-                miniblocks.timestamp as block_timestamp, events_select.*
+            SELECT
+                COALESCE(miniblocks.hash, retained.hash) as "block_hash",
+                COALESCE(miniblocks.l1_batch_number, retained.l1_batch_number) as "l1_batch_number",
+                COALESCE(miniblocks.timestamp, retained.timestamp) as block_timestamp,
+                events_select.*
             FROM events_select
-            INNER JOIN miniblocks ON events_select.miniblock_number = miniblocks.number
+            LEFT JOIN miniblocks ON events_select.miniblock_number = miniblocks.number
+            LEFT JOIN pruning_retained_miniblocks retained
+                ON events_select.miniblock_number = retained.number
+            WHERE miniblocks.number IS NOT NULL OR retained.number IS NOT NULL
             ORDER BY miniblock_number ASC, event_index_in_block ASC
             "#,
             where_sql, arg_index
@@ -0,0 +1,703 @@
+#![doc = include_str!("../doc/ExternalProverDal.md")]
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use zksync_db_connection::{
+    connection::Connection, error::DalResult, instrument::InstrumentExt,
+    utils::pg_interval_from_duration,
+};
+use zksync_types::{L1BatchNumber, H256};
+
+use crate::{Core, CoreDal};
+
+#[derive(Debug)]
+pub struct ExternalProverDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+/// External prover allowed to claim batches via the external proof integration API.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExternalProver {
+    pub id: i64,
+    pub name: String,
+    pub is_active: bool,
+}
+
+/// Batch leased to an external prover.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExternalProofLease {
+    pub l1_batch_number: L1BatchNumber,
+    pub prover_id: i64,
+    pub lease_expires_at: DateTime<Utc>,
+}
+
+/// Statistics of an external prover.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExternalProverStats {
+    pub name: String,
+    pub claimed_batches: u64,
+    pub accepted_proofs: u64,
+    pub rejected_proofs: u64,
+    pub released_batches: u64,
+    pub expired_leases: u64,
+    /// Number of batches currently leased to the prover.
+    pub active_leases: u64,
+    /// Total time between claiming batches and submitting accepted proofs for them.
+    pub proving_time: Duration,
+}
+
+impl ExternalProverDal<'_, '_> {
+    pub async fn insert_prover(&mut self, name: &str, api_key_hash: H256) -> DalResult<i64> {
+        sqlx::query_scalar!(
+            r#"
+            INSERT INTO
+            external_provers (name, api_key_hash, created_at, updated_at)
+            VALUES
+            ($1, $2, NOW(), NOW())
+            RETURNING
+            id
+            "#,
+            name,
+            api_key_hash.as_bytes()
+        )
+        .instrument("insert_prover")
+        .with_arg("name", &name)
+        .fetch_one(self.storage)
+        .await
+    }
+
+    pub async fn get_prover_by_api_key_hash(
+        &mut self,
+        api_key_hash: H256,
+    ) -> DalResult<Option<ExternalProver>> {
+        let prover = sqlx::query!(
+            r#"
+            SELECT
+                id,
+                name,
+                is_active
+            FROM
+                external_provers
+            WHERE
+                api_key_hash = $1
+            "#,
+            api_key_hash.as_bytes()
+        )
+        .instrument("get_prover_by_api_key_hash")
+        .fetch_optional(self.storage)
+        .await?
+        .map(|row| ExternalProver {
+            id: row.id,
+            name: row.name,
+            is_active: row.is_active,
+        });
+
+        Ok(prover)
+    }
+
+    pub async fn set_prover_active(&mut self, prover_id: i64, is_active: bool) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE external_provers
+            SET
+                is_active = $2,
+                updated_at = NOW()
+            WHERE
+                id = $1
+            "#,
+            prover_id,
+            is_active
+        )
+        .instrument("set_prover_active")
+        .with_arg("prover_id", &prover_id)
+        .with_arg("is_active", &is_active)
+        .execute(self.storage)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Leases the oldest batch ready for proving to the prover. Besides unpicked batches, batches
+    /// with expired leases are reassigned, unless they were picked by the prover gateway after
+    /// the lease has expired.
+    ///
+    /// The batch is marked as picked by a prover in `proof_generation_details`, so that it's not
+    /// picked by the prover gateway while the lease is active.
+    pub async fn claim_batch(
+        &mut self,
+        prover_id: i64,
+        lease_duration: Duration,
+    ) -> DalResult<Option<ExternalProofLease>> {
+        let lease_duration = pg_interval_from_duration(lease_duration);
+        let mut transaction = self.storage.start_transaction().await?;
+
+        let Some(batch) = sqlx::query!(
+            r#"
+            SELECT
+                p.l1_batch_number,
+                leases.prover_id AS "previous_prover_id?"
+            FROM
+                proof_generation_details p
+            LEFT JOIN l1_batches ON p.l1_batch_number = l1_batches.number
+            LEFT JOIN
+                external_proof_leases leases
+                ON p.l1_batch_number = leases.l1_batch_number
+            WHERE
+                p.vm_run_data_blob_url IS NOT NULL
+                AND p.proof_gen_data_blob_url IS NOT NULL
+                AND l1_batches.hash IS NOT NULL
+                AND l1_batches.aux_data_hash IS NOT NULL
+                AND l1_batches.meta_parameters_hash IS NOT NULL
+                AND (
+                    p.status = 'unpicked'
+                    OR (
+                        p.status = 'picked_by_prover'
+                        AND leases.lease_expires_at < NOW()
+                        AND p.prover_taken_at <= leases.lease_expires_at
+                    )
+                )
+            ORDER BY
+                p.l1_batch_number ASC
+            LIMIT
+                1
+            FOR UPDATE OF p SKIP LOCKED
+            "#
+        )
+        .instrument("claim_batch#get_batch")
+        .fetch_optional(&mut transaction)
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        sqlx::query!(
+            r#"
+            UPDATE proof_generation_details
+            SET
+                status = 'picked_by_prover',
+                updated_at = NOW(),
+                prover_taken_at = NOW()
+            WHERE
+                l1_batch_number = $1
+            "#,
+            batch.l1_batch_number
+        )
+        .instrument("claim_batch#lock_batch")
+        .with_arg("l1_batch_number", &batch.l1_batch_number)
+        .execute(&mut transaction)
+        .await?;
+
+        let lease_expires_at = sqlx::query_scalar!(
+            r#"
+            INSERT INTO
+            external_proof_leases (
+                l1_batch_number, prover_id, lease_expires_at, created_at, updated_at
+            )
+            VALUES
+            ($1, $2, NOW() + $3::INTERVAL, NOW(), NOW())
+            ON CONFLICT (l1_batch_number) DO
+            UPDATE
+            SET
+            prover_id = $2,
+            lease_expires_at = NOW() + $3::INTERVAL,
+            created_at = NOW(),
+            updated_at = NOW()
+            RETURNING
+            lease_expires_at
+            "#,
+            batch.l1_batch_number,
+            prover_id,
+            &lease_duration
+        )
+        .instrument("claim_batch#insert_lease")
+        .with_arg("l1_batch_number", &batch.l1_batch_number)
+        .with_arg("prover_id", &prover_id)
+        .with_arg("lease_duration", &lease_duration)
+        .fetch_one(&mut transaction)
+        .await?;
+
+        if let Some(previous_prover_id) = batch.previous_prover_id {
+            sqlx::query!(
+                r#"
+                UPDATE external_provers
+                SET
+                    expired_leases = expired_leases + 1,
+                    updated_at = NOW()
+                WHERE
+                    id = $1
+                "#,
+                previous_prover_id
+            )
+            .instrument("claim_batch#count_expired_lease")
+            .with_arg("previous_prover_id", &previous_prover_id)
+            .execute(&mut transaction)
+            .await?;
+        }
+
+        sqlx::query!(
+            r#"
+            UPDATE external_provers
+            SET
+                claimed_batches = claimed_batches + 1,
+                updated_at = NOW()
+            WHERE
+                id = $1
+            "#,
+            prover_id
+        )
+        .instrument("claim_batch#count_claimed_batch")
+        .with_arg("prover_id", &prover_id)
+        .execute(&mut transaction)
+        .await?;
+
+        transaction.commit().await?;
+        Ok(Some(ExternalProofLease {
+            l1_batch_number: L1BatchNumber(batch.l1_batch_number as u32),
+            prover_id,
+            lease_expires_at: lease_expires_at.and_utc(),
+        }))
+    }
+
+    /// Extends an active lease of the batch held by the prover. Returns `None` if the prover
+    /// doesn't hold an active lease of the batch.
+    pub async fn renew_lease(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        prover_id: i64,
+        lease_duration: Duration,
+    ) -> DalResult<Option<ExternalProofLease>> {
+        let lease_duration = pg_interval_from_duration(lease_duration);
+        let lease = sqlx::query!(
+            r#"
+            UPDATE external_proof_leases
+            SET
+                lease_expires_at = NOW() + $3::INTERVAL,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $1
+                AND prover_id = $2
+                AND lease_expires_at > NOW()
+            RETURNING
+            l1_batch_number,
+            prover_id,
+            lease_expires_at
+            "#,
+            i64::from(l1_batch_number.0),
+            prover_id,
+            &lease_duration
+        )
+        .instrument("renew_lease")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("prover_id", &prover_id)
+        .with_arg("lease_duration", &lease_duration)
+        .fetch_optional(self.storage)
+        .await?
+        .map(|row| ExternalProofLease {
+            l1_batch_number: L1BatchNumber(row.l1_batch_number as u32),
+            prover_id: row.prover_id,
+            lease_expires_at: row.lease_expires_at.and_utc(),
+        });
+
+        Ok(lease)
+    }
+
+    pub async fn get_active_lease(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        prover_id: i64,
+    ) -> DalResult<Option<ExternalProofLease>> {
+        let lease = sqlx::query!(
+            r#"
+            SELECT
+                l1_batch_number,
+                prover_id,
+                lease_expires_at
+            FROM
+                external_proof_leases
+            WHERE
+                l1_batch_number = $1
+                AND prover_id = $2
+                AND lease_expires_at > NOW()
+            "#,
+            i64::from(l1_batch_number.0),
+            prover_id
+        )
+        .instrument("get_active_lease")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("prover_id", &prover_id)
+        .fetch_optional(self.storage)
+        .await?
+        .map(|row| ExternalProofLease {
+            l1_batch_number: L1BatchNumber(row.l1_batch_number as u32),
+            prover_id: row.prover_id,
+            lease_expires_at: row.lease_expires_at.and_utc(),
+        });
+
+        Ok(lease)
+    }
+
+    /// Gives up an active lease of the batch held by the prover, making the batch available
+    /// for proving again. Returns `false` if the prover doesn't hold an active lease of the batch.
+    pub async fn release_batch(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        prover_id: i64,
+    ) -> DalResult<bool> {
+        let mut transaction = self.storage.start_transaction().await?;
+        let released = sqlx::query!(
+            r#"
+            DELETE FROM external_proof_leases
+            WHERE
+                l1_batch_number = $1
+                AND prover_id = $2
+                AND lease_expires_at > NOW()
+            "#,
+            i64::from(l1_batch_number.0),
+            prover_id
+        )
+        .instrument("release_batch#delete_lease")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("prover_id", &prover_id)
+        .execute(&mut transaction)
+        .await?
+        .rows_affected()
+            > 0;
+        if !released {
+            return Ok(false);
+        }
+
+        transaction
+            .proof_generation_dal()
+            .unlock_batch(l1_batch_number)
+            .await?;
+        sqlx::query!(
+            r#"
+            UPDATE external_provers
+            SET
+                released_batches = released_batches + 1,
+                updated_at = NOW()
+            WHERE
+                id = $1
+            "#,
+            prover_id
+        )
+        .instrument("release_batch#count_released_batch")
+        .with_arg("prover_id", &prover_id)
+        .execute(&mut transaction)
+        .await?;
+
+        transaction.commit().await?;
+        Ok(true)
+    }
+
+    /// Removes the lease of the batch after a proof submitted by the prover was accepted.
+    /// Must be called in the same transaction as saving the proof in `proof_generation_details`.
+    /// Returns `false` if the batch isn't leased to the prover or the lease has expired.
+    pub async fn mark_proof_accepted(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        prover_id: i64,
+    ) -> DalResult<bool> {
+        let Some(proving_time_sec) = sqlx::query_scalar!(
+            r#"
+            DELETE FROM external_proof_leases
+            WHERE
+                l1_batch_number = $1
+                AND prover_id = $2
+                AND lease_expires_at > NOW()
+            RETURNING
+            EXTRACT(
+                EPOCH
+                FROM
+                NOW() - created_at
+            )::BIGINT AS "proving_time_sec!"
+            "#,
+            i64::from(l1_batch_number.0),
+            prover_id
+        )
+        .instrument("mark_proof_accepted#delete_lease")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("prover_id", &prover_id)
+        .fetch_optional(self.storage)
+        .await?
+        else {
+            return Ok(false);
+        };
+
+        sqlx::query!(
+            r#"
+            UPDATE external_provers
+            SET
+                accepted_proofs = accepted_proofs + 1,
+                proving_time_sec = proving_time_sec + $2,
+                updated_at = NOW()
+            WHERE
+                id = $1
+            "#,
+            prover_id,
+            proving_time_sec
+        )
+        .instrument("mark_proof_accepted#count_accepted_proof")
+        .with_arg("prover_id", &prover_id)
+        .execute(self.storage)
+        .await?;
+
+        Ok(true)
+    }
+
+    pub async fn mark_proof_rejected(&mut self, prover_id: i64) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE external_provers
+            SET
+                rejected_proofs = rejected_proofs + 1,
+                updated_at = NOW()
+            WHERE
+                id = $1
+            "#,
+            prover_id
+        )
+        .instrument("mark_proof_rejected")
+        .with_arg("prover_id", &prover_id)
+        .execute(self.storage)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_prover_stats(
+        &mut self,
+        prover_id: i64,
+    ) -> DalResult<Option<ExternalProverStats>> {
+        let stats = sqlx::query!(
+            r#"
+            SELECT
+                name,
+                claimed_batches,
+                accepted_proofs,
+                rejected_proofs,
+                released_batches,
+                expired_leases,
+                proving_time_sec,
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        external_proof_leases
+                    WHERE
+                        prover_id = external_provers.id
+                        AND lease_expires_at > NOW()
+                ) AS "active_leases!"
+            FROM
+                external_provers
+            WHERE
+                id = $1
+            "#,
+            prover_id
+        )
+        .instrument("get_prover_stats")
+        .with_arg("prover_id", &prover_id)
+        .fetch_optional(self.storage)
+        .await?
+        .map(|row| ExternalProverStats {
+            name: row.name,
+            claimed_batches: row.claimed_batches as u64,
+            accepted_proofs: row.accepted_proofs as u64,
+            rejected_proofs: row.rejected_proofs as u64,
+            released_batches: row.released_batches as u64,
+            expired_leases: row.expired_leases as u64,
+            active_leases: row.active_leases as u64,
+            proving_time: Duration::from_secs(row.proving_time_sec as u64),
+        });
+
+        Ok(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{
+        block::L1BatchTreeData, commitment::L1BatchCommitmentArtifacts, ProtocolVersion,
+    };
+
+    use super::*;
+    use crate::{tests::create_l1_batch_header, ConnectionPool};
+
+    async fn prepare_batch(conn: &mut Connection<'_, Core>, number: L1BatchNumber) {
+        conn.blocks_dal()
+            .insert_mock_l1_batch(&create_l1_batch_header(number.0))
+            .await
+            .unwrap();
+        conn.proof_generation_dal()
+            .insert_proof_generation_details(number)
+            .await
+            .unwrap();
+        conn.proof_generation_dal()
+            .save_vm_runner_artifacts_metadata(number, "vm_run")
+            .await
+            .unwrap();
+        conn.proof_generation_dal()
+            .save_merkle_paths_artifacts_metadata(number, "data")
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .save_l1_batch_tree_data(
+                number,
+                &L1BatchTreeData {
+                    hash: H256::zero(),
+                    rollup_last_leaf_index: 123,
+                },
+            )
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .save_l1_batch_commitment_artifacts(number, &L1BatchCommitmentArtifacts::default())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn external_proof_marketplace_workflow() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        prepare_batch(&mut conn, L1BatchNumber(1)).await;
+        prepare_batch(&mut conn, L1BatchNumber(2)).await;
+
+        let alice = conn
+            .external_prover_dal()
+            .insert_prover("alice", H256::repeat_byte(1))
+            .await
+            .unwrap();
+        let bob = conn
+            .external_prover_dal()
+            .insert_prover("bob", H256::repeat_byte(2))
+            .await
+            .unwrap();
+        let prover = conn
+            .external_prover_dal()
+            .get_prover_by_api_key_hash(H256::repeat_byte(2))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(prover.id, bob);
+        assert!(prover.is_active);
+
+        let lease = conn
+            .external_prover_dal()
+            .claim_batch(alice, Duration::from_secs(3600))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(lease.l1_batch_number, L1BatchNumber(1));
+        // Leased batches are not picked by the prover gateway, even after its processing timeout.
+        let picked_l1_batch = conn
+            .proof_generation_dal()
+            .lock_batch_for_proving(Duration::ZERO)
+            .await
+            .unwrap();
+        assert_eq!(picked_l1_batch, Some(L1BatchNumber(2)));
+        conn.proof_generation_dal()
+            .unlock_batch(L1BatchNumber(2))
+            .await
+            .unwrap();
+
+        // Only the lease holder can renew or release the lease.
+        let renewed = conn
+            .external_prover_dal()
+            .renew_lease(L1BatchNumber(1), bob, Duration::from_secs(3600))
+            .await
+            .unwrap();
+        assert_eq!(renewed, None);
+        assert!(!conn
+            .external_prover_dal()
+            .release_batch(L1BatchNumber(1), bob)
+            .await
+            .unwrap());
+        assert!(conn
+            .external_prover_dal()
+            .release_batch(L1BatchNumber(1), alice)
+            .await
+            .unwrap());
+
+        // Released batch can be claimed by another prover. An expired lease is reassigned.
+        let lease = conn
+            .external_prover_dal()
+            .claim_batch(bob, Duration::ZERO)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(lease.l1_batch_number, L1BatchNumber(1));
+        // A proof for an expired lease is not accepted.
+        assert!(!conn
+            .external_prover_dal()
+            .mark_proof_accepted(L1BatchNumber(1), bob)
+            .await
+            .unwrap());
+        let lease = conn
+            .external_prover_dal()
+            .claim_batch(alice, Duration::from_secs(3600))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(lease.l1_batch_number, L1BatchNumber(1));
+        assert!(conn
+            .external_prover_dal()
+            .get_active_lease(L1BatchNumber(1), alice)
+            .await
+            .unwrap()
+            .is_some());
+
+        conn.external_prover_dal()
+            .mark_proof_rejected(alice)
+            .await
+            .unwrap();
+        assert!(conn
+            .external_prover_dal()
+            .mark_proof_accepted(L1BatchNumber(1), alice)
+            .await
+            .unwrap());
+        conn.proof_generation_dal()
+            .save_proof_artifacts_metadata(L1BatchNumber(1), "proof")
+            .await
+            .unwrap();
+
+        let stats = conn
+            .external_prover_dal()
+            .get_prover_stats(alice)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stats.name, "alice");
+        assert_eq!(stats.claimed_batches, 2);
+        assert_eq!(stats.released_batches, 1);
+        assert_eq!(stats.rejected_proofs, 1);
+        assert_eq!(stats.accepted_proofs, 1);
+        assert_eq!(stats.active_leases, 0);
+        let stats = conn
+            .external_prover_dal()
+            .get_prover_stats(bob)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stats.claimed_batches, 1);
+        assert_eq!(stats.expired_leases, 1);
+
+        let lease = conn
+            .external_prover_dal()
+            .claim_batch(bob, Duration::from_secs(3600))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(lease.l1_batch_number, L1BatchNumber(2));
+        let lease = conn
+            .external_prover_dal()
+            .claim_batch(alice, Duration::from_secs(3600))
+            .await
+            .unwrap();
+        assert_eq!(lease, None);
+    }
+}
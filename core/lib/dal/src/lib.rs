@@ -17,8 +17,9 @@ use crate::{
     custom_genesis_export_dal::CustomGenesisExportDal, data_availability_dal::DataAvailabilityDal,
     eth_sender_dal::EthSenderDal, eth_watcher_dal::EthWatcherDal,
     etherscan_verification_dal::EtherscanVerificationDal, events_dal::EventsDal,
-    events_web3_dal::EventsWeb3Dal, factory_deps_dal::FactoryDepsDal,
-    proof_generation_dal::ProofGenerationDal, protocol_versions_dal::ProtocolVersionsDal,
+    events_web3_dal::EventsWeb3Dal, external_prover_dal::ExternalProverDal,
+    factory_deps_dal::FactoryDepsDal, proof_generation_dal::ProofGenerationDal,
+    protocol_versions_dal::ProtocolVersionsDal,
     protocol_versions_web3_dal::ProtocolVersionsWeb3Dal, pruning_dal::PruningDal,
     snapshot_recovery_dal::SnapshotRecoveryDal, snapshots_creator_dal::SnapshotsCreatorDal,
     snapshots_dal::SnapshotsDal, storage_logs_dal::StorageLogsDal,
@@ -41,6 +42,7 @@ pub mod eth_watcher_dal;
 pub mod etherscan_verification_dal;
 pub mod events_dal;
 pub mod events_web3_dal;
+pub mod external_prover_dal;
 pub mod factory_deps_dal;
 pub mod helpers;
 pub mod metrics;
@@ -120,6 +122,8 @@ where
 
     fn tee_proof_generation_dal(&mut self) -> TeeProofGenerationDal<'_, 'a>;
 
+    fn external_prover_dal(&mut self) -> ExternalProverDal<'_, 'a>;
+
     fn system_dal(&mut self) -> SystemDal<'_, 'a>;
 
     fn snapshots_dal(&mut self) -> SnapshotsDal<'_, 'a>;
@@ -234,6 +238,10 @@ impl<'a> CoreDal<'a> for Connection<'a, Core> {
         TeeProofGenerationDal { storage: self }
     }
 
+    fn external_prover_dal(&mut self) -> ExternalProverDal<'_, 'a> {
+        ExternalProverDal { storage: self }
+    }
+
     fn system_dal(&mut self) -> SystemDal<'_, 'a> {
         SystemDal { storage: self }
     }
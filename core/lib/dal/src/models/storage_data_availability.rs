@@ -5,11 +5,14 @@ use zksync_types::{
     Address, L1BatchNumber,
 };
 
+use crate::{models::bigdecimal_to_u256, BigDecimal};
+
 /// Represents a blob in the data availability layer.
 #[derive(Debug, Clone)]
 pub(crate) struct StorageDABlob {
     pub l1_batch_number: i64,
     pub blob_id: String,
+    pub da_layer: String,
     pub inclusion_data: Option<Vec<u8>>,
     pub sent_at: NaiveDateTime,
 }
@@ -19,6 +22,8 @@ impl From<StorageDABlob> for DataAvailabilityBlob {
         DataAvailabilityBlob {
             l1_batch_number: L1BatchNumber(blob.l1_batch_number as u32),
             blob_id: blob.blob_id,
+            // safe to unwrap because the value in the database is assumed to be always correct
+            layer: blob.da_layer.parse().unwrap(),
             inclusion_data: blob.inclusion_data,
             sent_at: blob.sent_at.and_utc(),
         }
@@ -32,6 +37,10 @@ pub struct StorageDADetails {
     pub inclusion_data: Option<Vec<u8>>,
     pub sent_at: NaiveDateTime,
     pub l2_da_validator_address: Option<Vec<u8>>,
+    pub da_layer: String,
+    pub secondary_blob_id: Option<String>,
+    pub fee: Option<BigDecimal>,
+    pub fee_in_base_token: Option<BigDecimal>,
 }
 
 impl From<StorageDADetails> for DataAvailabilityDetails {
@@ -45,6 +54,10 @@ impl From<StorageDADetails> for DataAvailabilityDetails {
             l2_da_validator: row
                 .l2_da_validator_address
                 .map(|addr| Address::from_slice(addr.as_slice())),
+            layer: row.da_layer.parse().unwrap(),
+            secondary_blob_id: row.secondary_blob_id,
+            fee: row.fee.map(bigdecimal_to_u256),
+            fee_in_base_token: row.fee_in_base_token.map(bigdecimal_to_u256),
         }
     }
 }
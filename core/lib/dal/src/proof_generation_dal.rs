@@ -36,7 +36,9 @@ impl ProofGenerationDal<'_, '_> {
     /// Marks the batch as picked by the prover, preventing it from being picked twice.
     ///
     /// The batch can be unpicked either via a corresponding DAL method, or it is considered
-    /// not picked after `processing_timeout` passes.
+    /// not picked after `processing_timeout` passes. Batches leased to external provers
+    /// (see [`ExternalProverDal`](crate::external_prover_dal::ExternalProverDal)) are not
+    /// considered timed out until their lease expires.
     pub async fn lock_batch_for_proving(
         &mut self,
         processing_timeout: Duration,
@@ -68,6 +70,16 @@ impl ProofGenerationDal<'_, '_> {
                         OR (
                             status = 'picked_by_prover'
                             AND prover_taken_at < NOW() - $1::INTERVAL
+                            AND NOT EXISTS (
+                                SELECT
+                                    1
+                                FROM
+                                    external_proof_leases
+                                WHERE
+                                    external_proof_leases.l1_batch_number
+                                    = proof_generation_details.l1_batch_number
+                                    AND external_proof_leases.lease_expires_at > NOW()
+                            )
                         )
                     ORDER BY
                         l1_batch_number ASC
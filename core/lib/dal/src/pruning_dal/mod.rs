@@ -1,7 +1,7 @@
 use std::ops;
 
 use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
-use zksync_types::{L1BatchNumber, L2BlockNumber, H256};
+use zksync_types::{Address, L1BatchNumber, L2BlockNumber, H256};
 
 use crate::Core;
 
@@ -40,6 +40,39 @@ impl PruningInfo {
     }
 }
 
+/// Rules specifying data that should be retained during hard pruning. By default, no data is retained.
+///
+/// Retained data stays available via `eth_getLogs` and transaction receipt lookups; minimal headers
+/// of the L2 blocks containing it are persisted so that the block hash / timestamp can be returned.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RetentionRules {
+    /// Events emitted by these contracts are retained.
+    pub event_addresses: Vec<Address>,
+    /// Transactions initiated by or sent to these accounts are retained together with the data
+    /// necessary to serve their receipts (events and L2-to-L1 logs).
+    pub transaction_accounts: Vec<Address>,
+    /// Whether call traces of the retained transactions are retained as well.
+    pub retain_call_traces: bool,
+}
+
+impl RetentionRules {
+    /// Returns `true` if the rules do not retain any data.
+    pub fn is_empty(&self) -> bool {
+        self.event_addresses.is_empty() && self.transaction_accounts.is_empty()
+    }
+
+    fn event_addresses_bytes(&self) -> Vec<&[u8]> {
+        self.event_addresses.iter().map(Address::as_bytes).collect()
+    }
+
+    fn transaction_accounts_bytes(&self) -> Vec<&[u8]> {
+        self.transaction_accounts
+            .iter()
+            .map(Address::as_bytes)
+            .collect()
+    }
+}
+
 /// Statistics about a single hard pruning iteration.
 #[derive(Debug, Default)]
 pub struct HardPruningStats {
@@ -49,6 +82,8 @@ pub struct HardPruningStats {
     pub deleted_events: u64,
     pub deleted_call_traces: u64,
     pub deleted_l2_to_l1_logs: u64,
+    /// Number of pruned L2 blocks for which minimal headers were retained because of [`RetentionRules`].
+    pub retained_l2_blocks: u64,
 }
 
 #[derive(Debug)]
@@ -193,6 +228,22 @@ impl PruningDal<'_, '_> {
         &mut self,
         last_l1_batch_to_prune: L1BatchNumber,
         last_l2_block_to_prune: L2BlockNumber,
+    ) -> DalResult<HardPruningStats> {
+        self.hard_prune_batches_range_with_retention(
+            last_l1_batch_to_prune,
+            last_l2_block_to_prune,
+            &RetentionRules::default(),
+        )
+        .await
+    }
+
+    /// Same as [`Self::hard_prune_batches_range()`], but retains data matching the provided `retention_rules`.
+    /// Does not insert pruning logs; the caller is responsible to do this!
+    pub async fn hard_prune_batches_range_with_retention(
+        &mut self,
+        last_l1_batch_to_prune: L1BatchNumber,
+        last_l2_block_to_prune: L2BlockNumber,
+        retention_rules: &RetentionRules,
     ) -> DalResult<HardPruningStats> {
         let row = sqlx::query!(
             r#"
@@ -216,22 +267,27 @@ impl PruningDal<'_, '_> {
         };
 
         let first_l2_block_to_prune = L2BlockNumber(first_l2_block_to_prune as u32);
+        let l2_blocks_to_prune = first_l2_block_to_prune..=last_l2_block_to_prune;
 
+        let retained_l2_blocks = if retention_rules.is_empty() {
+            0
+        } else {
+            self.retain_l2_block_headers(l2_blocks_to_prune.clone(), retention_rules)
+                .await?
+        };
         let deleted_events = self
-            .delete_events(first_l2_block_to_prune..=last_l2_block_to_prune)
+            .delete_events(l2_blocks_to_prune.clone(), retention_rules)
             .await?;
         let deleted_l2_to_l1_logs = self
-            .delete_l2_to_l1_logs(first_l2_block_to_prune..=last_l2_block_to_prune)
+            .delete_l2_to_l1_logs(l2_blocks_to_prune.clone(), retention_rules)
             .await?;
         let deleted_call_traces = self
-            .delete_call_traces(first_l2_block_to_prune..=last_l2_block_to_prune)
+            .delete_call_traces(l2_blocks_to_prune.clone(), retention_rules)
             .await?;
-        self.clear_transaction_fields(first_l2_block_to_prune..=last_l2_block_to_prune)
+        self.clear_transaction_fields(l2_blocks_to_prune.clone(), retention_rules)
             .await?;
 
-        let deleted_storage_logs = self
-            .prune_storage_logs(first_l2_block_to_prune..=last_l2_block_to_prune)
-            .await?;
+        let deleted_storage_logs = self.prune_storage_logs(l2_blocks_to_prune).await?;
         let deleted_l1_batches = self.delete_l1_batches(last_l1_batch_to_prune).await?;
         let deleted_l2_blocks = self.delete_l2_blocks(last_l2_block_to_prune).await?;
 
@@ -242,22 +298,104 @@ impl PruningDal<'_, '_> {
             deleted_l2_to_l1_logs,
             deleted_call_traces,
             deleted_storage_logs,
+            retained_l2_blocks,
         };
         Ok(stats)
     }
 
+    /// Persists minimal headers for L2 blocks in the specified range that contain data retained according to `retention_rules`.
+    async fn retain_l2_block_headers(
+        &mut self,
+        l2_blocks_to_prune: ops::RangeInclusive<L2BlockNumber>,
+        retention_rules: &RetentionRules,
+    ) -> DalResult<u64> {
+        let execution_result = sqlx::query!(
+            r#"
+            INSERT INTO
+            pruning_retained_miniblocks (
+                number,
+                l1_batch_number,
+                hash,
+                timestamp,
+                created_at
+            )
+            SELECT
+                miniblocks.number,
+                miniblocks.l1_batch_number,
+                miniblocks.hash,
+                miniblocks.timestamp,
+                NOW()
+            FROM
+                miniblocks
+            WHERE
+                miniblocks.number BETWEEN $1 AND $2
+                AND miniblocks.l1_batch_number IS NOT NULL
+                AND (
+                    EXISTS (
+                        SELECT
+                            1
+                        FROM
+                            events
+                        WHERE
+                            events.miniblock_number = miniblocks.number
+                            AND events.address = ANY($3)
+                    )
+                    OR EXISTS (
+                        SELECT
+                            1
+                        FROM
+                            transactions
+                        WHERE
+                            transactions.miniblock_number = miniblocks.number
+                            AND (
+                                transactions.initiator_address = ANY($4)
+                                OR transactions.contract_address = ANY($4)
+                            )
+                    )
+                )
+            ON CONFLICT (number) DO NOTHING
+            "#,
+            i64::from(l2_blocks_to_prune.start().0),
+            i64::from(l2_blocks_to_prune.end().0),
+            &retention_rules.event_addresses_bytes() as &[&[u8]],
+            &retention_rules.transaction_accounts_bytes() as &[&[u8]],
+        )
+        .instrument("hard_prune_batches_range#retain_l2_block_headers")
+        .with_arg("l2_blocks_to_prune", &l2_blocks_to_prune)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(execution_result.rows_affected())
+    }
+
     async fn delete_events(
         &mut self,
         l2_blocks_to_prune: ops::RangeInclusive<L2BlockNumber>,
+        retention_rules: &RetentionRules,
     ) -> DalResult<u64> {
         let execution_result = sqlx::query!(
             r#"
             DELETE FROM events
             WHERE
                 miniblock_number BETWEEN $1 AND $2
+                AND address != ALL($3)
+                AND tx_hash NOT IN (
+                    SELECT
+                        hash
+                    FROM
+                        transactions
+                    WHERE
+                        miniblock_number BETWEEN $1 AND $2
+                        AND (
+                            initiator_address = ANY($4)
+                            OR contract_address = ANY($4)
+                        )
+                )
             "#,
             i64::from(l2_blocks_to_prune.start().0),
-            i64::from(l2_blocks_to_prune.end().0)
+            i64::from(l2_blocks_to_prune.end().0),
+            &retention_rules.event_addresses_bytes() as &[&[u8]],
+            &retention_rules.transaction_accounts_bytes() as &[&[u8]],
         )
         .instrument("hard_prune_batches_range#delete_events")
         .with_arg("l2_blocks_to_prune", &l2_blocks_to_prune)
@@ -270,15 +408,30 @@ impl PruningDal<'_, '_> {
     async fn delete_l2_to_l1_logs(
         &mut self,
         l2_blocks_to_prune: ops::RangeInclusive<L2BlockNumber>,
+        retention_rules: &RetentionRules,
     ) -> DalResult<u64> {
+        // L2-to-L1 logs are a part of transaction receipts, so they are retained for retained transactions.
         let execution_result = sqlx::query!(
             r#"
             DELETE FROM l2_to_l1_logs
             WHERE
                 miniblock_number BETWEEN $1 AND $2
+                AND tx_hash NOT IN (
+                    SELECT
+                        hash
+                    FROM
+                        transactions
+                    WHERE
+                        miniblock_number BETWEEN $1 AND $2
+                        AND (
+                            initiator_address = ANY($3)
+                            OR contract_address = ANY($3)
+                        )
+                )
             "#,
             i64::from(l2_blocks_to_prune.start().0),
-            i64::from(l2_blocks_to_prune.end().0)
+            i64::from(l2_blocks_to_prune.end().0),
+            &retention_rules.transaction_accounts_bytes() as &[&[u8]],
         )
         .instrument("hard_prune_batches_range#delete_l2_to_l1_logs")
         .with_arg("l2_blocks_to_prune", &l2_blocks_to_prune)
@@ -294,7 +447,13 @@ impl PruningDal<'_, '_> {
     async fn delete_call_traces(
         &mut self,
         l2_blocks_to_prune: ops::RangeInclusive<L2BlockNumber>,
+        retention_rules: &RetentionRules,
     ) -> DalResult<u64> {
+        let retained_accounts = if retention_rules.retain_call_traces {
+            retention_rules.transaction_accounts_bytes()
+        } else {
+            vec![]
+        };
         let execution_result = sqlx::query!(
             r#"
             DELETE FROM call_traces
@@ -306,10 +465,15 @@ impl PruningDal<'_, '_> {
                         transactions
                     WHERE
                         miniblock_number BETWEEN $1 AND $2
+                        AND (
+                            initiator_address = ANY($3)
+                            OR contract_address = ANY($3)
+                        ) IS NOT TRUE
                 )
             "#,
             i64::from(l2_blocks_to_prune.start().0),
-            i64::from(l2_blocks_to_prune.end().0)
+            i64::from(l2_blocks_to_prune.end().0),
+            &retained_accounts as &[&[u8]],
         )
         .instrument("hard_prune_batches_range#delete_call_traces")
         .with_arg("l2_blocks_to_prune", &l2_blocks_to_prune)
@@ -327,9 +491,12 @@ impl PruningDal<'_, '_> {
     //   to a certain L1 batch / L2 block, and thus do naturally check pruning.
     // - `data`: used by `TransactionsWeb3Dal` queries, which explicitly check whether it was pruned.
     // - `execution_info`: not used in queries.
+    //
+    // Transactions retained according to `retention_rules` are not touched, so that their receipts can still be served.
     async fn clear_transaction_fields(
         &mut self,
         l2_blocks_to_prune: ops::RangeInclusive<L2BlockNumber>,
+        retention_rules: &RetentionRules,
     ) -> DalResult<u64> {
         let execution_result = sqlx::query!(
             r#"
@@ -342,9 +509,14 @@ impl PruningDal<'_, '_> {
             WHERE
                 miniblock_number BETWEEN $1 AND $2
                 AND upgrade_id IS NULL
+                AND (
+                    initiator_address = ANY($3)
+                    OR contract_address = ANY($3)
+                ) IS NOT TRUE
             "#,
             i64::from(l2_blocks_to_prune.start().0),
-            i64::from(l2_blocks_to_prune.end().0)
+            i64::from(l2_blocks_to_prune.end().0),
+            &retention_rules.transaction_accounts_bytes() as &[&[u8]],
         )
         .instrument("hard_prune_batches_range#clear_transaction_fields")
         .with_arg("l2_blocks_to_prune", &l2_blocks_to_prune)
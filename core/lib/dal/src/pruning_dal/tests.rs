@@ -2,10 +2,10 @@ use std::ops;
 
 use zksync_db_connection::connection::Connection;
 use zksync_types::{
-    tx::IncludedTxLocation, AccountTreeId, L1BatchNumber, L2BlockNumber, L2ChainId,
-    ProtocolVersion, ProtocolVersionId, StorageKey, StorageLog, H256,
+    api::GetLogsFilter, tx::IncludedTxLocation, AccountTreeId, Address, L1BatchNumber,
+    L2BlockNumber, L2ChainId, ProtocolVersion, ProtocolVersionId, StorageKey, StorageLog, H256,
 };
-use zksync_vm_interface::{tracer::ValidationTraces, TransactionExecutionMetrics};
+use zksync_vm_interface::{tracer::ValidationTraces, Call, TransactionExecutionMetrics};
 
 use super::*;
 use crate::{
@@ -432,6 +432,74 @@ async fn l1_batches_can_be_hard_pruned() {
     assert_l1_batches_not_exist(&mut transaction, L1BatchNumber(1)..=L1BatchNumber(9)).await;
 }
 
+#[tokio::test]
+async fn retention_rules_are_honored_during_hard_pruning() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+
+    let mut conn = pool.connection().await.unwrap();
+    let mut transaction = conn.start_transaction().await.unwrap();
+    insert_realistic_l1_batches(&mut transaction, 10).await;
+
+    let retained_address = Address::repeat_byte(2);
+    let retention_rules = RetentionRules {
+        event_addresses: vec![retained_address],
+        ..RetentionRules::default()
+    };
+    let stats = transaction
+        .pruning_dal()
+        .hard_prune_batches_range_with_retention(
+            L1BatchNumber(4),
+            L2BlockNumber(9),
+            &retention_rules,
+        )
+        .await
+        .unwrap();
+    // Each L2 block contains 5 events, one of which is emitted by the retained address.
+    assert_eq!(stats.deleted_events, 40);
+    assert_eq!(stats.deleted_l2_to_l1_logs, 50);
+    assert_eq!(stats.deleted_l2_blocks, 10);
+    assert_eq!(stats.retained_l2_blocks, 10);
+    assert_l1_batches_not_exist(&mut transaction, L1BatchNumber(0)..=L1BatchNumber(4)).await;
+
+    let filter = GetLogsFilter {
+        from_block: L2BlockNumber(0),
+        to_block: L2BlockNumber(19),
+        addresses: vec![retained_address],
+        topics: vec![],
+    };
+    let logs = transaction
+        .events_web3_dal()
+        .get_logs(filter, 100)
+        .await
+        .unwrap();
+    assert_eq!(logs.len(), 20);
+    for log in &logs {
+        assert_eq!(log.address, retained_address);
+        let block_number = log.block_number.unwrap().as_u32();
+        let expected_hash = create_l2_block_header(block_number).hash;
+        assert_eq!(log.block_hash, Some(expected_hash));
+        assert_eq!(
+            log.l1_batch_number,
+            Some((block_number / 2).into()),
+            "{log:?}"
+        );
+    }
+
+    // Events not matching the retention rules should be removed.
+    let filter = GetLogsFilter {
+        from_block: L2BlockNumber(0),
+        to_block: L2BlockNumber(9),
+        addresses: vec![Address::repeat_byte(3)],
+        topics: vec![],
+    };
+    let logs = transaction
+        .events_web3_dal()
+        .get_logs(filter, 100)
+        .await
+        .unwrap();
+    assert!(logs.is_empty(), "{logs:?}");
+}
+
 #[tokio::test]
 async fn transactions_are_handled_correctly_after_pruning() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -470,7 +538,10 @@ async fn transactions_are_handled_correctly_after_pruning() {
 
     let affected_count = conn
         .pruning_dal()
-        .clear_transaction_fields(L2BlockNumber(1)..=L2BlockNumber(1))
+        .clear_transaction_fields(
+            L2BlockNumber(1)..=L2BlockNumber(1),
+            &RetentionRules::default(),
+        )
         .await
         .unwrap();
     assert_eq!(affected_count, 1);
@@ -496,3 +567,107 @@ async fn transactions_are_handled_correctly_after_pruning() {
         .unwrap();
     assert!(transaction_details.is_none(), "{transaction_details:?}");
 }
+
+async fn test_transaction_retention(retain_call_traces: bool) {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    conn.protocol_versions_dal()
+        .save_protocol_version_with_tx(&ProtocolVersion::default())
+        .await
+        .unwrap();
+    insert_l1_batch(&mut conn, L1BatchNumber(1)).await;
+
+    let retained_tx = mock_l2_transaction();
+    let pruned_tx = mock_l2_transaction();
+    let mut tx_results = vec![];
+    for tx in [&retained_tx, &pruned_tx] {
+        conn.transactions_dal()
+            .insert_transaction_l2(
+                tx,
+                TransactionExecutionMetrics::default(),
+                ValidationTraces::default(),
+            )
+            .await
+            .unwrap();
+        let mut tx_result = mock_execution_result(tx.clone());
+        tx_result.call_traces.push(Call {
+            from: tx.initiator_account(),
+            to: Address::from_low_u64_be(2),
+            value: 100.into(),
+            ..Call::default()
+        });
+        tx_results.push(tx_result);
+    }
+    let l2_block_header = create_l2_block_header(1);
+    conn.blocks_dal()
+        .insert_l2_block(&l2_block_header)
+        .await
+        .unwrap();
+    conn.transactions_dal()
+        .mark_txs_as_executed_in_l2_block(
+            L2BlockNumber(1),
+            &tx_results,
+            1.into(),
+            ProtocolVersionId::latest(),
+            false,
+        )
+        .await
+        .unwrap();
+    conn.blocks_dal()
+        .mark_l2_blocks_as_executed_in_l1_batch(L1BatchNumber(1))
+        .await
+        .unwrap();
+
+    let retention_rules = RetentionRules {
+        transaction_accounts: vec![retained_tx.initiator_account()],
+        retain_call_traces,
+        ..RetentionRules::default()
+    };
+    let stats = conn
+        .pruning_dal()
+        .hard_prune_batches_range_with_retention(
+            L1BatchNumber(1),
+            L2BlockNumber(1),
+            &retention_rules,
+        )
+        .await
+        .unwrap();
+    assert_eq!(stats.deleted_l2_blocks, 1);
+    assert_eq!(stats.retained_l2_blocks, 1);
+    let expected_deleted_call_traces = if retain_call_traces { 1 } else { 2 };
+    assert_eq!(stats.deleted_call_traces, expected_deleted_call_traces);
+
+    // Only the receipt of the retained transaction should be available; it should reference the retained L2 block header.
+    let receipts = conn
+        .transactions_web3_dal()
+        .get_transaction_receipts(&[retained_tx.hash(), pruned_tx.hash()])
+        .await
+        .unwrap();
+    assert_eq!(receipts.len(), 1, "{receipts:?}");
+    assert_eq!(receipts[0].inner.transaction_hash, retained_tx.hash());
+    assert_eq!(receipts[0].inner.block_hash, l2_block_header.hash);
+    assert_eq!(receipts[0].inner.l1_batch_number, Some(1.into()));
+
+    let retained_call_trace = conn
+        .transactions_dal()
+        .get_call_trace(retained_tx.hash())
+        .await
+        .unwrap();
+    assert_eq!(retained_call_trace.is_some(), retain_call_traces);
+    let pruned_call_trace = conn
+        .transactions_dal()
+        .get_call_trace(pruned_tx.hash())
+        .await
+        .unwrap();
+    assert!(pruned_call_trace.is_none(), "{pruned_call_trace:?}");
+}
+
+#[tokio::test]
+async fn transaction_retention_rules_are_honored_during_hard_pruning() {
+    test_transaction_retention(false).await;
+}
+
+#[tokio::test]
+async fn call_traces_are_retained_during_hard_pruning() {
+    test_transaction_retention(true).await;
+}
@@ -1,16 +1,16 @@
 #![doc = include_str!("../doc/TeeProofGenerationDal.md")]
-use std::time::Duration;
+use std::{collections::HashSet, time::Duration};
 
 use chrono::{DateTime, Utc};
 use strum::{Display, EnumString};
 use zksync_db_connection::{
     connection::Connection,
-    error::DalResult,
+    error::{DalResult, SqlxContext},
     instrument::{InstrumentExt, Instrumented},
     interpolate_query, match_query_as,
     utils::pg_interval_from_duration,
 };
-use zksync_types::{tee_types::TeeType, L1BatchNumber};
+use zksync_types::{tee_types::TeeType, L1BatchNumber, H256};
 
 use crate::{
     models::storage_tee_proof::{StorageLockedBatch, StorageTeeProof},
@@ -41,6 +41,71 @@ pub enum TeeProofGenerationJobStatus {
     PermanentlyIgnored,
 }
 
+/// Status of a TEE attestation registered by a TEE prover.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display)]
+pub enum TeeAttestationStatus {
+    /// The attestation was saved without verification, since verification is disabled.
+    #[strum(serialize = "unverified")]
+    Unverified,
+    /// The attestation quote was successfully verified.
+    #[strum(serialize = "verified")]
+    Verified,
+    /// The attestation quote failed verification. Proofs signed by the attested key are rejected.
+    #[strum(serialize = "rejected")]
+    Rejected,
+}
+
+/// Measurements of the attested TEE extracted from an attestation quote.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TeeMeasurements {
+    Sgx {
+        mr_enclave: H256,
+        mr_signer: H256,
+    },
+    Tdx {
+        mr_td: Vec<u8>,
+        /// Runtime measurement registers RTMR0..RTMR3.
+        rtmrs: [Vec<u8>; 4],
+    },
+}
+
+impl TeeMeasurements {
+    pub fn tee_type(&self) -> TeeType {
+        match self {
+            Self::Sgx { .. } => TeeType::Sgx,
+            Self::Tdx { .. } => TeeType::Tdx,
+        }
+    }
+}
+
+/// Outcome of verifying a TEE attestation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TeeAttestationVerification {
+    pub status: TeeAttestationStatus,
+    /// Measurements from the quote, if it could be parsed.
+    pub measurements: Option<TeeMeasurements>,
+    /// Reason why the attestation was rejected.
+    pub error: Option<String>,
+}
+
+impl TeeAttestationVerification {
+    pub fn unverified() -> Self {
+        Self {
+            status: TeeAttestationStatus::Unverified,
+            measurements: None,
+            error: None,
+        }
+    }
+}
+
+/// Information about a registered TEE attestation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TeeAttestationInfo {
+    pub status: TeeAttestationStatus,
+    /// TEE type from the attestation quote. `None` if the attestation was not verified.
+    pub tee_type: Option<TeeType>,
+}
+
 /// Represents a locked batch picked by a TEE prover. A batch is locked when taken by a TEE prover
 /// ([TeeProofGenerationJobStatus::PickedByProver]). It can transition to one of three states:
 /// 1. [TeeProofGenerationJobStatus::Generated].
@@ -206,7 +271,6 @@ impl TeeProofGenerationDal<'_, '_> {
             r#"
             UPDATE tee_proof_generation_details
             SET
-                tee_type = $1,
                 status = $2,
                 pubkey = $3,
                 signature = $4,
@@ -214,6 +278,7 @@ impl TeeProofGenerationDal<'_, '_> {
                 updated_at = NOW()
             WHERE
                 l1_batch_number = $6
+                AND tee_type = $1
             "#,
             tee_type.to_string(),
             TeeProofGenerationJobStatus::Generated.to_string(),
@@ -244,21 +309,81 @@ impl TeeProofGenerationDal<'_, '_> {
         Ok(())
     }
 
-    pub async fn save_attestation(&mut self, pubkey: &[u8], attestation: &[u8]) -> DalResult<()> {
+    /// Saves the attestation together with the outcome of its verification. An attestation for the same key
+    /// is overwritten unless it was already verified.
+    pub async fn save_attestation(
+        &mut self,
+        pubkey: &[u8],
+        attestation: &[u8],
+        verification: &TeeAttestationVerification,
+    ) -> DalResult<()> {
+        let (mr_enclave, mr_signer, mr_td, rtmrs) = match &verification.measurements {
+            Some(TeeMeasurements::Sgx {
+                mr_enclave,
+                mr_signer,
+            }) => (
+                Some(mr_enclave.as_bytes()),
+                Some(mr_signer.as_bytes()),
+                None,
+                None,
+            ),
+            Some(TeeMeasurements::Tdx { mr_td, rtmrs }) => {
+                (None, None, Some(mr_td.as_slice()), Some(rtmrs.to_vec()))
+            }
+            None => (None, None, None, None),
+        };
+        let tee_type = verification
+            .measurements
+            .as_ref()
+            .map(|measurements| measurements.tee_type().to_string());
+
         let query = sqlx::query!(
             r#"
             INSERT INTO
-            tee_attestations (pubkey, attestation)
+            tee_attestations (
+                pubkey,
+                attestation,
+                status,
+                tee_type,
+                mr_enclave,
+                mr_signer,
+                mr_td,
+                rtmrs,
+                verification_error,
+                updated_at
+            )
             VALUES
-            ($1, $2)
-            ON CONFLICT (pubkey) DO NOTHING
+            ($1, $2, $3, $4, $5, $6, $7, $8, $9, NOW())
+            ON CONFLICT (pubkey) DO
+            UPDATE
+            SET
+            attestation = excluded.attestation,
+            status = excluded.status,
+            tee_type = excluded.tee_type,
+            mr_enclave = excluded.mr_enclave,
+            mr_signer = excluded.mr_signer,
+            mr_td = excluded.mr_td,
+            rtmrs = excluded.rtmrs,
+            verification_error = excluded.verification_error,
+            updated_at = NOW()
+            WHERE
+                tee_attestations.status != $10
             "#,
             pubkey,
-            attestation
+            attestation,
+            verification.status.to_string(),
+            tee_type,
+            mr_enclave,
+            mr_signer,
+            mr_td,
+            rtmrs.as_deref(),
+            verification.error.as_deref(),
+            TeeAttestationStatus::Verified.to_string(),
         );
         let instrumentation = Instrumented::new("save_attestation")
             .with_arg("pubkey", &pubkey)
-            .with_arg("attestation", &attestation);
+            .with_arg("attestation", &attestation)
+            .with_arg("verification", verification);
         instrumentation
             .clone()
             .with(query)
@@ -268,6 +393,71 @@ impl TeeProofGenerationDal<'_, '_> {
         Ok(())
     }
 
+    /// Returns information about the attestation for the specified key, or `None` if the key was never attested.
+    pub async fn get_attestation_info(
+        &mut self,
+        pubkey: &[u8],
+    ) -> DalResult<Option<TeeAttestationInfo>> {
+        sqlx::query!(
+            r#"
+            SELECT
+                status,
+                tee_type
+            FROM
+                tee_attestations
+            WHERE
+                pubkey = $1
+            "#,
+            pubkey
+        )
+        .try_map(|row| {
+            Ok(TeeAttestationInfo {
+                status: row.status.parse().decode_column("status")?,
+                tee_type: row
+                    .tee_type
+                    .map(|tee_type| tee_type.parse())
+                    .transpose()
+                    .decode_column("tee_type")?,
+            })
+        })
+        .instrument("get_attestation_info")
+        .with_arg("pubkey", &pubkey)
+        .fetch_optional(self.storage)
+        .await
+    }
+
+    /// Checks whether the batch is TEE-verified, i.e., proven by each of the specified TEE types.
+    pub async fn is_batch_tee_verified(
+        &mut self,
+        batch_number: L1BatchNumber,
+        tee_types: &[TeeType],
+    ) -> DalResult<bool> {
+        let tee_types: Vec<_> = tee_types.iter().map(ToString::to_string).collect();
+        let proven_tee_types = sqlx::query_scalar!(
+            r#"
+            SELECT
+                COUNT(DISTINCT tee_type) AS "count!"
+            FROM
+                tee_proof_generation_details
+            WHERE
+                l1_batch_number = $1
+                AND status = $2
+                AND tee_type = ANY($3)
+            "#,
+            i64::from(batch_number.0),
+            TeeProofGenerationJobStatus::Generated.to_string(),
+            &tee_types
+        )
+        .instrument("is_batch_tee_verified")
+        .with_arg("batch_number", &batch_number)
+        .with_arg("tee_types", &tee_types)
+        .fetch_one(self.storage)
+        .await?;
+
+        let tee_types: HashSet<_> = tee_types.iter().collect();
+        Ok(proven_tee_types as usize == tee_types.len())
+    }
+
     pub async fn get_tee_proofs(
         &mut self,
         batch_number: L1BatchNumber,
@@ -70,18 +70,24 @@ impl TransactionsWeb3Dal<'_, '_> {
                 transactions.refunded_gas,
                 transactions.gas_limit,
                 transactions.nonce,
-                miniblocks.hash AS "block_hash",
-                miniblocks.l1_batch_number AS "l1_batch_number?",
-                miniblocks.timestamp AS "block_timestamp?"
+                COALESCE(miniblocks.hash, retained.hash) AS "block_hash!",
+                COALESCE(miniblocks.l1_batch_number, retained.l1_batch_number) AS "l1_batch_number?",
+                COALESCE(miniblocks.timestamp, retained.timestamp) AS "block_timestamp?"
             FROM
                 transactions
-            JOIN miniblocks ON miniblocks.number = transactions.miniblock_number
+            LEFT JOIN miniblocks ON miniblocks.number = transactions.miniblock_number
+            LEFT JOIN pruning_retained_miniblocks retained ON retained.number = transactions.miniblock_number
             WHERE
                 transactions.hash = ANY($1)
                 AND transactions.data != '{}'::jsonb
+                AND (
+                    miniblocks.number IS NOT NULL
+                    OR retained.number IS NOT NULL
+                )
             "#,
             // ^ Filter out transactions with pruned data, which would lead to potentially incomplete / bogus
-            // transaction info.
+            // transaction info. Transactions retained during hard pruning have their data preserved and are
+            // tied to a retained L2 block header.
             &hash_bytes as &[&[u8]],
         )
         .instrument("get_transaction_receipts")
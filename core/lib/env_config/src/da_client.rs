@@ -22,6 +22,9 @@ use zksync_config::{
 
 use crate::{envy_load, FromEnv};
 
+/// Prefix of the environment variables configuring the secondary DA client used by the DA dispatcher.
+pub const SECONDARY_DA_CLIENT_ENV_PREFIX: &str = "DA_SECONDARY_";
+
 pub fn da_client_config_from_env(prefix: &str) -> anyhow::Result<DAClientConfig> {
     let client_tag = env::var(format!("{}CLIENT", prefix))?;
     let config = match client_tag.as_str() {
@@ -291,6 +294,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn from_env_secondary_celestia_client() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            DA_SECONDARY_CLIENT="Celestia"
+            DA_SECONDARY_API_NODE_URL="localhost:12345"
+            DA_SECONDARY_NAMESPACE="0x1234567890abcdef"
+            DA_SECONDARY_CHAIN_ID="mocha-4"
+            DA_SECONDARY_TIMEOUT_MS="7000"
+            DA_SECONDARY_SECRETS_PRIVATE_KEY="f55baf7c0e4e33b1d78fbf52f069c426bc36cff1aceb9bc8f45d14c07f034d73"
+        "#;
+        lock.set_env(config);
+
+        let actual = da_client_config_from_env(SECONDARY_DA_CLIENT_ENV_PREFIX).unwrap();
+        assert_eq!(
+            actual,
+            expected_celestia_da_layer_config(
+                "localhost:12345",
+                "0x1234567890abcdef",
+                "mocha-4",
+                7000
+            )
+        );
+        let secrets = da_client_secrets_from_env(SECONDARY_DA_CLIENT_ENV_PREFIX).unwrap();
+        assert!(matches!(secrets, DataAvailabilitySecrets::Celestia(_)));
+    }
+
     #[test]
     fn from_env_eigen_client() {
         let mut lock = MUTEX.lock();
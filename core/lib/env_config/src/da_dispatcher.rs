@@ -10,7 +10,7 @@ impl FromEnv for DADispatcherConfig {
 
 #[cfg(test)]
 mod tests {
-    use zksync_config::configs::da_dispatcher::DADispatcherConfig;
+    use zksync_config::configs::da_dispatcher::{DADispatcherConfig, SecondaryDALayerPolicy};
 
     use super::*;
     use crate::test_utils::EnvMutex;
@@ -28,6 +28,18 @@ mod tests {
             max_retries: Some(max_retries),
             use_dummy_inclusion_data: Some(true),
             inclusion_verification_transition_enabled: None,
+            secondary_layer_policy: Some(SecondaryDALayerPolicy::DualPosting),
+            fallback_after_failures: None,
+            primary_layer_recheck_interval_ms: Some(60_000),
+            max_batches_per_blob: Some(4),
+            compress_blobs: Some(true),
+            fee_token_address: Some(
+                "0x0000000000000000000000000000000000000123"
+                    .parse()
+                    .unwrap(),
+            ),
+            fee_token_decimals: Some(6),
+            pubdata_price_feedback_enabled: Some(true),
         }
     }
 
@@ -39,6 +51,13 @@ mod tests {
             DA_DISPATCHER_MAX_ROWS_TO_DISPATCH=60
             DA_DISPATCHER_MAX_RETRIES=7
             DA_DISPATCHER_USE_DUMMY_INCLUSION_DATA="true"
+            DA_DISPATCHER_SECONDARY_LAYER_POLICY="DualPosting"
+            DA_DISPATCHER_PRIMARY_LAYER_RECHECK_INTERVAL_MS=60000
+            DA_DISPATCHER_MAX_BATCHES_PER_BLOB=4
+            DA_DISPATCHER_COMPRESS_BLOBS="true"
+            DA_DISPATCHER_FEE_TOKEN_ADDRESS="0x0000000000000000000000000000000000000123"
+            DA_DISPATCHER_FEE_TOKEN_DECIMALS=6
+            DA_DISPATCHER_PUBDATA_PRICE_FEEDBACK_ENABLED="true"
         "#;
         lock.set_env(config);
         let actual = DADispatcherConfig::from_env().unwrap();
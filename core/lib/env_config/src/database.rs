@@ -89,6 +89,7 @@ mod tests {
             DATABASE_EXPERIMENTAL_STATE_KEEPER_DB_BLOCK_CACHE_CAPACITY_MB=64
             DATABASE_EXPERIMENTAL_STATE_KEEPER_DB_MAX_OPEN_FILES=100
             DATABASE_EXPERIMENTAL_MERKLE_TREE_REPAIR_STALE_KEYS=true
+            DATABASE_EXPERIMENTAL_ZK_OS_MERKLE_TREE_PATH=/db/zk_os_tree
         "#;
         lock.set_env(config);
 
@@ -111,6 +112,10 @@ mod tests {
             NonZeroU32::new(100)
         );
         assert!(db_config.experimental.merkle_tree_repair_stale_keys);
+        assert_eq!(
+            db_config.experimental.zk_os_merkle_tree_path.as_deref(),
+            Some("/db/zk_os_tree")
+        );
     }
 
     #[test]
@@ -121,6 +126,7 @@ mod tests {
             "DATABASE_EXPERIMENTAL_STATE_KEEPER_DB_MAX_OPEN_FILES",
             "DATABASE_EXPERIMENTAL_STATE_KEEPER_DB_BLOCK_CACHE_CAPACITY_MB",
             "DATABASE_EXPERIMENTAL_MERKLE_TREE_REPAIR_STALE_KEYS",
+            "DATABASE_EXPERIMENTAL_ZK_OS_MERKLE_TREE_PATH",
             "DATABASE_MERKLE_TREE_BACKUP_PATH",
             "DATABASE_MERKLE_TREE_PATH",
             "DATABASE_MERKLE_TREE_MODE",
@@ -148,6 +154,7 @@ mod tests {
         );
         assert_eq!(db_config.experimental.state_keeper_db_max_open_files, None);
         assert!(!db_config.experimental.merkle_tree_repair_stale_keys);
+        assert_eq!(db_config.experimental.zk_os_merkle_tree_path, None);
 
         // Check that new env variable for Merkle tree path is supported
         lock.set_env("DATABASE_MERKLE_TREE_PATH=/db/tree/main");
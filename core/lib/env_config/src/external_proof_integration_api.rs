@@ -19,13 +19,19 @@ mod tests {
     static MUTEX: EnvMutex = EnvMutex::new();
 
     fn expected_config() -> ExternalProofIntegrationApiConfig {
-        ExternalProofIntegrationApiConfig { http_port: 3320 }
+        ExternalProofIntegrationApiConfig {
+            http_port: 3320,
+            verification_keys_path: Some("/etc/zksync/keys".to_owned()),
+            lease_timeout_in_secs: 1800,
+        }
     }
 
     #[test]
     fn from_env() {
         let config = r#"
             EXTERNAL_PROOF_INTEGRATION_API_HTTP_PORT="3320"
+            EXTERNAL_PROOF_INTEGRATION_API_VERIFICATION_KEYS_PATH="/etc/zksync/keys"
+            EXTERNAL_PROOF_INTEGRATION_API_LEASE_TIMEOUT_IN_SECS="1800"
         "#;
         let mut lock = MUTEX.lock();
         lock.set_env(config);
@@ -19,6 +19,7 @@ mod tests {
         FriProverGatewayConfig {
             api_url: "http://private-dns-for-server".to_string(),
             api_poll_duration_secs: 100,
+            chains: vec![],
             prometheus_listener_port: 3316,
             prometheus_pushgateway_url: "http://127.0.0.1:9091".to_string(),
             prometheus_push_interval_ms: Some(100),
@@ -13,7 +13,7 @@ impl FromEnv for ProofDataHandlerConfig {
 
 #[cfg(test)]
 mod tests {
-    use zksync_basic_types::L1BatchNumber;
+    use zksync_basic_types::{tee_types::TeeType, L1BatchNumber, H256};
     use zksync_config::configs::TeeConfig;
 
     use super::*;
@@ -28,8 +28,16 @@ mod tests {
             tee_config: TeeConfig {
                 tee_support: true,
                 first_tee_processed_batch: L1BatchNumber(1337),
+                tee_types: vec![TeeType::Sgx, TeeType::Tdx],
                 tee_proof_generation_timeout_in_secs: 600,
                 tee_batch_permanently_ignored_timeout_in_hours: 240,
+                tee_attestation_verification: true,
+                tee_allowed_mrenclaves: vec![H256::repeat_byte(0x11), H256::repeat_byte(0x22)],
+                tee_allowed_mrsigners: vec![H256::repeat_byte(0x33)],
+                tee_tdx_policies: vec![],
+                tee_attestation_root_ca_path: Some("/etc/sgx/root_ca.pem".to_owned()),
+                tee_pccs_url: "https://pccs.example.com".to_owned(),
+                tee_collateral_cache_ttl_in_secs: 7200,
             },
         }
     }
@@ -41,8 +49,15 @@ mod tests {
             PROOF_DATA_HANDLER_HTTP_PORT="3320"
             PROOF_DATA_HANDLER_TEE_SUPPORT="true"
             PROOF_DATA_HANDLER_FIRST_TEE_PROCESSED_BATCH="1337"
+            PROOF_DATA_HANDLER_TEE_TYPES="sgx,tdx"
             PROOF_DATA_HANDLER_TEE_PROOF_GENERATION_TIMEOUT_IN_SECS="600"
             PROOF_DATA_HANDLER_TEE_BATCH_PERMANENTLY_IGNORED_TIMEOUT_IN_HOURS="240"
+            PROOF_DATA_HANDLER_TEE_ATTESTATION_VERIFICATION="true"
+            PROOF_DATA_HANDLER_TEE_ALLOWED_MRENCLAVES="0x1111111111111111111111111111111111111111111111111111111111111111,0x2222222222222222222222222222222222222222222222222222222222222222"
+            PROOF_DATA_HANDLER_TEE_ALLOWED_MRSIGNERS="0x3333333333333333333333333333333333333333333333333333333333333333"
+            PROOF_DATA_HANDLER_TEE_ATTESTATION_ROOT_CA_PATH="/etc/sgx/root_ca.pem"
+            PROOF_DATA_HANDLER_TEE_PCCS_URL="https://pccs.example.com"
+            PROOF_DATA_HANDLER_TEE_COLLATERAL_CACHE_TTL_IN_SECS="7200"
         "#;
         let mut lock = MUTEX.lock();
         lock.set_env(config);
@@ -32,6 +32,10 @@ mod tests {
             witness_generator_queue_reporter_run_interval_ms: 10000,
             witness_job_queuer_run_interval_ms: 10000,
             http_port: 3074,
+            batch_sla_ms: 10800000,
+            sla_monitor_run_interval_ms: 10000,
+            sla_at_risk_threshold_ms: 1800000,
+            sla_at_risk_min_priority: 0,
         }
     }
 
@@ -49,6 +53,10 @@ mod tests {
         config.prover_queue_reporter_run_interval_ms += 1;
         config.witness_generator_queue_reporter_run_interval_ms += 1;
         config.witness_job_queuer_run_interval_ms += 1;
+        config.batch_sla_ms += 1;
+        config.sla_monitor_run_interval_ms += 1;
+        config.sla_at_risk_threshold_ms += 1;
+        config.sla_at_risk_min_priority = 100;
         config
     }
 
@@ -83,6 +91,10 @@ mod tests {
             PROVER_JOB_MONITOR_WITNESS_GENERATOR_QUEUE_REPORTER_RUN_INTERVAL_MS=10001
             PROVER_JOB_MONITOR_WITNESS_JOB_QUEUER_RUN_INTERVAL_MS=10001
             PROVER_JOB_MONITOR_HTTP_PORT=3074
+            PROVER_JOB_MONITOR_BATCH_SLA_MS=10800001
+            PROVER_JOB_MONITOR_SLA_MONITOR_RUN_INTERVAL_MS=10001
+            PROVER_JOB_MONITOR_SLA_AT_RISK_THRESHOLD_MS=1800001
+            PROVER_JOB_MONITOR_SLA_AT_RISK_MIN_PRIORITY=100
         "#;
         let mut lock = MUTEX.lock();
         lock.set_env(config);
@@ -1,5 +1,6 @@
 use zksync_config::configs::{
     BasicWitnessInputProducerConfig, ExperimentalVmConfig, ProtectiveReadsWriterConfig,
+    StateDiffExporterConfig,
 };
 
 use crate::{envy_load, FromEnv};
@@ -16,6 +17,15 @@ impl FromEnv for BasicWitnessInputProducerConfig {
     }
 }
 
+impl FromEnv for StateDiffExporterConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load(
+            "vm_runner.state_diff_exporter",
+            "VM_RUNNER_STATE_DIFF_EXPORTER_",
+        )
+    }
+}
+
 impl FromEnv for ExperimentalVmConfig {
     fn from_env() -> anyhow::Result<Self> {
         Ok(Self {
@@ -50,6 +60,26 @@ mod tests {
         assert_eq!(config.first_processed_batch, L1BatchNumber(123));
     }
 
+    #[test]
+    fn state_diff_exporter_config_from_env() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            VM_RUNNER_STATE_DIFF_EXPORTER_DB_PATH=/db/state_diff_exporter
+            VM_RUNNER_STATE_DIFF_EXPORTER_WINDOW_SIZE=1
+            VM_RUNNER_STATE_DIFF_EXPORTER_FIRST_PROCESSED_BATCH=10
+            VM_RUNNER_STATE_DIFF_EXPORTER_FILE_SINK_PATH=/data/state_diffs
+        "#;
+        lock.set_env(config);
+
+        let config = StateDiffExporterConfig::from_env().unwrap();
+        assert_eq!(config.db_path, "/db/state_diff_exporter");
+        assert_eq!(config.window_size, 1);
+        assert_eq!(config.first_processed_batch, L1BatchNumber(10));
+        assert_eq!(config.file_sink_path.as_deref(), Some("/data/state_diffs"));
+        assert_eq!(config.kafka_rest_url, None);
+        assert_eq!(config.kafka_topic, "state_diffs");
+    }
+
     #[test]
     fn experimental_vm_config_from_env() {
         let mut lock = MUTEX.lock();
@@ -41,6 +41,8 @@ impl FileBackedObjectStore {
             Bucket::NodeAggregationWitnessJobsFri,
             Bucket::SchedulerWitnessJobsFri,
             Bucket::ProofsFri,
+            Bucket::ProofsTee,
+            Bucket::TeeVerifierInput,
             Bucket::StorageSnapshot,
             Bucket::VmDumps,
         ] {
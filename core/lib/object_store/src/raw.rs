@@ -17,6 +17,7 @@ pub enum Bucket {
     SchedulerWitnessJobsFri,
     ProofsFri,
     ProofsTee,
+    TeeVerifierInput,
     StorageSnapshot,
     DataAvailability,
     VmDumps,
@@ -36,6 +37,7 @@ impl Bucket {
             Self::SchedulerWitnessJobsFri => "scheduler_witness_jobs_fri",
             Self::ProofsFri => "proofs_fri",
             Self::ProofsTee => "proofs_tee",
+            Self::TeeVerifierInput => "tee_verifier_inputs",
             Self::StorageSnapshot => "storage_logs_snapshots",
             Self::DataAvailability => "data_availability",
             Self::VmDumps => "vm_dumps",
@@ -50,6 +50,7 @@ impl ProtoRepr for proto::DataAvailabilityClient {
                             max_retries: *required(&gas_relay_conf.max_retries)
                                 .context("max_retries")?
                                 as usize,
+                            inclusion_delay_ms: gas_relay_conf.inclusion_delay_ms,
                         })
                     }
                     None => return Err(anyhow::anyhow!("Invalid Avail DA configuration")),
@@ -121,6 +122,7 @@ impl ProtoRepr for proto::DataAvailabilityClient {
                         proto::avail_config::Config::GasRelay(proto::AvailGasRelayConfig {
                             gas_relay_api_url: Some(conf.gas_relay_api_url.clone()),
                             max_retries: Some(conf.max_retries as u64),
+                            inclusion_delay_ms: conf.inclusion_delay_ms,
                         }),
                     ),
                 },
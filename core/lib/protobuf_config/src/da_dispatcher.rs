@@ -1,7 +1,24 @@
-use zksync_config::configs::{self};
+use anyhow::Context as _;
+use zksync_config::configs::{self, da_dispatcher::SecondaryDALayerPolicy};
 use zksync_protobuf::ProtoRepr;
 
-use crate::proto::da_dispatcher as proto;
+use crate::{parse_h160, proto::da_dispatcher as proto};
+
+impl proto::SecondaryLayerPolicy {
+    fn new(x: &SecondaryDALayerPolicy) -> Self {
+        match x {
+            SecondaryDALayerPolicy::Fallback => Self::Fallback,
+            SecondaryDALayerPolicy::DualPosting => Self::DualPosting,
+        }
+    }
+
+    fn parse(&self) -> SecondaryDALayerPolicy {
+        match self {
+            Self::Fallback => SecondaryDALayerPolicy::Fallback,
+            Self::DualPosting => SecondaryDALayerPolicy::DualPosting,
+        }
+    }
+}
 
 impl ProtoRepr for proto::DataAvailabilityDispatcher {
     type Type = configs::da_dispatcher::DADispatcherConfig;
@@ -14,6 +31,23 @@ impl ProtoRepr for proto::DataAvailabilityDispatcher {
             use_dummy_inclusion_data: self.use_dummy_inclusion_data,
             inclusion_verification_transition_enabled: self
                 .inclusion_verification_transition_enabled,
+            secondary_layer_policy: self
+                .secondary_layer_policy
+                .map(|x| anyhow::Ok(proto::SecondaryLayerPolicy::try_from(x)?.parse()))
+                .transpose()
+                .context("secondary_layer_policy")?,
+            fallback_after_failures: self.fallback_after_failures,
+            primary_layer_recheck_interval_ms: self.primary_layer_recheck_interval_ms,
+            max_batches_per_blob: self.max_batches_per_blob,
+            compress_blobs: self.compress_blobs,
+            fee_token_address: self
+                .fee_token_address
+                .as_ref()
+                .map(|x| parse_h160(x))
+                .transpose()
+                .context("fee_token_address")?,
+            fee_token_decimals: self.fee_token_decimals,
+            pubdata_price_feedback_enabled: self.pubdata_price_feedback_enabled,
         })
     }
 
@@ -25,6 +59,17 @@ impl ProtoRepr for proto::DataAvailabilityDispatcher {
             use_dummy_inclusion_data: this.use_dummy_inclusion_data,
             inclusion_verification_transition_enabled: this
                 .inclusion_verification_transition_enabled,
+            secondary_layer_policy: this
+                .secondary_layer_policy
+                .as_ref()
+                .map(|x| proto::SecondaryLayerPolicy::new(x).into()),
+            fallback_after_failures: this.fallback_after_failures,
+            primary_layer_recheck_interval_ms: this.primary_layer_recheck_interval_ms,
+            max_batches_per_blob: this.max_batches_per_blob,
+            compress_blobs: this.compress_blobs,
+            fee_token_address: this.fee_token_address.map(|x| format!("{:?}", x)),
+            fee_token_decimals: this.fee_token_decimals,
+            pubdata_price_feedback_enabled: this.pubdata_price_feedback_enabled,
         }
     }
 }
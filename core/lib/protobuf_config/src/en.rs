@@ -37,6 +37,7 @@ impl ProtoRepr for proto::ExternalNode {
                 .bridge_addresses_refresh_interval_sec
                 .and_then(NonZeroU64::new),
             gateway_chain_id: self.gateway_chain_id.map(SLChainId),
+            da_blob_verification_enabled: self.da_blob_verification_enabled,
         })
     }
 
@@ -56,6 +57,7 @@ impl ProtoRepr for proto::ExternalNode {
                 .bridge_addresses_refresh_interval_sec
                 .map(|a| a.get()),
             gateway_chain_id: this.gateway_chain_id.map(|c| c.0),
+            da_blob_verification_enabled: this.da_blob_verification_enabled,
         }
     }
 }
@@ -36,6 +36,7 @@ impl ProtoRepr for proto::Db {
                 .include_indices_and_filters_in_block_cache
                 .unwrap_or(false),
             merkle_tree_repair_stale_keys: self.merkle_tree_repair_stale_keys.unwrap_or(false),
+            zk_os_merkle_tree_path: self.zk_os_merkle_tree_path.clone(),
         })
     }
 
@@ -55,6 +56,7 @@ impl ProtoRepr for proto::Db {
                 this.include_indices_and_filters_in_block_cache,
             ),
             merkle_tree_repair_stale_keys: Some(this.merkle_tree_repair_stale_keys),
+            zk_os_merkle_tree_path: this.zk_os_merkle_tree_path.clone(),
         }
     }
 }
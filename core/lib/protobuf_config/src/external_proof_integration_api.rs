@@ -11,12 +11,18 @@ impl ProtoRepr for proto::ExternalProofIntegrationApi {
             http_port: required(&self.http_port)
                 .and_then(|p| Ok((*p).try_into()?))
                 .context("http_port")?,
+            verification_keys_path: self.verification_keys_path.clone(),
+            lease_timeout_in_secs: self
+                .lease_timeout_in_secs
+                .unwrap_or_else(ExternalProofIntegrationApiConfig::default_lease_timeout_in_secs),
         })
     }
 
     fn build(this: &Self::Type) -> Self {
         Self {
             http_port: Some(this.http_port.into()),
+            verification_keys_path: this.verification_keys_path.clone(),
+            lease_timeout_in_secs: Some(this.lease_timeout_in_secs),
         }
     }
 }
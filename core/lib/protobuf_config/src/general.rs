@@ -29,11 +29,13 @@ impl ProtoRepr for proto::GeneralConfig {
             snapshot_creator: read_optional_repr(&self.snapshot_creator),
             observability: read_optional_repr(&self.observability),
             da_client_config: read_optional_repr(&self.da_client),
+            secondary_da_client_config: read_optional_repr(&self.secondary_da_client),
             da_dispatcher_config: read_optional_repr(&self.da_dispatcher),
             protective_reads_writer_config: read_optional_repr(&self.protective_reads_writer),
             basic_witness_input_producer_config: read_optional_repr(
                 &self.basic_witness_input_producer,
             ),
+            state_diff_exporter_config: read_optional_repr(&self.state_diff_exporter),
             core_object_store: read_optional_repr(&self.core_object_store),
             base_token_adjuster: read_optional_repr(&self.base_token_adjuster),
             commitment_generator: read_optional_repr(&self.commitment_generator),
@@ -79,6 +81,10 @@ impl ProtoRepr for proto::GeneralConfig {
             snapshot_creator: this.snapshot_creator.as_ref().map(ProtoRepr::build),
             observability: this.observability.as_ref().map(ProtoRepr::build),
             da_client: this.da_client_config.as_ref().map(ProtoRepr::build),
+            secondary_da_client: this
+                .secondary_da_client_config
+                .as_ref()
+                .map(ProtoRepr::build),
             da_dispatcher: this.da_dispatcher_config.as_ref().map(ProtoRepr::build),
             protective_reads_writer: this
                 .protective_reads_writer_config
@@ -88,6 +94,10 @@ impl ProtoRepr for proto::GeneralConfig {
                 .basic_witness_input_producer_config
                 .as_ref()
                 .map(ProtoRepr::build),
+            state_diff_exporter: this
+                .state_diff_exporter_config
+                .as_ref()
+                .map(ProtoRepr::build),
             commitment_generator: this.commitment_generator.as_ref().map(ProtoRepr::build),
             snapshot_recovery: this.snapshot_recovery.as_ref().map(ProtoRepr::build),
             pruning: this.pruning.as_ref().map(ProtoRepr::build),
@@ -1,9 +1,9 @@
 use anyhow::Context as _;
 use zksync_config::configs;
 use zksync_protobuf::{repr::ProtoRepr, required};
-use zksync_types::L1BatchNumber;
+use zksync_types::{tee_types::TeeType, L1BatchNumber};
 
-use crate::proto::prover as proto;
+use crate::{parse_h256, proto::prover as proto};
 
 impl ProtoRepr for proto::ProofDataHandler {
     type Type = configs::ProofDataHandlerConfig;
@@ -23,6 +23,16 @@ impl ProtoRepr for proto::ProofDataHandler {
                     .first_tee_processed_batch
                     .map(|x| L1BatchNumber(x as u32))
                     .unwrap_or_else(configs::TeeConfig::default_first_tee_processed_batch),
+                tee_types: if self.tee_types.is_empty() {
+                    configs::TeeConfig::default_tee_types()
+                } else {
+                    self.tee_types
+                        .iter()
+                        .enumerate()
+                        .map(|(i, tee_type)| tee_type.parse::<TeeType>().context(i))
+                        .collect::<Result<_, _>>()
+                        .context("tee_types")?
+                },
                 tee_proof_generation_timeout_in_secs: self
                     .tee_proof_generation_timeout_in_secs
                     .map(|x| x as u16)
@@ -35,6 +45,36 @@ impl ProtoRepr for proto::ProofDataHandler {
                     .unwrap_or_else(
                         configs::TeeConfig::default_tee_batch_permanently_ignored_timeout_in_hours,
                     ),
+                tee_attestation_verification: self.tee_attestation_verification.unwrap_or(false),
+                tee_allowed_mrenclaves: self
+                    .tee_allowed_mrenclaves
+                    .iter()
+                    .enumerate()
+                    .map(|(i, hash)| parse_h256(hash).context(i))
+                    .collect::<Result<_, _>>()
+                    .context("tee_allowed_mrenclaves")?,
+                tee_allowed_mrsigners: self
+                    .tee_allowed_mrsigners
+                    .iter()
+                    .enumerate()
+                    .map(|(i, hash)| parse_h256(hash).context(i))
+                    .collect::<Result<_, _>>()
+                    .context("tee_allowed_mrsigners")?,
+                tee_tdx_policies: self
+                    .tee_tdx_policies
+                    .iter()
+                    .enumerate()
+                    .map(|(i, policy)| policy.read().context(i))
+                    .collect::<Result<_, _>>()
+                    .context("tee_tdx_policies")?,
+                tee_attestation_root_ca_path: self.tee_attestation_root_ca_path.clone(),
+                tee_pccs_url: self
+                    .tee_pccs_url
+                    .clone()
+                    .unwrap_or_else(configs::TeeConfig::default_tee_pccs_url),
+                tee_collateral_cache_ttl_in_secs: self
+                    .tee_collateral_cache_ttl_in_secs
+                    .unwrap_or_else(configs::TeeConfig::default_tee_collateral_cache_ttl_in_secs),
             },
         })
     }
@@ -45,6 +85,12 @@ impl ProtoRepr for proto::ProofDataHandler {
             proof_generation_timeout_in_secs: Some(this.proof_generation_timeout_in_secs.into()),
             tee_support: Some(this.tee_config.tee_support),
             first_tee_processed_batch: Some(this.tee_config.first_tee_processed_batch.0 as u64),
+            tee_types: this
+                .tee_config
+                .tee_types
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
             tee_proof_generation_timeout_in_secs: Some(
                 this.tee_config.tee_proof_generation_timeout_in_secs.into(),
             ),
@@ -53,6 +99,68 @@ impl ProtoRepr for proto::ProofDataHandler {
                     .tee_batch_permanently_ignored_timeout_in_hours
                     .into(),
             ),
+            tee_attestation_verification: Some(this.tee_config.tee_attestation_verification),
+            tee_allowed_mrenclaves: this
+                .tee_config
+                .tee_allowed_mrenclaves
+                .iter()
+                .map(|hash| format!("{hash:?}"))
+                .collect(),
+            tee_allowed_mrsigners: this
+                .tee_config
+                .tee_allowed_mrsigners
+                .iter()
+                .map(|hash| format!("{hash:?}"))
+                .collect(),
+            tee_tdx_policies: this
+                .tee_config
+                .tee_tdx_policies
+                .iter()
+                .map(ProtoRepr::build)
+                .collect(),
+            tee_attestation_root_ca_path: this.tee_config.tee_attestation_root_ca_path.clone(),
+            tee_pccs_url: Some(this.tee_config.tee_pccs_url.clone()),
+            tee_collateral_cache_ttl_in_secs: Some(
+                this.tee_config.tee_collateral_cache_ttl_in_secs,
+            ),
+        }
+    }
+}
+
+impl ProtoRepr for proto::TdxMeasurementPolicy {
+    type Type = configs::TdxMeasurementPolicy;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        let rtmrs = [&self.rtmr0, &self.rtmr1, &self.rtmr2, &self.rtmr3];
+        let mut parsed_rtmrs = [None, None, None, None];
+        for (i, (rtmr, parsed)) in rtmrs.into_iter().zip(&mut parsed_rtmrs).enumerate() {
+            *parsed = rtmr
+                .as_deref()
+                .map(parse_hex)
+                .transpose()
+                .with_context(|| format!("rtmr{i}"))?;
+        }
+        Ok(Self::Type {
+            mr_td: parse_hex(required(&self.mr_td).context("mr_td")?).context("mr_td")?,
+            rtmrs: parsed_rtmrs,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        let [rtmr0, rtmr1, rtmr2, rtmr3] = this
+            .rtmrs
+            .clone()
+            .map(|rtmr| rtmr.map(|value| format!("0x{}", hex::encode(value))));
+        Self {
+            mr_td: Some(format!("0x{}", hex::encode(&this.mr_td))),
+            rtmr0,
+            rtmr1,
+            rtmr2,
+            rtmr3,
         }
     }
 }
+
+fn parse_hex(value: &str) -> anyhow::Result<Vec<u8>> {
+    Ok(hex::decode(value.strip_prefix("0x").unwrap_or(value))?)
+}
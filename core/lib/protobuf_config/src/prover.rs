@@ -1,7 +1,7 @@
 use std::collections::HashSet;
 
 use anyhow::Context as _;
-use zksync_basic_types::basic_fri_types::CircuitIdRoundTuple;
+use zksync_basic_types::{basic_fri_types::CircuitIdRoundTuple, L2ChainId};
 use zksync_config::configs;
 use zksync_protobuf::{repr::ProtoRepr, required};
 
@@ -136,6 +136,13 @@ impl ProtoRepr for proto::ProverGateway {
             api_poll_duration_secs: required(&self.api_poll_duration_secs)
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("api_poll_duration_secs")?,
+            chains: self
+                .chains
+                .iter()
+                .enumerate()
+                .map(|(i, chain)| chain.read().context(i))
+                .collect::<anyhow::Result<_>>()
+                .context("chains")?,
             prometheus_listener_port: required(&self.prometheus_listener_port)
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("prometheus_listener_port")?,
@@ -150,6 +157,7 @@ impl ProtoRepr for proto::ProverGateway {
         Self {
             api_url: Some(this.api_url.clone()),
             api_poll_duration_secs: Some(this.api_poll_duration_secs.into()),
+            chains: this.chains.iter().map(ProtoRepr::build).collect(),
             prometheus_listener_port: Some(this.prometheus_listener_port.into()),
             prometheus_pushgateway_url: Some(this.prometheus_pushgateway_url.clone()),
             prometheus_push_interval_ms: this.prometheus_push_interval_ms,
@@ -157,6 +165,27 @@ impl ProtoRepr for proto::ProverGateway {
     }
 }
 
+impl ProtoRepr for proto::ProverGatewayChain {
+    type Type = configs::ProverGatewayChainConfig;
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            chain_id: required(&self.chain_id)
+                .and_then(|x| L2ChainId::new(*x).map_err(|err| anyhow::anyhow!(err)))
+                .context("chain_id")?,
+            api_url: required(&self.api_url).context("api_url")?.clone(),
+            weight: self.weight.unwrap_or(Self::Type::default_weight()),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            chain_id: Some(this.chain_id.as_u64()),
+            api_url: Some(this.api_url.clone()),
+            weight: Some(this.weight),
+        }
+    }
+}
+
 impl ProtoRepr for proto::WitnessGenerator {
     type Type = configs::FriWitnessGeneratorConfig;
     fn read(&self) -> anyhow::Result<Self::Type> {
@@ -98,6 +98,16 @@ impl ProtoRepr for proto::ProverJobMonitor {
             http_port: required(&self.http_port)
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("http_port")?,
+            batch_sla_ms: self
+                .batch_sla_ms
+                .unwrap_or_else(Self::Type::default_batch_sla_ms),
+            sla_monitor_run_interval_ms: self
+                .sla_monitor_run_interval_ms
+                .unwrap_or_else(Self::Type::default_sla_monitor_run_interval_ms),
+            sla_at_risk_threshold_ms: self
+                .sla_at_risk_threshold_ms
+                .unwrap_or_else(Self::Type::default_sla_at_risk_threshold_ms),
+            sla_at_risk_min_priority: self.sla_at_risk_min_priority.unwrap_or_default(),
         })
     }
 
@@ -130,6 +140,10 @@ impl ProtoRepr for proto::ProverJobMonitor {
             ),
             witness_job_queuer_run_interval_ms: Some(this.witness_job_queuer_run_interval_ms),
             http_port: Some(this.http_port.into()),
+            batch_sla_ms: Some(this.batch_sla_ms),
+            sla_monitor_run_interval_ms: Some(this.sla_monitor_run_interval_ms),
+            sla_at_risk_threshold_ms: Some(this.sla_at_risk_threshold_ms),
+            sla_at_risk_min_priority: Some(this.sla_at_risk_min_priority),
         }
     }
 }
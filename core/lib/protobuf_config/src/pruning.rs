@@ -1,9 +1,10 @@
 use std::num::NonZeroU64;
 
+use anyhow::Context as _;
 use zksync_config::configs::PruningConfig;
 use zksync_protobuf::ProtoRepr;
 
-use crate::proto::pruning as proto;
+use crate::{parse_h160, proto::pruning as proto};
 
 impl ProtoRepr for proto::Pruning {
     type Type = PruningConfig;
@@ -14,6 +15,21 @@ impl ProtoRepr for proto::Pruning {
             chunk_size: self.chunk_size,
             removal_delay_sec: self.removal_delay_sec.and_then(NonZeroU64::new),
             data_retention_sec: self.data_retention_sec,
+            retained_event_addresses: self
+                .retained_event_addresses
+                .iter()
+                .enumerate()
+                .map(|(i, addr)| parse_h160(addr).context(i))
+                .collect::<Result<_, _>>()
+                .context("retained_event_addresses")?,
+            retained_transaction_accounts: self
+                .retained_transaction_accounts
+                .iter()
+                .enumerate()
+                .map(|(i, addr)| parse_h160(addr).context(i))
+                .collect::<Result<_, _>>()
+                .context("retained_transaction_accounts")?,
+            retain_call_traces: self.retain_call_traces.unwrap_or_default(),
         })
     }
 
@@ -23,6 +39,17 @@ impl ProtoRepr for proto::Pruning {
             chunk_size: this.chunk_size,
             removal_delay_sec: this.removal_delay_sec.map(|a| a.get()),
             data_retention_sec: this.data_retention_sec,
+            retained_event_addresses: this
+                .retained_event_addresses
+                .iter()
+                .map(|addr| format!("{addr:?}"))
+                .collect(),
+            retained_transaction_accounts: this
+                .retained_transaction_accounts
+                .iter()
+                .map(|addr| format!("{addr:?}"))
+                .collect(),
+            retain_call_traces: Some(this.retain_call_traces),
         }
     }
 }
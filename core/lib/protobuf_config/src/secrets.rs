@@ -28,6 +28,7 @@ impl ProtoRepr for proto::Secrets {
             database: read_optional_repr(&self.database),
             l1: read_optional_repr(&self.l1),
             data_availability: read_optional_repr(&self.da),
+            secondary_data_availability: read_optional_repr(&self.secondary_da),
             contract_verifier: read_optional_repr(&self.contract_verifier),
         })
     }
@@ -39,6 +40,10 @@ impl ProtoRepr for proto::Secrets {
             consensus: this.consensus.as_ref().map(ProtoRepr::build),
             da: this.data_availability.as_ref().map(ProtoRepr::build),
             contract_verifier: this.contract_verifier.as_ref().map(ProtoRepr::build),
+            secondary_da: this
+                .secondary_data_availability
+                .as_ref()
+                .map(ProtoRepr::build),
         }
     }
 }
@@ -24,6 +24,7 @@ impl ProtoRepr for proto::SnapshotsCreator {
                 .context("storage_logs_chunk_size")?,
             concurrent_queries_count: *required(&self.concurrent_queries_count)
                 .context("concurrent_queries_count")?,
+            rocksdb_cache_path: self.rocksdb_cache_path.clone(),
             object_store,
         })
     }
@@ -34,6 +35,7 @@ impl ProtoRepr for proto::SnapshotsCreator {
             l1_batch_number: this.l1_batch_number.map(|num| num.0),
             storage_logs_chunk_size: Some(this.storage_logs_chunk_size),
             concurrent_queries_count: Some(this.concurrent_queries_count),
+            rocksdb_cache_path: this.rocksdb_cache_path.clone(),
             object_store: this.object_store.as_ref().map(ProtoRepr::build),
         }
     }
@@ -46,6 +46,7 @@ fn test_encoding() {
     test_encode_all_formats::<ReprConv<proto::da_dispatcher::DataAvailabilityDispatcher>>(rng);
     test_encode_all_formats::<ReprConv<proto::vm_runner::ProtectiveReadsWriter>>(rng);
     test_encode_all_formats::<ReprConv<proto::vm_runner::BasicWitnessInputProducer>>(rng);
+    test_encode_all_formats::<ReprConv<proto::vm_runner::StateDiffExporter>>(rng);
     test_encode_all_formats::<ReprConv<proto::commitment_generator::CommitmentGenerator>>(rng);
     test_encode_all_formats::<ReprConv<proto::snapshot_recovery::Postgres>>(rng);
     test_encode_all_formats::<ReprConv<proto::snapshot_recovery::SnapshotRecovery>>(rng);
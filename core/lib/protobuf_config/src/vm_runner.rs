@@ -48,3 +48,31 @@ impl ProtoRepr for proto::BasicWitnessInputProducer {
         }
     }
 }
+
+impl ProtoRepr for proto::StateDiffExporter {
+    type Type = configs::StateDiffExporterConfig;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            db_path: required(&self.db_path).context("db_path")?.clone(),
+            window_size: *required(&self.window_size).context("window_size")? as u32,
+            first_processed_batch: L1BatchNumber(
+                *required(&self.first_processed_batch).context("first_batch")? as u32,
+            ),
+            file_sink_path: self.file_sink_path.clone(),
+            kafka_rest_url: self.kafka_rest_url.clone(),
+            kafka_topic: required(&self.kafka_topic).context("kafka_topic")?.clone(),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            db_path: Some(this.db_path.clone()),
+            window_size: Some(this.window_size as u64),
+            first_processed_batch: Some(this.first_processed_batch.0 as u64),
+            file_sink_path: this.file_sink_path.clone(),
+            kafka_rest_url: this.kafka_rest_url.clone(),
+            kafka_topic: Some(this.kafka_topic.clone()),
+        }
+    }
+}
@@ -308,6 +308,17 @@ impl TeeVerifierInput {
     }
 }
 
+impl StoredObject for TeeVerifierInput {
+    const BUCKET: Bucket = Bucket::TeeVerifierInput;
+    type Key<'a> = L1BatchNumber;
+
+    fn encode_key(key: Self::Key<'_>) -> String {
+        format!("tee_verifier_input_for_l1_batch_{key}.bin")
+    }
+
+    serialize_using_bincode!();
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
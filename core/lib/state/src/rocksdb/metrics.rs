@@ -51,3 +51,24 @@ pub(super) struct RocksdbRecoveryMetrics {
 
 #[vise::register]
 pub(super) static RECOVERY_METRICS: vise::Global<RocksdbRecoveryMetrics> = vise::Global::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "stage", rename_all = "snake_case")]
+pub(super) enum SnapshotExportStage {
+    LoadFactoryDeps,
+    LoadEntries,
+    LoadInitialWrites,
+}
+
+/// Metrics related to exporting protocol snapshots.
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_state_keeper_secondary_storage_snapshot_export")]
+pub(super) struct RocksdbSnapshotExportMetrics {
+    /// Latency of a snapshot export stage. Stages related to storage logs are measured per chunk.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub latency: Family<SnapshotExportStage, Histogram<Duration>>,
+}
+
+#[vise::register]
+pub(super) static SNAPSHOT_EXPORT_METRICS: vise::Global<RocksdbSnapshotExportMetrics> =
+    vise::Global::new();
@@ -35,12 +35,14 @@ use zksync_storage::{db::NamedColumnFamily, RocksDB, RocksDBOptions};
 use zksync_types::{L1BatchNumber, StorageKey, StorageValue, H256};
 use zksync_vm_interface::storage::ReadStorage;
 
+pub use self::snapshot::RocksdbSnapshotExport;
 #[cfg(test)]
 use self::tests::RocksdbStorageEventListener;
 use self::{metrics::METRICS, recovery::Strategy};
 
 mod metrics;
 mod recovery;
+mod snapshot;
 #[cfg(test)]
 mod tests;
 
@@ -273,7 +275,6 @@ impl RocksdbStorageBuilder {
 
 impl RocksdbStorage {
     const L1_BATCH_NUMBER_KEY: &'static [u8] = b"block_number";
-    #[allow(dead_code)]
     const ENUM_INDEX_MIGRATION_CURSOR: &'static [u8] = b"enum_index_migration_cursor";
 
     /// Desired size of log chunks loaded from Postgres during snapshot recovery.
@@ -281,7 +282,6 @@ impl RocksdbStorage {
     /// (i.e., not changed after a node restart).
     const DESIRED_LOG_CHUNK_SIZE: u64 = 200_000;
 
-    #[allow(dead_code)]
     fn is_special_key(key: &[u8]) -> bool {
         key == Self::L1_BATCH_NUMBER_KEY || key == Self::ENUM_INDEX_MIGRATION_CURSOR
     }
@@ -0,0 +1,259 @@
+//! Logic for [`RocksdbStorage`] related to exporting protocol snapshots.
+
+use std::collections::HashMap;
+
+use anyhow::Context as _;
+use tokio::{
+    sync::{mpsc, oneshot},
+    task::JoinHandle,
+};
+use zksync_dal::{Connection, Core, CoreDal};
+use zksync_storage::RocksDB;
+use zksync_types::{
+    snapshots::{
+        uniform_hashed_keys_chunk, SnapshotFactoryDependencies, SnapshotFactoryDependency,
+        SnapshotStorageLog, SnapshotStorageLogsChunk,
+    },
+    L1BatchNumber, H256,
+};
+
+use super::{
+    deserialize_l1_batch_number,
+    metrics::{SnapshotExportStage, SNAPSHOT_EXPORT_METRICS},
+    RocksdbStorage, StateKeeperColumnFamily, StateValue,
+};
+
+/// Storage log loaded from RocksDB. Unlike [`SnapshotStorageLog`], it doesn't contain the L1 batch
+/// of the initial write since it's not persisted in RocksDB.
+#[derive(Debug)]
+struct RawStorageLog {
+    key: H256,
+    value: H256,
+    enumeration_index: u64,
+}
+
+#[derive(Debug)]
+struct RawChunk {
+    id: u64,
+    storage_logs: Vec<RawStorageLog>,
+}
+
+#[derive(Debug)]
+struct ExportHeader {
+    l1_batch_number: L1BatchNumber,
+    factory_deps: SnapshotFactoryDependencies,
+}
+
+/// Protocol snapshot exported from [`RocksdbStorage`]. Created using [`RocksdbStorage::export_snapshot()`].
+///
+/// All data is read from a consistent point-in-time view of RocksDB, so the storage can be updated
+/// while the export is in progress. Storage logs are loaded by chunks in the same way as in the snapshot creator
+/// (i.e., using [`uniform_hashed_keys_chunk()`]); the only data loaded from Postgres is the L1 batches
+/// of initial writes for the exported keys.
+#[derive(Debug)]
+pub struct RocksdbSnapshotExport {
+    l1_batch_number: L1BatchNumber,
+    chunk_count: u64,
+    factory_deps: Option<SnapshotFactoryDependencies>,
+    chunks_receiver: mpsc::Receiver<RawChunk>,
+    export_task: Option<JoinHandle<anyhow::Result<()>>>,
+}
+
+impl RocksdbSnapshotExport {
+    /// Max number of keys in a single Postgres query for initial writes.
+    const INITIAL_WRITES_QUERY_CHUNK_SIZE: usize = 10_000;
+
+    /// Returns the L1 batch number of the snapshot, i.e., the last L1 batch processed by the storage.
+    pub fn l1_batch_number(&self) -> L1BatchNumber {
+        self.l1_batch_number
+    }
+
+    /// Returns the number of storage log chunks in the snapshot.
+    pub fn chunk_count(&self) -> u64 {
+        self.chunk_count
+    }
+
+    /// Takes factory dependencies from the snapshot. Returns `None` if the dependencies were already taken.
+    pub fn take_factory_deps(&mut self) -> Option<SnapshotFactoryDependencies> {
+        self.factory_deps.take()
+    }
+
+    /// Returns the next chunk of storage logs together with its ID. Chunks are returned in the order of their IDs.
+    /// Returns `Ok(None)` if all chunks were exported.
+    ///
+    /// # Errors
+    ///
+    /// - Propagates RocksDB and Postgres errors.
+    /// - Errors if the RocksDB and Postgres data is inconsistent (e.g., enumeration indices of a key differ).
+    pub async fn next_chunk(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<Option<(u64, SnapshotStorageLogsChunk)>> {
+        let Some(raw_chunk) = self.chunks_receiver.recv().await else {
+            // The export task has terminated; check whether it was successful.
+            if let Some(export_task) = self.export_task.take() {
+                export_task
+                    .await
+                    .context("panicked exporting snapshot from RocksDB")??;
+            }
+            return Ok(None);
+        };
+
+        let latency =
+            SNAPSHOT_EXPORT_METRICS.latency[&SnapshotExportStage::LoadInitialWrites].start();
+        let keys: Vec<_> = raw_chunk.storage_logs.iter().map(|log| log.key).collect();
+        let mut initial_writes = HashMap::with_capacity(keys.len());
+        for keys_chunk in keys.chunks(Self::INITIAL_WRITES_QUERY_CHUNK_SIZE) {
+            initial_writes.extend(
+                storage
+                    .storage_logs_dal()
+                    .get_l1_batches_and_indices_for_initial_writes(keys_chunk)
+                    .await?,
+            );
+        }
+        latency.observe();
+
+        let storage_logs = raw_chunk
+            .storage_logs
+            .into_iter()
+            .map(|log| {
+                let &(l1_batch_number, enumeration_index) =
+                    initial_writes.get(&log.key).with_context(|| {
+                        format!("initial write for key {:?} is missing in Postgres", log.key)
+                    })?;
+                anyhow::ensure!(
+                    enumeration_index == log.enumeration_index
+                        && l1_batch_number <= self.l1_batch_number,
+                    "Mismatch between RocksDB cache ({log:?}) and Postgres initial write \
+                     (L1 batch #{l1_batch_number}, enumeration index {enumeration_index})"
+                );
+                Ok(SnapshotStorageLog {
+                    key: log.key,
+                    value: log.value,
+                    l1_batch_number_of_initial_write: l1_batch_number,
+                    enumeration_index,
+                })
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Some((
+            raw_chunk.id,
+            SnapshotStorageLogsChunk { storage_logs },
+        )))
+    }
+}
+
+impl RocksdbStorage {
+    /// Starts exporting a protocol snapshot from this storage, with storage logs split into `chunk_count` chunks.
+    /// The snapshot corresponds to the last L1 batch persisted in the storage; pending changes are not exported.
+    ///
+    /// # Errors
+    ///
+    /// - Propagates RocksDB I/O errors.
+    /// - Errors if the storage is empty, or contains state values without enumeration indices.
+    pub async fn export_snapshot(&self, chunk_count: u64) -> anyhow::Result<RocksdbSnapshotExport> {
+        anyhow::ensure!(chunk_count > 0, "Snapshot chunk count must be positive");
+
+        let db = self.db.clone();
+        let (header_sender, header_receiver) = oneshot::channel();
+        // Use the minimum capacity to not load many chunks into RAM if the consumer is slow.
+        let (chunks_sender, chunks_receiver) = mpsc::channel(1);
+        let export_task = tokio::task::spawn_blocking(move || {
+            Self::export_snapshot_blocking(&db, chunk_count, header_sender, &chunks_sender)
+        });
+
+        let Ok(header) = header_receiver.await else {
+            // The export task has terminated before sending the header; it must have errored or panicked.
+            export_task
+                .await
+                .context("panicked exporting snapshot from RocksDB")??;
+            anyhow::bail!("snapshot export unexpectedly terminated");
+        };
+        tracing::info!(
+            "Started exporting snapshot for L1 batch #{} from RocksDB cache in {chunk_count} chunks",
+            header.l1_batch_number
+        );
+        Ok(RocksdbSnapshotExport {
+            l1_batch_number: header.l1_batch_number,
+            chunk_count,
+            factory_deps: Some(header.factory_deps),
+            chunks_receiver,
+            export_task: Some(export_task),
+        })
+    }
+
+    fn export_snapshot_blocking(
+        db: &RocksDB<StateKeeperColumnFamily>,
+        chunk_count: u64,
+        header_sender: oneshot::Sender<ExportHeader>,
+        chunks_sender: &mpsc::Sender<RawChunk>,
+    ) -> anyhow::Result<()> {
+        let snapshot = db.snapshot();
+        let next_l1_batch_number = snapshot
+            .get_cf(StateKeeperColumnFamily::State, Self::L1_BATCH_NUMBER_KEY)
+            .context("failed getting L1 batch number from RocksDB")?
+            .map(|bytes| deserialize_l1_batch_number(&bytes));
+        let l1_batch_number = next_l1_batch_number
+            .and_then(|number| number.checked_sub(1))
+            .context("RocksDB cache doesn't contain any L1 batches; cannot export snapshot")?;
+        let l1_batch_number = L1BatchNumber(l1_batch_number);
+
+        let latency =
+            SNAPSHOT_EXPORT_METRICS.latency[&SnapshotExportStage::LoadFactoryDeps].start();
+        let factory_deps = snapshot
+            .from_iterator_cf(StateKeeperColumnFamily::FactoryDeps, [].as_slice()..)
+            .map(|(_, bytecode)| SnapshotFactoryDependency {
+                bytecode: bytecode.into_vec().into(),
+            })
+            .collect();
+        let factory_deps = SnapshotFactoryDependencies { factory_deps };
+        latency.observe();
+
+        let header = ExportHeader {
+            l1_batch_number,
+            factory_deps,
+        };
+        if header_sender.send(header).is_err() {
+            return Ok(()); // The export was dropped
+        }
+
+        for id in 0..chunk_count {
+            let latency =
+                SNAPSHOT_EXPORT_METRICS.latency[&SnapshotExportStage::LoadEntries].start();
+            let key_range = uniform_hashed_keys_chunk(id, chunk_count);
+            let end_key = key_range.end().as_bytes();
+            let storage_logs = snapshot
+                .from_iterator_cf(
+                    StateKeeperColumnFamily::State,
+                    key_range.start().as_bytes()..,
+                )
+                .take_while(|(key, _)| **key <= *end_key)
+                .filter(|(key, _)| !Self::is_special_key(key))
+                .map(|(key, value)| {
+                    let key = H256::from_slice(&key);
+                    let StateValue { value, enum_index } = StateValue::deserialize(&value);
+                    let enumeration_index = enum_index.with_context(|| {
+                        format!("state value for key {key:?} doesn't have an enumeration index")
+                    })?;
+                    Ok(RawStorageLog {
+                        key,
+                        value,
+                        enumeration_index,
+                    })
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            latency.observe();
+            tracing::debug!(
+                "Loaded {} storage logs for chunk {id}/{chunk_count} from RocksDB",
+                storage_logs.len()
+            );
+
+            if chunks_sender
+                .blocking_send(RawChunk { id, storage_logs })
+                .is_err()
+            {
+                return Ok(()); // The export was dropped
+            }
+        }
+        Ok(())
+    }
+}
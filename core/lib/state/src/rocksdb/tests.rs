@@ -7,7 +7,10 @@ use tempfile::TempDir;
 use test_casing::test_casing;
 use tokio::sync::RwLock;
 use zksync_dal::{ConnectionPool, Core};
-use zksync_types::{L2BlockNumber, StorageLog};
+use zksync_types::{
+    snapshots::{uniform_hashed_keys_chunk, SnapshotStorageLogsChunk},
+    L2BlockNumber, StorageLog,
+};
 
 use super::*;
 use crate::test_utils::{
@@ -448,3 +451,134 @@ async fn recovery_fault_tolerance() {
         assert!(!storage.is_write_initial(&log.key));
     }
 }
+
+async fn prepare_postgres_for_snapshot_export(conn: &mut Connection<'_, Core>) {
+    prepare_postgres(conn).await;
+    let storage_logs = gen_storage_logs(20..40);
+    create_l2_block(conn, L2BlockNumber(1), storage_logs.clone()).await;
+    insert_factory_deps(conn, L2BlockNumber(1), 0..2).await;
+    create_l1_batch(conn, L1BatchNumber(1), &storage_logs).await;
+
+    let inserted_storage_logs = gen_storage_logs(50..60);
+    let mut new_storage_logs = inserted_storage_logs.clone();
+    new_storage_logs.extend(storage_logs.iter().step_by(3).map(|&log| StorageLog {
+        value: H256::zero(),
+        ..log
+    }));
+    create_l2_block(conn, L2BlockNumber(2), new_storage_logs).await;
+    insert_factory_deps(conn, L2BlockNumber(2), 2..5).await;
+    create_l1_batch(conn, L1BatchNumber(2), &inserted_storage_logs).await;
+}
+
+async fn export_all_chunks(
+    export: &mut RocksdbSnapshotExport,
+    conn: &mut Connection<'_, Core>,
+) -> Vec<SnapshotStorageLogsChunk> {
+    let mut chunks = vec![];
+    while let Some((chunk_id, chunk)) = export.next_chunk(conn).await.unwrap() {
+        assert_eq!(chunk_id, chunks.len() as u64);
+        chunks.push(chunk);
+    }
+    assert_eq!(chunks.len() as u64, export.chunk_count());
+    chunks
+}
+
+#[test_casing(3, [1, 3, 10])]
+#[tokio::test]
+async fn exporting_snapshot(chunk_count: u64) {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    prepare_postgres_for_snapshot_export(&mut conn).await;
+
+    let dir = TempDir::new().expect("cannot create temporary dir for state keeper");
+    let storage = sync_test_storage(&dir, &mut conn).await;
+    let mut export = storage.export_snapshot(chunk_count).await.unwrap();
+    assert_eq!(export.l1_batch_number(), L1BatchNumber(2));
+
+    let mut factory_deps: Vec<_> = export
+        .take_factory_deps()
+        .unwrap()
+        .factory_deps
+        .into_iter()
+        .map(|dep| dep.bytecode.0)
+        .collect();
+    factory_deps.sort_unstable();
+    let mut expected_factory_deps: Vec<_> = conn
+        .snapshots_creator_dal()
+        .get_all_factory_deps(L2BlockNumber(2))
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|(_, bytecode)| bytecode)
+        .collect();
+    expected_factory_deps.sort_unstable();
+    assert_eq!(factory_deps, expected_factory_deps);
+    assert!(export.take_factory_deps().is_none());
+
+    let chunks = export_all_chunks(&mut export, &mut conn).await;
+    for (chunk_id, chunk) in (0..).zip(chunks) {
+        let key_range = uniform_hashed_keys_chunk(chunk_id, chunk_count);
+        let mut expected_logs = conn
+            .snapshots_creator_dal()
+            .get_storage_logs_chunk(L2BlockNumber(2), L1BatchNumber(2), key_range)
+            .await
+            .unwrap();
+        expected_logs.sort_unstable_by_key(|log| log.key);
+        assert_eq!(chunk.storage_logs, expected_logs);
+    }
+}
+
+#[tokio::test]
+async fn snapshot_export_is_not_affected_by_storage_updates() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    prepare_postgres_for_snapshot_export(&mut conn).await;
+
+    let dir = TempDir::new().expect("cannot create temporary dir for state keeper");
+    let mut storage = sync_test_storage(&dir, &mut conn).await;
+    let mut export = storage.export_snapshot(3).await.unwrap();
+
+    // Overwrite all values in the storage after the export has started.
+    let updated_logs = gen_storage_logs(20..40);
+    let updates = updated_logs
+        .iter()
+        .map(|log| (log.key.hashed_key(), H256::repeat_byte(0xff)))
+        .collect();
+    let changed_keys = RocksdbStorage::process_transaction_logs(&storage.db, updates);
+    storage.pending_patch.state = changed_keys
+        .into_iter()
+        .map(|(key, state_value)| (key, (state_value.value, state_value.enum_index.unwrap())))
+        .collect();
+    storage.save(Some(L1BatchNumber(3))).await.unwrap();
+    for log in &updated_logs {
+        assert_eq!(storage.read_value(&log.key), H256::repeat_byte(0xff));
+    }
+
+    assert_eq!(export.l1_batch_number(), L1BatchNumber(2));
+    let exported_logs: HashMap<_, _> = export_all_chunks(&mut export, &mut conn)
+        .await
+        .into_iter()
+        .flat_map(|chunk| chunk.storage_logs)
+        .map(|log| (log.key, log.value))
+        .collect();
+    assert_eq!(exported_logs.len(), 30);
+    for log in &updated_logs {
+        assert_ne!(
+            exported_logs[&log.key.hashed_key()],
+            H256::repeat_byte(0xff)
+        );
+    }
+}
+
+#[tokio::test]
+async fn exporting_snapshot_from_empty_storage_errors() {
+    let dir = TempDir::new().expect("cannot create temporary dir for state keeper");
+    let storage = RocksdbStorage::new(dir.path().into(), RocksdbStorageOptions::default())
+        .await
+        .unwrap();
+    let err = storage.export_snapshot(1).await.unwrap_err();
+    assert!(
+        format!("{err:#}").contains("doesn't contain any L1 batches"),
+        "{err:#}"
+    );
+}
@@ -628,6 +628,15 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
         // ^ unwrap() is safe for the same reasons as in `prefix_iterator_cf()`.
     }
 
+    /// Creates a consistent point-in-time view of this database. Writes performed after the snapshot is created
+    /// are not visible via the snapshot.
+    pub fn snapshot(&self) -> RocksDBSnapshot<'_, CF> {
+        RocksDBSnapshot {
+            db: self,
+            inner: self.inner.db.snapshot(),
+        }
+    }
+
     /// Creates a new profiled operation.
     pub fn new_profiled_operation(&self, name: &'static str) -> ProfiledOperation {
         ProfiledOperation {
@@ -703,6 +712,43 @@ impl<CF: NamedColumnFamily> WeakRocksDB<CF> {
     }
 }
 
+/// Consistent point-in-time view of a [`RocksDB`] instance created with [`RocksDB::snapshot()`].
+pub struct RocksDBSnapshot<'a, CF> {
+    db: &'a RocksDB<CF>,
+    inner: rocksdb::SnapshotWithThreadMode<'a, DB>,
+}
+
+impl<CF> fmt::Debug for RocksDBSnapshot<'_, CF> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("RocksDBSnapshot")
+            .field("db", &self.db.inner.db_name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<CF: NamedColumnFamily> RocksDBSnapshot<'_, CF> {
+    pub fn get_cf(&self, cf: CF, key: &[u8]) -> Result<Option<Vec<u8>>, rocksdb::Error> {
+        let cf = self.db.column_family(cf);
+        self.inner.get_cf(cf, key)
+    }
+
+    /// Iterates over key-value pairs in the specified column family `cf` in the lexical
+    /// key order starting from the given `key_from`.
+    pub fn from_iterator_cf(
+        &self,
+        cf: CF,
+        keys: ops::RangeFrom<&[u8]>,
+    ) -> impl Iterator<Item = (Box<[u8]>, Box<[u8]>)> + '_ {
+        let cf = self.db.column_family(cf);
+        self.inner
+            .iterator_cf(cf, IteratorMode::From(keys.start, Direction::Forward))
+            .map(Result::unwrap)
+            .fuse()
+        // ^ unwrap() is safe for the same reasons as in `RocksDB::prefix_iterator_cf()`.
+    }
+}
+
 /// Profiling information for a logical I/O operation on RocksDB. Can be used to profile operations
 /// distributed in time, including on multiple threads.
 #[must_use = "`start_profiling()` should be called one or more times to actually perform profiling"]
@@ -1054,6 +1100,40 @@ mod tests {
         assert_eq!(value, b"value2");
     }
 
+    #[test]
+    fn snapshot_is_not_affected_by_subsequent_writes() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = RocksDB::<NewColumnFamilies>::new(temp_dir.path()).unwrap();
+        let mut batch = db.new_write_batch();
+        batch.put_cf(NewColumnFamilies::Default, b"test", b"value");
+        batch.put_cf(NewColumnFamilies::Default, b"test2", b"value2");
+        db.write(batch).unwrap();
+
+        let snapshot = db.snapshot();
+        let mut batch = db.new_write_batch();
+        batch.put_cf(NewColumnFamilies::Default, b"test", b"new_value");
+        batch.put_cf(NewColumnFamilies::Default, b"test1", b"value1");
+        batch.delete_cf(NewColumnFamilies::Default, b"test2");
+        db.write(batch).unwrap();
+
+        let value = snapshot
+            .get_cf(NewColumnFamilies::Default, b"test")
+            .unwrap()
+            .unwrap();
+        assert_eq!(value, b"value");
+        let keys: Vec<_> = snapshot
+            .from_iterator_cf(NewColumnFamilies::Default, b"test".as_slice()..)
+            .map(|(key, _)| key.into_vec())
+            .collect();
+        assert_eq!(keys, [b"test".to_vec(), b"test2".to_vec()]);
+
+        let value = db
+            .get_cf(NewColumnFamilies::Default, b"test")
+            .unwrap()
+            .unwrap();
+        assert_eq!(value, b"new_value");
+    }
+
     #[test]
     fn profiling_basics() {
         let temp_dir = TempDir::new().unwrap();
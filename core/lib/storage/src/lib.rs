@@ -1,5 +1,5 @@
 pub mod db;
 mod metrics;
 
-pub use db::{RocksDB, RocksDBOptions, StalledWritesRetries, WeakRocksDB};
+pub use db::{RocksDB, RocksDBOptions, RocksDBSnapshot, StalledWritesRetries, WeakRocksDB};
 pub use rocksdb;
@@ -0,0 +1,53 @@
+use serde::Serialize;
+use zksync_types::{L2BlockNumber, H256};
+
+/// Structured reason of a failed verification. Returned by [`Verify::verify()`](crate::Verify::verify())
+/// wrapped in an [`anyhow::Error`]; can be extracted using [`anyhow::Error::downcast_ref()`].
+#[derive(Debug, Clone, PartialEq, Serialize, thiserror::Error)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum VerificationError {
+    /// A transaction cannot be executed in the VM.
+    #[error("failed executing transaction #{tx_index} ({tx_hash:?}) in L2 block #{l2_block_number}: {reason}")]
+    VmExecution {
+        l2_block_number: L2BlockNumber,
+        /// 0-based index of the transaction in the L2 block.
+        tx_index: usize,
+        tx_hash: H256,
+        reason: String,
+    },
+    /// The VM produced a different number of deduplicated storage logs than there are Merkle paths in the input.
+    #[error("VM produced {vm_log_count} storage logs, while the input contains {merkle_path_count} Merkle paths")]
+    StorageLogCountMismatch {
+        vm_log_count: usize,
+        merkle_path_count: usize,
+    },
+    /// A storage log produced by the VM diverges from the corresponding tree log in the input.
+    #[error(
+        "storage log #{index} for hashed key {hashed_key:?} produced by the VM diverges from the input: \
+         VM {vm_operation} {vm_value:?}, tree {tree_log}"
+    )]
+    StorageLogMismatch {
+        /// 0-based index of the log among deduplicated storage logs.
+        index: usize,
+        hashed_key: H256,
+        /// Either `read` or `write`.
+        vm_operation: &'static str,
+        vm_value: H256,
+        /// Debug representation of the tree log entry from the input.
+        tree_log: String,
+    },
+    /// The Merkle path for a storage slot doesn't lead to the expected root hash.
+    #[error("Merkle path #{index} for hashed key {hashed_key:?} is invalid: {reason}")]
+    InvalidMerklePath {
+        /// 0-based index of the Merkle path in the input.
+        index: usize,
+        hashed_key: H256,
+        /// Root hash of the tree before applying the storage log.
+        root_hash: H256,
+        reason: String,
+    },
+    /// The root hash of the tree after the batch differs from the expected one.
+    #[error("root hash mismatch: expected {expected:?}, got {actual:?}")]
+    RootHashMismatch { expected: H256, actual: H256 },
+}
@@ -4,7 +4,7 @@
 //! executing the VM and verifying all the accessed memory slots by their
 //! merkle path.
 
-use anyhow::{bail, Context, Result};
+use anyhow::{bail, Result};
 use zksync_crypto_primitives::hasher::blake2::Blake2Hasher;
 use zksync_merkle_tree::{
     BlockOutputWithProofs, TreeInstruction, TreeLogEntry, TreeLogEntryWithProof, ValueHash,
@@ -27,6 +27,10 @@ use zksync_types::{
     StorageLog, StorageValue, Transaction, H256,
 };
 
+pub use crate::error::VerificationError;
+
+mod error;
+
 /// A structure to hold the result of verification.
 pub struct VerificationResult {
     /// The root hash of the batch that was verified.
@@ -35,6 +39,20 @@ pub struct VerificationResult {
     pub batch_number: L1BatchNumber,
 }
 
+impl VerificationResult {
+    /// Checks that the verified root hash is equal to the expected one (e.g., the one signed in a TEE proof).
+    pub fn check_root_hash(&self, expected: H256) -> Result<(), VerificationError> {
+        if self.value_hash == expected {
+            Ok(())
+        } else {
+            Err(VerificationError::RootHashMismatch {
+                expected,
+                actual: self.value_hash,
+            })
+        }
+    }
+}
+
 /// A trait for the computations that can be verified in TEE.
 pub trait Verify {
     fn verify(self) -> anyhow::Result<VerificationResult>;
@@ -48,7 +66,8 @@ impl Verify for V1TeeVerifierInput {
     /// # Errors
     ///
     /// Returns a verbose error of the failure, because any error is
-    /// not actionable.
+    /// not actionable. If the input is well-formed, but doesn't verify, the error
+    /// wraps a [`VerificationError`].
     fn verify(self) -> anyhow::Result<VerificationResult> {
         let old_root_hash = self.l1_batch_env.previous_batch_hash.unwrap();
         let enumeration_index = self.merkle_paths.next_enumeration_index();
@@ -93,12 +112,26 @@ impl Verify for V1TeeVerifierInput {
 
         let block_output_with_proofs = get_bowp(self.merkle_paths)?;
 
-        let instructions: Vec<TreeInstruction> =
-            generate_tree_instructions(enumeration_index, &block_output_with_proofs, vm_out)?;
-
-        block_output_with_proofs
-            .verify_proofs(&Blake2Hasher, old_root_hash, &instructions)
-            .context("Failed to verify_proofs {l1_batch_number} correctly!")?;
+        let storage_logs = vm_out.final_execution_state.deduplicated_storage_logs;
+        let instructions: Vec<TreeInstruction> = generate_tree_instructions(
+            enumeration_index,
+            &block_output_with_proofs,
+            &storage_logs,
+        )?;
+
+        if let Err(err) =
+            block_output_with_proofs.verify_proofs(&Blake2Hasher, old_root_hash, &instructions)
+        {
+            tracing::error!("Failed to verify Merkle paths for L1 batch #{batch_number}: {err:#}");
+            let err = find_invalid_merkle_path(
+                block_output_with_proofs,
+                old_root_hash,
+                &storage_logs,
+                &instructions,
+            )
+            .map_or(err, anyhow::Error::from);
+            return Err(err);
+        }
 
         Ok(VerificationResult {
             value_hash: block_output_with_proofs.root_hash().unwrap(),
@@ -191,10 +224,14 @@ fn execute_vm<S: ReadStorage>(
             l2_block_data.number,
             l2_block_data.txs.len(),
         );
-        for tx in &l2_block_data.txs {
+        for (tx_index, tx) in l2_block_data.txs.iter().enumerate() {
             tracing::trace!("Started execution of tx: {tx:?}");
-            execute_tx(tx, &mut vm)
-                .context("failed to execute transaction in TeeVerifierInputProducer")?;
+            execute_tx(tx, &mut vm).map_err(|err| VerificationError::VmExecution {
+                l2_block_number: l2_block_data.number,
+                tx_index,
+                tx_hash: tx.hash(),
+                reason: format!("{err:#}"),
+            })?;
             tracing::trace!("Finished execution of tx: {tx:?}");
         }
 
@@ -213,10 +250,11 @@ fn execute_vm<S: ReadStorage>(
 
 /// Map `LogQuery` and `TreeLogEntry` to a `TreeInstruction`
 fn map_log_tree(
+    index: usize,
     storage_log: &StorageLog,
     tree_log_entry: &TreeLogEntry,
     idx: &mut u64,
-) -> anyhow::Result<TreeInstruction> {
+) -> Result<TreeInstruction, VerificationError> {
     let key = storage_log.key.hashed_key_u256();
     let tree_instruction = match (storage_log.is_write(), *tree_log_entry) {
         (true, TreeLogEntry::Updated { leaf_index, .. }) => {
@@ -236,7 +274,7 @@ fn map_log_tree(
                     storage_log.value,
                     value
                 );
-                anyhow::bail!("Failed to map LogQuery to TreeInstruction");
+                return Err(storage_log_mismatch(index, storage_log, tree_log_entry));
             }
             TreeInstruction::Read(key)
         }
@@ -250,26 +288,82 @@ fn map_log_tree(
                 ?tree_log_entry,
                 "Failed to map LogQuery to TreeInstruction"
             );
-            anyhow::bail!("Failed to map LogQuery to TreeInstruction");
+            return Err(storage_log_mismatch(index, storage_log, tree_log_entry));
         }
     };
 
     Ok(tree_instruction)
 }
 
+fn storage_log_mismatch(
+    index: usize,
+    storage_log: &StorageLog,
+    tree_log_entry: &TreeLogEntry,
+) -> VerificationError {
+    VerificationError::StorageLogMismatch {
+        index,
+        hashed_key: storage_log.key.hashed_key(),
+        vm_operation: if storage_log.is_write() {
+            "write"
+        } else {
+            "read"
+        },
+        vm_value: storage_log.value,
+        tree_log: format!("{tree_log_entry:?}"),
+    }
+}
+
 /// Generates the `TreeInstruction`s from the VM executions.
 fn generate_tree_instructions(
     mut idx: u64,
     bowp: &BlockOutputWithProofs,
-    vm_out: FinishedL1Batch,
-) -> anyhow::Result<Vec<TreeInstruction>> {
-    vm_out
-        .final_execution_state
-        .deduplicated_storage_logs
-        .into_iter()
-        .zip(bowp.logs.iter())
-        .map(|(log_query, tree_log_entry)| map_log_tree(&log_query, &tree_log_entry.base, &mut idx))
-        .collect::<Result<Vec<_>, _>>()
+    storage_logs: &[StorageLog],
+) -> Result<Vec<TreeInstruction>, VerificationError> {
+    if storage_logs.len() != bowp.logs.len() {
+        return Err(VerificationError::StorageLogCountMismatch {
+            vm_log_count: storage_logs.len(),
+            merkle_path_count: bowp.logs.len(),
+        });
+    }
+
+    storage_logs
+        .iter()
+        .zip(&bowp.logs)
+        .enumerate()
+        .map(|(i, (log_query, tree_log_entry))| {
+            map_log_tree(i, log_query, &tree_log_entry.base, &mut idx)
+        })
+        .collect()
+}
+
+/// Finds the first storage slot whose Merkle path doesn't verify. Called after verifying all paths at once
+/// has failed, to provide a more detailed error.
+fn find_invalid_merkle_path(
+    bowp: BlockOutputWithProofs,
+    old_root_hash: ValueHash,
+    storage_logs: &[StorageLog],
+    instructions: &[TreeInstruction],
+) -> Option<VerificationError> {
+    let mut root_hash = old_root_hash;
+    let logs = bowp.logs.into_iter().zip(storage_logs).zip(instructions);
+    for (index, ((log, storage_log), instruction)) in logs.enumerate() {
+        let next_root_hash = log.root_hash;
+        let single_log_output = BlockOutputWithProofs {
+            logs: vec![log],
+            leaf_count: 0,
+        };
+        if let Err(err) = single_log_output.verify_proofs(&Blake2Hasher, root_hash, &[*instruction])
+        {
+            return Some(VerificationError::InvalidMerklePath {
+                index,
+                hashed_key: storage_log.key.hashed_key(),
+                root_hash,
+                reason: format!("{err:#}"),
+            });
+        }
+        root_hash = next_root_hash;
+    }
+    None
 }
 
 fn execute_tx<S: ReadStorage>(
@@ -304,9 +398,103 @@ mod tests {
     use zksync_contracts::{BaseSystemContracts, SystemContractCode};
     use zksync_multivm::interface::{L1BatchEnv, SystemEnv, TxExecutionMode};
     use zksync_prover_interface::inputs::{TeeVerifierInput, VMRunWitnessInputData};
+    use zksync_types::{AccountTreeId, Address, StorageKey};
 
     use super::*;
 
+    fn tree_log(base: TreeLogEntry, root_hash: ValueHash) -> TreeLogEntryWithProof {
+        TreeLogEntryWithProof {
+            base,
+            merkle_path: vec![],
+            root_hash,
+        }
+    }
+
+    fn storage_key() -> StorageKey {
+        StorageKey::new(AccountTreeId::new(Address::repeat_byte(1)), H256::zero())
+    }
+
+    #[test]
+    fn diverging_storage_logs() {
+        let storage_logs = [
+            StorageLog::new_read_log(storage_key(), H256::zero()),
+            StorageLog::new_read_log(storage_key(), H256::repeat_byte(1)),
+        ];
+        let read_entry = TreeLogEntry::Read {
+            leaf_index: 1,
+            value: H256::repeat_byte(2),
+        };
+        let bowp = BlockOutputWithProofs {
+            logs: vec![
+                tree_log(TreeLogEntry::ReadMissingKey, ValueHash::zero()),
+                tree_log(read_entry, ValueHash::zero()),
+            ],
+            leaf_count: 0,
+        };
+
+        let err = generate_tree_instructions(1, &bowp, &storage_logs).unwrap_err();
+        assert_eq!(
+            err,
+            VerificationError::StorageLogMismatch {
+                index: 1,
+                hashed_key: storage_key().hashed_key(),
+                vm_operation: "read",
+                vm_value: H256::repeat_byte(1),
+                tree_log: format!("{read_entry:?}"),
+            }
+        );
+
+        let err = generate_tree_instructions(1, &bowp, &storage_logs[..1]).unwrap_err();
+        assert_eq!(
+            err,
+            VerificationError::StorageLogCountMismatch {
+                vm_log_count: 1,
+                merkle_path_count: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn finding_invalid_merkle_path() {
+        let root_hash = ValueHash::repeat_byte(3);
+        let storage_logs = [StorageLog::new_read_log(storage_key(), H256::zero())];
+        let bowp = BlockOutputWithProofs {
+            logs: vec![tree_log(TreeLogEntry::ReadMissingKey, root_hash)],
+            leaf_count: 0,
+        };
+        let instructions = [TreeInstruction::Read(storage_key().hashed_key_u256())];
+
+        let err = find_invalid_merkle_path(bowp, root_hash, &storage_logs, &instructions).unwrap();
+        assert!(
+            matches!(
+                &err,
+                VerificationError::InvalidMerklePath { index: 0, root_hash: hash, .. }
+                    if *hash == root_hash
+            ),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn serializing_verification_error() {
+        let result = VerificationResult {
+            value_hash: H256::repeat_byte(1),
+            batch_number: L1BatchNumber(1),
+        };
+        result.check_root_hash(H256::repeat_byte(1)).unwrap();
+        let err = result.check_root_hash(H256::zero()).unwrap_err();
+
+        let json = serde_json::to_value(&err).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "kind": "root_hash_mismatch",
+                "expected": H256::zero(),
+                "actual": H256::repeat_byte(1),
+            })
+        );
+    }
+
     #[test]
     fn test_v1_serialization() {
         let tvi = V1TeeVerifierInput::new(
@@ -978,6 +978,12 @@ pub struct DataAvailabilityDetails {
     pub inclusion_data: Option<Vec<u8>>,
     pub sent_at: DateTime<Utc>,
     pub l2_da_validator: Option<Address>,
+    /// Share of the DA blob fee attributed to the batch, in the smallest units of the DA layer's native token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee: Option<U256>,
+    /// DA fee of the batch converted to the base token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_in_base_token: Option<U256>,
 }
 
 #[cfg(test)]
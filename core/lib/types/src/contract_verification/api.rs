@@ -254,19 +254,44 @@ pub enum VerificationProblem {
     IncorrectMetadata,
 }
 
+/// Level of contract verification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum VerificationLevel {
+    /// The deployed bytecode fully matches the compiled one.
+    Full,
+    /// The deployed bytecode matches the compiled one only up to the metadata hash. Partially verified contracts
+    /// can be re-verified to upgrade the verification level to full.
+    Partial,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct VerificationInfo {
     pub request: VerificationRequest,
     pub artifacts: CompilationArtifacts,
     pub verified_at: DateTime<Utc>,
+    /// Verification level. Not set if the level is unknown (e.g., for infos persisted before the level was introduced);
+    /// such infos are not considered fully verified.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verification_level: Option<VerificationLevel>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub verification_problems: Vec<VerificationProblem>,
+    /// Address of the verified contract this info was inherited from because of the identical bytecode.
+    /// Not set if the contract was verified directly.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inherited_from: Option<Address>,
 }
 
 impl VerificationInfo {
     pub fn is_perfect_match(&self) -> bool {
-        self.verification_problems.is_empty()
+        self.verification_level == Some(VerificationLevel::Full)
+    }
+
+    /// Marks this info as a partial match because of the metadata hash mismatch.
+    pub fn mark_as_partial_match(&mut self) {
+        self.verification_level = Some(VerificationLevel::Partial);
+        self.verification_problems = vec![VerificationProblem::IncorrectMetadata];
     }
 
     pub fn bytecode_marker(&self) -> BytecodeMarker {
@@ -293,7 +318,7 @@ pub struct VerificationRequestStatus {
 mod tests {
     use assert_matches::assert_matches;
 
-    use super::SourceCodeData;
+    use super::{SourceCodeData, VerificationInfo, VerificationLevel};
 
     #[test]
     fn source_code_deserialization() {
@@ -322,4 +347,34 @@ mod tests {
             serde_json::from_str::<SourceCodeData>(type_not_specified_object_str);
         assert!(type_not_specified_object_result.is_err());
     }
+
+    #[test]
+    fn verification_info_without_level_is_not_perfect_match() {
+        let mut info = serde_json::json!({
+            "request": {
+                "id": 1,
+                "contractAddress": "0x0000000000000000000000000000000000008001",
+                "sourceCode": "text",
+                "contractName": "Test",
+                "compilerZksolcVersion": "v1.5.0",
+                "compilerSolcVersion": "0.8.24",
+                "optimizationUsed": true,
+            },
+            "artifacts": {
+                "bytecode": [0, 1],
+                "abi": [],
+            },
+            "verifiedAt": "2025-01-01T00:00:00Z",
+        });
+        let legacy_info: VerificationInfo = serde_json::from_value(info.clone()).unwrap();
+        assert_eq!(legacy_info.verification_level, None);
+        assert!(!legacy_info.is_perfect_match());
+        let serialized = serde_json::to_value(&legacy_info).unwrap();
+        assert!(serialized.get("verificationLevel").is_none());
+
+        info["verificationLevel"] = "full".into();
+        let full_info: VerificationInfo = serde_json::from_value(info).unwrap();
+        assert_eq!(full_info.verification_level, Some(VerificationLevel::Full));
+        assert!(full_info.is_perfect_match());
+    }
 }
@@ -0,0 +1,157 @@
+//! Types related to proxy contracts.
+
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::api::VerificationInfo;
+use crate::{h256_to_address, Address, L2BlockNumber, H256};
+
+/// Kind of a proxy contract, determined by the storage slot holding the implementation (or beacon) address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProxyKind {
+    /// [EIP-1967] proxy storing the implementation address in the standard slot. Transparent and UUPS proxies
+    /// fall into this category.
+    ///
+    /// [EIP-1967]: https://eips.ethereum.org/EIPS/eip-1967
+    Eip1967,
+    /// EIP-1967 beacon proxy storing the address of the beacon that provides the implementation address.
+    Eip1967Beacon,
+    /// Legacy OpenZeppelin (ZeppelinOS) proxy predating EIP-1967.
+    ZeppelinOs,
+}
+
+impl ProxyKind {
+    pub const ALL: [Self; 3] = [Self::Eip1967, Self::Eip1967Beacon, Self::ZeppelinOs];
+
+    /// `bytes32(uint256(keccak256("eip1967.proxy.implementation")) - 1)`
+    const EIP1967_IMPLEMENTATION_SLOT: H256 = H256([
+        0x36, 0x08, 0x94, 0xa1, 0x3b, 0xa1, 0xa3, 0x21, 0x06, 0x67, 0xc8, 0x28, 0x49, 0x2d, 0xb9,
+        0x8d, 0xca, 0x3e, 0x20, 0x76, 0xcc, 0x37, 0x35, 0xa9, 0x20, 0xa3, 0xca, 0x50, 0x5d, 0x38,
+        0x2b, 0xbc,
+    ]);
+    /// `bytes32(uint256(keccak256("eip1967.proxy.beacon")) - 1)`
+    const EIP1967_BEACON_SLOT: H256 = H256([
+        0xa3, 0xf0, 0xad, 0x74, 0xe5, 0x42, 0x3a, 0xeb, 0xfd, 0x80, 0xd3, 0xef, 0x43, 0x46, 0x57,
+        0x83, 0x35, 0xa9, 0xa7, 0x2a, 0xea, 0xee, 0x59, 0xff, 0x6c, 0xb3, 0x58, 0x2b, 0x35, 0x13,
+        0x3d, 0x50,
+    ]);
+    /// `keccak256("org.zeppelinos.proxy.implementation")`
+    const ZEPPELIN_OS_IMPLEMENTATION_SLOT: H256 = H256([
+        0x70, 0x50, 0xc9, 0xe0, 0xf4, 0xca, 0x76, 0x9c, 0x69, 0xbd, 0x3a, 0x8e, 0xf7, 0x40, 0xbc,
+        0x37, 0x93, 0x4f, 0x8e, 0x2c, 0x03, 0x6e, 0x5a, 0x72, 0x3f, 0xd8, 0xee, 0x04, 0x8e, 0xd3,
+        0xf8, 0xc3,
+    ]);
+    /// Slot of the implementation address in OpenZeppelin's `UpgradeableBeacon` (follows the `Ownable` owner slot).
+    pub const BEACON_IMPLEMENTATION_SLOT: H256 = {
+        let mut slot = [0_u8; 32];
+        slot[31] = 1;
+        H256(slot)
+    };
+
+    /// Returns the storage slot of the proxy contract that holds the target address.
+    pub fn storage_slot(self) -> H256 {
+        match self {
+            Self::Eip1967 => Self::EIP1967_IMPLEMENTATION_SLOT,
+            Self::Eip1967Beacon => Self::EIP1967_BEACON_SLOT,
+            Self::ZeppelinOs => Self::ZEPPELIN_OS_IMPLEMENTATION_SLOT,
+        }
+    }
+
+    pub fn from_storage_slot(slot: H256) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.storage_slot() == slot)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Eip1967 => "eip1967",
+            Self::Eip1967Beacon => "eip1967_beacon",
+            Self::ZeppelinOs => "zeppelin_os",
+        }
+    }
+}
+
+impl FromStr for ProxyKind {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == s)
+            .ok_or("unknown proxy kind; expected one of `eip1967`, `eip1967_beacon`, `zeppelin_os`")
+    }
+}
+
+/// Link between a proxy contract and its target (the implementation, or the beacon for beacon proxies).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProxyLink {
+    pub proxy_address: Address,
+    pub kind: ProxyKind,
+    pub target_address: Address,
+    /// L2 block in which the target address was set.
+    pub l2_block_number: L2BlockNumber,
+}
+
+impl ProxyLink {
+    /// Parses a value of the proxy storage slot. Returns `None` if the value is not a valid non-zero address.
+    pub fn parse_target_address(slot_value: H256) -> Option<Address> {
+        let is_address = slot_value.as_bytes()[..12].iter().all(|&byte| byte == 0);
+        (is_address && !slot_value.is_zero()).then(|| h256_to_address(&slot_value))
+    }
+}
+
+/// Verification info of a proxy contract merged with the info of its current implementation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyVerificationInfo {
+    pub proxy_kind: ProxyKind,
+    /// Beacon providing the implementation address; only set for beacon proxies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub beacon_address: Option<Address>,
+    pub implementation_address: Address,
+    /// Verification info of the proxy itself; `None` if the proxy is not verified.
+    pub proxy: Option<VerificationInfo>,
+    /// Verification info of the implementation; `None` if the implementation is not verified.
+    pub implementation: Option<VerificationInfo>,
+    /// ABI of the implementation merged with the ABI of the proxy. Implementation entries take precedence
+    /// over the proxy entries with the same signature.
+    pub merged_abi: serde_json::Value,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{address_to_h256, u256_to_h256, web3::keccak256, U256};
+
+    #[test]
+    fn proxy_storage_slots() {
+        for (kind, preimage, offset) in [
+            (ProxyKind::Eip1967, "eip1967.proxy.implementation", 1_u64),
+            (ProxyKind::Eip1967Beacon, "eip1967.proxy.beacon", 1),
+            (
+                ProxyKind::ZeppelinOs,
+                "org.zeppelinos.proxy.implementation",
+                0,
+            ),
+        ] {
+            let expected =
+                U256::from_big_endian(&keccak256(preimage.as_bytes())) - U256::from(offset);
+            let expected = u256_to_h256(expected);
+            assert_eq!(kind.storage_slot(), expected, "{kind:?}");
+            assert_eq!(ProxyKind::from_storage_slot(expected), Some(kind));
+            assert_eq!(kind.as_str().parse::<ProxyKind>().unwrap(), kind);
+        }
+    }
+
+    #[test]
+    fn parsing_target_address() {
+        let address = Address::repeat_byte(0x23);
+        let slot_value = address_to_h256(&address);
+        assert_eq!(ProxyLink::parse_target_address(slot_value), Some(address));
+        assert_eq!(ProxyLink::parse_target_address(H256::zero()), None);
+        assert_eq!(ProxyLink::parse_target_address(H256::repeat_byte(1)), None);
+    }
+}
@@ -7,13 +7,19 @@ use zksync_basic_types::H256;
 pub use zksync_crypto_primitives::hasher::blake2::Blake2Hasher;
 
 pub use self::{
+    consistency::ConsistencyError,
     errors::DeserializeError,
-    hasher::HashTree,
-    storage::{Database, MerkleTreeColumnFamily, PatchSet, Patched, RocksDBWrapper},
-    types::{BatchOutput, TreeEntry},
+    hasher::{BatchTreeProof, HashTree, IntermediateHash, MerkleTreeView, TreeOperation},
+    metrics::PruningStats,
+    pruning::{MerkleTreePruner, MerkleTreePrunerHandle, PrunerStoppedError},
+    repair::StaleKeysRepairStats,
+    storage::{
+        Database, MerkleTreeColumnFamily, PatchSet, Patched, PruneDatabase, PrunePatchSet,
+        RocksDBWrapper,
+    },
+    types::{BatchOutput, Leaf, TreeEntry},
 };
 use crate::{
-    hasher::BatchTreeProof,
     metrics::{BatchProofStage, LoadStage, MerkleTreeInfo, METRICS},
     storage::{TreeUpdate, WorkingPatchSet},
     types::MAX_TREE_DEPTH,
@@ -23,6 +29,8 @@ mod consistency;
 mod errors;
 mod hasher;
 mod metrics;
+mod pruning;
+mod repair;
 mod storage;
 #[cfg(test)]
 mod tests;
@@ -136,6 +144,18 @@ impl<DB: Database, P: TreeParams> MerkleTree<DB, P> {
         Ok(manifest.version_count.checked_sub(1))
     }
 
+    /// Returns the root hash and leaf count of a tree at the specified `version`, or `None` if the version
+    /// was not written yet.
+    pub fn root_info(&self, version: u64) -> anyhow::Result<Option<BatchOutput>> {
+        let Some(root) = self.db.try_root(version)? else {
+            return Ok(None);
+        };
+        Ok(Some(BatchOutput {
+            root_hash: root.hash::<P>(&self.hasher),
+            leaf_count: root.leaf_count,
+        }))
+    }
+
     pub fn latest_root_hash(&self) -> anyhow::Result<Option<H256>> {
         let Some(version) = self
             .latest_version()
@@ -169,7 +189,29 @@ impl<DB: Database, P: TreeParams> MerkleTree<DB, P> {
     ///
     /// Proxies database I/O errors.
     pub fn extend(&mut self, entries: &[TreeEntry]) -> anyhow::Result<BatchOutput> {
-        let (output, _) = self.extend_inner(entries, None)?;
+        let (output, _) = self.extend_inner(entries, None, 0)?;
+        Ok(output)
+    }
+
+    /// Recovers an empty tree from a snapshot by creating its first version equal to `version`. Versions before `version`
+    /// will not be present in the tree.
+    ///
+    /// Entries are assigned leaf indices in the order they are supplied, so they should be sorted by the enumeration index
+    /// for the tree to be consistent with a tree built incrementally. All keys in the provided entries must be distinct.
+    ///
+    /// # Errors
+    ///
+    /// - Returns an error if the tree is not empty.
+    /// - Proxies database I/O errors.
+    pub fn recover(&mut self, version: u64, entries: &[TreeEntry]) -> anyhow::Result<BatchOutput> {
+        let latest_version = self
+            .latest_version()
+            .context("failed getting latest version")?;
+        anyhow::ensure!(
+            latest_version.is_none(),
+            "Cannot recover a non-empty tree (latest version: {latest_version:?})"
+        );
+        let (output, _) = self.extend_inner(entries, None, version)?;
         Ok(output)
     }
 
@@ -178,6 +220,7 @@ impl<DB: Database, P: TreeParams> MerkleTree<DB, P> {
         &mut self,
         entries: &[TreeEntry],
         read_keys: Option<&[H256]>,
+        empty_tree_version: u64,
     ) -> anyhow::Result<(BatchOutput, Option<BatchTreeProof>)> {
         let latest_version = self
             .latest_version()
@@ -191,7 +234,7 @@ impl<DB: Database, P: TreeParams> MerkleTree<DB, P> {
         } else {
             (
                 WorkingPatchSet::<P>::empty(),
-                TreeUpdate::for_empty_tree(entries)?,
+                TreeUpdate::for_empty_tree(empty_tree_version, entries)?,
             )
         };
         let elapsed = load_nodes_latency.observe();
@@ -268,7 +311,7 @@ impl<DB: Database, P: TreeParams> MerkleTree<DB, P> {
         entries: &[TreeEntry],
         read_keys: &[H256],
     ) -> anyhow::Result<(BatchOutput, BatchTreeProof)> {
-        let (output, proof) = self.extend_inner(entries, Some(read_keys))?;
+        let (output, proof) = self.extend_inner(entries, Some(read_keys), 0)?;
         Ok((output, proof.unwrap()))
     }
 
@@ -276,8 +319,6 @@ impl<DB: Database, P: TreeParams> MerkleTree<DB, P> {
         let mut manifest = self.db.try_manifest()?.unwrap_or_default();
         let current_version_count = manifest.version_count;
         if current_version_count > retained_version_count {
-            // TODO: It is necessary to remove "future" stale keys since otherwise they may be used in future pruning and lead
-            //   to non-obsolete tree nodes getting removed.
             manifest.version_count = retained_version_count;
             self.db.truncate(manifest, ..current_version_count)?;
         }
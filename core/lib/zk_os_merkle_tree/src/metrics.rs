@@ -1,6 +1,6 @@
 //! Merkle tree metrics.
 
-use std::time::Duration;
+use std::{ops, time::Duration};
 
 use vise::{
     Buckets, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Info, Metrics, Unit,
@@ -96,6 +96,9 @@ pub(crate) struct MerkleTreeMetrics {
     /// the level of redundancy of the tree.
     #[metrics(buckets = NODE_COUNT_BUCKETS)]
     pub apply_patch_copied_hashes: Histogram<usize>,
+    /// Total number of stale keys persisted to RocksDB in a single patch.
+    #[metrics(buckets = NODE_COUNT_BUCKETS)]
+    pub apply_patch_stale_keys_count: Histogram<usize>,
 
     /// Number of hashes included in a generated proof.
     #[metrics(buckets = NODE_COUNT_BUCKETS)]
@@ -104,3 +107,69 @@ pub(crate) struct MerkleTreeMetrics {
 
 #[vise::register]
 pub(crate) static METRICS: vise::Global<MerkleTreeMetrics> = vise::Global::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "bound", rename_all = "snake_case")]
+enum Bound {
+    Start,
+    End,
+}
+
+const LARGE_NODE_COUNT_BUCKETS: Buckets = Buckets::values(&[
+    1_000.0,
+    2_000.0,
+    5_000.0,
+    10_000.0,
+    20_000.0,
+    50_000.0,
+    100_000.0,
+    200_000.0,
+    500_000.0,
+    1_000_000.0,
+    2_000_000.0,
+    5_000_000.0,
+]);
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "zk_os_merkle_tree_pruning")]
+pub(crate) struct PruningMetrics {
+    /// Minimum Merkle tree version targeted after a single pruning iteration. The iteration
+    /// may not remove all stale keys to this version if there are too many.
+    target_retained_version: Gauge<u64>,
+    /// Number of pruned node keys on a specific pruning iteration.
+    #[metrics(buckets = LARGE_NODE_COUNT_BUCKETS)]
+    key_count: Histogram<usize>,
+    /// Lower and upper boundaries on the new stale key versions deleted
+    /// during a pruning iteration. The lower boundary is inclusive, the upper one is exclusive.
+    deleted_stale_key_versions: Family<Bound, Gauge<u64>>,
+    /// Time spent loading stale keys per pruning iteration.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub load_stale_keys_latency: Histogram<Duration>,
+    /// Time spent removing stale keys from RocksDB per pruning iteration.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub apply_patch_latency: Histogram<Duration>,
+}
+
+#[vise::register]
+pub(crate) static PRUNING_METRICS: vise::Global<PruningMetrics> = vise::Global::new();
+
+/// Stats for a single pruning iteration.
+#[derive(Debug)]
+pub struct PruningStats {
+    pub target_retained_version: u64,
+    pub pruned_key_count: usize,
+    pub deleted_stale_key_versions: ops::Range<u64>,
+}
+
+impl PruningStats {
+    pub(crate) fn report(&self) {
+        PRUNING_METRICS
+            .target_retained_version
+            .set(self.target_retained_version);
+        PRUNING_METRICS.key_count.observe(self.pruned_key_count);
+        PRUNING_METRICS.deleted_stale_key_versions[&Bound::Start]
+            .set(self.deleted_stale_key_versions.start);
+        PRUNING_METRICS.deleted_stale_key_versions[&Bound::End]
+            .set(self.deleted_stale_key_versions.end);
+    }
+}
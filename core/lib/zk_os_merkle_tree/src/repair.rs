@@ -0,0 +1,208 @@
+//! Verification and repair of stale keys recorded for the tree.
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Context as _;
+
+use crate::{
+    leaf_nibbles, max_node_children,
+    storage::PruneDatabase,
+    types::{Node, NodeKey},
+    MerkleTree, TreeParams,
+};
+
+/// Stats returned by [`MerkleTree::repair_stale_keys()`].
+#[derive(Debug, Clone, Default)]
+pub struct StaleKeysRepairStats {
+    /// Number of checked tree versions.
+    pub checked_version_count: u64,
+    /// Number of removed bogus stale keys.
+    pub repaired_key_count: usize,
+}
+
+impl<DB: PruneDatabase, P: TreeParams> MerkleTree<DB, P> {
+    /// Returns stale keys recorded for the specified `version` that are bogus, i.e., are referenced by the tree
+    /// at this version, or are recorded for a non-existing version. If bogus stale keys are left in the database,
+    /// pruning will remove nodes that are still in use, breaking the tree.
+    ///
+    /// # Errors
+    ///
+    /// Proxies database errors.
+    pub fn bogus_stale_keys(&self, version: u64) -> anyhow::Result<Vec<NodeKey>> {
+        let stale_keys = self.db.stale_keys(version);
+        if stale_keys.is_empty() {
+            return Ok(vec![]);
+        }
+        let Some(root) = self.db.try_root(version)? else {
+            // Stale keys for a version are removed before the version root is pruned, so the version must be missing
+            // (e.g., stale keys were left after truncation).
+            return Ok(stale_keys);
+        };
+
+        // Nodes replaced by `version` must have a lesser version. For roots, this is the only check necessary.
+        let (mut stale_keys, mut bogus_keys): (Vec<_>, Vec<_>) = stale_keys
+            .into_iter()
+            .partition(|key| key.version < version);
+        stale_keys.retain(|key| key.nibble_count > 0);
+        stale_keys.sort_unstable_by_key(|key| (key.nibble_count, key.index_on_level));
+
+        // Traverse the tree level by level, only loading internal nodes on the paths to the stale keys.
+        let mut parent_level = HashMap::from([(0_u64, root.root_node)]);
+        for nibble_count in 1..=leaf_nibbles::<P>() {
+            let mut next_level_keys = vec![];
+            let mut loaded_indices = HashSet::new();
+            for key in &stale_keys {
+                if key.nibble_count < nibble_count {
+                    continue;
+                }
+                let bit_shift = (key.nibble_count - nibble_count) * P::INTERNAL_NODE_DEPTH;
+                let index_on_level = key.index_on_level >> bit_shift;
+                let parent_idx = index_on_level >> P::INTERNAL_NODE_DEPTH;
+                let child_idx = (index_on_level % u64::from(max_node_children::<P>())) as usize;
+                let child_ref = parent_level
+                    .get(&parent_idx)
+                    .and_then(|parent| parent.children.get(child_idx));
+                let Some(child_ref) = child_ref else {
+                    // The node position is not present in the tree at `version`, so the key cannot be referenced.
+                    continue;
+                };
+
+                if key.nibble_count == nibble_count {
+                    if child_ref.version == key.version {
+                        bogus_keys.push(*key);
+                    }
+                } else if loaded_indices.insert(index_on_level) {
+                    next_level_keys.push(NodeKey {
+                        version: child_ref.version,
+                        nibble_count,
+                        index_on_level,
+                    });
+                }
+            }
+
+            if next_level_keys.is_empty() {
+                break;
+            }
+            let nodes = self
+                .db
+                .try_nodes(&next_level_keys)
+                .with_context(|| format!("failed loading nodes at version {version}"))?;
+            parent_level = next_level_keys
+                .iter()
+                .zip(nodes)
+                .map(|(key, node)| match node {
+                    Node::Internal(node) => (key.index_on_level, node),
+                    Node::Leaf(_) => unreachable!("leaves are never loaded"),
+                })
+                .collect();
+        }
+        Ok(bogus_keys)
+    }
+
+    /// Checks stale keys for all versions that have them and removes bogus ones (see [`Self::bogus_stale_keys()`]).
+    ///
+    /// # Errors
+    ///
+    /// Proxies database errors.
+    pub fn repair_stale_keys(&mut self) -> anyhow::Result<StaleKeysRepairStats> {
+        let mut stats = StaleKeysRepairStats::default();
+        let Some(min_version) = self.db.min_stale_key_version() else {
+            tracing::info!("No stale keys in the tree; nothing to repair");
+            return Ok(stats);
+        };
+        let latest_version = self.latest_version()?.unwrap_or(0);
+
+        for version in min_version..=latest_version.max(min_version) {
+            let bogus_keys = self.bogus_stale_keys(version)?;
+            stats.checked_version_count += 1;
+            if bogus_keys.is_empty() {
+                continue;
+            }
+
+            tracing::warn!(
+                version,
+                bogus_keys.len = bogus_keys.len(),
+                bogus_keys.sample = ?&bogus_keys[..bogus_keys.len().min(5)],
+                "Found bogus stale keys; removing"
+            );
+            stats.repaired_key_count += bogus_keys.len();
+            self.db
+                .remove_stale_keys(version, &bogus_keys)
+                .with_context(|| format!("failed removing stale keys for version {version}"))?;
+        }
+        tracing::info!("Finished repairing stale keys: {stats:?}");
+        Ok(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_basic_types::H256;
+
+    use super::*;
+    use crate::{DefaultTreeParams, PatchSet, TreeEntry};
+
+    fn create_tree<DB: PruneDatabase>(db: DB) -> MerkleTree<DB> {
+        let mut tree = MerkleTree::new(db).unwrap();
+        let entries: Vec<_> = (0..50)
+            .map(|i| TreeEntry {
+                key: H256::from_low_u64_be(i + 1),
+                value: H256::from_low_u64_be(i),
+            })
+            .collect();
+        for chunk in entries.chunks(10) {
+            tree.extend(chunk).unwrap();
+        }
+        let updates: Vec<_> = entries
+            .iter()
+            .step_by(3)
+            .map(|entry| TreeEntry {
+                value: H256::repeat_byte(1),
+                ..*entry
+            })
+            .collect();
+        tree.extend(&updates).unwrap();
+        tree
+    }
+
+    #[test]
+    fn stale_keys_for_normal_tree_are_not_bogus() {
+        let mut tree = create_tree(PatchSet::default());
+        let latest_version = tree.latest_version().unwrap().unwrap();
+        for version in 0..=latest_version {
+            assert!(tree.bogus_stale_keys(version).unwrap().is_empty());
+        }
+        assert!(!tree.db.stale_keys(latest_version).is_empty());
+
+        let stats = tree.repair_stale_keys().unwrap();
+        assert_eq!(stats.checked_version_count, latest_version);
+        assert_eq!(stats.repaired_key_count, 0);
+    }
+
+    #[test]
+    fn detecting_and_repairing_bogus_stale_keys() {
+        let mut tree = create_tree(PatchSet::default());
+        let latest_version = tree.latest_version().unwrap().unwrap();
+
+        // Emulate stale keys incorrectly recorded for the latest version: these nodes are still in use.
+        // Leaf #3 was inserted in the first version and wasn't changed since.
+        let live_leaf_key = NodeKey {
+            version: 0,
+            nibble_count: leaf_nibbles::<DefaultTreeParams>(),
+            index_on_level: 3,
+        };
+        let live_root_key = NodeKey::root(latest_version);
+        tree.db
+            .stale_keys_by_version_mut()
+            .insert(latest_version, vec![live_leaf_key, live_root_key]);
+
+        let mut bogus_keys = tree.bogus_stale_keys(latest_version).unwrap();
+        bogus_keys.sort_unstable_by_key(|key| key.nibble_count);
+        assert_eq!(bogus_keys, [live_root_key, live_leaf_key]);
+
+        let stats = tree.repair_stale_keys().unwrap();
+        assert_eq!(stats.repaired_key_count, 2);
+        assert!(tree.db.stale_keys(latest_version).is_empty());
+        assert!(tree.bogus_stale_keys(latest_version).unwrap().is_empty());
+    }
+}
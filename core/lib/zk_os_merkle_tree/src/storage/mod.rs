@@ -49,6 +49,9 @@ pub trait Database: Send + Sync {
 
     /// Truncates the tree. `manifest` specifies the new number of tree versions, and `truncated_versions`
     /// contains the last version *before* the truncation. This operation should be atomic.
+    ///
+    /// Besides key indices, truncation must remove stale keys recorded for the truncated versions; otherwise,
+    /// nodes used by the versions written after truncation may be pruned.
     fn truncate(
         &mut self,
         manifest: Manifest,
@@ -86,6 +89,71 @@ impl<DB: Database + ?Sized> Database for &mut DB {
     }
 }
 
+/// Analogue of [`PatchSet`] used when pruning past versions of the Merkle tree.
+#[derive(Debug)]
+pub struct PrunePatchSet {
+    /// Keys that need to be removed from the tree. Logically, the version of each key
+    /// should be less than `min_retained_version`.
+    pruned_node_keys: Vec<NodeKey>,
+    /// Range of replacing versions for stale keys that need to be removed.
+    deleted_stale_key_versions: ops::Range<u64>,
+}
+
+impl PrunePatchSet {
+    pub(crate) fn new(
+        pruned_node_keys: Vec<NodeKey>,
+        deleted_stale_key_versions: ops::Range<u64>,
+    ) -> Self {
+        Self {
+            pruned_node_keys,
+            deleted_stale_key_versions,
+        }
+    }
+}
+
+/// Functionality to prune past versions of the Merkle tree.
+pub trait PruneDatabase: Database {
+    /// Returns the minimum new version for stale keys present in this database, or `None`
+    /// if there are no stale keys.
+    fn min_stale_key_version(&self) -> Option<u64>;
+
+    /// Returns a list of node keys obsoleted in the specified `version` of the tree.
+    fn stale_keys(&self, version: u64) -> Vec<NodeKey>;
+
+    /// Atomically prunes the tree and removes the corresponding stale keys.
+    ///
+    /// # Errors
+    ///
+    /// Propagates database I/O errors.
+    fn prune(&mut self, patch: PrunePatchSet) -> anyhow::Result<()>;
+
+    /// Removes the specified stale keys recorded for `version` without touching the tree nodes. Used to repair
+    /// incorrectly recorded stale keys.
+    ///
+    /// # Errors
+    ///
+    /// Propagates database I/O errors.
+    fn remove_stale_keys(&mut self, version: u64, keys: &[NodeKey]) -> anyhow::Result<()>;
+}
+
+impl<DB: PruneDatabase + ?Sized> PruneDatabase for &mut DB {
+    fn min_stale_key_version(&self) -> Option<u64> {
+        (**self).min_stale_key_version()
+    }
+
+    fn stale_keys(&self, version: u64) -> Vec<NodeKey> {
+        (**self).stale_keys(version)
+    }
+
+    fn prune(&mut self, patch: PrunePatchSet) -> anyhow::Result<()> {
+        (**self).prune(patch)
+    }
+
+    fn remove_stale_keys(&mut self, version: u64, keys: &[NodeKey]) -> anyhow::Result<()> {
+        (**self).remove_stale_keys(version, keys)
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 #[cfg_attr(test, derive(PartialEq))]
 struct InsertedKeyEntry {
@@ -127,6 +195,15 @@ impl PartialPatchSet {
     fn total_internal_nodes(&self) -> usize {
         self.internal.iter().map(HashMap::len).sum()
     }
+
+    fn remove_node(&mut self, nibble_count: u8, index_on_level: u64) {
+        let nibble_count = usize::from(nibble_count);
+        if nibble_count < self.internal.len() {
+            self.internal[nibble_count].remove(&index_on_level);
+        } else {
+            self.leaves.remove(&index_on_level);
+        }
+    }
 }
 
 /// Immutable in-memory changeset that can atomically applied to a [`Database`].
@@ -136,7 +213,8 @@ pub struct PatchSet {
     patches_by_version: HashMap<u64, PartialPatchSet>,
     // We maintain a joint index for all versions to make it easier to use `PatchSet` as a `Database` or in a `Patched` wrapper.
     sorted_new_leaves: BTreeMap<H256, InsertedKeyEntry>,
-    // TODO: stale keys
+    /// Keys of the nodes replaced in each version.
+    stale_keys_by_version: HashMap<u64, Vec<NodeKey>>,
 }
 
 impl PatchSet {
@@ -187,6 +265,11 @@ impl PatchSet {
     pub(crate) fn manifest_mut(&mut self) -> &mut Manifest {
         &mut self.manifest
     }
+
+    #[cfg(test)]
+    pub(crate) fn stale_keys_by_version_mut(&mut self) -> &mut HashMap<u64, Vec<NodeKey>> {
+        &mut self.stale_keys_by_version
+    }
 }
 
 impl Database for PatchSet {
@@ -213,6 +296,8 @@ impl Database for PatchSet {
         Ok(self
             .patches_by_version
             .get(&version)
+            // The root may be absent if it was pruned.
+            .filter(|patch| patch.internal[0].contains_key(&0))
             .map(PartialPatchSet::root))
     }
 
@@ -242,6 +327,8 @@ impl Database for PatchSet {
         self.manifest = patch.manifest;
         self.patches_by_version.extend(patch.patches_by_version);
         self.sorted_new_leaves.extend(patch.sorted_new_leaves);
+        self.stale_keys_by_version
+            .extend(patch.stale_keys_by_version);
         Ok(())
     }
 
@@ -256,12 +343,48 @@ impl Database for PatchSet {
         // This requires a full scan, but we assume there aren't that many data in a patch (it's mostly used as a `Database` for testing).
         self.sorted_new_leaves
             .retain(|_, entry| entry.inserted_at < new_version_count);
+        self.stale_keys_by_version
+            .retain(|&version, _| version < new_version_count);
 
         self.manifest = manifest;
         Ok(())
     }
 }
 
+impl PruneDatabase for PatchSet {
+    fn min_stale_key_version(&self) -> Option<u64> {
+        self.stale_keys_by_version
+            .iter()
+            .filter_map(|(&version, keys)| (!keys.is_empty()).then_some(version))
+            .min()
+    }
+
+    fn stale_keys(&self, version: u64) -> Vec<NodeKey> {
+        self.stale_keys_by_version
+            .get(&version)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn prune(&mut self, patch: PrunePatchSet) -> anyhow::Result<()> {
+        for key in &patch.pruned_node_keys {
+            if let Some(sub_patch) = self.patches_by_version.get_mut(&key.version) {
+                sub_patch.remove_node(key.nibble_count, key.index_on_level);
+            }
+        }
+        self.stale_keys_by_version
+            .retain(|version, _| !patch.deleted_stale_key_versions.contains(version));
+        Ok(())
+    }
+
+    fn remove_stale_keys(&mut self, version: u64, keys: &[NodeKey]) -> anyhow::Result<()> {
+        if let Some(stale_keys) = self.stale_keys_by_version.get_mut(&version) {
+            stale_keys.retain(|key| !keys.contains(key));
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug)]
 pub struct Patched<DB> {
     inner: DB,
@@ -35,20 +35,22 @@ pub(crate) struct TreeUpdate {
 }
 
 impl TreeUpdate {
-    pub(crate) fn for_empty_tree(entries: &[TreeEntry]) -> anyhow::Result<Self> {
+    /// Creates an update for an empty tree. `version` is the first tree version to be created;
+    /// it is non-zero if the tree is recovered from a snapshot.
+    pub(crate) fn for_empty_tree(version: u64, entries: &[TreeEntry]) -> anyhow::Result<Self> {
         let mut sorted_new_leaves = BTreeMap::from([
             (
                 H256::zero(),
                 InsertedKeyEntry {
                     index: 0,
-                    inserted_at: 0,
+                    inserted_at: version,
                 },
             ),
             (
                 H256::repeat_byte(0xff),
                 InsertedKeyEntry {
                     index: 1,
-                    inserted_at: 0,
+                    inserted_at: version,
                 },
             ),
         ]);
@@ -57,7 +59,7 @@ impl TreeUpdate {
                 entry.key,
                 InsertedKeyEntry {
                     index: i as u64 + 2,
-                    inserted_at: 0,
+                    inserted_at: version,
                 },
             )
         }));
@@ -98,7 +100,7 @@ impl TreeUpdate {
         }
 
         Ok(Self {
-            version: 0,
+            version,
             sorted_new_leaves,
             updates: vec![],
             inserts,
@@ -125,25 +127,36 @@ impl TreeUpdate {
 pub(crate) struct FinalTreeUpdate {
     pub(super) version: u64,
     pub(super) sorted_new_leaves: BTreeMap<H256, InsertedKeyEntry>,
+    /// Keys of the nodes replaced in this update.
+    pub(super) stale_keys: Vec<NodeKey>,
 }
 
 impl PartialPatchSet {
     /// Updates ancestor's `ChildRef` version for all loaded internal nodes. This should be called before adding new leaves
     /// to the tree; it works because the loaded leaves are exactly the leaves for which ancestor versions must be updated.
-    fn update_ancestor_versions<P: TreeParams>(&mut self, version: u64) {
+    ///
+    /// Returns keys of all nodes replaced by the update (i.e., the loaded leaves and their non-root ancestors).
+    fn update_ancestor_versions<P: TreeParams>(&mut self, version: u64) -> Vec<NodeKey> {
         let mut indices: Vec<_> = self.leaves.keys().copied().collect();
         indices.sort_unstable();
+        let mut stale_keys = vec![];
 
-        for internal_level in self.internal.iter_mut().rev() {
+        for (nibble_count, internal_level) in self.internal.iter_mut().enumerate().rev() {
+            let child_nibble_count = nibble_count as u8 + 1;
             let mut prev_index = None;
             indices = indices
                 .into_iter()
                 .filter_map(|idx| {
                     let parent_idx = idx >> P::INTERNAL_NODE_DEPTH;
                     let parent = internal_level.get_mut(&parent_idx).unwrap();
-                    parent
-                        .child_mut((idx % u64::from(max_node_children::<P>())) as usize)
-                        .version = version;
+                    let child_ref =
+                        parent.child_mut((idx % u64::from(max_node_children::<P>())) as usize);
+                    stale_keys.push(NodeKey {
+                        version: child_ref.version,
+                        nibble_count: child_nibble_count,
+                        index_on_level: idx,
+                    });
+                    child_ref.version = version;
 
                     if prev_index == Some(parent_idx) {
                         None
@@ -154,6 +167,7 @@ impl PartialPatchSet {
                 })
                 .collect();
         }
+        stale_keys
     }
 
     fn remove_readonly_nodes(&mut self, updated_version: u64) -> usize {
@@ -401,7 +415,11 @@ impl<P: TreeParams> WorkingPatchSet<P> {
         }
 
         // Update ancestor versions based on the remaining leaves.
-        this.update_ancestor_versions::<P>(version);
+        let mut stale_keys = this.update_ancestor_versions::<P>(version);
+        if this.leaf_count > 0 {
+            // The root of the previous version is always replaced. (If the tree is empty, there's no previous version.)
+            stale_keys.push(NodeKey::root(version - 1));
+        }
         if readonly_leaf_indices_len > 0 {
             // Filter out all internal nodes that were not updated (= don't have updated child refs).
             let removed_node_count = this.remove_readonly_nodes(version);
@@ -478,6 +496,7 @@ impl<P: TreeParams> WorkingPatchSet<P> {
         FinalTreeUpdate {
             version,
             sorted_new_leaves: update.sorted_new_leaves,
+            stale_keys,
         }
     }
 
@@ -533,6 +552,7 @@ impl<P: TreeParams> WorkingPatchSet<P> {
             },
             patches_by_version: HashMap::from([(update.version, this)]),
             sorted_new_leaves: update.sorted_new_leaves,
+            stale_keys_by_version: HashMap::from([(update.version, update.stale_keys)]),
         };
         (patch, output)
     }
@@ -10,7 +10,7 @@ use zksync_storage::{db::NamedColumnFamily, rocksdb, rocksdb::DBPinnableSlice, R
 use crate::{
     errors::{DeserializeContext, DeserializeErrorKind},
     metrics::METRICS,
-    storage::{InsertedKeyEntry, PartialPatchSet, PatchSet},
+    storage::{InsertedKeyEntry, PartialPatchSet, PatchSet, PruneDatabase, PrunePatchSet},
     types::{InternalNode, KeyLookup, Leaf, Manifest, Node, NodeKey, Root},
     Database, DeserializeError,
 };
@@ -25,6 +25,24 @@ impl NodeKey {
         buffer[9..].copy_from_slice(&self.index_on_level.to_be_bytes());
         buffer
     }
+
+    fn from_db_key(buffer: &[u8]) -> Self {
+        assert_eq!(buffer.len(), Self::DB_KEY_LEN, "Invalid node key length");
+        Self {
+            version: u64::from_be_bytes(buffer[..8].try_into().unwrap()),
+            nibble_count: buffer[8],
+            index_on_level: u64::from_be_bytes(buffer[9..].try_into().unwrap()),
+        }
+    }
+
+    /// Key in the [`MerkleTreeColumnFamily::StaleKeys`] CF. The key is prefixed by the version at which
+    /// the node became stale, so that stale keys can be efficiently iterated over by this version.
+    fn as_stale_db_key(&self, stale_since: u64) -> [u8; 8 + Self::DB_KEY_LEN] {
+        let mut buffer = [0_u8; 8 + Self::DB_KEY_LEN];
+        buffer[..8].copy_from_slice(&stale_since.to_be_bytes());
+        buffer[8..].copy_from_slice(&self.as_db_key());
+        buffer
+    }
 }
 
 /// RocksDB column families used by the tree.
@@ -35,17 +53,19 @@ pub enum MerkleTreeColumnFamily {
     Tree,
     /// Resolves keys to (index, version) tuples.
     KeyIndices,
-    // TODO: stale keys
+    /// Column family containing stale node keys that are eventually removed by the pruning logic.
+    StaleKeys,
 }
 
 impl NamedColumnFamily for MerkleTreeColumnFamily {
     const DB_NAME: &'static str = "zkos_merkle_tree";
-    const ALL: &'static [Self] = &[Self::Tree, Self::KeyIndices];
+    const ALL: &'static [Self] = &[Self::Tree, Self::KeyIndices, Self::StaleKeys];
 
     fn name(&self) -> &'static str {
         match self {
             Self::Tree => "default",
             Self::KeyIndices => "key_indices",
+            Self::StaleKeys => "stale_keys",
         }
     }
 
@@ -249,6 +269,7 @@ impl Database for RocksDBWrapper {
 
         let copied_hashes = patch.copied_hashes_count();
         let new_leaves = patch.sorted_new_leaves.len();
+        let stale_keys: usize = patch.stale_keys_by_version.values().map(Vec::len).sum();
         let total_leaves: usize = patch
             .patches_by_version
             .values()
@@ -312,6 +333,13 @@ impl Database for RocksDBWrapper {
             }
         }
 
+        let stale_keys_cf = MerkleTreeColumnFamily::StaleKeys;
+        for (version, keys) in patch.stale_keys_by_version {
+            for key in keys {
+                write_batch.put_cf(stale_keys_cf, &key.as_stale_db_key(version), &[]);
+            }
+        }
+
         METRICS
             .apply_patch_key_lookup_entries_count
             .observe(new_leaves);
@@ -320,12 +348,14 @@ impl Database for RocksDBWrapper {
             .apply_patch_internal_nodes_count
             .observe(total_internal_nodes);
         METRICS.apply_patch_copied_hashes.observe(copied_hashes);
+        METRICS.apply_patch_stale_keys_count.observe(stale_keys);
         tracing::debug!(
             total_size = write_batch.size_in_bytes(),
             new_leaves,
             total_leaves,
             total_internal_nodes,
             copied_hashes,
+            stale_keys,
             "writing to RocksDB"
         );
 
@@ -395,6 +425,15 @@ impl Database for RocksDBWrapper {
             first_new_leaf_index = new_leaf_count;
         }
 
+        // Remove stale keys for the truncated versions. Otherwise, nodes they point to may be pruned even if they are used
+        // by tree versions written after the truncation.
+        let first_version = &manifest.version_count.to_be_bytes() as &[_];
+        let last_version = &truncated_versions.end.to_be_bytes();
+        write_batch.delete_range_cf(
+            MerkleTreeColumnFamily::StaleKeys,
+            first_version..last_version,
+        );
+
         self.db
             .write(write_batch)
             .context("Failed writing a batch to RocksDB")?;
@@ -402,6 +441,61 @@ impl Database for RocksDBWrapper {
     }
 }
 
+impl PruneDatabase for RocksDBWrapper {
+    fn min_stale_key_version(&self) -> Option<u64> {
+        let stale_keys_cf = MerkleTreeColumnFamily::StaleKeys;
+        let (raw_key, _) = self.db.prefix_iterator_cf(stale_keys_cf, &[]).next()?;
+        let version_prefix: [u8; 8] = raw_key[..8].try_into().unwrap();
+        Some(u64::from_be_bytes(version_prefix))
+    }
+
+    fn stale_keys(&self, version: u64) -> Vec<NodeKey> {
+        let stale_keys_cf = MerkleTreeColumnFamily::StaleKeys;
+        let version_prefix = version.to_be_bytes();
+        let keys = self
+            .db
+            .prefix_iterator_cf(stale_keys_cf, &version_prefix)
+            .map(|(raw_key, _)| {
+                debug_assert_eq!(raw_key[..8], version_prefix);
+                NodeKey::from_db_key(&raw_key[8..])
+            });
+        keys.collect()
+    }
+
+    fn prune(&mut self, patch: PrunePatchSet) -> anyhow::Result<()> {
+        let mut write_batch = self.db.new_write_batch();
+
+        let tree_cf = MerkleTreeColumnFamily::Tree;
+        for pruned_key in &patch.pruned_node_keys {
+            write_batch.delete_cf(tree_cf, &pruned_key.as_db_key());
+        }
+
+        let first_version = &patch.deleted_stale_key_versions.start.to_be_bytes() as &[_];
+        let last_version = &patch.deleted_stale_key_versions.end.to_be_bytes();
+        write_batch.delete_range_cf(
+            MerkleTreeColumnFamily::StaleKeys,
+            first_version..last_version,
+        );
+
+        self.db
+            .write(write_batch)
+            .context("Failed writing a batch to RocksDB")
+    }
+
+    fn remove_stale_keys(&mut self, version: u64, keys: &[NodeKey]) -> anyhow::Result<()> {
+        let mut write_batch = self.db.new_write_batch();
+        for key in keys {
+            write_batch.delete_cf(
+                MerkleTreeColumnFamily::StaleKeys,
+                &key.as_stale_db_key(version),
+            );
+        }
+        self.db
+            .write(write_batch)
+            .context("Failed writing a batch to RocksDB")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::{BTreeMap, HashMap};
@@ -625,4 +719,27 @@ mod tests {
         let all_keys = get_all_keys(&tree.db);
         assert_eq!(all_keys, [H256::zero(), H256::repeat_byte(0xff)]);
     }
+
+    #[test]
+    fn truncating_tree_removes_stale_keys() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = RocksDBWrapper::new(temp_dir.path()).unwrap();
+
+        let mut tree = MerkleTree::new(db).unwrap();
+        for i in 1..=3 {
+            tree.extend(&[TreeEntry {
+                key: H256::repeat_byte(i),
+                value: H256::repeat_byte(i + 1),
+            }])
+            .unwrap();
+        }
+        assert_eq!(tree.db.min_stale_key_version(), Some(1));
+        for version in 1..=2 {
+            assert!(!tree.db.stale_keys(version).is_empty());
+        }
+
+        tree.truncate_recent_versions(2).unwrap();
+        assert!(!tree.db.stale_keys(1).is_empty());
+        assert!(tree.db.stale_keys(2).is_empty());
+    }
 }
@@ -7,7 +7,7 @@ use crate::{leaf_nibbles, DefaultTreeParams, MerkleTree, TreeEntry, TreeParams};
 
 #[test]
 fn creating_min_update_for_empty_tree() {
-    let update = TreeUpdate::for_empty_tree(&[]).unwrap();
+    let update = TreeUpdate::for_empty_tree(0, &[]).unwrap();
     assert_eq!(update.version, 0);
     assert!(update.updates.is_empty());
 
@@ -34,16 +34,19 @@ fn creating_min_update_for_empty_tree() {
 
 #[test]
 fn creating_non_empty_update_for_empty_tree() {
-    let update = TreeUpdate::for_empty_tree(&[
-        TreeEntry {
-            key: H256::repeat_byte(2),
-            value: H256::from_low_u64_be(1),
-        },
-        TreeEntry {
-            key: H256::repeat_byte(1),
-            value: H256::from_low_u64_be(2),
-        },
-    ])
+    let update = TreeUpdate::for_empty_tree(
+        0,
+        &[
+            TreeEntry {
+                key: H256::repeat_byte(2),
+                value: H256::from_low_u64_be(1),
+            },
+            TreeEntry {
+                key: H256::repeat_byte(1),
+                value: H256::from_low_u64_be(2),
+            },
+        ],
+    )
     .unwrap();
     assert_eq!(update.version, 0);
     assert!(update.updates.is_empty());
@@ -119,7 +122,7 @@ fn test_creating_empty_tree<P: TreeParams<Hasher = Blake2Hasher>>() {
     }
 
     let mut patch = WorkingPatchSet::<P>::empty();
-    let final_update = patch.update(TreeUpdate::for_empty_tree(&[]).unwrap());
+    let final_update = patch.update(TreeUpdate::for_empty_tree(0, &[]).unwrap());
     assert_eq!(final_update.version, 0);
 
     {
@@ -173,10 +176,13 @@ where
     }
 
     let mut patch = WorkingPatchSet::<P>::empty();
-    let update = TreeUpdate::for_empty_tree(&[TreeEntry {
-        key: H256::repeat_byte(0x01),
-        value: H256::repeat_byte(0x10),
-    }])
+    let update = TreeUpdate::for_empty_tree(
+        0,
+        &[TreeEntry {
+            key: H256::repeat_byte(0x01),
+            value: H256::repeat_byte(0x10),
+        }],
+    )
     .unwrap();
     let final_update = patch.update(update);
 
@@ -212,7 +218,7 @@ where
     }
 
     let mut patch = WorkingPatchSet::<P>::empty();
-    let final_update = patch.update(TreeUpdate::for_empty_tree(&[]).unwrap());
+    let final_update = patch.update(TreeUpdate::for_empty_tree(0, &[]).unwrap());
     let (patch, ..) = patch.finalize(&Blake2Hasher, final_update);
 
     let merkle_tree = MerkleTree::<_, P>::with_hasher(patch, Blake2Hasher).unwrap();
@@ -301,7 +307,7 @@ where
     }
 
     let mut patch = WorkingPatchSet::<P>::empty();
-    let final_update = patch.update(TreeUpdate::for_empty_tree(&[]).unwrap());
+    let final_update = patch.update(TreeUpdate::for_empty_tree(0, &[]).unwrap());
     let (patch, ..) = patch.finalize(&Blake2Hasher, final_update);
 
     let mut merkle_tree = MerkleTree::<_, P>::with_hasher(patch, Blake2Hasher).unwrap();
@@ -348,8 +354,29 @@ where
     }
 
     let final_update = patch.update(update);
+    // The updated leaf, all its ancestors and the previous root must become stale.
+    let leaf_key = NodeKey {
+        version: 1,
+        nibble_count: leaf_nibbles::<P>(),
+        index_on_level: 2,
+    };
+    let expected_stale_keys: HashSet<_> = (1..leaf_nibbles::<P>())
+        .map(|nibble_count| NodeKey {
+            version: 1,
+            nibble_count,
+            index_on_level: 0,
+        })
+        .chain([leaf_key, NodeKey::root(1)])
+        .collect();
+    let stale_keys: HashSet<_> = final_update.stale_keys.iter().copied().collect();
+    assert_eq!(stale_keys, expected_stale_keys);
+
     let (new_patch, ..) = patch.finalize(&Blake2Hasher, final_update);
     merkle_tree.db.apply_patch(new_patch).unwrap();
+    assert_eq!(
+        merkle_tree.db.stale_keys(2).len(),
+        expected_stale_keys.len()
+    );
 
     let expected_root_hash: H256 =
         "0x4b6bd61930a8dee1bc412d8a38780f098137be9edbf29c078546b7492748d251"
@@ -413,6 +440,23 @@ where
     );
 
     let final_update = patch.update(update);
+    // Both loaded leaves, their common ancestors and the previous root must become stale.
+    let expected_stale_keys: HashSet<_> = (1..leaf_nibbles::<P>())
+        .map(|nibble_count| NodeKey {
+            version: 0,
+            nibble_count,
+            index_on_level: 0,
+        })
+        .chain([1, 2].map(|index_on_level| NodeKey {
+            version: 0,
+            nibble_count: leaf_nibbles::<P>(),
+            index_on_level,
+        }))
+        .chain([NodeKey::root(0)])
+        .collect();
+    let stale_keys: HashSet<_> = final_update.stale_keys.iter().copied().collect();
+    assert_eq!(stale_keys, expected_stale_keys);
+
     let (new_patch, ..) = patch.finalize(&Blake2Hasher, final_update);
     merkle_tree.db.apply_patch(new_patch).unwrap();
 
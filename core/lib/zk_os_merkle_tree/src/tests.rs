@@ -427,6 +427,58 @@ fn read_proofs() {
     test_read_proofs(PatchSet::default());
 }
 
+fn test_recovering_tree(db: impl Database) {
+    const RNG_SEED: u64 = 1234;
+    const RECOVERY_VERSION: u64 = 42;
+
+    let mut rng = StdRng::seed_from_u64(RNG_SEED);
+    let nodes = (0..500).map(|_| TreeEntry {
+        key: H256(rng.gen()),
+        value: H256(rng.gen()),
+    });
+    let entries: Vec<_> = nodes.collect();
+    let (recovered_entries, new_entries) = entries.split_at(300);
+
+    let mut tree = MerkleTree::new(db).unwrap();
+    let output = tree.recover(RECOVERY_VERSION, recovered_entries).unwrap();
+    assert_eq!(output.root_hash, naive_hash_tree(recovered_entries));
+    assert_eq!(output.leaf_count, recovered_entries.len() as u64 + 2);
+    assert_eq!(tree.latest_version().unwrap(), Some(RECOVERY_VERSION));
+    assert_eq!(tree.root_hash(RECOVERY_VERSION - 1).unwrap(), None);
+    let root_info = tree.root_info(RECOVERY_VERSION).unwrap().unwrap();
+    assert_eq!(root_info.root_hash, output.root_hash);
+    assert_eq!(root_info.leaf_count, output.leaf_count);
+    tree.verify_consistency(RECOVERY_VERSION).unwrap();
+
+    let err = tree.recover(0, &[]).unwrap_err().to_string();
+    assert!(err.contains("non-empty tree"), "{err}");
+
+    let (new_output, proof) = tree.extend_with_proof(new_entries, &[]).unwrap();
+    proof
+        .verify(&Blake2Hasher, 64, Some(output), new_entries, &[])
+        .unwrap();
+    assert_eq!(new_output.root_hash, naive_hash_tree(&entries));
+    assert_eq!(tree.latest_version().unwrap(), Some(RECOVERY_VERSION + 1));
+    tree.verify_consistency(RECOVERY_VERSION + 1).unwrap();
+
+    let keys: Vec<_> = entries.iter().map(|entry| entry.key).collect();
+    let proof = tree.prove(RECOVERY_VERSION, &keys).unwrap();
+    let tree_view = proof
+        .verify_reads(&Blake2Hasher, 64, output, &keys)
+        .unwrap();
+    for (i, key) in keys.iter().enumerate() {
+        assert_eq!(
+            tree_view.read_entries[key].is_some(),
+            i < recovered_entries.len()
+        );
+    }
+}
+
+#[test]
+fn recovering_tree() {
+    test_recovering_tree(PatchSet::default());
+}
+
 mod rocksdb {
     use tempfile::TempDir;
 
@@ -490,4 +542,11 @@ mod rocksdb {
         let db = RocksDBWrapper::new(temp_dir.path()).unwrap();
         test_using_patched_database(db);
     }
+
+    #[test]
+    fn recovering_tree() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = RocksDBWrapper::new(temp_dir.path()).unwrap();
+        test_recovering_tree(db);
+    }
 }
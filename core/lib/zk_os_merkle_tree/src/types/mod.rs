@@ -122,7 +122,7 @@ pub enum KeyLookup {
 }
 
 /// Unique key for a versioned tree node.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct NodeKey {
     /// Tree version.
     pub(crate) version: u64,
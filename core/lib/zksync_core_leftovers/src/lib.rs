@@ -62,6 +62,8 @@ pub enum Component {
     ExternalProofIntegrationApi,
     /// VM runner-based component that allows to test experimental VM features. Doesn't save any data to Postgres.
     VmPlayground,
+    /// VM runner-based component that exports storage diffs, factory deps and events to external sinks.
+    VmRunnerStateDiffExporter,
 }
 
 #[derive(Debug)]
@@ -104,6 +106,9 @@ impl FromStr for Components {
             }
             "vm_runner_bwip" => Ok(Components(vec![Component::VmRunnerBwip])),
             "vm_playground" => Ok(Components(vec![Component::VmPlayground])),
+            "vm_runner_state_diff_exporter" => {
+                Ok(Components(vec![Component::VmRunnerStateDiffExporter]))
+            }
             "external_proof_integration_api" => {
                 Ok(Components(vec![Component::ExternalProofIntegrationApi]))
             }
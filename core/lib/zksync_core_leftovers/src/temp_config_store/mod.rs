@@ -10,7 +10,7 @@ use zksync_config::{
         },
         fri_prover_group::FriProverGroupConfig,
         house_keeper::HouseKeeperConfig,
-        vm_runner::BasicWitnessInputProducerConfig,
+        vm_runner::{BasicWitnessInputProducerConfig, StateDiffExporterConfig},
         wallets::{AddressWallet, EthSender, StateKeeper, TokenMultiplierSetter, Wallet, Wallets},
         CommitmentGeneratorConfig, DatabaseSecrets, ExperimentalVmConfig,
         ExternalPriceApiClientConfig, FriProofCompressorConfig, FriProverConfig,
@@ -22,7 +22,10 @@ use zksync_config::{
     DBConfig, EthConfig, EthWatchConfig, ExternalProofIntegrationApiConfig, GasAdjusterConfig,
     ObjectStoreConfig, PostgresConfig, SnapshotsCreatorConfig,
 };
-use zksync_env_config::FromEnv;
+use zksync_env_config::{
+    da_client::{da_client_config_from_env, SECONDARY_DA_CLIENT_ENV_PREFIX},
+    FromEnv,
+};
 use zksync_protobuf::repr::ProtoRepr;
 use zksync_protobuf_config::proto::secrets::Secrets;
 
@@ -69,9 +72,11 @@ pub struct TempConfigStore {
     pub observability: Option<ObservabilityConfig>,
     pub snapshot_creator: Option<SnapshotsCreatorConfig>,
     pub da_client_config: Option<DAClientConfig>,
+    pub secondary_da_client_config: Option<DAClientConfig>,
     pub da_dispatcher_config: Option<DADispatcherConfig>,
     pub protective_reads_writer_config: Option<ProtectiveReadsWriterConfig>,
     pub basic_witness_input_producer_config: Option<BasicWitnessInputProducerConfig>,
+    pub state_diff_exporter_config: Option<StateDiffExporterConfig>,
     pub core_object_store: Option<ObjectStoreConfig>,
     pub base_token_adjuster_config: Option<BaseTokenAdjusterConfig>,
     pub commitment_generator: Option<CommitmentGeneratorConfig>,
@@ -108,9 +113,11 @@ impl TempConfigStore {
             snapshot_creator: self.snapshot_creator.clone(),
             observability: self.observability.clone(),
             da_client_config: self.da_client_config.clone(),
+            secondary_da_client_config: self.secondary_da_client_config.clone(),
             da_dispatcher_config: self.da_dispatcher_config.clone(),
             protective_reads_writer_config: self.protective_reads_writer_config.clone(),
             basic_witness_input_producer_config: self.basic_witness_input_producer_config.clone(),
+            state_diff_exporter_config: self.state_diff_exporter_config.clone(),
             core_object_store: self.core_object_store.clone(),
             base_token_adjuster: self.base_token_adjuster_config.clone(),
             commitment_generator: self.commitment_generator.clone(),
@@ -193,9 +200,11 @@ fn load_env_config() -> anyhow::Result<TempConfigStore> {
         observability: ObservabilityConfig::from_env().ok(),
         snapshot_creator: SnapshotsCreatorConfig::from_env().ok(),
         da_client_config: DAClientConfig::from_env().ok(),
+        secondary_da_client_config: da_client_config_from_env(SECONDARY_DA_CLIENT_ENV_PREFIX).ok(),
         da_dispatcher_config: DADispatcherConfig::from_env().ok(),
         protective_reads_writer_config: ProtectiveReadsWriterConfig::from_env().ok(),
         basic_witness_input_producer_config: BasicWitnessInputProducerConfig::from_env().ok(),
+        state_diff_exporter_config: StateDiffExporterConfig::from_env().ok(),
         core_object_store: ObjectStoreConfig::from_env().ok(),
         base_token_adjuster_config: BaseTokenAdjusterConfig::from_env().ok(),
         commitment_generator: None,
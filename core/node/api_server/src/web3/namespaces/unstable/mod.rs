@@ -172,6 +172,8 @@ impl UnstableNamespace {
             inclusion_data: da_details.inclusion_data,
             sent_at: da_details.sent_at,
             l2_da_validator: da_details.l2_da_validator,
+            fee: da_details.fee,
+            fee_in_base_token: da_details.fee_in_base_token,
         }))
     }
 
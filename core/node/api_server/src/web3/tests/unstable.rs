@@ -1,5 +1,6 @@
 //! Tests for the `unstable` Web3 namespace.
 
+use zksync_dal::tee_proof_generation_dal::TeeAttestationVerification;
 use zksync_types::tee_types::TeeType;
 use zksync_web3_decl::namespaces::UnstableNamespaceClient;
 
@@ -32,7 +33,11 @@ impl HttpTest for GetTeeProofsTest {
         let mut storage = pool.connection().await.unwrap();
         let mut tee_proof_generation_dal = storage.tee_proof_generation_dal();
         tee_proof_generation_dal
-            .save_attestation(&pubkey, &attestation)
+            .save_attestation(
+                &pubkey,
+                &attestation,
+                &TeeAttestationVerification::unverified(),
+            )
             .await?;
         tee_proof_generation_dal
             .insert_tee_proof_generation_job(batch_no, tee_type)
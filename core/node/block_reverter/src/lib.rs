@@ -376,6 +376,11 @@ impl BlockReverter {
             .vm_runner_dal()
             .delete_bwip_data(last_l1_batch_to_keep)
             .await?;
+        tracing::info!("Rolling back vm_runner_state_diff_exports");
+        transaction
+            .vm_runner_dal()
+            .delete_state_diff_exports(last_l1_batch_to_keep)
+            .await?;
         tracing::info!("Rolling back L2 blocks");
         transaction
             .blocks_dal()
@@ -1,7 +1,8 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use tower_http::cors::CorsLayer;
 use zksync_dal::{ConnectionPool, Core};
+use zksync_types::L2ChainId;
 
 use crate::cache::SupportedCompilersCache;
 
@@ -10,18 +11,27 @@ pub(crate) struct RestApi {
     pub(crate) master_connection_pool: ConnectionPool<Core>,
     pub(crate) replica_connection_pool: ConnectionPool<Core>,
     pub(crate) supported_compilers: Arc<SupportedCompilersCache>,
+    /// ID of the served chain. Used to check chain IDs in the Sourcify-compatible API.
+    pub(crate) l2_chain_id: L2ChainId,
+    /// Max time to wait for verification to complete in the Sourcify-compatible `/verify` endpoint.
+    pub(crate) sourcify_verification_timeout: Duration,
 }
 
 impl RestApi {
+    const DEFAULT_SOURCIFY_VERIFICATION_TIMEOUT: Duration = Duration::from_secs(60);
+
     pub fn new(
         master_connection_pool: ConnectionPool<Core>,
         replica_connection_pool: ConnectionPool<Core>,
+        l2_chain_id: L2ChainId,
     ) -> Self {
         let supported_compilers = SupportedCompilersCache::new(replica_connection_pool.clone());
         Self {
             supported_compilers: Arc::new(supported_compilers),
             master_connection_pool,
             replica_connection_pool,
+            l2_chain_id,
+            sourcify_verification_timeout: Self::DEFAULT_SOURCIFY_VERIFICATION_TIMEOUT,
         }
     }
 
@@ -55,6 +65,19 @@ impl RestApi {
                 "/contract_verification/info/:address",
                 axum::routing::get(Self::verification_info),
             )
+            .route(
+                "/contract_verification/proxy_info/:address",
+                axum::routing::get(Self::proxy_verification_info),
+            )
+            .route(
+                "/api",
+                axum::routing::get(Self::etherscan_get).post(Self::etherscan_post),
+            )
+            .route("/verify", axum::routing::post(Self::sourcify_verify))
+            .route(
+                "/files/any/:chain_id/:address",
+                axum::routing::get(Self::sourcify_files_any),
+            )
             .layer(CorsLayer::permissive())
             .with_state(Arc::new(self))
     }
@@ -13,7 +13,7 @@ use zksync_types::{
     contract_verification::{
         api::{
             CompilerVersions, SourceCodeData, VerificationIncomingRequest, VerificationInfo,
-            VerificationProblem, VerificationRequestStatus,
+            VerificationRequestStatus,
         },
         contract_identifier::ContractIdentifier,
     },
@@ -31,6 +31,7 @@ pub(crate) enum ApiError {
     NoDeployedContract,
     RequestNotFound,
     VerificationInfoNotFound,
+    ProxyNotFound,
     AlreadyVerified,
     ActiveRequestExists(usize),
     Internal(anyhow::Error),
@@ -60,6 +61,7 @@ impl ApiError {
             Self::NoDeployedContract => "There is no deployed contract on this address".into(),
             Self::RequestNotFound => "request not found".into(),
             Self::VerificationInfoNotFound => "verification info not found for address".into(),
+            Self::ProxyNotFound => "contract at address is not a known proxy".into(),
             Self::AlreadyVerified => "contract is already verified".into(),
             Self::ActiveRequestExists(id) => {
                 format!("active request for this contract already exists, ID: {id}")
@@ -67,11 +69,9 @@ impl ApiError {
             Self::Internal(_) => "internal server error".into(),
         }
     }
-}
 
-impl IntoResponse for ApiError {
-    fn into_response(self) -> Response {
-        let status_code = match &self {
+    pub fn status_code(&self) -> StatusCode {
+        match self {
             Self::IncorrectCompilerVersions
             | Self::UnsupportedCompilerVersions
             | Self::MissingZkCompilerVersion
@@ -80,19 +80,26 @@ impl IntoResponse for ApiError {
             | Self::AlreadyVerified
             | Self::ActiveRequestExists(_) => StatusCode::BAD_REQUEST,
 
-            Self::RequestNotFound | Self::VerificationInfoNotFound => StatusCode::NOT_FOUND,
-
-            Self::Internal(err) => {
-                // Do not expose the error details to the client, but log it.
-                tracing::warn!("Internal error: {err:#}");
-                StatusCode::INTERNAL_SERVER_ERROR
+            Self::RequestNotFound | Self::VerificationInfoNotFound | Self::ProxyNotFound => {
+                StatusCode::NOT_FOUND
             }
-        };
-        (status_code, self.message()).into_response()
+
+            Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        if let Self::Internal(err) = &self {
+            // Do not expose the error details to the client, but log it.
+            tracing::warn!("Internal error: {err:#}");
+        }
+        (self.status_code(), self.message()).into_response()
     }
 }
 
-type ApiResult<T> = Result<Json<T>, ApiError>;
+pub(crate) type ApiResult<T> = Result<Json<T>, ApiError>;
 
 impl RestApi {
     #[tracing::instrument(skip(query))]
@@ -127,9 +134,19 @@ impl RestApi {
         Json(request): Json<VerificationIncomingRequest>,
     ) -> ApiResult<usize> {
         let method_latency = METRICS.call[&"contract_verification"].start();
-        Self::validate_contract_verification_query(&request)?;
+        let request_id = self_.add_verification_request(&request).await?;
+        method_latency.observe();
+        Ok(Json(request_id))
+    }
 
-        let is_compilation_supported = self_
+    /// Validates the verification request and adds it to the queue. Shared by all verification APIs.
+    pub(crate) async fn add_verification_request(
+        &self,
+        request: &VerificationIncomingRequest,
+    ) -> Result<usize, ApiError> {
+        Self::validate_contract_verification_query(request)?;
+
+        let is_compilation_supported = self
             .supported_compilers
             .get(|supported| supported.contain(&request.compiler_versions))
             .await?;
@@ -137,10 +154,7 @@ impl RestApi {
             return Err(ApiError::UnsupportedCompilerVersions);
         }
 
-        let mut storage = self_
-            .master_connection_pool
-            .connection_tagged("api")
-            .await?;
+        let mut storage = self.master_connection_pool.connection_tagged("api").await?;
 
         // Verification is only allowed if the contract is either wasn't verified yet
         // or the verification is partial.
@@ -149,7 +163,7 @@ impl RestApi {
             .get_contract_verification_info(request.contract_address)
             .await?;
         if let Some(verification_info) = verification_info {
-            let fully_verified = verification_info.verification_problems.is_empty();
+            let fully_verified = verification_info.is_perfect_match();
             // System contracts can be force deployed during an upgrade, so it should be possible
             // to re-verify them.
             let is_system = match &verification_info.request.req.source_code_data {
@@ -192,10 +206,9 @@ impl RestApi {
 
         let request_id = storage
             .contract_verification_dal()
-            .add_contract_verification_request(&request)
+            .add_contract_verification_request(request)
             .await?;
-        method_latency.observe();
-        Ok(Json(request_id))
+        Ok(request_id)
     }
 
     #[tracing::instrument(skip(self_))]
@@ -267,23 +280,40 @@ impl RestApi {
         address: Path<Address>,
     ) -> ApiResult<VerificationInfo> {
         let method_latency = METRICS.call[&"contract_verification_info"].start();
-        let mut conn = self_
+        let info = self_
+            .get_verification_info(*address)
+            .await?
+            .ok_or(ApiError::VerificationInfoNotFound)?;
+        method_latency.observe();
+        Ok(Json(info))
+    }
+
+    /// Returns verification info for the contract. If the contract wasn't verified directly, falls back to the info
+    /// inherited from a contract with the same bytecode, and then to a partial match.
+    pub(crate) async fn get_verification_info(
+        &self,
+        address: Address,
+    ) -> Result<Option<VerificationInfo>, ApiError> {
+        let mut conn = self
             .replica_connection_pool
             .connection_tagged("api")
             .await?;
         let mut dal = conn.contract_verification_dal();
 
-        let info = if let Some(info) = dal.get_contract_verification_info(*address).await? {
+        if let Some(info) = dal.get_contract_verification_info(address).await? {
+            return Ok(Some(info));
+        }
+        if let Some(info) = dal.get_inherited_verification_info(address).await? {
+            return Ok(Some(info));
+        }
+        let info = get_partial_match_verification_info(&mut dal, address).await?;
+        Ok(info.map(|mut info| {
+            let source_address = info.request.req.contract_address;
+            if source_address != address {
+                info.inherited_from = Some(source_address);
+            }
             info
-        } else if let Some(partial_match) =
-            get_partial_match_verification_info(&mut dal, *address).await?
-        {
-            partial_match
-        } else {
-            return Err(ApiError::VerificationInfoNotFound);
-        };
-        method_latency.observe();
-        Ok(Json(info))
+        }))
     }
 }
 
@@ -332,7 +362,7 @@ async fn get_partial_match_verification_info(
         }
 
         // Mark the contract as partial match (regardless of other issues).
-        info.verification_problems = vec![VerificationProblem::IncorrectMetadata];
+        info.mark_as_partial_match();
     }
 
     Ok(Some(info))
@@ -0,0 +1,139 @@
+//! Etherscan-compatible verification API.
+//!
+//! Allows using stock verification tooling (e.g., `forge verify-contract` or `hardhat-verify`) with the contract verifier.
+//! Supports the following actions of the `contract` module: `verifysourcecode`, `checkverifystatus`, `getabi`
+//! and `getsourcecode`. Verification requests are translated to [`VerificationIncomingRequest`]s and are processed
+//! in the same way as requests submitted via the native API; request IDs are used as GUIDs.
+//!
+//! [`VerificationIncomingRequest`]: zksync_types::contract_verification::api::VerificationIncomingRequest
+
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{
+    extract::{Query, State},
+    Form, Json,
+};
+use zksync_dal::CoreDal;
+
+use self::types::{EtherscanError, EtherscanParams, EtherscanResponse, EtherscanSourceCode};
+use crate::{api_decl::RestApi, api_impl::ApiError, metrics::METRICS};
+
+pub(crate) mod types;
+
+type EtherscanResult = Result<serde_json::Value, EtherscanError>;
+
+impl RestApi {
+    #[tracing::instrument(skip_all)]
+    pub async fn etherscan_get(
+        State(self_): State<Arc<Self>>,
+        Query(query): Query<HashMap<String, String>>,
+    ) -> Result<Json<EtherscanResponse>, ApiError> {
+        let params = EtherscanParams::new(query, None);
+        self_.handle_etherscan_request(&params).await
+    }
+
+    #[tracing::instrument(skip_all)]
+    pub async fn etherscan_post(
+        State(self_): State<Arc<Self>>,
+        Query(query): Query<HashMap<String, String>>,
+        Form(form): Form<HashMap<String, String>>,
+    ) -> Result<Json<EtherscanResponse>, ApiError> {
+        let params = EtherscanParams::new(query, Some(form));
+        self_.handle_etherscan_request(&params).await
+    }
+
+    async fn handle_etherscan_request(
+        &self,
+        params: &EtherscanParams,
+    ) -> Result<Json<EtherscanResponse>, ApiError> {
+        let result = if params.get("module") == Some("contract") {
+            match params.get("action") {
+                Some("verifysourcecode") => self.etherscan_verify_source_code(params).await,
+                Some("checkverifystatus") => self.etherscan_check_verify_status(params).await,
+                Some("getabi") => self.etherscan_get_abi(params).await,
+                Some("getsourcecode") => self.etherscan_get_source_code(params).await,
+                _ => Err(EtherscanError::InvalidAction),
+            }
+        } else {
+            Err(EtherscanError::InvalidModule)
+        };
+
+        Ok(Json(match result {
+            Ok(result) => EtherscanResponse::ok(result),
+            // Internal errors are not a part of the Etherscan API, so we return them as-is.
+            Err(EtherscanError::Api(err @ ApiError::Internal(_))) => return Err(err),
+            Err(err) => EtherscanResponse::not_ok(err.message()),
+        }))
+    }
+
+    async fn etherscan_verify_source_code(&self, params: &EtherscanParams) -> EtherscanResult {
+        let method_latency = METRICS.call[&"etherscan_verifysourcecode"].start();
+        let request = params.to_verification_request()?;
+        let request_id =
+            self.add_verification_request(&request)
+                .await
+                .map_err(|err| match err {
+                    ApiError::NoDeployedContract => {
+                        EtherscanError::NoDeployedContract(request.contract_address)
+                    }
+                    _ => err.into(),
+                })?;
+        method_latency.observe();
+        Ok(request_id.to_string().into())
+    }
+
+    async fn etherscan_check_verify_status(&self, params: &EtherscanParams) -> EtherscanResult {
+        let method_latency = METRICS.call[&"etherscan_checkverifystatus"].start();
+        let request_id: usize = params
+            .required("guid")?
+            .parse()
+            .map_err(|_| EtherscanError::UnknownGuid)?;
+        let status = self
+            .replica_connection_pool
+            .connection_tagged("api")
+            .await
+            .map_err(ApiError::from)?
+            .contract_verification_dal()
+            .get_verification_request_status(request_id)
+            .await
+            .map_err(ApiError::from)?
+            .ok_or(EtherscanError::UnknownGuid)?;
+        method_latency.observe();
+
+        // Messages are matched by Etherscan clients, so they must be exactly the same as in Etherscan.
+        let message = match status.status.as_str() {
+            "queued" | "in_progress" => "Pending in queue",
+            "successful" => return Ok("Pass - Verified".into()),
+            _ => {
+                tracing::debug!("Verification request #{request_id} failed: {status:?}");
+                "Fail - Unable to verify"
+            }
+        };
+        Err(EtherscanError::Status(message))
+    }
+
+    async fn etherscan_get_abi(&self, params: &EtherscanParams) -> EtherscanResult {
+        let method_latency = METRICS.call[&"etherscan_getabi"].start();
+        let address = params.address("address")?;
+        let info = self
+            .get_verification_info(address)
+            .await?
+            .ok_or(EtherscanError::SourceCodeNotVerified)?;
+        method_latency.observe();
+        Ok(info.artifacts.abi.to_string().into())
+    }
+
+    async fn etherscan_get_source_code(&self, params: &EtherscanParams) -> EtherscanResult {
+        let method_latency = METRICS.call[&"etherscan_getsourcecode"].start();
+        let address = params.address("address")?;
+        let info = self.get_verification_info(address).await?;
+        let mut source_code = info
+            .as_ref()
+            .map_or_else(EtherscanSourceCode::not_verified, EtherscanSourceCode::new);
+        if let Some(proxy) = self.resolve_proxy(address).await? {
+            source_code.set_implementation(proxy.implementation_address);
+        }
+        method_latency.observe();
+        Ok(serde_json::to_value([source_code]).expect("failed serializing source code"))
+    }
+}
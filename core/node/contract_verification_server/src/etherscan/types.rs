@@ -0,0 +1,362 @@
+//! Types used by the Etherscan-compatible API.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use zksync_types::{
+    contract_verification::api::{
+        CompilerVersions, SourceCodeData, VerificationEvmSettings, VerificationIncomingRequest,
+        VerificationInfo,
+    },
+    web3::Bytes,
+    Address,
+};
+
+use crate::api_impl::ApiError;
+
+/// Etherscan API error. Unlike [`ApiError`], these errors are returned with the 200 HTTP status code
+/// and are encoded in the response body, as expected by Etherscan clients.
+#[derive(Debug)]
+pub(crate) enum EtherscanError {
+    InvalidModule,
+    InvalidAction,
+    MissingParam(&'static str),
+    InvalidParam(&'static str),
+    UnsupportedCodeFormat(String),
+    NoDeployedContract(Address),
+    AlreadyVerified,
+    SourceCodeNotVerified,
+    UnknownGuid,
+    /// Status of a verification request that isn't successful (e.g., pending or failed).
+    Status(&'static str),
+    Api(ApiError),
+}
+
+impl From<ApiError> for EtherscanError {
+    fn from(err: ApiError) -> Self {
+        match err {
+            ApiError::AlreadyVerified => Self::AlreadyVerified,
+            _ => Self::Api(err),
+        }
+    }
+}
+
+impl EtherscanError {
+    pub fn message(&self) -> String {
+        match self {
+            Self::InvalidModule => "Error! Missing or invalid Module name".into(),
+            Self::InvalidAction => "Error! Missing or invalid Action name".into(),
+            Self::MissingParam(name) => format!("Error! Missing parameter `{name}`"),
+            Self::InvalidParam(name) => format!("Error! Invalid parameter `{name}`"),
+            Self::UnsupportedCodeFormat(format) => {
+                format!("Error! Unsupported code format `{format}`")
+            }
+            // Etherscan clients (e.g., Foundry) expect this exact prefix and retry verification on it.
+            Self::NoDeployedContract(address) => {
+                format!("Unable to locate ContractCode at {address:?}")
+            }
+            Self::AlreadyVerified => "Contract source code already verified".into(),
+            Self::SourceCodeNotVerified => "Contract source code not verified".into(),
+            Self::UnknownGuid => "Error! Unknown GUID".into(),
+            Self::Status(status) => (*status).into(),
+            Self::Api(err) => err.message(),
+        }
+    }
+}
+
+/// Parameters of an Etherscan API request merged from the query string and the form body.
+/// Parameter names are case-insensitive.
+#[derive(Debug, Default)]
+pub(crate) struct EtherscanParams(HashMap<String, String>);
+
+impl EtherscanParams {
+    pub fn new(query: HashMap<String, String>, form: Option<HashMap<String, String>>) -> Self {
+        let params = query
+            .into_iter()
+            .chain(form.into_iter().flatten())
+            .map(|(name, value)| (name.to_ascii_lowercase(), value))
+            .collect();
+        Self(params)
+    }
+
+    /// Gets a non-empty parameter value. `name` must be lowercase.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .get(name)
+            .map(String::as_str)
+            .filter(|value| !value.is_empty())
+    }
+
+    pub fn required(&self, name: &'static str) -> Result<&str, EtherscanError> {
+        self.get(name).ok_or(EtherscanError::MissingParam(name))
+    }
+
+    fn bool(&self, name: &'static str) -> Result<bool, EtherscanError> {
+        match self.get(name) {
+            None | Some("0" | "false") => Ok(false),
+            Some("1" | "true") => Ok(true),
+            Some(_) => Err(EtherscanError::InvalidParam(name)),
+        }
+    }
+
+    pub fn address(&self, name: &'static str) -> Result<Address, EtherscanError> {
+        self.required(name)?
+            .parse()
+            .map_err(|_| EtherscanError::InvalidParam(name))
+    }
+
+    /// Converts params of a `verifysourcecode` request to the verification request.
+    pub fn to_verification_request(&self) -> Result<VerificationIncomingRequest, EtherscanError> {
+        let contract_address = self.address("contractaddress")?;
+        let source_code = self.required("sourcecode")?;
+        let code_format = self.get("codeformat").unwrap_or("solidity-single-file");
+        let source_code_data = parse_source_code(code_format, source_code)?;
+
+        let compiler_version = normalize_compiler_version(self.required("compilerversion")?);
+        let compiler_versions = match &source_code_data {
+            SourceCodeData::VyperMultiFile(_) => CompilerVersions::Vyper {
+                compiler_zkvyper_version: self
+                    .get("zkvyperversion")
+                    .map(|version| normalize_compiler_version(version).to_owned()),
+                compiler_vyper_version: compiler_version.to_owned(),
+            },
+            _ => CompilerVersions::Solc {
+                compiler_zksolc_version: self
+                    .get("zksolcversion")
+                    .map(|version| normalize_compiler_version(version).to_owned()),
+                compiler_solc_version: compiler_version.to_owned(),
+            },
+        };
+
+        // Etherscan API misspells this param; we support both spellings.
+        let constructor_arguments = self
+            .get("constructorarguements")
+            .or_else(|| self.get("constructorarguments"))
+            .unwrap_or_default();
+        let constructor_arguments = hex::decode(
+            constructor_arguments
+                .strip_prefix("0x")
+                .unwrap_or(constructor_arguments),
+        )
+        .map_err(|_| EtherscanError::InvalidParam("constructorArguements"))?;
+
+        let optimizer_runs = self
+            .get("runs")
+            .map(str::parse)
+            .transpose()
+            .map_err(|_| EtherscanError::InvalidParam("runs"))?;
+        let evm_version = self
+            .get("evmversion")
+            .filter(|version| !version.eq_ignore_ascii_case("default"))
+            .map(str::to_owned);
+
+        Ok(VerificationIncomingRequest {
+            contract_address,
+            source_code_data,
+            contract_name: self.required("contractname")?.to_owned(),
+            compiler_versions,
+            optimization_used: self.bool("optimizationused")?,
+            optimizer_mode: self.get("optimizermode").map(str::to_owned),
+            constructor_arguments: Bytes(constructor_arguments),
+            is_system: self.bool("issystem")? || self.bool("enableeravmextensions")?,
+            force_evmla: self.bool("forceevmla")?,
+            evm_specific: VerificationEvmSettings {
+                evm_version,
+                optimizer_runs,
+            },
+        })
+    }
+}
+
+/// Strips Etherscan-specific decorations from the compiler version, e.g. `v0.8.24+commit.e11b9ed9` -> `0.8.24`,
+/// or `vyper:0.3.10` -> `0.3.10`.
+pub(crate) fn normalize_compiler_version(version: &str) -> &str {
+    let version = version.strip_prefix("vyper:").unwrap_or(version);
+    let version = version.strip_prefix('v').unwrap_or(version);
+    version
+        .split_once('+')
+        .map_or(version, |(version, _)| version)
+}
+
+fn parse_source_code(
+    code_format: &str,
+    source_code: &str,
+) -> Result<SourceCodeData, EtherscanError> {
+    Ok(match code_format {
+        "solidity-single-file" => SourceCodeData::SolSingleFile(source_code.to_owned()),
+        "solidity-standard-json-input" => {
+            let input = serde_json::from_str(source_code)
+                .map_err(|_| EtherscanError::InvalidParam("sourceCode"))?;
+            SourceCodeData::StandardJsonInput(input)
+        }
+        "vyper-json" => {
+            #[derive(Deserialize)]
+            struct VyperSource {
+                content: String,
+            }
+
+            #[derive(Deserialize)]
+            struct VyperJsonInput {
+                sources: HashMap<String, VyperSource>,
+            }
+
+            let input: VyperJsonInput = serde_json::from_str(source_code)
+                .map_err(|_| EtherscanError::InvalidParam("sourceCode"))?;
+            let sources = input
+                .sources
+                .into_iter()
+                .map(|(path, source)| (path, source.content))
+                .collect();
+            SourceCodeData::VyperMultiFile(sources)
+        }
+        "vyper-multi-file" => {
+            let sources = serde_json::from_str(source_code)
+                .map_err(|_| EtherscanError::InvalidParam("sourceCode"))?;
+            SourceCodeData::VyperMultiFile(sources)
+        }
+        _ => {
+            return Err(EtherscanError::UnsupportedCodeFormat(
+                code_format.to_owned(),
+            ))
+        }
+    })
+}
+
+/// Etherscan API response.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct EtherscanResponse {
+    pub status: String,
+    pub message: String,
+    pub result: serde_json::Value,
+}
+
+impl EtherscanResponse {
+    pub fn ok(result: impl Into<serde_json::Value>) -> Self {
+        Self {
+            status: "1".into(),
+            message: "OK".into(),
+            result: result.into(),
+        }
+    }
+
+    pub fn not_ok(result: impl Into<serde_json::Value>) -> Self {
+        Self {
+            status: "0".into(),
+            message: "NOTOK".into(),
+            result: result.into(),
+        }
+    }
+}
+
+/// Entry returned by the `getsourcecode` action.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub(crate) struct EtherscanSourceCode {
+    pub source_code: String,
+    #[serde(rename = "ABI")]
+    pub abi: String,
+    pub contract_name: String,
+    pub compiler_version: String,
+    /// Version of the zk compiler (`zksolc` or `zkvyper`); not present in the original Etherscan API.
+    pub zk_compiler_version: String,
+    pub optimization_used: String,
+    pub runs: String,
+    pub constructor_arguments: String,
+    #[serde(rename = "EVMVersion")]
+    pub evm_version: String,
+    pub library: String,
+    pub license_type: String,
+    pub proxy: String,
+    pub implementation: String,
+    pub swarm_source: String,
+}
+
+impl EtherscanSourceCode {
+    pub fn not_verified() -> Self {
+        Self {
+            abi: EtherscanError::SourceCodeNotVerified.message(),
+            proxy: "0".into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn new(info: &VerificationInfo) -> Self {
+        let request = &info.request.req;
+        let source_code = match &request.source_code_data {
+            SourceCodeData::SolSingleFile(source) | SourceCodeData::YulSingleFile(source) => {
+                source.clone()
+            }
+            // Etherscan wraps JSON input into double braces to distinguish it from single-file sources.
+            SourceCodeData::StandardJsonInput(input) => {
+                format!("{{{}}}", serde_json::Value::from(input.clone()))
+            }
+            SourceCodeData::VyperMultiFile(sources) => {
+                serde_json::to_string(sources).expect("failed serializing Vyper sources")
+            }
+        };
+
+        Self {
+            source_code,
+            abi: info.artifacts.abi.to_string(),
+            contract_name: request.contract_name.clone(),
+            compiler_version: request.compiler_versions.compiler_version().to_owned(),
+            zk_compiler_version: request
+                .compiler_versions
+                .zk_compiler_version()
+                .unwrap_or_default()
+                .to_owned(),
+            optimization_used: if request.optimization_used { "1" } else { "0" }.into(),
+            runs: request
+                .evm_specific
+                .optimizer_runs
+                .map_or_else(String::new, |runs| runs.to_string()),
+            constructor_arguments: hex::encode(&request.constructor_arguments.0),
+            evm_version: request
+                .evm_specific
+                .evm_version
+                .clone()
+                .unwrap_or_else(|| "Default".into()),
+            proxy: "0".into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn set_implementation(&mut self, implementation_address: Address) {
+        self.proxy = "1".into();
+        self.implementation = format!("{implementation_address:?}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizing_compiler_versions() {
+        assert_eq!(normalize_compiler_version("0.8.24"), "0.8.24");
+        assert_eq!(normalize_compiler_version("v0.8.24"), "0.8.24");
+        assert_eq!(
+            normalize_compiler_version("v0.8.24+commit.e11b9ed9"),
+            "0.8.24"
+        );
+        assert_eq!(normalize_compiler_version("vyper:0.3.10"), "0.3.10");
+        assert_eq!(
+            normalize_compiler_version("zkVM-0.8.24-1.0.1"),
+            "zkVM-0.8.24-1.0.1"
+        );
+    }
+
+    #[test]
+    fn parsing_vyper_json_input() {
+        let input = r##"{
+            "language": "Vyper",
+            "sources": { "contracts/Test.vy": { "content": "# @version ^0.3.10" } }
+        }"##;
+        let data = parse_source_code("vyper-json", input).unwrap();
+        let SourceCodeData::VyperMultiFile(sources) = data else {
+            panic!("unexpected source code data: {data:?}");
+        };
+        assert_eq!(sources.len(), 1);
+        assert_eq!(sources["contracts/Test.vy"], "# @version ^0.3.10");
+    }
+}
@@ -0,0 +1,205 @@
+//! Verification info for proxy contracts.
+//!
+//! Links between proxies and their implementations are tracked by the contract verifier based on writes
+//! to the standard proxy storage slots. For beacon proxies, the implementation address is read from the current
+//! state of the beacon.
+
+use std::{collections::HashSet, sync::Arc};
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use zksync_dal::CoreDal;
+use zksync_types::{
+    contract_verification::proxy::{ProxyKind, ProxyLink, ProxyVerificationInfo},
+    AccountTreeId, Address, StorageKey,
+};
+
+use crate::{
+    api_decl::RestApi,
+    api_impl::{ApiError, ApiResult},
+    metrics::METRICS,
+};
+
+/// Proxy contract with the resolved implementation.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ResolvedProxy {
+    pub kind: ProxyKind,
+    pub beacon_address: Option<Address>,
+    pub implementation_address: Address,
+}
+
+impl RestApi {
+    #[tracing::instrument(skip(self_))]
+    pub async fn proxy_verification_info(
+        State(self_): State<Arc<Self>>,
+        address: Path<Address>,
+    ) -> ApiResult<ProxyVerificationInfo> {
+        let method_latency = METRICS.call[&"contract_verification_proxy_info"].start();
+        let proxy = self_
+            .resolve_proxy(*address)
+            .await?
+            .ok_or(ApiError::ProxyNotFound)?;
+        let proxy_info = self_.get_verification_info(*address).await?;
+        let implementation_info = self_
+            .get_verification_info(proxy.implementation_address)
+            .await?;
+
+        let empty_abi = serde_json::Value::Array(vec![]);
+        let merged_abi = merge_abis(
+            proxy_info
+                .as_ref()
+                .map_or(&empty_abi, |info| &info.artifacts.abi),
+            implementation_info
+                .as_ref()
+                .map_or(&empty_abi, |info| &info.artifacts.abi),
+        );
+        method_latency.observe();
+
+        Ok(Json(ProxyVerificationInfo {
+            proxy_kind: proxy.kind,
+            beacon_address: proxy.beacon_address,
+            implementation_address: proxy.implementation_address,
+            proxy: proxy_info,
+            implementation: implementation_info,
+            merged_abi,
+        }))
+    }
+
+    /// Resolves the current implementation of a proxy contract. Returns `None` if the contract is not a known proxy,
+    /// or if the implementation of a beacon proxy cannot be resolved.
+    pub(crate) async fn resolve_proxy(
+        &self,
+        address: Address,
+    ) -> Result<Option<ResolvedProxy>, ApiError> {
+        let mut conn = self
+            .replica_connection_pool
+            .connection_tagged("api")
+            .await?;
+        let Some(link) = conn
+            .contract_verification_dal()
+            .get_proxy_link(address)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        Ok(match link.kind {
+            ProxyKind::Eip1967 | ProxyKind::ZeppelinOs => Some(ResolvedProxy {
+                kind: link.kind,
+                beacon_address: None,
+                implementation_address: link.target_address,
+            }),
+            ProxyKind::Eip1967Beacon => {
+                let beacon_address = link.target_address;
+                let key = StorageKey::new(
+                    AccountTreeId::new(beacon_address),
+                    ProxyKind::BEACON_IMPLEMENTATION_SLOT,
+                );
+                let slot_value = conn.storage_web3_dal().get_value(&key).await?;
+                ProxyLink::parse_target_address(slot_value).map(|implementation_address| {
+                    ResolvedProxy {
+                        kind: link.kind,
+                        beacon_address: Some(beacon_address),
+                        implementation_address,
+                    }
+                })
+            }
+        })
+    }
+}
+
+/// Returns a key identifying an ABI entry for the purposes of merging.
+fn abi_entry_key(entry: &serde_json::Value) -> String {
+    let entry_type = entry["type"].as_str().unwrap_or("function");
+    let name = entry["name"].as_str().unwrap_or_default();
+    let input_types = entry["inputs"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|input| input["type"].as_str().unwrap_or_default());
+    let input_types: Vec<_> = input_types.collect();
+    format!("{entry_type} {name}({})", input_types.join(","))
+}
+
+/// Merges ABIs of the proxy and its implementation. The implementation constructor is excluded since it cannot
+/// be called via the proxy; proxy entries clashing with implementation entries are excluded as well.
+fn merge_abis(
+    proxy_abi: &serde_json::Value,
+    implementation_abi: &serde_json::Value,
+) -> serde_json::Value {
+    let implementation_entries = implementation_abi
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|entry| entry["type"] != "constructor");
+    let mut keys = HashSet::new();
+    let mut merged = vec![];
+    for entry in implementation_entries {
+        keys.insert(abi_entry_key(entry));
+        merged.push(entry.clone());
+    }
+    for entry in proxy_abi.as_array().into_iter().flatten() {
+        if !keys.contains(&abi_entry_key(entry)) {
+            merged.push(entry.clone());
+        }
+    }
+    merged.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merging_abis() {
+        let proxy_abi = serde_json::json!([
+            { "type": "constructor", "inputs": [{ "name": "impl", "type": "address" }] },
+            { "type": "fallback" },
+            {
+                "type": "function",
+                "name": "upgradeTo",
+                "inputs": [{ "name": "impl", "type": "address" }],
+                "outputs": [],
+            },
+            { "type": "event", "name": "Upgraded", "inputs": [{ "name": "impl", "type": "address" }] },
+        ]);
+        let implementation_abi = serde_json::json!([
+            { "type": "constructor", "inputs": [] },
+            { "type": "fallback" },
+            {
+                "type": "function",
+                "name": "increment",
+                "inputs": [{ "name": "x", "type": "uint256" }],
+                "outputs": [],
+            },
+            {
+                "type": "function",
+                "name": "upgradeTo",
+                "inputs": [{ "name": "newImpl", "type": "address" }],
+                "outputs": [],
+            },
+        ]);
+
+        let merged = merge_abis(&proxy_abi, &implementation_abi);
+        let merged_keys: Vec<_> = merged
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(abi_entry_key)
+            .collect();
+        assert_eq!(
+            merged_keys,
+            [
+                "fallback ()",
+                "function increment(uint256)",
+                "function upgradeTo(address)",
+                "constructor (address)",
+                "event Upgraded(address)",
+            ]
+        );
+        // The implementation entry should take precedence.
+        assert_eq!(merged[2]["inputs"][0]["name"], "newImpl");
+    }
+}
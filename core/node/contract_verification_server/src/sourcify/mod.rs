@@ -0,0 +1,148 @@
+//! Sourcify-compatible verification API.
+//!
+//! Allows tools resolving contracts via Sourcify to use the contract verifier. Supports the following endpoints:
+//!
+//! - `POST /verify` accepts Solidity metadata (i.e., `metadata.json`) together with the sources. The metadata
+//!   is converted to the standard JSON compiler input, and the request is processed in the same way as requests
+//!   submitted via the native API. Unlike the native API, the endpoint waits for the verification to complete.
+//! - `GET /files/any/{chainId}/{address}` returns the metadata and sources of a verified contract in the Sourcify
+//!   repository layout.
+//!
+//! The server only serves a single chain; requests for other chain IDs are rejected.
+//!
+//! Full (`perfect` in Sourcify terms) and partial matches are distinguished in the same way as in the native API;
+//! a partial match means that the contract bytecode matches the compiled one only up to the metadata hash.
+
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Context as _;
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use tokio::time::Instant;
+use zksync_dal::CoreDal;
+use zksync_types::{contract_verification::api::VerificationInfo, Address};
+
+use self::types::{
+    SourcifyError, SourcifyFiles, SourcifyVerifyRequest, SourcifyVerifyResponse,
+    SourcifyVerifyResult, VerifyStatus,
+};
+use crate::{api_decl::RestApi, api_impl::ApiError, metrics::METRICS};
+
+pub(crate) mod types;
+
+impl RestApi {
+    /// Interval between polling the status of a verification request submitted via `/verify`.
+    const SOURCIFY_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+    #[tracing::instrument(skip_all, fields(address = ?request.address))]
+    pub async fn sourcify_verify(
+        State(self_): State<Arc<Self>>,
+        Json(request): Json<SourcifyVerifyRequest>,
+    ) -> Result<Json<SourcifyVerifyResponse>, SourcifyError> {
+        let method_latency = METRICS.call[&"sourcify_verify"].start();
+        if !self_.is_served_chain(&request.chain) {
+            return Err(SourcifyError::UnsupportedChain(request.chain));
+        }
+        let verification_request = request.to_verification_request()?;
+        let status = match self_.add_verification_request(&verification_request).await {
+            Ok(request_id) => {
+                let info = self_
+                    .wait_for_verification(request_id, request.address)
+                    .await?;
+                VerifyStatus::new(&info)
+            }
+            // Only fully verified contracts cannot be re-verified.
+            Err(ApiError::AlreadyVerified) => VerifyStatus::Perfect,
+            Err(err) => return Err(err.into()),
+        };
+        method_latency.observe();
+
+        Ok(Json(SourcifyVerifyResponse {
+            result: vec![SourcifyVerifyResult {
+                address: request.address,
+                chain_id: request.chain,
+                status,
+            }],
+        }))
+    }
+
+    /// Checks whether the chain ID specified as a decimal string corresponds to the served chain.
+    fn is_served_chain(&self, chain_id: &str) -> bool {
+        chain_id.parse::<u64>().ok() == Some(self.l2_chain_id.as_u64())
+    }
+
+    async fn wait_for_verification(
+        &self,
+        request_id: usize,
+        address: Address,
+    ) -> Result<VerificationInfo, SourcifyError> {
+        let started_at = Instant::now();
+        loop {
+            // Use the master pool so that the replication lag doesn't affect the result.
+            let mut storage = self
+                .master_connection_pool
+                .connection_tagged("api")
+                .await
+                .map_err(ApiError::from)?;
+            let status = storage
+                .contract_verification_dal()
+                .get_verification_request_status(request_id)
+                .await
+                .map_err(ApiError::from)?
+                .with_context(|| format!("verification request #{request_id} disappeared"))
+                .map_err(ApiError::from)?;
+
+            match status.status.as_str() {
+                "successful" => {
+                    let info = storage
+                        .contract_verification_dal()
+                        .get_contract_verification_info(address)
+                        .await
+                        .map_err(ApiError::from)?
+                        .with_context(|| {
+                            format!(
+                                "verification info is missing for successful request #{request_id}"
+                            )
+                        })
+                        .map_err(ApiError::from)?;
+                    return Ok(info);
+                }
+                "failed" => {
+                    let mut message = status.error.unwrap_or_default();
+                    for compilation_error in status.compilation_errors.into_iter().flatten() {
+                        message.push('\n');
+                        message.push_str(&compilation_error);
+                    }
+                    return Err(SourcifyError::VerificationFailed(message));
+                }
+                _ => { /* The request is still being processed */ }
+            }
+            drop(storage);
+
+            if started_at.elapsed() >= self.sourcify_verification_timeout {
+                return Err(SourcifyError::VerificationPending(request_id));
+            }
+            tokio::time::sleep(Self::SOURCIFY_POLL_INTERVAL).await;
+        }
+    }
+
+    #[tracing::instrument(skip(self_))]
+    pub async fn sourcify_files_any(
+        State(self_): State<Arc<Self>>,
+        Path((chain_id, address)): Path<(String, Address)>,
+    ) -> Result<Json<SourcifyFiles>, SourcifyError> {
+        let method_latency = METRICS.call[&"sourcify_files_any"].start();
+        if !self_.is_served_chain(&chain_id) {
+            return Err(SourcifyError::FilesNotFound);
+        }
+        let info = self_
+            .get_verification_info(address)
+            .await?
+            .ok_or(SourcifyError::FilesNotFound)?;
+        let files = SourcifyFiles::new(&info, &chain_id);
+        method_latency.observe();
+        Ok(Json(files))
+    }
+}
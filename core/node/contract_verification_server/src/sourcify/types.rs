@@ -0,0 +1,540 @@
+//! Types used by the Sourcify-compatible API.
+
+use std::collections::{BTreeMap, HashMap};
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use zksync_types::{
+    contract_verification::api::{
+        CompilerVersions, SourceCodeData, VerificationEvmSettings, VerificationIncomingRequest,
+        VerificationInfo,
+    },
+    web3::{keccak256, Bytes},
+    Address, H256,
+};
+
+use crate::{api_impl::ApiError, etherscan::types::normalize_compiler_version};
+
+/// Sourcify API error. Errors are returned as JSON objects with the `error` field, as expected by Sourcify clients.
+#[derive(Debug)]
+pub(crate) enum SourcifyError {
+    MissingMetadata,
+    AmbiguousMetadata,
+    InvalidMetadata(String),
+    UnsupportedLanguage(String),
+    InvalidCompilationTarget,
+    UnsupportedChain(String),
+    MissingSource(String),
+    VerificationFailed(String),
+    VerificationPending(usize),
+    FilesNotFound,
+    Api(ApiError),
+}
+
+impl From<ApiError> for SourcifyError {
+    fn from(err: ApiError) -> Self {
+        Self::Api(err)
+    }
+}
+
+impl SourcifyError {
+    pub fn message(&self) -> String {
+        match self {
+            Self::MissingMetadata => "Metadata file not found".into(),
+            Self::AmbiguousMetadata => {
+                "Multiple metadata files found; only a single contract can be verified at a time"
+                    .into()
+            }
+            Self::InvalidMetadata(err) => format!("Invalid metadata: {err}"),
+            Self::UnsupportedLanguage(language) => format!("Unsupported language `{language}`"),
+            Self::InvalidCompilationTarget => {
+                "Metadata must specify exactly one compilation target".into()
+            }
+            Self::UnsupportedChain(chain) => format!("Unsupported chain `{chain}`"),
+            Self::MissingSource(path) => format!("Missing source file: {path}"),
+            Self::VerificationFailed(err) => format!("Verification failed: {err}"),
+            Self::VerificationPending(id) => {
+                format!("Verification is still in progress; check status at /contract_verification/{id}")
+            }
+            // Sourcify clients match this message.
+            Self::FilesNotFound => "Files have not been found!".into(),
+            Self::Api(err) => err.message(),
+        }
+    }
+
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::MissingMetadata
+            | Self::AmbiguousMetadata
+            | Self::InvalidMetadata(_)
+            | Self::UnsupportedLanguage(_)
+            | Self::InvalidCompilationTarget
+            | Self::UnsupportedChain(_)
+            | Self::MissingSource(_)
+            | Self::VerificationFailed(_) => StatusCode::BAD_REQUEST,
+            Self::VerificationPending(_) => StatusCode::ACCEPTED,
+            Self::FilesNotFound => StatusCode::NOT_FOUND,
+            Self::Api(err) => err.status_code(),
+        }
+    }
+}
+
+impl IntoResponse for SourcifyError {
+    fn into_response(self) -> Response {
+        if let Self::Api(err @ ApiError::Internal(_)) = self {
+            // Internal errors are logged and obfuscated in the same way as for the native API.
+            return err.into_response();
+        }
+        let body = serde_json::json!({ "error": self.message() });
+        (self.status_code(), Json(body)).into_response()
+    }
+}
+
+/// Body of the Sourcify `/verify` request.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SourcifyVerifyRequest {
+    pub address: Address,
+    /// Chain ID as a decimal string. Must match the chain served by the server.
+    pub chain: String,
+    /// Mapping from file names to their contents. Must contain the Solidity metadata file
+    /// and all sources referenced by it (unless the sources are inlined into metadata).
+    pub files: HashMap<String, String>,
+    /// Version of `zksolc` used to compile the contract; not present in the original Sourcify API.
+    /// Required for EraVM contracts.
+    #[serde(default)]
+    pub zksolc_version: Option<String>,
+    /// Constructor arguments; not present in the original Sourcify API (Sourcify extracts them from the deployment
+    /// transaction). Required if the contract was deployed with non-empty constructor arguments.
+    #[serde(default)]
+    pub constructor_arguments: Bytes,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetadataCompiler {
+    version: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetadataSource {
+    keccak256: Option<H256>,
+    content: Option<String>,
+}
+
+/// Subset of the Solidity contract metadata (i.e., `metadata.json`) necessary for verification.
+#[derive(Debug, Deserialize)]
+struct SolidityMetadata {
+    compiler: MetadataCompiler,
+    language: String,
+    settings: serde_json::Map<String, Value>,
+    sources: BTreeMap<String, MetadataSource>,
+}
+
+impl SolidityMetadata {
+    /// Finds the metadata file among the provided files. Files are recognized by their contents, not names.
+    fn find(files: &HashMap<String, String>) -> Result<(&str, Self), SourcifyError> {
+        let mut found = None;
+        for (name, content) in files {
+            if !name.ends_with(".json") {
+                continue;
+            }
+            let Ok(metadata) = serde_json::from_str::<Self>(content) else {
+                continue;
+            };
+            if found.is_some() {
+                return Err(SourcifyError::AmbiguousMetadata);
+            }
+            found = Some((name.as_str(), metadata));
+        }
+        found.ok_or(SourcifyError::MissingMetadata)
+    }
+}
+
+impl SourcifyVerifyRequest {
+    /// Converts Sourcify-style metadata and sources to a verification request with the standard JSON compiler input.
+    /// Sources are matched with the metadata by their keccak256 hashes, so file names are not significant.
+    pub fn to_verification_request(&self) -> Result<VerificationIncomingRequest, SourcifyError> {
+        let (metadata_name, metadata) = SolidityMetadata::find(&self.files)?;
+        if !matches!(metadata.language.as_str(), "Solidity" | "Yul") {
+            return Err(SourcifyError::UnsupportedLanguage(metadata.language));
+        }
+
+        let files_by_hash: HashMap<_, _> = self
+            .files
+            .iter()
+            .filter(|(name, _)| name.as_str() != metadata_name)
+            .map(|(_, content)| (H256(keccak256(content.as_bytes())), content))
+            .collect();
+        let mut sources = serde_json::Map::with_capacity(metadata.sources.len());
+        for (path, source) in metadata.sources {
+            let content = match (source.content, source.keccak256) {
+                (Some(content), _) => content,
+                (None, Some(hash)) => files_by_hash
+                    .get(&hash)
+                    .map(|&content| content.clone())
+                    .ok_or_else(|| SourcifyError::MissingSource(path.clone()))?,
+                (None, None) => return Err(SourcifyError::MissingSource(path)),
+            };
+            sources.insert(path, serde_json::json!({ "content": content }));
+        }
+
+        let mut settings = metadata.settings;
+        let contract_name = match settings.remove("compilationTarget") {
+            Some(Value::Object(target)) if target.len() == 1 => {
+                let (path, name) = target.into_iter().next().unwrap();
+                let name = name
+                    .as_str()
+                    .ok_or(SourcifyError::InvalidCompilationTarget)?;
+                format!("{path}:{name}")
+            }
+            _ => return Err(SourcifyError::InvalidCompilationTarget),
+        };
+        if let Some(libraries) = settings.remove("libraries") {
+            settings.insert("libraries".into(), convert_libraries(libraries)?);
+        }
+
+        let optimizer = settings.get("optimizer");
+        let optimization_used = optimizer
+            .and_then(|optimizer| optimizer.get("enabled"))
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        let optimizer_runs = optimizer
+            .and_then(|optimizer| optimizer.get("runs"))
+            .and_then(Value::as_u64)
+            .map(u16::try_from)
+            .transpose()
+            .map_err(|_| {
+                SourcifyError::InvalidMetadata("optimizer runs are out of range".into())
+            })?;
+        let evm_version = settings
+            .get("evmVersion")
+            .and_then(Value::as_str)
+            .map(str::to_owned);
+        let is_system = ["enableEraVMExtensions", "isSystem"]
+            .iter()
+            .any(|&name| settings.get(name).and_then(Value::as_bool) == Some(true));
+
+        let mut input = serde_json::Map::new();
+        input.insert("language".into(), metadata.language.into());
+        input.insert("sources".into(), sources.into());
+        input.insert("settings".into(), settings.into());
+
+        Ok(VerificationIncomingRequest {
+            contract_address: self.address,
+            source_code_data: SourceCodeData::StandardJsonInput(input),
+            contract_name,
+            compiler_versions: CompilerVersions::Solc {
+                compiler_zksolc_version: self
+                    .zksolc_version
+                    .as_deref()
+                    .map(|version| normalize_compiler_version(version).to_owned()),
+                compiler_solc_version: normalize_compiler_version(&metadata.compiler.version)
+                    .to_owned(),
+            },
+            optimization_used,
+            optimizer_mode: None,
+            constructor_arguments: self.constructor_arguments.clone(),
+            is_system,
+            force_evmla: false,
+            evm_specific: VerificationEvmSettings {
+                evm_version,
+                optimizer_runs,
+            },
+        })
+    }
+}
+
+/// Converts libraries from the metadata format (`{ "path:Name": "0x..." }`) to the standard JSON input format
+/// (`{ "path": { "Name": "0x..." } }`).
+fn convert_libraries(libraries: Value) -> Result<Value, SourcifyError> {
+    let Value::Object(libraries) = libraries else {
+        return Err(SourcifyError::InvalidMetadata(
+            "libraries must be an object".into(),
+        ));
+    };
+    let mut converted = serde_json::Map::<String, Value>::new();
+    for (qualified_name, address) in libraries {
+        let (path, name) = qualified_name
+            .rsplit_once(':')
+            .unwrap_or(("", &qualified_name));
+        let entry = converted
+            .entry(path)
+            .or_insert_with(|| Value::Object(serde_json::Map::new()));
+        entry
+            .as_object_mut()
+            .unwrap()
+            .insert(name.to_owned(), address);
+    }
+    Ok(converted.into())
+}
+
+/// Status of a match as returned in `/verify` responses.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum VerifyStatus {
+    Perfect,
+    Partial,
+}
+
+impl VerifyStatus {
+    pub fn new(info: &VerificationInfo) -> Self {
+        if info.is_perfect_match() {
+            Self::Perfect
+        } else {
+            Self::Partial
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SourcifyVerifyResult {
+    pub address: Address,
+    pub chain_id: String,
+    pub status: VerifyStatus,
+}
+
+/// Response to the `/verify` request.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct SourcifyVerifyResponse {
+    pub result: Vec<SourcifyVerifyResult>,
+}
+
+/// Status of a match as returned in `/files/any` responses. Confusingly, it differs from [`VerifyStatus`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum FilesStatus {
+    Full,
+    Partial,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct SourcifyFile {
+    pub name: String,
+    pub path: String,
+    pub content: String,
+}
+
+/// Response to the `/files/any/{chainId}/{address}` request.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct SourcifyFiles {
+    pub status: FilesStatus,
+    pub files: Vec<SourcifyFile>,
+}
+
+impl SourcifyFiles {
+    /// Restores the metadata and sources of a verified contract in the Sourcify repository layout.
+    /// The metadata is reconstructed from the verification request, so it only contains fields relevant for verification.
+    pub fn new(info: &VerificationInfo, chain_id: &str) -> Self {
+        let request = &info.request.req;
+        let (status, match_dir) = match VerifyStatus::new(info) {
+            VerifyStatus::Perfect => (FilesStatus::Full, "full_match"),
+            VerifyStatus::Partial => (FilesStatus::Partial, "partial_match"),
+        };
+        let contract_dir = format!(
+            "/contracts/{match_dir}/{chain_id}/{:?}",
+            request.contract_address
+        );
+
+        let (language, extension) = match &request.source_code_data {
+            SourceCodeData::SolSingleFile(_) => ("Solidity", "sol"),
+            SourceCodeData::YulSingleFile(_) => ("Yul", "yul"),
+            SourceCodeData::VyperMultiFile(_) => ("Vyper", "vy"),
+            SourceCodeData::StandardJsonInput(input) => (
+                input
+                    .get("language")
+                    .and_then(Value::as_str)
+                    .unwrap_or("Solidity"),
+                "sol",
+            ),
+        };
+        let (target_path, target_name) = match request.contract_name.rsplit_once(':') {
+            Some((path, name)) => (path.to_owned(), name),
+            None => (
+                format!("{}.{extension}", request.contract_name),
+                request.contract_name.as_str(),
+            ),
+        };
+
+        let (sources, mut settings): (BTreeMap<_, _>, _) = match &request.source_code_data {
+            SourceCodeData::SolSingleFile(source) | SourceCodeData::YulSingleFile(source) => (
+                [(target_path.clone(), source.clone())].into(),
+                serde_json::Map::new(),
+            ),
+            SourceCodeData::VyperMultiFile(sources) => (
+                sources
+                    .iter()
+                    .map(|(path, source)| (path.clone(), source.clone()))
+                    .collect(),
+                serde_json::Map::new(),
+            ),
+            SourceCodeData::StandardJsonInput(input) => {
+                let sources = input
+                    .get("sources")
+                    .and_then(Value::as_object)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|(path, source)| {
+                        let content = source.get("content")?.as_str()?;
+                        Some((path.clone(), content.to_owned()))
+                    })
+                    .collect();
+                let mut settings = input
+                    .get("settings")
+                    .and_then(Value::as_object)
+                    .cloned()
+                    .unwrap_or_default();
+                settings.remove("outputSelection");
+                (sources, settings)
+            }
+        };
+
+        if !settings.contains_key("optimizer") {
+            let mut optimizer = serde_json::json!({ "enabled": request.optimization_used });
+            if let Some(runs) = request.evm_specific.optimizer_runs {
+                optimizer["runs"] = runs.into();
+            }
+            settings.insert("optimizer".into(), optimizer);
+        }
+        if let Some(evm_version) = &request.evm_specific.evm_version {
+            settings
+                .entry("evmVersion")
+                .or_insert_with(|| evm_version.clone().into());
+        }
+        settings.insert(
+            "compilationTarget".into(),
+            serde_json::json!({ target_path: target_name }),
+        );
+
+        let metadata_sources: serde_json::Map<_, _> = sources
+            .iter()
+            .map(|(path, content)| {
+                let hash = H256(keccak256(content.as_bytes()));
+                (path.clone(), serde_json::json!({ "keccak256": hash }))
+            })
+            .collect();
+        let metadata = serde_json::json!({
+            "compiler": { "version": request.compiler_versions.compiler_version() },
+            "language": language,
+            "output": { "abi": info.artifacts.abi },
+            "settings": settings,
+            "sources": metadata_sources,
+            "version": 1,
+        });
+
+        let metadata_file = SourcifyFile {
+            name: "metadata.json".into(),
+            path: format!("{contract_dir}/metadata.json"),
+            content: serde_json::to_string_pretty(&metadata).expect("failed serializing metadata"),
+        };
+        let source_files = sources.into_iter().map(|(path, content)| SourcifyFile {
+            name: path.rsplit('/').next().unwrap_or(&path).to_owned(),
+            path: format!("{contract_dir}/sources/{path}"),
+            content,
+        });
+        Self {
+            status,
+            files: [metadata_file].into_iter().chain(source_files).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "contract Test {}";
+
+    fn metadata() -> Value {
+        serde_json::json!({
+            "compiler": { "version": "0.8.27+commit.40a35a09" },
+            "language": "Solidity",
+            "output": { "abi": [] },
+            "settings": {
+                "compilationTarget": { "contracts/Test.sol": "Test" },
+                "evmVersion": "cancun",
+                "libraries": { "contracts/Lib.sol:Lib": "0x0000000000000000000000000000000000000001" },
+                "optimizer": { "enabled": true, "runs": 200 },
+                "remappings": []
+            },
+            "sources": {
+                "contracts/Test.sol": {
+                    "keccak256": H256(keccak256(SOURCE.as_bytes())),
+                    "urls": []
+                }
+            },
+            "version": 1
+        })
+    }
+
+    fn verify_request(files: HashMap<String, String>) -> SourcifyVerifyRequest {
+        SourcifyVerifyRequest {
+            address: Address::repeat_byte(0x23),
+            chain: "270".into(),
+            files,
+            zksolc_version: Some("v1.5.6".into()),
+            constructor_arguments: Bytes::default(),
+        }
+    }
+
+    #[test]
+    fn converting_metadata_to_verification_request() {
+        let files = HashMap::from([
+            ("metadata.json".to_owned(), metadata().to_string()),
+            // File names are not significant; sources are matched by hash.
+            ("Test.sol".to_owned(), SOURCE.to_owned()),
+        ]);
+        let request = verify_request(files).to_verification_request().unwrap();
+
+        assert_eq!(request.contract_name, "contracts/Test.sol:Test");
+        assert_eq!(
+            request.compiler_versions,
+            CompilerVersions::Solc {
+                compiler_zksolc_version: Some("1.5.6".into()),
+                compiler_solc_version: "0.8.27".into(),
+            }
+        );
+        assert!(request.optimization_used);
+        assert_eq!(request.evm_specific.optimizer_runs, Some(200));
+        assert_eq!(request.evm_specific.evm_version.as_deref(), Some("cancun"));
+
+        let SourceCodeData::StandardJsonInput(input) = &request.source_code_data else {
+            panic!(
+                "unexpected source code data: {:?}",
+                request.source_code_data
+            );
+        };
+        assert_eq!(input["language"], "Solidity");
+        assert_eq!(input["sources"]["contracts/Test.sol"]["content"], SOURCE);
+        let settings = input["settings"].as_object().unwrap();
+        assert!(!settings.contains_key("compilationTarget"));
+        assert_eq!(
+            settings["libraries"],
+            serde_json::json!({
+                "contracts/Lib.sol": { "Lib": "0x0000000000000000000000000000000000000001" }
+            })
+        );
+    }
+
+    #[test]
+    fn converting_metadata_with_missing_source() {
+        let files = HashMap::from([
+            ("metadata.json".to_owned(), metadata().to_string()),
+            ("Test.sol".to_owned(), "contract Other {}".to_owned()),
+        ]);
+        let err = verify_request(files).to_verification_request().unwrap_err();
+        assert!(
+            matches!(&err, SourcifyError::MissingSource(path) if path == "contracts/Test.sol"),
+            "{err:?}"
+        );
+
+        let files = HashMap::from([("Test.sol".to_owned(), SOURCE.to_owned())]);
+        let err = verify_request(files).to_verification_request().unwrap_err();
+        assert!(matches!(err, SourcifyError::MissingMetadata), "{err:?}");
+    }
+}
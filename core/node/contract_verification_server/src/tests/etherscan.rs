@@ -0,0 +1,210 @@
+//! Tests for the Etherscan-compatible API.
+
+use axum::http::Method;
+
+use super::*;
+
+fn etherscan_verification_params<'a>(
+    address: &'a str,
+    bytecode_kind: BytecodeMarker,
+) -> Vec<(&'a str, &'a str)> {
+    let mut params = vec![
+        ("module", "contract"),
+        ("action", "verifysourcecode"),
+        ("contractaddress", address),
+        ("sourceCode", "contract Test {}"),
+        ("codeformat", "solidity-single-file"),
+        ("contractname", "Test"),
+        ("compilerversion", "v0.8.27+commit.40a35a09"),
+        ("optimizationUsed", "1"),
+        ("constructorArguements", ""),
+    ];
+    if bytecode_kind == BytecodeMarker::EraVm {
+        params.push(("zksolcVersion", "v1.5.6"));
+    }
+    params
+}
+
+#[test_casing(2, [BytecodeMarker::EraVm, BytecodeMarker::Evm])]
+#[tokio::test]
+async fn verifying_contract_via_etherscan_api(bytecode_kind: BytecodeMarker) {
+    let pool = ConnectionPool::test_pool().await;
+    let contract_verifier = MockContractVerifier::new(pool.clone());
+    let client = MockApiClient::new(pool.clone());
+    let mut storage = pool.connection().await.unwrap();
+    prepare_storage(&mut storage).await;
+
+    let address = Address::repeat_byte(0x23);
+    let address_str = format!("{address:?}");
+    mock_deploy_contract(&mut storage, address, bytecode_kind).await;
+
+    let params = etherscan_verification_params(&address_str, bytecode_kind);
+    let response = client.send_etherscan_request(Method::POST, &params).await;
+    assert_eq!(response.status, "1", "{response:?}");
+    assert_eq!(response.result, "1");
+
+    let status_params = [
+        ("module", "contract"),
+        ("action", "checkverifystatus"),
+        ("guid", "1"),
+    ];
+    let response = client
+        .send_etherscan_request(Method::GET, &status_params)
+        .await;
+    assert_eq!(response.status, "0");
+    assert_eq!(response.result, "Pending in queue");
+
+    // The request should be equivalent to one submitted via the native API.
+    let verification_request = serde_json::json!({
+        "contractAddress": address,
+        "sourceCode": "contract Test {}",
+        "contractName": "Test",
+        "compilerZksolcVersion": match bytecode_kind {
+            BytecodeMarker::EraVm => Some(ZKSOLC_VERSION),
+            BytecodeMarker::Evm => None,
+        },
+        "compilerSolcVersion": SOLC_VERSION,
+        "optimizationUsed": true,
+    });
+    contract_verifier
+        .pick_up_next_request(1, &verification_request, bytecode_kind)
+        .await;
+    let response = client
+        .send_etherscan_request(Method::GET, &status_params)
+        .await;
+    assert_eq!(response.result, "Pending in queue");
+
+    let mut verification_info = mock_verification_info(1, &verification_request);
+    let abi = serde_json::json!([{ "type": "constructor", "inputs": [] }]);
+    verification_info.artifacts.abi = abi.clone();
+    contract_verifier.verify_contract(verification_info).await;
+
+    let response = client
+        .send_etherscan_request(Method::GET, &status_params)
+        .await;
+    assert_eq!(response.status, "1");
+    assert_eq!(response.result, "Pass - Verified");
+
+    let response = client
+        .send_etherscan_request(
+            Method::GET,
+            &[
+                ("module", "contract"),
+                ("action", "getabi"),
+                ("address", &address_str),
+            ],
+        )
+        .await;
+    assert_eq!(response.status, "1");
+    let response_abi: serde_json::Value =
+        serde_json::from_str(response.result.as_str().unwrap()).unwrap();
+    assert_eq!(response_abi, abi);
+
+    let response = client
+        .send_etherscan_request(
+            Method::GET,
+            &[
+                ("module", "contract"),
+                ("action", "getsourcecode"),
+                ("address", &address_str),
+            ],
+        )
+        .await;
+    assert_eq!(response.status, "1");
+    let source_code = &response.result[0];
+    assert_eq!(source_code["SourceCode"], "contract Test {}");
+    assert_eq!(source_code["ContractName"], "Test");
+    assert_eq!(source_code["CompilerVersion"], SOLC_VERSION);
+    assert_eq!(source_code["OptimizationUsed"], "1");
+    let expected_zk_version = match bytecode_kind {
+        BytecodeMarker::EraVm => ZKSOLC_VERSION,
+        BytecodeMarker::Evm => "",
+    };
+    assert_eq!(source_code["ZkCompilerVersion"], expected_zk_version);
+
+    // Repeated verification should be rejected.
+    let response = client.send_etherscan_request(Method::POST, &params).await;
+    assert_eq!(response.status, "0");
+    assert_eq!(response.result, "Contract source code already verified");
+}
+
+#[tokio::test]
+async fn etherscan_api_errors() {
+    let pool = ConnectionPool::test_pool().await;
+    let client = MockApiClient::new(pool.clone());
+    let mut storage = pool.connection().await.unwrap();
+    prepare_storage(&mut storage).await;
+
+    let address = Address::repeat_byte(0x23);
+    let address_str = format!("{address:?}");
+
+    let response = client
+        .send_etherscan_request(Method::GET, &[("module", "account")])
+        .await;
+    assert_eq!(response.status, "0");
+    assert_eq!(response.message, "NOTOK");
+    assert_eq!(response.result, "Error! Missing or invalid Module name");
+
+    let response = client
+        .send_etherscan_request(Method::GET, &[("module", "contract"), ("action", "what")])
+        .await;
+    assert_eq!(response.result, "Error! Missing or invalid Action name");
+
+    let response = client
+        .send_etherscan_request(
+            Method::GET,
+            &[("module", "contract"), ("action", "checkverifystatus")],
+        )
+        .await;
+    assert_eq!(response.result, "Error! Missing parameter `guid`");
+
+    let response = client
+        .send_etherscan_request(
+            Method::GET,
+            &[
+                ("module", "contract"),
+                ("action", "checkverifystatus"),
+                ("guid", "1"),
+            ],
+        )
+        .await;
+    assert_eq!(response.result, "Error! Unknown GUID");
+
+    let params = etherscan_verification_params(&address_str, BytecodeMarker::EraVm);
+    let response = client.send_etherscan_request(Method::POST, &params).await;
+    assert_eq!(response.status, "0");
+    assert_eq!(
+        response.result,
+        format!("Unable to locate ContractCode at {address:?}")
+    );
+
+    let response = client
+        .send_etherscan_request(
+            Method::GET,
+            &[
+                ("module", "contract"),
+                ("action", "getabi"),
+                ("address", &address_str),
+            ],
+        )
+        .await;
+    assert_eq!(response.status, "0");
+    assert_eq!(response.result, "Contract source code not verified");
+
+    let response = client
+        .send_etherscan_request(
+            Method::GET,
+            &[
+                ("module", "contract"),
+                ("action", "getsourcecode"),
+                ("address", &address_str),
+            ],
+        )
+        .await;
+    assert_eq!(response.status, "1");
+    assert_eq!(response.result[0]["SourceCode"], "");
+    assert_eq!(
+        response.result[0]["ABI"],
+        "Contract source code not verified"
+    );
+}
@@ -5,15 +5,22 @@ use std::{str, vec};
 use test_casing::test_casing;
 use utils::{mock_verification_info, MockApiClient, MockContractVerifier};
 use zksync_types::{
-    bytecode::BytecodeMarker, contract_verification::api::VerificationProblem, Address,
+    bytecode::BytecodeMarker,
+    contract_verification::api::{VerificationLevel, VerificationProblem},
+    Address,
 };
 
 use super::*;
 use crate::{
     api_impl::ApiError,
-    tests::utils::{mock_deploy_contract, prepare_storage, SOLC_VERSION, ZKSOLC_VERSION},
+    tests::utils::{
+        mock_deploy_contract, mock_deploy_events, prepare_storage, SOLC_VERSION, ZKSOLC_VERSION,
+    },
 };
 
+mod etherscan;
+mod proxy;
+mod sourcify;
 mod utils;
 
 #[tokio::test]
@@ -127,7 +134,7 @@ async fn partial_verification(bytecode_kind: BytecodeMarker) {
 
     // Verify contract (with a verification problem)
     let mut verification_info = mock_verification_info(id, &verification_request);
-    verification_info.verification_problems = vec![VerificationProblem::IncorrectMetadata];
+    verification_info.mark_as_partial_match();
     contract_verifier
         .verify_contract(verification_info.clone())
         .await;
@@ -137,6 +144,7 @@ async fn partial_verification(bytecode_kind: BytecodeMarker) {
     // We should be able to fetch verification info
     let info = client.verification_info(address).await;
     assert_eq!(info.request.id, id);
+    assert_eq!(info.verification_level, Some(VerificationLevel::Partial));
     assert_eq!(
         info.verification_problems,
         vec![VerificationProblem::IncorrectMetadata]
@@ -153,6 +161,7 @@ async fn partial_verification(bytecode_kind: BytecodeMarker) {
 
     // Verify new contract
     verification_info.request.id = new_id;
+    verification_info.verification_level = Some(VerificationLevel::Full);
     verification_info.verification_problems.clear();
     contract_verifier.verify_contract(verification_info).await;
 
@@ -162,9 +171,75 @@ async fn partial_verification(bytecode_kind: BytecodeMarker) {
     // Now verification info should be updated
     let info = client.verification_info(address).await;
     assert_eq!(info.request.id, new_id);
+    assert_eq!(info.verification_level, Some(VerificationLevel::Full));
     assert_eq!(info.verification_problems, vec![]);
 }
 
+#[test_casing(2, [BytecodeMarker::EraVm, BytecodeMarker::Evm])]
+#[tokio::test]
+async fn inheriting_verification_info(bytecode_kind: BytecodeMarker) {
+    let pool = ConnectionPool::test_pool().await;
+    let contract_verifier = MockContractVerifier::new(pool.clone());
+    let client = MockApiClient::new(pool.clone());
+    let mut storage = pool.connection().await.unwrap();
+    prepare_storage(&mut storage).await;
+
+    let address = Address::repeat_byte(0x23);
+    let identical_address = Address::repeat_byte(0x24);
+    mock_deploy_contract(&mut storage, address, bytecode_kind).await;
+    mock_deploy_contract(&mut storage, identical_address, bytecode_kind).await;
+    mock_deploy_events(&mut storage, &[address, identical_address], bytecode_kind).await;
+
+    let verification_request = serde_json::json!({
+        "contractAddress": address,
+        "sourceCode": "contract Test {}",
+        "contractName": "Test",
+        "compilerZksolcVersion": match bytecode_kind {
+            BytecodeMarker::EraVm => Some(ZKSOLC_VERSION),
+            BytecodeMarker::Evm => None,
+        },
+        "compilerSolcVersion": SOLC_VERSION,
+        "optimizationUsed": true,
+    });
+    let id = client
+        .send_verification_request(&verification_request)
+        .await;
+    contract_verifier
+        .pick_up_next_request(id, &verification_request, bytecode_kind)
+        .await;
+    let verification_info = mock_verification_info(id, &verification_request);
+    contract_verifier.verify_contract(verification_info).await;
+    let inherited_count = storage
+        .contract_verification_dal()
+        .inherit_verification_info(address)
+        .await
+        .unwrap();
+    assert_eq!(inherited_count, 1);
+
+    let info = client.verification_info(address).await;
+    assert_eq!(info.inherited_from, None);
+    let info = client.verification_info(identical_address).await;
+    assert_eq!(info.request.id, id);
+    assert_eq!(info.inherited_from, Some(address));
+
+    // Inheriting contracts can still be verified directly.
+    let verification_request = serde_json::json!({
+        "contractAddress": identical_address,
+        "sourceCode": "contract Test {}",
+        "contractName": "Test",
+        "compilerZksolcVersion": match bytecode_kind {
+            BytecodeMarker::EraVm => Some(ZKSOLC_VERSION),
+            BytecodeMarker::Evm => None,
+        },
+        "compilerSolcVersion": SOLC_VERSION,
+        "optimizationUsed": true,
+    });
+    let new_id = client
+        .send_verification_request(&verification_request)
+        .await;
+    assert_eq!(new_id, id + 1);
+}
+
 #[test_casing(2, [BytecodeMarker::EraVm, BytecodeMarker::Evm])]
 #[tokio::test]
 async fn submitting_request_with_invalid_compiler_type(bytecode_kind: BytecodeMarker) {
@@ -0,0 +1,121 @@
+//! Tests for proxy verification info.
+
+use axum::http::Method;
+use zksync_dal::{Core, CoreDal};
+use zksync_types::{
+    address_to_h256,
+    contract_verification::proxy::{ProxyKind, ProxyLink},
+    AccountTreeId, L2BlockNumber, StorageKey, StorageLog,
+};
+
+use super::*;
+
+async fn verify_implementation(
+    pool: &ConnectionPool<Core>,
+    client: &MockApiClient,
+    address: Address,
+) {
+    let contract_verifier = MockContractVerifier::new(pool.clone());
+    let verification_request = serde_json::json!({
+        "contractAddress": address,
+        "sourceCode": "contract Test {}",
+        "contractName": "Test",
+        "compilerZksolcVersion": ZKSOLC_VERSION,
+        "compilerSolcVersion": SOLC_VERSION,
+        "optimizationUsed": true,
+    });
+    let id = client
+        .send_verification_request(&verification_request)
+        .await;
+    contract_verifier
+        .pick_up_next_request(id, &verification_request, BytecodeMarker::EraVm)
+        .await;
+    let mut verification_info = mock_verification_info(id, &verification_request);
+    verification_info.artifacts.abi = serde_json::json!([
+        { "type": "constructor", "inputs": [] },
+        { "type": "function", "name": "increment", "inputs": [], "outputs": [] },
+    ]);
+    contract_verifier.verify_contract(verification_info).await;
+}
+
+#[test_casing(2, [false, true])]
+#[tokio::test]
+async fn getting_proxy_verification_info(beacon: bool) {
+    let pool = ConnectionPool::test_pool().await;
+    let client = MockApiClient::new(pool.clone());
+    let mut storage = pool.connection().await.unwrap();
+    prepare_storage(&mut storage).await;
+
+    let proxy_address = Address::repeat_byte(0x23);
+    let implementation_address = Address::repeat_byte(0x24);
+    let beacon_address = Address::repeat_byte(0x25);
+    // Use different bytecode kinds so that the proxy doesn't get a partial match with the implementation.
+    mock_deploy_contract(&mut storage, proxy_address, BytecodeMarker::Evm).await;
+    mock_deploy_contract(&mut storage, implementation_address, BytecodeMarker::EraVm).await;
+    verify_implementation(&pool, &client, implementation_address).await;
+
+    client
+        .assert_proxy_verification_info_error(proxy_address, ApiError::ProxyNotFound)
+        .await;
+
+    let (kind, target_address) = if beacon {
+        let implementation_log = StorageLog::new_write_log(
+            StorageKey::new(
+                AccountTreeId::new(beacon_address),
+                ProxyKind::BEACON_IMPLEMENTATION_SLOT,
+            ),
+            address_to_h256(&implementation_address),
+        );
+        storage
+            .storage_logs_dal()
+            .append_storage_logs(L2BlockNumber(0), &[implementation_log])
+            .await
+            .unwrap();
+        (ProxyKind::Eip1967Beacon, beacon_address)
+    } else {
+        (ProxyKind::Eip1967, implementation_address)
+    };
+    let link = ProxyLink {
+        proxy_address,
+        kind,
+        target_address,
+        l2_block_number: L2BlockNumber(0),
+    };
+    storage
+        .contract_verification_dal()
+        .save_proxy_links(&[link])
+        .await
+        .unwrap();
+
+    let info = client.proxy_verification_info(proxy_address).await;
+    assert_eq!(info.proxy_kind, kind);
+    assert_eq!(info.beacon_address, beacon.then_some(beacon_address));
+    assert_eq!(info.implementation_address, implementation_address);
+    assert!(info.proxy.is_none());
+    let implementation_info = info.implementation.unwrap();
+    assert_eq!(
+        implementation_info.request.req.contract_address,
+        implementation_address
+    );
+    assert_eq!(
+        info.merged_abi,
+        serde_json::json!([
+            { "type": "function", "name": "increment", "inputs": [], "outputs": [] },
+        ])
+    );
+
+    let proxy_address_str = format!("{proxy_address:?}");
+    let params = [
+        ("module", "contract"),
+        ("action", "getsourcecode"),
+        ("address", proxy_address_str.as_str()),
+    ];
+    let response = client.send_etherscan_request(Method::GET, &params).await;
+    assert_eq!(response.status, "1", "{response:?}");
+    let source_code = &response.result[0];
+    assert_eq!(source_code["Proxy"], "1");
+    assert_eq!(
+        source_code["Implementation"],
+        format!("{implementation_address:?}")
+    );
+}
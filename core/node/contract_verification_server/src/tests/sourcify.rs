@@ -0,0 +1,271 @@
+//! Tests for the Sourcify-compatible API.
+
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use zksync_dal::{Core, CoreDal};
+use zksync_types::{contract_verification::api::SourceCodeData, web3::keccak256, L2ChainId, H256};
+
+use super::*;
+
+const SOURCE: &str = "contract Test {}";
+
+fn sourcify_verification_body(address: Address) -> serde_json::Value {
+    let metadata = serde_json::json!({
+        "compiler": { "version": "0.8.27+commit.40a35a09" },
+        "language": "Solidity",
+        "output": { "abi": [] },
+        "settings": {
+            "compilationTarget": { "contracts/Test.sol": "Test" },
+            "optimizer": { "enabled": true, "runs": 200 }
+        },
+        "sources": {
+            "contracts/Test.sol": { "keccak256": H256(keccak256(SOURCE.as_bytes())) }
+        },
+        "version": 1
+    });
+    serde_json::json!({
+        "address": address,
+        "chain": "270",
+        "files": {
+            "metadata.json": metadata.to_string(),
+            "Test.sol": SOURCE,
+        },
+        "zksolcVersion": ZKSOLC_VERSION,
+    })
+}
+
+async fn wait_for_request(pool: &ConnectionPool<Core>, id: usize) {
+    let mut storage = pool.connection().await.unwrap();
+    while storage
+        .contract_verification_dal()
+        .get_verification_request_status(id)
+        .await
+        .unwrap()
+        .is_none()
+    {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+}
+
+#[test_casing(2, [false, true])]
+#[tokio::test]
+async fn verifying_contract_via_sourcify_api(partial_match: bool) {
+    let pool = ConnectionPool::test_pool().await;
+    let contract_verifier = MockContractVerifier::new(pool.clone());
+    let client = MockApiClient::new(pool.clone());
+    let mut storage = pool.connection().await.unwrap();
+    prepare_storage(&mut storage).await;
+
+    let address = Address::repeat_byte(0x23);
+    mock_deploy_contract(&mut storage, address, BytecodeMarker::EraVm).await;
+
+    // The request should be equivalent to one submitted via the native API.
+    let verification_request = serde_json::json!({
+        "contractAddress": address,
+        "codeFormat": "solidity-standard-json-input",
+        "sourceCode": {
+            "language": "Solidity",
+            "sources": { "contracts/Test.sol": { "content": SOURCE } },
+            "settings": { "optimizer": { "enabled": true, "runs": 200 } },
+        },
+        "contractName": "contracts/Test.sol:Test",
+        "compilerZksolcVersion": ZKSOLC_VERSION,
+        "compilerSolcVersion": SOLC_VERSION,
+        "optimizationUsed": true,
+        "optimizerRuns": 200,
+    });
+    let body = sourcify_verification_body(address);
+    let verify_future = client.send_sourcify_request("/verify", Some(&body));
+    let verifier_future = async {
+        wait_for_request(&pool, 1).await;
+        contract_verifier
+            .pick_up_next_request(1, &verification_request, BytecodeMarker::EraVm)
+            .await;
+        let mut verification_info = mock_verification_info(1, &verification_request);
+        if partial_match {
+            verification_info.mark_as_partial_match();
+        }
+        contract_verifier.verify_contract(verification_info).await;
+    };
+    let ((status, response), ()) = tokio::join!(verify_future, verifier_future);
+
+    assert_eq!(status, StatusCode::OK, "{response:?}");
+    let expected_status = if partial_match { "partial" } else { "perfect" };
+    assert_eq!(
+        response,
+        serde_json::json!({
+            "result": [{ "address": address, "chainId": "270", "status": expected_status }],
+        })
+    );
+
+    let stored_request = client.verification_info(address).await.request.req;
+    let SourceCodeData::StandardJsonInput(input) = &stored_request.source_code_data else {
+        panic!(
+            "unexpected source code: {:?}",
+            stored_request.source_code_data
+        );
+    };
+    assert_eq!(input["sources"]["contracts/Test.sol"]["content"], SOURCE);
+
+    let (status, response) = client
+        .send_sourcify_request(&format!("/files/any/270/{address:?}"), None)
+        .await;
+    assert_eq!(status, StatusCode::OK, "{response:?}");
+    let expected_status = if partial_match { "partial" } else { "full" };
+    assert_eq!(response["status"], expected_status);
+    let files = response["files"].as_array().unwrap();
+    assert_eq!(files.len(), 2, "{files:?}");
+
+    let match_dir = if partial_match {
+        "partial_match"
+    } else {
+        "full_match"
+    };
+    assert_eq!(files[0]["name"], "metadata.json");
+    assert_eq!(
+        files[0]["path"],
+        format!("/contracts/{match_dir}/270/{address:?}/metadata.json")
+    );
+    let metadata: serde_json::Value =
+        serde_json::from_str(files[0]["content"].as_str().unwrap()).unwrap();
+    assert_eq!(metadata["compiler"]["version"], SOLC_VERSION);
+    assert_eq!(
+        metadata["settings"]["compilationTarget"],
+        serde_json::json!({ "contracts/Test.sol": "Test" })
+    );
+    assert_eq!(
+        metadata["sources"]["contracts/Test.sol"]["keccak256"],
+        serde_json::json!(H256(keccak256(SOURCE.as_bytes())))
+    );
+
+    assert_eq!(files[1]["name"], "Test.sol");
+    assert_eq!(
+        files[1]["path"],
+        format!("/contracts/{match_dir}/270/{address:?}/sources/contracts/Test.sol")
+    );
+    assert_eq!(files[1]["content"], SOURCE);
+}
+
+#[tokio::test]
+async fn sourcify_verification_timeout() {
+    let pool = ConnectionPool::test_pool().await;
+    let mut api = RestApi::new(pool.clone(), pool.clone(), L2ChainId::default());
+    api.sourcify_verification_timeout = Duration::ZERO;
+    let client = MockApiClient::from_api(api);
+    let mut storage = pool.connection().await.unwrap();
+    prepare_storage(&mut storage).await;
+
+    let address = Address::repeat_byte(0x23);
+    mock_deploy_contract(&mut storage, address, BytecodeMarker::EraVm).await;
+
+    let body = sourcify_verification_body(address);
+    let (status, response) = client.send_sourcify_request("/verify", Some(&body)).await;
+    assert_eq!(status, StatusCode::ACCEPTED, "{response:?}");
+    let error = response["error"].as_str().unwrap();
+    assert!(error.contains("still in progress"), "{error}");
+
+    // The request should remain in the queue.
+    let status = client.verification_status(1).await;
+    assert_eq!(status.status, "queued");
+}
+
+#[tokio::test]
+async fn sourcify_errors() {
+    let pool = ConnectionPool::test_pool().await;
+    let client = MockApiClient::new(pool.clone());
+    let mut storage = pool.connection().await.unwrap();
+    prepare_storage(&mut storage).await;
+
+    let address = Address::repeat_byte(0x23);
+    let (status, response) = client
+        .send_sourcify_request(&format!("/files/any/270/{address:?}"), None)
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(
+        response,
+        serde_json::json!({ "error": "Files have not been found!" })
+    );
+
+    let body = sourcify_verification_body(address);
+    let (status, response) = client.send_sourcify_request("/verify", Some(&body)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        response,
+        serde_json::json!({ "error": ApiError::NoDeployedContract.message() })
+    );
+
+    mock_deploy_contract(&mut storage, address, BytecodeMarker::EraVm).await;
+    let mut body = sourcify_verification_body(address);
+    body["files"]
+        .as_object_mut()
+        .unwrap()
+        .remove("Test.sol")
+        .unwrap();
+    let (status, response) = client.send_sourcify_request("/verify", Some(&body)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        response,
+        serde_json::json!({ "error": "Missing source file: contracts/Test.sol" })
+    );
+}
+
+#[tokio::test]
+async fn sourcify_requests_for_other_chains_are_rejected() {
+    let pool = ConnectionPool::test_pool().await;
+    let client = MockApiClient::new(pool.clone());
+    let mut storage = pool.connection().await.unwrap();
+    prepare_storage(&mut storage).await;
+
+    let address = Address::repeat_byte(0x23);
+    mock_deploy_contract(&mut storage, address, BytecodeMarker::EraVm).await;
+    let verification_request = serde_json::json!({
+        "contractAddress": address,
+        "codeFormat": "solidity-standard-json-input",
+        "sourceCode": {
+            "language": "Solidity",
+            "sources": { "contracts/Test.sol": { "content": SOURCE } },
+            "settings": { "optimizer": { "enabled": true, "runs": 200 } },
+        },
+        "contractName": "contracts/Test.sol:Test",
+        "compilerZksolcVersion": ZKSOLC_VERSION,
+        "compilerSolcVersion": SOLC_VERSION,
+        "optimizationUsed": true,
+        "optimizerRuns": 200,
+    });
+    let id = client
+        .send_verification_request(&verification_request)
+        .await;
+    let contract_verifier = MockContractVerifier::new(pool.clone());
+    contract_verifier
+        .pick_up_next_request(id, &verification_request, BytecodeMarker::EraVm)
+        .await;
+    contract_verifier
+        .verify_contract(mock_verification_info(id, &verification_request))
+        .await;
+
+    let (status, response) = client
+        .send_sourcify_request(&format!("/files/any/270/{address:?}"), None)
+        .await;
+    assert_eq!(status, StatusCode::OK, "{response:?}");
+
+    for chain_id in ["1", "0x10e", "test"] {
+        let (status, response) = client
+            .send_sourcify_request(&format!("/files/any/{chain_id}/{address:?}"), None)
+            .await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "{response:?}");
+        assert_eq!(
+            response,
+            serde_json::json!({ "error": "Files have not been found!" })
+        );
+
+        let mut body = sourcify_verification_body(address);
+        body["chain"] = chain_id.into();
+        let (status, response) = client.send_sourcify_request("/verify", Some(&body)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{response:?}");
+        assert_eq!(
+            response,
+            serde_json::json!({ "error": format!("Unsupported chain `{chain_id}`") })
+        );
+    }
+}
@@ -8,18 +8,27 @@ use axum::{
 use http_body_util::BodyExt as _;
 use serde::Deserialize;
 use tower::ServiceExt;
+use url::form_urlencoded;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_node_test_utils::create_l2_block;
 use zksync_types::{
+    address_to_h256,
     bytecode::{BytecodeHash, BytecodeMarker},
-    contract_verification::api::{
-        CompilationArtifacts, CompilerVersions, VerificationIncomingRequest, VerificationInfo,
-        VerificationRequest, VerificationRequestStatus,
+    contract_verification::{
+        api::{
+            CompilationArtifacts, CompilerVersions, VerificationIncomingRequest, VerificationInfo,
+            VerificationLevel, VerificationRequest, VerificationRequestStatus,
+        },
+        proxy::ProxyVerificationInfo,
     },
-    get_code_key, Address, L2BlockNumber, ProtocolVersion, StorageLog, H256,
+    get_code_key,
+    tx::IncludedTxLocation,
+    Address, L1BatchNumber, L2BlockNumber, L2ChainId, ProtocolVersion, StorageLog,
+    CONTRACT_DEPLOYER_ADDRESS, H256,
 };
+use zksync_vm_interface::VmEvent;
 
-use crate::{api_impl::ApiError, RestApi};
+use crate::{api_impl::ApiError, etherscan::types::EtherscanResponse, RestApi};
 
 pub(super) const SOLC_VERSION: &str = "0.8.27";
 pub(super) const ZKSOLC_VERSION: &str = "1.5.6";
@@ -48,15 +57,19 @@ pub(super) async fn prepare_storage(storage: &mut Connection<'_, Core>) {
         .unwrap();
 }
 
+fn mock_bytecode_hash(kind: BytecodeMarker) -> H256 {
+    match kind {
+        BytecodeMarker::EraVm => BytecodeHash::for_bytecode(&[0; 32]).value(),
+        BytecodeMarker::Evm => BytecodeHash::for_evm_bytecode(0, &[0; 96]).value(),
+    }
+}
+
 pub(super) async fn mock_deploy_contract(
     storage: &mut Connection<'_, Core>,
     address: Address,
     kind: BytecodeMarker,
 ) {
-    let bytecode_hash = match kind {
-        BytecodeMarker::EraVm => BytecodeHash::for_bytecode(&[0; 32]).value(),
-        BytecodeMarker::Evm => BytecodeHash::for_evm_bytecode(0, &[0; 96]).value(),
-    };
+    let bytecode_hash = mock_bytecode_hash(kind);
     let deploy_log = StorageLog::new_write_log(get_code_key(&address), bytecode_hash);
     storage
         .storage_logs_dal()
@@ -65,6 +78,38 @@ pub(super) async fn mock_deploy_contract(
         .unwrap()
 }
 
+/// Saves deployment events for contracts with the same bytecode in the genesis L2 block.
+pub(super) async fn mock_deploy_events(
+    storage: &mut Connection<'_, Core>,
+    addresses: &[Address],
+    kind: BytecodeMarker,
+) {
+    let bytecode_hash = mock_bytecode_hash(kind);
+    let events: Vec<_> = addresses
+        .iter()
+        .map(|address| VmEvent {
+            location: (L1BatchNumber(0), 0),
+            address: CONTRACT_DEPLOYER_ADDRESS,
+            indexed_topics: vec![
+                VmEvent::DEPLOY_EVENT_SIGNATURE,
+                H256::zero(),
+                bytecode_hash,
+                address_to_h256(address),
+            ],
+            value: vec![],
+        })
+        .collect();
+    let location = IncludedTxLocation {
+        tx_hash: H256::repeat_byte(1),
+        tx_index_in_l2_block: 0,
+    };
+    storage
+        .events_dal()
+        .save_events(L2BlockNumber(0), &[(location, events.iter().collect())])
+        .await
+        .unwrap();
+}
+
 pub(super) fn mock_verification_info(
     id: usize,
     verification_request: &serde_json::Value,
@@ -80,7 +125,9 @@ pub(super) fn mock_verification_info(
             abi: Default::default(),
         },
         verified_at: Default::default(),
+        verification_level: Some(VerificationLevel::Full),
         verification_problems: Vec::new(),
+        inherited_from: None,
     }
 }
 
@@ -158,8 +205,12 @@ pub(super) struct MockApiClient {
 
 impl MockApiClient {
     pub fn new(pool: ConnectionPool<Core>) -> Self {
+        Self::from_api(RestApi::new(pool.clone(), pool, L2ChainId::default()))
+    }
+
+    pub fn from_api(api: RestApi) -> Self {
         Self {
-            router: RestApi::new(pool.clone(), pool).into_router(),
+            router: api.into_router(),
         }
     }
 
@@ -213,6 +264,30 @@ impl MockApiClient {
         Self::assert_response_error(response, expected_err).await;
     }
 
+    pub async fn proxy_verification_info(&self, address: Address) -> ProxyVerificationInfo {
+        let response = self
+            .send_request(
+                &format!("/contract_verification/proxy_info/{address:?}"),
+                None,
+            )
+            .await;
+        Self::json_response::<ProxyVerificationInfo>(response).await
+    }
+
+    pub async fn assert_proxy_verification_info_error(
+        &self,
+        address: Address,
+        expected_err: ApiError,
+    ) {
+        let response = self
+            .send_request(
+                &format!("/contract_verification/proxy_info/{address:?}"),
+                None,
+            )
+            .await;
+        Self::assert_response_error(response, expected_err).await;
+    }
+
     pub async fn zksolc_versions(&self) -> Vec<String> {
         let response = self
             .send_request("/contract_verification/zksolc_versions", None)
@@ -227,6 +302,44 @@ impl MockApiClient {
         Self::json_response::<Vec<String>>(response).await
     }
 
+    /// Sends an Etherscan API request. For `POST` requests, params are sent as a form; otherwise, in the query string.
+    pub async fn send_etherscan_request(
+        &self,
+        method: Method,
+        params: &[(&str, &str)],
+    ) -> EtherscanResponse {
+        let encoded_params = form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(params)
+            .finish();
+        let req = if method == Method::POST {
+            Request::builder()
+                .method(method)
+                .uri("/api")
+                .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+                .body(Body::from(encoded_params))
+        } else {
+            Request::builder()
+                .method(method)
+                .uri(format!("/api?{encoded_params}"))
+                .body(Body::empty())
+        };
+        let response = self.router.clone().oneshot(req.unwrap()).await.unwrap();
+        Self::json_response(response).await
+    }
+
+    /// Sends a Sourcify API request and returns the response status together with the JSON body.
+    pub async fn send_sourcify_request(
+        &self,
+        url: &str,
+        body: Option<&serde_json::Value>,
+    ) -> (StatusCode, serde_json::Value) {
+        let response = self.send_request(url, body).await;
+        let status = response.status();
+        let response = response.into_body().collect().await.unwrap().to_bytes();
+        let response = serde_json::from_slice(&response).expect("Unable to deserialize response");
+        (status, response)
+    }
+
     async fn send_request(&self, url: &str, body: Option<&serde_json::Value>) -> Response<Body> {
         let (method, body) = match body {
             Some(body) => (Method::POST, Body::from(serde_json::to_vec(body).unwrap())),
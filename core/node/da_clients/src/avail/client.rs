@@ -19,7 +19,7 @@ use zksync_types::{
 };
 
 use crate::{
-    avail::sdk::{GasRelayClient, RawAvailClient},
+    avail::sdk::{decode_submitted_data, GasRelayClient, RawAvailClient},
     utils::{to_non_retriable_da_error, to_retriable_da_error},
 };
 
@@ -133,6 +133,7 @@ impl AvailClient {
                     &conf.gas_relay_api_url,
                     gas_relay_api_key.0.expose_secret(),
                     conf.max_retries,
+                    conf.inclusion_delay_ms.map(Duration::from_millis),
                     Arc::clone(&api_client),
                 )
                 .await?;
@@ -195,16 +196,28 @@ impl DataAvailabilityClient for AvailClient {
                     .get_tx_id(&ws_client, block_hash.as_str(), extrinsic.as_str())
                     .await
                     .map_err(to_non_retriable_da_error)?;
-                Ok(DispatchResponse::from(format!("{}:{}", block_hash, tx_id)))
+                // The fee is only used for accounting, so failing to query it shouldn't fail the dispatch.
+                let fee = client
+                    .query_fee(&ws_client, block_hash.as_str(), extrinsic.as_str())
+                    .await
+                    .map_err(|err| {
+                        tracing::warn!("Failed querying fee for Avail extrinsic: {err:#}")
+                    })
+                    .ok();
+                Ok(DispatchResponse {
+                    blob_id: format!("{}:{}", block_hash, tx_id),
+                    fee,
+                })
             }
             AvailClientMode::GasRelay(client) => {
                 let (block_hash, extrinsic_index) = client
                     .post_data(data)
                     .await
                     .map_err(to_retriable_da_error)?;
-                Ok(DispatchResponse {
-                    blob_id: format!("{:x}:{}", block_hash, extrinsic_index),
-                })
+                Ok(DispatchResponse::from(format!(
+                    "{:x}:{}",
+                    block_hash, extrinsic_index
+                )))
             }
         }
     }
@@ -270,6 +283,45 @@ impl DataAvailabilityClient for AvailClient {
         }
     }
 
+    async fn fetch_blob(&self, blob_id: &str) -> anyhow::Result<Option<Vec<u8>>, DAError> {
+        let (AvailClientMode::Default(client), AvailClientConfig::FullClient(default_config)) =
+            (self.sdk_client.as_ref(), &self.config.config)
+        else {
+            // The gas relay API doesn't provide access to the submitted data
+            return Err(DAError {
+                error: anyhow!("Blob retrieval is not supported in the gas relay mode"),
+                is_retriable: false,
+            });
+        };
+
+        let (block_hash, tx_idx) = blob_id.split_once(':').ok_or_else(|| DAError {
+            error: anyhow!("Invalid blob ID format"),
+            is_retriable: false,
+        })?;
+        let tx_idx = tx_idx.parse::<usize>().map_err(to_non_retriable_da_error)?;
+        let block_hash = if block_hash.starts_with("0x") {
+            block_hash.to_owned()
+        } else {
+            format!("0x{block_hash}")
+        };
+
+        let ws_client = WsClientBuilder::default()
+            .build(default_config.api_node_url.as_str())
+            .await
+            .map_err(to_retriable_da_error)?;
+        let Some(extrinsic) = client
+            .get_extrinsic(&ws_client, &block_hash, tx_idx)
+            .await
+            .map_err(to_retriable_da_error)?
+        else {
+            return Ok(None);
+        };
+
+        decode_submitted_data(&extrinsic)
+            .map(Some)
+            .map_err(to_non_retriable_da_error)
+    }
+
     fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient> {
         Box::new(self.clone())
     }
@@ -343,6 +343,60 @@ impl RawAvailClient {
         Ok(tx_id)
     }
 
+    /// Returns the fee (in the smallest units of AVAIL) charged for the extrinsic included into the given block
+    pub(crate) async fn query_fee(
+        &self,
+        client: &Client,
+        block_hash: &str,
+        hex_ext: &str,
+    ) -> anyhow::Result<u64> {
+        let resp: serde_json::Value = client
+            .request(
+                "payment_queryInfo",
+                rpc_params![format!("0x{hex_ext}"), format!("0x{block_hash}")],
+            )
+            .await
+            .context("Error calling payment_queryInfo RPC")?;
+
+        let partial_fee = resp
+            .get("partialFee")
+            .ok_or_else(|| anyhow::anyhow!("No field named partialFee in fee info"))?;
+        // Depending on the node version, the fee is serialized either as a number or as a decimal string.
+        match partial_fee {
+            serde_json::Value::Number(fee) => fee
+                .as_u64()
+                .ok_or_else(|| anyhow::anyhow!("Invalid partial fee: {fee}")),
+            serde_json::Value::String(fee) => fee.parse().context("Unable to parse partial fee"),
+            _ => bail!("Invalid partial fee: {partial_fee}"),
+        }
+    }
+
+    /// Returns the extrinsic with the given index in the block, or `None` if the block is not known to the node
+    pub(crate) async fn get_extrinsic(
+        &self,
+        client: &Client,
+        block_hash: &str,
+        tx_id: usize,
+    ) -> anyhow::Result<Option<Vec<u8>>> {
+        let resp: serde_json::Value = client
+            .request("chain_getBlock", rpc_params![block_hash])
+            .await?;
+        if resp.is_null() {
+            return Ok(None);
+        }
+
+        let extrinsic = resp
+            .get("block")
+            .and_then(|block| block.get("extrinsics"))
+            .and_then(|extrinsics| extrinsics.get(tx_id))
+            .and_then(|extrinsic| extrinsic.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Extrinsic #{tx_id} not found in block"))?;
+        let extrinsic = hex::decode(extrinsic.strip_prefix("0x").unwrap_or(extrinsic))
+            .context("Extrinsic is not hex-encoded")?;
+
+        Ok(Some(extrinsic))
+    }
+
     /// Returns the balance of the address controlled by the `keypair`
     pub async fn balance(&self, client: &Client) -> anyhow::Result<u64> {
         let address = to_addr(self.keypair.clone());
@@ -361,6 +415,57 @@ impl RawAvailClient {
     }
 }
 
+/// Extracts the data from an extrinsic with the layout produced by [`RawAvailClient::build_extrinsic()`],
+/// i.e., a signed `DataAvailability.submit_data` call.
+pub(crate) fn decode_submitted_data(extrinsic: &[u8]) -> anyhow::Result<Vec<u8>> {
+    fn skip(input: &mut &[u8], len: usize) -> anyhow::Result<()> {
+        anyhow::ensure!(input.len() >= len, "Extrinsic is truncated");
+        *input = &input[len..];
+        Ok(())
+    }
+
+    let mut input = extrinsic;
+    let Compact(len) = Compact::<u32>::decode(&mut input)?;
+    anyhow::ensure!(input.len() == len as usize, "Extrinsic length mismatch");
+
+    let version = u8::decode(&mut input)?;
+    anyhow::ensure!(
+        version == 0b10000000 + PROTOCOL_VERSION,
+        "Unsupported extrinsic version: {version}"
+    );
+
+    // sender
+    let address_kind = u8::decode(&mut input)?;
+    anyhow::ensure!(
+        address_kind == 0,
+        "Unsupported address kind: {address_kind}"
+    );
+    skip(&mut input, 32)?;
+
+    // signature
+    let signature_len = match u8::decode(&mut input)? {
+        0 | 1 => 64, // Ed25519 or Sr25519
+        2 => 65,     // Ecdsa
+        kind => bail!("Unsupported signature kind: {kind}"),
+    };
+    skip(&mut input, signature_len)?;
+
+    // extra params: era (1 byte if immortal, 2 bytes otherwise), nonce, tip and app ID
+    if u8::decode(&mut input)? != 0 {
+        skip(&mut input, 1)?;
+    }
+    Compact::<u64>::decode(&mut input)?;
+    Compact::<u128>::decode(&mut input)?;
+    Compact::<u32>::decode(&mut input)?;
+
+    // call data: pallet and call indices, followed by the submitted data
+    skip(&mut input, 2)?;
+    let data = Vec::<u8>::decode(&mut input)?;
+    anyhow::ensure!(input.is_empty(), "Unexpected bytes after the call data");
+
+    Ok(data)
+}
+
 fn blake2<const N: usize>(data: Vec<u8>) -> [u8; N] {
     blake2b_simd::Params::new()
         .hash_length(N)
@@ -404,6 +509,7 @@ pub(crate) struct GasRelayClient {
     api_url: String,
     api_key: String,
     max_retries: usize,
+    inclusion_delay: time::Duration,
     api_client: Arc<reqwest::Client>,
 }
 
@@ -430,12 +536,14 @@ impl GasRelayClient {
         api_url: &str,
         api_key: &str,
         max_retries: usize,
+        inclusion_delay: Option<time::Duration>,
         api_client: Arc<reqwest::Client>,
     ) -> anyhow::Result<Self> {
         Ok(Self {
             api_url: api_url.to_owned(),
             api_key: api_key.to_owned(),
             max_retries,
+            inclusion_delay: inclusion_delay.unwrap_or(Self::DEFAULT_INCLUSION_DELAY),
             api_client,
         })
     }
@@ -474,7 +582,7 @@ impl GasRelayClient {
             self.api_url, submit_response.submission_id
         );
 
-        tokio::time::sleep(Self::DEFAULT_INCLUSION_DELAY).await;
+        tokio::time::sleep(self.inclusion_delay).await;
         let status_response = (|| async {
             self.api_client
                 .get(&status_url)
@@ -517,3 +625,31 @@ impl GasRelayClient {
         Ok((block_hash, extrinsic_index))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decoding_submitted_data() {
+        let data = vec![1, 2, 3, 4, 5];
+        let mut encoded_inner = vec![0b10000000 + PROTOCOL_VERSION];
+        encoded_inner.push(0);
+        encoded_inner.extend([1; 32]);
+        encoded_inner.push(1);
+        encoded_inner.extend([2; 64]);
+        encoded_inner.push(0);
+        Compact(5_u64).encode_to(&mut encoded_inner);
+        Compact(0_u128).encode_to(&mut encoded_inner);
+        Compact(7_u32).encode_to(&mut encoded_inner);
+        encoded_inner.extend([29, 1]);
+        data.encode_to(&mut encoded_inner);
+
+        let mut extrinsic = vec![];
+        Compact(encoded_inner.len() as u32).encode_to(&mut extrinsic);
+        extrinsic.extend_from_slice(&encoded_inner);
+        assert_eq!(decode_submitted_data(&extrinsic).unwrap(), data);
+
+        decode_submitted_data(&extrinsic[..extrinsic.len() - 1]).unwrap_err();
+    }
+}
@@ -65,7 +65,7 @@ impl DataAvailabilityClient for CelestiaClient {
         let blob = Blob::new(namespace, data).map_err(to_non_retriable_da_error)?;
 
         let commitment = blob.commitment;
-        let blob_tx = self
+        let (blob_tx, fee) = self
             .client
             .prepare(vec![blob])
             .await
@@ -83,6 +83,7 @@ impl DataAvailabilityClient for CelestiaClient {
 
         Ok(DispatchResponse {
             blob_id: hex::encode(&blob_bytes),
+            fee: Some(fee),
         })
     }
 
@@ -90,6 +91,13 @@ impl DataAvailabilityClient for CelestiaClient {
         Ok(Some(InclusionData { data: vec![] }))
     }
 
+    async fn fetch_blob(&self, _: &str) -> Result<Option<Vec<u8>>, DAError> {
+        // Blobs can only be retrieved via the Celestia node API, while the client talks to a consensus node.
+        Err(to_non_retriable_da_error(anyhow::anyhow!(
+            "Blob retrieval is not supported by the Celestia client"
+        )))
+    }
+
     fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient> {
         Box::new(self.clone())
     }
@@ -70,8 +70,8 @@ impl RawCelestiaClient {
         })
     }
 
-    /// Prepares a blob transaction for the given blobs.
-    pub(crate) async fn prepare(&self, blobs: Vec<Blob>) -> anyhow::Result<BlobTx> {
+    /// Prepares a blob transaction for the given blobs. Returns the transaction together with its fee (in `utia`).
+    pub(crate) async fn prepare(&self, blobs: Vec<Blob>) -> anyhow::Result<(BlobTx, u64)> {
         let (gas_per_blob_byte, tx_size_cost_per_byte, min_gas_price, base_account) = tokio::try_join!(
             self.get_gas_per_blob_byte(),
             self.fetch_tx_size_cost_per_byte(),
@@ -97,7 +97,7 @@ impl RawCelestiaClient {
             &self.signing_key,
         );
 
-        Ok(new_blob_tx(&signed_tx, blobs.iter()))
+        Ok((new_blob_tx(&signed_tx, blobs.iter()), fee))
     }
 
     /// Submits the blob transaction to the node and returns the height of the block in which it was
@@ -70,6 +70,13 @@ impl DataAvailabilityClient for EigenClient {
         }
     }
 
+    async fn fetch_blob(&self, blob_id: &str) -> Result<Option<Vec<u8>>, DAError> {
+        self.client
+            .get_blob(blob_id)
+            .await
+            .map_err(to_retriable_da_error)
+    }
+
     fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient> {
         Box::new(self.clone())
     }
@@ -5,13 +5,14 @@ mod sdk;
 mod verifier;
 
 pub use self::client::{EigenClient, GetBlobData};
+/// Generated types of the EigenDA disperser API. Public so that they can be used by stand-in servers in tests.
 #[allow(clippy::all)]
-pub(crate) mod disperser {
+pub mod disperser {
     include!("generated/disperser.rs");
 }
 
 #[allow(clippy::all)]
-pub(crate) mod common {
+pub mod common {
     include!("generated/common.rs");
 }
 
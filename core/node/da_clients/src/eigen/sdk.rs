@@ -189,6 +189,17 @@ impl RawEigenClient {
         Ok(Some(blob_info))
     }
 
+    /// Retrieves the blob with the given request ID from the disperser. Returns `None` if the blob
+    /// is not confirmed yet.
+    pub async fn get_blob(&self, request_id: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        let Some(blob_info) = self.try_get_inclusion_data(request_id.to_string()).await? else {
+            return Ok(None);
+        };
+        let blob_info = blob_info::BlobInfo::try_from(blob_info)
+            .map_err(|e| anyhow::anyhow!("Failed to convert blob info: {}", e))?;
+        Ok(Some(self.get_blob_data(blob_info).await?))
+    }
+
     pub async fn get_inclusion_data(&self, request_id: &str) -> anyhow::Result<Option<Vec<u8>>> {
         let blob_info = self.get_commitment(request_id).await?;
         if let Some(blob_info) = blob_info {
@@ -18,6 +18,13 @@ impl DataAvailabilityClient for NoDAClient {
         Ok(Some(InclusionData::default()))
     }
 
+    async fn fetch_blob(&self, _: &str) -> Result<Option<Vec<u8>>, DAError> {
+        Err(DAError {
+            error: anyhow::anyhow!("NoDA client doesn't store blobs"),
+            is_retriable: false,
+        })
+    }
+
     fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient> {
         Box::new(self.clone())
     }
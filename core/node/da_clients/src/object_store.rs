@@ -49,9 +49,7 @@ impl DataAvailabilityClient for ObjectStoreDAClient {
             });
         }
 
-        Ok(DispatchResponse {
-            blob_id: batch_number.to_string(),
-        })
+        Ok(DispatchResponse::from(batch_number.to_string()))
     }
 
     async fn get_inclusion_data(&self, key: &str) -> Result<Option<InclusionData>, DAError> {
@@ -80,6 +78,26 @@ impl DataAvailabilityClient for ObjectStoreDAClient {
         return Ok(Some(InclusionData::default()));
     }
 
+    async fn fetch_blob(&self, key: &str) -> Result<Option<Vec<u8>>, DAError> {
+        let key_u32 = key.parse::<u32>().map_err(|err| DAError {
+            error: anyhow::Error::from(err).context(format!("Failed to parse blob key: {}", key)),
+            is_retriable: false,
+        })?;
+
+        match self
+            .object_store
+            .get::<StorablePubdata>(L1BatchNumber(key_u32))
+            .await
+        {
+            Ok(pubdata) => Ok(Some(pubdata.data)),
+            Err(zksync_object_store::ObjectStoreError::KeyNotFound(_)) => Ok(None),
+            Err(err) => Err(DAError {
+                is_retriable: err.is_retriable(),
+                error: anyhow::Error::from(err),
+            }),
+        }
+    }
+
     fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient> {
         Box::new(self.clone())
     }
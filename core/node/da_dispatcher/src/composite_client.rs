@@ -0,0 +1,529 @@
+//! Composite client dispatching blobs to the primary and (optionally) the secondary DA layer.
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::Context as _;
+use tokio::time::Instant;
+use zksync_config::{configs::da_dispatcher::SecondaryDALayerPolicy, DADispatcherConfig};
+use zksync_da_client::{
+    types::{DispatchResponse, InclusionData},
+    DataAvailabilityClient,
+};
+use zksync_types::{
+    commitment::PubdataType,
+    pubdata_da::{DataAvailabilityBlob, DataAvailabilityLayer},
+    utils::client_type_to_pubdata_type,
+    L1BatchNumber,
+};
+
+use crate::{
+    da_dispatcher::retry,
+    metrics::{DALayerLabel, METRICS},
+};
+
+/// Blob dispatched via [`CompositeDAClient`].
+#[derive(Debug, PartialEq)]
+pub(crate) struct DispatchedBlob {
+    /// ID of the blob in the `layer`.
+    pub blob_id: String,
+    /// DA layer holding the blob.
+    pub layer: DataAvailabilityLayer,
+    /// ID of the blob copy in the secondary layer. Only set when dual-posting blobs.
+    pub secondary_blob_id: Option<String>,
+    /// Fee paid for the blob to the `layer`, in the smallest units of the layer's native token.
+    pub fee: Option<u64>,
+}
+
+#[derive(Debug, Default)]
+struct SwitchOverState {
+    consecutive_primary_failures: u32,
+    /// Time of the last failed dispatch to the primary layer after which the secondary layer was used.
+    switched_over_at: Option<Instant>,
+}
+
+/// Client dispatching blobs to the primary DA layer and, depending on the [`SecondaryDALayerPolicy`],
+/// to the secondary one.
+///
+/// The L1 DA validator can only verify inclusion of blobs in a single DA layer, so the secondary layer is never used
+/// as a source of inclusion data. With the fallback policy, this means that the chain must use dummy inclusion data;
+/// this is checked by the dispatcher on start.
+#[derive(Debug, Clone)]
+pub(crate) struct CompositeDAClient {
+    primary: Box<dyn DataAvailabilityClient>,
+    secondary: Option<Box<dyn DataAvailabilityClient>>,
+    policy: SecondaryDALayerPolicy,
+    max_retries: u16,
+    fallback_after_failures: u32,
+    primary_recheck_interval: Duration,
+    state: Arc<Mutex<SwitchOverState>>,
+}
+
+impl CompositeDAClient {
+    pub fn new(primary: Box<dyn DataAvailabilityClient>, config: &DADispatcherConfig) -> Self {
+        Self {
+            primary,
+            secondary: None,
+            policy: config.secondary_layer_policy(),
+            max_retries: config.max_retries(),
+            fallback_after_failures: config.fallback_after_failures(),
+            primary_recheck_interval: config.primary_layer_recheck_interval(),
+            state: Arc::default(),
+        }
+    }
+
+    pub fn set_secondary(&mut self, secondary: Box<dyn DataAvailabilityClient>) {
+        self.secondary = Some(secondary);
+    }
+
+    pub fn primary(&self) -> &dyn DataAvailabilityClient {
+        self.primary.as_ref()
+    }
+
+    /// Returns the policy of using the secondary layer, or `None` if the secondary layer is not configured.
+    pub fn secondary_layer_policy(&self) -> Option<SecondaryDALayerPolicy> {
+        self.secondary.as_ref().map(|_| self.policy)
+    }
+
+    /// Returns the maximum blob size accepted by all configured DA layers.
+    pub fn blob_size_limit(&self) -> Option<usize> {
+        let secondary_limit = self
+            .secondary
+            .as_ref()
+            .and_then(|secondary| secondary.blob_size_limit());
+        match (self.primary.blob_size_limit(), secondary_limit) {
+            (Some(primary), Some(secondary)) => Some(primary.min(secondary)),
+            (primary, secondary) => primary.or(secondary),
+        }
+    }
+
+    /// Returns the pubdata type of dispatched blobs. It is always defined by the primary layer since it's what
+    /// the L1 DA validator and external nodes expect; the layer actually holding a blob is recorded separately.
+    pub fn pubdata_type(&self) -> PubdataType {
+        client_type_to_pubdata_type(self.primary.client_type())
+    }
+
+    pub async fn dispatch_blob(
+        &self,
+        batch_number: L1BatchNumber,
+        data: Vec<u8>,
+    ) -> anyhow::Result<DispatchedBlob> {
+        let Some(secondary) = &self.secondary else {
+            let response = self
+                .dispatch_to(self.primary.as_ref(), batch_number, &data)
+                .await?;
+            return Ok(self.dispatched(response, DataAvailabilityLayer::Primary, None));
+        };
+
+        match self.policy {
+            SecondaryDALayerPolicy::Fallback => {
+                self.dispatch_with_fallback(secondary.as_ref(), batch_number, data)
+                    .await
+            }
+            SecondaryDALayerPolicy::DualPosting => {
+                self.dispatch_to_both_layers(secondary.as_ref(), batch_number, data)
+                    .await
+            }
+        }
+    }
+
+    async fn dispatch_to(
+        &self,
+        client: &dyn DataAvailabilityClient,
+        batch_number: L1BatchNumber,
+        data: &[u8],
+    ) -> anyhow::Result<DispatchResponse> {
+        retry(self.max_retries, batch_number, || {
+            client.dispatch_blob(batch_number.0, data.to_vec())
+        })
+        .await
+    }
+
+    fn dispatched(
+        &self,
+        response: DispatchResponse,
+        layer: DataAvailabilityLayer,
+        secondary_response: Option<DispatchResponse>,
+    ) -> DispatchedBlob {
+        let label = match layer {
+            DataAvailabilityLayer::Primary => DALayerLabel::Primary,
+            DataAvailabilityLayer::Secondary => DALayerLabel::Secondary,
+        };
+        METRICS.dispatched_blobs[&label].inc();
+        if let Some(fee) = response.fee {
+            METRICS.blob_fees[&label].inc_by(fee);
+        }
+        if let Some(secondary_response) = &secondary_response {
+            METRICS.dispatched_blobs[&DALayerLabel::Secondary].inc();
+            if let Some(fee) = secondary_response.fee {
+                METRICS.blob_fees[&DALayerLabel::Secondary].inc_by(fee);
+            }
+        }
+
+        DispatchedBlob {
+            blob_id: response.blob_id,
+            layer,
+            secondary_blob_id: secondary_response.map(|response| response.blob_id),
+            fee: response.fee,
+        }
+    }
+
+    async fn dispatch_with_fallback(
+        &self,
+        secondary: &dyn DataAvailabilityClient,
+        batch_number: L1BatchNumber,
+        data: Vec<u8>,
+    ) -> anyhow::Result<DispatchedBlob> {
+        if self.should_try_primary() {
+            match self
+                .dispatch_to(self.primary.as_ref(), batch_number, &data)
+                .await
+            {
+                Ok(response) => {
+                    self.on_primary_success();
+                    return Ok(self.dispatched(response, DataAvailabilityLayer::Primary, None));
+                }
+                Err(err) => {
+                    if !self.on_primary_failure() {
+                        return Err(err.context("failed dispatching blob to the primary DA layer"));
+                    }
+                    tracing::warn!(
+                        "Failed dispatching blob for L1 batch #{batch_number} to the primary DA layer, \
+                         falling back to the secondary layer: {err:#}"
+                    );
+                }
+            }
+        }
+
+        let response = self
+            .dispatch_to(secondary, batch_number, &data)
+            .await
+            .context("failed dispatching blob to the secondary DA layer")?;
+        Ok(self.dispatched(response, DataAvailabilityLayer::Secondary, None))
+    }
+
+    /// The primary layer is tried unless the client has switched over to the secondary one recently.
+    fn should_try_primary(&self) -> bool {
+        let state = self.state.lock().unwrap();
+        state
+            .switched_over_at
+            .map_or(true, |at| at.elapsed() >= self.primary_recheck_interval)
+    }
+
+    fn on_primary_success(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.consecutive_primary_failures = 0;
+        if state.switched_over_at.take().is_some() {
+            tracing::info!("Primary DA layer is available again; switching back to it");
+            METRICS.switched_to_secondary_layer.set(0);
+        }
+    }
+
+    /// Returns whether the blob should be dispatched to the secondary layer.
+    fn on_primary_failure(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        state.consecutive_primary_failures += 1;
+        if state.consecutive_primary_failures < self.fallback_after_failures {
+            return false;
+        }
+
+        if state.switched_over_at.is_none() {
+            tracing::warn!(
+                "Switching over to the secondary DA layer after {} consecutive failed dispatches to the primary layer",
+                state.consecutive_primary_failures
+            );
+            METRICS.switched_to_secondary_layer.set(1);
+        }
+        state.switched_over_at = Some(Instant::now());
+        true
+    }
+
+    /// Dispatches the blob to both layers concurrently. The primary layer is authoritative, i.e., failing to dispatch
+    /// to it fails the entire dispatch, while failures of the secondary layer are only logged.
+    async fn dispatch_to_both_layers(
+        &self,
+        secondary: &dyn DataAvailabilityClient,
+        batch_number: L1BatchNumber,
+        data: Vec<u8>,
+    ) -> anyhow::Result<DispatchedBlob> {
+        let (primary_result, secondary_result) = futures::join!(
+            self.dispatch_to(self.primary.as_ref(), batch_number, &data),
+            self.dispatch_to(secondary, batch_number, &data)
+        );
+        let response = primary_result.context("failed dispatching blob to the primary DA layer")?;
+        let secondary_response = match secondary_result {
+            Ok(response) => Some(response),
+            Err(err) => {
+                tracing::warn!(
+                    "Failed dispatching copy of the blob for L1 batch #{batch_number} to the secondary DA layer: {err:#}"
+                );
+                METRICS.secondary_dispatch_errors.inc();
+                None
+            }
+        };
+        Ok(self.dispatched(response, DataAvailabilityLayer::Primary, secondary_response))
+    }
+
+    /// Fetches inclusion data for the blob from the layer holding it.
+    pub async fn get_inclusion_data(
+        &self,
+        blob: &DataAvailabilityBlob,
+    ) -> anyhow::Result<Option<InclusionData>> {
+        match blob.layer {
+            DataAvailabilityLayer::Primary => Ok(self
+                .primary
+                .get_inclusion_data(&blob.blob_id)
+                .await?),
+            DataAvailabilityLayer::Secondary => anyhow::bail!(
+                "blob for L1 batch #{} is held by the secondary DA layer, inclusion in which cannot be verified \
+                 by the L1 DA validator; dummy inclusion data must be used",
+                blob.l1_batch_number
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use zksync_da_client::types::{ClientType, DAError, DispatchResponse};
+
+    use super::*;
+
+    const MOCK_FEE: u64 = 100;
+
+    #[derive(Debug, Clone)]
+    struct MockDAClient {
+        name: &'static str,
+        is_available: Arc<AtomicBool>,
+        dispatched_blobs: Arc<AtomicUsize>,
+    }
+
+    impl MockDAClient {
+        fn new(name: &'static str) -> Self {
+            Self {
+                name,
+                is_available: Arc::new(AtomicBool::new(true)),
+                dispatched_blobs: Arc::default(),
+            }
+        }
+
+        fn set_available(&self, is_available: bool) {
+            self.is_available.store(is_available, Ordering::SeqCst);
+        }
+
+        fn dispatched_blobs(&self) -> usize {
+            self.dispatched_blobs.load(Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl DataAvailabilityClient for MockDAClient {
+        async fn dispatch_blob(
+            &self,
+            batch_number: u32,
+            _data: Vec<u8>,
+        ) -> Result<DispatchResponse, DAError> {
+            if !self.is_available.load(Ordering::SeqCst) {
+                return Err(DAError {
+                    error: anyhow::anyhow!("{} is unavailable", self.name),
+                    is_retriable: false,
+                });
+            }
+            self.dispatched_blobs.fetch_add(1, Ordering::SeqCst);
+            Ok(DispatchResponse {
+                blob_id: format!("{}-{batch_number}", self.name),
+                fee: Some(MOCK_FEE),
+            })
+        }
+
+        async fn get_inclusion_data(
+            &self,
+            blob_id: &str,
+        ) -> Result<Option<InclusionData>, DAError> {
+            Ok(Some(InclusionData {
+                data: blob_id.as_bytes().to_vec(),
+            }))
+        }
+
+        async fn fetch_blob(&self, _blob_id: &str) -> Result<Option<Vec<u8>>, DAError> {
+            Ok(None)
+        }
+
+        fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient> {
+            Box::new(self.clone())
+        }
+
+        fn blob_size_limit(&self) -> Option<usize> {
+            None
+        }
+
+        fn client_type(&self) -> ClientType {
+            ClientType::ObjectStore
+        }
+
+        async fn balance(&self) -> Result<u64, DAError> {
+            Ok(0)
+        }
+    }
+
+    fn create_client(
+        policy: SecondaryDALayerPolicy,
+        fallback_after_failures: u32,
+        primary_recheck_interval: Duration,
+    ) -> (CompositeDAClient, MockDAClient, MockDAClient) {
+        let config = DADispatcherConfig {
+            secondary_layer_policy: Some(policy),
+            fallback_after_failures: Some(fallback_after_failures),
+            primary_layer_recheck_interval_ms: Some(primary_recheck_interval.as_millis() as u64),
+            ..DADispatcherConfig::for_tests()
+        };
+        let primary = MockDAClient::new("primary");
+        let secondary = MockDAClient::new("secondary");
+        let mut client = CompositeDAClient::new(Box::new(primary.clone()), &config);
+        client.set_secondary(Box::new(secondary.clone()));
+        (client, primary, secondary)
+    }
+
+    fn blob(blob_id: &str, layer: DataAvailabilityLayer) -> DispatchedBlob {
+        DispatchedBlob {
+            blob_id: blob_id.to_owned(),
+            layer,
+            secondary_blob_id: None,
+            fee: Some(MOCK_FEE),
+        }
+    }
+
+    #[tokio::test]
+    async fn falling_back_to_secondary_layer() {
+        let (client, primary, secondary) = create_client(
+            SecondaryDALayerPolicy::Fallback,
+            2,
+            Duration::from_secs(3_600),
+        );
+        let dispatched = client
+            .dispatch_blob(L1BatchNumber(1), vec![1])
+            .await
+            .unwrap();
+        assert_eq!(
+            dispatched,
+            blob("primary-1", DataAvailabilityLayer::Primary)
+        );
+
+        primary.set_available(false);
+        // The first failure should not trigger the switch-over.
+        client
+            .dispatch_blob(L1BatchNumber(2), vec![2])
+            .await
+            .unwrap_err();
+        assert_eq!(secondary.dispatched_blobs(), 0);
+
+        let dispatched = client
+            .dispatch_blob(L1BatchNumber(2), vec![2])
+            .await
+            .unwrap();
+        assert_eq!(
+            dispatched,
+            blob("secondary-2", DataAvailabilityLayer::Secondary)
+        );
+
+        // The primary layer shouldn't be tried until the recheck interval elapses.
+        primary.set_available(true);
+        let dispatched = client
+            .dispatch_blob(L1BatchNumber(3), vec![3])
+            .await
+            .unwrap();
+        assert_eq!(
+            dispatched,
+            blob("secondary-3", DataAvailabilityLayer::Secondary)
+        );
+        assert_eq!(primary.dispatched_blobs(), 1);
+    }
+
+    #[tokio::test]
+    async fn switching_back_to_primary_layer() {
+        let (client, primary, _) =
+            create_client(SecondaryDALayerPolicy::Fallback, 1, Duration::ZERO);
+        primary.set_available(false);
+        let dispatched = client
+            .dispatch_blob(L1BatchNumber(1), vec![1])
+            .await
+            .unwrap();
+        assert_eq!(
+            dispatched,
+            blob("secondary-1", DataAvailabilityLayer::Secondary)
+        );
+
+        primary.set_available(true);
+        let dispatched = client
+            .dispatch_blob(L1BatchNumber(2), vec![2])
+            .await
+            .unwrap();
+        assert_eq!(
+            dispatched,
+            blob("primary-2", DataAvailabilityLayer::Primary)
+        );
+        assert!(client.state.lock().unwrap().switched_over_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn dual_posting_blobs() {
+        let (client, primary, secondary) =
+            create_client(SecondaryDALayerPolicy::DualPosting, 1, Duration::ZERO);
+        let dispatched = client
+            .dispatch_blob(L1BatchNumber(1), vec![1])
+            .await
+            .unwrap();
+        assert_eq!(
+            dispatched,
+            DispatchedBlob {
+                blob_id: "primary-1".to_owned(),
+                layer: DataAvailabilityLayer::Primary,
+                secondary_blob_id: Some("secondary-1".to_owned()),
+                fee: Some(MOCK_FEE),
+            }
+        );
+
+        // Failures of the secondary layer should not prevent dispatching.
+        secondary.set_available(false);
+        let dispatched = client
+            .dispatch_blob(L1BatchNumber(2), vec![2])
+            .await
+            .unwrap();
+        assert_eq!(
+            dispatched,
+            blob("primary-2", DataAvailabilityLayer::Primary)
+        );
+
+        // ...while failures of the primary layer should.
+        secondary.set_available(true);
+        primary.set_available(false);
+        client
+            .dispatch_blob(L1BatchNumber(3), vec![3])
+            .await
+            .unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn getting_inclusion_data() {
+        let (client, ..) = create_client(SecondaryDALayerPolicy::Fallback, 1, Duration::ZERO);
+        let mut blob = DataAvailabilityBlob {
+            l1_batch_number: L1BatchNumber(1),
+            blob_id: "primary-1".to_owned(),
+            layer: DataAvailabilityLayer::Primary,
+            inclusion_data: None,
+            sent_at: Utc::now(),
+        };
+        let inclusion_data = client.get_inclusion_data(&blob).await.unwrap().unwrap();
+        assert_eq!(inclusion_data.data, b"primary-1");
+
+        blob.layer = DataAvailabilityLayer::Secondary;
+        let err = client.get_inclusion_data(&blob).await.unwrap_err();
+        assert!(err.to_string().contains("dummy inclusion data"), "{err}");
+    }
+}
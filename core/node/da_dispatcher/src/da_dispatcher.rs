@@ -4,8 +4,11 @@ use anyhow::Context;
 use chrono::Utc;
 use rand::Rng;
 use tokio::sync::watch::Receiver;
-use zksync_config::{ContractsConfig, DADispatcherConfig};
+use zksync_config::{
+    configs::da_dispatcher::SecondaryDALayerPolicy, ContractsConfig, DADispatcherConfig,
+};
 use zksync_da_client::{
+    packing::{BatchFrame, BlobCompression},
     types::{DAError, InclusionData},
     DataAvailabilityClient,
 };
@@ -14,20 +17,27 @@ use zksync_eth_client::{
     clients::{DynClient, L1},
     EthInterface,
 };
+use zksync_external_price_api::PriceAPIClient;
 use zksync_types::{
-    ethabi, l2_to_l1_log::L2ToL1Log, utils::client_type_to_pubdata_type, web3::CallRequest,
-    Address, L1BatchNumber, H256,
+    ethabi, l2_to_l1_log::L2ToL1Log, pubdata_da::DataAvailabilityLayer, web3::CallRequest, Address,
+    L1BatchNumber, H256, U256,
 };
 
-use crate::metrics::METRICS;
+use crate::{
+    composite_client::{CompositeDAClient, DispatchedBlob},
+    fees::{split_fee, FeeConverter},
+    metrics::METRICS,
+    packing::{pack_batches, PackedBlob},
+};
 
 #[derive(Debug, Clone)]
 pub struct DataAvailabilityDispatcher {
-    client: Box<dyn DataAvailabilityClient>,
+    client: CompositeDAClient,
     pool: ConnectionPool<Core>,
     config: DADispatcherConfig,
     contracts_config: ContractsConfig,
     settlement_layer_client: Box<DynClient<L1>>,
+    fee_converter: Option<FeeConverter>,
 
     transitional_l2_da_validator_address: Option<Address>, // set only if inclusion_verification_transition_enabled is true
 }
@@ -42,15 +52,30 @@ impl DataAvailabilityDispatcher {
     ) -> Self {
         Self {
             pool,
+            client: CompositeDAClient::new(client, &config),
             config,
-            client,
             contracts_config,
             settlement_layer_client,
+            fee_converter: None,
 
             transitional_l2_da_validator_address: None,
         }
     }
 
+    /// Configures the price API client used to convert DA fees to the base token. Has no effect unless
+    /// the DA layer token is configured.
+    pub fn with_price_api_client(mut self, client: Arc<dyn PriceAPIClient>) -> Self {
+        self.fee_converter = FeeConverter::new(client, &self.config);
+        self
+    }
+
+    /// Configures the client for the secondary DA layer, which is used according to the configured
+    /// [`SecondaryDALayerPolicy`].
+    pub fn with_secondary_client(mut self, client: Box<dyn DataAvailabilityClient>) -> Self {
+        self.client.set_secondary(client);
+        self
+    }
+
     pub async fn run(mut self, mut stop_receiver: Receiver<bool>) -> anyhow::Result<()> {
         self.check_for_misconfiguration().await?;
         let self_arc = Arc::new(self.clone());
@@ -121,57 +146,145 @@ impl DataAvailabilityDispatcher {
             .await?;
         drop(conn);
 
-        for batch in &batches {
+        let blobs = if self.config.packing_enabled() {
+            let frames: Vec<_> = batches
+                .iter()
+                .map(|batch| BatchFrame {
+                    l1_batch_number: batch.l1_batch_number.0,
+                    pubdata: batch.pubdata.clone(),
+                })
+                .collect();
+            let compression = if self.config.compress_blobs() {
+                BlobCompression::Zlib
+            } else {
+                BlobCompression::None
+            };
+            pack_batches(
+                &frames,
+                self.config.max_batches_per_blob() as usize,
+                compression,
+                self.client.blob_size_limit(),
+            )
+            .context("failed to pack L1 batches into blobs")?
+        } else {
+            batches
+                .iter()
+                .map(|batch| PackedBlob {
+                    batch_count: 1,
+                    data: batch.pubdata.clone(),
+                })
+                .collect()
+        };
+
+        let mut blob_batches = batches.as_slice();
+        for blob in blobs {
+            let (batches_in_blob, rest) = blob_batches.split_at(blob.batch_count);
+            blob_batches = rest;
+            let first_batch_number = batches_in_blob[0].l1_batch_number;
+            let last_batch_number = batches_in_blob[batches_in_blob.len() - 1].l1_batch_number;
+            let pubdata_len: usize = batches_in_blob
+                .iter()
+                .map(|batch| batch.pubdata.len())
+                .sum();
+            let blob_len = blob.data.len();
+
             let dispatch_latency = METRICS.blob_dispatch_latency.start();
-            let dispatch_response = retry(self.config.max_retries(), batch.l1_batch_number, || {
-                self.client
-                    .dispatch_blob(batch.l1_batch_number.0, batch.pubdata.clone())
-            })
-            .await
-            .with_context(|| {
-                format!(
-                    "failed to dispatch a blob with batch_number: {}, pubdata_len: {}",
-                    batch.l1_batch_number,
-                    batch.pubdata.len()
-                )
-            })?;
+            let dispatched_blob = self
+                .client
+                .dispatch_blob(first_batch_number, blob.data)
+                .await
+                .with_context(|| {
+                    format!(
+                        "failed to dispatch a blob with batch_numbers: {first_batch_number}..={last_batch_number}, \
+                         pubdata_len: {pubdata_len}, blob_len: {blob_len}"
+                    )
+                })?;
             let dispatch_latency_duration = dispatch_latency.observe();
 
             let sent_at = Utc::now();
+            let fee_in_base_token = self.fee_in_base_token(&dispatched_blob).await;
+            let pubdata_sizes: Vec<_> = batches_in_blob
+                .iter()
+                .map(|batch| batch.pubdata.len())
+                .collect();
+            let batch_fees = dispatched_blob
+                .fee
+                .map(|fee| split_fee(U256::from(fee), &pubdata_sizes));
+            let batch_fees_in_base_token =
+                fee_in_base_token.map(|fee| split_fee(fee, &pubdata_sizes));
 
             let mut conn = self.pool.connection_tagged("da_dispatcher").await?;
-            conn.data_availability_dal()
-                .insert_l1_batch_da(
-                    batch.l1_batch_number,
-                    dispatch_response.blob_id.as_str(),
-                    sent_at.naive_utc(),
-                    client_type_to_pubdata_type(self.client.client_type()),
-                    None,
-                    Some(find_l2_da_validator_address(batch.system_logs.as_slice())?),
-                )
-                .await?;
+            let mut transaction = conn.start_transaction().await?;
+            for (i, batch) in batches_in_blob.iter().enumerate() {
+                transaction
+                    .data_availability_dal()
+                    .insert_l1_batch_da(
+                        batch.l1_batch_number,
+                        dispatched_blob.blob_id.as_str(),
+                        sent_at.naive_utc(),
+                        self.client.pubdata_type(),
+                        None,
+                        Some(find_l2_da_validator_address(batch.system_logs.as_slice())?),
+                    )
+                    .await?;
+                transaction
+                    .data_availability_dal()
+                    .save_l1_batch_da_layer(
+                        batch.l1_batch_number,
+                        dispatched_blob.layer,
+                        dispatched_blob.secondary_blob_id.as_deref(),
+                    )
+                    .await?;
+                if let Some(batch_fees) = &batch_fees {
+                    transaction
+                        .data_availability_dal()
+                        .save_l1_batch_da_fee(
+                            batch.l1_batch_number,
+                            batch_fees[i],
+                            batch_fees_in_base_token.as_ref().map(|fees| fees[i]),
+                        )
+                        .await?;
+                }
+            }
+            transaction.commit().await?;
             drop(conn);
 
             METRICS
                 .last_dispatched_l1_batch
-                .set(batch.l1_batch_number.0 as usize);
-            METRICS.blob_size.observe(batch.pubdata.len());
-            METRICS.sealed_to_dispatched_lag.observe(
-                sent_at
-                    .signed_duration_since(batch.sealed_at)
-                    .to_std()
-                    .context("sent_at has to be higher than sealed_at")?,
-            );
+                .set(last_batch_number.0 as usize);
+            METRICS.blob_size.observe(blob_len);
+            METRICS.batches_per_blob.observe(batches_in_blob.len());
+            if let Some(fee) = fee_in_base_token {
+                METRICS
+                    .blob_fees_in_base_token
+                    .inc_by(u64::try_from(fee).unwrap_or(u64::MAX));
+                if pubdata_len > 0 {
+                    let fee_per_byte = fee / U256::from(pubdata_len);
+                    METRICS
+                        .fee_per_pubdata_byte_in_base_token
+                        .set(u64::try_from(fee_per_byte).unwrap_or(u64::MAX));
+                }
+            }
+            for batch in batches_in_blob {
+                METRICS.sealed_to_dispatched_lag.observe(
+                    sent_at
+                        .signed_duration_since(batch.sealed_at)
+                        .to_std()
+                        .context("sent_at has to be higher than sealed_at")?,
+                );
+            }
             tracing::info!(
-                "Dispatched a DA for batch_number: {}, pubdata_size: {}, dispatch_latency: {dispatch_latency_duration:?}",
-                batch.l1_batch_number,
-                batch.pubdata.len(),
+                "Dispatched a DA blob for batch_numbers: {first_batch_number}..={last_batch_number}, pubdata_size: {pubdata_len}, \
+                 blob_size: {blob_len}, dispatch_latency: {dispatch_latency_duration:?}, layer: {:?}, fee: {:?}, \
+                 fee_in_base_token: {fee_in_base_token:?}",
+                dispatched_blob.layer,
+                dispatched_blob.fee,
             );
         }
 
         // We don't need to report this metric every iteration, only once when the balance is changed
         if !batches.is_empty() {
-            let client_arc = Arc::new(self.client.clone_boxed());
+            let client_arc = Arc::new(self.client.primary().clone_boxed());
 
             tokio::spawn(async move {
                 let balance = client_arc
@@ -193,6 +306,35 @@ impl DataAvailabilityDispatcher {
         Ok(())
     }
 
+    /// Converts the fee paid for the blob to the base token. Only fees paid to the primary layer are converted,
+    /// since the DA layer token is configured for it. Conversion errors are logged and don't fail the dispatch.
+    async fn fee_in_base_token(&self, blob: &DispatchedBlob) -> Option<U256> {
+        let fee = blob.fee?;
+        let converter = self.fee_converter.as_ref()?;
+        if blob.layer != DataAvailabilityLayer::Primary {
+            return None;
+        }
+
+        let result: anyhow::Result<_> = async {
+            let mut conn = self.pool.connection_tagged("da_dispatcher").await?;
+            // The ratio is absent if the base token is ETH.
+            let base_token_ratio = conn.base_token_dal().get_latest_ratio().await?;
+            drop(conn);
+            converter
+                .to_base_token(U256::from(fee), base_token_ratio.as_ref())
+                .await
+        }
+        .await;
+
+        match result {
+            Ok(fee) => Some(fee),
+            Err(err) => {
+                tracing::warn!("Failed converting DA fee {fee} to the base token: {err:#}");
+                None
+            }
+        }
+    }
+
     /// Polls the data availability layer for inclusion data, and saves it in the database.
     async fn poll_for_inclusion(&self) -> anyhow::Result<()> {
         if self.config.inclusion_verification_transition_enabled() {
@@ -227,7 +369,7 @@ impl DataAvailabilityDispatcher {
             Some(InclusionData { data: vec![] })
         } else {
             self.client
-                .get_inclusion_data(blob_info.blob_id.as_str())
+                .get_inclusion_data(&blob_info)
                 .await
                 .with_context(|| {
                     format!(
@@ -242,13 +384,26 @@ impl DataAvailabilityDispatcher {
         };
 
         let mut conn = self.pool.connection_tagged("da_dispatcher").await?;
-        conn.data_availability_dal()
+        let mut transaction = conn.start_transaction().await?;
+        transaction
+            .data_availability_dal()
             .save_l1_batch_inclusion_data(
                 L1BatchNumber(blob_info.l1_batch_number.0),
                 inclusion_data.data.as_slice(),
             )
             .await?;
+        // Other L1 batches packed into the same blob share its inclusion data.
+        let packed_batch_count = if self.config.packing_enabled() {
+            transaction
+                .data_availability_dal()
+                .save_inclusion_data_for_blob(&blob_info.blob_id, inclusion_data.data.as_slice())
+                .await?
+        } else {
+            0
+        };
+        transaction.commit().await?;
         drop(conn);
+        let last_included_l1_batch = blob_info.l1_batch_number.0 + packed_batch_count as u32;
 
         let inclusion_latency = Utc::now().signed_duration_since(blob_info.sent_at);
         if let Ok(latency) = inclusion_latency.to_std() {
@@ -256,10 +411,10 @@ impl DataAvailabilityDispatcher {
         }
         METRICS
             .last_included_l1_batch
-            .set(blob_info.l1_batch_number.0 as usize);
+            .set(last_included_l1_batch as usize);
 
         tracing::info!(
-            "Received an inclusion data for a batch_number: {}, inclusion_latency_seconds: {}",
+            "Received an inclusion data for batch_numbers: {}..={last_included_l1_batch}, inclusion_latency_seconds: {}",
             blob_info.l1_batch_number,
             inclusion_latency.num_seconds()
         );
@@ -281,6 +436,22 @@ impl DataAvailabilityDispatcher {
             }
         }
 
+        if self.client.secondary_layer_policy() == Some(SecondaryDALayerPolicy::Fallback)
+            && !self.config.use_dummy_inclusion_data()
+        {
+            anyhow::bail!(
+                "Falling back to the secondary DA layer requires dummy inclusion data, since the L1 DA validator \
+                 can only verify inclusion in the primary layer"
+            );
+        }
+
+        if self.config.packing_enabled() && !self.config.use_dummy_inclusion_data() {
+            anyhow::bail!(
+                "Packing or compressing blobs requires dummy inclusion data, since the L1 DA validator \
+                 verifies inclusion of the raw pubdata of a single L1 batch"
+            );
+        }
+
         if self.config.inclusion_verification_transition_enabled() {
             self.transitional_l2_da_validator_address = Some(
                 self.contracts_config
@@ -320,7 +491,7 @@ impl DataAvailabilityDispatcher {
     }
 }
 
-async fn retry<T, Fut, F>(
+pub(crate) async fn retry<T, Fut, F>(
     max_retries: u16,
     batch_number: L1BatchNumber,
     mut f: F,
@@ -372,3 +543,306 @@ pub fn find_l2_da_validator_address(system_logs: &[L2ToL1Log]) -> anyhow::Result
         .value
         .into())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{net::SocketAddr, sync::Mutex};
+
+    use async_trait::async_trait;
+    use tokio::sync::watch;
+    use zksync_config::configs::da_client::avail::{
+        AvailClientConfig, AvailConfig, AvailGasRelayConfig, AvailSecrets,
+    };
+    use zksync_da_client::{
+        packing::unpack_blob,
+        types::{ClientType, DispatchResponse},
+    };
+    use zksync_da_clients::avail::AvailClient;
+    use zksync_da_mock_server::{MockDAServer, MockDAServerConfig};
+    use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
+    use zksync_node_test_utils::create_l1_batch;
+    use zksync_types::l2_to_l1_log::SystemL2ToL1Log;
+    use zksync_web3_decl::client::{MockClient, L1};
+
+    use super::*;
+
+    async fn spawn_mock_server(config: MockDAServerConfig) -> (SocketAddr, watch::Sender<bool>) {
+        let server = MockDAServer::bind(SocketAddr::from(([127, 0, 0, 1], 0)), config)
+            .await
+            .unwrap();
+        let address = server.local_addr().unwrap();
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        tokio::spawn(server.run(stop_receiver));
+        (address, stop_sender)
+    }
+
+    async fn avail_client(server_address: SocketAddr) -> AvailClient {
+        let url = format!("http://{server_address}");
+        let client_config = AvailConfig {
+            bridge_api_url: url.clone(),
+            timeout_ms: 5_000,
+            config: AvailClientConfig::GasRelay(AvailGasRelayConfig {
+                gas_relay_api_url: url,
+                max_retries: 1,
+                inclusion_delay_ms: Some(0),
+            }),
+        };
+        let secrets = AvailSecrets {
+            seed_phrase: None,
+            gas_relay_api_key: Some("api-key".to_owned().into()),
+        };
+        AvailClient::new(client_config, secrets).await.unwrap()
+    }
+
+    /// DA client wrapper recording all dispatched blobs.
+    #[derive(Debug, Clone)]
+    struct RecordingClient {
+        inner: Box<dyn DataAvailabilityClient>,
+        dispatched_blobs: Arc<Mutex<Vec<Vec<u8>>>>,
+    }
+
+    #[async_trait]
+    impl DataAvailabilityClient for RecordingClient {
+        async fn dispatch_blob(
+            &self,
+            batch_number: u32,
+            data: Vec<u8>,
+        ) -> Result<DispatchResponse, DAError> {
+            self.dispatched_blobs.lock().unwrap().push(data.clone());
+            self.inner.dispatch_blob(batch_number, data).await
+        }
+
+        async fn get_inclusion_data(
+            &self,
+            blob_id: &str,
+        ) -> Result<Option<InclusionData>, DAError> {
+            self.inner.get_inclusion_data(blob_id).await
+        }
+
+        async fn fetch_blob(&self, blob_id: &str) -> Result<Option<Vec<u8>>, DAError> {
+            self.inner.fetch_blob(blob_id).await
+        }
+
+        fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient> {
+            Box::new(self.clone())
+        }
+
+        fn blob_size_limit(&self) -> Option<usize> {
+            self.inner.blob_size_limit()
+        }
+
+        fn client_type(&self) -> ClientType {
+            self.inner.client_type()
+        }
+
+        async fn balance(&self) -> Result<u64, DAError> {
+            self.inner.balance().await
+        }
+    }
+
+    async fn create_dispatcher(
+        pool: ConnectionPool<Core>,
+        config: DADispatcherConfig,
+        server_address: SocketAddr,
+    ) -> DataAvailabilityDispatcher {
+        let client = avail_client(server_address).await;
+        create_dispatcher_with_client(pool, config, Box::new(client))
+    }
+
+    fn create_dispatcher_with_client(
+        pool: ConnectionPool<Core>,
+        config: DADispatcherConfig,
+        client: Box<dyn DataAvailabilityClient>,
+    ) -> DataAvailabilityDispatcher {
+        let settlement_layer_client = MockClient::builder(L1::default()).build();
+        DataAvailabilityDispatcher::new(
+            pool,
+            config,
+            client,
+            ContractsConfig::for_tests(),
+            Box::new(settlement_layer_client),
+        )
+    }
+
+    async fn seal_l1_batches(pool: &ConnectionPool<Core>, count: u32) {
+        let mut conn = pool.connection().await.unwrap();
+        insert_genesis_batch(&mut conn, &GenesisParams::mock())
+            .await
+            .unwrap();
+        for number in 1..=count {
+            let mut header = create_l1_batch(number);
+            header.pubdata_input = Some(vec![number as u8; 128]);
+            header.system_logs.push(SystemL2ToL1Log(L2ToL1Log {
+                key: H256::from_low_u64_be(u64::from(
+                    zksync_system_constants::L2_DA_VALIDATOR_OUTPUT_HASH_KEY,
+                )),
+                value: H256::from(Address::repeat_byte(0x23)),
+                ..L2ToL1Log::default()
+            }));
+            conn.blocks_dal()
+                .insert_mock_l1_batch(&header)
+                .await
+                .unwrap();
+        }
+    }
+
+    async fn dispatched_blob_id(
+        pool: &ConnectionPool<Core>,
+        number: u32,
+    ) -> Option<(String, Option<Vec<u8>>)> {
+        let details = pool
+            .connection()
+            .await
+            .unwrap()
+            .data_availability_dal()
+            .get_da_details_by_batch_number(L1BatchNumber(number))
+            .await
+            .unwrap()?;
+        Some((details.blob_id, details.inclusion_data))
+    }
+
+    #[tokio::test]
+    async fn dispatch_is_retried_on_transient_errors() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        seal_l1_batches(&pool, 2).await;
+        let server_config = MockDAServerConfig {
+            fail_first_requests: 1,
+            ..MockDAServerConfig::default()
+        };
+        let (address, _stop_sender) = spawn_mock_server(server_config).await;
+        let config = DADispatcherConfig {
+            max_retries: Some(1),
+            ..DADispatcherConfig::for_tests()
+        };
+        let dispatcher = create_dispatcher(pool.clone(), config, address).await;
+
+        // The first request fails, but is retried by the dispatcher.
+        dispatcher.dispatch().await.unwrap();
+
+        let (blob_id, inclusion_data) = dispatched_blob_id(&pool, 1).await.unwrap();
+        let (other_blob_id, _) = dispatched_blob_id(&pool, 2).await.unwrap();
+        assert_ne!(blob_id, other_blob_id);
+        assert_eq!(inclusion_data, None);
+    }
+
+    #[tokio::test]
+    async fn dispatch_fails_after_exhausting_retries() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        seal_l1_batches(&pool, 1).await;
+        let server_config = MockDAServerConfig {
+            fail_first_requests: 2,
+            ..MockDAServerConfig::default()
+        };
+        let (address, _stop_sender) = spawn_mock_server(server_config).await;
+        let config = DADispatcherConfig {
+            max_retries: Some(1),
+            ..DADispatcherConfig::for_tests()
+        };
+        let dispatcher = create_dispatcher(pool.clone(), config, address).await;
+
+        dispatcher.dispatch().await.unwrap_err();
+        assert_eq!(dispatched_blob_id(&pool, 1).await, None);
+
+        // The server has recovered, so the next iteration should succeed.
+        dispatcher.dispatch().await.unwrap();
+        assert!(dispatched_blob_id(&pool, 1).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn polling_for_inclusion_data() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        seal_l1_batches(&pool, 2).await;
+        let inclusion_delay = Duration::from_millis(500);
+        let server_config = MockDAServerConfig {
+            inclusion_delay,
+            ..MockDAServerConfig::default()
+        };
+        let (address, _stop_sender) = spawn_mock_server(server_config).await;
+        let dispatcher =
+            create_dispatcher(pool.clone(), DADispatcherConfig::for_tests(), address).await;
+
+        dispatcher.dispatch().await.unwrap();
+        // Blobs aren't included yet.
+        dispatcher.poll_for_inclusion().await.unwrap();
+        let (_, inclusion_data) = dispatched_blob_id(&pool, 1).await.unwrap();
+        assert_eq!(inclusion_data, None);
+
+        tokio::time::sleep(inclusion_delay * 2).await;
+        // Each iteration polls for the first blob awaiting inclusion.
+        dispatcher.poll_for_inclusion().await.unwrap();
+        let (_, inclusion_data) = dispatched_blob_id(&pool, 1).await.unwrap();
+        assert!(!inclusion_data.unwrap().is_empty());
+        let (_, inclusion_data) = dispatched_blob_id(&pool, 2).await.unwrap();
+        assert_eq!(inclusion_data, None);
+
+        dispatcher.poll_for_inclusion().await.unwrap();
+        let (_, inclusion_data) = dispatched_blob_id(&pool, 2).await.unwrap();
+        assert!(!inclusion_data.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn packing_several_batches_into_blob() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        seal_l1_batches(&pool, 5).await;
+        let (address, _stop_sender) = spawn_mock_server(MockDAServerConfig::default()).await;
+        let client = RecordingClient {
+            inner: Box::new(avail_client(address).await),
+            dispatched_blobs: Arc::default(),
+        };
+        let config = DADispatcherConfig {
+            max_batches_per_blob: Some(3),
+            compress_blobs: Some(true),
+            use_dummy_inclusion_data: Some(true),
+            ..DADispatcherConfig::for_tests()
+        };
+        let dispatcher =
+            create_dispatcher_with_client(pool.clone(), config, Box::new(client.clone()));
+
+        dispatcher.dispatch().await.unwrap();
+
+        let dispatched_blobs = client.dispatched_blobs.lock().unwrap().clone();
+        assert_eq!(dispatched_blobs.len(), 2);
+        let packed_batches: Vec<_> = dispatched_blobs
+            .iter()
+            .map(|blob| {
+                let frames = unpack_blob(blob).unwrap();
+                for frame in &frames {
+                    assert_eq!(frame.pubdata, [frame.l1_batch_number as u8; 128]);
+                }
+                frames
+                    .iter()
+                    .map(|frame| frame.l1_batch_number)
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        assert_eq!(packed_batches, [vec![1, 2, 3], vec![4, 5]]);
+
+        let mut blob_ids = vec![];
+        for number in 1..=5 {
+            let (blob_id, inclusion_data) = dispatched_blob_id(&pool, number).await.unwrap();
+            assert_eq!(inclusion_data, None);
+            blob_ids.push(blob_id);
+        }
+        assert_eq!(blob_ids[0], blob_ids[1]);
+        assert_eq!(blob_ids[0], blob_ids[2]);
+        assert_eq!(blob_ids[3], blob_ids[4]);
+        assert_ne!(blob_ids[0], blob_ids[3]);
+
+        // Inclusion data is saved for all batches packed into a blob at once.
+        dispatcher.poll_for_inclusion().await.unwrap();
+        for number in 1..=3 {
+            let (_, inclusion_data) = dispatched_blob_id(&pool, number).await.unwrap();
+            assert_eq!(inclusion_data, Some(vec![]));
+        }
+        for number in 4..=5 {
+            let (_, inclusion_data) = dispatched_blob_id(&pool, number).await.unwrap();
+            assert_eq!(inclusion_data, None);
+        }
+
+        dispatcher.poll_for_inclusion().await.unwrap();
+        for number in 4..=5 {
+            let (_, inclusion_data) = dispatched_blob_id(&pool, number).await.unwrap();
+            assert_eq!(inclusion_data, Some(vec![]));
+        }
+    }
+}
@@ -0,0 +1,172 @@
+//! Accounting of fees paid to the DA layer.
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::time::Instant;
+use zksync_config::DADispatcherConfig;
+use zksync_external_price_api::PriceAPIClient;
+use zksync_types::{
+    base_token_ratio::{BaseTokenAPIRatio, BaseTokenRatio},
+    Address, U256,
+};
+
+/// How long the fetched price of the DA layer token is reused.
+const FEE_TOKEN_PRICE_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Converts fees paid to the primary DA layer from its native token to the base token of the chain.
+///
+/// The native token is converted to ETH using the price from the external price API, and then to the base token
+/// using the latest base token ratio persisted by the base token adjuster.
+#[derive(Debug, Clone)]
+pub(crate) struct FeeConverter {
+    price_api_client: Arc<dyn PriceAPIClient>,
+    fee_token_address: Address,
+    fee_token_decimals: u32,
+    cached_price: Arc<Mutex<Option<(BaseTokenAPIRatio, Instant)>>>,
+}
+
+impl FeeConverter {
+    /// Returns `None` if the DA layer token is not configured.
+    pub fn new(
+        price_api_client: Arc<dyn PriceAPIClient>,
+        config: &DADispatcherConfig,
+    ) -> Option<Self> {
+        Some(Self {
+            price_api_client,
+            fee_token_address: config.fee_token_address?,
+            fee_token_decimals: config.fee_token_decimals(),
+            cached_price: Arc::default(),
+        })
+    }
+
+    async fn fee_token_price(&self) -> anyhow::Result<BaseTokenAPIRatio> {
+        if let Some((price, fetched_at)) = *self.cached_price.lock().unwrap() {
+            if fetched_at.elapsed() < FEE_TOKEN_PRICE_CACHE_TTL {
+                return Ok(price);
+            }
+        }
+
+        let price = self
+            .price_api_client
+            .fetch_ratio(self.fee_token_address)
+            .await?;
+        *self.cached_price.lock().unwrap() = Some((price, Instant::now()));
+        Ok(price)
+    }
+
+    /// Converts the fee to the base token. If `base_token_ratio` is `None`, the base token is assumed to be ETH.
+    pub async fn to_base_token(
+        &self,
+        fee: U256,
+        base_token_ratio: Option<&BaseTokenRatio>,
+    ) -> anyhow::Result<U256> {
+        let fee_token_price = self.fee_token_price().await?;
+        Ok(convert_fee(
+            fee,
+            self.fee_token_decimals,
+            &fee_token_price,
+            base_token_ratio,
+        ))
+    }
+}
+
+/// Converts the fee in the smallest units of the DA layer token to the smallest units of the base token.
+/// Both `fee_token_price` and `base_token_ratio` express how many whole tokens are worth 1 ETH.
+fn convert_fee(
+    fee: U256,
+    fee_token_decimals: u32,
+    fee_token_price: &BaseTokenAPIRatio,
+    base_token_ratio: Option<&BaseTokenRatio>,
+) -> U256 {
+    let (base_numerator, base_denominator) = base_token_ratio.map_or((1, 1), |ratio| {
+        (ratio.numerator.get(), ratio.denominator.get())
+    });
+    let numerator = fee
+        * U256::exp10(18)
+        * U256::from(fee_token_price.denominator.get())
+        * U256::from(base_numerator);
+    let denominator = U256::exp10(fee_token_decimals as usize)
+        * U256::from(fee_token_price.numerator.get())
+        * U256::from(base_denominator);
+    numerator / denominator
+}
+
+/// Splits the fee for a blob between L1 batches packed into it, proportionally to the size of their pubdata.
+/// The rounding remainder is attributed to the last batch.
+pub(crate) fn split_fee(fee: U256, pubdata_sizes: &[usize]) -> Vec<U256> {
+    let total_size: usize = pubdata_sizes.iter().sum();
+    let mut shares: Vec<_> = if total_size == 0 {
+        let batch_count = U256::from(pubdata_sizes.len());
+        pubdata_sizes.iter().map(|_| fee / batch_count).collect()
+    } else {
+        pubdata_sizes
+            .iter()
+            .map(|&size| fee * U256::from(size) / U256::from(total_size))
+            .collect()
+    };
+    let distributed = shares.iter().fold(U256::zero(), |acc, share| acc + share);
+    if let Some(last) = shares.last_mut() {
+        *last += fee - distributed;
+    }
+    shares
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU64;
+
+    use chrono::Utc;
+
+    use super::*;
+
+    fn ratio(numerator: u64, denominator: u64) -> BaseTokenAPIRatio {
+        BaseTokenAPIRatio {
+            numerator: NonZeroU64::new(numerator).unwrap(),
+            denominator: NonZeroU64::new(denominator).unwrap(),
+            ratio_timestamp: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn converting_fee_to_base_token() {
+        // 1 ETH = 500 DA tokens with 6 decimals, so 1 DA token (10^6 units) = 0.002 ETH.
+        let fee_token_price = ratio(500, 1);
+        let fee = convert_fee(U256::from(1_000_000), 6, &fee_token_price, None);
+        assert_eq!(fee, U256::exp10(15) * 2);
+
+        // 1 ETH = 2,000 base tokens.
+        let api_ratio = ratio(2_000, 1);
+        let base_token_ratio = BaseTokenRatio {
+            id: 1,
+            ratio_timestamp: Utc::now(),
+            numerator: api_ratio.numerator,
+            denominator: api_ratio.denominator,
+            used_in_l1: false,
+        };
+        let fee = convert_fee(
+            U256::from(1_000_000),
+            6,
+            &fee_token_price,
+            Some(&base_token_ratio),
+        );
+        assert_eq!(fee, U256::exp10(18) * 4);
+    }
+
+    #[test]
+    fn splitting_fee_between_batches() {
+        let shares = split_fee(U256::from(100), &[100, 300]);
+        assert_eq!(shares, [U256::from(25), U256::from(75)]);
+
+        let shares = split_fee(U256::from(100), &[1, 1, 1]);
+        assert_eq!(shares, [U256::from(33), U256::from(33), U256::from(34)]);
+
+        let shares = split_fee(U256::from(10), &[0, 0]);
+        assert_eq!(shares, [U256::from(5), U256::from(5)]);
+
+        let shares = split_fee(U256::from(10), &[42]);
+        assert_eq!(shares, [U256::from(10)]);
+    }
+}
@@ -1,4 +1,7 @@
 pub use self::da_dispatcher::DataAvailabilityDispatcher;
 
+mod composite_client;
 mod da_dispatcher;
+mod fees;
 mod metrics;
+mod packing;
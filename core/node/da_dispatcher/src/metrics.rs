@@ -1,11 +1,20 @@
 use std::time::Duration;
 
-use vise::{Buckets, Gauge, Histogram, Metrics, Unit};
+use vise::{
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics, Unit,
+};
 
 /// Buckets for `blob_dispatch_latency` (from 0.1 to 120 seconds).
 const DISPATCH_LATENCIES: Buckets =
     Buckets::values(&[0.1, 0.5, 1.0, 2.0, 5.0, 10.0, 30.0, 60.0, 120.0, 240.0]);
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "layer", rename_all = "snake_case")]
+pub(super) enum DALayerLabel {
+    Primary,
+    Secondary,
+}
+
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "server_da_dispatcher")]
 pub(super) struct DataAvailabilityDispatcherMetrics {
@@ -19,6 +28,9 @@ pub(super) struct DataAvailabilityDispatcherMetrics {
     /// Buckets are bytes ranging from 1 KB to 16 MB, which has to satisfy all blob size values.
     #[metrics(buckets = Buckets::exponential(1_024.0..=16.0 * 1_024.0 * 1_024.0, 2.0), unit = Unit::Bytes)]
     pub blob_size: Histogram<usize>,
+    /// Number of L1 batches packed into a dispatched blob.
+    #[metrics(buckets = Buckets::linear(1.0..=16.0, 1.0))]
+    pub batches_per_blob: Histogram<usize>,
     /// Number of transactions resent by the DA dispatcher.
     #[metrics(buckets = Buckets::linear(0.0..=10.0, 1.0))]
     pub dispatch_call_retries: Histogram<usize>,
@@ -32,6 +44,19 @@ pub(super) struct DataAvailabilityDispatcherMetrics {
     pub sealed_to_dispatched_lag: Histogram<Duration>,
     /// The balance of the operator wallet on DA network.
     pub operator_balance: Gauge<u64>,
+    /// Number of blobs dispatched to each DA layer. Blobs dual-posted to the secondary layer are counted for both layers.
+    pub dispatched_blobs: Family<DALayerLabel, Counter>,
+    /// Total fees paid for dispatched blobs to each DA layer, in the smallest units of the layer's native token.
+    pub blob_fees: Family<DALayerLabel, Counter>,
+    /// Total fees paid for blobs in the primary DA layer, converted to the base token.
+    pub blob_fees_in_base_token: Counter,
+    /// Fee paid per byte of pubdata for the last blob in the primary DA layer, converted to the base token.
+    /// Can be compared with the pubdata price charged by the fee model.
+    pub fee_per_pubdata_byte_in_base_token: Gauge<u64>,
+    /// Set to 1 if the dispatcher has switched over to the secondary DA layer.
+    pub switched_to_secondary_layer: Gauge<u64>,
+    /// Number of failed dispatches to the secondary DA layer when dual-posting blobs.
+    pub secondary_dispatch_errors: Counter,
 }
 
 #[vise::register]
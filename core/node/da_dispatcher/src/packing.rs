@@ -0,0 +1,105 @@
+//! Grouping of L1 batches into packed DA blobs.
+
+use zksync_da_client::packing::{pack_blob, BatchFrame, BlobCompression};
+
+/// Blob holding pubdata of one or more consecutive L1 batches.
+#[derive(Debug)]
+pub(crate) struct PackedBlob {
+    /// Number of L1 batches in the blob.
+    pub batch_count: usize,
+    pub data: Vec<u8>,
+}
+
+/// Splits pubdata of L1 batches (sorted by number) into blobs. Each blob contains at most `max_batches_per_blob`
+/// consecutive batches and doesn't exceed `size_limit`, unless it holds a single batch.
+pub(crate) fn pack_batches(
+    frames: &[BatchFrame],
+    max_batches_per_blob: usize,
+    compression: BlobCompression,
+    size_limit: Option<usize>,
+) -> anyhow::Result<Vec<PackedBlob>> {
+    let mut blobs = vec![];
+    let mut start = 0;
+    while start < frames.len() {
+        let mut end = start + 1;
+        let mut data = pack_blob(&frames[start..end], compression)?;
+        while end < frames.len()
+            && end - start < max_batches_per_blob
+            && frames[end].l1_batch_number == frames[end - 1].l1_batch_number + 1
+        {
+            let candidate = pack_blob(&frames[start..=end], compression)?;
+            if size_limit.is_some_and(|limit| candidate.len() > limit) {
+                break;
+            }
+            data = candidate;
+            end += 1;
+        }
+
+        blobs.push(PackedBlob {
+            batch_count: end - start,
+            data,
+        });
+        start = end;
+    }
+    Ok(blobs)
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_da_client::packing::unpack_blob;
+
+    use super::*;
+
+    fn frames(numbers: impl IntoIterator<Item = u32>) -> Vec<BatchFrame> {
+        numbers
+            .into_iter()
+            .map(|l1_batch_number| BatchFrame {
+                l1_batch_number,
+                pubdata: vec![l1_batch_number as u8; 100],
+            })
+            .collect()
+    }
+
+    fn batch_counts(blobs: &[PackedBlob]) -> Vec<usize> {
+        blobs.iter().map(|blob| blob.batch_count).collect()
+    }
+
+    #[test]
+    fn packing_batches_up_to_limit() {
+        let frames = frames(1..=5);
+        let blobs = pack_batches(&frames, 2, BlobCompression::None, None).unwrap();
+        assert_eq!(batch_counts(&blobs), [2, 2, 1]);
+        assert_eq!(unpack_blob(&blobs[1].data).unwrap(), frames[2..4]);
+
+        let blobs = pack_batches(&frames, 10, BlobCompression::Zlib, None).unwrap();
+        assert_eq!(batch_counts(&blobs), [5]);
+        assert_eq!(unpack_blob(&blobs[0].data).unwrap(), frames);
+    }
+
+    #[test]
+    fn packing_respects_blob_size_limit() {
+        let frames = frames(1..=5);
+        let two_batches_size = pack_blob(&frames[..2], BlobCompression::None)
+            .unwrap()
+            .len();
+        let blobs = pack_batches(
+            &frames,
+            10,
+            BlobCompression::None,
+            Some(two_batches_size + 50),
+        )
+        .unwrap();
+        assert_eq!(batch_counts(&blobs), [2, 2, 1]);
+
+        // A single batch is always packed, even if it exceeds the limit.
+        let blobs = pack_batches(&frames, 10, BlobCompression::None, Some(10)).unwrap();
+        assert_eq!(batch_counts(&blobs), [1; 5]);
+    }
+
+    #[test]
+    fn non_consecutive_batches_are_not_packed_together() {
+        let frames = frames([1, 2, 4, 5]);
+        let blobs = pack_batches(&frames, 10, BlobCompression::None, None).unwrap();
+        assert_eq!(batch_counts(&blobs), [2, 2]);
+    }
+}
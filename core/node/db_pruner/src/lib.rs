@@ -8,6 +8,7 @@ use std::{
 use anyhow::Context as _;
 use serde::{Deserialize, Serialize};
 use tokio::sync::watch;
+pub use zksync_dal::pruning_dal::RetentionRules;
 use zksync_dal::{
     pruning_dal::{HardPruningInfo, PruningInfo, SoftPruningInfo},
     Connection, ConnectionPool, Core, CoreDal,
@@ -39,6 +40,8 @@ pub struct DbPrunerConfig {
     /// Minimum age of an L1 batch in order for it to be eligible for pruning. Setting this to zero
     /// will effectively disable this pruning criterion.
     pub minimum_l1_batch_age: Duration,
+    /// Rules for data that should survive hard pruning (e.g., events emitted by specific contracts).
+    pub retention_rules: RetentionRules,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -252,9 +255,10 @@ impl DbPruner {
 
         let mut dal = transaction.pruning_dal();
         let stats = tokio::select! {
-            result = dal.hard_prune_batches_range(
+            result = dal.hard_prune_batches_range_with_retention(
                 soft_pruned.l1_batch,
                 soft_pruned.l2_block,
+                &self.config.retention_rules,
             ) => result?,
 
             _ = stop_receiver.changed() => {
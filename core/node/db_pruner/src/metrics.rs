@@ -58,6 +58,8 @@ pub(super) struct DbPrunerMetrics {
     deleted_entities: Family<PrunedEntityType, Histogram<u64>>,
     /// Number of times a certain condition has resulted in a specific outcome (succeeded, failed, or errored).
     condition_outcomes: Family<ConditionOutcomeLabels, Counter>,
+    /// Total number of hard-pruned L2 blocks with data retained according to retention rules.
+    retained_l2_blocks: Counter,
 }
 
 impl DbPrunerMetrics {
@@ -69,11 +71,13 @@ impl DbPrunerMetrics {
             deleted_events,
             deleted_call_traces,
             deleted_l2_to_l1_logs,
+            retained_l2_blocks,
         } = stats;
         tracing::info!(
             "Performed pruning of database, deleted {deleted_l1_batches} L1 batches, {deleted_l2_blocks} L2 blocks, \
              {deleted_storage_logs} storage logs, \
-             {deleted_events} events, {deleted_call_traces} call traces, {deleted_l2_to_l1_logs} L2-to-L1 logs"
+             {deleted_events} events, {deleted_call_traces} call traces, {deleted_l2_to_l1_logs} L2-to-L1 logs; \
+             retained data in {retained_l2_blocks} L2 blocks"
         );
 
         self.deleted_entities[&PrunedEntityType::L1Batch].observe(deleted_l1_batches);
@@ -82,6 +86,7 @@ impl DbPrunerMetrics {
         self.deleted_entities[&PrunedEntityType::Event].observe(deleted_events);
         self.deleted_entities[&PrunedEntityType::L2ToL1Log].observe(deleted_l2_to_l1_logs);
         self.deleted_entities[&PrunedEntityType::CallTrace].observe(deleted_call_traces);
+        self.retained_l2_blocks.inc_by(retained_l2_blocks);
     }
 
     pub fn observe_condition(&self, condition: &dyn PruneCondition, outcome: ConditionOutcome) {
@@ -20,6 +20,14 @@ pub(crate) enum ProcessorError {
     Internal,
     #[error("Proof verification not possible anymore, batch is too old")]
     ProofIsGone,
+    #[error("Proof marketplace is not enabled")]
+    MarketplaceDisabled,
+    #[error("Missing or invalid API key")]
+    Unauthorized,
+    #[error("Batch {0} is not leased to this prover, or the lease has expired")]
+    LeaseNotHeld(L1BatchNumber),
+    #[error("Proof rejected: {0}")]
+    ProofRejected(String),
 }
 
 impl ProcessorError {
@@ -31,6 +39,10 @@ impl ProcessorError {
             Self::InvalidFile(_) => StatusCode::BAD_REQUEST,
             Self::BatchNotReady(_) => StatusCode::NOT_FOUND,
             Self::ProofIsGone => StatusCode::GONE,
+            Self::MarketplaceDisabled => StatusCode::NOT_FOUND,
+            Self::Unauthorized => StatusCode::UNAUTHORIZED,
+            Self::LeaseNotHeld(_) => StatusCode::CONFLICT,
+            Self::ProofRejected(_) => StatusCode::UNPROCESSABLE_ENTITY,
         }
     }
 }
@@ -1,25 +1,29 @@
 mod error;
+mod marketplace;
 mod metrics;
 mod middleware;
 mod processor;
 mod types;
+mod verifier;
 
 use std::net::SocketAddr;
 
 use anyhow::Context;
 use axum::{
     extract::{Path, Request, State},
+    http::StatusCode,
     middleware::Next,
+    response::{IntoResponse, Response},
     routing::{get, post},
-    Router,
+    Json, Router,
 };
 use error::ProcessorError;
 use tokio::sync::watch;
-use types::{ExternalProof, ProofGenerationDataResponse};
+use types::{ExternalProof, LeaseResponse, ProofGenerationDataResponse, ProverStatsResponse};
 use zksync_basic_types::L1BatchNumber;
 
-pub use crate::processor::Processor;
-use crate::{metrics::Method, middleware::MetricsMiddleware};
+use crate::{marketplace::AuthenticatedProver, metrics::Method, middleware::MetricsMiddleware};
+pub use crate::{marketplace::Marketplace, processor::Processor, verifier::ProofVerifier};
 
 /// External API implementation.
 #[derive(Debug)]
@@ -54,6 +58,31 @@ impl Api {
                 "/verify_proof/:l1_batch_number",
                 post(Api::verify_proof).layer(middleware_factory(Method::VerifyProof)),
             )
+            .route(
+                "/marketplace/claim",
+                post(Api::claim_batch).layer(middleware_factory(Method::ClaimBatch)),
+            )
+            .route(
+                "/marketplace/lease/:l1_batch_number",
+                post(Api::renew_lease).layer(middleware_factory(Method::RenewLease)),
+            )
+            .route(
+                "/marketplace/proof_generation_data/:l1_batch_number",
+                get(Api::leased_generation_data)
+                    .layer(middleware_factory(Method::GetLeasedProofGenerationData)),
+            )
+            .route(
+                "/marketplace/submit/:l1_batch_number",
+                post(Api::submit_proof).layer(middleware_factory(Method::SubmitProof)),
+            )
+            .route(
+                "/marketplace/release/:l1_batch_number",
+                post(Api::release_batch).layer(middleware_factory(Method::ReleaseBatch)),
+            )
+            .route(
+                "/marketplace/stats",
+                get(Api::prover_stats).layer(middleware_factory(Method::GetProverStats)),
+            )
             .with_state(processor);
 
         Self { router, port }
@@ -105,4 +134,63 @@ impl Api {
             .verify_proof(L1BatchNumber(l1_batch_number), proof)
             .await
     }
+
+    async fn claim_batch(
+        State(processor): State<Processor>,
+        AuthenticatedProver(prover): AuthenticatedProver,
+    ) -> Result<Response, ProcessorError> {
+        Ok(match processor.claim_batch(&prover).await? {
+            Some(lease) => Json(lease).into_response(),
+            None => StatusCode::NO_CONTENT.into_response(),
+        })
+    }
+
+    async fn renew_lease(
+        State(processor): State<Processor>,
+        AuthenticatedProver(prover): AuthenticatedProver,
+        Path(l1_batch_number): Path<u32>,
+    ) -> Result<Json<LeaseResponse>, ProcessorError> {
+        processor
+            .renew_lease(&prover, L1BatchNumber(l1_batch_number))
+            .await
+            .map(Json)
+    }
+
+    async fn leased_generation_data(
+        State(processor): State<Processor>,
+        AuthenticatedProver(prover): AuthenticatedProver,
+        Path(l1_batch_number): Path<u32>,
+    ) -> Result<ProofGenerationDataResponse, ProcessorError> {
+        processor
+            .leased_proof_generation_data(&prover, L1BatchNumber(l1_batch_number))
+            .await
+    }
+
+    async fn submit_proof(
+        State(processor): State<Processor>,
+        AuthenticatedProver(prover): AuthenticatedProver,
+        Path(l1_batch_number): Path<u32>,
+        proof: ExternalProof,
+    ) -> Result<(), ProcessorError> {
+        processor
+            .submit_proof(&prover, L1BatchNumber(l1_batch_number), proof)
+            .await
+    }
+
+    async fn release_batch(
+        State(processor): State<Processor>,
+        AuthenticatedProver(prover): AuthenticatedProver,
+        Path(l1_batch_number): Path<u32>,
+    ) -> Result<(), ProcessorError> {
+        processor
+            .release_batch(&prover, L1BatchNumber(l1_batch_number))
+            .await
+    }
+
+    async fn prover_stats(
+        State(processor): State<Processor>,
+        AuthenticatedProver(prover): AuthenticatedProver,
+    ) -> Result<Json<ProverStatsResponse>, ProcessorError> {
+        processor.prover_stats(&prover).await.map(Json)
+    }
 }
@@ -0,0 +1,358 @@
+//! Proof marketplace: registered external provers claim batches, hold leases for them and submit proofs,
+//! which are verified and stored as the batch proofs.
+
+use std::time::Duration;
+
+use axum::{
+    extract::FromRequestParts,
+    http::{header, request::Parts},
+};
+use zksync_dal::{external_prover_dal::ExternalProver, ConnectionPool, Core, CoreDal};
+use zksync_prover_interface::outputs::L1BatchProofForL1;
+use zksync_types::{
+    commitment::serialize_commitments, web3::keccak256, L1BatchNumber, ProtocolVersionId, H256,
+    STATE_DIFF_HASH_KEY_PRE_GATEWAY,
+};
+
+use crate::{
+    error::ProcessorError,
+    types::{ExternalProof, LeaseResponse, ProofGenerationDataResponse, ProverStatsResponse},
+    verifier::{batch_proof_public_input, ProofVerifier},
+    Processor,
+};
+
+/// Proof marketplace state.
+#[derive(Debug, Clone)]
+pub struct Marketplace {
+    /// Master pool: unlike other endpoints of the API, the marketplace modifies the database.
+    pool: ConnectionPool<Core>,
+    verifier: ProofVerifier,
+    lease_timeout: Duration,
+}
+
+impl Marketplace {
+    pub fn new(
+        pool: ConnectionPool<Core>,
+        verifier: ProofVerifier,
+        lease_timeout: Duration,
+    ) -> Self {
+        Self {
+            pool,
+            verifier,
+            lease_timeout,
+        }
+    }
+}
+
+/// External prover authenticated with the `Authorization: Bearer <API key>` header.
+#[derive(Debug)]
+pub(crate) struct AuthenticatedProver(pub ExternalProver);
+
+#[async_trait::async_trait]
+impl FromRequestParts<Processor> for AuthenticatedProver {
+    type Rejection = ProcessorError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        processor: &Processor,
+    ) -> Result<Self, Self::Rejection> {
+        let marketplace = processor.marketplace()?;
+        let api_key = parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or(ProcessorError::Unauthorized)?;
+        // Only hashes of API keys are stored.
+        let api_key_hash = H256(keccak256(api_key.trim().as_bytes()));
+
+        let prover = marketplace
+            .pool
+            .connection()
+            .await?
+            .external_prover_dal()
+            .get_prover_by_api_key_hash(api_key_hash)
+            .await?
+            .filter(|prover| prover.is_active)
+            .ok_or(ProcessorError::Unauthorized)?;
+        Ok(Self(prover))
+    }
+}
+
+impl Processor {
+    fn marketplace(&self) -> Result<&Marketplace, ProcessorError> {
+        self.marketplace
+            .as_ref()
+            .ok_or(ProcessorError::MarketplaceDisabled)
+    }
+
+    pub(crate) async fn claim_batch(
+        &self,
+        prover: &ExternalProver,
+    ) -> Result<Option<LeaseResponse>, ProcessorError> {
+        let marketplace = self.marketplace()?;
+        let lease = marketplace
+            .pool
+            .connection()
+            .await?
+            .external_prover_dal()
+            .claim_batch(prover.id, marketplace.lease_timeout)
+            .await?;
+        if let Some(lease) = &lease {
+            tracing::info!(
+                "Batch {} is leased to external prover `{}` until {}",
+                lease.l1_batch_number,
+                prover.name,
+                lease.lease_expires_at
+            );
+        }
+        Ok(lease.map(LeaseResponse::from))
+    }
+
+    pub(crate) async fn renew_lease(
+        &self,
+        prover: &ExternalProver,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<LeaseResponse, ProcessorError> {
+        let marketplace = self.marketplace()?;
+        let lease = marketplace
+            .pool
+            .connection()
+            .await?
+            .external_prover_dal()
+            .renew_lease(l1_batch_number, prover.id, marketplace.lease_timeout)
+            .await?
+            .ok_or(ProcessorError::LeaseNotHeld(l1_batch_number))?;
+        Ok(lease.into())
+    }
+
+    pub(crate) async fn leased_proof_generation_data(
+        &self,
+        prover: &ExternalProver,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<ProofGenerationDataResponse, ProcessorError> {
+        self.ensure_lease_held(prover, l1_batch_number).await?;
+        self.proof_generation_data_for_existing_batch_internal(l1_batch_number)
+            .await
+            .map(ProofGenerationDataResponse)
+    }
+
+    pub(crate) async fn release_batch(
+        &self,
+        prover: &ExternalProver,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<(), ProcessorError> {
+        let released = self
+            .marketplace()?
+            .pool
+            .connection()
+            .await?
+            .external_prover_dal()
+            .release_batch(l1_batch_number, prover.id)
+            .await?;
+        if !released {
+            return Err(ProcessorError::LeaseNotHeld(l1_batch_number));
+        }
+        tracing::info!(
+            "External prover `{}` released batch {l1_batch_number}",
+            prover.name
+        );
+        Ok(())
+    }
+
+    pub(crate) async fn prover_stats(
+        &self,
+        prover: &ExternalProver,
+    ) -> Result<ProverStatsResponse, ProcessorError> {
+        let stats = self
+            .marketplace()?
+            .pool
+            .connection()
+            .await?
+            .external_prover_dal()
+            .get_prover_stats(prover.id)
+            .await?
+            .ok_or(ProcessorError::Unauthorized)?;
+        Ok(stats.into())
+    }
+
+    /// Verifies the proof submitted for a leased batch and saves it as the batch proof. Rejected proofs
+    /// are counted in the prover statistics; the lease isn't affected, so the prover can resubmit the proof
+    /// while it holds the lease.
+    pub(crate) async fn submit_proof(
+        &self,
+        prover: &ExternalProver,
+        l1_batch_number: L1BatchNumber,
+        proof: ExternalProof,
+    ) -> Result<(), ProcessorError> {
+        let marketplace = self.marketplace()?;
+        self.ensure_lease_held(prover, l1_batch_number).await?;
+
+        let proof = proof.into_proof();
+        if let Err(reason) = self
+            .check_proof(marketplace, l1_batch_number, proof.clone())
+            .await?
+        {
+            tracing::info!(
+                "Rejected proof for batch {l1_batch_number} submitted by external prover `{}`: {reason}",
+                prover.name
+            );
+            marketplace
+                .pool
+                .connection()
+                .await?
+                .external_prover_dal()
+                .mark_proof_rejected(prover.id)
+                .await?;
+            return Err(ProcessorError::ProofRejected(reason));
+        }
+
+        let blob_url = self
+            .blob_store
+            .put((l1_batch_number, proof.protocol_version()), &proof)
+            .await?;
+
+        let mut conn = marketplace.pool.connection().await?;
+        let mut transaction = conn.start_transaction().await?;
+        let accepted = transaction
+            .external_prover_dal()
+            .mark_proof_accepted(l1_batch_number, prover.id)
+            .await?;
+        if !accepted {
+            // The lease has expired in the meantime (and the batch may be claimed by another prover).
+            return Err(ProcessorError::LeaseNotHeld(l1_batch_number));
+        }
+        transaction
+            .proof_generation_dal()
+            .save_proof_artifacts_metadata(l1_batch_number, &blob_url)
+            .await?;
+        transaction.commit().await?;
+
+        tracing::info!(
+            "Accepted proof for batch {l1_batch_number} submitted by external prover `{}`",
+            prover.name
+        );
+        Ok(())
+    }
+
+    async fn ensure_lease_held(
+        &self,
+        prover: &ExternalProver,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<(), ProcessorError> {
+        self.marketplace()?
+            .pool
+            .connection()
+            .await?
+            .external_prover_dal()
+            .get_active_lease(l1_batch_number, prover.id)
+            .await?
+            .ok_or(ProcessorError::LeaseNotHeld(l1_batch_number))?;
+        Ok(())
+    }
+
+    /// Checks that the proof is produced for the expected protocol version, that its public inputs
+    /// correspond to the batch and that it's valid for the batch verification key. Returns the rejection
+    /// reason if any of the checks fails.
+    ///
+    /// Aggregation result coordinates are supplied by the prover and aren't bound by the proof, so the batch
+    /// is identified by the SNARK public input, which is computed from commitments of the batch and
+    /// the previous batch the same way as in the L1 executor contract.
+    async fn check_proof(
+        &self,
+        marketplace: &Marketplace,
+        l1_batch_number: L1BatchNumber,
+        proof: L1BatchProofForL1,
+    ) -> Result<Result<(), String>, ProcessorError> {
+        let mut conn = marketplace.pool.connection().await?;
+        let l1_batch = conn
+            .blocks_dal()
+            .get_l1_batch_metadata(l1_batch_number)
+            .await?
+            .ok_or(ProcessorError::BatchNotReady(l1_batch_number))?;
+        let prev_l1_batch_number = l1_batch_number - 1;
+        let prev_l1_batch = conn
+            .blocks_dal()
+            .get_l1_batch_metadata(prev_l1_batch_number)
+            .await?
+            .ok_or_else(|| {
+                tracing::error!("Missing metadata for batch {prev_l1_batch_number}");
+                ProcessorError::Internal
+            })?;
+        let minor_version = l1_batch
+            .header
+            .protocol_version
+            .unwrap_or_else(ProtocolVersionId::last_potentially_undefined);
+        let protocol_version = conn
+            .protocol_versions_dal()
+            .get_protocol_version_with_latest_patch(minor_version)
+            .await?
+            .ok_or_else(|| {
+                tracing::error!("Missing L1 verifier info for protocol version {minor_version}");
+                ProcessorError::Internal
+            })?;
+        drop(conn);
+
+        if proof.protocol_version() != protocol_version.version {
+            return Ok(Err(format!(
+                "proof is generated for protocol version {}, expected {}",
+                proof.protocol_version(),
+                protocol_version.version
+            )));
+        }
+
+        let aggregation_coords = proof.aggregation_result_coords();
+        let system_logs_hash = H256(keccak256(&serialize_commitments(
+            &l1_batch.header.system_logs,
+        )));
+        let state_diff_hash = if minor_version.is_pre_gateway() {
+            l1_batch.header.system_logs.iter().find_map(|log| {
+                (log.0.key == H256::from_low_u64_be(STATE_DIFF_HASH_KEY_PRE_GATEWAY as u64))
+                    .then_some(log.0.value)
+            })
+        } else {
+            l1_batch.metadata.state_diff_hash
+        };
+        let expected_coords = [
+            ("system_logs_hash", Some(system_logs_hash)),
+            ("state_diff_hash", state_diff_hash),
+            (
+                "bootloader_heap_initial_content",
+                l1_batch.metadata.bootloader_initial_content_commitment,
+            ),
+            (
+                "events_queue_state",
+                l1_batch.metadata.events_queue_commitment,
+            ),
+        ];
+        for ((name, expected), actual) in expected_coords.into_iter().zip(aggregation_coords) {
+            let Some(expected) = expected else {
+                tracing::error!("Missing {name} for batch {l1_batch_number}");
+                return Err(ProcessorError::Internal);
+            };
+            let actual = H256(actual);
+            if expected != actual {
+                return Ok(Err(format!(
+                    "aggregation result {name} doesn't match: expected {expected:?}, got {actual:?}"
+                )));
+            }
+        }
+
+        let expected_public_input = batch_proof_public_input(
+            prev_l1_batch.metadata.commitment,
+            l1_batch.metadata.commitment,
+        );
+        marketplace
+            .verifier
+            .verify(
+                proof,
+                protocol_version.l1_verifier_config,
+                expected_public_input,
+            )
+            .await
+            .map_err(|err| {
+                tracing::error!("Failed verifying proof for batch {l1_batch_number}: {err:#}");
+                ProcessorError::Internal
+            })
+    }
+}
@@ -8,6 +8,12 @@ pub(crate) enum Method {
     GetLatestProofGenerationData,
     GetSpecificProofGenerationData,
     VerifyProof,
+    ClaimBatch,
+    RenewLease,
+    GetLeasedProofGenerationData,
+    SubmitProof,
+    ReleaseBatch,
+    GetProverStats,
 }
 
 #[derive(Debug, Metrics)]
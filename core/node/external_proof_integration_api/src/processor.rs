@@ -15,15 +15,17 @@ use zksync_prover_interface::{
 
 use crate::{
     error::ProcessorError,
+    marketplace::Marketplace,
     types::{ExternalProof, ProofGenerationDataResponse},
 };
 
 /// Backend-agnostic implementation of the API logic.
 #[derive(Clone)]
 pub struct Processor {
-    blob_store: Arc<dyn ObjectStore>,
+    pub(crate) blob_store: Arc<dyn ObjectStore>,
     pool: ConnectionPool<Core>,
     commitment_mode: L1BatchCommitmentMode,
+    pub(crate) marketplace: Option<Marketplace>,
 }
 
 impl Processor {
@@ -36,9 +38,16 @@ impl Processor {
             blob_store,
             pool,
             commitment_mode,
+            marketplace: None,
         }
     }
 
+    /// Enables the proof marketplace endpoints.
+    pub fn with_marketplace(mut self, marketplace: Marketplace) -> Self {
+        self.marketplace = Some(marketplace);
+        self
+    }
+
     pub(crate) async fn verify_proof(
         &self,
         l1_batch_number: L1BatchNumber,
@@ -98,7 +107,7 @@ impl Processor {
             .await?)
     }
 
-    async fn proof_generation_data_for_existing_batch_internal(
+    pub(crate) async fn proof_generation_data_for_existing_batch_internal(
         &self,
         l1_batch_number: L1BatchNumber,
     ) -> Result<ProofGenerationData, ProcessorError> {
@@ -3,7 +3,10 @@ use axum::{
     http::header,
     response::{IntoResponse, Response},
 };
-use zksync_basic_types::protocol_version::ProtocolSemanticVersion;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use zksync_basic_types::{protocol_version::ProtocolSemanticVersion, L1BatchNumber};
+use zksync_dal::external_prover_dal::{ExternalProofLease, ExternalProverStats};
 use zksync_prover_interface::{api::ProofGenerationData, outputs::L1BatchProofForL1};
 
 use crate::error::{FileError, ProcessorError};
@@ -35,9 +38,53 @@ impl IntoResponse for ProofGenerationDataResponse {
     }
 }
 
+/// Batch leased to an external prover.
+#[derive(Debug, Serialize)]
+pub(crate) struct LeaseResponse {
+    pub l1_batch_number: L1BatchNumber,
+    pub lease_expires_at: DateTime<Utc>,
+}
+
+impl From<ExternalProofLease> for LeaseResponse {
+    fn from(lease: ExternalProofLease) -> Self {
+        Self {
+            l1_batch_number: lease.l1_batch_number,
+            lease_expires_at: lease.lease_expires_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ProverStatsResponse {
+    pub name: String,
+    pub claimed_batches: u64,
+    pub accepted_proofs: u64,
+    pub rejected_proofs: u64,
+    pub released_batches: u64,
+    pub expired_leases: u64,
+    pub active_leases: u64,
+    pub proving_time_secs: u64,
+}
+
+impl From<ExternalProverStats> for ProverStatsResponse {
+    fn from(stats: ExternalProverStats) -> Self {
+        Self {
+            name: stats.name,
+            claimed_batches: stats.claimed_batches,
+            accepted_proofs: stats.accepted_proofs,
+            rejected_proofs: stats.rejected_proofs,
+            released_batches: stats.released_batches,
+            expired_leases: stats.expired_leases,
+            active_leases: stats.active_leases,
+            proving_time_secs: stats.proving_time.as_secs(),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct ExternalProof {
     raw: Vec<u8>,
+    proof: L1BatchProofForL1,
     protocol_version: ProtocolSemanticVersion,
 }
 
@@ -49,6 +96,10 @@ impl ExternalProof {
         self.protocol_version
     }
 
+    pub fn into_proof(self) -> L1BatchProofForL1 {
+        self.proof
+    }
+
     pub fn verify(&self, correct: L1BatchProofForL1) -> Result<(), ProcessorError> {
         let protocol_version = match correct.clone() {
             L1BatchProofForL1::Fflonk(proof) => proof.protocol_version,
@@ -101,14 +152,11 @@ impl<S: Send + Sync> FromRequest<S> for ExternalProof {
     async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
         let serialized_proof = Self::extract_from_multipart(req, state).await?;
         let proof: L1BatchProofForL1 = bincode::deserialize(&serialized_proof)?;
-
-        let protocol_version = match proof {
-            L1BatchProofForL1::Fflonk(proof) => proof.protocol_version,
-            L1BatchProofForL1::Plonk(proof) => proof.protocol_version,
-        };
+        let protocol_version = proof.protocol_version();
 
         Ok(Self {
             raw: serialized_proof,
+            proof,
             protocol_version,
         })
     }
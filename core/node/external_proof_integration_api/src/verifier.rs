@@ -0,0 +1,305 @@
+//! Verification of SNARK wrapper proofs submitted to the proof marketplace.
+
+use std::{fmt, fs, path::Path, sync::Arc};
+
+use anyhow::Context as _;
+use bellman::{
+    compact_bn256::Fq,
+    pairing::bn256::{Bn256, Fr},
+    plonk::{
+        better_better_cs::{setup::VerificationKey as PlonkVerificationKey, verifier},
+        commitments::transcript::keccak_transcript::RollingKeccakTranscript,
+    },
+    CurveAffine, PrimeField, PrimeFieldRepr,
+};
+use circuit_definitions::circuit_definitions::aux_layer::{
+    ZkSyncSnarkWrapperCircuit, ZkSyncSnarkWrapperCircuitNoLookupCustomGate,
+};
+use fflonk::FflonkVerificationKey;
+use zksync_basic_types::{protocol_version::L1VerifierConfig, web3::keccak256, H256, U256};
+use zksync_prover_interface::outputs::L1BatchProofForL1;
+
+type SnarkVerificationKey = PlonkVerificationKey<Bn256, ZkSyncSnarkWrapperCircuit>;
+type FflonkSnarkVerificationKey =
+    FflonkVerificationKey<Bn256, ZkSyncSnarkWrapperCircuitNoLookupCustomGate>;
+
+/// Verifies final proofs against SNARK wrapper verification keys loaded from disk. The key used
+/// for a batch is selected by its hash in the L1 verifier config of the batch protocol version.
+#[derive(Clone)]
+pub struct ProofVerifier {
+    plonk_key: Option<(H256, Arc<SnarkVerificationKey>)>,
+    fflonk_key: Option<(H256, Arc<FflonkSnarkVerificationKey>)>,
+}
+
+impl fmt::Debug for ProofVerifier {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("ProofVerifier")
+            .field(
+                "plonk_key_hash",
+                &self.plonk_key.as_ref().map(|(hash, _)| hash),
+            )
+            .field(
+                "fflonk_key_hash",
+                &self.fflonk_key.as_ref().map(|(hash, _)| hash),
+            )
+            .finish()
+    }
+}
+
+impl ProofVerifier {
+    const PLONK_KEY_FILE: &'static str = "verification_snark_key.json";
+    const FFLONK_KEY_FILE: &'static str = "fflonk_verification_snark_key.json";
+
+    /// Loads verification keys from the specified directory. At least one of the keys must be present.
+    pub fn load(keys_path: &Path) -> anyhow::Result<Self> {
+        let plonk_key = Self::load_key(&keys_path.join(Self::PLONK_KEY_FILE))?
+            .map(|key: SnarkVerificationKey| (plonk_key_hash(&key), Arc::new(key)));
+        let fflonk_key = Self::load_key(&keys_path.join(Self::FFLONK_KEY_FILE))?
+            .map(|key: FflonkSnarkVerificationKey| (fflonk_key_hash(&key), Arc::new(key)));
+        anyhow::ensure!(
+            plonk_key.is_some() || fflonk_key.is_some(),
+            "no verification keys found in {keys_path:?}"
+        );
+
+        let this = Self {
+            plonk_key,
+            fflonk_key,
+        };
+        tracing::info!("Loaded verification keys for proof marketplace: {this:?}");
+        Ok(this)
+    }
+
+    fn load_key<K: serde::de::DeserializeOwned>(path: &Path) -> anyhow::Result<Option<K>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let key = fs::read_to_string(path).with_context(|| format!("failed reading {path:?}"))?;
+        let key = serde_json::from_str(&key).with_context(|| format!("failed parsing {path:?}"))?;
+        Ok(Some(key))
+    }
+
+    /// Verifies the proof and checks that it's generated for the batch with the specified public input
+    /// (see [`batch_proof_public_input()`]). This is a CPU-heavy operation, so it's executed on a blocking thread.
+    pub(crate) async fn verify(
+        &self,
+        proof: L1BatchProofForL1,
+        verifier_config: L1VerifierConfig,
+        expected_public_input: U256,
+    ) -> anyhow::Result<Result<(), String>> {
+        let this = self.clone();
+        tokio::task::spawn_blocking(move || {
+            this.verify_blocking(&proof, &verifier_config, expected_public_input)
+        })
+        .await
+        .context("proof verification panicked")
+    }
+
+    fn verify_blocking(
+        &self,
+        proof: &L1BatchProofForL1,
+        verifier_config: &L1VerifierConfig,
+        expected_public_input: U256,
+    ) -> Result<(), String> {
+        // Aggregation result coordinates are supplied by the prover, so the batch the proof is generated for
+        // is only determined by the public input bound by the proof itself.
+        let public_inputs = match proof {
+            L1BatchProofForL1::Plonk(proof) => &proof.scheduler_proof.inputs,
+            L1BatchProofForL1::Fflonk(proof) => &proof.scheduler_proof.inputs,
+        };
+        let [public_input] = public_inputs.as_slice() else {
+            return Err(format!(
+                "proof has {} public inputs, expected 1",
+                public_inputs.len()
+            ));
+        };
+        let public_input = field_element_to_u256(public_input);
+        if public_input != expected_public_input {
+            return Err(format!(
+                "proof public input doesn't match the batch: expected {expected_public_input:#x}, got {public_input:#x}"
+            ));
+        }
+
+        let is_valid = match proof {
+            L1BatchProofForL1::Plonk(proof) => {
+                let expected_hash = verifier_config.snark_wrapper_vk_hash;
+                let key = Self::select_key(self.plonk_key.as_ref(), expected_hash, "PLONK")?;
+                verifier::verify::<_, _, RollingKeccakTranscript<Fr>>(
+                    key,
+                    &proof.scheduler_proof,
+                    None,
+                )
+            }
+            L1BatchProofForL1::Fflonk(proof) => {
+                let expected_hash = verifier_config
+                    .fflonk_snark_wrapper_vk_hash
+                    .ok_or("FFLONK proofs are not accepted for this protocol version")?;
+                let key = Self::select_key(self.fflonk_key.as_ref(), expected_hash, "FFLONK")?;
+                fflonk::verify::<_, _, RollingKeccakTranscript<Fr>>(
+                    key,
+                    &proof.scheduler_proof,
+                    None,
+                )
+            }
+        };
+
+        match is_valid {
+            Ok(true) => Ok(()),
+            Ok(false) => Err("proof verification failed".to_owned()),
+            Err(err) => Err(format!("proof verification failed: {err:?}")),
+        }
+    }
+
+    fn select_key<K>(
+        key: Option<&(H256, Arc<K>)>,
+        expected_hash: H256,
+        proof_type: &str,
+    ) -> Result<&K, String> {
+        match key {
+            Some((hash, key)) if *hash == expected_hash => Ok(key.as_ref()),
+            _ => Err(format!(
+                "no {proof_type} verification key with hash {expected_hash:?} is configured"
+            )),
+        }
+    }
+}
+
+/// Calculates the public input of the proof of a batch the same way as the L1 executor contract:
+/// `uint256(keccak256(abi.encodePacked(prevBatchCommitment, batchCommitment))) >> 32`.
+pub(crate) fn batch_proof_public_input(
+    prev_batch_commitment: H256,
+    batch_commitment: H256,
+) -> U256 {
+    let mut packed = [0_u8; 64];
+    packed[..32].copy_from_slice(prev_batch_commitment.as_bytes());
+    packed[32..].copy_from_slice(batch_commitment.as_bytes());
+    U256::from_big_endian(&keccak256(&packed)) >> 32
+}
+
+fn field_element_to_u256(value: &Fr) -> U256 {
+    let mut bytes = vec![];
+    value.into_repr().write_be(&mut bytes).unwrap();
+    U256::from_big_endian(&bytes)
+}
+
+/// Calculates the hash of a PLONK SNARK wrapper verification key the same way as the L1 verifier contract.
+fn plonk_key_hash(key: &SnarkVerificationKey) -> H256 {
+    let mut res = vec![];
+    let points = key
+        .gate_setup_commitments
+        .iter()
+        .chain(&key.gate_selectors_commitments)
+        .chain(&key.permutation_commitments)
+        .chain(key.lookup_selector_commitment.as_ref())
+        .chain(&key.lookup_tables_commitments)
+        .chain(key.lookup_table_type_commitment.as_ref());
+    for point in points {
+        let (x, y) = point.as_xy();
+        x.into_repr().write_be(&mut res).unwrap();
+        y.into_repr().write_be(&mut res).unwrap();
+    }
+    // Flag for using the recursive part.
+    Fq::default().into_repr().write_be(&mut res).unwrap();
+    H256(keccak256(&res))
+}
+
+/// Calculates the hash of an FFLONK SNARK wrapper verification key the same way as the L1 verifier contract.
+fn fflonk_key_hash(key: &FflonkSnarkVerificationKey) -> H256 {
+    let mut res = vec![0_u8; 32];
+    U256::from(key.num_inputs).to_big_endian(&mut res[0..32]);
+    let (x, y) = key.c0.as_xy();
+    x.into_repr().write_be(&mut res).unwrap();
+    y.into_repr().write_be(&mut res).unwrap();
+    for non_residue in &key.non_residues {
+        non_residue.into_repr().write_be(&mut res).unwrap();
+    }
+    for g2_element in &key.g2_elements {
+        res.extend(g2_element.into_uncompressed().as_ref());
+    }
+    H256(keccak256(&res))
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_object_store::StoredObject;
+    use zksync_utils::env::Workspace;
+
+    use super::*;
+
+    fn load_verifier() -> ProofVerifier {
+        ProofVerifier::load(&Workspace::locate().prover().join("data/keys")).unwrap()
+    }
+
+    fn load_proof() -> L1BatchProofForL1 {
+        let path = Workspace::locate()
+            .core()
+            .join("lib/prover_interface/tests/l1_batch_proof_1_0_24_0.bin");
+        StoredObject::deserialize(fs::read(path).unwrap()).unwrap()
+    }
+
+    fn public_input(proof: &L1BatchProofForL1) -> U256 {
+        let inputs = match proof {
+            L1BatchProofForL1::Plonk(proof) => &proof.scheduler_proof.inputs,
+            L1BatchProofForL1::Fflonk(proof) => &proof.scheduler_proof.inputs,
+        };
+        field_element_to_u256(&inputs[0])
+    }
+
+    #[test]
+    fn public_input_fits_into_field() {
+        let input = batch_proof_public_input(H256::repeat_byte(0xff), H256::repeat_byte(0xfe));
+        assert!(input.bits() <= 224, "{input:#x}");
+        assert_ne!(
+            input,
+            batch_proof_public_input(H256::repeat_byte(0xfe), H256::repeat_byte(0xff))
+        );
+    }
+
+    #[test]
+    fn rejecting_proof_of_another_batch_with_rewritten_coords() {
+        let verifier = load_verifier();
+        let mut proof = load_proof();
+        let proof_input = public_input(&proof);
+        // A malicious prover replaces aggregation result coordinates with those of the leased batch.
+        let leased_batch_coords = [[1; 32], [2; 32], [3; 32], [4; 32]];
+        match &mut proof {
+            L1BatchProofForL1::Plonk(proof) => {
+                proof.aggregation_result_coords = leased_batch_coords
+            }
+            L1BatchProofForL1::Fflonk(proof) => {
+                proof.aggregation_result_coords = leased_batch_coords
+            }
+        }
+        let expected_input = batch_proof_public_input(H256::repeat_byte(1), H256::repeat_byte(2));
+        assert_ne!(proof_input, expected_input);
+
+        let verifier_config = L1VerifierConfig {
+            snark_wrapper_vk_hash: verifier.plonk_key.as_ref().unwrap().0,
+            fflonk_snark_wrapper_vk_hash: verifier.fflonk_key.as_ref().map(|(hash, _)| *hash),
+        };
+        let err = verifier
+            .verify_blocking(&proof, &verifier_config, expected_input)
+            .unwrap_err();
+        assert!(err.contains("public input doesn't match"), "{err}");
+    }
+
+    #[test]
+    fn key_hashes_match_committed_keys() {
+        let keys_path = Workspace::locate().prover().join("data/keys");
+        let commitments = fs::read_to_string(keys_path.join("commitments.json")).unwrap();
+        let commitments: serde_json::Value = serde_json::from_str(&commitments).unwrap();
+        let expected_hash =
+            |name: &str| -> H256 { serde_json::from_value(commitments[name].clone()).unwrap() };
+
+        let key: SnarkVerificationKey =
+            ProofVerifier::load_key(&keys_path.join(ProofVerifier::PLONK_KEY_FILE))
+                .unwrap()
+                .expect("no PLONK key");
+        assert_eq!(plonk_key_hash(&key), expected_hash("snark_wrapper"));
+        let key: FflonkSnarkVerificationKey =
+            ProofVerifier::load_key(&keys_path.join(ProofVerifier::FFLONK_KEY_FILE))
+                .unwrap()
+                .expect("no FFLONK key");
+        assert_eq!(fflonk_key_hash(&key), expected_hash("fflonk_snark_wrapper"));
+    }
+}
@@ -0,0 +1,78 @@
+//! Pubdata price feedback from the fees paid to the DA layer.
+
+use std::{
+    fmt,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use anyhow::Context as _;
+use tokio::sync::watch;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+
+/// Number of the latest L1 batches with a known DA fee used to compute the pubdata price.
+const FEE_WINDOW_BATCHES: usize = 100;
+/// Interval between refreshing the pubdata price from the database.
+const UPDATE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Provides the pubdata price based on the fees paid to the DA layer. Used on validium chains, for which
+/// the pubdata price cannot be estimated from the L1 fees.
+pub trait DAPubdataPriceProvider: fmt::Debug + Send + Sync + 'static {
+    /// Returns the price of a pubdata byte denominated in the base token, or `None` if it's unknown.
+    fn pubdata_price_in_base_token(&self) -> Option<u64>;
+}
+
+/// [`DAPubdataPriceProvider`] periodically computing the average DA fee per pubdata byte for recent L1 batches,
+/// as recorded by the DA dispatcher.
+#[derive(Debug, Clone)]
+pub struct DBDAPubdataPriceProvider {
+    pool: ConnectionPool<Core>,
+    latest_price: Arc<RwLock<Option<u64>>>,
+}
+
+impl DBDAPubdataPriceProvider {
+    pub fn new(pool: ConnectionPool<Core>) -> Self {
+        Self {
+            pool,
+            latest_price: Arc::default(),
+        }
+    }
+
+    pub async fn run(&self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let mut timer = tokio::time::interval(UPDATE_INTERVAL);
+
+        while !*stop_receiver.borrow_and_update() {
+            tokio::select! {
+                _ = timer.tick() => { /* continue iterations */ }
+                _ = stop_receiver.changed() => break,
+            }
+
+            self.update_price().await?;
+        }
+
+        tracing::info!("Stop signal received, DA pubdata price provider is shutting down");
+        Ok(())
+    }
+
+    async fn update_price(&self) -> anyhow::Result<()> {
+        let price = self
+            .pool
+            .connection_tagged("da_pubdata_price_provider")
+            .await
+            .context("Failed to obtain connection to the database")?
+            .data_availability_dal()
+            .get_recent_da_fee_per_pubdata_byte(FEE_WINDOW_BATCHES)
+            .await
+            .context("Failed to get recent DA fees")?;
+
+        tracing::debug!("Updated pubdata price based on DA fees: {price:?}");
+        *self.latest_price.write().unwrap() = price;
+        Ok(())
+    }
+}
+
+impl DAPubdataPriceProvider for DBDAPubdataPriceProvider {
+    fn pubdata_price_in_base_token(&self) -> Option<u64> {
+        *self.latest_price.read().unwrap()
+    }
+}
@@ -7,8 +7,9 @@ use zksync_types::fee_model::{
     BaseTokenConversionRatio, BatchFeeInput, FeeModelConfig, FeeParams, FeeParamsV1, FeeParamsV2,
 };
 
-use crate::l1_gas_price::GasAdjuster;
+use crate::{da_pubdata_price::DAPubdataPriceProvider, l1_gas_price::GasAdjuster};
 
+pub mod da_pubdata_price;
 pub mod l1_gas_price;
 
 /// Trait responsible for providing numerator and denominator for adjusting gas price that is denominated
@@ -50,6 +51,7 @@ impl dyn BatchFeeModelInputProvider {
 pub struct MainNodeFeeInputProvider {
     provider: Arc<GasAdjuster>,
     base_token_ratio_provider: Arc<dyn BaseTokenRatioProvider>,
+    da_pubdata_price_provider: Option<Arc<dyn DAPubdataPriceProvider>>,
     config: FeeModelConfig,
 }
 
@@ -61,12 +63,15 @@ impl BatchFeeModelInputProvider for MainNodeFeeInputProvider {
                 config,
                 l1_gas_price: self.provider.estimate_effective_gas_price(),
             }),
-            FeeModelConfig::V2(config) => FeeParams::V2(FeeParamsV2::new(
-                config,
-                self.provider.estimate_effective_gas_price(),
-                self.provider.estimate_effective_pubdata_price(),
-                self.base_token_ratio_provider.get_conversion_ratio(),
-            )),
+            FeeModelConfig::V2(config) => {
+                let conversion_ratio = self.base_token_ratio_provider.get_conversion_ratio();
+                FeeParams::V2(FeeParamsV2::new(
+                    config,
+                    self.provider.estimate_effective_gas_price(),
+                    self.effective_pubdata_price(conversion_ratio),
+                    conversion_ratio,
+                ))
+            }
         }
     }
 }
@@ -80,9 +85,38 @@ impl MainNodeFeeInputProvider {
         Self {
             provider,
             base_token_ratio_provider,
+            da_pubdata_price_provider: None,
             config,
         }
     }
+
+    /// Adds the feedback from the fees paid to the DA layer. If the DA fees are known, the pubdata price
+    /// is at least the average DA fee per pubdata byte.
+    pub fn with_da_pubdata_price_provider(
+        mut self,
+        provider: Arc<dyn DAPubdataPriceProvider>,
+    ) -> Self {
+        self.da_pubdata_price_provider = Some(provider);
+        self
+    }
+
+    /// Returns the pubdata price in wei.
+    fn effective_pubdata_price(&self, conversion_ratio: BaseTokenConversionRatio) -> u64 {
+        let estimated_price = self.provider.estimate_effective_pubdata_price();
+        let Some(da_price) = self
+            .da_pubdata_price_provider
+            .as_ref()
+            .and_then(|provider| provider.pubdata_price_in_base_token())
+        else {
+            return estimated_price;
+        };
+
+        // The DA fees are denominated in the base token, while fee params expect prices in wei.
+        let numerator = u128::from(conversion_ratio.numerator.get());
+        let denominator = u128::from(conversion_ratio.denominator.get());
+        let da_price_in_wei = (u128::from(da_price) * denominator).div_ceil(numerator);
+        estimated_price.max(da_price_in_wei.try_into().unwrap_or(u64::MAX))
+    }
 }
 
 /// The fee model provider to be used in the API. It returns the maximum batch fee input between the projected main node one and
@@ -356,6 +390,53 @@ mod tests {
         }
     }
 
+    #[derive(Debug)]
+    struct DummyDAPubdataPriceProvider(Option<u64>);
+
+    impl DAPubdataPriceProvider for DummyDAPubdataPriceProvider {
+        fn pubdata_price_in_base_token(&self) -> Option<u64> {
+            self.0
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pubdata_price_feedback_from_da_fees() {
+        // 1 ETH = 2 BaseToken
+        let conversion_ratio = BaseTokenConversionRatio {
+            numerator: NonZeroU64::new(2).unwrap(),
+            denominator: NonZeroU64::new(1).unwrap(),
+        };
+        let config = FeeModelConfig::V2(FeeModelConfigV2 {
+            minimal_l2_gas_price: 1_000,
+            compute_overhead_part: 1.0,
+            pubdata_overhead_part: 1.0,
+            batch_overhead_l1_gas: 1,
+            max_gas_per_batch: 1,
+            max_pubdata_per_batch: 1,
+        });
+
+        // (DA fee per byte in base token, expected pubdata price in base token)
+        let cases = [(None, 2_000), (Some(5_001), 5_002), (Some(1_000), 2_000)];
+        for (da_price, expected_pubdata_price) in cases {
+            let gas_adjuster = setup_gas_adjuster(1_000, 1_000).await;
+            let fee_provider = MainNodeFeeInputProvider::new(
+                Arc::new(gas_adjuster),
+                Arc::new(DummyTokenRatioProvider::new(conversion_ratio)),
+                config,
+            )
+            .with_da_pubdata_price_provider(Arc::new(DummyDAPubdataPriceProvider(da_price)));
+
+            let FeeParams::V2(params) = fee_provider.get_fee_model_params() else {
+                panic!("Expected FeeParams::V2");
+            };
+            assert_eq!(
+                params.l1_pubdata_price(),
+                expected_pubdata_price,
+                "DA price: {da_price:?}"
+            );
+        }
+    }
+
     // Helper function to create BaseFees.
     fn test_base_fees(block: u64, blob: U256, pubdata: U256) -> BaseFees {
         BaseFees {
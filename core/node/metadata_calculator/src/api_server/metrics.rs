@@ -12,6 +12,8 @@ pub(super) enum MerkleTreeApiMethod {
     GetNodes,
     GetStaleKeys,
     GetBogusStaleKeys,
+    ZkOsInfo,
+    ZkOsGetProofs,
 }
 
 /// Metrics for Merkle tree API.
@@ -21,11 +21,13 @@ use zksync_merkle_tree::{
 use zksync_types::{u256_to_h256, web3, L1BatchNumber, H256, U256};
 
 use self::metrics::{MerkleTreeApiMethod, API_METRICS};
-use crate::{AsyncTreeReader, LazyAsyncTreeReader, MerkleTreeInfo};
+pub use self::zk_os::{ZkOsTreeLeaf, ZkOsTreeOperation, ZkOsTreeProof};
+use crate::{AsyncTreeReader, LazyAsyncTreeReader, LazyZkOsTreeReader, MerkleTreeInfo};
 
 mod metrics;
 #[cfg(test)]
 mod tests;
+mod zk_os;
 
 #[derive(Debug, Serialize, Deserialize)]
 struct TreeProofsRequest {
@@ -500,11 +502,12 @@ impl AsyncTreeReader {
     async fn create_api_server(
         self,
         bind_address: &SocketAddr,
+        zk_os_tree: Option<LazyZkOsTreeReader>,
         mut stop_receiver: watch::Receiver<bool>,
     ) -> anyhow::Result<MerkleTreeServer> {
         tracing::debug!("Starting Merkle tree API server on {bind_address}");
 
-        let app = Router::new()
+        let mut app = Router::new()
             .route("/", routing::get(Self::info_handler))
             .route("/proofs", routing::post(Self::get_proofs_handler))
             .route("/debug/nodes", routing::post(Self::get_nodes_handler))
@@ -517,6 +520,9 @@ impl AsyncTreeReader {
                 routing::post(Self::bogus_stale_keys_handler),
             )
             .with_state(self);
+        if let Some(zk_os_tree) = zk_os_tree {
+            app = app.merge(zk_os::router(zk_os_tree));
+        }
 
         let listener = tokio::net::TcpListener::bind(bind_address)
             .await
@@ -551,7 +557,20 @@ impl AsyncTreeReader {
         bind_address: SocketAddr,
         stop_receiver: watch::Receiver<bool>,
     ) -> anyhow::Result<()> {
-        self.create_api_server(&bind_address, stop_receiver)
+        self.create_api_server(&bind_address, None, stop_receiver)
+            .await?
+            .run()
+            .await
+    }
+
+    /// Runs the HTTP API server additionally serving the experimental ZK OS tree endpoints (`/zk_os` and `/zk_os/proofs`).
+    pub async fn run_api_server_with_zk_os_tree(
+        self,
+        zk_os_tree: LazyZkOsTreeReader,
+        bind_address: SocketAddr,
+        stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        self.create_api_server(&bind_address, Some(zk_os_tree), stop_receiver)
             .await?
             .run()
             .await
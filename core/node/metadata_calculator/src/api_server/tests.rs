@@ -29,7 +29,7 @@ async fn merkle_tree_api() {
         .wait()
         .await
         .unwrap()
-        .create_api_server(&api_addr, stop_receiver.clone())
+        .create_api_server(&api_addr, None, stop_receiver.clone())
         .await
         .unwrap();
     let local_addr = *api_server.local_addr();
@@ -116,6 +116,89 @@ async fn merkle_tree_api() {
     api_server_task.await.unwrap().unwrap();
 }
 
+#[tokio::test]
+async fn zk_os_tree_api() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let (calculator, _) = setup_calculator(&temp_dir.path().join("main"), pool.clone(), true).await;
+    let api_addr = (Ipv4Addr::LOCALHOST, 0).into();
+
+    reset_db_state(&pool, 5).await;
+    let zk_os_task =
+        calculator.zk_os_tree_task(temp_dir.path().join("zk_os").to_str().unwrap().to_owned());
+    let mut zk_os_health_check = zk_os_task.health_check();
+    let zk_os_reader = zk_os_task.tree_reader();
+    let tree_reader = calculator.tree_reader();
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let zk_os_task = tokio::spawn(zk_os_task.run(stop_receiver.clone()));
+    run_calculator(calculator).await;
+    zk_os_health_check
+        .wait_for(|health| {
+            matches!(health.status(), HealthStatus::Ready)
+                && health.details().unwrap()["next_l1_batch_number"] == 6
+        })
+        .await;
+
+    let api_server = tree_reader
+        .wait()
+        .await
+        .unwrap()
+        .create_api_server(&api_addr, Some(zk_os_reader), stop_receiver.clone())
+        .await
+        .unwrap();
+    let local_addr = *api_server.local_addr();
+    let api_server_task = tokio::spawn(api_server.run());
+    let client = reqwest::Client::new();
+
+    let info: crate::ZkOsTreeInfo = client
+        .get(format!("http://{local_addr}/zk_os"))
+        .send()
+        .await
+        .unwrap()
+        .error_for_status()
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(info.next_l1_batch_number, L1BatchNumber(6));
+    let root_hash = info.root_hash.expect("no root hash");
+
+    let mut keys: Vec<_> = gen_storage_logs(20..30, 1)[0]
+        .iter()
+        .map(|log| log.key.hashed_key())
+        .collect();
+    // Extend with some non-existing keys.
+    keys.extend((0_u8..10).map(H256::repeat_byte));
+
+    let proof: ZkOsTreeProof = client
+        .post(format!("http://{local_addr}/zk_os/proofs"))
+        .json(&serde_json::json!({ "l1_batch_number": 5, "keys": keys }))
+        .send()
+        .await
+        .unwrap()
+        .error_for_status()
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let entries = proof.verify(root_hash, &keys).unwrap();
+    for (i, key) in keys.iter().enumerate() {
+        assert_eq!(entries[key].is_some(), i < 10);
+    }
+
+    let response = client
+        .post(format!("http://{local_addr}/zk_os/proofs"))
+        .json(&serde_json::json!({ "l1_batch_number": 10, "keys": [] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    stop_sender.send_replace(true);
+    api_server_task.await.unwrap().unwrap();
+    zk_os_task.await.unwrap().unwrap();
+}
+
 fn assert_raw_nodes_response(response: &serde_json::Value) {
     let response = response.as_object().expect("not an object");
     let response = response["nodes"].as_object().expect("not an object");
@@ -0,0 +1,231 @@
+//! Experimental API for the ZK OS Merkle tree.
+
+use std::collections::{BTreeMap, HashMap};
+
+use axum::{
+    extract::State,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing, Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use zk_os_merkle_tree::{
+    BatchOutput, BatchTreeProof, Blake2Hasher, DefaultTreeParams, IntermediateHash, Leaf,
+    TreeOperation, TreeParams,
+};
+use zksync_types::{L1BatchNumber, H256};
+
+use super::{
+    metrics::{MerkleTreeApiMethod, API_METRICS},
+    Problem, PROBLEM_CONTENT_TYPE,
+};
+use crate::{LazyZkOsTreeReader, ZkOsTreeInfo};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ZkOsTreeProofsRequest {
+    l1_batch_number: L1BatchNumber,
+    keys: Vec<H256>,
+}
+
+/// Operation on a tree entry in [`ZkOsTreeProof`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ZkOsTreeOperation {
+    /// Entry is present in the tree at the specified leaf index.
+    Hit { index: u64 },
+    /// Entry is missing from the tree; `prev_index` is the index of the lexicographically previous existing leaf.
+    Miss { prev_index: u64 },
+}
+
+impl From<TreeOperation> for ZkOsTreeOperation {
+    fn from(op: TreeOperation) -> Self {
+        match op {
+            TreeOperation::Hit { index } => Self::Hit { index },
+            TreeOperation::Miss { prev_index } => Self::Miss { prev_index },
+        }
+    }
+}
+
+impl From<ZkOsTreeOperation> for TreeOperation {
+    fn from(op: ZkOsTreeOperation) -> Self {
+        match op {
+            ZkOsTreeOperation::Hit { index } => Self::Hit { index },
+            ZkOsTreeOperation::Miss { prev_index } => Self::Miss { prev_index },
+        }
+    }
+}
+
+/// Leaf of the ZK OS tree included into [`ZkOsTreeProof`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ZkOsTreeLeaf {
+    pub key: H256,
+    pub value: H256,
+    pub prev_index: u64,
+    pub next_index: u64,
+}
+
+/// Read proof for the ZK OS Merkle tree returned by the tree API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZkOsTreeProof {
+    /// Root hash of the tree at the requested L1 batch.
+    pub root_hash: H256,
+    /// Number of leaves in the tree at the requested L1 batch (including 2 guard leaves).
+    pub leaf_count: u64,
+    /// Operations corresponding 1-to-1 to the requested keys.
+    pub read_operations: Vec<ZkOsTreeOperation>,
+    /// Tree leaves sufficient to prove the operations, keyed by the leaf index.
+    pub sorted_leaves: BTreeMap<u64, ZkOsTreeLeaf>,
+    /// Intermediate hashes necessary to restore the root hash.
+    pub hashes: Vec<H256>,
+}
+
+impl ZkOsTreeProof {
+    fn new(output: BatchOutput, proof: BatchTreeProof) -> Self {
+        Self {
+            root_hash: output.root_hash,
+            leaf_count: output.leaf_count,
+            read_operations: proof.read_operations.into_iter().map(Into::into).collect(),
+            sorted_leaves: proof
+                .sorted_leaves
+                .into_iter()
+                .map(|(idx, leaf)| {
+                    let leaf = ZkOsTreeLeaf {
+                        key: leaf.key,
+                        value: leaf.value,
+                        prev_index: leaf.prev_index,
+                        next_index: leaf.next_index,
+                    };
+                    (idx, leaf)
+                })
+                .collect(),
+            hashes: proof.hashes.into_iter().map(|hash| hash.value).collect(),
+        }
+    }
+
+    /// Verifies this proof for the specified `keys` against a trusted root hash. Returns proven values for the keys;
+    /// `None` values correspond to missing keys.
+    pub fn verify(
+        self,
+        trusted_root_hash: H256,
+        keys: &[H256],
+    ) -> anyhow::Result<HashMap<H256, Option<H256>>> {
+        anyhow::ensure!(
+            self.root_hash == trusted_root_hash,
+            "Root hash in proof {:?} differs from the trusted root hash {trusted_root_hash:?}",
+            self.root_hash
+        );
+        let output = BatchOutput {
+            root_hash: self.root_hash,
+            leaf_count: self.leaf_count,
+        };
+        let proof = BatchTreeProof {
+            operations: vec![],
+            read_operations: self.read_operations.into_iter().map(Into::into).collect(),
+            sorted_leaves: self
+                .sorted_leaves
+                .into_iter()
+                .map(|(idx, leaf)| {
+                    let leaf = Leaf {
+                        key: leaf.key,
+                        value: leaf.value,
+                        prev_index: leaf.prev_index,
+                        next_index: leaf.next_index,
+                    };
+                    (idx, leaf)
+                })
+                .collect(),
+            hashes: self
+                .hashes
+                .into_iter()
+                .map(|value| IntermediateHash { value })
+                .collect(),
+        };
+        let tree_depth = <DefaultTreeParams as TreeParams>::TREE_DEPTH;
+        let view = proof.verify_reads(&Blake2Hasher, tree_depth, output, keys)?;
+        anyhow::ensure!(
+            view.root_hash == trusted_root_hash,
+            "Restored root hash {:?} differs from the trusted root hash {trusted_root_hash:?}",
+            view.root_hash
+        );
+        Ok(view.read_entries)
+    }
+}
+
+/// Server-side error of the ZK OS tree API.
+#[derive(Debug)]
+enum ZkOsTreeApiError {
+    NotReady,
+    NoVersion(L1BatchNumber),
+    Internal(anyhow::Error),
+}
+
+impl IntoResponse for ZkOsTreeApiError {
+    fn into_response(self) -> Response {
+        let headers = [(header::CONTENT_TYPE, PROBLEM_CONTENT_TYPE)];
+        let (status, body) = match self {
+            Self::NotReady => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Problem {
+                    r#type: "/errors#zk-os-tree-not-ready",
+                    title: "ZK OS tree is not ready",
+                    detail: "ZK OS tree is initializing".to_owned(),
+                    data: (),
+                },
+            ),
+            Self::NoVersion(l1_batch_number) => (
+                StatusCode::NOT_FOUND,
+                Problem {
+                    r#type: "/errors#l1-batch-not-found",
+                    title: "L1 batch not found",
+                    detail: format!("L1 batch #{l1_batch_number} is not present in the ZK OS tree"),
+                    data: (),
+                },
+            ),
+            Self::Internal(err) => {
+                tracing::warn!("Internal error in ZK OS tree API: {err:#}");
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Problem {
+                        r#type: "/errors#internal",
+                        title: "Internal error",
+                        detail: format!("{err:#}"),
+                        data: (),
+                    },
+                )
+            }
+        };
+        (status, headers, Json(body)).into_response()
+    }
+}
+
+async fn info_handler(
+    State(reader): State<LazyZkOsTreeReader>,
+) -> Result<Json<ZkOsTreeInfo>, ZkOsTreeApiError> {
+    let latency = API_METRICS.latency[&MerkleTreeApiMethod::ZkOsInfo].start();
+    let reader = reader.read().ok_or(ZkOsTreeApiError::NotReady)?;
+    let info = reader.info().await.map_err(ZkOsTreeApiError::Internal)?;
+    latency.observe();
+    Ok(Json(info))
+}
+
+async fn get_proofs_handler(
+    State(reader): State<LazyZkOsTreeReader>,
+    Json(request): Json<ZkOsTreeProofsRequest>,
+) -> Result<Json<ZkOsTreeProof>, ZkOsTreeApiError> {
+    let latency = API_METRICS.latency[&MerkleTreeApiMethod::ZkOsGetProofs].start();
+    let reader = reader.read().ok_or(ZkOsTreeApiError::NotReady)?;
+    let (output, proof) = reader
+        .prove(request.l1_batch_number, request.keys)
+        .await
+        .map_err(ZkOsTreeApiError::Internal)?
+        .ok_or(ZkOsTreeApiError::NoVersion(request.l1_batch_number))?;
+    latency.observe();
+    Ok(Json(ZkOsTreeProof::new(output, proof)))
+}
+
+pub(super) fn router(reader: LazyZkOsTreeReader) -> Router {
+    Router::new()
+        .route("/zk_os", routing::get(info_handler))
+        .route("/zk_os/proofs", routing::post(get_proofs_handler))
+        .with_state(reader)
+}
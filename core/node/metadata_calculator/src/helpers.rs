@@ -27,7 +27,9 @@ use zksync_merkle_tree::{
     Database, Key, MerkleTreeColumnFamily, NoVersionError, RocksDBWrapper, TreeEntry,
     TreeEntryWithProof, TreeInstruction,
 };
-use zksync_storage::{RocksDB, RocksDBOptions, StalledWritesRetries, WeakRocksDB};
+use zksync_storage::{
+    db::NamedColumnFamily, RocksDB, RocksDBOptions, StalledWritesRetries, WeakRocksDB,
+};
 use zksync_types::{
     block::{L1BatchHeader, L1BatchTreeData},
     writes::TreeWrite,
@@ -139,6 +141,16 @@ pub(super) async fn create_db(config: MetadataCalculatorConfig) -> anyhow::Resul
 }
 
 fn create_db_sync(config: &MetadataCalculatorConfig) -> anyhow::Result<RocksDBWrapper> {
+    let mut db = RocksDBWrapper::from(open_rocksdb(config)?);
+    db.set_multi_get_chunk_size(config.multi_get_chunk_size);
+    Ok(db)
+}
+
+/// Opens a RocksDB instance for a Merkle tree with the specified params. Generic over column families
+/// so that it can be used both for the main tree and for the ZK OS tree.
+pub(super) fn open_rocksdb<CF: NamedColumnFamily>(
+    config: &MetadataCalculatorConfig,
+) -> anyhow::Result<RocksDB<CF>> {
     let path = Path::new(config.db_path.as_str());
     let &MetadataCalculatorConfig {
         max_open_files,
@@ -173,8 +185,6 @@ fn create_db_sync(config: &MetadataCalculatorConfig) -> anyhow::Result<RocksDBWr
         // some writes to RocksDB may occur, but not be visible to the test code.
         db = db.with_sync_writes();
     }
-    let mut db = RocksDBWrapper::from(db);
-    db.set_multi_get_chunk_size(multi_get_chunk_size);
     Ok(db)
 }
 
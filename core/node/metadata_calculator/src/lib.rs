@@ -27,6 +27,7 @@ pub use self::{
     helpers::{AsyncTreeReader, LazyAsyncTreeReader, MerkleTreeInfo},
     pruning::MerkleTreePruningTask,
     repair::StaleKeysRepairTask,
+    zk_os::{LazyZkOsTreeReader, ZkOsTreeInfo, ZkOsTreeReader, ZkOsTreeTask},
 };
 use crate::helpers::create_readonly_db;
 
@@ -39,6 +40,7 @@ mod repair;
 #[cfg(test)]
 pub(crate) mod tests;
 mod updater;
+mod zk_os;
 
 #[derive(Debug, Clone)]
 pub struct MetadataCalculatorRecoveryConfig {
@@ -210,6 +212,17 @@ impl MetadataCalculator {
         StaleKeysRepairTask::new(self.tree_reader())
     }
 
+    /// Returns a task building the experimental ZK OS Merkle tree in parallel with the main tree. The ZK OS tree
+    /// is stored in a separate RocksDB instance at `db_path`; other RocksDB options are shared with the main tree.
+    /// This method should be called once.
+    pub fn zk_os_tree_task(&self, db_path: String) -> ZkOsTreeTask {
+        let config = MetadataCalculatorConfig {
+            db_path,
+            ..self.config.clone()
+        };
+        ZkOsTreeTask::new(config, self.pool.clone())
+    }
+
     async fn create_tree(&self) -> anyhow::Result<GenericAsyncTree> {
         self.health_updater
             .update(MerkleTreeHealth::Initialization.into());
@@ -260,3 +260,22 @@ pub(super) struct MetadataCalculatorRecoveryMetrics {
 #[vise::register]
 pub(super) static RECOVERY_METRICS: vise::Global<MetadataCalculatorRecoveryMetrics> =
     vise::Global::new();
+
+/// Metrics for the ZK OS Merkle tree built in parallel with the main tree.
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_metadata_calculator_zk_os_tree")]
+pub(super) struct ZkOsTreeMetrics {
+    /// Latest L1 batch processed by the tree.
+    pub l1_batch_number: Gauge<u64>,
+    /// Current number of leaves in the tree (including 2 guard leaves).
+    pub leaf_count: Gauge<u64>,
+    /// Latency of recovering the tree from a Postgres snapshot.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub recovery_latency: Histogram<Duration>,
+    /// Latency of processing a single L1 batch (including loading its data from Postgres).
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub process_l1_batch_latency: Histogram<Duration>,
+}
+
+#[vise::register]
+pub(super) static ZK_OS_METRICS: vise::Global<ZkOsTreeMetrics> = vise::Global::new();
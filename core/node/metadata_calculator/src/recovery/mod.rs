@@ -110,16 +110,16 @@ impl HandleRecoveryEvent for RecoveryHealthUpdater<'_> {
 }
 
 #[derive(Debug, Clone, Copy)]
-struct InitParameters {
-    l1_batch: L1BatchNumber,
-    l2_block: L2BlockNumber,
+pub(super) struct InitParameters {
+    pub(super) l1_batch: L1BatchNumber,
+    pub(super) l2_block: L2BlockNumber,
     expected_root_hash: Option<H256>,
     log_count: u64,
     desired_chunk_size: u64,
 }
 
 impl InitParameters {
-    async fn new(
+    pub(super) async fn new(
         pool: &ConnectionPool<Core>,
         config: &MetadataCalculatorRecoveryConfig,
     ) -> anyhow::Result<Option<Self>> {
@@ -173,7 +173,7 @@ impl InitParameters {
         }))
     }
 
-    fn chunk_count(&self) -> u64 {
+    pub(super) fn chunk_count(&self) -> u64 {
         self.log_count.div_ceil(self.desired_chunk_size)
     }
 }
@@ -0,0 +1,498 @@
+//! Experimental support of the ZK OS Merkle tree.
+//!
+//! The ZK OS tree is built in parallel with the main Merkle tree from the same Postgres data. The tree version
+//! is equal to the L1 batch number, and leaf indices of the tree are aligned with enumeration indices of storage keys
+//! (a key with enumeration index `i` has leaf index `i + 1`, since leaves 0 and 1 are occupied by guards).
+//! The tree doesn't influence L1 batch commitments; it's only used to test the new commitment scheme on a real chain,
+//! e.g., by requesting its root hashes and proofs via the tree API. Using the ZK OS tree instead of the main tree
+//! (i.e., as the source of L1 batch root hashes) is not supported.
+
+use std::{sync::Arc, time::Instant};
+
+use anyhow::Context as _;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+use zk_os_merkle_tree::{
+    BatchOutput, BatchTreeProof, MerkleTree, MerkleTreeColumnFamily, MerkleTreePruner,
+    MerkleTreePrunerHandle, RocksDBWrapper, TreeEntry,
+};
+use zksync_config::configs::database::MerkleTreeMode;
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+use zksync_merkle_tree::TreeInstruction;
+use zksync_types::{snapshots::uniform_hashed_keys_chunk, L1BatchNumber, H256, U256};
+
+use crate::{
+    helpers::{open_rocksdb, L1BatchWithLogs},
+    metrics::ZK_OS_METRICS,
+    recovery::InitParameters,
+    MetadataCalculatorConfig,
+};
+
+#[cfg(test)]
+mod tests;
+
+type ZkOsTree = MerkleTree<RocksDBWrapper>;
+
+/// Converts a hashed key in the format used by the main Merkle tree (little-endian [`U256`]) back to the hashed key bytes,
+/// which are used as keys in the ZK OS tree.
+fn hashed_key_to_h256(key: U256) -> H256 {
+    let mut bytes = [0_u8; 32];
+    key.to_little_endian(&mut bytes);
+    H256(bytes)
+}
+
+/// General information about the ZK OS Merkle tree.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZkOsTreeInfo {
+    /// Root hash of the latest tree version, or `None` if the tree is empty.
+    pub root_hash: Option<H256>,
+    pub next_l1_batch_number: L1BatchNumber,
+    /// Number of leaves in the tree, including 2 guard leaves.
+    pub leaf_count: u64,
+}
+
+/// Health details for the ZK OS Merkle tree.
+#[derive(Debug, Serialize)]
+#[serde(tag = "stage", rename_all = "snake_case")]
+enum ZkOsTreeHealth {
+    Initialization,
+    Recovery { l1_batch: L1BatchNumber },
+    MainLoop(ZkOsTreeInfo),
+}
+
+impl From<ZkOsTreeHealth> for Health {
+    fn from(details: ZkOsTreeHealth) -> Self {
+        let status = match &details {
+            ZkOsTreeHealth::Initialization | ZkOsTreeHealth::Recovery { .. } => {
+                HealthStatus::Affected
+            }
+            ZkOsTreeHealth::MainLoop(_) => HealthStatus::Ready,
+        };
+        Self::from(status).with_details(details)
+    }
+}
+
+/// Async readonly access to the ZK OS Merkle tree.
+#[derive(Debug, Clone)]
+pub struct ZkOsTreeReader(Arc<ZkOsTree>);
+
+impl ZkOsTreeReader {
+    fn new(db: RocksDBWrapper) -> anyhow::Result<Self> {
+        Ok(Self(Arc::new(MerkleTree::new(db)?)))
+    }
+
+    fn info_sync(tree: &ZkOsTree) -> anyhow::Result<ZkOsTreeInfo> {
+        let Some(latest_version) = tree.latest_version()? else {
+            return Ok(ZkOsTreeInfo {
+                root_hash: None,
+                next_l1_batch_number: L1BatchNumber(0),
+                leaf_count: 0,
+            });
+        };
+        let output = tree
+            .root_info(latest_version)?
+            .with_context(|| format!("latest tree version {latest_version} is missing"))?;
+        let latest_l1_batch = u32::try_from(latest_version).context("tree version overflow")?;
+        Ok(ZkOsTreeInfo {
+            root_hash: Some(output.root_hash),
+            next_l1_batch_number: L1BatchNumber(latest_l1_batch) + 1,
+            leaf_count: output.leaf_count,
+        })
+    }
+
+    /// Returns general information about the tree.
+    pub async fn info(self) -> anyhow::Result<ZkOsTreeInfo> {
+        tokio::task::spawn_blocking(move || Self::info_sync(&self.0))
+            .await
+            .context("panicked getting ZK OS tree info")?
+    }
+
+    /// Returns the root hash and leaf count of the tree after the specified L1 batch, or `None` if the tree
+    /// doesn't contain the corresponding version.
+    pub async fn root_info(
+        self,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<Option<BatchOutput>> {
+        tokio::task::spawn_blocking(move || self.0.root_info(l1_batch_number.0.into()))
+            .await
+            .context("panicked getting ZK OS tree root info")?
+    }
+
+    /// Creates a read proof for the specified `keys` at the tree version corresponding to `l1_batch_number`.
+    /// Returns `None` if the tree doesn't contain the corresponding version.
+    pub async fn prove(
+        self,
+        l1_batch_number: L1BatchNumber,
+        keys: Vec<H256>,
+    ) -> anyhow::Result<Option<(BatchOutput, BatchTreeProof)>> {
+        tokio::task::spawn_blocking(move || {
+            let version = l1_batch_number.0.into();
+            let Some(output) = self.0.root_info(version)? else {
+                return Ok(None);
+            };
+            let proof = self.0.prove(version, &keys)?;
+            Ok(Some((output, proof)))
+        })
+        .await
+        .context("panicked creating ZK OS tree proof")?
+    }
+}
+
+/// Lazily initialized [`ZkOsTreeReader`].
+#[derive(Debug, Clone)]
+pub struct LazyZkOsTreeReader(watch::Receiver<Option<ZkOsTreeReader>>);
+
+impl LazyZkOsTreeReader {
+    /// Returns a reader if it is initialized.
+    pub fn read(&self) -> Option<ZkOsTreeReader> {
+        self.0.borrow().clone()
+    }
+
+    /// Waits until the tree is initialized and returns a reader for it. If the tree is dropped before
+    /// getting initialized, returns `None`.
+    pub async fn wait(mut self) -> Option<ZkOsTreeReader> {
+        loop {
+            if let Some(reader) = self.0.borrow().clone() {
+                break Some(reader);
+            }
+            self.0.changed().await.ok()?;
+        }
+    }
+}
+
+/// Task building the ZK OS Merkle tree in parallel with the main tree. Created using
+/// [`MetadataCalculator::zk_os_tree_task()`](crate::MetadataCalculator::zk_os_tree_task()).
+#[derive(Debug)]
+#[must_use = "Task should `run()` in a managed Tokio task"]
+pub struct ZkOsTreeTask {
+    config: MetadataCalculatorConfig,
+    pool: ConnectionPool<Core>,
+    tree_reader: watch::Sender<Option<ZkOsTreeReader>>,
+    health_updater: HealthUpdater,
+}
+
+impl ZkOsTreeTask {
+    pub(super) fn new(config: MetadataCalculatorConfig, pool: ConnectionPool<Core>) -> Self {
+        let (_, health_updater) = ReactiveHealthCheck::new("zk_os_tree");
+        Self {
+            config,
+            pool,
+            tree_reader: watch::channel(None).0,
+            health_updater,
+        }
+    }
+
+    /// Returns a reference to the tree reader.
+    pub fn tree_reader(&self) -> LazyZkOsTreeReader {
+        LazyZkOsTreeReader(self.tree_reader.subscribe())
+    }
+
+    /// Returns a health check for this task.
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
+    async fn create_db(&self) -> anyhow::Result<RocksDBWrapper> {
+        let config = self.config.clone();
+        let db = tokio::task::spawn_blocking(move || {
+            let mut db = RocksDBWrapper::from(open_rocksdb::<MerkleTreeColumnFamily>(&config)?);
+            db.set_multi_get_chunk_size(config.multi_get_chunk_size);
+            anyhow::Ok(db)
+        })
+        .await
+        .context("panicked creating ZK OS Merkle tree RocksDB")??;
+        Ok(db)
+    }
+
+    /// Runs this task until a stop signal is received.
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        self.health_updater
+            .update(ZkOsTreeHealth::Initialization.into());
+        let db = self.create_db().await.with_context(|| {
+            format!(
+                "failed opening ZK OS Merkle tree RocksDB at `{}`",
+                self.config.db_path
+            )
+        })?;
+        let tree = MerkleTree::new(db.clone())?;
+        let Some(mut tree) = self.ensure_ready(tree, &stop_receiver).await? else {
+            return Ok(()); // recovery was interrupted by a stop signal
+        };
+        self.tree_reader
+            .send_replace(Some(ZkOsTreeReader::new(db.clone())?));
+
+        // Like for the main tree, the pruner is not allocated a managed task because it is blocking;
+        // it is stopped by dropping its handle.
+        let (pruner, pruner_handle) = MerkleTreePruner::new(db);
+        let pruner_task_handle = tokio::task::spawn_blocking(|| pruner.run());
+
+        let info = ZkOsTreeReader::info_sync(&tree)?;
+        tracing::info!(
+            "ZK OS Merkle tree is initialized and ready to process L1 batches: {info:?}"
+        );
+        let mut next_l1_batch = info.next_l1_batch_number;
+        self.health_updater
+            .update(ZkOsTreeHealth::MainLoop(info).into());
+
+        while !*stop_receiver.borrow_and_update() {
+            let mut storage = self.pool.connection_tagged("metadata_calculator").await?;
+            Self::ensure_not_pruned(&mut storage, next_l1_batch).await?;
+            if !Self::update_pruning_target(&mut storage, &pruner_handle).await? {
+                tracing::error!("ZK OS Merkle tree pruning thread unexpectedly stopped");
+                return pruner_task_handle
+                    .await
+                    .context("ZK OS Merkle tree pruning thread panicked")?;
+            }
+            let last_sealed_l1_batch = storage.blocks_dal().get_sealed_l1_batch_number().await?;
+            let last_l1_batch_to_process = last_sealed_l1_batch.map(|number| {
+                number.min(next_l1_batch + self.config.max_l1_batches_per_iter as u32 - 1)
+            });
+
+            let mut made_progress = false;
+            if let Some(last_l1_batch_to_process) = last_l1_batch_to_process {
+                while next_l1_batch <= last_l1_batch_to_process {
+                    let (new_tree, info) =
+                        Self::process_l1_batch(&mut storage, tree, next_l1_batch).await?;
+                    tree = new_tree;
+                    next_l1_batch += 1;
+                    made_progress = true;
+                    self.health_updater
+                        .update(ZkOsTreeHealth::MainLoop(info).into());
+                }
+            }
+            drop(storage);
+
+            if !made_progress {
+                tokio::time::timeout(self.config.delay_interval, stop_receiver.changed())
+                    .await
+                    .ok();
+            }
+        }
+        tracing::info!("Stop signal received, ZK OS Merkle tree is shutting down");
+        drop(pruner_handle);
+        pruner_task_handle
+            .await
+            .context("ZK OS Merkle tree pruning thread panicked")?
+    }
+
+    /// Sets the target retained tree version for the pruner based on the hard-pruned L1 batch in Postgres.
+    /// Returns `false` if the pruner has stopped.
+    async fn update_pruning_target(
+        storage: &mut Connection<'_, Core>,
+        pruner_handle: &MerkleTreePrunerHandle,
+    ) -> anyhow::Result<bool> {
+        let pruning_info = storage.pruning_dal().get_pruning_info().await?;
+        let Some(pruned) = pruning_info.last_hard_pruned else {
+            return Ok(true);
+        };
+        let target_retained_version = u64::from(pruned.l1_batch.0) + 1;
+        let Ok(prev_target_version) =
+            pruner_handle.set_target_retained_version(target_retained_version)
+        else {
+            return Ok(false);
+        };
+        if prev_target_version != target_retained_version {
+            tracing::info!(
+                "Set target retained ZK OS tree version from {prev_target_version} to {target_retained_version}"
+            );
+        }
+        Ok(true)
+    }
+
+    /// Ensures that the tree is ready for the normal operation: recovers it from a Postgres snapshot if necessary,
+    /// and checks that it's not ahead of Postgres.
+    async fn ensure_ready(
+        &self,
+        mut tree: ZkOsTree,
+        stop_receiver: &watch::Receiver<bool>,
+    ) -> anyhow::Result<Option<ZkOsTree>> {
+        let Some(latest_version) = tree.latest_version()? else {
+            let Some(params) = InitParameters::new(&self.pool, &self.config.recovery).await? else {
+                // The tree will be built from the genesis L1 batch.
+                return Ok(Some(tree));
+            };
+            return self.recover(tree, params, stop_receiver).await;
+        };
+
+        let mut storage = self.pool.connection_tagged("metadata_calculator").await?;
+        let last_sealed_l1_batch = storage.blocks_dal().get_sealed_l1_batch_number().await?;
+        drop(storage);
+        let Some(last_sealed_l1_batch) = last_sealed_l1_batch else {
+            // Postgres may contain no L1 batches after snapshot recovery; in this case, there's nothing to compare the tree with.
+            return Ok(Some(tree));
+        };
+        let last_sealed_l1_batch = u64::from(last_sealed_l1_batch.0);
+
+        if latest_version > last_sealed_l1_batch {
+            tracing::warn!(
+                "ZK OS Merkle tree is ahead of Postgres (latest tree version: {latest_version}, \
+                 latest sealed L1 batch: {last_sealed_l1_batch}); truncating the tree"
+            );
+            tree = tokio::task::spawn_blocking(move || {
+                tree.truncate_recent_versions(last_sealed_l1_batch + 1)?;
+                anyhow::Ok(tree)
+            })
+            .await
+            .context("panicked truncating ZK OS Merkle tree")??;
+        }
+        Ok(Some(tree))
+    }
+
+    /// Recovers the tree from the Postgres snapshot. Unlike the main tree recovery, this one is not resumable;
+    /// all snapshot entries are loaded into RAM and are then inserted into the tree in a single batch.
+    async fn recover(
+        &self,
+        mut tree: ZkOsTree,
+        params: InitParameters,
+        stop_receiver: &watch::Receiver<bool>,
+    ) -> anyhow::Result<Option<ZkOsTree>> {
+        let started_at = Instant::now();
+        let l1_batch = params.l1_batch;
+        self.health_updater
+            .update(ZkOsTreeHealth::Recovery { l1_batch }.into());
+        let chunk_count = params.chunk_count();
+        tracing::info!("Recovering ZK OS Merkle tree from Postgres snapshot in {chunk_count} chunks: {params:?}");
+
+        let mut storage = self.pool.connection_tagged("metadata_calculator").await?;
+        let mut all_entries = vec![];
+        for chunk_id in 0..chunk_count {
+            if *stop_receiver.borrow() {
+                return Ok(None);
+            }
+            let key_chunk = uniform_hashed_keys_chunk(chunk_id, chunk_count);
+            let entries = storage
+                .storage_logs_dal()
+                .get_tree_entries_for_l2_block(params.l2_block, key_chunk)
+                .await?;
+            tracing::debug!(
+                "Loaded {} entries for chunk {}/{chunk_count}",
+                entries.len(),
+                chunk_id + 1
+            );
+            all_entries.extend(entries);
+        }
+        Self::ensure_not_pruned(&mut storage, l1_batch + 1).await?;
+        drop(storage);
+
+        all_entries.sort_unstable_by_key(|entry| entry.leaf_index);
+        // Sanity check: enumeration indices must form a contiguous range starting from 1; otherwise,
+        // leaf indices in the tree won't correspond to enumeration indices.
+        for (i, entry) in all_entries.iter().enumerate() {
+            anyhow::ensure!(
+                entry.leaf_index == i as u64 + 1,
+                "Snapshot in Postgres is corrupted: unexpected enumeration index for entry {entry:?}, expected {}",
+                i + 1
+            );
+        }
+        let entries: Vec<_> = all_entries
+            .into_iter()
+            .map(|entry| TreeEntry {
+                key: entry.key,
+                value: entry.value,
+            })
+            .collect();
+
+        let output = tokio::task::spawn_blocking(move || {
+            let output = tree.recover(l1_batch.0.into(), &entries)?;
+            anyhow::Ok((tree, output))
+        })
+        .await
+        .context("panicked recovering ZK OS Merkle tree")??;
+        let (tree, output) = output;
+
+        let elapsed = started_at.elapsed();
+        ZK_OS_METRICS.recovery_latency.observe(elapsed);
+        tracing::info!(
+            "Recovered ZK OS Merkle tree at L1 batch #{l1_batch} in {elapsed:?}: {output:?}"
+        );
+        Ok(Some(tree))
+    }
+
+    /// Checks whether the requested L1 batch was pruned. Right now, the tree cannot recover from this situation,
+    /// so we exit with an error if this happens.
+    async fn ensure_not_pruned(
+        storage: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<()> {
+        let pruning_info = storage.pruning_dal().get_pruning_info().await?;
+        anyhow::ensure!(
+            pruning_info.last_soft_pruned.map_or(true, |info| info.l1_batch < l1_batch_number),
+            "L1 batch #{l1_batch_number}, next to be processed by the ZK OS tree, is pruned; the tree cannot continue operating. \
+             To recover the tree, drop its RocksDB directory and restart the node"
+        );
+        Ok(())
+    }
+
+    async fn process_l1_batch(
+        storage: &mut Connection<'_, Core>,
+        mut tree: ZkOsTree,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<(ZkOsTree, ZkOsTreeInfo)> {
+        let started_at = Instant::now();
+        let batch = L1BatchWithLogs::new(storage, l1_batch_number, MerkleTreeMode::Lightweight)
+            .await
+            .with_context(|| format!("failed loading tree input for L1 batch #{l1_batch_number}"))?
+            .with_context(|| format!("L1 batch #{l1_batch_number} is missing in Postgres"))?;
+
+        // Sorting by the enumeration index ensures that inserted keys get leaf indices aligned with enumeration indices.
+        let writes: Vec<_> = batch
+            .storage_logs
+            .into_iter()
+            .filter_map(|instruction| match instruction {
+                TreeInstruction::Write(entry) => Some(entry),
+                TreeInstruction::Read(_) => None,
+            })
+            .sorted_unstable_by_key(|entry| entry.leaf_index)
+            .collect();
+        let max_leaf_index = writes.last().map(|entry| entry.leaf_index);
+        let entries: Vec<_> = writes
+            .into_iter()
+            .map(|entry| TreeEntry {
+                key: hashed_key_to_h256(entry.key),
+                value: entry.value,
+            })
+            .collect();
+
+        let (tree, prev_leaf_count, output) = tokio::task::spawn_blocking(move || {
+            // An empty tree is initialized with 2 guard leaves on the first update.
+            let prev_leaf_count = match tree.latest_version()? {
+                Some(version) => tree
+                    .root_info(version)?
+                    .map_or(2, |output| output.leaf_count),
+                None => 2,
+            };
+            let output = tree.extend(&entries)?;
+            anyhow::Ok((tree, prev_leaf_count, output))
+        })
+        .await
+        .with_context(|| {
+            format!("ZK OS Merkle tree panicked when processing L1 batch #{l1_batch_number}")
+        })??;
+
+        let expected_leaf_count =
+            max_leaf_index.map_or(prev_leaf_count, |idx| prev_leaf_count.max(idx + 2));
+        anyhow::ensure!(
+            expected_leaf_count == output.leaf_count,
+            "Leaf count in ZK OS tree after processing L1 batch #{l1_batch_number} ({}) differs from the expected value \
+             ({expected_leaf_count}); the tree is inconsistent with enumeration indices in Postgres",
+            output.leaf_count
+        );
+
+        let elapsed = started_at.elapsed();
+        ZK_OS_METRICS.process_l1_batch_latency.observe(elapsed);
+        ZK_OS_METRICS.l1_batch_number.set(l1_batch_number.0.into());
+        ZK_OS_METRICS.leaf_count.set(output.leaf_count);
+        tracing::debug!(
+            "Processed L1 batch #{l1_batch_number} in ZK OS Merkle tree in {elapsed:?}: {output:?}"
+        );
+
+        let info = ZkOsTreeInfo {
+            root_hash: Some(output.root_hash),
+            next_l1_batch_number: l1_batch_number + 1,
+            leaf_count: output.leaf_count,
+        };
+        Ok((tree, info))
+    }
+}
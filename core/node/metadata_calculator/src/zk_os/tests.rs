@@ -0,0 +1,219 @@
+//! Tests for the ZK OS Merkle tree task.
+
+use tempfile::TempDir;
+use zk_os_merkle_tree::PatchSet;
+use zksync_health_check::CheckHealth;
+use zksync_node_test_utils::prepare_recovery_snapshot;
+use zksync_types::{L2BlockNumber, StorageLog};
+
+use super::*;
+use crate::tests::{
+    extend_db_state_from_l1_batch, gen_storage_logs, reset_db_state, setup_calculator,
+};
+
+/// Computes the expected root hash of the tree containing `logs` (the logs must be ordered by their enumeration indices).
+fn expected_root_hash(logs: &[StorageLog]) -> H256 {
+    let entries: Vec<_> = logs
+        .iter()
+        .map(|log| TreeEntry {
+            key: log.key.hashed_key(),
+            value: log.value,
+        })
+        .collect();
+    let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+    tree.extend(&entries).unwrap().root_hash
+}
+
+async fn wait_for_l1_batch(
+    health_check: &mut ReactiveHealthCheck,
+    next_l1_batch: L1BatchNumber,
+) -> ZkOsTreeInfo {
+    let health = health_check
+        .wait_for(|health| {
+            matches!(health.status(), HealthStatus::Ready)
+                && health.details().unwrap()["next_l1_batch_number"] == next_l1_batch.0
+        })
+        .await;
+    serde_json::from_value(health.details().unwrap().clone()).unwrap()
+}
+
+#[tokio::test]
+async fn building_zk_os_tree_from_genesis() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let (calculator, _) = setup_calculator(&temp_dir.path().join("main"), pool.clone(), true).await;
+    reset_db_state(&pool, 5).await;
+
+    let task =
+        calculator.zk_os_tree_task(temp_dir.path().join("zk_os").to_str().unwrap().to_owned());
+    let mut health_check = task.health_check();
+    assert_eq!(health_check.name(), "zk_os_tree");
+    let tree_reader = task.tree_reader();
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let task_handle = tokio::spawn(task.run(stop_receiver));
+
+    let info = wait_for_l1_batch(&mut health_check, L1BatchNumber(6)).await;
+
+    // Compare the incrementally built tree with the tree built from all entries at once.
+    let mut storage = pool.connection().await.unwrap();
+    let last_l2_block = storage
+        .blocks_dal()
+        .get_sealed_l2_block_number()
+        .await
+        .unwrap()
+        .unwrap();
+    let mut all_entries = storage
+        .storage_logs_dal()
+        .get_tree_entries_for_l2_block(last_l2_block, H256::zero()..=H256::repeat_byte(0xff))
+        .await
+        .unwrap();
+    all_entries.sort_unstable_by_key(|entry| entry.leaf_index);
+    let all_entries: Vec<_> = all_entries
+        .into_iter()
+        .map(|entry| TreeEntry {
+            key: entry.key,
+            value: entry.value,
+        })
+        .collect();
+    let mut expected_tree = MerkleTree::new(PatchSet::default()).unwrap();
+    let expected_output = expected_tree.extend(&all_entries).unwrap();
+    assert_eq!(info.root_hash, Some(expected_output.root_hash));
+    assert_eq!(info.leaf_count, expected_output.leaf_count);
+
+    let tree_reader = tree_reader.wait().await.unwrap();
+    let reader_info = tree_reader.clone().info().await.unwrap();
+    assert_eq!(reader_info.root_hash, info.root_hash);
+    assert_eq!(reader_info.next_l1_batch_number, L1BatchNumber(6));
+
+    let keys: Vec<_> = all_entries.iter().map(|entry| entry.key).collect();
+    let (output, proof) = tree_reader
+        .clone()
+        .prove(L1BatchNumber(5), keys.clone())
+        .await
+        .unwrap()
+        .expect("no tree version");
+    let tree_view = proof
+        .verify_reads(&zk_os_merkle_tree::Blake2Hasher, 64, output, &keys)
+        .unwrap();
+    assert_eq!(tree_view.root_hash, expected_output.root_hash);
+    for entry in &all_entries {
+        assert_eq!(tree_view.read_entries[&entry.key], Some(entry.value));
+    }
+
+    let missing_proof = tree_reader.prove(L1BatchNumber(10), keys).await.unwrap();
+    assert!(missing_proof.is_none());
+
+    stop_sender.send_replace(true);
+    task_handle.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn recovering_zk_os_tree_from_snapshot() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let snapshot_logs = gen_storage_logs(100..300, 1).pop().unwrap();
+    let mut storage = pool.connection().await.unwrap();
+    let snapshot_recovery = prepare_recovery_snapshot(
+        &mut storage,
+        L1BatchNumber(23),
+        L2BlockNumber(42),
+        &snapshot_logs,
+    )
+    .await;
+
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let (calculator, _) = setup_calculator(&temp_dir.path().join("main"), pool.clone(), true).await;
+    let task =
+        calculator.zk_os_tree_task(temp_dir.path().join("zk_os").to_str().unwrap().to_owned());
+    let mut health_check = task.health_check();
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let task_handle = tokio::spawn(task.run(stop_receiver));
+
+    let info = wait_for_l1_batch(&mut health_check, snapshot_recovery.l1_batch_number + 1).await;
+    assert_eq!(info.root_hash, Some(expected_root_hash(&snapshot_logs)));
+    assert_eq!(info.leaf_count, snapshot_logs.len() as u64 + 2);
+
+    // Emulate state keeper adding a new L1 batch to Postgres.
+    let mut new_logs = gen_storage_logs(500..600, 1).pop().unwrap();
+    // Logs must be sorted by `log.key` to match their enum index assignment
+    new_logs.sort_unstable_by_key(|log| log.key);
+    extend_db_state_from_l1_batch(
+        &mut storage,
+        snapshot_recovery.l1_batch_number + 1,
+        snapshot_recovery.l2_block_number + 1,
+        [new_logs.clone()],
+    )
+    .await;
+
+    let info = wait_for_l1_batch(&mut health_check, snapshot_recovery.l1_batch_number + 2).await;
+    let all_logs: Vec<_> = snapshot_logs.into_iter().chain(new_logs).collect();
+    assert_eq!(info.root_hash, Some(expected_root_hash(&all_logs)));
+
+    stop_sender.send_replace(true);
+    task_handle.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn zk_os_tree_is_truncated_if_ahead_of_postgres() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let (calculator, _) = setup_calculator(&temp_dir.path().join("main"), pool.clone(), true).await;
+    reset_db_state(&pool, 5).await;
+
+    let zk_os_path = temp_dir.path().join("zk_os").to_str().unwrap().to_owned();
+    let task = calculator.zk_os_tree_task(zk_os_path.clone());
+    let mut health_check = task.health_check();
+    let tree_reader = task.tree_reader();
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let task_handle = tokio::spawn(task.run(stop_receiver));
+    wait_for_l1_batch(&mut health_check, L1BatchNumber(6)).await;
+    let expected_output = tree_reader
+        .wait()
+        .await
+        .unwrap()
+        .root_info(L1BatchNumber(3))
+        .await
+        .unwrap()
+        .expect("no tree version");
+    stop_sender.send_replace(true);
+    task_handle.await.unwrap().unwrap();
+
+    // Emulate reverting the last 2 L1 batches in Postgres.
+    let mut storage = pool.connection().await.unwrap();
+    let (_, last_l2_block) = storage
+        .blocks_dal()
+        .get_l2_block_range_of_l1_batch(L1BatchNumber(3))
+        .await
+        .unwrap()
+        .unwrap();
+    storage
+        .storage_logs_dal()
+        .roll_back_storage_logs(last_l2_block)
+        .await
+        .unwrap();
+    storage
+        .blocks_dal()
+        .delete_l2_blocks(last_l2_block)
+        .await
+        .unwrap();
+    storage
+        .blocks_dal()
+        .delete_l1_batches(L1BatchNumber(3))
+        .await
+        .unwrap();
+    storage
+        .blocks_dal()
+        .delete_initial_writes(L1BatchNumber(3))
+        .await
+        .unwrap();
+
+    let task = calculator.zk_os_tree_task(zk_os_path);
+    let mut health_check = task.health_check();
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let task_handle = tokio::spawn(task.run(stop_receiver));
+    let info = wait_for_l1_batch(&mut health_check, L1BatchNumber(4)).await;
+    assert_eq!(info.root_hash, Some(expected_output.root_hash));
+    assert_eq!(info.leaf_count, expected_output.leaf_count);
+
+    stop_sender.send_replace(true);
+    task_handle.await.unwrap().unwrap();
+}
@@ -1,5 +1,6 @@
 use zksync_config::ContractVerifierConfig;
 use zksync_dal::{ConnectionPool, Core};
+use zksync_types::L2ChainId;
 
 use crate::{
     implementations::resources::pools::{MasterPool, PoolResource, ReplicaPool},
@@ -13,7 +14,19 @@ use crate::{
 ///
 /// Responsible for initialization of the contract verification server.
 #[derive(Debug)]
-pub struct ContractVerificationApiLayer(pub ContractVerifierConfig);
+pub struct ContractVerificationApiLayer {
+    config: ContractVerifierConfig,
+    l2_chain_id: L2ChainId,
+}
+
+impl ContractVerificationApiLayer {
+    pub fn new(config: ContractVerifierConfig, l2_chain_id: L2ChainId) -> Self {
+        Self {
+            config,
+            l2_chain_id,
+        }
+    }
+}
 
 #[derive(Debug, FromContext)]
 #[context(crate = crate)]
@@ -44,7 +57,8 @@ impl WiringLayer for ContractVerificationApiLayer {
         let contract_verification_api_task = ContractVerificationApiTask {
             master_pool,
             replica_pool,
-            config: self.0,
+            config: self.config,
+            l2_chain_id: self.l2_chain_id,
         };
         Ok(Output {
             contract_verification_api_task,
@@ -57,6 +71,7 @@ pub struct ContractVerificationApiTask {
     master_pool: ConnectionPool<Core>,
     replica_pool: ConnectionPool<Core>,
     config: ContractVerifierConfig,
+    l2_chain_id: L2ChainId,
 }
 
 #[async_trait::async_trait]
@@ -69,6 +84,7 @@ impl Task for ContractVerificationApiTask {
         zksync_contract_verification_server::start_server(
             self.master_pool,
             self.replica_pool,
+            self.l2_chain_id,
             self.config.bind_addr(),
             stop_receiver.0,
         )
@@ -66,6 +66,12 @@ pub struct GetBlobFromDB {
     pool: ConnectionPool<Core>,
 }
 
+impl GetBlobFromDB {
+    pub fn new(pool: ConnectionPool<Core>) -> Self {
+        Self { pool }
+    }
+}
+
 #[async_trait::async_trait]
 impl GetBlobData for GetBlobFromDB {
     async fn get_blob_data(&self, input: &str) -> anyhow::Result<Option<Vec<u8>>> {
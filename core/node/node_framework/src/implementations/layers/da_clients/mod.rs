@@ -3,3 +3,4 @@ pub mod celestia;
 pub mod eigen;
 pub mod no_da;
 pub mod object_store;
+pub mod secondary;
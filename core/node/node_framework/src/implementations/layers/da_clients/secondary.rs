@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use zksync_config::configs::{da_client::DAClientConfig, secrets::DataAvailabilitySecrets};
+use zksync_da_client::DataAvailabilityClient;
+use zksync_da_clients::{
+    avail::AvailClient, celestia::CelestiaClient, eigen::EigenClient, no_da::NoDAClient,
+    object_store::ObjectStoreDAClient,
+};
+
+use super::eigen::GetBlobFromDB;
+use crate::{
+    implementations::resources::{
+        da_client::SecondaryDAClientResource,
+        pools::{MasterPool, PoolResource},
+    },
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Wiring layer for the client of the secondary DA layer used by the DA dispatcher.
+#[derive(Debug)]
+pub struct SecondaryDAClientWiringLayer {
+    config: DAClientConfig,
+    secrets: Option<DataAvailabilitySecrets>,
+}
+
+impl SecondaryDAClientWiringLayer {
+    pub fn new(config: DAClientConfig, secrets: Option<DataAvailabilitySecrets>) -> Self {
+        Self { config, secrets }
+    }
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    /// Only used by the Eigen client.
+    pub master_pool: PoolResource<MasterPool>,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    pub client: SecondaryDAClientResource,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for SecondaryDAClientWiringLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "secondary_da_client_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let client: Box<dyn DataAvailabilityClient> = match (self.config, self.secrets) {
+            (DAClientConfig::Avail(config), Some(DataAvailabilitySecrets::Avail(secrets))) => {
+                Box::new(AvailClient::new(config, secrets).await?)
+            }
+            (
+                DAClientConfig::Celestia(config),
+                Some(DataAvailabilitySecrets::Celestia(secrets)),
+            ) => Box::new(CelestiaClient::new(config, secrets).await?),
+            (DAClientConfig::Eigen(config), Some(DataAvailabilitySecrets::Eigen(secrets))) => {
+                let get_blob_from_db = GetBlobFromDB::new(input.master_pool.get().await?);
+                Box::new(EigenClient::new(config, secrets, Arc::new(get_blob_from_db)).await?)
+            }
+            (DAClientConfig::ObjectStore(config), _) => {
+                Box::new(ObjectStoreDAClient::new(config).await?)
+            }
+            (DAClientConfig::NoDA, _) => Box::new(NoDAClient),
+            _ => {
+                return Err(WiringError::Configuration(
+                    "invalid pair of secondary da_client and da_secrets".to_owned(),
+                ))
+            }
+        };
+
+        Ok(Output {
+            client: SecondaryDAClientResource(client),
+        })
+    }
+}
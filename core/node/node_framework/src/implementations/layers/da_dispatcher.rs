@@ -6,9 +6,10 @@ use zksync_da_dispatcher::DataAvailabilityDispatcher;
 
 use crate::{
     implementations::resources::{
-        da_client::DAClientResource,
+        da_client::{DAClientResource, SecondaryDAClientResource},
         eth_interface::EthInterfaceResource,
         pools::{MasterPool, PoolResource},
+        price_api_client::PriceAPIClientResource,
     },
     service::StopReceiver,
     task::{Task, TaskId},
@@ -30,6 +31,9 @@ pub struct Input {
     pub master_pool: PoolResource<MasterPool>,
     pub eth_client: EthInterfaceResource,
     pub da_client: DAClientResource,
+    pub secondary_da_client: Option<SecondaryDAClientResource>,
+    /// Used to convert DA fees to the base token. If not provided, the fees are only recorded in native units.
+    pub price_api_client: Option<PriceAPIClientResource>,
 }
 
 #[derive(Debug, IntoContext)]
@@ -64,25 +68,42 @@ impl WiringLayer for DataAvailabilityDispatcherLayer {
 
     async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
         let da_client = input.da_client.0;
-        if let Some(limit) = da_client.blob_size_limit() {
-            if self.state_keeper_config.max_pubdata_per_batch > limit as u64 {
-                return Err(WiringError::Configuration(format!(
-                    "Max pubdata per batch is greater than the blob size limit: {} > {}",
-                    self.state_keeper_config.max_pubdata_per_batch, limit
-                )));
+        let secondary_da_client = input.secondary_da_client.map(|resource| resource.0);
+        let all_clients = [Some(&da_client), secondary_da_client.as_ref()];
+        for client in all_clients.into_iter().flatten() {
+            if let Some(limit) = client.blob_size_limit() {
+                if self.state_keeper_config.max_pubdata_per_batch > limit as u64 {
+                    return Err(WiringError::Configuration(format!(
+                        "Max pubdata per batch is greater than the blob size limit: {} > {}",
+                        self.state_keeper_config.max_pubdata_per_batch, limit
+                    )));
+                }
             }
         }
 
+        if self.da_config.fee_token_address.is_some() && input.price_api_client.is_none() {
+            tracing::warn!(
+                "DA fee token is configured, but the external price API client is not; DA fees will not be converted \
+                 to the base token"
+            );
+        }
+
         // A pool with size 2 is used here because there are 2 functions within a task that execute in parallel
         let master_pool = input.master_pool.get_custom(2).await?;
 
-        let da_dispatcher_task = DataAvailabilityDispatcher::new(
+        let mut da_dispatcher_task = DataAvailabilityDispatcher::new(
             master_pool,
             self.da_config,
             da_client,
             self.contracts_config,
             input.eth_client.0,
         );
+        if let Some(client) = secondary_da_client {
+            da_dispatcher_task = da_dispatcher_task.with_secondary_client(client);
+        }
+        if let Some(client) = input.price_api_client {
+            da_dispatcher_task = da_dispatcher_task.with_price_api_client(client.0);
+        }
 
         Ok(Output { da_dispatcher_task })
     }
@@ -1,3 +1,4 @@
+use zksync_da_client::types::ClientType;
 use zksync_node_sync::data_availability_fetcher::DataAvailabilityFetcher;
 
 use crate::{
@@ -15,7 +16,15 @@ use crate::{
 
 /// Wiring layer for [`DataAvailabilityFetcher`].
 #[derive(Debug)]
-pub struct DataAvailabilityFetcherLayer;
+pub struct DataAvailabilityFetcherLayer {
+    verify_blobs: bool,
+}
+
+impl DataAvailabilityFetcherLayer {
+    pub fn new(verify_blobs: bool) -> Self {
+        Self { verify_blobs }
+    }
+}
 
 #[derive(Debug, FromContext)]
 #[context(crate = crate)]
@@ -47,9 +56,18 @@ impl WiringLayer for DataAvailabilityFetcherLayer {
         let pool = input.master_pool.get().await?;
         let MainNodeClientResource(client) = input.main_node_client;
         let DAClientResource(da_client) = input.da_client;
+        if self.verify_blobs && matches!(da_client.client_type(), ClientType::Celestia) {
+            // The Celestia client cannot retrieve blobs, so the fetcher would fail on the first L1 batch.
+            return Err(WiringError::Configuration(
+                "DA blob verification is not supported for the Celestia client".to_owned(),
+            ));
+        }
 
         tracing::info!("Running data availability fetcher.");
-        let task = DataAvailabilityFetcher::new(client, pool, da_client);
+        let mut task = DataAvailabilityFetcher::new(client, pool, da_client);
+        if self.verify_blobs {
+            task = task.with_blob_verification();
+        }
 
         // Insert healthcheck
         input
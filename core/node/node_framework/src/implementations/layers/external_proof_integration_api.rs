@@ -1,11 +1,14 @@
+use std::path::Path;
+
+use anyhow::Context as _;
 use zksync_config::configs::external_proof_integration_api::ExternalProofIntegrationApiConfig;
-use zksync_external_proof_integration_api::{Api, Processor};
+use zksync_external_proof_integration_api::{Api, Marketplace, Processor, ProofVerifier};
 use zksync_types::commitment::L1BatchCommitmentMode;
 
 use crate::{
     implementations::resources::{
         object_store::ObjectStoreResource,
-        pools::{PoolResource, ReplicaPool},
+        pools::{MasterPool, PoolResource, ReplicaPool},
     },
     service::StopReceiver,
     task::{Task, TaskId},
@@ -23,6 +26,7 @@ pub struct ExternalProofIntegrationApiLayer {
 #[derive(Debug, FromContext)]
 #[context(crate = crate)]
 pub struct Input {
+    pub master_pool: PoolResource<MasterPool>,
     pub replica_pool: PoolResource<ReplicaPool>,
     pub object_store: ObjectStoreResource,
 }
@@ -59,11 +63,19 @@ impl WiringLayer for ExternalProofIntegrationApiLayer {
         let replica_pool = input.replica_pool.get().await.unwrap();
         let blob_store = input.object_store.0;
 
-        let processor = Processor::new(blob_store, replica_pool, self.commitment_mode);
-        let task = Api::new(
-            processor,
-            self.external_proof_integration_api_config.http_port,
-        );
+        let config = &self.external_proof_integration_api_config;
+        let mut processor = Processor::new(blob_store, replica_pool, self.commitment_mode);
+        if let Some(keys_path) = &config.verification_keys_path {
+            let verifier = ProofVerifier::load(Path::new(keys_path))
+                .context("failed loading verification keys for proof marketplace")?;
+            let master_pool = input.master_pool.get().await?;
+            processor = processor.with_marketplace(Marketplace::new(
+                master_pool,
+                verifier,
+                config.lease_timeout(),
+            ));
+        }
+        let task = Api::new(processor, config.http_port);
 
         Ok(Output { task })
     }
@@ -1,7 +1,9 @@
 use std::sync::Arc;
 
 use zksync_config::configs::chain::{FeeModelVersion, StateKeeperConfig};
-use zksync_node_fee_model::{ApiFeeInputProvider, MainNodeFeeInputProvider};
+use zksync_node_fee_model::{
+    da_pubdata_price::DBDAPubdataPriceProvider, ApiFeeInputProvider, MainNodeFeeInputProvider,
+};
 use zksync_types::fee_model::{FeeModelConfig, FeeModelConfigV1, FeeModelConfigV2};
 
 use crate::{
@@ -12,6 +14,8 @@ use crate::{
         l1_tx_params::TxParamsResource,
         pools::{PoolResource, ReplicaPool},
     },
+    service::StopReceiver,
+    task::{Task, TaskId},
     wiring_layer::{WiringError, WiringLayer},
     FromContext, IntoContext,
 };
@@ -21,6 +25,7 @@ use crate::{
 #[derive(Debug)]
 pub struct L1GasLayer {
     fee_model_config: FeeModelConfig,
+    da_pubdata_price_feedback: bool,
 }
 
 #[derive(Debug, FromContext)]
@@ -39,15 +44,25 @@ pub struct Output {
     pub sequencer_fee_input: SequencerFeeInputResource,
     pub api_fee_input: ApiFeeInputResource,
     pub l1_tx_params: TxParamsResource,
+    #[context(task)]
+    pub da_pubdata_price_provider_task: Option<DBDAPubdataPriceProvider>,
 }
 
 impl L1GasLayer {
     pub fn new(state_keeper_config: &StateKeeperConfig) -> Self {
         Self {
             fee_model_config: Self::map_config(state_keeper_config),
+            da_pubdata_price_feedback: false,
         }
     }
 
+    /// Makes the fee model take the fees paid to the DA layer (as recorded by the DA dispatcher) into account
+    /// when computing the pubdata price.
+    pub fn with_da_pubdata_price_feedback(mut self) -> Self {
+        self.da_pubdata_price_feedback = true;
+        self
+    }
+
     fn map_config(state_keeper_config: &StateKeeperConfig) -> FeeModelConfig {
         match state_keeper_config.fee_model_version {
             FeeModelVersion::V1 => FeeModelConfig::V1(FeeModelConfigV1 {
@@ -76,14 +91,23 @@ impl WiringLayer for L1GasLayer {
 
     async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
         let ratio_provider = input.base_token_ratio_provider;
+        let replica_pool = input.replica_pool.get().await?;
 
-        let main_fee_input_provider = Arc::new(MainNodeFeeInputProvider::new(
+        let mut main_fee_input_provider = MainNodeFeeInputProvider::new(
             input.gas_adjuster.0.clone(),
             ratio_provider.0,
             self.fee_model_config,
-        ));
+        );
+        let da_pubdata_price_provider = self
+            .da_pubdata_price_feedback
+            .then(|| DBDAPubdataPriceProvider::new(replica_pool.clone()));
+        if let Some(provider) = &da_pubdata_price_provider {
+            // Cloning the provider preserves the internal state.
+            main_fee_input_provider =
+                main_fee_input_provider.with_da_pubdata_price_provider(Arc::new(provider.clone()));
+        }
+        let main_fee_input_provider = Arc::new(main_fee_input_provider);
 
-        let replica_pool = input.replica_pool.get().await?;
         let api_fee_input_provider = Arc::new(ApiFeeInputProvider::new(
             main_fee_input_provider.clone(),
             replica_pool,
@@ -93,6 +117,18 @@ impl WiringLayer for L1GasLayer {
             sequencer_fee_input: main_fee_input_provider.into(),
             api_fee_input: api_fee_input_provider.into(),
             l1_tx_params: input.gas_adjuster.0.into(),
+            da_pubdata_price_provider_task: da_pubdata_price_provider,
         })
     }
 }
+
+#[async_trait::async_trait]
+impl Task for DBDAPubdataPriceProvider {
+    fn id(&self) -> TaskId {
+        "da_pubdata_price_provider".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
@@ -7,8 +7,9 @@ use std::{
 use anyhow::Context as _;
 use zksync_config::configs::{api::MerkleTreeApiConfig, database::MerkleTreeMode};
 use zksync_metadata_calculator::{
-    LazyAsyncTreeReader, MerkleTreePruningTask, MerkleTreeReaderConfig, MetadataCalculator,
-    MetadataCalculatorConfig, StaleKeysRepairTask, TreeReaderTask,
+    LazyAsyncTreeReader, LazyZkOsTreeReader, MerkleTreePruningTask, MerkleTreeReaderConfig,
+    MetadataCalculator, MetadataCalculatorConfig, StaleKeysRepairTask, TreeReaderTask,
+    ZkOsTreeTask,
 };
 use zksync_storage::RocksDB;
 
@@ -32,6 +33,7 @@ pub struct MetadataCalculatorLayer {
     tree_api_config: Option<MerkleTreeApiConfig>,
     pruning_config: Option<Duration>,
     stale_keys_repair_enabled: bool,
+    zk_os_tree_path: Option<String>,
 }
 
 #[derive(Debug, FromContext)]
@@ -60,6 +62,9 @@ pub struct Output {
     /// Only provided if enabled in the config.
     #[context(task)]
     pub stale_keys_repair_task: Option<StaleKeysRepairTask>,
+    /// Only provided if enabled in the config.
+    #[context(task)]
+    pub zk_os_tree_task: Option<ZkOsTreeTask>,
     pub rocksdb_shutdown_hook: ShutdownHook,
 }
 
@@ -70,6 +75,7 @@ impl MetadataCalculatorLayer {
             tree_api_config: None,
             pruning_config: None,
             stale_keys_repair_enabled: false,
+            zk_os_tree_path: None,
         }
     }
 
@@ -87,6 +93,12 @@ impl MetadataCalculatorLayer {
         self.stale_keys_repair_enabled = true;
         self
     }
+
+    /// Enables building the experimental ZK OS Merkle tree at the specified RocksDB path in parallel with the main tree.
+    pub fn with_zk_os_tree(mut self, db_path: String) -> Self {
+        self.zk_os_tree_path = Some(db_path);
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -129,12 +141,24 @@ impl WiringLayer for MetadataCalculatorLayer {
             .insert_custom_component(Arc::new(metadata_calculator.tree_health_check()))
             .map_err(WiringError::internal)?;
 
+        let zk_os_tree_task = self
+            .zk_os_tree_path
+            .map(|db_path| -> Result<ZkOsTreeTask, WiringError> {
+                let task = metadata_calculator.zk_os_tree_task(db_path);
+                app_health
+                    .insert_component(task.health_check())
+                    .map_err(|err| WiringError::Internal(err.into()))?;
+                Ok(task)
+            })
+            .transpose()?;
+
         let tree_api_task = self.tree_api_config.map(|tree_api_config| {
             let bind_addr = (Ipv4Addr::UNSPECIFIED, tree_api_config.port).into();
             let tree_reader = metadata_calculator.tree_reader();
             TreeApiTask {
                 bind_addr,
                 tree_reader,
+                zk_os_tree_reader: zk_os_tree_task.as_ref().map(ZkOsTreeTask::tree_reader),
             }
         });
 
@@ -172,6 +196,7 @@ impl WiringLayer for MetadataCalculatorLayer {
             tree_api_task,
             pruning_task,
             stale_keys_repair_task,
+            zk_os_tree_task,
             rocksdb_shutdown_hook,
         })
     }
@@ -192,6 +217,7 @@ impl Task for MetadataCalculator {
 pub struct TreeApiTask {
     bind_addr: SocketAddr,
     tree_reader: LazyAsyncTreeReader,
+    zk_os_tree_reader: Option<LazyZkOsTreeReader>,
 }
 
 #[async_trait::async_trait]
@@ -202,7 +228,17 @@ impl Task for TreeApiTask {
 
     async fn run(self: Box<Self>, mut stop_receiver: StopReceiver) -> anyhow::Result<()> {
         if let Some(reader) = self.tree_reader.wait().await {
-            reader.run_api_server(self.bind_addr, stop_receiver.0).await
+            if let Some(zk_os_tree_reader) = self.zk_os_tree_reader {
+                reader
+                    .run_api_server_with_zk_os_tree(
+                        zk_os_tree_reader,
+                        self.bind_addr,
+                        stop_receiver.0,
+                    )
+                    .await
+            } else {
+                reader.run_api_server(self.bind_addr, stop_receiver.0).await
+            }
         } else {
             // Tree is dropped before initialized, e.g. because the node is getting shut down.
             // We don't want to treat this as an error since it could mask the real shutdown cause in logs etc.
@@ -224,6 +260,17 @@ impl Task for StaleKeysRepairTask {
     }
 }
 
+#[async_trait::async_trait]
+impl Task for ZkOsTreeTask {
+    fn id(&self) -> TaskId {
+        "zk_os_merkle_tree".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
+
 #[async_trait::async_trait]
 impl Task for MerkleTreePruningTask {
     fn id(&self) -> TaskId {
@@ -273,6 +320,7 @@ impl WiringLayer for TreeApiServerLayer {
         let tree_api_task = TreeApiTask {
             bind_addr,
             tree_reader: tree_reader_task.tree_reader(),
+            zk_os_tree_reader: None,
         };
         Ok(TreeApiServerOutput {
             tree_api_client: TreeApiClientResource(Arc::new(tree_reader_task.tree_reader())),
@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use zksync_node_db_pruner::{DbPruner, DbPrunerConfig};
+use zksync_node_db_pruner::{DbPruner, DbPrunerConfig, RetentionRules};
 
 use crate::{
     implementations::resources::{
@@ -19,6 +19,7 @@ pub struct PruningLayer {
     pruning_removal_delay: Duration,
     pruning_chunk_size: u32,
     minimum_l1_batch_age: Duration,
+    retention_rules: RetentionRules,
 }
 
 #[derive(Debug, FromContext)]
@@ -46,8 +47,15 @@ impl PruningLayer {
             pruning_removal_delay,
             pruning_chunk_size,
             minimum_l1_batch_age,
+            retention_rules: RetentionRules::default(),
         }
     }
+
+    /// Sets rules for data that should survive hard pruning.
+    pub fn with_retention_rules(mut self, retention_rules: RetentionRules) -> Self {
+        self.retention_rules = retention_rules;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -67,6 +75,7 @@ impl WiringLayer for PruningLayer {
                 removal_delay: self.pruning_removal_delay,
                 pruned_batch_chunk_size: self.pruning_chunk_size,
                 minimum_l1_batch_age: self.minimum_l1_batch_age,
+                retention_rules: self.retention_rules,
             },
             main_pool,
         );
@@ -8,6 +8,7 @@ use crate::{
 pub mod bwip;
 pub mod playground;
 pub mod protective_reads;
+pub mod state_diff_exporter;
 
 #[async_trait::async_trait]
 impl<Io: VmRunnerIo> Task for StorageSyncTask<Io> {
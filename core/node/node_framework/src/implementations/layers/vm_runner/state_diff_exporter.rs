@@ -0,0 +1,108 @@
+use zksync_config::configs::vm_runner::StateDiffExporterConfig;
+use zksync_node_framework_derive::FromContext;
+use zksync_types::L2ChainId;
+use zksync_vm_runner::{
+    impls::{FileSink, KafkaRestSink, StateDiffExporter, StateDiffExporterIo, StateDiffSink},
+    ConcurrentOutputHandlerFactoryTask, StorageSyncTask,
+};
+
+use crate::{
+    implementations::resources::pools::{MasterPool, PoolResource},
+    service::StopReceiver,
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+    IntoContext,
+};
+
+/// Wiring layer for the state diff exporter.
+#[derive(Debug)]
+pub struct StateDiffExporterLayer {
+    config: StateDiffExporterConfig,
+    zksync_network_id: L2ChainId,
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub master_pool: PoolResource<MasterPool>,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    #[context(task)]
+    pub state_diff_exporter: StateDiffExporter,
+    #[context(task)]
+    pub loader_task: StorageSyncTask<StateDiffExporterIo>,
+    #[context(task)]
+    pub output_handler_factory_task: ConcurrentOutputHandlerFactoryTask<StateDiffExporterIo>,
+}
+
+impl StateDiffExporterLayer {
+    pub fn new(config: StateDiffExporterConfig, zksync_network_id: L2ChainId) -> Self {
+        Self {
+            config,
+            zksync_network_id,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for StateDiffExporterLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "vm_runner_state_diff_exporter"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let mut sinks: Vec<Box<dyn StateDiffSink>> = vec![];
+        if let Some(path) = &self.config.file_sink_path {
+            sinks.push(Box::new(FileSink::new(path)));
+        }
+        if let Some(url) = &self.config.kafka_rest_url {
+            sinks.push(Box::new(KafkaRestSink::new(url, &self.config.kafka_topic)?));
+        }
+        if sinks.is_empty() {
+            return Err(WiringError::Configuration(
+                "state diff exporter requires at least one sink (file or Kafka) to be configured"
+                    .to_owned(),
+            ));
+        }
+
+        let (state_diff_exporter, tasks) = StateDiffExporter::new(
+            // One for `StorageSyncTask` which can hold a long-term connection in case it needs to
+            // catch up cache.
+            //
+            // One for `ConcurrentOutputHandlerFactoryTask`/`VmRunner` as they need occasional access
+            // to DB for querying last processed batch and last ready to be loaded batch.
+            //
+            // Output handlers don't access the DB.
+            input.master_pool.get_custom(2).await?,
+            self.config.db_path,
+            self.zksync_network_id,
+            self.config.first_processed_batch,
+            self.config.window_size,
+            sinks,
+        )
+        .await?;
+
+        Ok(Output {
+            state_diff_exporter,
+            loader_task: tasks.loader_task,
+            output_handler_factory_task: tasks.output_handler_factory_task,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for StateDiffExporter {
+    fn id(&self) -> TaskId {
+        "vm_runner/state_diff_exporter".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(&stop_receiver.0).await
+    }
+}
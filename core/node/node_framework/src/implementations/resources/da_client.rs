@@ -11,3 +11,13 @@ impl Resource for DAClientResource {
         "common/da_client".into()
     }
 }
+
+/// Represents a client of the secondary DA layer used by the DA dispatcher.
+#[derive(Debug, Clone)]
+pub struct SecondaryDAClientResource(pub Box<dyn DataAvailabilityClient>);
+
+impl Resource for SecondaryDAClientResource {
+    fn name() -> String {
+        "common/secondary_da_client".into()
+    }
+}
@@ -5,7 +5,11 @@ use std::time::Duration;
 use anyhow::Context as _;
 use serde::Serialize;
 use tokio::sync::watch;
-use zksync_da_client::{types::InclusionData, DataAvailabilityClient};
+use zksync_da_client::{
+    packing::{is_packed_blob, unpack_blob, BatchFrame},
+    types::InclusionData,
+    DataAvailabilityClient,
+};
 use zksync_dal::{ConnectionPool, Core, CoreDal};
 use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
 use zksync_types::{commitment::PubdataType, utils::client_type_to_pubdata_type, L1BatchNumber};
@@ -14,16 +18,31 @@ use zksync_web3_decl::{
     namespaces::UnstableNamespaceClient,
 };
 
+#[cfg(test)]
+mod tests;
+
 #[derive(Debug, Serialize)]
 #[serde(untagged)]
 enum DataAvailabilityFetcherHealth {
     Ready {
         #[serde(skip_serializing_if = "Option::is_none")]
         last_fetched_batch_number: Option<L1BatchNumber>,
+        /// Number of L1 batches for which DA blob verification was skipped because there was no locally produced pubdata.
+        #[serde(skip_serializing_if = "is_zero")]
+        skipped_blob_verifications: u64,
     },
     Affected {
         error: String,
     },
+    /// Pubdata in the DA blob differs from the one produced by the local execution.
+    PubdataMismatch {
+        l1_batch_number: L1BatchNumber,
+        blob_id: String,
+    },
+}
+
+fn is_zero(value: &u64) -> bool {
+    *value == 0
 }
 
 #[derive(Debug)]
@@ -52,13 +71,29 @@ enum StepOutcome {
     NoProgress,
     NoInclusionDataFromMainNode,
     UnableToFetchInclusionData,
+    UnableToFetchBlob,
+    /// Pubdata in the DA blob doesn't match the locally produced one; DA info for the batch is not persisted.
+    PubdataMismatch(L1BatchNumber),
+}
+
+/// Outcome of verifying a DA blob against the locally produced pubdata.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlobVerification {
+    Verified,
+    /// There's no locally produced pubdata for the L1 batch, so the blob cannot be verified.
+    Skipped,
+    /// The blob is not available yet.
+    Unavailable,
+    /// The blob doesn't contain pubdata for the L1 batch, or the pubdata differs from the local one.
+    Mismatch,
 }
 
 impl From<DataAvailabilityFetcherHealth> for Health {
     fn from(health: DataAvailabilityFetcherHealth) -> Self {
         let status = match health {
             DataAvailabilityFetcherHealth::Ready { .. } => HealthStatus::Ready,
-            DataAvailabilityFetcherHealth::Affected { .. } => HealthStatus::Affected,
+            DataAvailabilityFetcherHealth::Affected { .. }
+            | DataAvailabilityFetcherHealth::PubdataMismatch { .. } => HealthStatus::Affected,
         };
         Self::from(status).with_details(health)
     }
@@ -73,6 +108,14 @@ pub struct DataAvailabilityFetcher {
     pool: ConnectionPool<Core>,
     health_updater: HealthUpdater,
     poll_interval: Duration,
+    verify_blobs: bool,
+    /// ID and L1 batch frames of the last retrieved DA blob. Consecutive L1 batches may be packed into a single blob,
+    /// so this allows to retrieve and decompress each blob only once.
+    last_blob: Option<(String, Vec<BatchFrame>)>,
+    /// The first L1 batch (and its blob ID) with the pubdata mismatch, if any.
+    pubdata_mismatch: Option<(L1BatchNumber, String)>,
+    /// Number of L1 batches for which blob verification was skipped.
+    skipped_blob_verifications: u64,
 }
 
 impl DataAvailabilityFetcher {
@@ -90,9 +133,21 @@ impl DataAvailabilityFetcher {
             pool,
             health_updater: ReactiveHealthCheck::new("data_availability_fetcher").1,
             poll_interval: Self::DEFAULT_POLL_INTERVAL,
+            verify_blobs: false,
+            last_blob: None,
+            pubdata_mismatch: None,
+            skipped_blob_verifications: 0,
         }
     }
 
+    /// Enables retrieval of DA blobs. Pubdata in each blob is compared with the pubdata produced by the local
+    /// execution of the L1 batch; a mismatch is reported via the health check, and DA info for the mismatched batch
+    /// (and thus for all subsequent batches) is not persisted.
+    pub fn with_blob_verification(mut self) -> Self {
+        self.verify_blobs = true;
+        self
+    }
+
     /// Returns a health check for this fetcher.
     pub fn health_check(&self) -> ReactiveHealthCheck {
         self.health_updater.subscribe()
@@ -210,6 +265,22 @@ impl DataAvailabilityFetcher {
             )));
         }
 
+        if self.verify_blobs && pubdata_type != PubdataType::NoDA {
+            match self
+                .verify_blob(l1_batch_to_fetch, &da_details.blob_id)
+                .await?
+            {
+                BlobVerification::Verified => {}
+                BlobVerification::Skipped => {
+                    self.skipped_blob_verifications += 1;
+                }
+                BlobVerification::Unavailable => return Ok(StepOutcome::UnableToFetchBlob),
+                BlobVerification::Mismatch => {
+                    return Ok(StepOutcome::PubdataMismatch(l1_batch_to_fetch));
+                }
+            }
+        }
+
         let mut connection = self
             .pool
             .connection_tagged("data_availability_fetcher")
@@ -236,9 +307,98 @@ impl DataAvailabilityFetcher {
         Ok(StepOutcome::UpdatedBatch(l1_batch_to_fetch))
     }
 
+    /// Retrieves the blob from the DA layer and compares the L1 batch pubdata in it with the pubdata
+    /// produced by the local execution. A mismatch is recorded to be reported via the health check.
+    async fn verify_blob(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        blob_id: &str,
+    ) -> Result<BlobVerification, DataAvailabilityFetcherError> {
+        let is_blob_cached = matches!(&self.last_blob, Some((id, _)) if id == blob_id);
+        if !is_blob_cached {
+            let blob = self.da_client.fetch_blob(blob_id).await.map_err(|err| {
+                DataAvailabilityFetcherError {
+                    is_retriable: err.is_retriable(),
+                    error: anyhow::anyhow!("Error fetching DA blob: {err}"),
+                }
+            })?;
+            let Some(blob) = blob else {
+                return Ok(BlobVerification::Unavailable);
+            };
+            // Blobs that are not packed contain the raw pubdata of a single L1 batch.
+            let frames = if is_packed_blob(&blob) {
+                unpack_blob(&blob).unwrap_or_else(|err| {
+                    tracing::warn!("Failed unpacking DA blob {blob_id}: {err:#}");
+                    vec![]
+                })
+            } else {
+                vec![BatchFrame {
+                    l1_batch_number: l1_batch_number.0,
+                    pubdata: blob,
+                }]
+            };
+            self.last_blob = Some((blob_id.to_owned(), frames));
+        }
+
+        let mut connection = self
+            .pool
+            .connection_tagged("data_availability_fetcher")
+            .await
+            .map_err(|err| to_fatal_error(err.generalize()))?;
+        let local_pubdata = connection
+            .blocks_dal()
+            .get_l1_batch_header(l1_batch_number)
+            .await
+            .map_err(|err| to_fatal_error(err.generalize()))?
+            .and_then(|header| header.pubdata_input);
+        drop(connection);
+
+        let Some(local_pubdata) = local_pubdata else {
+            tracing::warn!(
+                "No locally produced pubdata for L1 batch #{l1_batch_number}; skipping DA blob verification"
+            );
+            return Ok(BlobVerification::Skipped);
+        };
+
+        let blob_pubdata = self.last_blob.as_ref().and_then(|(_, frames)| {
+            frames
+                .iter()
+                .find(|frame| frame.l1_batch_number == l1_batch_number.0)
+        });
+        let Some(blob_pubdata) = blob_pubdata else {
+            tracing::error!("L1 batch #{l1_batch_number} is not present in DA blob {blob_id}");
+            self.pubdata_mismatch
+                .get_or_insert_with(|| (l1_batch_number, blob_id.to_owned()));
+            return Ok(BlobVerification::Mismatch);
+        };
+
+        if blob_pubdata.pubdata == local_pubdata {
+            tracing::debug!(
+                "Verified pubdata for L1 batch #{l1_batch_number} in DA blob {blob_id}"
+            );
+            Ok(BlobVerification::Verified)
+        } else {
+            tracing::error!(
+                "Pubdata for L1 batch #{l1_batch_number} in DA blob {blob_id} doesn't match the locally produced pubdata"
+            );
+            self.pubdata_mismatch
+                .get_or_insert_with(|| (l1_batch_number, blob_id.to_owned()));
+            Ok(BlobVerification::Mismatch)
+        }
+    }
+
     fn update_health(&self, last_fetched_batch_number: Option<L1BatchNumber>) {
-        let health = DataAvailabilityFetcherHealth::Ready {
-            last_fetched_batch_number,
+        // The pubdata mismatch is not recoverable, so it's reported until the node is restarted.
+        let health = if let Some((l1_batch_number, blob_id)) = &self.pubdata_mismatch {
+            DataAvailabilityFetcherHealth::PubdataMismatch {
+                l1_batch_number: *l1_batch_number,
+                blob_id: blob_id.clone(),
+            }
+        } else {
+            DataAvailabilityFetcherHealth::Ready {
+                last_fetched_batch_number,
+                skipped_blob_verifications: self.skipped_blob_verifications,
+            }
         };
         self.health_updater.update(health.into());
     }
@@ -271,6 +431,18 @@ impl DataAvailabilityFetcher {
                     self.update_health(last_updated_l1_batch);
                     true
                 }
+                Ok(StepOutcome::UnableToFetchBlob) => {
+                    tracing::warn!("DA blob for the batch is not available yet, will retry later");
+                    self.update_health(last_updated_l1_batch);
+                    true
+                }
+                Ok(StepOutcome::PubdataMismatch(batch_number)) => {
+                    tracing::warn!(
+                        "Not persisting DA info for L1 batch #{batch_number} because of the pubdata mismatch, will retry later"
+                    );
+                    self.update_health(last_updated_l1_batch);
+                    true
+                }
                 Err(err) => {
                     if err.is_retriable {
                         tracing::warn!(
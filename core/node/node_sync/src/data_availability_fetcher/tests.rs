@@ -0,0 +1,384 @@
+//! Tests for the data availability fetcher.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use assert_matches::assert_matches;
+use async_trait::async_trait;
+use chrono::{TimeZone, Utc};
+use test_casing::test_casing;
+use zksync_da_client::{
+    packing::{pack_blob, BlobCompression},
+    types::{ClientType, DAError, DispatchResponse},
+};
+use zksync_dal::Connection;
+use zksync_health_check::CheckHealth;
+use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
+use zksync_node_test_utils::create_l1_batch;
+use zksync_types::api::DataAvailabilityDetails;
+use zksync_web3_decl::client::MockClient;
+
+use super::*;
+
+/// DA client serving blobs from memory.
+#[derive(Debug, Clone, Default)]
+struct MockDAClient {
+    blobs: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+    fetch_count: Arc<AtomicUsize>,
+}
+
+impl MockDAClient {
+    fn insert_blob(&self, blob_id: &str, blob: Vec<u8>) {
+        self.blobs.lock().unwrap().insert(blob_id.to_owned(), blob);
+    }
+
+    fn fetch_count(&self) -> usize {
+        self.fetch_count.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait]
+impl DataAvailabilityClient for MockDAClient {
+    async fn dispatch_blob(
+        &self,
+        _batch_number: u32,
+        _data: Vec<u8>,
+    ) -> Result<DispatchResponse, DAError> {
+        unimplemented!("not used by the fetcher")
+    }
+
+    async fn get_inclusion_data(&self, _blob_id: &str) -> Result<Option<InclusionData>, DAError> {
+        Ok(Some(InclusionData::default()))
+    }
+
+    async fn fetch_blob(&self, blob_id: &str) -> Result<Option<Vec<u8>>, DAError> {
+        self.fetch_count.fetch_add(1, Ordering::SeqCst);
+        Ok(self.blobs.lock().unwrap().get(blob_id).cloned())
+    }
+
+    fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient> {
+        Box::new(self.clone())
+    }
+
+    fn blob_size_limit(&self) -> Option<usize> {
+        None
+    }
+
+    fn client_type(&self) -> ClientType {
+        ClientType::Avail
+    }
+
+    async fn balance(&self) -> Result<u64, DAError> {
+        Ok(0)
+    }
+}
+
+fn mock_pubdata(number: L1BatchNumber) -> Vec<u8> {
+    vec![number.0 as u8; 64]
+}
+
+async fn seal_l1_batches(storage: &mut Connection<'_, Core>, count: u32) {
+    insert_genesis_batch(storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    for number in 1..=count {
+        let mut header = create_l1_batch(number);
+        header.pubdata_input = Some(mock_pubdata(L1BatchNumber(number)));
+        storage
+            .blocks_dal()
+            .insert_mock_l1_batch(&header)
+            .await
+            .unwrap();
+    }
+}
+
+/// Creates a main node client returning DA details with the specified blob IDs.
+fn mock_main_node_client(blob_ids: HashMap<L1BatchNumber, String>) -> MockClient<L2> {
+    MockClient::builder(L2::default())
+        .method(
+            "unstable_getDataAvailabilityDetails",
+            move |number: L1BatchNumber| {
+                Ok(blob_ids
+                    .get(&number)
+                    .map(|blob_id| DataAvailabilityDetails {
+                        pubdata_type: Some(PubdataType::Avail),
+                        blob_id: blob_id.clone(),
+                        inclusion_data: Some(vec![]),
+                        sent_at: Utc.timestamp_opt(1_000, 0).unwrap(),
+                        l2_da_validator: None,
+                        fee: None,
+                        fee_in_base_token: None,
+                    }))
+            },
+        )
+        .build()
+}
+
+fn packed_blob(numbers: impl Iterator<Item = u32>, compression: BlobCompression) -> Vec<u8> {
+    let frames: Vec<_> = numbers
+        .map(|number| BatchFrame {
+            l1_batch_number: number,
+            pubdata: mock_pubdata(L1BatchNumber(number)),
+        })
+        .collect();
+    pack_blob(&frames, compression).unwrap()
+}
+
+#[test_casing(2, [BlobCompression::None, BlobCompression::Zlib])]
+#[tokio::test]
+async fn verifying_packed_blob_with_several_batches(compression: BlobCompression) {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    seal_l1_batches(&mut storage, 3).await;
+
+    let da_client = MockDAClient::default();
+    da_client.insert_blob("blob", packed_blob(1..=3, compression));
+    let blob_ids = (1..=3)
+        .map(|number| (L1BatchNumber(number), "blob".to_owned()))
+        .collect();
+    let client = mock_main_node_client(blob_ids);
+    let mut fetcher =
+        DataAvailabilityFetcher::new(Box::new(client), pool, Box::new(da_client.clone()))
+            .with_blob_verification();
+    let health_check = fetcher.health_check();
+
+    for number in 1..=3 {
+        let outcome = fetcher.step().await.unwrap();
+        assert_matches!(outcome, StepOutcome::UpdatedBatch(n) if n == L1BatchNumber(number));
+    }
+    assert_matches!(fetcher.step().await.unwrap(), StepOutcome::NoProgress);
+    // The blob is retrieved and decompressed only once for all batches packed into it.
+    assert_eq!(da_client.fetch_count(), 1);
+    assert_eq!(fetcher.pubdata_mismatch, None);
+
+    fetcher.update_health(Some(L1BatchNumber(3)));
+    let health = health_check.check_health().await;
+    assert_matches!(health.status(), HealthStatus::Ready);
+
+    for number in 1..=3 {
+        let details = storage
+            .data_availability_dal()
+            .get_da_details_by_batch_number(L1BatchNumber(number))
+            .await
+            .unwrap()
+            .expect("no DA details");
+        assert_eq!(details.blob_id, "blob");
+    }
+}
+
+#[tokio::test]
+async fn verifying_raw_blobs() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    seal_l1_batches(&mut storage, 2).await;
+
+    let da_client = MockDAClient::default();
+    let mut blob_ids = HashMap::new();
+    for number in [L1BatchNumber(1), L1BatchNumber(2)] {
+        let blob_id = format!("blob{number}");
+        da_client.insert_blob(&blob_id, mock_pubdata(number));
+        blob_ids.insert(number, blob_id);
+    }
+    let client = mock_main_node_client(blob_ids);
+    let mut fetcher =
+        DataAvailabilityFetcher::new(Box::new(client), pool, Box::new(da_client.clone()))
+            .with_blob_verification();
+
+    for number in 1..=2 {
+        let outcome = fetcher.step().await.unwrap();
+        assert_matches!(outcome, StepOutcome::UpdatedBatch(n) if n == L1BatchNumber(number));
+    }
+    assert_eq!(da_client.fetch_count(), 2);
+    assert_eq!(fetcher.pubdata_mismatch, None);
+}
+
+#[test_casing(2, [false, true])]
+#[tokio::test]
+async fn pubdata_mismatch_is_reported(packed: bool) {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    seal_l1_batches(&mut storage, 3).await;
+
+    let da_client = MockDAClient::default();
+    let mut blob_ids = HashMap::new();
+    for number in (1..=3).map(L1BatchNumber) {
+        let blob_id = format!("blob{number}");
+        let mut pubdata = mock_pubdata(number);
+        if number == L1BatchNumber(2) {
+            pubdata[0] ^= 1;
+        }
+        let blob = if packed {
+            let frame = BatchFrame {
+                l1_batch_number: number.0,
+                pubdata,
+            };
+            pack_blob(&[frame], BlobCompression::Zlib).unwrap()
+        } else {
+            pubdata
+        };
+        da_client.insert_blob(&blob_id, blob);
+        blob_ids.insert(number, blob_id);
+    }
+    let client = mock_main_node_client(blob_ids);
+    let mut fetcher = DataAvailabilityFetcher::new(Box::new(client), pool, Box::new(da_client))
+        .with_blob_verification();
+    let health_check = fetcher.health_check();
+
+    let outcome = fetcher.step().await.unwrap();
+    assert_matches!(outcome, StepOutcome::UpdatedBatch(L1BatchNumber(1)));
+    // DA info for the mismatched batch is not persisted, so the fetcher doesn't progress past it.
+    for _ in 0..2 {
+        let outcome = fetcher.step().await.unwrap();
+        assert_matches!(outcome, StepOutcome::PubdataMismatch(L1BatchNumber(2)));
+    }
+    assert_eq!(
+        fetcher.pubdata_mismatch,
+        Some((L1BatchNumber(2), "blob2".to_owned()))
+    );
+    let details = storage
+        .data_availability_dal()
+        .get_da_details_by_batch_number(L1BatchNumber(2))
+        .await
+        .unwrap();
+    assert!(details.is_none());
+
+    fetcher.update_health(Some(L1BatchNumber(1)));
+    let health = health_check.check_health().await;
+    assert_matches!(health.status(), HealthStatus::Affected);
+    let details = health.details().unwrap();
+    assert_eq!(
+        *details,
+        serde_json::json!({
+            "l1_batch_number": 2,
+            "blob_id": "blob2",
+        })
+    );
+}
+
+#[tokio::test]
+async fn missing_batch_in_packed_blob_is_reported() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    seal_l1_batches(&mut storage, 2).await;
+
+    let da_client = MockDAClient::default();
+    // The blob doesn't contain pubdata for L1 batch #2.
+    da_client.insert_blob("blob", packed_blob(1..=1, BlobCompression::None));
+    let blob_ids = (1..=2)
+        .map(|number| (L1BatchNumber(number), "blob".to_owned()))
+        .collect();
+    let client = mock_main_node_client(blob_ids);
+    let mut fetcher = DataAvailabilityFetcher::new(Box::new(client), pool, Box::new(da_client))
+        .with_blob_verification();
+
+    let outcome = fetcher.step().await.unwrap();
+    assert_matches!(outcome, StepOutcome::UpdatedBatch(L1BatchNumber(1)));
+    let outcome = fetcher.step().await.unwrap();
+    assert_matches!(outcome, StepOutcome::PubdataMismatch(L1BatchNumber(2)));
+    assert_eq!(
+        fetcher.pubdata_mismatch,
+        Some((L1BatchNumber(2), "blob".to_owned()))
+    );
+    let details = storage
+        .data_availability_dal()
+        .get_da_details_by_batch_number(L1BatchNumber(2))
+        .await
+        .unwrap();
+    assert!(details.is_none());
+}
+
+#[tokio::test]
+async fn skipped_verifications_are_reported() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    // The batch has no locally produced pubdata, so its blob cannot be verified.
+    storage
+        .blocks_dal()
+        .insert_mock_l1_batch(&create_l1_batch(1))
+        .await
+        .unwrap();
+
+    let da_client = MockDAClient::default();
+    da_client.insert_blob("blob", mock_pubdata(L1BatchNumber(1)));
+    let blob_ids = HashMap::from([(L1BatchNumber(1), "blob".to_owned())]);
+    let client = mock_main_node_client(blob_ids);
+    let mut fetcher = DataAvailabilityFetcher::new(Box::new(client), pool, Box::new(da_client))
+        .with_blob_verification();
+    let health_check = fetcher.health_check();
+
+    let outcome = fetcher.step().await.unwrap();
+    assert_matches!(outcome, StepOutcome::UpdatedBatch(L1BatchNumber(1)));
+    assert_eq!(fetcher.pubdata_mismatch, None);
+
+    fetcher.update_health(Some(L1BatchNumber(1)));
+    let health = health_check.check_health().await;
+    assert_matches!(health.status(), HealthStatus::Ready);
+    assert_eq!(
+        *health.details().unwrap(),
+        serde_json::json!({
+            "last_fetched_batch_number": 1,
+            "skipped_blob_verifications": 1,
+        })
+    );
+}
+
+#[tokio::test]
+async fn unavailable_blob_is_retried() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    seal_l1_batches(&mut storage, 1).await;
+
+    let da_client = MockDAClient::default();
+    let blob_ids = HashMap::from([(L1BatchNumber(1), "blob".to_owned())]);
+    let client = mock_main_node_client(blob_ids);
+    let mut fetcher =
+        DataAvailabilityFetcher::new(Box::new(client), pool, Box::new(da_client.clone()))
+            .with_blob_verification();
+    fetcher.poll_interval = Duration::from_millis(10);
+    let mut health_check = fetcher.health_check();
+
+    assert_matches!(
+        fetcher.step().await.unwrap(),
+        StepOutcome::UnableToFetchBlob
+    );
+    // DA details must not be persisted until the blob is verified.
+    let details = storage
+        .data_availability_dal()
+        .get_da_details_by_batch_number(L1BatchNumber(1))
+        .await
+        .unwrap();
+    assert!(details.is_none());
+
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let fetcher_task = tokio::spawn(fetcher.run(stop_receiver));
+    // Wait until the fetcher retries fetching the blob.
+    while da_client.fetch_count() < 3 {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    da_client.insert_blob("blob", mock_pubdata(L1BatchNumber(1)));
+
+    health_check
+        .wait_for(|health| {
+            health.status() == HealthStatus::Ready
+                && health.details() == Some(&serde_json::json!({ "last_fetched_batch_number": 1 }))
+        })
+        .await;
+    let details = storage
+        .data_availability_dal()
+        .get_da_details_by_batch_number(L1BatchNumber(1))
+        .await
+        .unwrap()
+        .expect("no DA details");
+    assert_eq!(details.blob_id, "blob");
+
+    stop_sender.send_replace(true);
+    fetcher_task.await.unwrap().unwrap();
+}
@@ -0,0 +1,98 @@
+//! Cache of the collateral used to verify quotes.
+
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Context as _;
+use tokio::{sync::Mutex, time::Instant};
+
+/// CAs issuing PCK certificates; a quote may be signed by a PCK certificate issued by either of them.
+const PCK_CAS: [&str; 2] = ["processor", "platform"];
+
+/// Collateral required to verify quotes.
+#[derive(Debug, Clone)]
+pub(crate) struct QuoteCollateral {
+    /// DER-encoded CRLs of the CAs issuing PCK certificates.
+    pub pck_crls: Vec<Vec<u8>>,
+}
+
+#[derive(Debug)]
+enum CollateralSource {
+    /// Intel PCS or a PCCS instance exposing the same API.
+    Pccs {
+        client: reqwest::Client,
+        url: String,
+    },
+    #[cfg(test)]
+    Fixed(QuoteCollateral),
+}
+
+impl CollateralSource {
+    async fn fetch(&self) -> anyhow::Result<QuoteCollateral> {
+        match self {
+            Self::Pccs { client, url } => {
+                let mut pck_crls = Vec::with_capacity(PCK_CAS.len());
+                for ca in PCK_CAS {
+                    let crl_url = format!("{url}/sgx/certification/v4/pckcrl?ca={ca}&encoding=der");
+                    let crl = client
+                        .get(&crl_url)
+                        .send()
+                        .await
+                        .and_then(reqwest::Response::error_for_status)
+                        .with_context(|| format!("failed fetching PCK CRL from {crl_url}"))?
+                        .bytes()
+                        .await
+                        .with_context(|| format!("failed reading PCK CRL from {crl_url}"))?;
+                    pck_crls.push(crl.to_vec());
+                }
+                Ok(QuoteCollateral { pck_crls })
+            }
+            #[cfg(test)]
+            Self::Fixed(collateral) => Ok(collateral.clone()),
+        }
+    }
+}
+
+/// Caches the collateral fetched from its source for the configured time.
+#[derive(Debug)]
+pub(crate) struct CollateralCache {
+    source: CollateralSource,
+    ttl: Duration,
+    cached: Mutex<Option<(Arc<QuoteCollateral>, Instant)>>,
+}
+
+impl CollateralCache {
+    pub fn new(pccs_url: &str, ttl: Duration) -> Self {
+        Self {
+            source: CollateralSource::Pccs {
+                client: reqwest::Client::new(),
+                url: pccs_url.trim_end_matches('/').to_owned(),
+            },
+            ttl,
+            cached: Mutex::default(),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn fixed(collateral: QuoteCollateral) -> Self {
+        Self {
+            source: CollateralSource::Fixed(collateral),
+            ttl: Duration::ZERO,
+            cached: Mutex::default(),
+        }
+    }
+
+    pub async fn get(&self) -> anyhow::Result<Arc<QuoteCollateral>> {
+        // The lock is held while fetching, so that concurrent requests don't fetch the collateral several times.
+        let mut cached = self.cached.lock().await;
+        if let Some((collateral, fetched_at)) = cached.as_ref() {
+            if fetched_at.elapsed() < self.ttl {
+                return Ok(collateral.clone());
+            }
+        }
+
+        let collateral = Arc::new(self.source.fetch().await?);
+        tracing::info!("Fetched quote verification collateral");
+        *cached = Some((collateral.clone(), Instant::now()));
+        Ok(collateral)
+    }
+}
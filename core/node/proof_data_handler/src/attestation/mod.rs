@@ -0,0 +1,330 @@
+//! Verification of DCAP attestation quotes (SGX and TDX) registered by TEE provers.
+//!
+//! A quote is accepted if:
+//!
+//! - it is signed by the attestation key, and the attestation key is committed to by the quoting enclave report;
+//! - the quoting enclave report is signed by a PCK certificate chaining to the configured root CA
+//!   and not revoked according to the PCK CRLs;
+//! - the quoting enclave is signed by Intel, has the product ID expected for the TEE type, and its security
+//!   version is not below the minimum one (i.e., the QE is up to date);
+//! - for SGX, MRENCLAVE or MRSIGNER of the attested enclave is allowlisted, and the enclave is not a debug one;
+//!   for TDX, the measurement registers of the trust domain satisfy one of the configured policies, and the trust domain
+//!   is not a debug one;
+//! - the report data of the attested TEE starts with the registered public key.
+//!
+//! TCB levels of the platform (i.e., TCB info for the platform FMSPC) are not evaluated.
+
+use std::collections::HashSet;
+
+use anyhow::Context as _;
+use rustls_pki_types::{CertificateDer, TrustAnchor, UnixTime};
+use sha2::{Digest, Sha256};
+use webpki::{
+    CertRevocationList, EndEntityCert, KeyUsage, OwnedCertRevocationList, RevocationCheckDepth,
+    RevocationOptionsBuilder, UnknownStatusPolicy,
+};
+use zksync_config::configs::{TdxMeasurementPolicy, TeeConfig};
+use zksync_dal::tee_proof_generation_dal::{
+    TeeAttestationStatus, TeeAttestationVerification, TeeMeasurements,
+};
+use zksync_types::{tee_types::TeeType, H256};
+
+use self::{
+    collateral::{CollateralCache, QuoteCollateral},
+    quote::{ecdsa_signature_to_der, Quote, QuoteBody, ReportBody, TdReport},
+};
+
+mod collateral;
+mod quote;
+#[cfg(test)]
+pub(crate) mod tests;
+
+/// PCK certificates don't have an extended key usage; if one is present, the certificate is rejected.
+const PCK_KEY_USAGE: KeyUsage = KeyUsage::required_if_present(&[]);
+
+/// MRSIGNER of Intel quoting enclaves (both the SGX QE3 and the TDX QE).
+const QE_MRSIGNER: H256 = H256([
+    0x8c, 0x4f, 0x57, 0x75, 0xd7, 0x96, 0x50, 0x3e, 0x96, 0x13, 0x7f, 0x77, 0xc6, 0x8a, 0x82, 0x9a,
+    0x00, 0x56, 0xac, 0x8d, 0xed, 0x70, 0x14, 0x0b, 0x08, 0x1b, 0x09, 0x44, 0x90, 0xc5, 0x7b, 0xff,
+]);
+
+/// Expected ISVPRODID and minimum ISVSVN of the quoting enclave, as specified by the Intel QE identity
+/// for the TEE type. Quoting enclaves with a lower ISVSVN have a TCB status other than `UpToDate`.
+#[derive(Debug, Clone, Copy)]
+struct QeIdentity {
+    isv_prod_id: u16,
+    min_isv_svn: u16,
+}
+
+impl QeIdentity {
+    const SGX: Self = Self {
+        isv_prod_id: 1,
+        min_isv_svn: 8,
+    };
+    const TDX: Self = Self {
+        isv_prod_id: 2,
+        min_isv_svn: 4,
+    };
+
+    fn check(self, qe_report: &ReportBody<'_>) -> anyhow::Result<()> {
+        let mr_signer = qe_report.mr_signer();
+        anyhow::ensure!(
+            mr_signer == QE_MRSIGNER,
+            "quoting enclave MRSIGNER {mr_signer:?} is not trusted"
+        );
+        let isv_prod_id = qe_report.isv_prod_id();
+        anyhow::ensure!(
+            isv_prod_id == self.isv_prod_id,
+            "unexpected quoting enclave ISVPRODID {isv_prod_id}, expected {}",
+            self.isv_prod_id
+        );
+        let isv_svn = qe_report.isv_svn();
+        anyhow::ensure!(
+            isv_svn >= self.min_isv_svn,
+            "quoting enclave ISVSVN {isv_svn} is below the minimum {}",
+            self.min_isv_svn
+        );
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct AttestationVerifier {
+    root_ca: CertificateDer<'static>,
+    allowed_mrenclaves: HashSet<H256>,
+    allowed_mrsigners: HashSet<H256>,
+    tdx_policies: Vec<TdxMeasurementPolicy>,
+    collateral: CollateralCache,
+}
+
+impl AttestationVerifier {
+    pub fn new(config: &TeeConfig) -> anyhow::Result<Self> {
+        let root_ca_path = config
+            .tee_attestation_root_ca_path
+            .as_ref()
+            .context("root CA path must be configured if attestation verification is enabled")?;
+        let root_ca = std::fs::read(root_ca_path)
+            .with_context(|| format!("failed reading root CA from {root_ca_path}"))?;
+        let collateral =
+            CollateralCache::new(&config.tee_pccs_url, config.tee_collateral_cache_ttl());
+        Self::from_parts(config, &root_ca, collateral)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn for_tests(
+        config: &TeeConfig,
+        root_ca_pem: &[u8],
+        pck_crls: Vec<Vec<u8>>,
+    ) -> Self {
+        let collateral = CollateralCache::fixed(QuoteCollateral { pck_crls });
+        Self::from_parts(config, root_ca_pem, collateral).unwrap()
+    }
+
+    fn from_parts(
+        config: &TeeConfig,
+        root_ca_pem: &[u8],
+        collateral: CollateralCache,
+    ) -> anyhow::Result<Self> {
+        let root_ca = pem::parse(root_ca_pem).context("malformed root CA")?;
+        anyhow::ensure!(
+            root_ca.tag() == "CERTIFICATE",
+            "root CA is not a certificate"
+        );
+        let root_ca = CertificateDer::from(root_ca.into_contents());
+        webpki::anchor_from_trusted_cert(&root_ca).context("invalid root CA")?;
+
+        for (i, policy) in config.tee_tdx_policies.iter().enumerate() {
+            let registers = std::iter::once(&policy.mr_td).chain(policy.rtmrs.iter().flatten());
+            for register in registers {
+                anyhow::ensure!(
+                    register.len() == TdReport::REGISTER_LEN,
+                    "TDX policy #{i} contains a measurement register of invalid length {}",
+                    register.len()
+                );
+            }
+        }
+
+        for tee_type in &config.tee_types {
+            let has_policy = match tee_type {
+                TeeType::Sgx => {
+                    !config.tee_allowed_mrenclaves.is_empty()
+                        || !config.tee_allowed_mrsigners.is_empty()
+                }
+                TeeType::Tdx => !config.tee_tdx_policies.is_empty(),
+                _ => false,
+            };
+            if !has_policy {
+                tracing::warn!(
+                    "No measurements are allowed for TEE type {tee_type}; all its attestations will be rejected"
+                );
+            }
+        }
+        Ok(Self {
+            root_ca,
+            allowed_mrenclaves: config.tee_allowed_mrenclaves.iter().copied().collect(),
+            allowed_mrsigners: config.tee_allowed_mrsigners.iter().copied().collect(),
+            tdx_policies: config.tee_tdx_policies.clone(),
+            collateral,
+        })
+    }
+
+    /// Verifies the quote attesting `pubkey`. Returns an error only if the quote cannot be verified
+    /// for a transient reason (e.g., the collateral cannot be fetched); rejected quotes are reported
+    /// in the returned status.
+    pub async fn verify(
+        &self,
+        pubkey: &[u8],
+        quote: &[u8],
+    ) -> anyhow::Result<TeeAttestationVerification> {
+        let quote = match Quote::parse(quote) {
+            Ok(quote) => quote,
+            Err(err) => {
+                return Ok(TeeAttestationVerification {
+                    status: TeeAttestationStatus::Rejected,
+                    measurements: None,
+                    error: Some(format!("malformed quote: {err:#}")),
+                });
+            }
+        };
+        let collateral = self.collateral.get().await?;
+        let result = self.check_quote(&quote, pubkey, &collateral, UnixTime::now());
+
+        Ok(TeeAttestationVerification {
+            status: if result.is_ok() {
+                TeeAttestationStatus::Verified
+            } else {
+                TeeAttestationStatus::Rejected
+            },
+            measurements: Some(measurements(&quote.body)),
+            error: result.err().map(|err| format!("{err:#}")),
+        })
+    }
+
+    fn check_quote(
+        &self,
+        quote: &Quote<'_>,
+        pubkey: &[u8],
+        collateral: &QuoteCollateral,
+        now: UnixTime,
+    ) -> anyhow::Result<()> {
+        anyhow::ensure!(!pubkey.is_empty(), "public key is empty");
+        let mut attestation_key = Vec::with_capacity(quote.attestation_key.len() + 1);
+        attestation_key.push(0x04); // uncompressed SEC1 point
+        attestation_key.extend_from_slice(quote.attestation_key);
+        ring::signature::UnparsedPublicKey::new(
+            &ring::signature::ECDSA_P256_SHA256_FIXED,
+            &attestation_key,
+        )
+        .verify(quote.signed_data, quote.signature)
+        .map_err(|_| anyhow::anyhow!("quote signature is invalid"))?;
+
+        let attestation_key_hash = Sha256::new()
+            .chain_update(quote.attestation_key)
+            .chain_update(quote.qe_auth_data)
+            .finalize();
+        let qe_report_data = quote.qe_report.report_data();
+        anyhow::ensure!(
+            qe_report_data[..32] == attestation_key_hash[..]
+                && qe_report_data[32..].iter().all(|&b| b == 0),
+            "attestation key is not committed to by the QE report"
+        );
+
+        let (pck_cert, intermediates) = quote
+            .pck_cert_chain
+            .split_first()
+            .context("PCK certificate chain is empty")?;
+        let pck_cert = CertificateDer::from(pck_cert.as_slice());
+        let pck_cert = EndEntityCert::try_from(&pck_cert).context("malformed PCK certificate")?;
+        let intermediates: Vec<_> = intermediates
+            .iter()
+            .map(|cert| CertificateDer::from(cert.as_slice()))
+            .collect();
+        let trust_anchors: [TrustAnchor<'_>; 1] =
+            [webpki::anchor_from_trusted_cert(&self.root_ca).context("invalid root CA")?];
+        let crls = collateral
+            .pck_crls
+            .iter()
+            .map(|crl| OwnedCertRevocationList::from_der(crl).map(CertRevocationList::from))
+            .collect::<Result<Vec<_>, _>>()
+            .context("malformed PCK CRL")?;
+        let crls: Vec<_> = crls.iter().collect();
+        let revocation = RevocationOptionsBuilder::new(&crls)
+            .map_err(|_| anyhow::anyhow!("no PCK CRLs are available"))?
+            .with_depth(RevocationCheckDepth::EndEntity)
+            .with_status_policy(UnknownStatusPolicy::Deny)
+            .build();
+        pck_cert
+            .verify_for_usage(
+                &[webpki::ring::ECDSA_P256_SHA256],
+                &trust_anchors,
+                &intermediates,
+                now,
+                PCK_KEY_USAGE,
+                Some(revocation),
+                None,
+            )
+            .context("PCK certificate chain is invalid")?;
+        pck_cert
+            .verify_signature(
+                webpki::ring::ECDSA_P256_SHA256,
+                quote.qe_report.raw,
+                &ecdsa_signature_to_der(quote.qe_report_signature),
+            )
+            .context("QE report signature is invalid")?;
+        let qe_identity = match &quote.body {
+            QuoteBody::Sgx(_) => QeIdentity::SGX,
+            QuoteBody::Tdx(_) => QeIdentity::TDX,
+        };
+        qe_identity.check(&quote.qe_report)?;
+
+        match &quote.body {
+            QuoteBody::Sgx(report_body) => {
+                anyhow::ensure!(!report_body.is_debug(), "enclave is in debug mode");
+                let mr_enclave = report_body.mr_enclave();
+                let mr_signer = report_body.mr_signer();
+                anyhow::ensure!(
+                    self.allowed_mrenclaves.contains(&mr_enclave)
+                        || self.allowed_mrsigners.contains(&mr_signer),
+                    "neither MRENCLAVE {mr_enclave:?} nor MRSIGNER {mr_signer:?} is allowed"
+                );
+            }
+            QuoteBody::Tdx(report) => {
+                anyhow::ensure!(!report.is_debug(), "trust domain is in debug mode");
+                anyhow::ensure!(
+                    self.tdx_policies
+                        .iter()
+                        .any(|policy| satisfies_tdx_policy(report, policy)),
+                    "TD measurements (MRTD 0x{}) don't satisfy any TDX policy",
+                    hex::encode(report.mr_td())
+                );
+            }
+        }
+        anyhow::ensure!(
+            quote.body.report_data().starts_with(pubkey),
+            "report data doesn't commit to the public key"
+        );
+        Ok(())
+    }
+}
+
+fn measurements(body: &QuoteBody<'_>) -> TeeMeasurements {
+    match body {
+        QuoteBody::Sgx(report_body) => TeeMeasurements::Sgx {
+            mr_enclave: report_body.mr_enclave(),
+            mr_signer: report_body.mr_signer(),
+        },
+        QuoteBody::Tdx(report) => TeeMeasurements::Tdx {
+            mr_td: report.mr_td().to_vec(),
+            rtmrs: report.rtmrs().map(<[u8]>::to_vec),
+        },
+    }
+}
+
+fn satisfies_tdx_policy(report: &TdReport<'_>, policy: &TdxMeasurementPolicy) -> bool {
+    report.mr_td() == policy.mr_td
+        && report
+            .rtmrs()
+            .iter()
+            .zip(&policy.rtmrs)
+            .all(|(rtmr, expected)| expected.as_ref().map_or(true, |expected| rtmr == expected))
+}
@@ -0,0 +1,284 @@
+//! Parsing of DCAP quotes with ECDSA-256 with P-256 attestation keys: SGX quotes (versions 3 and 4)
+//! and TDX quotes (version 4).
+
+use anyhow::Context as _;
+use zksync_types::H256;
+
+const ATTESTATION_KEY_TYPE_ECDSA_P256: u16 = 2;
+/// TEE type values in the header of version 4 quotes.
+const HEADER_TEE_TYPE_SGX: u32 = 0;
+const HEADER_TEE_TYPE_TDX: u32 = 0x81;
+/// Certification data containing the PEM-encoded PCK certificate chain (leaf, intermediate CA and root CA).
+const CERTIFICATION_DATA_TYPE_PCK_CERT_CHAIN: u16 = 5;
+/// Certification data containing the quoting enclave report, its signature and authentication data,
+/// followed by nested certification data. Used in version 4 quotes.
+const CERTIFICATION_DATA_TYPE_QE_REPORT: u16 = 6;
+
+const HEADER_LEN: usize = 48;
+const REPORT_BODY_LEN: usize = 384;
+const TD_REPORT_LEN: usize = 584;
+const ECDSA_SIGNATURE_LEN: usize = 64;
+const ECDSA_PUBLIC_KEY_LEN: usize = 64;
+/// `DEBUG` bit in the flags of SGX enclave attributes.
+const SGX_FLAGS_DEBUG: u64 = 1 << 1;
+/// `TUD.DEBUG` bit in TDX trust domain attributes.
+const TD_ATTRIBUTES_DEBUG: u64 = 1;
+
+/// Enclave report body, as found in the quote both for the attested enclave and the quoting enclave.
+#[derive(Debug, Clone)]
+pub(super) struct ReportBody<'a> {
+    pub raw: &'a [u8],
+}
+
+impl ReportBody<'_> {
+    /// Enclave attributes: 8-byte flags followed by 8-byte XFRM.
+    pub fn attributes(&self) -> &[u8] {
+        &self.raw[48..64]
+    }
+
+    /// Checks whether the enclave is launched in the debug mode, in which its memory can be inspected.
+    pub fn is_debug(&self) -> bool {
+        let flags = u64::from_le_bytes(self.attributes()[..8].try_into().unwrap());
+        flags & SGX_FLAGS_DEBUG != 0
+    }
+
+    pub fn mr_enclave(&self) -> H256 {
+        H256::from_slice(&self.raw[64..96])
+    }
+
+    pub fn mr_signer(&self) -> H256 {
+        H256::from_slice(&self.raw[128..160])
+    }
+
+    pub fn isv_prod_id(&self) -> u16 {
+        u16::from_le_bytes([self.raw[256], self.raw[257]])
+    }
+
+    pub fn isv_svn(&self) -> u16 {
+        u16::from_le_bytes([self.raw[258], self.raw[259]])
+    }
+
+    pub fn report_data(&self) -> &[u8] {
+        &self.raw[320..384]
+    }
+}
+
+/// Report of a TDX trust domain.
+#[derive(Debug, Clone)]
+pub(super) struct TdReport<'a> {
+    pub raw: &'a [u8],
+}
+
+impl TdReport<'_> {
+    /// Length of a TDX measurement register.
+    pub const REGISTER_LEN: usize = 48;
+
+    /// Attributes of the trust domain (`TD_ATTRIBUTES`).
+    pub fn td_attributes(&self) -> u64 {
+        u64::from_le_bytes(self.raw[120..128].try_into().unwrap())
+    }
+
+    /// Checks whether the trust domain is launched in the debug mode, in which its state can be inspected by the host.
+    pub fn is_debug(&self) -> bool {
+        self.td_attributes() & TD_ATTRIBUTES_DEBUG != 0
+    }
+
+    pub fn mr_td(&self) -> &[u8] {
+        &self.raw[136..136 + Self::REGISTER_LEN]
+    }
+
+    pub fn rtmrs(&self) -> [&[u8]; 4] {
+        [0, 1, 2, 3].map(|i| {
+            let start = 328 + i * Self::REGISTER_LEN;
+            &self.raw[start..start + Self::REGISTER_LEN]
+        })
+    }
+
+    pub fn report_data(&self) -> &[u8] {
+        &self.raw[520..584]
+    }
+}
+
+/// Body of the quote describing the attested TEE.
+#[derive(Debug, Clone)]
+pub(super) enum QuoteBody<'a> {
+    Sgx(ReportBody<'a>),
+    Tdx(TdReport<'a>),
+}
+
+impl QuoteBody<'_> {
+    pub fn report_data(&self) -> &[u8] {
+        match self {
+            Self::Sgx(body) => body.report_data(),
+            Self::Tdx(report) => report.report_data(),
+        }
+    }
+}
+
+/// DCAP quote. All slices borrow from the raw quote bytes.
+#[derive(Debug, Clone)]
+pub(super) struct Quote<'a> {
+    /// Header and body of the quote; signed by the attestation key.
+    pub signed_data: &'a [u8],
+    pub body: QuoteBody<'a>,
+    /// Raw `r || s` signature of `signed_data`.
+    pub signature: &'a [u8],
+    /// Raw `x || y` coordinates of the attestation key.
+    pub attestation_key: &'a [u8],
+    /// Report of the quoting enclave; its report data commits to the attestation key.
+    pub qe_report: ReportBody<'a>,
+    /// Raw `r || s` signature of the quoting enclave report by the PCK certificate.
+    pub qe_report_signature: &'a [u8],
+    pub qe_auth_data: &'a [u8],
+    /// DER-encoded PCK certificate chain starting from the leaf certificate.
+    pub pck_cert_chain: Vec<Vec<u8>>,
+}
+
+impl<'a> Quote<'a> {
+    pub fn parse(bytes: &'a [u8]) -> anyhow::Result<Self> {
+        let mut reader = Reader { bytes };
+        let header = reader.read(HEADER_LEN).context("header")?;
+        let version = u16::from_le_bytes([header[0], header[1]]);
+        let key_type = u16::from_le_bytes([header[2], header[3]]);
+        anyhow::ensure!(
+            key_type == ATTESTATION_KEY_TYPE_ECDSA_P256,
+            "unsupported attestation key type {key_type}"
+        );
+        let header_tee_type = u32::from_le_bytes(header[4..8].try_into().unwrap());
+        let body = match (version, header_tee_type) {
+            // Version 3 quotes are SGX-only and have the TEE type field reserved.
+            (3, _) | (4, HEADER_TEE_TYPE_SGX) => QuoteBody::Sgx(ReportBody {
+                raw: reader.read(REPORT_BODY_LEN).context("report body")?,
+            }),
+            (4, HEADER_TEE_TYPE_TDX) => QuoteBody::Tdx(TdReport {
+                raw: reader.read(TD_REPORT_LEN).context("TD report")?,
+            }),
+            (4, _) => anyhow::bail!("unsupported TEE type {header_tee_type:#x}"),
+            _ => anyhow::bail!("unsupported quote version {version}"),
+        };
+        let signed_data = &bytes[..bytes.len() - reader.bytes.len()];
+
+        let signature_data_len = reader.read_u32().context("signature data length")?;
+        let mut reader = Reader {
+            bytes: reader
+                .read(signature_data_len as usize)
+                .context("signature data")?,
+        };
+        let signature = reader.read(ECDSA_SIGNATURE_LEN).context("signature")?;
+        let attestation_key = reader
+            .read(ECDSA_PUBLIC_KEY_LEN)
+            .context("attestation key")?;
+        if version == 4 {
+            // In version 4 quotes, QE report is wrapped into certification data.
+            let certification_data = reader
+                .read_certification_data(CERTIFICATION_DATA_TYPE_QE_REPORT)
+                .context("QE report certification data")?;
+            reader = Reader {
+                bytes: certification_data,
+            };
+        }
+        let qe_report = reader.read(REPORT_BODY_LEN).context("QE report")?;
+        let qe_report_signature = reader
+            .read(ECDSA_SIGNATURE_LEN)
+            .context("QE report signature")?;
+        let qe_auth_data_len = reader.read_u16().context("QE auth data length")?;
+        let qe_auth_data = reader
+            .read(qe_auth_data_len.into())
+            .context("QE auth data")?;
+        let certification_data = reader
+            .read_certification_data(CERTIFICATION_DATA_TYPE_PCK_CERT_CHAIN)
+            .context("PCK certification data")?;
+        let pck_cert_chain = parse_pem_chain(certification_data)?;
+
+        Ok(Self {
+            signed_data,
+            body,
+            signature,
+            attestation_key,
+            qe_report: ReportBody { raw: qe_report },
+            qe_report_signature,
+            qe_auth_data,
+            pck_cert_chain,
+        })
+    }
+}
+
+fn parse_pem_chain(data: &[u8]) -> anyhow::Result<Vec<Vec<u8>>> {
+    // The chain is usually terminated by a null byte.
+    let data = data.strip_suffix(&[0]).unwrap_or(data);
+    let chain = pem::parse_many(data).context("malformed PCK certificate chain")?;
+    anyhow::ensure!(!chain.is_empty(), "PCK certificate chain is empty");
+    chain
+        .into_iter()
+        .map(|entry| {
+            anyhow::ensure!(
+                entry.tag() == "CERTIFICATE",
+                "unexpected PEM entry in PCK certificate chain: {}",
+                entry.tag()
+            );
+            Ok(entry.into_contents())
+        })
+        .collect()
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn read(&mut self, len: usize) -> anyhow::Result<&'a [u8]> {
+        anyhow::ensure!(
+            self.bytes.len() >= len,
+            "unexpected end of quote: expected {len} bytes, got {}",
+            self.bytes.len()
+        );
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn read_u16(&mut self) -> anyhow::Result<u16> {
+        Ok(u16::from_le_bytes(self.read(2)?.try_into().unwrap()))
+    }
+
+    fn read_u32(&mut self) -> anyhow::Result<u32> {
+        Ok(u32::from_le_bytes(self.read(4)?.try_into().unwrap()))
+    }
+
+    fn read_certification_data(&mut self, expected_type: u16) -> anyhow::Result<&'a [u8]> {
+        let data_type = self.read_u16().context("type")?;
+        anyhow::ensure!(
+            data_type == expected_type,
+            "unexpected certification data type {data_type}, expected {expected_type}"
+        );
+        let len = self.read_u32().context("length")?;
+        self.read(len as usize).context("data")
+    }
+}
+
+/// Encodes a raw `r || s` ECDSA signature as an ASN.1 DER `Ecdsa-Sig-Value`.
+pub(super) fn ecdsa_signature_to_der(raw: &[u8]) -> Vec<u8> {
+    fn encode_integer(bytes: &[u8], out: &mut Vec<u8>) {
+        let first_nonzero = bytes
+            .iter()
+            .position(|&b| b != 0)
+            .unwrap_or(bytes.len() - 1);
+        let bytes = &bytes[first_nonzero..];
+        let needs_padding = bytes[0] & 0x80 != 0;
+        out.push(0x02);
+        out.push((bytes.len() + usize::from(needs_padding)) as u8);
+        if needs_padding {
+            out.push(0);
+        }
+        out.extend_from_slice(bytes);
+    }
+
+    let (r, s) = raw.split_at(raw.len() / 2);
+    let mut integers = Vec::with_capacity(raw.len() + 6);
+    encode_integer(r, &mut integers);
+    encode_integer(s, &mut integers);
+    // Both integers are at most 33 bytes long, so the sequence length always fits into the short form.
+    let mut der = vec![0x30, integers.len() as u8];
+    der.extend_from_slice(&integers);
+    der
+}
@@ -0,0 +1,245 @@
+#!/usr/bin/env python3
+"""Generates synthetic SGX and TDX DCAP quote fixtures signed by a test PCK certificate chain.
+
+Usage: python3 generate.py  (writes files into the directory of this script)
+"""
+
+import datetime
+import hashlib
+import os
+import struct
+
+from cryptography import x509
+from cryptography.hazmat.primitives import hashes, serialization
+from cryptography.hazmat.primitives.asymmetric import ec
+from cryptography.hazmat.primitives.asymmetric.utils import decode_dss_signature
+from cryptography.x509.oid import NameOID
+
+OUT_DIR = os.path.dirname(os.path.abspath(__file__))
+NOT_BEFORE = datetime.datetime(2024, 1, 1, tzinfo=datetime.timezone.utc)
+NOT_AFTER = datetime.datetime(2049, 12, 31, tzinfo=datetime.timezone.utc)
+
+# Enclave measurements and the attested TEE key (compressed secp256k1 public key) embedded into the quote.
+MR_ENCLAVE = hashlib.sha256(b"test enclave").digest()
+MR_SIGNER = hashlib.sha256(b"test enclave signer").digest()
+MR_TD = hashlib.sha384(b"test trust domain").digest()
+RTMRS = [hashlib.sha384(b"test RTMR %d" % i).digest() for i in range(4)]
+# The TEE key is derived deterministically, so that tests can sign proofs with it.
+TEE_SECRET_KEY = hashlib.sha256(b"test TEE key").digest()
+TEE_PUBKEY = (
+    ec.derive_private_key(int.from_bytes(TEE_SECRET_KEY, "big"), ec.SECP256K1())
+    .public_key()
+    .public_bytes(serialization.Encoding.X962, serialization.PublicFormat.CompressedPoint)
+)
+QE_VENDOR_ID = bytes.fromhex("939a7233f79c4ca9940a0db3957f0607")
+# Identity of Intel quoting enclaves: MRSIGNER, and (ISVPRODID, ISVSVN) of the SGX QE3 and the TDX QE.
+QE_MR_SIGNER = bytes.fromhex("8c4f5775d796503e96137f77c68a829a0056ac8ded70140b081b094490c57bff")
+SGX_QE_IDENTITY = (1, 8)
+TDX_QE_IDENTITY = (2, 4)
+# SGX enclave attribute flags: INIT | MODE64BIT, and the same with DEBUG; XFRM is x87 | SSE.
+SGX_FLAGS = 0x05
+SGX_FLAGS_DEBUG = 0x07
+SGX_XFRM = 0x03
+# TDX trust domain attributes: none set, and TUD.DEBUG.
+TD_ATTRIBUTES = 0
+TD_ATTRIBUTES_DEBUG = 0x01
+
+
+def name(common_name):
+    return x509.Name(
+        [
+            x509.NameAttribute(NameOID.COMMON_NAME, common_name),
+            x509.NameAttribute(NameOID.ORGANIZATION_NAME, "Test"),
+        ]
+    )
+
+
+def make_cert(subject, subject_key, issuer, issuer_key, serial, is_ca):
+    builder = (
+        x509.CertificateBuilder()
+        .subject_name(subject)
+        .issuer_name(issuer)
+        .public_key(subject_key.public_key())
+        .serial_number(serial)
+        .not_valid_before(NOT_BEFORE)
+        .not_valid_after(NOT_AFTER)
+        .add_extension(
+            x509.SubjectKeyIdentifier.from_public_key(subject_key.public_key()),
+            critical=False,
+        )
+        .add_extension(
+            x509.AuthorityKeyIdentifier.from_issuer_public_key(issuer_key.public_key()),
+            critical=False,
+        )
+    )
+    if is_ca:
+        builder = builder.add_extension(
+            x509.BasicConstraints(ca=True, path_length=None if subject == issuer else 0),
+            critical=True,
+        ).add_extension(
+            x509.KeyUsage(
+                digital_signature=False,
+                content_commitment=False,
+                key_encipherment=False,
+                data_encipherment=False,
+                key_agreement=False,
+                key_cert_sign=True,
+                crl_sign=True,
+                encipher_only=False,
+                decipher_only=False,
+            ),
+            critical=True,
+        )
+    else:
+        builder = builder.add_extension(
+            x509.BasicConstraints(ca=False, path_length=None), critical=True
+        ).add_extension(
+            x509.KeyUsage(
+                digital_signature=True,
+                content_commitment=True,
+                key_encipherment=False,
+                data_encipherment=False,
+                key_agreement=False,
+                key_cert_sign=False,
+                crl_sign=False,
+                encipher_only=False,
+                decipher_only=False,
+            ),
+            critical=True,
+        )
+    return builder.sign(issuer_key, hashes.SHA256())
+
+
+def make_crl(issuer_cert, issuer_key, revoked_serials):
+    builder = (
+        x509.CertificateRevocationListBuilder()
+        .issuer_name(issuer_cert.subject)
+        .last_update(NOT_BEFORE)
+        .next_update(NOT_AFTER)
+        .add_extension(x509.CRLNumber(1), critical=False)
+        .add_extension(
+            x509.AuthorityKeyIdentifier.from_issuer_public_key(issuer_key.public_key()),
+            critical=False,
+        )
+    )
+    for serial in revoked_serials:
+        builder = builder.add_revoked_certificate(
+            x509.RevokedCertificateBuilder()
+            .serial_number(serial)
+            .revocation_date(NOT_BEFORE)
+            .build()
+        )
+    return builder.sign(issuer_key, hashes.SHA256())
+
+
+def raw_signature(key, message):
+    r, s = decode_dss_signature(key.sign(message, ec.ECDSA(hashes.SHA256())))
+    return r.to_bytes(32, "big") + s.to_bytes(32, "big")
+
+
+def report_body(mr_enclave, mr_signer, report_data, flags=SGX_FLAGS, isv_prod_id=0, isv_svn=0):
+    body = bytearray(384)
+    body[48:64] = struct.pack("<QQ", flags, SGX_XFRM)
+    body[64:96] = mr_enclave
+    body[128:160] = mr_signer
+    body[256:260] = struct.pack("<HH", isv_prod_id, isv_svn)
+    body[320:384] = report_data.ljust(64, b"\0")
+    return bytes(body)
+
+
+def td_report(mr_td, rtmrs, report_data, td_attributes=TD_ATTRIBUTES):
+    report = bytearray(584)
+    report[120:128] = struct.pack("<Q", td_attributes)
+    report[136:184] = mr_td
+    for i, rtmr in enumerate(rtmrs):
+        report[328 + i * 48 : 376 + i * 48] = rtmr
+    report[520:584] = report_data.ljust(64, b"\0")
+    return bytes(report)
+
+
+def main():
+    root_key = ec.generate_private_key(ec.SECP256R1())
+    ca_key = ec.generate_private_key(ec.SECP256R1())
+    pck_key = ec.generate_private_key(ec.SECP256R1())
+    root_name = name("Test SGX Root CA")
+    ca_name = name("Test SGX PCK Processor CA")
+    root = make_cert(root_name, root_key, root_name, root_key, 1, True)
+    ca = make_cert(ca_name, ca_key, root_name, root_key, 2, True)
+    pck = make_cert(name("Test SGX PCK Certificate"), pck_key, ca_name, ca_key, 3, False)
+
+    attestation_key = ec.generate_private_key(ec.SECP256R1())
+    attestation_pubkey = attestation_key.public_key().public_bytes(
+        serialization.Encoding.X962, serialization.PublicFormat.UncompressedPoint
+    )[1:]
+    qe_auth_data = bytes(range(32))
+    cert_chain = b"".join(
+        cert.public_bytes(serialization.Encoding.PEM) for cert in (pck, ca, root)
+    ) + b"\0"
+
+    def make_quote(version, tee_type, body, qe_identity, qe_mr_signer=QE_MR_SIGNER):
+        isv_prod_id, isv_svn = qe_identity
+        qe_report = report_body(
+            hashlib.sha256(b"quoting enclave").digest(),
+            qe_mr_signer,
+            hashlib.sha256(attestation_pubkey + qe_auth_data).digest(),
+            isv_prod_id=isv_prod_id,
+            isv_svn=isv_svn,
+        )
+        qe_certification_data = (
+            qe_report
+            + raw_signature(pck_key, qe_report)
+            + struct.pack("<H", len(qe_auth_data))
+            + qe_auth_data
+            + struct.pack("<HI", 5, len(cert_chain))
+            + cert_chain
+        )
+        header = struct.pack("<HHIHH", version, 2, tee_type, 1, 1) + QE_VENDOR_ID + bytes(20)
+        signature_data = raw_signature(attestation_key, header + body) + attestation_pubkey
+        if version == 3:
+            signature_data += qe_certification_data
+        else:
+            signature_data += struct.pack("<HI", 6, len(qe_certification_data))
+            signature_data += qe_certification_data
+        return header + body + struct.pack("<I", len(signature_data)) + signature_data
+
+    enclave_report = report_body(MR_ENCLAVE, MR_SIGNER, TEE_PUBKEY)
+    quote = make_quote(3, 0, enclave_report, SGX_QE_IDENTITY)
+    debug_quote = make_quote(
+        3, 0, report_body(MR_ENCLAVE, MR_SIGNER, TEE_PUBKEY, flags=SGX_FLAGS_DEBUG), SGX_QE_IDENTITY
+    )
+    outdated_qe_quote = make_quote(3, 0, enclave_report, (SGX_QE_IDENTITY[0], SGX_QE_IDENTITY[1] - 1))
+    untrusted_qe_quote = make_quote(
+        3, 0, enclave_report, SGX_QE_IDENTITY, hashlib.sha256(b"quoting enclave signer").digest()
+    )
+    tdx_quote = make_quote(4, 0x81, td_report(MR_TD, RTMRS, TEE_PUBKEY), TDX_QE_IDENTITY)
+    debug_tdx_quote = make_quote(
+        4, 0x81, td_report(MR_TD, RTMRS, TEE_PUBKEY, TD_ATTRIBUTES_DEBUG), TDX_QE_IDENTITY
+    )
+
+    def write(file_name, data):
+        with open(os.path.join(OUT_DIR, file_name), "wb") as file:
+            file.write(data)
+
+    write("root_ca.pem", root.public_bytes(serialization.Encoding.PEM))
+    write("quote.dat", quote)
+    write("quote_debug.dat", debug_quote)
+    write("quote_outdated_qe.dat", outdated_qe_quote)
+    write("quote_untrusted_qe.dat", untrusted_qe_quote)
+    write("tdx_quote.dat", tdx_quote)
+    write("tdx_quote_debug.dat", debug_tdx_quote)
+    write("pck_crl.der", make_crl(ca, ca_key, []).public_bytes(serialization.Encoding.DER))
+    write(
+        "pck_crl_revoked.der",
+        make_crl(ca, ca_key, [pck.serial_number]).public_bytes(serialization.Encoding.DER),
+    )
+    print("MRENCLAVE:", MR_ENCLAVE.hex())
+    print("MRSIGNER:", MR_SIGNER.hex())
+    print("MRTD:", MR_TD.hex())
+    for i, rtmr in enumerate(RTMRS):
+        print(f"RTMR{i}:", rtmr.hex())
+    print("TEE secret key:", TEE_SECRET_KEY.hex())
+    print("TEE pubkey:", TEE_PUBKEY.hex())
+
+
+if __name__ == "__main__":
+    main()
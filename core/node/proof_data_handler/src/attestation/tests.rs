@@ -0,0 +1,260 @@
+//! Tests for attestation verification based on the fixtures generated by `testdata/generate.py`.
+
+use std::str::FromStr;
+
+use zksync_config::configs::{TdxMeasurementPolicy, TeeConfig};
+use zksync_dal::tee_proof_generation_dal::{TeeAttestationStatus, TeeMeasurements};
+use zksync_types::{tee_types::TeeType, H256};
+
+use super::{quote::Quote, AttestationVerifier, QeIdentity};
+
+pub(crate) const ROOT_CA: &[u8] = include_bytes!("testdata/root_ca.pem");
+pub(crate) const QUOTE: &[u8] = include_bytes!("testdata/quote.dat");
+/// Same as [`QUOTE`], but produced by a debug enclave.
+const DEBUG_QUOTE: &[u8] = include_bytes!("testdata/quote_debug.dat");
+/// Same as [`QUOTE`], but produced by a quoting enclave with an ISVSVN below the minimum.
+const OUTDATED_QE_QUOTE: &[u8] = include_bytes!("testdata/quote_outdated_qe.dat");
+/// Same as [`QUOTE`], but produced by a quoting enclave not signed by Intel.
+const UNTRUSTED_QE_QUOTE: &[u8] = include_bytes!("testdata/quote_untrusted_qe.dat");
+pub(crate) const TDX_QUOTE: &[u8] = include_bytes!("testdata/tdx_quote.dat");
+/// Same as [`TDX_QUOTE`], but produced by a debug trust domain.
+const DEBUG_TDX_QUOTE: &[u8] = include_bytes!("testdata/tdx_quote_debug.dat");
+pub(crate) const PCK_CRL: &[u8] = include_bytes!("testdata/pck_crl.der");
+const PCK_CRL_REVOKED: &[u8] = include_bytes!("testdata/pck_crl_revoked.der");
+
+/// Offset of MRENCLAVE of the attested enclave in the quote.
+const MR_ENCLAVE_OFFSET: usize = 48 + 64;
+/// Offset of MRTD of the trust domain in the TDX quote.
+const MR_TD_OFFSET: usize = 48 + 136;
+
+pub(crate) fn mr_enclave() -> H256 {
+    H256::from_str("8f39f4fe3dc284e9614f67d672f9f8498f301fa93424f9f426e2c1ffe5ebfb5d").unwrap()
+}
+
+fn mr_signer() -> H256 {
+    H256::from_str("0f7ffd2c1a9855e5aed74b3e4caeabc6004fc49348d07e2d7b2c479f401e8844").unwrap()
+}
+
+fn mr_td() -> Vec<u8> {
+    hex::decode(
+        "b0f83a6e391604125002a2402a28c241e6dd253f29b8fc85e642b9f0faf54af2\
+         59b2fcc0f01decb25aca69085cf7b6ea",
+    )
+    .unwrap()
+}
+
+fn rtmr0() -> Vec<u8> {
+    hex::decode(
+        "282c704e8b4ae06dbc9bde8fd3d342f2f6bd411ccee90a225d75c21b22baba81\
+         17f3e4e97f118e29a039bbb98fb203f1",
+    )
+    .unwrap()
+}
+
+pub(crate) fn tee_secret_key() -> secp256k1::SecretKey {
+    let bytes =
+        hex::decode("6bebee00ece1875c403f7f5209051244efbf1b08d13e87641b61eba90b65de7c").unwrap();
+    secp256k1::SecretKey::from_slice(&bytes).unwrap()
+}
+
+pub(crate) fn tee_pubkey() -> Vec<u8> {
+    hex::decode("03039a703c32bfbb62d39bf5dfad27e5a48648edab7436b3da19cdec1b3a682392").unwrap()
+}
+
+pub(crate) fn tdx_policy(rtmr0: Option<Vec<u8>>) -> TdxMeasurementPolicy {
+    TdxMeasurementPolicy {
+        mr_td: mr_td(),
+        rtmrs: [rtmr0, None, None, None],
+    }
+}
+
+fn verifier(allowed_mrenclaves: &[H256], allowed_mrsigners: &[H256]) -> AttestationVerifier {
+    let config = TeeConfig {
+        tee_allowed_mrenclaves: allowed_mrenclaves.to_vec(),
+        tee_allowed_mrsigners: allowed_mrsigners.to_vec(),
+        ..TeeConfig::default()
+    };
+    AttestationVerifier::for_tests(&config, ROOT_CA, vec![PCK_CRL.to_vec()])
+}
+
+fn tdx_verifier(tdx_policies: Vec<TdxMeasurementPolicy>) -> AttestationVerifier {
+    let config = TeeConfig {
+        tee_types: vec![TeeType::Tdx],
+        tee_tdx_policies: tdx_policies,
+        ..TeeConfig::default()
+    };
+    AttestationVerifier::for_tests(&config, ROOT_CA, vec![PCK_CRL.to_vec()])
+}
+
+async fn assert_rejected(verifier: &AttestationVerifier, quote: &[u8], expected_error: &str) {
+    let verification = verifier.verify(&tee_pubkey(), quote).await.unwrap();
+    assert_eq!(verification.status, TeeAttestationStatus::Rejected);
+    let error = verification.error.unwrap();
+    assert!(error.contains(expected_error), "{error}");
+}
+
+#[tokio::test]
+async fn verifying_valid_quote() {
+    for verifier in [
+        verifier(&[mr_enclave()], &[]),
+        verifier(&[], &[mr_signer()]),
+    ] {
+        let verification = verifier.verify(&tee_pubkey(), QUOTE).await.unwrap();
+        assert_eq!(verification.status, TeeAttestationStatus::Verified);
+        assert_eq!(
+            verification.measurements,
+            Some(TeeMeasurements::Sgx {
+                mr_enclave: mr_enclave(),
+                mr_signer: mr_signer(),
+            })
+        );
+        assert_eq!(verification.error, None);
+    }
+}
+
+#[tokio::test]
+async fn rejecting_quote_from_unknown_enclave() {
+    let verifier = verifier(&[H256::repeat_byte(1)], &[H256::repeat_byte(2)]);
+    let verification = verifier.verify(&tee_pubkey(), QUOTE).await.unwrap();
+    assert_eq!(verification.status, TeeAttestationStatus::Rejected);
+    // Measurements are recorded even for rejected quotes.
+    assert_eq!(
+        verification.measurements,
+        Some(TeeMeasurements::Sgx {
+            mr_enclave: mr_enclave(),
+            mr_signer: mr_signer(),
+        })
+    );
+    assert!(verification.error.unwrap().contains("is allowed"));
+}
+
+#[tokio::test]
+async fn rejecting_quote_for_another_key() {
+    let verifier = verifier(&[mr_enclave()], &[]);
+    let mut pubkey = tee_pubkey();
+    pubkey[1] ^= 1;
+    let verification = verifier.verify(&pubkey, QUOTE).await.unwrap();
+    assert_eq!(verification.status, TeeAttestationStatus::Rejected);
+    assert!(verification.error.unwrap().contains("public key"));
+}
+
+#[tokio::test]
+async fn rejecting_tampered_quote() {
+    let verifier = verifier(&[mr_enclave()], &[]);
+    let mut quote = QUOTE.to_vec();
+    quote[MR_ENCLAVE_OFFSET] ^= 1;
+    assert_rejected(&verifier, &quote, "quote signature is invalid").await;
+
+    assert_rejected(&verifier, &QUOTE[..1_000], "malformed quote").await;
+    assert_rejected(&verifier, b"not a quote", "malformed quote").await;
+}
+
+#[tokio::test]
+async fn rejecting_quote_from_debug_enclave() {
+    let verifier = verifier(&[mr_enclave()], &[mr_signer()]);
+    assert_rejected(&verifier, DEBUG_QUOTE, "debug mode").await;
+}
+
+#[tokio::test]
+async fn rejecting_quote_from_untrusted_quoting_enclave() {
+    let verifier = verifier(&[mr_enclave()], &[]);
+    assert_rejected(&verifier, UNTRUSTED_QE_QUOTE, "is not trusted").await;
+    assert_rejected(&verifier, OUTDATED_QE_QUOTE, "below the minimum").await;
+}
+
+#[test]
+fn quoting_enclave_product_id_depends_on_tee_type() {
+    let sgx_qe_report = Quote::parse(QUOTE).unwrap().qe_report;
+    QeIdentity::SGX.check(&sgx_qe_report).unwrap();
+    let err = QeIdentity::TDX.check(&sgx_qe_report).unwrap_err();
+    assert!(err.to_string().contains("ISVPRODID"), "{err}");
+
+    let tdx_qe_report = Quote::parse(TDX_QUOTE).unwrap().qe_report;
+    QeIdentity::TDX.check(&tdx_qe_report).unwrap();
+}
+
+#[tokio::test]
+async fn rejecting_quote_with_revoked_pck_certificate() {
+    let config = TeeConfig {
+        tee_allowed_mrenclaves: vec![mr_enclave()],
+        ..TeeConfig::default()
+    };
+    let verifier = AttestationVerifier::for_tests(&config, ROOT_CA, vec![PCK_CRL_REVOKED.to_vec()]);
+    assert_rejected(&verifier, QUOTE, "PCK certificate chain is invalid").await;
+
+    let verifier = AttestationVerifier::for_tests(&config, ROOT_CA, vec![]);
+    assert_rejected(&verifier, QUOTE, "no PCK CRLs").await;
+}
+
+#[tokio::test]
+async fn verifying_valid_tdx_quote() {
+    for policy in [tdx_policy(None), tdx_policy(Some(rtmr0()))] {
+        let verifier = tdx_verifier(vec![policy]);
+        let verification = verifier.verify(&tee_pubkey(), TDX_QUOTE).await.unwrap();
+        assert_eq!(
+            verification.status,
+            TeeAttestationStatus::Verified,
+            "{verification:?}"
+        );
+        match verification.measurements {
+            Some(TeeMeasurements::Tdx {
+                mr_td: actual,
+                rtmrs,
+            }) => {
+                assert_eq!(actual, mr_td());
+                assert_eq!(rtmrs[0], rtmr0());
+            }
+            other => panic!("unexpected measurements: {other:?}"),
+        }
+    }
+}
+
+#[tokio::test]
+async fn rejecting_tdx_quote_violating_policy() {
+    let wrong_rtmr_verifier = tdx_verifier(vec![tdx_policy(Some(vec![0; 48]))]);
+    assert_rejected(
+        &wrong_rtmr_verifier,
+        TDX_QUOTE,
+        "don't satisfy any TDX policy",
+    )
+    .await;
+
+    // SGX allowlists don't apply to TDX quotes and vice versa.
+    let sgx_verifier = verifier(&[mr_enclave()], &[mr_signer()]);
+    assert_rejected(&sgx_verifier, TDX_QUOTE, "don't satisfy any TDX policy").await;
+    let tdx_verifier = tdx_verifier(vec![tdx_policy(None)]);
+    assert_rejected(&tdx_verifier, QUOTE, "is allowed").await;
+}
+
+#[tokio::test]
+async fn rejecting_quote_from_debug_trust_domain() {
+    let verifier = tdx_verifier(vec![tdx_policy(None)]);
+    assert_rejected(&verifier, DEBUG_TDX_QUOTE, "debug mode").await;
+}
+
+#[tokio::test]
+async fn rejecting_tampered_tdx_quote() {
+    let verifier = tdx_verifier(vec![tdx_policy(None)]);
+    let mut quote = TDX_QUOTE.to_vec();
+    quote[MR_TD_OFFSET] ^= 1;
+    assert_rejected(&verifier, &quote, "quote signature is invalid").await;
+
+    // Unknown TEE type in the quote header
+    let mut quote = TDX_QUOTE.to_vec();
+    quote[4] = 0x42;
+    assert_rejected(&verifier, &quote, "unsupported TEE type").await;
+}
+
+#[test]
+fn tdx_policy_with_invalid_register_length_is_rejected() {
+    let config = TeeConfig {
+        tee_tdx_policies: vec![TdxMeasurementPolicy {
+            mr_td: vec![0; 32],
+            rtmrs: Default::default(),
+        }],
+        ..TeeConfig::default()
+    };
+    let collateral = super::CollateralCache::fixed(super::QuoteCollateral { pck_crls: vec![] });
+    let err = AttestationVerifier::from_parts(&config, ROOT_CA, collateral).unwrap_err();
+    assert!(err.to_string().contains("invalid length"), "{err}");
+}
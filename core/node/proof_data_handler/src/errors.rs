@@ -4,11 +4,20 @@ use axum::{
 };
 use zksync_dal::DalError;
 use zksync_object_store::ObjectStoreError;
+use zksync_types::tee_types::TeeType;
 
 pub(crate) enum RequestProcessorError {
     GeneralError(String),
     ObjectStore(ObjectStoreError),
     Dal(DalError),
+    /// The attestation quote failed verification.
+    AttestationRejected(String),
+    /// The TEE proof is signed by a key without a verified attestation.
+    UnattestedKey,
+    /// The TEE type is not among the configured TEE types.
+    UnsupportedTeeType(TeeType),
+    /// The TEE proof doesn't match the L1 batch or isn't signed by the submitted key.
+    InvalidProof(String),
 }
 
 impl From<DalError> for RequestProcessorError {
@@ -41,6 +50,22 @@ impl IntoResponse for RequestProcessorError {
                     "Failed fetching/saving from db".to_owned(),
                 )
             }
+            Self::AttestationRejected(err) => {
+                tracing::warn!("Attestation rejected: {err}");
+                (
+                    StatusCode::BAD_REQUEST,
+                    format!("Attestation rejected: {err}"),
+                )
+            }
+            Self::UnattestedKey => (
+                StatusCode::FORBIDDEN,
+                "Proof is signed by a key without a verified attestation".to_owned(),
+            ),
+            Self::UnsupportedTeeType(tee_type) => (
+                StatusCode::BAD_REQUEST,
+                format!("TEE type {tee_type} is not supported"),
+            ),
+            Self::InvalidProof(err) => (StatusCode::BAD_REQUEST, format!("Invalid proof: {err}")),
         };
         (status_code, message).into_response()
     }
@@ -1,6 +1,7 @@
 use std::{net::SocketAddr, sync::Arc};
 
 use anyhow::Context as _;
+use attestation::AttestationVerifier;
 use axum::{extract::Path, http::StatusCode, response::IntoResponse, routing::post, Json, Router};
 use request_processor::RequestProcessor;
 use tee_request_processor::TeeRequestProcessor;
@@ -17,6 +18,7 @@ use zksync_types::{commitment::L1BatchCommitmentMode, L2ChainId};
 #[cfg(test)]
 mod tests;
 
+mod attestation;
 mod errors;
 mod metrics;
 mod request_processor;
@@ -32,12 +34,20 @@ pub async fn run_server(
 ) -> anyhow::Result<()> {
     let bind_address = SocketAddr::from(([0, 0, 0, 0], config.http_port));
     tracing::info!("Starting proof data handler server on {bind_address}");
+    let attestation_verifier = if config.tee_config.tee_attestation_verification {
+        let verifier = AttestationVerifier::new(&config.tee_config)
+            .context("failed initializing TEE attestation verifier")?;
+        Some(Arc::new(verifier))
+    } else {
+        None
+    };
     let app = create_proof_processing_router(
         blob_store,
         connection_pool,
         config,
         commitment_mode,
         l2_chain_id,
+        attestation_verifier,
     );
 
     let listener = tokio::net::TcpListener::bind(bind_address)
@@ -62,6 +72,7 @@ fn create_proof_processing_router(
     config: ProofDataHandlerConfig,
     commitment_mode: L1BatchCommitmentMode,
     l2_chain_id: L2ChainId,
+    attestation_verifier: Option<Arc<AttestationVerifier>>,
 ) -> Router {
     let get_proof_gen_processor = RequestProcessor::new(
         blob_store.clone(),
@@ -95,8 +106,13 @@ fn create_proof_processing_router(
         );
 
     if config.tee_config.tee_support {
-        let get_tee_proof_gen_processor =
-            TeeRequestProcessor::new(blob_store, connection_pool, config.clone(), l2_chain_id);
+        let get_tee_proof_gen_processor = TeeRequestProcessor::new(
+            blob_store,
+            connection_pool,
+            config.clone(),
+            l2_chain_id,
+            attestation_verifier,
+        );
         let submit_tee_proof_processor = get_tee_proof_gen_processor.clone();
         let register_tee_attestation_processor = get_tee_proof_gen_processor.clone();
 
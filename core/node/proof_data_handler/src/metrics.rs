@@ -1,6 +1,7 @@
 use std::{fmt, time::Duration};
 
-use vise::{EncodeLabelSet, EncodeLabelValue, Family, Histogram, Metrics, Unit};
+use vise::{Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics, Unit};
+use zksync_dal::tee_proof_generation_dal::TeeAttestationStatus;
 use zksync_object_store::bincode;
 use zksync_prover_interface::inputs::WitnessInputData;
 use zksync_types::tee_types::TeeType;
@@ -19,6 +20,10 @@ pub(super) struct ProofDataHandlerMetrics {
     pub total_blob_size_in_mb: Histogram<u64>,
     #[metrics(buckets = vise::Buckets::LATENCIES, unit = Unit::Seconds)]
     pub tee_proof_roundtrip_time: Family<MetricsTeeType, Histogram<Duration>>,
+    /// Number of registered TEE attestations by their verification status.
+    pub tee_attestations: Family<MetricsAttestationStatus, Counter>,
+    /// Last L1 batch proven by all configured TEE types.
+    pub last_tee_verified_batch: Gauge<u64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, EncodeLabelSet, EncodeLabelValue)]
@@ -37,6 +42,16 @@ impl From<TeeType> for MetricsTeeType {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, EncodeLabelSet, EncodeLabelValue)]
+#[metrics(label = "status")]
+pub(crate) struct MetricsAttestationStatus(pub TeeAttestationStatus);
+
+impl fmt::Display for MetricsAttestationStatus {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(formatter)
+    }
+}
+
 impl ProofDataHandlerMetrics {
     pub fn observe_blob_sizes(&self, blob: &WitnessInputData) {
         let vm_run_data_blob_size_in_mb =
@@ -2,9 +2,12 @@ use std::sync::Arc;
 
 use axum::{extract::Path, Json};
 use chrono::{Duration as ChronoDuration, Utc};
+use secp256k1::{ecdsa::Signature, Message, PublicKey, SECP256K1};
 use zksync_config::configs::ProofDataHandlerConfig;
 use zksync_dal::{
-    tee_proof_generation_dal::{LockedBatch, TeeProofGenerationJobStatus},
+    tee_proof_generation_dal::{
+        LockedBatch, TeeAttestationStatus, TeeAttestationVerification, TeeProofGenerationJobStatus,
+    },
     ConnectionPool, Core, CoreDal,
 };
 use zksync_object_store::{ObjectStore, ObjectStoreError};
@@ -17,10 +20,14 @@ use zksync_prover_interface::{
         TeeVerifierInput, V1TeeVerifierInput, VMRunWitnessInputData, WitnessInputMerklePaths,
     },
 };
-use zksync_types::{tee_types::TeeType, L1BatchNumber, L2ChainId};
+use zksync_types::{tee_types::TeeType, L1BatchNumber, L2ChainId, H256};
 use zksync_vm_executor::storage::L1BatchParamsProvider;
 
-use crate::{errors::RequestProcessorError, metrics::METRICS};
+use crate::{
+    attestation::AttestationVerifier,
+    errors::RequestProcessorError,
+    metrics::{MetricsAttestationStatus, METRICS},
+};
 
 #[derive(Clone)]
 pub(crate) struct TeeRequestProcessor {
@@ -28,6 +35,8 @@ pub(crate) struct TeeRequestProcessor {
     pool: ConnectionPool<Core>,
     config: ProofDataHandlerConfig,
     l2_chain_id: L2ChainId,
+    /// Verifies attestations; `None` if attestation verification is disabled.
+    attestation_verifier: Option<Arc<AttestationVerifier>>,
 }
 
 impl TeeRequestProcessor {
@@ -36,12 +45,14 @@ impl TeeRequestProcessor {
         pool: ConnectionPool<Core>,
         config: ProofDataHandlerConfig,
         l2_chain_id: L2ChainId,
+        attestation_verifier: Option<Arc<AttestationVerifier>>,
     ) -> Self {
         Self {
             blob_store,
             pool,
             config,
             l2_chain_id,
+            attestation_verifier,
         }
     }
 
@@ -51,6 +62,10 @@ impl TeeRequestProcessor {
     ) -> Result<Option<Json<TeeProofGenerationDataResponse>>, RequestProcessorError> {
         tracing::info!("Received request for proof generation data: {:?}", request);
 
+        if !self.config.tee_config.tee_types.contains(&request.tee_type) {
+            return Err(RequestProcessorError::UnsupportedTeeType(request.tee_type));
+        }
+
         let batch_ignored_timeout = ChronoDuration::from_std(
             self.config
                 .tee_config
@@ -109,11 +124,25 @@ impl TeeRequestProcessor {
         }
     }
 
+    /// Returns the TEE verifier input for the batch. The input is built and saved to the object store once per batch
+    /// (keyed by the batch number), so that all TEE types and retries are served the same input, and disputed proofs
+    /// can be re-verified offline. Saved inputs are never removed by the node; retention must be configured
+    /// for the `tee_verifier_inputs` bucket in the object store (e.g., as an object lifecycle rule).
     #[tracing::instrument(skip(self))]
     async fn tee_verifier_input_for_existing_batch(
         &self,
         l1_batch_number: L1BatchNumber,
     ) -> Result<TeeVerifierInput, RequestProcessorError> {
+        match self.blob_store.get(l1_batch_number).await {
+            Ok(input) => return Ok(input),
+            Err(ObjectStoreError::KeyNotFound(_)) => { /* the input needs to be built */ }
+            Err(err) => {
+                tracing::warn!(
+                    "Failed loading saved TEE verifier input for L1 batch {l1_batch_number}, rebuilding it: {err}"
+                );
+            }
+        }
+
         let vm_run_data: VMRunWitnessInputData = self
             .blob_store
             .get(l1_batch_number)
@@ -160,14 +189,20 @@ impl TeeRequestProcessor {
                 "system_env, l1_batch_env missing".into(),
             ))?;
 
-        Ok(TeeVerifierInput::new(V1TeeVerifierInput {
+        let input = TeeVerifierInput::new(V1TeeVerifierInput {
             vm_run_data,
             merkle_paths,
             l2_blocks_execution_data,
             l1_batch_env,
             system_env,
             pubdata_params,
-        }))
+        });
+        if let Err(err) = self.blob_store.put(l1_batch_number, &input).await {
+            tracing::warn!(
+                "Failed saving TEE verifier input for L1 batch {l1_batch_number}: {err}"
+            );
+        }
+        Ok(input)
     }
 
     async fn lock_batch_for_proving(
@@ -210,6 +245,54 @@ impl TeeRequestProcessor {
     ) -> Result<Json<SubmitProofResponse>, RequestProcessorError> {
         let l1_batch_number = L1BatchNumber(l1_batch_number);
         let mut connection = self.pool.connection_tagged("tee_request_processor").await?;
+
+        if !self.config.tee_config.tee_types.contains(&proof.0.tee_type) {
+            return Err(RequestProcessorError::UnsupportedTeeType(proof.0.tee_type));
+        }
+        if self.attestation_verifier.is_some() {
+            let info = connection
+                .tee_proof_generation_dal()
+                .get_attestation_info(&proof.0.pubkey)
+                .await?;
+            let is_attested = info.map_or(false, |info| {
+                info.status == TeeAttestationStatus::Verified
+                    && info.tee_type == Some(proof.0.tee_type)
+            });
+            if !is_attested {
+                tracing::warn!(
+                    "Rejecting {} proof for batch {l1_batch_number} signed by key {} with attestation {info:?}",
+                    proof.0.tee_type,
+                    hex::encode(&proof.0.pubkey)
+                );
+                return Err(RequestProcessorError::UnattestedKey);
+            }
+        }
+
+        let root_hash = connection
+            .blocks_dal()
+            .get_l1_batch_state_root(l1_batch_number)
+            .await?
+            .ok_or_else(|| {
+                RequestProcessorError::GeneralError(format!(
+                    "Missing root hash for L1 batch {l1_batch_number}"
+                ))
+            })?;
+        if proof.0.proof != root_hash.as_bytes() {
+            return Err(RequestProcessorError::InvalidProof(format!(
+                "proof doesn't match root hash {root_hash:?} of L1 batch {l1_batch_number}"
+            )));
+        }
+        if let Err(err) = verify_proof_signature(&proof.0.pubkey, &proof.0.signature, root_hash) {
+            tracing::warn!(
+                "Rejecting {} proof for batch {l1_batch_number} with invalid signature by key {}: {err}",
+                proof.0.tee_type,
+                hex::encode(&proof.0.pubkey)
+            );
+            return Err(RequestProcessorError::InvalidProof(format!(
+                "invalid signature: {err}"
+            )));
+        }
+
         let mut dal = connection.tee_proof_generation_dal();
 
         dal.save_proof_artifacts_metadata(
@@ -221,6 +304,20 @@ impl TeeRequestProcessor {
         )
         .await?;
 
+        let is_tee_verified = connection
+            .tee_proof_generation_dal()
+            .is_batch_tee_verified(l1_batch_number, &self.config.tee_config.tee_types)
+            .await?;
+        if is_tee_verified {
+            tracing::info!(
+                "L1 batch {l1_batch_number} is proven by all required TEE types: {:?}",
+                self.config.tee_config.tee_types
+            );
+            METRICS
+                .last_tee_verified_batch
+                .set(l1_batch_number.0.into());
+        }
+
         let sealed_at = connection
             .blocks_dal()
             .get_batch_sealed_at(l1_batch_number)
@@ -251,12 +348,42 @@ impl TeeRequestProcessor {
     ) -> Result<Json<RegisterTeeAttestationResponse>, RequestProcessorError> {
         tracing::info!("Received attestation: {:?}", payload);
 
-        let mut connection = self.pool.connection_tagged("tee_request_processor").await?;
-        let mut dal = connection.tee_proof_generation_dal();
+        let verification = match &self.attestation_verifier {
+            Some(verifier) => verifier
+                .verify(&payload.pubkey, &payload.attestation)
+                .await
+                .map_err(|err| RequestProcessorError::GeneralError(format!("{err:#}")))?,
+            None => TeeAttestationVerification::unverified(),
+        };
+        METRICS.tee_attestations[&MetricsAttestationStatus(verification.status)].inc();
 
-        dal.save_attestation(&payload.pubkey, &payload.attestation)
+        let mut connection = self.pool.connection_tagged("tee_request_processor").await?;
+        connection
+            .tee_proof_generation_dal()
+            .save_attestation(&payload.pubkey, &payload.attestation, &verification)
             .await?;
 
+        if verification.status == TeeAttestationStatus::Rejected {
+            let err = verification.error.unwrap_or_default();
+            return Err(RequestProcessorError::AttestationRejected(err));
+        }
         Ok(Json(RegisterTeeAttestationResponse::Success))
     }
 }
+
+/// Checks that `signature` is a secp256k1 ECDSA signature of the L1 batch `root_hash` made with the private key
+/// corresponding to `pubkey`. Signatures are in the format produced by TEE provers: a 64-byte compact signature
+/// followed by the recovery ID, which isn't needed since the public key is known.
+fn verify_proof_signature(
+    pubkey: &[u8],
+    signature: &[u8],
+    root_hash: H256,
+) -> Result<(), secp256k1::Error> {
+    if signature.len() != 65 {
+        return Err(secp256k1::Error::InvalidSignature);
+    }
+    let pubkey = PublicKey::from_slice(pubkey)?;
+    let signature = Signature::from_compact(&signature[..64])?;
+    let message = Message::from_slice(root_hash.as_bytes())?;
+    SECP256K1.verify_ecdsa(&message, &signature, &pubkey)
+}
@@ -1,20 +1,34 @@
+use std::sync::Arc;
+
 use axum::{
     body::Body,
     http::{self, Method, Request, StatusCode},
     response::Response,
     Router,
 };
+use secp256k1::{Message, PublicKey, SecretKey, SECP256K1};
 use serde_json::json;
 use tower::ServiceExt;
 use zksync_config::configs::{ProofDataHandlerConfig, TeeConfig};
-use zksync_dal::{ConnectionPool, CoreDal};
+use zksync_dal::{
+    tee_proof_generation_dal::{TeeAttestationStatus, TeeAttestationVerification},
+    ConnectionPool, CoreDal,
+};
+use zksync_node_test_utils::create_l1_batch;
 use zksync_object_store::MockObjectStore;
-use zksync_prover_interface::api::SubmitTeeProofRequest;
+use zksync_prover_interface::{
+    api::{RegisterTeeAttestationRequest, SubmitTeeProofRequest},
+    inputs::TeeVerifierInput,
+    outputs::L1BatchTeeProofForL1,
+};
 use zksync_types::{
-    commitment::L1BatchCommitmentMode, tee_types::TeeType, L1BatchNumber, L2ChainId,
+    commitment::L1BatchCommitmentMode, tee_types::TeeType, L1BatchNumber, L2ChainId, H256,
 };
 
-use crate::create_proof_processing_router;
+use crate::{
+    attestation::{tests as fixtures, AttestationVerifier},
+    create_proof_processing_router,
+};
 
 #[tokio::test]
 async fn request_tee_proof_inputs() {
@@ -31,10 +45,12 @@ async fn request_tee_proof_inputs() {
                 first_tee_processed_batch: L1BatchNumber(0),
                 tee_proof_generation_timeout_in_secs: 600,
                 tee_batch_permanently_ignored_timeout_in_hours: 10 * 24,
+                ..TeeConfig::default()
             },
         },
         L1BatchCommitmentMode::Rollup,
         L2ChainId::default(),
+        None,
     );
     let test_cases = vec![
         (json!({ "tee_type": "sgx" }), StatusCode::NO_CONTENT),
@@ -63,6 +79,59 @@ async fn request_tee_proof_inputs() {
     }
 }
 
+// Test that the TEE verifier input saved for a batch is served for all TEE types instead of being rebuilt
+#[tokio::test]
+async fn saved_tee_verifier_input_is_reused() {
+    let batch_number = L1BatchNumber(1);
+    let db_conn_pool = ConnectionPool::test_pool().await;
+    mock_l1_batch_root_hash(&db_conn_pool, batch_number).await;
+    let mut connection = db_conn_pool.connection().await.unwrap();
+    let mut dal = connection.proof_generation_dal();
+    dal.insert_proof_generation_details(batch_number)
+        .await
+        .unwrap();
+    dal.save_vm_runner_artifacts_metadata(batch_number, "vm_run_data")
+        .await
+        .unwrap();
+    dal.save_merkle_paths_artifacts_metadata(batch_number, "merkle_paths")
+        .await
+        .unwrap();
+
+    // Inputs used to build the TEE verifier input are missing, so the saved input must be served.
+    let blob_store = MockObjectStore::arc();
+    blob_store
+        .put(batch_number, &TeeVerifierInput::V0)
+        .await
+        .unwrap();
+    let app = create_proof_processing_router(
+        blob_store.clone(),
+        db_conn_pool.clone(),
+        ProofDataHandlerConfig {
+            http_port: 1337,
+            proof_generation_timeout_in_secs: 10,
+            tee_config: TeeConfig {
+                tee_support: true,
+                tee_types: vec![TeeType::Sgx, TeeType::Tdx],
+                ..TeeConfig::default()
+            },
+        },
+        L1BatchCommitmentMode::Rollup,
+        L2ChainId::default(),
+        None,
+    );
+
+    for tee_type in ["sgx", "tdx"] {
+        let response =
+            send_request(&app, "/tee/proof_inputs", json!({ "tee_type": tee_type })).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let input: TeeVerifierInput = serde_json::from_slice(&body).unwrap();
+        assert_eq!(input, TeeVerifierInput::V0);
+    }
+}
+
 // Test /tee/submit_proofs endpoint using a mocked TEE proof and verify response and db state
 #[tokio::test]
 async fn submit_tee_proof() {
@@ -70,15 +139,14 @@ async fn submit_tee_proof() {
     let db_conn_pool = ConnectionPool::test_pool().await;
 
     mock_tee_batch_status(db_conn_pool.clone(), batch_number).await;
+    let root_hash = mock_l1_batch_root_hash(&db_conn_pool, batch_number).await;
 
-    let tee_proof_request_str = r#"{
-        "signature": "0001020304",
-        "pubkey": "0506070809",
-        "proof": "0A0B0C0D0E",
-        "tee_type": "sgx"
-    }"#;
-    let tee_proof_request =
-        serde_json::from_str::<SubmitTeeProofRequest>(tee_proof_request_str).unwrap();
+    let tee_proof_request = signed_tee_proof(
+        &fixtures::tee_secret_key(),
+        root_hash,
+        root_hash,
+        TeeType::Sgx,
+    );
     let uri = format!("/tee/submit_proofs/{}", batch_number.0);
     let app = create_proof_processing_router(
         MockObjectStore::arc(),
@@ -91,10 +159,12 @@ async fn submit_tee_proof() {
                 first_tee_processed_batch: L1BatchNumber(0),
                 tee_proof_generation_timeout_in_secs: 600,
                 tee_batch_permanently_ignored_timeout_in_hours: 10 * 24,
+                ..TeeConfig::default()
             },
         },
         L1BatchCommitmentMode::Rollup,
         L2ChainId::default(),
+        None,
     );
 
     // this should fail because we haven't saved the attestation for the pubkey yet
@@ -108,7 +178,11 @@ async fn submit_tee_proof() {
     let mut proof_dal = db_conn_pool.connection().await.unwrap();
     proof_dal
         .tee_proof_generation_dal()
-        .save_attestation(&tee_proof_request.0.pubkey, &attestation)
+        .save_attestation(
+            &tee_proof_request.0.pubkey,
+            &attestation,
+            &TeeAttestationVerification::unverified(),
+        )
         .await
         .expect("Failed to save attestation");
 
@@ -149,6 +223,238 @@ async fn submit_tee_proof() {
     assert_eq!(proof.pubkey.as_ref().unwrap(), &tee_proof_request.0.pubkey);
 }
 
+// Test /tee/register_attestation and /tee/submit_proofs endpoints with attestation verification enabled
+#[tokio::test]
+async fn submit_tee_proof_with_attestation_verification() {
+    let batch_number = L1BatchNumber::from(1);
+    let db_conn_pool = ConnectionPool::test_pool().await;
+
+    mock_tee_batch_status(db_conn_pool.clone(), batch_number).await;
+    let root_hash = mock_l1_batch_root_hash(&db_conn_pool, batch_number).await;
+
+    let tee_config = TeeConfig {
+        tee_support: true,
+        tee_attestation_verification: true,
+        tee_allowed_mrenclaves: vec![fixtures::mr_enclave()],
+        tee_types: vec![TeeType::Sgx, TeeType::Tdx],
+        tee_tdx_policies: vec![fixtures::tdx_policy(None)],
+        ..TeeConfig::default()
+    };
+    let verifier = AttestationVerifier::for_tests(
+        &tee_config,
+        fixtures::ROOT_CA,
+        vec![fixtures::PCK_CRL.to_vec()],
+    );
+    let app = create_proof_processing_router(
+        MockObjectStore::arc(),
+        db_conn_pool.clone(),
+        ProofDataHandlerConfig {
+            http_port: 1337,
+            proof_generation_timeout_in_secs: 10,
+            tee_config,
+        },
+        L1BatchCommitmentMode::Rollup,
+        L2ChainId::default(),
+        Some(Arc::new(verifier)),
+    );
+
+    // a key attested by a malformed quote should be rejected
+
+    let rejected_pubkey = vec![5, 6, 7, 8, 9];
+    let request = RegisterTeeAttestationRequest {
+        attestation: vec![1, 2, 3],
+        pubkey: rejected_pubkey.clone(),
+    };
+    let response = send_request(&app, "/tee/register_attestation", json!(request)).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let mut connection = db_conn_pool.connection().await.unwrap();
+    let status = connection
+        .tee_proof_generation_dal()
+        .get_attestation_info(&rejected_pubkey)
+        .await
+        .unwrap()
+        .map(|info| info.status);
+    assert_eq!(status, Some(TeeAttestationStatus::Rejected));
+
+    // proofs signed by the rejected key should not be accepted
+
+    let uri = format!("/tee/submit_proofs/{}", batch_number.0);
+    let mut tee_proof_request = signed_tee_proof(
+        &fixtures::tee_secret_key(),
+        root_hash,
+        root_hash,
+        TeeType::Sgx,
+    );
+    tee_proof_request.0.pubkey = rejected_pubkey;
+    let response = send_submit_tee_proof_request(&app, &uri, &tee_proof_request).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // a key attested by a valid quote should be accepted
+
+    let request = RegisterTeeAttestationRequest {
+        attestation: fixtures::QUOTE.to_vec(),
+        pubkey: fixtures::tee_pubkey(),
+    };
+    let response = send_request(&app, "/tee/register_attestation", json!(request)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let status = connection
+        .tee_proof_generation_dal()
+        .get_attestation_info(&fixtures::tee_pubkey())
+        .await
+        .unwrap()
+        .map(|info| info.status);
+    assert_eq!(status, Some(TeeAttestationStatus::Verified));
+
+    // a verified attestation cannot be overwritten by a rejected one
+
+    let request = RegisterTeeAttestationRequest {
+        attestation: vec![1, 2, 3],
+        pubkey: fixtures::tee_pubkey(),
+    };
+    let response = send_request(&app, "/tee/register_attestation", json!(request)).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let status = connection
+        .tee_proof_generation_dal()
+        .get_attestation_info(&fixtures::tee_pubkey())
+        .await
+        .unwrap()
+        .map(|info| info.status);
+    assert_eq!(status, Some(TeeAttestationStatus::Verified));
+
+    // proofs signed by the verified key should only be accepted for the attested TEE type
+
+    tee_proof_request.0.pubkey = fixtures::tee_pubkey();
+    tee_proof_request.0.tee_type = TeeType::Tdx;
+    let response = send_submit_tee_proof_request(&app, &uri, &tee_proof_request).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    tee_proof_request.0.tee_type = TeeType::Sgx;
+    let response = send_submit_tee_proof_request(&app, &uri, &tee_proof_request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+// Test that /tee/submit_proofs rejects proofs with forged signatures or for a wrong root hash
+#[tokio::test]
+async fn submit_tee_proof_with_invalid_signature() {
+    let batch_number = L1BatchNumber::from(1);
+    let db_conn_pool = ConnectionPool::test_pool().await;
+    mock_tee_batch_status(db_conn_pool.clone(), batch_number).await;
+    let root_hash = mock_l1_batch_root_hash(&db_conn_pool, batch_number).await;
+
+    let app = create_proof_processing_router(
+        MockObjectStore::arc(),
+        db_conn_pool.clone(),
+        ProofDataHandlerConfig {
+            http_port: 1337,
+            proof_generation_timeout_in_secs: 10,
+            tee_config: TeeConfig {
+                tee_support: true,
+                ..TeeConfig::default()
+            },
+        },
+        L1BatchCommitmentMode::Rollup,
+        L2ChainId::default(),
+        None,
+    );
+    let mut connection = db_conn_pool.connection().await.unwrap();
+    connection
+        .tee_proof_generation_dal()
+        .save_attestation(
+            &fixtures::tee_pubkey(),
+            &[1, 2, 3],
+            &TeeAttestationVerification::unverified(),
+        )
+        .await
+        .unwrap();
+    let uri = format!("/tee/submit_proofs/{}", batch_number.0);
+    let secret_key = fixtures::tee_secret_key();
+    let other_hash = H256::repeat_byte(0x23);
+
+    // the signature is made by another key
+    let mut request = signed_tee_proof(
+        &SecretKey::from_slice(&[0x42; 32]).unwrap(),
+        root_hash,
+        root_hash,
+        TeeType::Sgx,
+    );
+    request.0.pubkey = fixtures::tee_pubkey();
+    let response = send_submit_tee_proof_request(&app, &uri, &request).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // the signature is made for another message
+    let request = signed_tee_proof(&secret_key, root_hash, other_hash, TeeType::Sgx);
+    let response = send_submit_tee_proof_request(&app, &uri, &request).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // the signature is malformed
+    let mut request = signed_tee_proof(&secret_key, root_hash, root_hash, TeeType::Sgx);
+    request.0.signature.truncate(10);
+    let response = send_submit_tee_proof_request(&app, &uri, &request).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // the proof is correctly signed, but isn't for the batch root hash
+    let request = signed_tee_proof(&secret_key, other_hash, other_hash, TeeType::Sgx);
+    let response = send_submit_tee_proof_request(&app, &uri, &request).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let proofs = connection
+        .tee_proof_generation_dal()
+        .get_tee_proofs(batch_number, Some(TeeType::Sgx))
+        .await
+        .unwrap();
+    assert_eq!(proofs.len(), 1);
+    assert_eq!(proofs[0].proof, None);
+
+    let request = signed_tee_proof(&secret_key, root_hash, root_hash, TeeType::Sgx);
+    let response = send_submit_tee_proof_request(&app, &uri, &request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+/// Inserts a mock L1 batch with a root hash, which TEE proofs for the batch must be signed over.
+async fn mock_l1_batch_root_hash(
+    db_conn_pool: &ConnectionPool<zksync_dal::Core>,
+    batch_number: L1BatchNumber,
+) -> H256 {
+    let root_hash = H256::repeat_byte(0x11);
+    let mut connection = db_conn_pool.connection().await.unwrap();
+    connection
+        .blocks_dal()
+        .insert_mock_l1_batch(&create_l1_batch(batch_number.0))
+        .await
+        .unwrap();
+    connection
+        .blocks_dal()
+        .set_l1_batch_hash(batch_number, root_hash)
+        .await
+        .unwrap();
+    root_hash
+}
+
+/// Creates a TEE proof for `root_hash` with the signature of `signed_hash` in the format used by TEE provers.
+fn signed_tee_proof(
+    secret_key: &SecretKey,
+    root_hash: H256,
+    signed_hash: H256,
+    tee_type: TeeType,
+) -> SubmitTeeProofRequest {
+    let message = Message::from_slice(signed_hash.as_bytes()).unwrap();
+    let (recovery_id, signature) = SECP256K1
+        .sign_ecdsa_recoverable(&message, secret_key)
+        .serialize_compact();
+    let mut signature = signature.to_vec();
+    signature.push(recovery_id.to_i32() as u8);
+    SubmitTeeProofRequest(Box::new(L1BatchTeeProofForL1 {
+        signature,
+        pubkey: PublicKey::from_secret_key(SECP256K1, secret_key)
+            .serialize()
+            .to_vec(),
+        proof: root_hash.as_bytes().to_vec(),
+        tee_type,
+    }))
+}
+
 // Mock SQL db with information about the status of the TEE proof generation
 async fn mock_tee_batch_status(
     db_conn_pool: ConnectionPool<zksync_dal::Core>,
@@ -184,7 +490,11 @@ async fn send_submit_tee_proof_request(
     uri: &str,
     tee_proof_request: &SubmitTeeProofRequest,
 ) -> Response {
-    let req_body = Body::from(serde_json::to_vec(tee_proof_request).unwrap());
+    send_request(app, uri, serde_json::to_value(tee_proof_request).unwrap()).await
+}
+
+async fn send_request(app: &Router, uri: &str, body: serde_json::Value) -> Response {
+    let req_body = Body::from(serde_json::to_vec(&body).unwrap());
     app.clone()
         .oneshot(
             Request::builder()
@@ -3,6 +3,7 @@
 mod bwip;
 mod playground;
 mod protective_reads;
+mod state_diffs;
 
 pub use self::{
     bwip::{
@@ -13,4 +14,9 @@ pub use self::{
         VmPlaygroundStorageOptions, VmPlaygroundTasks,
     },
     protective_reads::{ProtectiveReadsIo, ProtectiveReadsWriter, ProtectiveReadsWriterTasks},
+    state_diffs::{
+        DecodedStorageKey, ExportedEvent, ExportedFactoryDep, FileSink, KafkaRestSink,
+        L1BatchStateDiff, L2BlockStateDiff, StateDiffExporter, StateDiffExporterIo,
+        StateDiffExporterTasks, StateDiffRecord, StateDiffSink, StorageDiff,
+    },
 };
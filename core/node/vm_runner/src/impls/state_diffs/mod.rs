@@ -0,0 +1,279 @@
+//! State diff exporter streaming storage diffs, factory deps and events for each L2 block / L1 batch
+//! to external sinks.
+
+use std::{mem, sync::Arc};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use tokio::sync::watch;
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_types::{L1BatchNumber, L2BlockNumber, L2ChainId};
+use zksync_vm_executor::batch::MainBatchExecutorFactory;
+use zksync_vm_interface::{L1BatchEnv, L2BlockEnv, SystemEnv};
+
+use self::types::KnownPreimages;
+pub use self::{
+    sinks::{FileSink, KafkaRestSink, StateDiffSink},
+    types::{
+        DecodedStorageKey, ExportedEvent, ExportedFactoryDep, L1BatchStateDiff, L2BlockStateDiff,
+        StateDiffRecord, StorageDiff,
+    },
+};
+use crate::{
+    storage::StorageSyncTask, ConcurrentOutputHandlerFactory, ConcurrentOutputHandlerFactoryTask,
+    L1BatchOutput, L2BlockOutput, OutputHandler, OutputHandlerFactory, VmRunner, VmRunnerIo,
+    VmRunnerStorage,
+};
+
+mod sinks;
+#[cfg(test)]
+mod tests;
+mod types;
+
+/// A standalone component that re-executes L1 batches and exports their state diffs to the configured sinks.
+/// Export progress is persisted in Postgres, so the exporter resumes from the last exported batch after a restart.
+#[derive(Debug)]
+pub struct StateDiffExporter {
+    vm_runner: VmRunner,
+}
+
+impl StateDiffExporter {
+    /// Creates a new exporter from the provided DB parameters and window size which
+    /// regulates how many batches this component can handle at the same time.
+    pub async fn new(
+        pool: ConnectionPool<Core>,
+        rocksdb_path: String,
+        chain_id: L2ChainId,
+        first_processed_batch: L1BatchNumber,
+        window_size: u32,
+        sinks: Vec<Box<dyn StateDiffSink>>,
+    ) -> anyhow::Result<(Self, StateDiffExporterTasks)> {
+        anyhow::ensure!(!sinks.is_empty(), "no state diff sinks are configured");
+
+        let io = StateDiffExporterIo {
+            first_processed_batch,
+            window_size,
+        };
+        let (loader, loader_task) =
+            VmRunnerStorage::new(pool.clone(), rocksdb_path, io.clone(), chain_id).await?;
+        let output_handler_factory = StateDiffOutputHandlerFactory {
+            sinks: sinks.into(),
+        };
+        let (output_handler_factory, output_handler_factory_task) =
+            ConcurrentOutputHandlerFactory::new(pool.clone(), io.clone(), output_handler_factory);
+        let batch_processor = MainBatchExecutorFactory::<()>::new(false);
+        let vm_runner = VmRunner::new(
+            pool,
+            Arc::new(io),
+            Arc::new(loader),
+            Arc::new(output_handler_factory),
+            Box::new(batch_processor),
+        );
+        Ok((
+            Self { vm_runner },
+            StateDiffExporterTasks {
+                loader_task,
+                output_handler_factory_task,
+            },
+        ))
+    }
+
+    /// Continuously loads new available batches and exports their state diffs.
+    ///
+    /// # Errors
+    ///
+    /// Propagates RocksDB, Postgres and sink errors.
+    pub async fn run(self, stop_receiver: &watch::Receiver<bool>) -> anyhow::Result<()> {
+        self.vm_runner.run(stop_receiver).await
+    }
+}
+
+/// A collections of tasks that need to be run in order for the state diff exporter to work as
+/// intended.
+#[derive(Debug)]
+pub struct StateDiffExporterTasks {
+    /// Task that synchronizes storage with new available batches.
+    pub loader_task: StorageSyncTask<StateDiffExporterIo>,
+    /// Task that handles output from processed batches.
+    pub output_handler_factory_task: ConcurrentOutputHandlerFactoryTask<StateDiffExporterIo>,
+}
+
+/// `VmRunnerIo` implementation for the state diff exporter.
+#[derive(Debug, Clone)]
+pub struct StateDiffExporterIo {
+    first_processed_batch: L1BatchNumber,
+    window_size: u32,
+}
+
+#[async_trait]
+impl VmRunnerIo for StateDiffExporterIo {
+    fn name(&self) -> &'static str {
+        "state_diff_exporter"
+    }
+
+    async fn latest_processed_batch(
+        &self,
+        conn: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<L1BatchNumber> {
+        Ok(conn
+            .vm_runner_dal()
+            .get_state_diff_exporter_latest_processed_batch()
+            .await?
+            .unwrap_or(self.first_processed_batch))
+    }
+
+    async fn last_ready_to_be_loaded_batch(
+        &self,
+        conn: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<L1BatchNumber> {
+        Ok(conn
+            .vm_runner_dal()
+            .get_state_diff_exporter_last_ready_batch(self.first_processed_batch, self.window_size)
+            .await?)
+    }
+
+    async fn mark_l1_batch_as_processing(
+        &self,
+        conn: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<()> {
+        Ok(conn
+            .vm_runner_dal()
+            .mark_state_diff_exporter_batch_as_processing(l1_batch_number)
+            .await?)
+    }
+
+    async fn mark_l1_batch_as_completed(
+        &self,
+        conn: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<()> {
+        conn.vm_runner_dal()
+            .mark_state_diff_exporter_batch_as_completed(l1_batch_number)
+            .await
+    }
+}
+
+#[derive(Debug)]
+struct StateDiffOutputHandler {
+    l1_batch_number: L1BatchNumber,
+    l1_batch_timestamp: u64,
+    preimages: KnownPreimages,
+    l2_blocks: Vec<L2BlockStateDiff>,
+    sinks: Arc<[Box<dyn StateDiffSink>]>,
+}
+
+#[async_trait]
+impl OutputHandler for StateDiffOutputHandler {
+    async fn handle_l2_block(
+        &mut self,
+        env: L2BlockEnv,
+        output: &L2BlockOutput,
+    ) -> anyhow::Result<()> {
+        let mut diff = L2BlockStateDiff {
+            l1_batch_number: self.l1_batch_number,
+            l2_block_number: L2BlockNumber(env.number),
+            timestamp: env.timestamp,
+            storage_diffs: vec![],
+            factory_deps: vec![],
+            events: vec![],
+        };
+
+        for (tx, exec_result) in &output.transactions {
+            let tx_hash = tx.hash();
+            self.preimages.insert_account(tx.initiator_account());
+            if let Some(recipient) = tx.recipient_account() {
+                self.preimages.insert_account(recipient);
+            }
+
+            let logs = &exec_result.tx_result.logs;
+            let writes = logs.storage_logs.iter().filter(|log| log.log.is_write());
+            diff.storage_diffs.extend(writes.map(StorageDiff::new));
+            for event in &logs.events {
+                self.preimages.insert_event_accounts(event);
+                diff.events.push(ExportedEvent::new(Some(tx_hash), event));
+            }
+
+            let factory_deps = tx.execute.factory_deps.iter().cloned();
+            diff.factory_deps
+                .extend(factory_deps.map(ExportedFactoryDep::new));
+            let dynamic_deps = exec_result.tx_result.dynamic_factory_deps.iter();
+            diff.factory_deps
+                .extend(dynamic_deps.map(|(&hash, bytecode)| {
+                    ExportedFactoryDep::with_hash(hash, bytecode.clone())
+                }));
+        }
+        self.l2_blocks.push(diff);
+        Ok(())
+    }
+
+    #[tracing::instrument(
+        name = "StateDiffOutputHandler::handle_l1_batch",
+        skip_all,
+        fields(l1_batch = %self.l1_batch_number)
+    )]
+    async fn handle_l1_batch(
+        mut self: Box<Self>,
+        output: Arc<L1BatchOutput>,
+    ) -> anyhow::Result<()> {
+        // Attribute changes made by the batch tip (e.g., fee payments to the operator) to the last L2 block in the batch.
+        let tip_logs = &output.batch.block_tip_execution_result.logs;
+        let last_block = self
+            .l2_blocks
+            .last_mut()
+            .context("L1 batch doesn't contain L2 blocks")?;
+        let tip_writes = tip_logs
+            .storage_logs
+            .iter()
+            .filter(|log| log.log.is_write());
+        last_block
+            .storage_diffs
+            .extend(tip_writes.map(StorageDiff::new));
+        for event in &tip_logs.events {
+            self.preimages.insert_event_accounts(event);
+            last_block.events.push(ExportedEvent::new(None, event));
+        }
+
+        let diff = L1BatchStateDiff::new(
+            self.l1_batch_number,
+            self.l1_batch_timestamp,
+            mem::take(&mut self.l2_blocks),
+            &self.preimages,
+        );
+        for sink in self.sinks.iter() {
+            sink.export(&diff)
+                .await
+                .with_context(|| format!("failed exporting state diff to {sink:?}"))?;
+        }
+        tracing::debug!(
+            l2_block_count = diff.l2_blocks.len(),
+            storage_diff_count = diff.storage_diffs.len(),
+            "Exported state diff for L1 batch"
+        );
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct StateDiffOutputHandlerFactory {
+    sinks: Arc<[Box<dyn StateDiffSink>]>,
+}
+
+#[async_trait]
+impl OutputHandlerFactory for StateDiffOutputHandlerFactory {
+    async fn create_handler(
+        &self,
+        _system_env: SystemEnv,
+        l1_batch_env: L1BatchEnv,
+    ) -> anyhow::Result<Box<dyn OutputHandler>> {
+        let mut preimages = KnownPreimages::default();
+        preimages.insert_account(l1_batch_env.fee_account);
+        Ok(Box::new(StateDiffOutputHandler {
+            l1_batch_number: l1_batch_env.number,
+            l1_batch_timestamp: l1_batch_env.timestamp,
+            preimages,
+            l2_blocks: vec![],
+            sinks: self.sinks.clone(),
+        }))
+    }
+}
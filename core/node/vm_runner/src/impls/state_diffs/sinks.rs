@@ -0,0 +1,126 @@
+//! Sinks for exported state diffs.
+
+use std::{fmt, path::PathBuf, time::Duration};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use serde::Serialize;
+
+use super::types::{L1BatchStateDiff, StateDiffRecord};
+
+/// Destination for state diffs produced by [`StateDiffExporter`](super::StateDiffExporter).
+///
+/// Diffs are exported with at-least-once semantics: if the exporter is restarted after exporting a batch, but before
+/// marking it as processed, the batch will be exported again. Thus, implementations should be idempotent (or at least
+/// tolerate duplicate records). Batches are exported in order only if the exporter window size is 1.
+#[async_trait]
+pub trait StateDiffSink: fmt::Debug + Send + Sync + 'static {
+    /// Exports a diff for a single L1 batch.
+    async fn export(&self, diff: &L1BatchStateDiff) -> anyhow::Result<()>;
+}
+
+/// Sink writing diffs for each L1 batch into a separate file in the JSON Lines format. Each line
+/// is a serialized [`StateDiffRecord`].
+#[derive(Debug)]
+pub struct FileSink {
+    dir: PathBuf,
+}
+
+impl FileSink {
+    /// Creates a sink writing to the specified directory. The directory will be created if necessary.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn file_path(&self, diff: &L1BatchStateDiff) -> PathBuf {
+        self.dir
+            .join(format!("l1_batch_{:010}.jsonl", diff.l1_batch_number.0))
+    }
+}
+
+#[async_trait]
+impl StateDiffSink for FileSink {
+    async fn export(&self, diff: &L1BatchStateDiff) -> anyhow::Result<()> {
+        let mut contents = vec![];
+        for record in diff.records() {
+            serde_json::to_writer(&mut contents, &record)?;
+            contents.push(b'\n');
+        }
+
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .with_context(|| format!("failed creating directory {:?}", self.dir))?;
+        // Write to a temporary file first so that a file with the final name is never partially written.
+        let path = self.file_path(diff);
+        let tmp_path = path.with_extension("jsonl.tmp");
+        tokio::fs::write(&tmp_path, &contents)
+            .await
+            .with_context(|| format!("failed writing {tmp_path:?}"))?;
+        tokio::fs::rename(&tmp_path, &path)
+            .await
+            .with_context(|| format!("failed renaming {tmp_path:?} to {path:?}"))?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct KafkaRecord<'a> {
+    key: String,
+    value: StateDiffRecord<'a>,
+}
+
+#[derive(Debug, Serialize)]
+struct KafkaProduceRequest<'a> {
+    records: Vec<KafkaRecord<'a>>,
+}
+
+/// Sink producing diffs to a Kafka topic via a [Kafka REST proxy](https://docs.confluent.io/platform/current/kafka-rest/index.html)
+/// (v2 API). Each [`StateDiffRecord`] is produced as a separate Kafka record keyed by [`StateDiffRecord::key()`].
+#[derive(Debug)]
+pub struct KafkaRestSink {
+    client: reqwest::Client,
+    topic_url: String,
+}
+
+impl KafkaRestSink {
+    const CONTENT_TYPE: &'static str = "application/vnd.kafka.json.v2+json";
+    const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+    /// Creates a sink producing records to the specified topic using the REST proxy at `base_url`.
+    pub fn new(base_url: &str, topic: &str) -> anyhow::Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Self::REQUEST_TIMEOUT)
+            .build()
+            .context("failed building HTTP client")?;
+        Ok(Self {
+            client,
+            topic_url: format!("{}/topics/{topic}", base_url.trim_end_matches('/')),
+        })
+    }
+}
+
+#[async_trait]
+impl StateDiffSink for KafkaRestSink {
+    async fn export(&self, diff: &L1BatchStateDiff) -> anyhow::Result<()> {
+        let request = KafkaProduceRequest {
+            records: diff
+                .records()
+                .map(|value| KafkaRecord {
+                    key: value.key(),
+                    value,
+                })
+                .collect(),
+        };
+        let body = serde_json::to_vec(&request)?;
+        self.client
+            .post(&self.topic_url)
+            .header(reqwest::header::CONTENT_TYPE, Self::CONTENT_TYPE)
+            .body(body)
+            .send()
+            .await
+            .with_context(|| format!("failed sending request to {}", self.topic_url))?
+            .error_for_status()
+            .context("Kafka REST proxy returned error")?;
+        Ok(())
+    }
+}
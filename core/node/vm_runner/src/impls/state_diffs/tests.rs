@@ -0,0 +1,112 @@
+use zksync_types::{
+    get_nonce_key, storage_key_for_eth_balance, Address, StorageKey, StorageLog,
+    StorageLogWithPreviousValue, H256,
+};
+use zksync_vm_interface::VmEvent;
+
+use super::*;
+
+fn write(key: StorageKey, previous_value: u64, value: u64) -> StorageDiff {
+    let log = StorageLogWithPreviousValue {
+        log: StorageLog::new_write_log(key, H256::from_low_u64_be(value)),
+        previous_value: H256::from_low_u64_be(previous_value),
+    };
+    StorageDiff::new(&log)
+}
+
+fn block_diff(number: u32, storage_diffs: Vec<StorageDiff>) -> L2BlockStateDiff {
+    L2BlockStateDiff {
+        l1_batch_number: L1BatchNumber(1),
+        l2_block_number: L2BlockNumber(number),
+        timestamp: number.into(),
+        storage_diffs,
+        factory_deps: vec![],
+        events: vec![],
+    }
+}
+
+fn mock_diff() -> L1BatchStateDiff {
+    let account = Address::repeat_byte(1);
+    let recipient = Address::repeat_byte(2);
+    let balance_key = storage_key_for_eth_balance(&account);
+    let nonce_key = get_nonce_key(&account);
+    let recipient_balance_key = storage_key_for_eth_balance(&recipient);
+
+    let mut preimages = KnownPreimages::default();
+    preimages.insert_account(account);
+    preimages.insert_event_accounts(&VmEvent {
+        location: (L1BatchNumber(1), 0),
+        address: Address::repeat_byte(0x10),
+        indexed_topics: vec![H256::repeat_byte(0xff), H256::from(recipient)],
+        value: vec![],
+    });
+
+    let l2_blocks = vec![
+        block_diff(1, vec![write(balance_key, 100, 90), write(nonce_key, 0, 1)]),
+        block_diff(
+            2,
+            vec![
+                write(balance_key, 90, 100),
+                write(recipient_balance_key, 0, 5),
+            ],
+        ),
+    ];
+    L1BatchStateDiff::new(L1BatchNumber(1), 1, l2_blocks, &preimages)
+}
+
+#[test]
+fn computing_l1_batch_state_diff() {
+    let diff = mock_diff();
+    let account = Address::repeat_byte(1);
+    let recipient = Address::repeat_byte(2);
+
+    assert_eq!(
+        diff.l2_blocks[0].storage_diffs[0].decoded_key,
+        Some(DecodedStorageKey::BaseTokenBalance { account })
+    );
+    assert_eq!(
+        diff.l2_blocks[0].storage_diffs[1].decoded_key,
+        Some(DecodedStorageKey::Nonce { account })
+    );
+
+    // The balance of `account` is reverted to its initial value, so it shouldn't be present in the net diff.
+    assert_eq!(diff.storage_diffs.len(), 2);
+    assert_eq!(
+        diff.storage_diffs[0].decoded_key,
+        Some(DecodedStorageKey::Nonce { account })
+    );
+    assert_eq!(
+        diff.storage_diffs[1].decoded_key,
+        Some(DecodedStorageKey::BaseTokenBalance { account: recipient })
+    );
+    assert_eq!(diff.storage_diffs[1].previous_value, H256::zero());
+    assert_eq!(diff.storage_diffs[1].value, H256::from_low_u64_be(5));
+
+    let keys: Vec<_> = diff.records().map(|record| record.key()).collect();
+    assert_eq!(keys, ["l2_block:1", "l2_block:2", "l1_batch:1"]);
+}
+
+#[tokio::test]
+async fn file_sink_basics() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let sink = FileSink::new(temp_dir.path().join("diffs"));
+    let diff = mock_diff();
+    sink.export(&diff).await.unwrap();
+    // Exports must be idempotent.
+    sink.export(&diff).await.unwrap();
+
+    let path = temp_dir.path().join("diffs/l1_batch_0000000001.jsonl");
+    let contents = std::fs::read_to_string(path).unwrap();
+    let records: Vec<serde_json::Value> = contents
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[0]["type"], "l2_block");
+    assert_eq!(records[0]["l2_block_number"], 1);
+    assert_eq!(records[2]["type"], "l1_batch");
+    assert_eq!(records[2]["storage_diffs"].as_array().unwrap().len(), 2);
+
+    let restored: L2BlockStateDiff = serde_json::from_value(records[1].clone()).unwrap();
+    assert_eq!(restored, diff.l2_blocks[1]);
+}
@@ -0,0 +1,270 @@
+//! Types exported by the state diff exporter.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use zksync_types::{
+    bytecode::BytecodeHash, get_code_key, get_nonce_key, h256_to_address,
+    storage_key_for_eth_balance, web3::Bytes, Address, L1BatchNumber, L2BlockNumber,
+    StorageLogWithPreviousValue, H256,
+};
+use zksync_vm_interface::VmEvent;
+
+/// Storage slot with a known preimage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DecodedStorageKey {
+    /// Base token balance of the account.
+    BaseTokenBalance {
+        /// Account holding the balance.
+        account: Address,
+    },
+    /// Nonce(s) of the account stored in the nonce holder system contract.
+    Nonce {
+        /// Account the nonce belongs to.
+        account: Address,
+    },
+    /// Versioned bytecode hash of the account stored in the account code storage system contract.
+    BytecodeHash {
+        /// Account the bytecode belongs to.
+        account: Address,
+    },
+}
+
+/// Diff of a single storage slot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StorageDiff {
+    /// Address of the contract owning the slot.
+    pub address: Address,
+    /// Slot key in the contract storage.
+    pub key: H256,
+    /// Hashed key of the slot (i.e., its key in the Merkle tree).
+    pub hashed_key: H256,
+    /// Value of the slot before the write.
+    pub previous_value: H256,
+    /// Value of the slot after the write.
+    pub value: H256,
+    /// Decoded slot key, if the preimage is known to the exporter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decoded_key: Option<DecodedStorageKey>,
+}
+
+/// Factory dependency (i.e., a bytecode) published in an L2 block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedFactoryDep {
+    /// Versioned bytecode hash.
+    pub hash: H256,
+    /// Bytecode.
+    pub bytecode: Bytes,
+}
+
+/// Event emitted in an L2 block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedEvent {
+    /// Hash of the transaction that has emitted the event. `None` for events emitted by the batch tip.
+    pub tx_hash: Option<H256>,
+    /// Address of the contract that has emitted the event.
+    pub address: Address,
+    /// Indexed topics of the event.
+    pub topics: Vec<H256>,
+    /// Non-indexed event data.
+    pub data: Bytes,
+}
+
+/// State diff for a single L2 block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct L2BlockStateDiff {
+    /// Number of the L1 batch the block belongs to.
+    pub l1_batch_number: L1BatchNumber,
+    /// L2 block number.
+    pub l2_block_number: L2BlockNumber,
+    /// L2 block timestamp.
+    pub timestamp: u64,
+    /// Storage writes performed in the block, in the execution order. May contain several writes to the same slot.
+    pub storage_diffs: Vec<StorageDiff>,
+    /// Factory dependencies published in the block.
+    pub factory_deps: Vec<ExportedFactoryDep>,
+    /// Events emitted in the block.
+    pub events: Vec<ExportedEvent>,
+}
+
+/// State diff for an L1 batch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct L1BatchStateDiff {
+    /// L1 batch number.
+    pub l1_batch_number: L1BatchNumber,
+    /// L1 batch timestamp.
+    pub timestamp: u64,
+    /// Diffs for all L2 blocks in the batch, in the execution order. Writes performed by the batch tip
+    /// are attributed to the last L2 block in the batch.
+    pub l2_blocks: Vec<L2BlockStateDiff>,
+    /// Net storage writes in the batch, i.e. deduplicated writes with their initial and final values, ordered
+    /// by the first write. Slots with the final value equal to the initial one are omitted.
+    pub storage_diffs: Vec<StorageDiff>,
+}
+
+impl L1BatchStateDiff {
+    pub(super) fn new(
+        l1_batch_number: L1BatchNumber,
+        timestamp: u64,
+        mut l2_blocks: Vec<L2BlockStateDiff>,
+        preimages: &KnownPreimages,
+    ) -> Self {
+        // Preimages are collected for the entire batch, so slots can only be decoded once the batch is executed.
+        for diff in l2_blocks
+            .iter_mut()
+            .flat_map(|block| &mut block.storage_diffs)
+        {
+            diff.decoded_key = preimages.decode(&diff.hashed_key);
+        }
+
+        let mut net_diffs = Vec::<StorageDiff>::new();
+        let mut indices = HashMap::new();
+        for diff in l2_blocks.iter().flat_map(|block| &block.storage_diffs) {
+            if let Some(&idx) = indices.get(&diff.hashed_key) {
+                net_diffs[idx].value = diff.value;
+            } else {
+                indices.insert(diff.hashed_key, net_diffs.len());
+                net_diffs.push(diff.clone());
+            }
+        }
+        net_diffs.retain(|diff| diff.previous_value != diff.value);
+
+        Self {
+            l1_batch_number,
+            timestamp,
+            l2_blocks,
+            storage_diffs: net_diffs,
+        }
+    }
+
+    /// Splits this diff into records suitable for streaming. Records for L2 blocks go first, followed by
+    /// a single record with batch-level diffs.
+    pub fn records(&self) -> impl Iterator<Item = StateDiffRecord<'_>> + '_ {
+        let batch_record = StateDiffRecord::L1Batch {
+            l1_batch_number: self.l1_batch_number,
+            timestamp: self.timestamp,
+            storage_diffs: &self.storage_diffs,
+        };
+        self.l2_blocks
+            .iter()
+            .map(StateDiffRecord::L2Block)
+            .chain([batch_record])
+    }
+}
+
+/// Single streamed record of the state diff exporter.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StateDiffRecord<'a> {
+    /// Diff for an L2 block.
+    L2Block(&'a L2BlockStateDiff),
+    /// Net diff for an L1 batch. Always follows the records for all L2 blocks in the batch.
+    L1Batch {
+        /// L1 batch number.
+        l1_batch_number: L1BatchNumber,
+        /// L1 batch timestamp.
+        timestamp: u64,
+        /// Net storage writes in the batch.
+        storage_diffs: &'a [StorageDiff],
+    },
+}
+
+impl StateDiffRecord<'_> {
+    /// Returns a key for the record that can be used for partitioning / deduplication (e.g., as a Kafka record key).
+    pub fn key(&self) -> String {
+        match self {
+            Self::L2Block(diff) => format!("l2_block:{}", diff.l2_block_number),
+            Self::L1Batch {
+                l1_batch_number, ..
+            } => format!("l1_batch:{l1_batch_number}"),
+        }
+    }
+}
+
+/// Map from hashed storage keys to their decoded preimages.
+#[derive(Debug, Default)]
+pub(super) struct KnownPreimages {
+    inner: HashMap<H256, DecodedStorageKey>,
+    accounts: HashSet<Address>,
+}
+
+impl KnownPreimages {
+    pub fn insert_account(&mut self, account: Address) {
+        if !self.accounts.insert(account) {
+            return;
+        }
+        let entries = [
+            (
+                storage_key_for_eth_balance(&account),
+                DecodedStorageKey::BaseTokenBalance { account },
+            ),
+            (
+                get_nonce_key(&account),
+                DecodedStorageKey::Nonce { account },
+            ),
+            (
+                get_code_key(&account),
+                DecodedStorageKey::BytecodeHash { account },
+            ),
+        ];
+        for (key, decoded) in entries {
+            self.inner.insert(key.hashed_key(), decoded);
+        }
+    }
+
+    /// Inserts accounts referenced by the event, including ones in the topics that look like ABI-encoded addresses
+    /// (e.g., sender and recipient of an ERC-20 `Transfer`).
+    pub fn insert_event_accounts(&mut self, event: &VmEvent) {
+        self.insert_account(event.address);
+        for topic in event.indexed_topics.iter().skip(1) {
+            if topic.as_bytes()[..12].iter().all(|&byte| byte == 0) && !topic.is_zero() {
+                self.insert_account(h256_to_address(topic));
+            }
+        }
+    }
+
+    pub fn decode(&self, hashed_key: &H256) -> Option<DecodedStorageKey> {
+        self.inner.get(hashed_key).copied()
+    }
+}
+
+impl StorageDiff {
+    pub(super) fn new(log: &StorageLogWithPreviousValue) -> Self {
+        Self {
+            address: *log.log.key.address(),
+            key: *log.log.key.key(),
+            hashed_key: log.log.key.hashed_key(),
+            previous_value: log.previous_value,
+            value: log.log.value,
+            decoded_key: None,
+        }
+    }
+}
+
+impl ExportedFactoryDep {
+    pub(super) fn new(bytecode: Vec<u8>) -> Self {
+        Self {
+            hash: BytecodeHash::for_bytecode(&bytecode).value(),
+            bytecode: bytecode.into(),
+        }
+    }
+
+    pub(super) fn with_hash(hash: H256, bytecode: Vec<u8>) -> Self {
+        Self {
+            hash,
+            bytecode: bytecode.into(),
+        }
+    }
+}
+
+impl ExportedEvent {
+    pub(super) fn new(tx_hash: Option<H256>, event: &VmEvent) -> Self {
+        Self {
+            tx_hash,
+            address: event.address,
+            topics: event.indexed_topics.clone(),
+            data: event.value.clone().into(),
+        }
+    }
+}
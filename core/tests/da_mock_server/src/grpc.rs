@@ -0,0 +1,546 @@
+//! gRPC stand-ins for the Celestia consensus node and the EigenDA disperser.
+
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    future,
+    marker::PhantomData,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use anyhow::Context as _;
+use prost::{Message, Name};
+use sha2::Digest;
+use tokio::{net::TcpListener, sync::watch};
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::{
+    body::BoxBody,
+    codec::ProstCodec,
+    codegen::{http, Body, BoxFuture, Context, Poll, Service, StdError},
+    server::{Grpc, NamedService},
+    transport::Server,
+    Status,
+};
+use zksync_basic_types::{web3::keccak256, H256};
+use zksync_da_clients::{
+    celestia::{
+        celestia_proto::{
+            Params as BlobParams, QueryParamsRequest as BlobParamsRequest,
+            QueryParamsResponse as BlobParamsResponse,
+        },
+        cosmos::{
+            auth::{
+                BaseAccount, Params as AuthParams, QueryAccountRequest, QueryAccountResponse,
+                QueryParamsRequest as AuthParamsRequest, QueryParamsResponse as AuthParamsResponse,
+            },
+            bank::v1beta1::{QueryAllBalancesRequest, QueryAllBalancesResponse},
+            base::{
+                abci::TxResponse,
+                node::{ConfigRequest, ConfigResponse},
+                v1beta1::Coin,
+            },
+            tx::v1beta1::{
+                BroadcastTxRequest, BroadcastTxResponse, GetTxRequest, GetTxResponse, Tx,
+            },
+        },
+        tendermint::types::BlobTx,
+    },
+    eigen::{
+        common::G1Commitment,
+        disperser::{
+            BatchHeader, BatchMetadata, BlobHeader, BlobInfo, BlobQuorumParam, BlobStatus,
+            BlobStatusReply, BlobStatusRequest, BlobVerificationProof, DisperseBlobReply,
+            DisperseBlobRequest, RetrieveBlobReply, RetrieveBlobRequest,
+        },
+    },
+};
+
+use crate::{FaultInjector, MockDAServerConfig};
+
+/// Denomination of Celestia fees and balances.
+const CELESTIA_DENOM: &str = "utia";
+const CELESTIA_GAS_PER_BLOB_BYTE: u32 = 8;
+const CELESTIA_TX_SIZE_COST_PER_BYTE: u64 = 10;
+const CELESTIA_MIN_GAS_PRICE: &str = "0.002utia";
+/// Initial balance of every Celestia account; fees paid for blobs are subtracted from it.
+const CELESTIA_INITIAL_BALANCE: u64 = 1_000_000_000_000;
+const CELESTIA_BLOB_TX_TYPE_ID: &str = "BLOB";
+/// Size of field elements the EigenDA disperser splits blobs into.
+const EIGEN_SYMBOL_SIZE: usize = 32;
+
+#[derive(Debug)]
+struct CelestiaTx {
+    height: i64,
+    submitted_at: Instant,
+}
+
+#[derive(Debug, Default)]
+struct CelestiaState {
+    txs: HashMap<String, CelestiaTx>,
+    fees_paid: u64,
+}
+
+#[derive(Debug)]
+struct EigenBlob {
+    data: Vec<u8>,
+    batch_header_hash: H256,
+    submitted_at: Instant,
+}
+
+#[derive(Debug, Default)]
+struct EigenState {
+    blobs: Vec<EigenBlob>,
+    by_request_id: HashMap<H256, usize>,
+    by_batch_header_hash: HashMap<H256, usize>,
+}
+
+#[derive(Debug)]
+struct GrpcState {
+    config: MockDAServerConfig,
+    faults: FaultInjector,
+    celestia: Mutex<CelestiaState>,
+    eigen: Mutex<EigenState>,
+}
+
+impl GrpcState {
+    async fn handle<B>(&self, request: http::Request<B>) -> http::Response<BoxBody>
+    where
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        let path = request.uri().path().to_owned();
+        if self.faults.should_fail().await {
+            tracing::info!("Injecting failure for gRPC method {path}");
+            return Status::unavailable("injected failure").to_http();
+        }
+
+        match path.as_str() {
+            "/celestia.blob.v1.Query/Params" => {
+                unary(request, |_: BlobParamsRequest| {
+                    Ok(BlobParamsResponse {
+                        params: Some(BlobParams {
+                            gas_per_blob_byte: CELESTIA_GAS_PER_BLOB_BYTE,
+                            ..BlobParams::default()
+                        }),
+                    })
+                })
+                .await
+            }
+            "/cosmos.auth.v1beta1.Query/Params" => {
+                unary(request, |_: AuthParamsRequest| {
+                    Ok(AuthParamsResponse {
+                        params: Some(AuthParams {
+                            tx_size_cost_per_byte: CELESTIA_TX_SIZE_COST_PER_BYTE,
+                            ..AuthParams::default()
+                        }),
+                    })
+                })
+                .await
+            }
+            "/cosmos.auth.v1beta1.Query/Account" => {
+                unary(request, |req| self.celestia_account(req)).await
+            }
+            "/cosmos.base.node.v1beta1.Service/Config" => {
+                unary(request, |_: ConfigRequest| {
+                    Ok(ConfigResponse {
+                        minimum_gas_price: CELESTIA_MIN_GAS_PRICE.to_owned(),
+                    })
+                })
+                .await
+            }
+            "/cosmos.tx.v1beta1.Service/BroadcastTx" => {
+                unary(request, |req| self.celestia_broadcast_tx(req)).await
+            }
+            "/cosmos.tx.v1beta1.Service/GetTx" => {
+                unary(request, |req| self.celestia_get_tx(req)).await
+            }
+            "/cosmos.bank.v1beta1.Query/AllBalances" => {
+                unary(request, |req| self.celestia_all_balances(req)).await
+            }
+            "/disperser.Disperser/DisperseBlob" => {
+                unary(request, |req| self.eigen_disperse_blob(req)).await
+            }
+            "/disperser.Disperser/GetBlobStatus" => {
+                unary(request, |req| self.eigen_get_blob_status(req)).await
+            }
+            "/disperser.Disperser/RetrieveBlob" => {
+                unary(request, |req| self.eigen_retrieve_blob(req)).await
+            }
+            _ => Status::unimplemented(format!("method {path} is not supported")).to_http(),
+        }
+    }
+
+    fn celestia_account(
+        &self,
+        request: QueryAccountRequest,
+    ) -> Result<QueryAccountResponse, Status> {
+        let sequence = self.celestia.lock().unwrap().txs.len() as u64;
+        let account = BaseAccount {
+            address: request.address,
+            pub_key: None,
+            account_number: 1,
+            sequence,
+        };
+        Ok(QueryAccountResponse {
+            account: Some(pbjson_types::Any {
+                type_url: BaseAccount::type_url(),
+                value: account.encode_to_vec().into(),
+            }),
+        })
+    }
+
+    /// Accepts a blob transaction without checking its signature. The transaction is included into a block
+    /// after the configured inclusion delay.
+    fn celestia_broadcast_tx(
+        &self,
+        request: BroadcastTxRequest,
+    ) -> Result<BroadcastTxResponse, Status> {
+        let blob_tx = BlobTx::decode(request.tx_bytes)
+            .map_err(|err| Status::invalid_argument(format!("invalid blob transaction: {err}")))?;
+        if blob_tx.type_id != CELESTIA_BLOB_TX_TYPE_ID || blob_tx.blobs.is_empty() {
+            return Err(Status::invalid_argument(
+                "transaction doesn't contain blobs",
+            ));
+        }
+        let tx = Tx::decode(&blob_tx.tx[..])
+            .map_err(|err| Status::invalid_argument(format!("invalid transaction: {err}")))?;
+        let fee: u64 = tx
+            .auth_info
+            .and_then(|auth_info| auth_info.fee)
+            .map_or(0, |fee| {
+                fee.amount
+                    .iter()
+                    .filter(|coin| coin.denom == CELESTIA_DENOM)
+                    .filter_map(|coin| coin.amount.parse::<u64>().ok())
+                    .sum()
+            });
+        // Celestia nodes return uppercase hashes; the client is expected to normalize them.
+        let tx_hash = hex::encode_upper(sha2::Sha256::digest(&blob_tx.tx));
+
+        let mut celestia = self.celestia.lock().unwrap();
+        // Every transaction is put into its own block.
+        let height = celestia.txs.len() as i64 + 1;
+        celestia.txs.insert(
+            tx_hash.clone(),
+            CelestiaTx {
+                height,
+                submitted_at: Instant::now(),
+            },
+        );
+        celestia.fees_paid += fee;
+        tracing::debug!(
+            "Accepted Celestia transaction {tx_hash} with {} blob(s)",
+            blob_tx.blobs.len()
+        );
+
+        Ok(BroadcastTxResponse {
+            tx_response: Some(TxResponse {
+                txhash: tx_hash,
+                ..TxResponse::default()
+            }),
+        })
+    }
+
+    fn celestia_get_tx(&self, request: GetTxRequest) -> Result<GetTxResponse, Status> {
+        let celestia = self.celestia.lock().unwrap();
+        let tx = celestia
+            .txs
+            .get(&request.hash.to_uppercase())
+            .ok_or_else(|| Status::not_found("transaction not found"))?;
+        // Mirrors the node, which doesn't know about transactions until they are included into a block.
+        if tx.submitted_at.elapsed() < self.config.inclusion_delay {
+            return Err(Status::not_found("transaction not found"));
+        }
+        Ok(GetTxResponse {
+            tx: None,
+            tx_response: Some(TxResponse {
+                height: tx.height,
+                txhash: request.hash,
+                ..TxResponse::default()
+            }),
+        })
+    }
+
+    fn celestia_all_balances(
+        &self,
+        _request: QueryAllBalancesRequest,
+    ) -> Result<QueryAllBalancesResponse, Status> {
+        let fees_paid = self.celestia.lock().unwrap().fees_paid;
+        Ok(QueryAllBalancesResponse {
+            balances: vec![Coin {
+                denom: CELESTIA_DENOM.to_owned(),
+                amount: CELESTIA_INITIAL_BALANCE
+                    .saturating_sub(fees_paid)
+                    .to_string(),
+            }],
+            pagination: None,
+        })
+    }
+
+    fn eigen_disperse_blob(
+        &self,
+        request: DisperseBlobRequest,
+    ) -> Result<DisperseBlobReply, Status> {
+        let data = request.data;
+        if data.is_empty() {
+            return Err(Status::invalid_argument("blob is empty"));
+        }
+        // Blobs are interpreted as sequences of field elements; the client pads every 31 bytes with a zero byte
+        // so that each element is below the field modulus.
+        if data.chunks(EIGEN_SYMBOL_SIZE).any(|symbol| symbol[0] != 0) {
+            return Err(Status::invalid_argument(
+                "blob contains invalid field elements",
+            ));
+        }
+
+        let mut eigen = self.eigen.lock().unwrap();
+        let index = eigen.blobs.len();
+        let request_id = H256(keccak256(
+            &[&keccak256(&data)[..], &(index as u64).to_be_bytes()].concat(),
+        ));
+        let batch_header_hash = H256(keccak256(request_id.as_bytes()));
+        tracing::debug!("Accepted EigenDA blob #{index} with {} bytes", data.len());
+        eigen.blobs.push(EigenBlob {
+            data,
+            batch_header_hash,
+            submitted_at: Instant::now(),
+        });
+        eigen.by_request_id.insert(request_id, index);
+        eigen.by_batch_header_hash.insert(batch_header_hash, index);
+
+        Ok(DisperseBlobReply {
+            result: BlobStatus::Processing.into(),
+            request_id: request_id.as_bytes().to_vec(),
+        })
+    }
+
+    fn eigen_get_blob_status(&self, request: BlobStatusRequest) -> Result<BlobStatusReply, Status> {
+        let request_id = parse_hash(&request.request_id, "request ID")?;
+        let eigen = self.eigen.lock().unwrap();
+        let index = *eigen
+            .by_request_id
+            .get(&request_id)
+            .ok_or_else(|| Status::not_found("blob not found"))?;
+        let blob = &eigen.blobs[index];
+        if blob.submitted_at.elapsed() < self.config.inclusion_delay {
+            return Ok(BlobStatusReply {
+                status: BlobStatus::Processing.into(),
+                info: None,
+            });
+        }
+        Ok(BlobStatusReply {
+            status: BlobStatus::Finalized.into(),
+            info: Some(eigen_blob_info(index, blob)),
+        })
+    }
+
+    fn eigen_retrieve_blob(
+        &self,
+        request: RetrieveBlobRequest,
+    ) -> Result<RetrieveBlobReply, Status> {
+        let batch_header_hash = parse_hash(&request.batch_header_hash, "batch header hash")?;
+        let eigen = self.eigen.lock().unwrap();
+        let blob = eigen
+            .by_batch_header_hash
+            .get(&batch_header_hash)
+            .filter(|_| request.blob_index == 0)
+            .map(|&index| &eigen.blobs[index])
+            .ok_or_else(|| Status::not_found("blob not found"))?;
+        Ok(RetrieveBlobReply {
+            data: blob.data.clone(),
+        })
+    }
+}
+
+/// Returns blob info with placeholder commitments and proofs, which cannot be verified against L1.
+/// Every blob is put into its own batch.
+fn eigen_blob_info(index: usize, blob: &EigenBlob) -> BlobInfo {
+    let data_hash = keccak256(&blob.data);
+    let batch_number = index as u32 + 1;
+    BlobInfo {
+        blob_header: Some(BlobHeader {
+            commitment: Some(G1Commitment {
+                x: data_hash.to_vec(),
+                y: keccak256(&data_hash).to_vec(),
+            }),
+            data_length: blob.data.len().div_ceil(EIGEN_SYMBOL_SIZE) as u32,
+            blob_quorum_params: vec![BlobQuorumParam {
+                quorum_number: 0,
+                adversary_threshold_percentage: 33,
+                confirmation_threshold_percentage: 55,
+                chunk_length: 1,
+            }],
+        }),
+        blob_verification_proof: Some(BlobVerificationProof {
+            batch_id: batch_number,
+            blob_index: 0,
+            batch_metadata: Some(BatchMetadata {
+                batch_header: Some(BatchHeader {
+                    batch_root: data_hash.to_vec(),
+                    quorum_numbers: vec![0],
+                    quorum_signed_percentages: vec![100],
+                    reference_block_number: batch_number,
+                }),
+                signatory_record_hash: vec![0; 32],
+                fee: vec![],
+                confirmation_block_number: batch_number,
+                batch_header_hash: blob.batch_header_hash.as_bytes().to_vec(),
+            }),
+            inclusion_proof: vec![],
+            quorum_indexes: vec![0],
+        }),
+    }
+}
+
+fn parse_hash(bytes: &[u8], name: &str) -> Result<H256, Status> {
+    if bytes.len() != 32 {
+        return Err(Status::invalid_argument(format!("invalid {name}")));
+    }
+    Ok(H256::from_slice(bytes))
+}
+
+/// Handles a unary gRPC call using the provided handler.
+async fn unary<Req, Resp, B>(
+    request: http::Request<B>,
+    handler: impl FnOnce(Req) -> Result<Resp, Status>,
+) -> http::Response<BoxBody>
+where
+    Req: Message + Default + Send + 'static,
+    Resp: Message + Send + 'static,
+    B: Body + Send + 'static,
+    B::Error: Into<StdError> + Send + 'static,
+{
+    let mut handler = Some(handler);
+    let service = tower::service_fn(move |request: tonic::Request<Req>| {
+        let handler = handler.take().expect("unary handler called twice");
+        future::ready(handler(request.into_inner()).map(tonic::Response::new))
+    });
+    Grpc::new(ProstCodec::<Resp, Req>::default())
+        .unary(service, request)
+        .await
+}
+
+/// Name of a gRPC service served by [`MockGrpcDAServer`]. `tonic` routes requests by the service name,
+/// so each service needs a separate type.
+trait ServiceName: Send + Sync + 'static {
+    const NAME: &'static str;
+}
+
+macro_rules! service_names {
+    ($($ty:ident => $name:literal,)+) => {
+        $(
+            #[derive(Debug)]
+            struct $ty;
+
+            impl ServiceName for $ty {
+                const NAME: &'static str = $name;
+            }
+        )+
+    };
+}
+
+service_names! {
+    CelestiaBlobQuery => "celestia.blob.v1.Query",
+    CosmosAuthQuery => "cosmos.auth.v1beta1.Query",
+    CosmosBankQuery => "cosmos.bank.v1beta1.Query",
+    CosmosNodeService => "cosmos.base.node.v1beta1.Service",
+    CosmosTxService => "cosmos.tx.v1beta1.Service",
+    EigenDisperser => "disperser.Disperser",
+}
+
+/// gRPC service dispatching all requests to the shared [`GrpcState`].
+#[derive(Debug)]
+struct GrpcService<N> {
+    state: Arc<GrpcState>,
+    _name: PhantomData<N>,
+}
+
+impl<N> Clone for GrpcService<N> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            _name: PhantomData,
+        }
+    }
+}
+
+impl<N: ServiceName> NamedService for GrpcService<N> {
+    const NAME: &'static str = N::NAME;
+}
+
+impl<N, B> Service<http::Request<B>> for GrpcService<N>
+where
+    B: Body + Send + 'static,
+    B::Error: Into<StdError> + Send + 'static,
+{
+    type Response = http::Response<BoxBody>;
+    type Error = Infallible;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        let state = self.state.clone();
+        Box::pin(async move { Ok(state.handle(request).await) })
+    }
+}
+
+/// Mock gRPC DA server bound to a local address. Serves the Celestia consensus node API used
+/// by the Celestia client, and the non-authenticated part of the EigenDA disperser API.
+#[derive(Debug)]
+pub struct MockGrpcDAServer {
+    listener: TcpListener,
+    state: Arc<GrpcState>,
+}
+
+impl MockGrpcDAServer {
+    /// Binds the server to the provided address. Use port 0 to pick a free port.
+    pub async fn bind(address: SocketAddr, config: MockDAServerConfig) -> anyhow::Result<Self> {
+        let listener = TcpListener::bind(address)
+            .await
+            .with_context(|| format!("Failed binding mock gRPC DA server to {address}"))?;
+        let state = Arc::new(GrpcState {
+            faults: FaultInjector::new(&config),
+            config,
+            celestia: Mutex::default(),
+            eigen: Mutex::default(),
+        });
+        Ok(Self { listener, state })
+    }
+
+    pub fn local_addr(&self) -> anyhow::Result<SocketAddr> {
+        self.listener
+            .local_addr()
+            .context("Failed getting mock gRPC DA server address")
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        tracing::info!("Starting mock gRPC DA server on {}", self.local_addr()?);
+        Server::builder()
+            .add_service(self.service::<CelestiaBlobQuery>())
+            .add_service(self.service::<CosmosAuthQuery>())
+            .add_service(self.service::<CosmosBankQuery>())
+            .add_service(self.service::<CosmosNodeService>())
+            .add_service(self.service::<CosmosTxService>())
+            .add_service(self.service::<EigenDisperser>())
+            .serve_with_incoming_shutdown(TcpListenerStream::new(self.listener), async move {
+                stop_receiver.changed().await.ok();
+                tracing::info!("Stop signal received, mock gRPC DA server is shutting down");
+            })
+            .await
+            .context("Mock gRPC DA server failed")?;
+        tracing::info!("Mock gRPC DA server shut down");
+        Ok(())
+    }
+
+    fn service<N>(&self) -> GrpcService<N> {
+        GrpcService {
+            state: self.state.clone(),
+            _name: PhantomData,
+        }
+    }
+}
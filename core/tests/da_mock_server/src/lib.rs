@@ -0,0 +1,307 @@
+//! Local stand-in for a data availability layer.
+//!
+//! [`MockDAServer`] speaks the subset of the Avail gas relay and bridge HTTP APIs that is used by
+//! the Avail DA client in the `GasRelay` mode. [`MockGrpcDAServer`] speaks the subsets of the Celestia
+//! consensus node and EigenDA disperser gRPC APIs used by the corresponding DA clients. The servers
+//! don't provide any real DA guarantees; the returned inclusion proofs are deterministic placeholders,
+//! so the servers must be used together with the dummy inclusion data verification on L1.
+//!
+//! Latency, failures and the delay before a blob is considered included can be configured,
+//! which allows exercising the retry and inclusion polling logic of the DA dispatcher offline.
+
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use anyhow::Context as _;
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, Request, State},
+    http::{HeaderMap, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use tokio::{net::TcpListener, sync::watch};
+use zksync_basic_types::{web3::keccak256, H256};
+
+pub use crate::grpc::MockGrpcDAServer;
+
+mod grpc;
+#[cfg(test)]
+mod tests;
+
+/// Configuration of the mock DA server.
+#[derive(Debug, Clone, Default)]
+pub struct MockDAServerConfig {
+    /// Artificial latency added to every request.
+    pub latency: Duration,
+    /// Probability in `[0, 1]` of a request failing with an internal server error (or the `UNAVAILABLE` status
+    /// for gRPC requests).
+    pub failure_rate: f64,
+    /// Number of first requests that fail unconditionally. Useful for deterministic tests.
+    pub fail_first_requests: usize,
+    /// Time after submission before the inclusion proof for a blob becomes available.
+    pub inclusion_delay: Duration,
+    /// If set, submissions without a matching `x-api-key` header are rejected.
+    pub api_key: Option<String>,
+}
+
+#[derive(Debug)]
+struct Submission {
+    block_hash: H256,
+    extrinsic_index: u64,
+    data_hash: H256,
+    submitted_at: Instant,
+}
+
+#[derive(Debug, Default)]
+struct Submissions {
+    list: Vec<Submission>,
+    by_block_hash: HashMap<H256, usize>,
+}
+
+/// Latency and failure injection shared by the HTTP and gRPC servers.
+#[derive(Debug)]
+struct FaultInjector {
+    latency: Duration,
+    failure_rate: f64,
+    remaining_failures: Mutex<usize>,
+}
+
+impl FaultInjector {
+    fn new(config: &MockDAServerConfig) -> Self {
+        Self {
+            latency: config.latency,
+            failure_rate: config.failure_rate,
+            remaining_failures: Mutex::new(config.fail_first_requests),
+        }
+    }
+
+    /// Delays the request by the configured latency and returns whether it should fail.
+    async fn should_fail(&self) -> bool {
+        if !self.latency.is_zero() {
+            tokio::time::sleep(self.latency).await;
+        }
+
+        let mut remaining_failures = self.remaining_failures.lock().unwrap();
+        if *remaining_failures > 0 {
+            *remaining_failures -= 1;
+            return true;
+        }
+        self.failure_rate > 0.0 && rand::thread_rng().gen_bool(self.failure_rate.min(1.0))
+    }
+}
+
+#[derive(Debug)]
+struct ServerState {
+    config: MockDAServerConfig,
+    faults: FaultInjector,
+    submissions: Mutex<Submissions>,
+}
+
+#[derive(Debug, Serialize)]
+struct SubmitResponse {
+    submission_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SubmissionInfoQuery {
+    submission_id: String,
+}
+
+#[derive(Debug, Serialize)]
+struct SubmissionInfo {
+    block_hash: H256,
+    extrinsic_index: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct SubmissionInfoResponse {
+    submission: SubmissionInfo,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProofQuery {
+    index: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BridgeProofResponse {
+    blob_root: H256,
+    bridge_root: H256,
+    data_root_index: u64,
+    data_root_proof: Vec<H256>,
+    leaf: H256,
+    leaf_index: u64,
+    leaf_proof: Vec<H256>,
+    range_hash: H256,
+}
+
+/// Mock DA server bound to a local address.
+#[derive(Debug)]
+pub struct MockDAServer {
+    listener: TcpListener,
+    router: Router,
+}
+
+impl MockDAServer {
+    /// Binds the server to the provided address. Use port 0 to pick a free port.
+    pub async fn bind(address: SocketAddr, config: MockDAServerConfig) -> anyhow::Result<Self> {
+        let listener = TcpListener::bind(address)
+            .await
+            .with_context(|| format!("Failed binding mock DA server to {address}"))?;
+        let state = Arc::new(ServerState {
+            faults: FaultInjector::new(&config),
+            config,
+            submissions: Mutex::default(),
+        });
+
+        let router = Router::new()
+            .route("/v1/submit_raw_data", post(Self::submit_raw_data))
+            .route("/v1/get_submission_info", get(Self::get_submission_info))
+            .route("/eth/proof/:block_hash", get(Self::get_proof))
+            .layer(middleware::from_fn_with_state(
+                state.clone(),
+                Self::inject_faults,
+            ))
+            .with_state(state);
+        Ok(Self { listener, router })
+    }
+
+    pub fn local_addr(&self) -> anyhow::Result<SocketAddr> {
+        self.listener
+            .local_addr()
+            .context("Failed getting mock DA server address")
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        tracing::info!("Starting mock DA server on {}", self.local_addr()?);
+        axum::serve(self.listener, self.router)
+            .with_graceful_shutdown(async move {
+                stop_receiver.changed().await.ok();
+                tracing::info!("Stop signal received, mock DA server is shutting down");
+            })
+            .await
+            .context("Mock DA server failed")?;
+        tracing::info!("Mock DA server shut down");
+        Ok(())
+    }
+
+    async fn inject_faults(
+        State(state): State<Arc<ServerState>>,
+        request: Request,
+        next: Next,
+    ) -> Response {
+        if state.faults.should_fail().await {
+            tracing::info!(
+                "Injecting failure for {} {}",
+                request.method(),
+                request.uri()
+            );
+            return (StatusCode::INTERNAL_SERVER_ERROR, "injected failure").into_response();
+        }
+        next.run(request).await
+    }
+
+    async fn submit_raw_data(
+        State(state): State<Arc<ServerState>>,
+        headers: HeaderMap,
+        data: Bytes,
+    ) -> Result<Json<SubmitResponse>, (StatusCode, &'static str)> {
+        if let Some(api_key) = &state.config.api_key {
+            let provided_key = headers.get("x-api-key").and_then(|key| key.to_str().ok());
+            if provided_key != Some(api_key.as_str()) {
+                return Err((StatusCode::UNAUTHORIZED, "invalid API key"));
+            }
+        }
+        if data.is_empty() {
+            return Err((StatusCode::BAD_REQUEST, "empty submission"));
+        }
+
+        let mut submissions = state.submissions.lock().unwrap();
+        let id = submissions.list.len();
+        let data_hash = H256(keccak256(&data));
+        // Every submission is put into its own block, so that block hashes are unique.
+        let block_hash = H256(keccak256(
+            &[data_hash.as_bytes(), &(id as u64).to_be_bytes()].concat(),
+        ));
+        submissions.list.push(Submission {
+            block_hash,
+            extrinsic_index: 1,
+            data_hash,
+            submitted_at: Instant::now(),
+        });
+        submissions.by_block_hash.insert(block_hash, id);
+        tracing::debug!("Accepted submission #{id} with {} bytes", data.len());
+
+        Ok(Json(SubmitResponse {
+            submission_id: id.to_string(),
+        }))
+    }
+
+    async fn get_submission_info(
+        State(state): State<Arc<ServerState>>,
+        Query(query): Query<SubmissionInfoQuery>,
+    ) -> Result<Json<SubmissionInfoResponse>, StatusCode> {
+        let submissions = state.submissions.lock().unwrap();
+        let submission = query
+            .submission_id
+            .parse::<usize>()
+            .ok()
+            .and_then(|id| submissions.list.get(id))
+            .ok_or(StatusCode::NOT_FOUND)?;
+        Ok(Json(SubmissionInfoResponse {
+            submission: SubmissionInfo {
+                block_hash: submission.block_hash,
+                extrinsic_index: submission.extrinsic_index,
+            },
+        }))
+    }
+
+    async fn get_proof(
+        State(state): State<Arc<ServerState>>,
+        Path(block_hash): Path<String>,
+        Query(query): Query<ProofQuery>,
+    ) -> Result<Json<BridgeProofResponse>, StatusCode> {
+        let block_hash = block_hash
+            .strip_prefix("0x")
+            .unwrap_or(&block_hash)
+            .parse::<H256>()
+            .map_err(|_| StatusCode::BAD_REQUEST)?;
+
+        let submissions = state.submissions.lock().unwrap();
+        let id = *submissions
+            .by_block_hash
+            .get(&block_hash)
+            .ok_or(StatusCode::NOT_FOUND)?;
+        let submission = &submissions.list[id];
+        if submission.extrinsic_index != query.index {
+            return Err(StatusCode::NOT_FOUND);
+        }
+        // Mirrors the bridge API, which responds with 404 until the block is bridged to L1.
+        if submission.submitted_at.elapsed() < state.config.inclusion_delay {
+            return Err(StatusCode::NOT_FOUND);
+        }
+
+        let leaf = submission.data_hash;
+        let blob_root = H256(keccak256(leaf.as_bytes()));
+        Ok(Json(BridgeProofResponse {
+            blob_root,
+            bridge_root: H256::zero(),
+            data_root_index: id as u64,
+            data_root_proof: vec![blob_root],
+            leaf,
+            leaf_index: 0,
+            leaf_proof: vec![],
+            range_hash: submission.block_hash,
+        }))
+    }
+}
@@ -0,0 +1,67 @@
+//! Runs the mock DA server as a standalone process, e.g. for local end-to-end tests of the DA dispatcher.
+//! See `README.md` for details.
+
+use std::{net::SocketAddr, time::Duration};
+
+use clap::Parser;
+use tokio::sync::watch;
+use zksync_da_mock_server::{MockDAServer, MockDAServerConfig, MockGrpcDAServer};
+
+#[derive(Debug, Parser)]
+#[command(author = "Matter Labs", about = "Local stand-in DA server")]
+struct Cli {
+    /// Port to listen on for HTTP requests (Avail).
+    #[arg(long, default_value_t = 3100)]
+    port: u16,
+    /// Port to listen on for gRPC requests (Celestia and Eigen).
+    #[arg(long, default_value_t = 3101)]
+    grpc_port: u16,
+    /// Artificial latency added to every request, in milliseconds.
+    #[arg(long, default_value_t = 0)]
+    latency_ms: u64,
+    /// Probability of a request failing with an internal server error.
+    #[arg(long, default_value_t = 0.0)]
+    failure_rate: f64,
+    /// Number of first requests that fail unconditionally.
+    #[arg(long, default_value_t = 0)]
+    fail_first_requests: usize,
+    /// Time after submission before the inclusion proof for a blob becomes available, in milliseconds.
+    #[arg(long, default_value_t = 0)]
+    inclusion_delay_ms: u64,
+    /// API key expected in submissions. If not set, any key is accepted.
+    #[arg(long)]
+    api_key: Option<String>,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let _guard = zksync_vlog::ObservabilityBuilder::new().build();
+    let cli = Cli::parse();
+    anyhow::ensure!(
+        (0.0..=1.0).contains(&cli.failure_rate),
+        "failure rate must be in [0, 1]"
+    );
+
+    let config = MockDAServerConfig {
+        latency: Duration::from_millis(cli.latency_ms),
+        failure_rate: cli.failure_rate,
+        fail_first_requests: cli.fail_first_requests,
+        inclusion_delay: Duration::from_millis(cli.inclusion_delay_ms),
+        api_key: cli.api_key,
+    };
+    let server =
+        MockDAServer::bind(SocketAddr::from(([0, 0, 0, 0], cli.port)), config.clone()).await?;
+    let grpc_server =
+        MockGrpcDAServer::bind(SocketAddr::from(([0, 0, 0, 0], cli.grpc_port)), config).await?;
+
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    tokio::spawn(async move {
+        tokio::signal::ctrl_c().await.ok();
+        stop_sender.send_replace(true);
+    });
+    tokio::try_join!(
+        server.run(stop_receiver.clone()),
+        grpc_server.run(stop_receiver)
+    )?;
+    Ok(())
+}
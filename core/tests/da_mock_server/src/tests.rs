@@ -0,0 +1,281 @@
+use tonic::Code;
+use zksync_basic_types::secrets::PrivateKey;
+use zksync_config::configs::da_client::{
+    avail::{AvailClientConfig, AvailConfig, AvailGasRelayConfig, AvailSecrets},
+    celestia::{CelestiaConfig, CelestiaSecrets},
+};
+use zksync_da_client::DataAvailabilityClient;
+use zksync_da_clients::{
+    avail::AvailClient,
+    celestia::CelestiaClient,
+    eigen::disperser::{
+        disperser_client::DisperserClient, BlobStatus, BlobStatusRequest, DisperseBlobRequest,
+        RetrieveBlobRequest,
+    },
+};
+
+use super::*;
+
+const API_KEY: &str = "test-api-key";
+
+async fn spawn_server(config: MockDAServerConfig) -> (SocketAddr, watch::Sender<bool>) {
+    let server = MockDAServer::bind(SocketAddr::from(([127, 0, 0, 1], 0)), config)
+        .await
+        .unwrap();
+    let address = server.local_addr().unwrap();
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    tokio::spawn(server.run(stop_receiver));
+    (address, stop_sender)
+}
+
+async fn spawn_grpc_server(config: MockDAServerConfig) -> (SocketAddr, watch::Sender<bool>) {
+    let server = MockGrpcDAServer::bind(SocketAddr::from(([127, 0, 0, 1], 0)), config)
+        .await
+        .unwrap();
+    let address = server.local_addr().unwrap();
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    tokio::spawn(server.run(stop_receiver));
+    (address, stop_sender)
+}
+
+async fn avail_client(address: SocketAddr, api_key: &str) -> AvailClient {
+    let url = format!("http://{address}");
+    let config = AvailConfig {
+        bridge_api_url: url.clone(),
+        timeout_ms: 5_000,
+        config: AvailClientConfig::GasRelay(AvailGasRelayConfig {
+            gas_relay_api_url: url,
+            max_retries: 1,
+            inclusion_delay_ms: Some(0),
+        }),
+    };
+    let secrets = AvailSecrets {
+        seed_phrase: None,
+        gas_relay_api_key: Some(api_key.to_owned().into()),
+    };
+    AvailClient::new(config, secrets).await.unwrap()
+}
+
+#[tokio::test]
+async fn dispatching_blob_and_polling_for_inclusion() {
+    let config = MockDAServerConfig {
+        inclusion_delay: Duration::from_millis(500),
+        api_key: Some(API_KEY.to_owned()),
+        ..MockDAServerConfig::default()
+    };
+    let (address, _stop_sender) = spawn_server(config).await;
+    let client = avail_client(address, API_KEY).await;
+
+    let response = client.dispatch_blob(1, vec![1; 32]).await.unwrap();
+    let other_response = client.dispatch_blob(2, vec![1; 32]).await.unwrap();
+    assert_ne!(response.blob_id, other_response.blob_id);
+
+    let inclusion_data = client.get_inclusion_data(&response.blob_id).await.unwrap();
+    assert!(inclusion_data.is_none());
+
+    tokio::time::sleep(Duration::from_millis(600)).await;
+    let inclusion_data = client.get_inclusion_data(&response.blob_id).await.unwrap();
+    assert!(!inclusion_data.unwrap().data.is_empty());
+}
+
+#[tokio::test]
+async fn injected_failures_are_retriable() {
+    let config = MockDAServerConfig {
+        fail_first_requests: 1,
+        ..MockDAServerConfig::default()
+    };
+    let (address, _stop_sender) = spawn_server(config).await;
+    let client = avail_client(address, API_KEY).await;
+
+    let err = client.dispatch_blob(1, vec![1; 32]).await.unwrap_err();
+    assert!(err.is_retriable(), "{err:?}");
+    let response = client.dispatch_blob(1, vec![1; 32]).await.unwrap();
+
+    let inclusion_data = client.get_inclusion_data(&response.blob_id).await.unwrap();
+    assert!(inclusion_data.is_some());
+}
+
+#[tokio::test]
+async fn unknown_blob_is_not_included() {
+    let (address, _stop_sender) = spawn_server(MockDAServerConfig::default()).await;
+    let client = avail_client(address, API_KEY).await;
+
+    let blob_id = format!("{:x}:1", H256::repeat_byte(1));
+    let inclusion_data = client.get_inclusion_data(&blob_id).await.unwrap();
+    assert!(inclusion_data.is_none());
+}
+
+#[tokio::test]
+async fn submission_with_invalid_api_key_is_rejected() {
+    let config = MockDAServerConfig {
+        api_key: Some(API_KEY.to_owned()),
+        ..MockDAServerConfig::default()
+    };
+    let (address, _stop_sender) = spawn_server(config).await;
+    let client = avail_client(address, "wrong-key").await;
+
+    client.dispatch_blob(1, vec![1; 32]).await.unwrap_err();
+}
+
+async fn celestia_client(address: SocketAddr) -> CelestiaClient {
+    let config = CelestiaConfig {
+        api_node_url: format!("http://{address}"),
+        namespace: "7a6b73796e63".to_owned(),
+        chain_id: "mock-celestia".to_owned(),
+        timeout_ms: 5_000,
+    };
+    let secrets = CelestiaSecrets {
+        private_key: PrivateKey::from(
+            "0101010101010101010101010101010101010101010101010101010101010101",
+        ),
+    };
+    CelestiaClient::new(config, secrets).await.unwrap()
+}
+
+#[tokio::test]
+async fn dispatching_blobs_to_celestia() {
+    let config = MockDAServerConfig {
+        inclusion_delay: Duration::from_millis(500),
+        ..MockDAServerConfig::default()
+    };
+    let (address, _stop_sender) = spawn_grpc_server(config).await;
+    let client = celestia_client(address).await;
+    let initial_balance = client.balance().await.unwrap();
+
+    // The client waits for the transaction to be included, so dispatching takes at least `inclusion_delay`.
+    let response = client.dispatch_blob(1, vec![1; 1_000]).await.unwrap();
+    let other_response = client.dispatch_blob(2, vec![1; 1_000]).await.unwrap();
+    // Blobs have the same commitment, but are included at different heights.
+    assert_ne!(response.blob_id, other_response.blob_id);
+
+    let fee = response.fee.unwrap();
+    assert!(fee > 0);
+    let balance = client.balance().await.unwrap();
+    assert_eq!(balance, initial_balance - fee - other_response.fee.unwrap());
+}
+
+#[tokio::test]
+async fn injected_failures_fail_celestia_dispatch() {
+    let config = MockDAServerConfig {
+        fail_first_requests: 1,
+        ..MockDAServerConfig::default()
+    };
+    let (address, _stop_sender) = spawn_grpc_server(config).await;
+    let client = celestia_client(address).await;
+
+    client.dispatch_blob(1, vec![1; 32]).await.unwrap_err();
+    client.dispatch_blob(1, vec![1; 32]).await.unwrap();
+}
+
+/// Pads data in the same way as the EigenDA client: each 31 bytes are prefixed with a zero byte.
+fn padded_eigen_blob(len: usize) -> Vec<u8> {
+    (0..len)
+        .map(|i| i as u8)
+        .collect::<Vec<_>>()
+        .chunks(31)
+        .flat_map(|chunk| [&[0][..], chunk].concat())
+        .collect()
+}
+
+fn disperse_request(data: Vec<u8>) -> DisperseBlobRequest {
+    DisperseBlobRequest {
+        data,
+        custom_quorum_numbers: vec![],
+        account_id: String::new(),
+    }
+}
+
+#[tokio::test]
+async fn dispersing_blob_to_eigen() {
+    let config = MockDAServerConfig {
+        inclusion_delay: Duration::from_millis(500),
+        ..MockDAServerConfig::default()
+    };
+    let (address, _stop_sender) = spawn_grpc_server(config).await;
+    let mut client = DisperserClient::connect(format!("http://{address}"))
+        .await
+        .unwrap();
+
+    let data = padded_eigen_blob(100);
+    let reply = client
+        .disperse_blob(disperse_request(data.clone()))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(reply.result, BlobStatus::Processing as i32);
+
+    let status_request = BlobStatusRequest {
+        request_id: reply.request_id,
+    };
+    let status = client
+        .get_blob_status(status_request.clone())
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(status.status, BlobStatus::Processing as i32);
+    assert!(status.info.is_none());
+
+    tokio::time::sleep(Duration::from_millis(600)).await;
+    let status = client
+        .get_blob_status(status_request)
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(status.status, BlobStatus::Finalized as i32);
+    let proof = status.info.unwrap().blob_verification_proof.unwrap();
+
+    let retrieved = client
+        .retrieve_blob(RetrieveBlobRequest {
+            batch_header_hash: proof.batch_metadata.unwrap().batch_header_hash,
+            blob_index: proof.blob_index,
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(retrieved.data, data);
+}
+
+#[tokio::test]
+async fn eigen_rejects_invalid_requests() {
+    let (address, _stop_sender) = spawn_grpc_server(MockDAServerConfig::default()).await;
+    let mut client = DisperserClient::connect(format!("http://{address}"))
+        .await
+        .unwrap();
+
+    // Not padded, so the data doesn't consist of valid field elements.
+    let err = client
+        .disperse_blob(disperse_request(vec![1; 64]))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+
+    let err = client
+        .get_blob_status(BlobStatusRequest {
+            request_id: vec![1; 32],
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn injected_failures_for_eigen() {
+    let config = MockDAServerConfig {
+        fail_first_requests: 1,
+        ..MockDAServerConfig::default()
+    };
+    let (address, _stop_sender) = spawn_grpc_server(config).await;
+    let mut client = DisperserClient::connect(format!("http://{address}"))
+        .await
+        .unwrap();
+
+    let err = client
+        .disperse_blob(disperse_request(padded_eigen_blob(10)))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::Unavailable);
+    client
+        .disperse_blob(disperse_request(padded_eigen_blob(10)))
+        .await
+        .unwrap();
+}
@@ -9,7 +9,9 @@ use clap::Parser;
 use shivini::{ProverContext, ProverContextConfig};
 use tokio_util::sync::CancellationToken;
 use zksync_circuit_prover::{FinalizationHintsCache, SetupDataCache, PROVER_BINARY_METRICS};
-use zksync_circuit_prover_service::job_runner::{circuit_prover_runner, WvgRunnerBuilder};
+use zksync_circuit_prover_service::job_runner::{
+    circuit_prover_runner, fake_circuit_prover_runner, WvgRunnerBuilder,
+};
 use zksync_config::{
     configs::{FriProverConfig, ObservabilityConfig},
     ObjectStoreConfig,
@@ -56,6 +58,11 @@ struct Cli {
     /// None corresponds to allocating all available VRAM.
     #[arg(short = 'm', long)]
     pub(crate) max_allocation: Option<usize>,
+    /// Exchange fake artifacts instead of generating witness vectors & proofs.
+    /// Runs as many fake provers as light & heavy WVGs together; needs neither setup data nor GPU.
+    /// For testing only.
+    #[arg(long)]
+    fake_proofs: bool,
 }
 
 #[tokio::main]
@@ -68,19 +75,6 @@ async fn main() -> anyhow::Result<()> {
         .install()
         .context("failed to install observability")?;
 
-    let (connection_pool, object_store, prover_context, setup_data_cache, hints) = load_resources(
-        opt.secrets_path,
-        opt.max_allocation,
-        object_store_config,
-        prover_config.setup_data_path.into(),
-    )
-    .await
-    .context("failed to load configs")?;
-
-    PROVER_BINARY_METRICS
-        .startup_time
-        .observe(start_time.elapsed());
-
     let cancellation_token = CancellationToken::new();
 
     let exporter_config = PrometheusExporterConfig::pull(prover_config.prometheus_port);
@@ -88,42 +82,76 @@ async fn main() -> anyhow::Result<()> {
 
     let mut tasks = vec![tokio::spawn(exporter_config.run(metrics_stop_receiver))];
 
-    let (witness_vector_sender, witness_vector_receiver) = tokio::sync::mpsc::channel(CHANNEL_SIZE);
+    if opt.fake_proofs {
+        tracing::warn!("Running in fake proof mode, no proofs will be generated");
+        let (connection_pool, object_store) = load_storage(opt.secrets_path, object_store_config)
+            .await
+            .context("failed to load storage")?;
+        PROVER_BINARY_METRICS
+            .startup_time
+            .observe(start_time.elapsed());
 
-    tracing::info!(
-        "Starting {} light WVGs and {} heavy WVGs.",
-        opt.light_wvg_count,
-        opt.heavy_wvg_count
-    );
+        let fake_circuit_prover_runner = fake_circuit_prover_runner(
+            connection_pool,
+            object_store,
+            PROVER_PROTOCOL_SEMANTIC_VERSION,
+            opt.light_wvg_count + opt.heavy_wvg_count,
+            cancellation_token.clone(),
+        );
+        tasks.extend(fake_circuit_prover_runner.run());
+    } else {
+        let (connection_pool, object_store, prover_context, setup_data_cache, hints) =
+            load_resources(
+                opt.secrets_path,
+                opt.max_allocation,
+                object_store_config,
+                prover_config.setup_data_path.into(),
+            )
+            .await
+            .context("failed to load configs")?;
 
-    let builder = WvgRunnerBuilder::new(
-        connection_pool.clone(),
-        object_store.clone(),
-        PROVER_PROTOCOL_SEMANTIC_VERSION,
-        hints.clone(),
-        witness_vector_sender,
-        cancellation_token.clone(),
-    );
+        PROVER_BINARY_METRICS
+            .startup_time
+            .observe(start_time.elapsed());
 
-    let light_wvg_runner = builder.light_wvg_runner(opt.light_wvg_count);
-    let heavy_wvg_runner = builder.heavy_wvg_runner(opt.heavy_wvg_count);
+        let (witness_vector_sender, witness_vector_receiver) =
+            tokio::sync::mpsc::channel(CHANNEL_SIZE);
 
-    tasks.extend(light_wvg_runner.run());
-    tasks.extend(heavy_wvg_runner.run());
+        tracing::info!(
+            "Starting {} light WVGs and {} heavy WVGs.",
+            opt.light_wvg_count,
+            opt.heavy_wvg_count
+        );
 
-    // necessary as it has a connection_pool which will keep 1 connection active by default
-    drop(builder);
+        let builder = WvgRunnerBuilder::new(
+            connection_pool.clone(),
+            object_store.clone(),
+            PROVER_PROTOCOL_SEMANTIC_VERSION,
+            hints.clone(),
+            witness_vector_sender,
+            cancellation_token.clone(),
+        );
 
-    let circuit_prover_runner = circuit_prover_runner(
-        connection_pool,
-        object_store,
-        PROVER_PROTOCOL_SEMANTIC_VERSION,
-        setup_data_cache,
-        witness_vector_receiver,
-        prover_context,
-    );
+        let light_wvg_runner = builder.light_wvg_runner(opt.light_wvg_count);
+        let heavy_wvg_runner = builder.heavy_wvg_runner(opt.heavy_wvg_count);
+
+        tasks.extend(light_wvg_runner.run());
+        tasks.extend(heavy_wvg_runner.run());
+
+        // necessary as it has a connection_pool which will keep 1 connection active by default
+        drop(builder);
 
-    tasks.extend(circuit_prover_runner.run());
+        let circuit_prover_runner = circuit_prover_runner(
+            connection_pool,
+            object_store,
+            PROVER_PROTOCOL_SEMANTIC_VERSION,
+            setup_data_cache,
+            witness_vector_receiver,
+            prover_context,
+        );
+
+        tasks.extend(circuit_prover_runner.run());
+    }
 
     let mut tasks = ManagedTasks::new(tasks);
     tokio::select! {
@@ -192,22 +220,7 @@ async fn load_resources(
     SetupDataCache,
     FinalizationHintsCache,
 )> {
-    let database_secrets =
-        load_database_secrets(secrets_path).context("failed to load database secrets")?;
-    let database_url = database_secrets
-        .prover_url
-        .context("no prover DB URl present")?;
-    // 2 connections for the witness vector generator job pickers (1 each) and 1 for gpu circuit prover job saver
-    let max_connections = 3;
-    let connection_pool = ConnectionPool::<Prover>::builder(database_url, max_connections)
-        .build()
-        .await
-        .context("failed to build connection pool")?;
-
-    let object_store = ObjectStoreFactory::new(object_store_config)
-        .create_store()
-        .await
-        .context("failed to create object store")?;
+    let (connection_pool, object_store) = load_storage(secrets_path, object_store_config).await?;
 
     let prover_context = match max_gpu_vram_allocation {
         Some(max_allocation) => ProverContext::create_with_config(
@@ -241,3 +254,29 @@ async fn load_resources(
         finalization_hints,
     ))
 }
+
+/// Loads storage necessary for both real & fake proving.
+/// - connection pool - necessary to pick & store jobs from database
+/// - object store - necessary  for loading and storing artifacts to object store
+async fn load_storage(
+    secrets_path: Option<PathBuf>,
+    object_store_config: ObjectStoreConfig,
+) -> anyhow::Result<(ConnectionPool<Prover>, Arc<dyn ObjectStore>)> {
+    let database_secrets =
+        load_database_secrets(secrets_path).context("failed to load database secrets")?;
+    let database_url = database_secrets
+        .prover_url
+        .context("no prover DB URl present")?;
+    // 2 connections for the witness vector generator job pickers (1 each) and 1 for gpu circuit prover job saver
+    let max_connections = 3;
+    let connection_pool = ConnectionPool::<Prover>::builder(database_url, max_connections)
+        .build()
+        .await
+        .context("failed to build connection pool")?;
+
+    let object_store = ObjectStoreFactory::new(object_store_config)
+        .create_store()
+        .await
+        .context("failed to create object store")?;
+    Ok((connection_pool, object_store))
+}
@@ -0,0 +1,152 @@
+use std::{sync::Arc, time::Instant};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use tokio::task::JoinHandle;
+use zksync_object_store::ObjectStore;
+use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
+use zksync_prover_fri_types::{
+    fake::{FakeArtifact, FakeArtifactKind},
+    get_current_pod_name, AuxOutputWitnessWrapper, FriProofWrapper,
+};
+use zksync_queued_job_processor::JobProcessor;
+use zksync_types::{
+    basic_fri_types::AggregationRound, protocol_version::ProtocolSemanticVersion, L1BatchNumber,
+};
+
+/// Proof compressor for fake proof mode. Picks compression jobs the same way as
+/// [`ProofCompressor`](crate::compressor::ProofCompressor), checks the fake scheduler proof and
+/// marks the job as skipped, so that the batch is submitted without a proof.
+///
+/// Fake proofs can't be wrapped into a proof verifiable on L1, so proof gateway reports such
+/// batches as having skipped proof generation; core must be configured to send dummy proofs.
+pub struct FakeProofCompressor {
+    blob_store: Arc<dyn ObjectStore>,
+    pool: ConnectionPool<Prover>,
+    max_attempts: u32,
+    protocol_version: ProtocolSemanticVersion,
+}
+
+impl FakeProofCompressor {
+    pub fn new(
+        blob_store: Arc<dyn ObjectStore>,
+        pool: ConnectionPool<Prover>,
+        max_attempts: u32,
+        protocol_version: ProtocolSemanticVersion,
+    ) -> Self {
+        Self {
+            blob_store,
+            pool,
+            max_attempts,
+            protocol_version,
+        }
+    }
+}
+
+#[async_trait]
+impl JobProcessor for FakeProofCompressor {
+    type Job = FakeArtifact;
+    type JobId = L1BatchNumber;
+    type JobArtifacts = ();
+
+    const SERVICE_NAME: &'static str = "FakeProofCompressor";
+
+    async fn get_next_job(&self) -> anyhow::Result<Option<(Self::JobId, Self::Job)>> {
+        let mut conn = self.pool.connection().await.unwrap();
+        let pod_name = get_current_pod_name();
+        let Some(l1_batch_number) = conn
+            .fri_proof_compressor_dal()
+            .get_next_proof_compression_job(&pod_name, self.protocol_version)
+            .await
+        else {
+            return Ok(None);
+        };
+        let Some(fri_proof_id) = conn
+            .fri_prover_jobs_dal()
+            .get_scheduler_proof_job_id(l1_batch_number)
+            .await
+        else {
+            anyhow::bail!("Scheduler proof is missing from database for batch {l1_batch_number}");
+        };
+        tracing::info!(
+            "Started fake proof compression for L1 batch: {:?}",
+            l1_batch_number
+        );
+
+        let fri_proof = FakeArtifact::get::<FriProofWrapper>(
+            &*self.blob_store,
+            fri_proof_id,
+            FakeArtifactKind::Proof,
+            l1_batch_number,
+        )
+        .await
+        .with_context(|| format!("Failed to get fake fri proof from blob store for {l1_batch_number} with id {fri_proof_id}"))?;
+        anyhow::ensure!(
+            fri_proof.aggregation_round == AggregationRound::Scheduler,
+            "Must be a scheduler proof, got {fri_proof:?}"
+        );
+        FakeArtifact::get::<AuxOutputWitnessWrapper>(
+            &*self.blob_store,
+            l1_batch_number,
+            FakeArtifactKind::AuxOutputWitness,
+            l1_batch_number,
+        )
+        .await
+        .context("Failed to get fake aux output witness from blob store")?;
+        Ok(Some((l1_batch_number, fri_proof)))
+    }
+
+    async fn save_failure(&self, job_id: Self::JobId, _started_at: Instant, error: String) {
+        self.pool
+            .connection()
+            .await
+            .unwrap()
+            .fri_proof_compressor_dal()
+            .mark_proof_compression_job_failed(&error, job_id)
+            .await;
+    }
+
+    async fn process_job(
+        &self,
+        _job_id: &L1BatchNumber,
+        _job: FakeArtifact,
+        _started_at: Instant,
+    ) -> JoinHandle<anyhow::Result<()>> {
+        tokio::spawn(async { Ok(()) })
+    }
+
+    async fn save_result(
+        &self,
+        job_id: Self::JobId,
+        started_at: Instant,
+        _artifacts: (),
+    ) -> anyhow::Result<()> {
+        tracing::info!("Finished fake proof compression for job: {job_id}");
+        self.pool
+            .connection()
+            .await
+            .unwrap()
+            .fri_proof_compressor_dal()
+            .mark_proof_compression_job_skipped(job_id, started_at.elapsed())
+            .await;
+        Ok(())
+    }
+
+    fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    async fn get_job_attempts(&self, job_id: &L1BatchNumber) -> anyhow::Result<u32> {
+        let mut prover_storage = self
+            .pool
+            .connection()
+            .await
+            .context("failed to acquire DB connection for FakeProofCompressor")?;
+        prover_storage
+            .fri_proof_compressor_dal()
+            .get_proof_compression_job_attempts(*job_id)
+            .await
+            .map(|attempts| attempts.unwrap_or(0))
+            .context("failed to get job attempts for FakeProofCompressor")
+    }
+}
@@ -18,10 +18,12 @@ use zksync_task_management::ManagedTasks;
 use zksync_vlog::prometheus::PrometheusExporterConfig;
 
 use crate::{
-    compressor::ProofCompressor, initial_setup_keys::download_initial_setup_keys_if_not_present,
+    compressor::ProofCompressor, fake_compressor::FakeProofCompressor,
+    initial_setup_keys::download_initial_setup_keys_if_not_present,
 };
 
 mod compressor;
+mod fake_compressor;
 mod initial_setup_keys;
 mod metrics;
 
@@ -38,6 +40,12 @@ struct Cli {
     pub(crate) config_path: Option<std::path::PathBuf>,
     #[arg(long)]
     pub(crate) secrets_path: Option<std::path::PathBuf>,
+    /// Skips compression of fake FRI proofs produced by the prover pipeline in fake proof mode.
+    /// No compressed proof is produced: jobs are marked as skipped, so proof gateway reports
+    /// skipped proof generation to core, which only works if core sends dummy proofs to L1.
+    /// Refuses to start if the prover database contains real compressed proofs. For testing only.
+    #[arg(long)]
+    pub(crate) fake_proofs: bool,
 }
 
 #[tokio::main]
@@ -76,32 +84,6 @@ async fn main() -> anyhow::Result<()> {
 
     let protocol_version = PROVER_PROTOCOL_SEMANTIC_VERSION;
 
-    let prover_config = general_config
-        .prover_config
-        .expect("ProverConfig doesn't exist");
-    let keystore =
-        Keystore::locate().with_setup_path(Some(prover_config.setup_data_path.clone().into()));
-
-    let l1_verifier_config = pool
-        .connection()
-        .await?
-        .fri_protocol_versions_dal()
-        .get_l1_verifier_config()
-        .await
-        .map_err(|_| anyhow::anyhow!("Failed to get L1 verifier config from database"))?;
-    if l1_verifier_config.fflonk_snark_wrapper_vk_hash.is_none() && is_fflonk {
-        anyhow::bail!("There was no FFLONK verification hash found in the database while trying to run compressor in FFLONK mode, aborting");
-    }
-
-    let proof_compressor = ProofCompressor::new(
-        blob_store,
-        pool,
-        config.max_attempts,
-        protocol_version,
-        keystore,
-        is_fflonk,
-    );
-
     let (stop_sender, stop_receiver) = watch::channel(false);
 
     let (stop_signal_sender, stop_signal_receiver) = oneshot::channel();
@@ -113,18 +95,64 @@ async fn main() -> anyhow::Result<()> {
     })
     .expect("Error setting Ctrl+C handler"); // Setting handler should always succeed.
 
-    setup_crs_keys(&config);
-
-    tracing::info!("Starting proof compressor");
-
     let prometheus_config = PrometheusExporterConfig::push(
         config.prometheus_pushgateway_url,
         Duration::from_millis(config.prometheus_push_interval_ms.unwrap_or(100)),
     );
-    let tasks = vec![
-        tokio::spawn(prometheus_config.run(stop_receiver.clone())),
-        tokio::spawn(proof_compressor.run(stop_receiver, opt.number_of_iterations)),
-    ];
+    let mut tasks = vec![tokio::spawn(prometheus_config.run(stop_receiver.clone()))];
+    if opt.fake_proofs {
+        // Batches compressed in fake proof mode are submitted without proofs, so a database
+        // that has already produced real proofs must never be used in this mode.
+        let has_compressed_proofs = pool
+            .connection()
+            .await?
+            .fri_proof_compressor_dal()
+            .has_compressed_proofs()
+            .await;
+        anyhow::ensure!(
+            !has_compressed_proofs,
+            "Prover database contains real compressed proofs; refusing to run in fake proof mode"
+        );
+        tracing::warn!("Starting fake proof compressor, batches will be submitted without proofs");
+        let proof_compressor =
+            FakeProofCompressor::new(blob_store, pool, config.max_attempts, protocol_version);
+        tasks.push(tokio::spawn(
+            proof_compressor.run(stop_receiver, opt.number_of_iterations),
+        ));
+    } else {
+        let prover_config = general_config
+            .prover_config
+            .expect("ProverConfig doesn't exist");
+        let keystore =
+            Keystore::locate().with_setup_path(Some(prover_config.setup_data_path.clone().into()));
+
+        let l1_verifier_config = pool
+            .connection()
+            .await?
+            .fri_protocol_versions_dal()
+            .get_l1_verifier_config()
+            .await
+            .map_err(|_| anyhow::anyhow!("Failed to get L1 verifier config from database"))?;
+        if l1_verifier_config.fflonk_snark_wrapper_vk_hash.is_none() && is_fflonk {
+            anyhow::bail!("There was no FFLONK verification hash found in the database while trying to run compressor in FFLONK mode, aborting");
+        }
+
+        let proof_compressor = ProofCompressor::new(
+            blob_store,
+            pool,
+            config.max_attempts,
+            protocol_version,
+            keystore,
+            is_fflonk,
+        );
+
+        setup_crs_keys(&config);
+
+        tracing::info!("Starting proof compressor");
+        tasks.push(tokio::spawn(
+            proof_compressor.run(stop_receiver, opt.number_of_iterations),
+        ));
+    }
 
     let mut tasks = ManagedTasks::new(tasks).allow_tasks_to_finish();
     tokio::select! {
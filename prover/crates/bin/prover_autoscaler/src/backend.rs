@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+
+use crate::cluster_types::Cluster;
+
+/// Scheduler running prover Deployments and their Pods, e.g. Kubernetes. Agent serves cluster
+/// status and scale requests from Scaler using a backend.
+#[async_trait]
+pub trait SchedulerBackend: Send + Sync {
+    /// Returns the current state of Deployments, Pods and scale errors in watched namespaces.
+    async fn cluster(&self) -> Cluster;
+    /// Sets the desired number of replicas of the Deployment `name` in `namespace`.
+    async fn scale(&self, namespace: &str, name: &str, size: i32) -> anyhow::Result<()>;
+}
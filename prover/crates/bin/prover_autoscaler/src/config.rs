@@ -19,6 +19,7 @@ pub struct ProverAutoscalerConfig {
     pub graceful_shutdown_timeout: Duration,
     pub agent_config: Option<ProverAutoscalerAgentConfig>,
     pub scaler_config: Option<ProverAutoscalerScalerConfig>,
+    pub simulator_config: Option<ProverAutoscalerSimulatorConfig>,
     pub observability: Option<ObservabilityConfig>,
 }
 
@@ -69,6 +70,30 @@ pub struct ProverAutoscalerScalerConfig {
     /// If dry-run enabled don't send any scale requests.
     #[serde(default)]
     pub dry_run: bool,
+    /// If set, every queue report fetched from prover-job-monitor is appended to this file as a
+    /// JSON line, to be replayed by the simulator later.
+    #[serde(default)]
+    pub queue_report_log_path: Option<PathBuf>,
+}
+
+/// Config of the Simulator, which replays recorded queue reports against simulated clusters using
+/// the policy from `scaler_config`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ProverAutoscalerSimulatorConfig {
+    /// Path to queue reports recorded by Scaler, see `queue_report_log_path`.
+    pub queue_report_log_path: PathBuf,
+    /// Time for a Pod to start running after a machine is available for it.
+    #[serde(
+        with = "humantime_serde",
+        default = "ProverAutoscalerSimulatorConfig::default_pod_startup_delay"
+    )]
+    pub pod_startup_delay: Duration,
+    /// Map of cluster name to number of machines available per Deployment. Pods above it stay
+    /// Pending and cause scale errors.
+    pub clusters: HashMap<String, HashMap<String, usize>>,
+    /// Cost of one replica-hour per Deployment. Deployments not listed are free.
+    #[serde(default)]
+    pub replica_hour_cost: HashMap<String, f64>,
 }
 
 // TODO: generate this enum by QueueReport from https://github.com/matter-labs/zksync-era/blob/main/prover/crates/bin/prover_job_monitor/src/autoscaler_queue_reporter.rs#L23
@@ -176,6 +201,13 @@ impl ProverAutoscalerScalerConfig {
     }
 }
 
+impl ProverAutoscalerSimulatorConfig {
+    /// Default pod_startup_delay -- 2m
+    pub fn default_pod_startup_delay() -> Duration {
+        Duration::from_secs(120)
+    }
+}
+
 impl ScalerTarget {
     pub fn default_speed() -> ScalarOrMap {
         ScalarOrMap::Scalar(1)
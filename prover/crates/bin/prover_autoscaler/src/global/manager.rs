@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use super::{
-    queuer,
+    queuer::{self, Queue},
     scaler::{Scaler, ScalerTrait},
     watcher,
 };
@@ -38,39 +38,7 @@ impl Manager {
                     .set(1);
             });
 
-        let mut scalers: Vec<Box<dyn ScalerTrait + Sync + Send>> = Vec::default();
-        let mut jobs = Vec::default();
-        for c in &config.scaler_targets {
-            jobs.push(c.queue_report_field);
-            match c.scaler_target_type {
-                ScalerTargetType::Gpu => scalers.push(Box::new(Scaler::<GpuKey>::new(
-                    c.queue_report_field,
-                    c.deployment.clone(),
-                    c.min_replicas,
-                    c.max_replicas
-                        .iter()
-                        .map(|(k, v)| (k.clone(), v.into_map_gpukey()))
-                        .collect(),
-                    c.speed.into_map_gpukey(),
-                    config.cluster_priorities.clone(),
-                    config.apply_min_to_namespace.clone(),
-                    chrono::Duration::seconds(config.long_pending_duration.as_secs() as i64),
-                ))),
-                ScalerTargetType::Simple => scalers.push(Box::new(Scaler::<NoKey>::new(
-                    c.queue_report_field,
-                    c.deployment.clone(),
-                    c.min_replicas,
-                    c.max_replicas
-                        .iter()
-                        .map(|(k, v)| (k.clone(), v.into_map_nokey()))
-                        .collect(),
-                    c.speed.into_map_nokey(),
-                    config.cluster_priorities.clone(),
-                    config.apply_min_to_namespace.clone(),
-                    chrono::Duration::seconds(config.long_pending_duration.as_secs() as i64),
-                ))),
-            };
-        }
+        let (jobs, scalers) = scalers_from_config(&config);
         Self {
             namespaces: config.protocol_versions.clone(),
             watcher,
@@ -81,6 +49,50 @@ impl Manager {
     }
 }
 
+/// Creates scalers for all configured targets. Returns them along with the list of jobs to get
+/// queues for.
+pub(crate) fn scalers_from_config(
+    config: &ProverAutoscalerScalerConfig,
+) -> (
+    Vec<QueueReportFields>,
+    Vec<Box<dyn ScalerTrait + Sync + Send>>,
+) {
+    let mut scalers: Vec<Box<dyn ScalerTrait + Sync + Send>> = Vec::default();
+    let mut jobs = Vec::default();
+    for c in &config.scaler_targets {
+        jobs.push(c.queue_report_field);
+        match c.scaler_target_type {
+            ScalerTargetType::Gpu => scalers.push(Box::new(Scaler::<GpuKey>::new(
+                c.queue_report_field,
+                c.deployment.clone(),
+                c.min_replicas,
+                c.max_replicas
+                    .iter()
+                    .map(|(k, v)| (k.clone(), v.into_map_gpukey()))
+                    .collect(),
+                c.speed.into_map_gpukey(),
+                config.cluster_priorities.clone(),
+                config.apply_min_to_namespace.clone(),
+                chrono::Duration::seconds(config.long_pending_duration.as_secs() as i64),
+            ))),
+            ScalerTargetType::Simple => scalers.push(Box::new(Scaler::<NoKey>::new(
+                c.queue_report_field,
+                c.deployment.clone(),
+                c.min_replicas,
+                c.max_replicas
+                    .iter()
+                    .map(|(k, v)| (k.clone(), v.into_map_nokey()))
+                    .collect(),
+                c.speed.into_map_nokey(),
+                config.cluster_priorities.clone(),
+                config.apply_min_to_namespace.clone(),
+                chrono::Duration::seconds(config.long_pending_duration.as_secs() as i64),
+            ))),
+        };
+    }
+    (jobs, scalers)
+}
+
 /// is_namespace_running returns true if there are some pods running in it.
 fn is_namespace_running(namespace: &str, clusters: &Clusters) -> bool {
     clusters
@@ -97,12 +109,39 @@ fn is_namespace_running(namespace: &str, clusters: &Clusters) -> bool {
         > 0
 }
 
+/// Runs all scalers for every namespace and collects scale requests per cluster.
+pub(crate) fn calculate_scale_requests(
+    namespaces: &HashMap<String, String>,
+    scalers: &[Box<dyn ScalerTrait + Sync + Send>],
+    queue: &Queue,
+    clusters: &Clusters,
+) -> HashMap<String, ScaleRequest> {
+    let mut scale_requests: HashMap<String, ScaleRequest> = HashMap::new();
+    for (ns, ppv) in namespaces {
+        for scaler in scalers {
+            let q = queue
+                .get(&(ppv.to_string(), scaler.queue_report_field()))
+                .cloned()
+                .unwrap_or(0);
+            AUTOSCALER_METRICS.queue[&(ns.clone(), scaler.deployment())].set(q);
+            tracing::debug!(
+                "Running eval for namespace {ns}, PPV {ppv}, scaler {} found queue {q}",
+                scaler.deployment()
+            );
+            if q > 0 || is_namespace_running(ns, clusters) {
+                scaler.run(ns, q, clusters, &mut scale_requests);
+            }
+        }
+    }
+    scale_requests
+}
+
 #[async_trait::async_trait]
 impl Task for Manager {
     async fn invoke(&self) -> anyhow::Result<()> {
         let queue = self.queuer.get_queue(&self.jobs).await.unwrap();
 
-        let mut scale_requests: HashMap<String, ScaleRequest> = HashMap::new();
+        let scale_requests;
         {
             let guard = self.watcher.data.lock().await; // Keeping the lock during all calls of run() for
                                                         // consitency.
@@ -112,22 +151,8 @@ impl Task for Manager {
                 return Ok(());
             }
 
-            for (ns, ppv) in &self.namespaces {
-                for scaler in &self.scalers {
-                    let q = queue
-                        .get(&(ppv.to_string(), scaler.queue_report_field()))
-                        .cloned()
-                        .unwrap_or(0);
-                    AUTOSCALER_METRICS.queue[&(ns.clone(), scaler.deployment())].set(q);
-                    tracing::debug!(
-                        "Running eval for namespace {ns}, PPV {ppv}, scaler {} found queue {q}",
-                        scaler.deployment()
-                    );
-                    if q > 0 || is_namespace_running(ns, &guard.clusters) {
-                        scaler.run(ns, q, &guard.clusters, &mut scale_requests);
-                    }
-                }
-            }
+            scale_requests =
+                calculate_scale_requests(&self.namespaces, &self.scalers, &queue, &guard.clusters);
         } // Unlock self.watcher.data.
 
         if let Err(err) = self.watcher.send_scale(scale_requests).await {
@@ -1,7 +1,9 @@
-use std::{collections::HashMap, ops::Deref};
+use std::{collections::HashMap, io::Write, ops::Deref, path::PathBuf};
 
 use anyhow::{Context, Ok};
+use chrono::{DateTime, Utc};
 use reqwest::Method;
+use serde::{Deserialize, Serialize};
 use zksync_prover_job_monitor::autoscaler_queue_reporter::{QueueReport, VersionedQueueReport};
 
 use crate::{config::QueueReportFields, http_client::HttpClient};
@@ -15,10 +17,39 @@ impl Deref for Queue {
     }
 }
 
+impl Queue {
+    /// Parses queue reports into Queue HashMap for provided list of jobs.
+    pub fn from_reports(reports: &[VersionedQueueReport], jobs: &[QueueReportFields]) -> Self {
+        Self(
+            reports
+                .iter()
+                .flat_map(|versioned_report| {
+                    jobs.iter().map(move |j| {
+                        (
+                            (versioned_report.version.to_string(), *j),
+                            target_to_queue(*j, &versioned_report.report),
+                        )
+                    })
+                })
+                .collect::<HashMap<_, _>>(),
+        )
+    }
+}
+
+/// Queue reports fetched at `timestamp`. Stored as JSON lines by Queuer, if
+/// `queue_report_log_path` is configured, and replayed by the simulator.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QueueRecord {
+    pub timestamp: DateTime<Utc>,
+    pub reports: Vec<VersionedQueueReport>,
+}
+
 #[derive(Default)]
 pub struct Queuer {
     http_client: HttpClient,
     pub prover_job_monitor_url: String,
+    /// File to append fetched queue reports to.
+    pub queue_report_log_path: Option<PathBuf>,
 }
 
 fn target_to_queue(target: QueueReportFields, report: &QueueReport) -> u64 {
@@ -35,13 +66,36 @@ fn target_to_queue(target: QueueReportFields, report: &QueueReport) -> u64 {
 }
 
 impl Queuer {
-    pub fn new(http_client: HttpClient, pjm_url: String) -> Self {
+    pub fn new(
+        http_client: HttpClient,
+        pjm_url: String,
+        queue_report_log_path: Option<PathBuf>,
+    ) -> Self {
         Self {
             http_client,
             prover_job_monitor_url: pjm_url,
+            queue_report_log_path,
         }
     }
 
+    fn record(&self, reports: Vec<VersionedQueueReport>) -> anyhow::Result<()> {
+        let Some(path) = &self.queue_report_log_path else {
+            return Ok(());
+        };
+        let record = QueueRecord {
+            timestamp: Utc::now(),
+            reports,
+        };
+        let mut line = serde_json::to_vec(&record)?;
+        line.push(b'\n');
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(&line))
+            .with_context(|| format!("failed to append queue report to {}", path.display()))
+    }
+
     /// Requests queue report from prover-job-monitor and parse it into Queue HashMap for provided
     /// list of jobs.
     pub async fn get_queue(&self, jobs: &[QueueReportFields]) -> anyhow::Result<Queue> {
@@ -57,18 +111,10 @@ impl Queuer {
             .json::<Vec<VersionedQueueReport>>()
             .await
             .context("Failed to read response as json")?;
-        Ok(Queue(
-            response
-                .iter()
-                .flat_map(|versioned_report| {
-                    jobs.iter().map(move |j| {
-                        (
-                            (versioned_report.version.to_string(), *j),
-                            target_to_queue(*j, &versioned_report.report),
-                        )
-                    })
-                })
-                .collect::<HashMap<_, _>>(),
-        ))
+        let queue = Queue::from_reports(&response, jobs);
+        if let Err(err) = self.record(response) {
+            tracing::warn!("Failed recording queue report: {err:#}");
+        }
+        Ok(queue)
     }
 }
@@ -347,6 +347,8 @@ pub trait ScalerTrait {
         clusters: &Clusters,
         requests: &mut HashMap<String, ScaleRequest>,
     );
+    /// Returns the queue covered by Running pods of the deployment in the namespace.
+    fn capacity(&self, namespace: &str, clusters: &Clusters) -> u64;
 }
 
 impl<K: Key> ScalerTrait for Scaler<K> {
@@ -386,6 +388,15 @@ impl<K: Key> ScalerTrait for Scaler<K> {
         }
         self.diff(namespace, replicas, clusters, requests);
     }
+
+    fn capacity(&self, namespace: &str, clusters: &Clusters) -> u64 {
+        clusters
+            .clusters
+            .values()
+            .flat_map(|c| self.convert_to_pool(namespace, c))
+            .map(|p| self.pods_to_speed(p.key, p.sum_by_pod_status(PodStatus::Running)) as u64)
+            .sum()
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,29 @@
+use async_trait::async_trait;
+
+use super::{Scaler, Watcher};
+use crate::{backend::SchedulerBackend, cluster_types::Cluster};
+
+/// Kubernetes backend: cluster state is collected by [`Watcher`], Deployments are scaled by
+/// [`Scaler`].
+#[derive(Clone)]
+pub struct KubernetesBackend {
+    watcher: Watcher,
+    scaler: Scaler,
+}
+
+impl KubernetesBackend {
+    pub fn new(watcher: Watcher, scaler: Scaler) -> Self {
+        Self { watcher, scaler }
+    }
+}
+
+#[async_trait]
+impl SchedulerBackend for KubernetesBackend {
+    async fn cluster(&self) -> Cluster {
+        self.watcher.cluster.lock().await.clone()
+    }
+
+    async fn scale(&self, namespace: &str, name: &str, size: i32) -> anyhow::Result<()> {
+        self.scaler.scale(namespace, name, size).await
+    }
+}
@@ -1,5 +1,7 @@
+pub use backend::KubernetesBackend;
 pub use scaler::Scaler;
 pub use watcher::Watcher;
 
+mod backend;
 mod scaler;
 mod watcher;
@@ -1,4 +1,5 @@
 pub mod agent;
+pub mod backend;
 pub(crate) mod cluster_types;
 pub mod config;
 pub mod global;
@@ -6,4 +7,5 @@ pub mod http_client;
 pub mod k8s;
 pub(crate) mod key;
 pub(crate) mod metrics;
+pub mod simulator;
 pub mod task_wiring;
@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 
 use anyhow::Context;
 use structopt::StructOpt;
@@ -11,7 +11,8 @@ use zksync_prover_autoscaler::{
     config::{config_from_yaml, ProverAutoscalerConfig},
     global::{manager::Manager, queuer::Queuer, watcher},
     http_client::HttpClient,
-    k8s::{Scaler, Watcher},
+    k8s::{KubernetesBackend, Scaler, Watcher},
+    simulator::{read_queue_records, Simulator},
     task_wiring::TaskRunner,
 };
 use zksync_task_management::ManagedTasks;
@@ -22,6 +23,7 @@ use zksync_vlog::prometheus::PrometheusExporterConfig;
 pub enum AutoscalerType {
     Scaler,
     Agent,
+    Simulator,
 }
 
 impl std::str::FromStr for AutoscalerType {
@@ -31,6 +33,7 @@ impl std::str::FromStr for AutoscalerType {
         match s {
             "scaler" => Ok(AutoscalerType::Scaler),
             "agent" => Ok(AutoscalerType::Agent),
+            "simulator" => Ok(AutoscalerType::Simulator),
             other => Err(format!("{} is not a valid AutoscalerType", other)),
         }
     }
@@ -39,9 +42,9 @@ impl std::str::FromStr for AutoscalerType {
 #[derive(Debug, StructOpt)]
 #[structopt(name = "Prover Autoscaler", about = "Run Prover Autoscaler components")]
 struct Opt {
-    /// Prover Autoscaler can run Agent or Scaler type.
+    /// Prover Autoscaler can run Agent, Scaler or Simulator type.
     ///
-    /// Specify `agent` or `scaler`
+    /// Specify `agent`, `scaler` or `simulator`
     #[structopt(short, long, default_value = "agent")]
     job: AutoscalerType,
     /// Name of the cluster Agent is watching.
@@ -98,8 +101,7 @@ async fn main() -> anyhow::Result<()> {
             tasks.push(tokio::spawn(watcher.clone().run()));
             tasks.push(tokio::spawn(agent::run_server(
                 agent_config.http_port,
-                watcher,
-                scaler,
+                Arc::new(KubernetesBackend::new(watcher, scaler)),
                 stop_receiver.clone(),
             )))
         }
@@ -114,10 +116,27 @@ async fn main() -> anyhow::Result<()> {
                 scaler_config.agents.clone(),
                 scaler_config.dry_run,
             );
-            let queuer = Queuer::new(http_client, scaler_config.prover_job_monitor_url.clone());
+            let queuer = Queuer::new(
+                http_client,
+                scaler_config.prover_job_monitor_url.clone(),
+                scaler_config.queue_report_log_path.clone(),
+            );
             let manager = Manager::new(watcher.clone(), queuer, scaler_config);
             tasks.extend(get_tasks(watcher, manager, interval, stop_receiver)?);
         }
+        AutoscalerType::Simulator => {
+            tracing::info!("Starting ProverAutoscaler Simulator");
+            let scaler_config = general_config.scaler_config.context("scaler_config")?;
+            let simulator_config = general_config
+                .simulator_config
+                .context("simulator_config")?;
+            let records = read_queue_records(&simulator_config.queue_report_log_path)?;
+            let report = Simulator::new(&scaler_config, &simulator_config)
+                .run(&records)
+                .await;
+            println!("{}", serde_json::to_string_pretty(&report)?);
+            return Ok(());
+        }
     }
 
     let mut tasks = ManagedTasks::new(tasks);
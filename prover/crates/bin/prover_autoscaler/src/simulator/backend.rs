@@ -0,0 +1,332 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Mutex,
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::{
+    backend::SchedulerBackend,
+    cluster_types::{Cluster, Deployment, Namespace, Pod, PodStatus, ScaleEvent},
+};
+
+#[derive(Debug)]
+struct SimulatedPod {
+    name: String,
+    created: DateTime<Utc>,
+    /// Time when a machine became available for the pod.
+    provisioned: Option<DateTime<Utc>>,
+    running: bool,
+    /// Whether the pod has already reported a scale error.
+    failed_scale_up: bool,
+}
+
+impl SimulatedPod {
+    /// Scale down order: unscheduled pods first, then starting ones, then running ones.
+    fn stage(&self) -> u8 {
+        match (self.provisioned, self.running) {
+            (None, _) => 0,
+            (Some(_), false) => 1,
+            (Some(_), true) => 2,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct SimulatedNamespace {
+    deployments: BTreeMap<String, Vec<SimulatedPod>>,
+    scale_errors: Vec<ScaleEvent>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    clock: DateTime<Utc>,
+    namespaces: BTreeMap<String, SimulatedNamespace>,
+    next_pod_id: usize,
+}
+
+/// Simulated cluster with a fixed number of machines per Deployment. Time is advanced explicitly
+/// by the simulator via [`SimulatedBackend::advance()`].
+#[derive(Debug)]
+pub struct SimulatedBackend {
+    name: String,
+    capacity: HashMap<String, usize>,
+    startup_delay: chrono::Duration,
+    state: Mutex<State>,
+}
+
+impl SimulatedBackend {
+    pub fn new(
+        name: String,
+        namespaces: impl IntoIterator<Item = String>,
+        capacity: HashMap<String, usize>,
+        startup_delay: chrono::Duration,
+    ) -> Self {
+        let namespaces = namespaces
+            .into_iter()
+            .map(|ns| {
+                let deployments = capacity.keys().map(|d| (d.clone(), vec![])).collect();
+                (
+                    ns,
+                    SimulatedNamespace {
+                        deployments,
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect();
+        Self {
+            name,
+            capacity,
+            startup_delay,
+            state: Mutex::new(State {
+                namespaces,
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Moves the clock to `now`: starts pods which got machines `startup_delay` ago and schedules
+    /// pending ones.
+    pub fn advance(&self, now: DateTime<Utc>) {
+        let mut state = self.state.lock().unwrap();
+        state.clock = now;
+        for pod in state
+            .namespaces
+            .values_mut()
+            .flat_map(|ns| ns.deployments.values_mut())
+            .flatten()
+        {
+            if matches!(pod.provisioned, Some(t) if t + self.startup_delay <= now) {
+                pod.running = true;
+            }
+        }
+        self.schedule(&mut state);
+    }
+
+    /// Returns the number of pods per Deployment which occupy machines, i.e. are billed.
+    pub fn billed_replicas(&self) -> HashMap<String, usize> {
+        let state = self.state.lock().unwrap();
+        let mut replicas = HashMap::new();
+        for ns in state.namespaces.values() {
+            for (deployment, pods) in &ns.deployments {
+                let billed = pods.iter().filter(|p| p.provisioned.is_some()).count();
+                *replicas.entry(deployment.clone()).or_default() += billed;
+            }
+        }
+        replicas
+    }
+
+    /// Assigns free machines to pods in the order of creation. Pods left without a machine report
+    /// a scale error once.
+    fn schedule(&self, state: &mut State) {
+        let clock = state.clock;
+        for (deployment, capacity) in &self.capacity {
+            let mut used = state
+                .namespaces
+                .values()
+                .filter_map(|ns| ns.deployments.get(deployment))
+                .flatten()
+                .filter(|p| p.provisioned.is_some())
+                .count();
+            for ns in state.namespaces.values_mut() {
+                let Some(pods) = ns.deployments.get_mut(deployment) else {
+                    continue;
+                };
+                for pod in pods.iter_mut().filter(|p| p.provisioned.is_none()) {
+                    if used < *capacity {
+                        pod.provisioned = Some(clock);
+                        used += 1;
+                    } else if !pod.failed_scale_up {
+                        pod.failed_scale_up = true;
+                        ns.scale_errors.push(ScaleEvent {
+                            name: format!("{}.FailedScaleUp", pod.name),
+                            time: clock,
+                        });
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl SchedulerBackend for SimulatedBackend {
+    /// Timestamps are shifted so that they have the same age relative to the wall clock as to
+    /// the simulated clock, because Scaler compares them with [`Utc::now()`].
+    async fn cluster(&self) -> Cluster {
+        let state = self.state.lock().unwrap();
+        let shift = Utc::now() - state.clock;
+        let namespaces = state
+            .namespaces
+            .iter()
+            .map(|(name, ns)| {
+                let mut namespace = Namespace {
+                    scale_errors: ns
+                        .scale_errors
+                        .iter()
+                        .map(|e| ScaleEvent {
+                            name: e.name.clone(),
+                            time: e.time + shift,
+                        })
+                        .collect(),
+                    ..Default::default()
+                };
+                for (deployment, pods) in &ns.deployments {
+                    let running = pods.iter().filter(|p| p.running).count();
+                    namespace.deployments.insert(
+                        deployment.clone(),
+                        Deployment {
+                            running: running as i32,
+                            desired: pods.len() as i32,
+                        },
+                    );
+                    for pod in pods {
+                        let (status, changed) = match pod.provisioned {
+                            Some(t) if pod.running => (PodStatus::Running, t + self.startup_delay),
+                            _ => (PodStatus::Pending, pod.created),
+                        };
+                        namespace.pods.insert(
+                            pod.name.clone(),
+                            Pod {
+                                owner: format!("ReplicaSet/{deployment}"),
+                                status: status.to_string(),
+                                changed: changed + shift,
+                            },
+                        );
+                    }
+                }
+                (name.clone(), namespace)
+            })
+            .collect();
+        Cluster {
+            name: self.name.clone(),
+            namespaces,
+        }
+    }
+
+    async fn scale(&self, namespace: &str, name: &str, size: i32) -> anyhow::Result<()> {
+        anyhow::ensure!(size >= 0, "negative number of replicas {size} for {name}");
+        let mut state = self.state.lock().unwrap();
+        let State {
+            clock,
+            namespaces,
+            next_pod_id,
+        } = &mut *state;
+        let pods = namespaces
+            .get_mut(namespace)
+            .and_then(|ns| ns.deployments.get_mut(name))
+            .ok_or_else(|| anyhow::anyhow!("deployment {namespace}/{name} not found"))?;
+
+        let size = size as usize;
+        while pods.len() < size {
+            pods.push(SimulatedPod {
+                name: format!("{name}-sim-{next_pod_id}"),
+                created: *clock,
+                provisioned: None,
+                running: false,
+                failed_scale_up: false,
+            });
+            *next_pod_id += 1;
+        }
+        while pods.len() > size {
+            // The newest pod among the least started ones is removed first.
+            let (i, _) = pods
+                .iter()
+                .enumerate()
+                .rev()
+                .min_by_key(|(_, p)| p.stage())
+                .unwrap();
+            pods.remove(i);
+        }
+        self.schedule(&mut state);
+        tracing::debug!("Simulated deployment {namespace}/{name} scaled to {size} replica(s)");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pod_statuses(cluster: &Cluster) -> BTreeMap<String, String> {
+        cluster.namespaces["prover"]
+            .pods
+            .iter()
+            .map(|(name, pod)| (name.clone(), pod.status.clone()))
+            .collect()
+    }
+
+    #[tracing_test::traced_test]
+    #[tokio::test]
+    async fn test_pod_lifecycle() {
+        let start = DateTime::<Utc>::UNIX_EPOCH;
+        let backend = SimulatedBackend::new(
+            "foo".into(),
+            ["prover".to_string()],
+            [("circuit-prover-gpu".to_string(), 2)].into(),
+            chrono::Duration::seconds(60),
+        );
+        backend.advance(start);
+
+        backend
+            .scale("prover", "circuit-prover-gpu", 3)
+            .await
+            .unwrap();
+        let cluster = backend.cluster().await;
+        assert_eq!(
+            pod_statuses(&cluster).values().collect::<Vec<_>>(),
+            ["Pending", "Pending", "Pending"]
+        );
+        assert_eq!(
+            cluster.namespaces["prover"].scale_errors.len(),
+            1,
+            "Only 2 machines are available"
+        );
+        assert_eq!(
+            backend.billed_replicas(),
+            [("circuit-prover-gpu".to_string(), 2)].into()
+        );
+
+        backend.advance(start + chrono::Duration::seconds(60));
+        let cluster = backend.cluster().await;
+        assert_eq!(
+            pod_statuses(&cluster),
+            [
+                (
+                    "circuit-prover-gpu-sim-0".to_string(),
+                    "Running".to_string()
+                ),
+                (
+                    "circuit-prover-gpu-sim-1".to_string(),
+                    "Running".to_string()
+                ),
+                (
+                    "circuit-prover-gpu-sim-2".to_string(),
+                    "Pending".to_string()
+                ),
+            ]
+            .into()
+        );
+        let deployment = &cluster.namespaces["prover"].deployments["circuit-prover-gpu"];
+        assert_eq!((deployment.running, deployment.desired), (2, 3));
+
+        backend
+            .scale("prover", "circuit-prover-gpu", 1)
+            .await
+            .unwrap();
+        let cluster = backend.cluster().await;
+        assert_eq!(
+            pod_statuses(&cluster),
+            [(
+                "circuit-prover-gpu-sim-0".to_string(),
+                "Running".to_string()
+            )]
+            .into(),
+            "Pending pod is removed first"
+        );
+
+        assert!(backend.scale("prover", "unknown", 1).await.is_err());
+    }
+}
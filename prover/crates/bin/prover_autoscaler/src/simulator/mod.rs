@@ -0,0 +1,329 @@
+//! Offline evaluation of scaling policies: recorded queue reports are replayed against simulated
+//! clusters and the resulting cost and scale up latency are reported.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+pub use self::backend::SimulatedBackend;
+use crate::{
+    backend::SchedulerBackend,
+    cluster_types::Clusters,
+    config::{ProverAutoscalerScalerConfig, ProverAutoscalerSimulatorConfig, QueueReportFields},
+    global::{
+        manager::{calculate_scale_requests, scalers_from_config},
+        queuer::{Queue, QueueRecord},
+        scaler::ScalerTrait,
+    },
+};
+
+mod backend;
+
+#[derive(Debug, Default, Serialize)]
+pub struct DeploymentReport {
+    /// Replica-hours of Pods occupying machines, including the startup time.
+    pub replica_hours: f64,
+    pub cost: f64,
+    /// Max number of Pods occupying machines at the same time.
+    pub max_replicas: usize,
+    #[serde(skip)]
+    replica_seconds: i64,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct JobReport {
+    /// Total time the queue exceeded the capacity of Running Pods.
+    pub underprovisioned_seconds: i64,
+    /// Number of periods when the queue exceeded the capacity of Running Pods.
+    pub scale_ups: usize,
+    /// Longest time it took to catch up with the queue.
+    pub max_scale_up_latency_seconds: i64,
+    pub mean_scale_up_latency_seconds: f64,
+    #[serde(skip)]
+    underprovisioned_since: Option<DateTime<Utc>>,
+}
+
+impl JobReport {
+    fn close_scale_up(&mut self, end: DateTime<Utc>) {
+        if let Some(since) = self.underprovisioned_since.take() {
+            self.scale_ups += 1;
+            self.max_scale_up_latency_seconds = self
+                .max_scale_up_latency_seconds
+                .max((end - since).num_seconds());
+        }
+    }
+}
+
+/// Result of a simulation. Jobs are keyed by `<namespace>/<deployment>`.
+#[derive(Debug, Default, Serialize)]
+pub struct SimulationReport {
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+    pub steps: usize,
+    pub total_cost: f64,
+    pub deployments: BTreeMap<String, DeploymentReport>,
+    pub jobs: BTreeMap<String, JobReport>,
+}
+
+/// State right after scaling, accounted until the next step.
+struct Step {
+    time: DateTime<Utc>,
+    billed_replicas: HashMap<String, usize>,
+    /// Job to whether its queue exceeds the capacity of Running Pods.
+    underprovisioned: Vec<(String, bool)>,
+}
+
+pub struct Simulator {
+    /// namespace to Protocol Version configuration.
+    namespaces: HashMap<String, String>,
+    jobs: Vec<QueueReportFields>,
+    scalers: Vec<Box<dyn ScalerTrait + Sync + Send>>,
+    backends: BTreeMap<String, SimulatedBackend>,
+    replica_hour_cost: HashMap<String, f64>,
+}
+
+impl Simulator {
+    pub fn new(
+        scaler_config: &ProverAutoscalerScalerConfig,
+        config: &ProverAutoscalerSimulatorConfig,
+    ) -> Self {
+        let (jobs, scalers) = scalers_from_config(scaler_config);
+        let startup_delay = chrono::Duration::seconds(config.pod_startup_delay.as_secs() as i64);
+        let backends = config
+            .clusters
+            .iter()
+            .map(|(name, capacity)| {
+                let backend = SimulatedBackend::new(
+                    name.clone(),
+                    scaler_config.protocol_versions.keys().cloned(),
+                    capacity.clone(),
+                    startup_delay,
+                );
+                (name.clone(), backend)
+            })
+            .collect();
+        Self {
+            namespaces: scaler_config.protocol_versions.clone(),
+            jobs,
+            scalers,
+            backends,
+            replica_hour_cost: config.replica_hour_cost.clone(),
+        }
+    }
+
+    /// Runs the scaling policy once per record, in the order of records. The state after the last
+    /// record is not accounted.
+    pub async fn run(&self, records: &[QueueRecord]) -> SimulationReport {
+        let mut report = SimulationReport {
+            start: records.first().map(|r| r.timestamp),
+            end: records.last().map(|r| r.timestamp),
+            steps: records.len(),
+            ..Default::default()
+        };
+        let mut previous: Option<Step> = None;
+        for record in records {
+            if let Some(step) = previous.take() {
+                self.account(&step, record.timestamp, &mut report);
+            }
+            previous = Some(self.step(record).await);
+        }
+        for (name, deployment) in &mut report.deployments {
+            deployment.replica_hours = deployment.replica_seconds as f64 / 3600.0;
+            deployment.cost =
+                deployment.replica_hours * self.replica_hour_cost.get(name).unwrap_or(&0.0);
+        }
+        for job in report.jobs.values_mut() {
+            if let Some(end) = report.end {
+                job.close_scale_up(end);
+            }
+            if job.scale_ups > 0 {
+                job.mean_scale_up_latency_seconds =
+                    job.underprovisioned_seconds as f64 / job.scale_ups as f64;
+            }
+        }
+        report.total_cost = report.deployments.values().map(|d| d.cost).sum();
+        report
+    }
+
+    async fn clusters(&self) -> Clusters {
+        let mut clusters = Clusters::default();
+        for (id, (name, backend)) in self.backends.iter().enumerate() {
+            clusters
+                .clusters
+                .insert(name.clone(), backend.cluster().await);
+            clusters.agent_ids.insert(name.clone(), id);
+        }
+        clusters
+    }
+
+    async fn step(&self, record: &QueueRecord) -> Step {
+        self.backends
+            .values()
+            .for_each(|backend| backend.advance(record.timestamp));
+
+        let queue = Queue::from_reports(&record.reports, &self.jobs);
+        let clusters = self.clusters().await;
+        let requests = calculate_scale_requests(&self.namespaces, &self.scalers, &queue, &clusters);
+        for (cluster, request) in requests {
+            let Some(backend) = self.backends.get(&cluster) else {
+                tracing::error!("Failed to find simulated cluster {cluster}");
+                continue;
+            };
+            for d in request.deployments {
+                if let Err(err) = backend.scale(&d.namespace, &d.name, d.size).await {
+                    tracing::warn!("Failed to scale in cluster {cluster}: {err}");
+                }
+            }
+        }
+
+        let clusters = self.clusters().await;
+        let mut billed_replicas = HashMap::new();
+        for backend in self.backends.values() {
+            for (deployment, replicas) in backend.billed_replicas() {
+                *billed_replicas.entry(deployment).or_default() += replicas;
+            }
+        }
+        let underprovisioned = self
+            .namespaces
+            .iter()
+            .flat_map(|(ns, ppv)| {
+                let (queue, clusters) = (&queue, &clusters);
+                self.scalers.iter().map(move |scaler| {
+                    let q = queue
+                        .get(&(ppv.to_string(), scaler.queue_report_field()))
+                        .cloned()
+                        .unwrap_or(0);
+                    (
+                        format!("{ns}/{}", scaler.deployment()),
+                        q > scaler.capacity(ns, clusters),
+                    )
+                })
+            })
+            .collect();
+        Step {
+            time: record.timestamp,
+            billed_replicas,
+            underprovisioned,
+        }
+    }
+
+    /// Accounts the state after `step` until `end`.
+    fn account(&self, step: &Step, end: DateTime<Utc>, report: &mut SimulationReport) {
+        let seconds = (end - step.time).num_seconds();
+        for (deployment, replicas) in &step.billed_replicas {
+            let d = report.deployments.entry(deployment.clone()).or_default();
+            d.replica_seconds += *replicas as i64 * seconds;
+            d.max_replicas = d.max_replicas.max(*replicas);
+        }
+        for (job, underprovisioned) in &step.underprovisioned {
+            let j = report.jobs.entry(job.clone()).or_default();
+            if *underprovisioned {
+                j.underprovisioned_seconds += seconds;
+                j.underprovisioned_since.get_or_insert(step.time);
+            } else {
+                j.close_scale_up(step.time);
+            }
+        }
+    }
+}
+
+/// Reads queue reports recorded by Scaler, one JSON record per line.
+pub fn read_queue_records(path: &Path) -> anyhow::Result<Vec<QueueRecord>> {
+    let data = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    data.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line)
+                .with_context(|| format!("failed to parse {}:{}", path.display(), i + 1))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::config::{ScalarOrMap, ScalerTarget, ScalerTargetType};
+
+    fn record(minute: i64, prover_jobs: usize) -> QueueRecord {
+        let empty = serde_json::json!({ "queued": 0, "in_progress": 0 });
+        serde_json::from_value(serde_json::json!({
+            "timestamp": DateTime::<Utc>::UNIX_EPOCH + chrono::Duration::minutes(minute),
+            "reports": [{
+                "version": "0.25.0",
+                "report": {
+                    "basic_witness_jobs": empty,
+                    "leaf_witness_jobs": empty,
+                    "node_witness_jobs": empty,
+                    "recursion_tip_witness_jobs": empty,
+                    "scheduler_witness_jobs": empty,
+                    "prover_jobs": { "queued": prover_jobs, "in_progress": 0 },
+                    "proof_compressor_jobs": empty,
+                },
+            }],
+        }))
+        .unwrap()
+    }
+
+    #[tracing_test::traced_test]
+    #[tokio::test]
+    async fn test_simulation() {
+        let scaler_config = ProverAutoscalerScalerConfig {
+            prometheus_port: 0,
+            scaler_run_interval: Duration::from_secs(60),
+            prover_job_monitor_url: String::new(),
+            agents: vec![],
+            protocol_versions: [("prover".into(), "0.25.0".into())].into(),
+            cluster_priorities: [("foo".into(), 0)].into(),
+            apply_min_to_namespace: None,
+            long_pending_duration: Duration::from_secs(600),
+            scaler_targets: vec![ScalerTarget {
+                scaler_target_type: ScalerTargetType::Simple,
+                queue_report_field: QueueReportFields::prover_jobs,
+                deployment: "circuit-prover-gpu".into(),
+                min_replicas: 0,
+                max_replicas: [("foo".into(), ScalarOrMap::Scalar(10))].into(),
+                speed: ScalarOrMap::Scalar(100),
+            }],
+            dry_run: false,
+            queue_report_log_path: None,
+        };
+        let config = ProverAutoscalerSimulatorConfig {
+            queue_report_log_path: "queue_reports.jsonl".into(),
+            pod_startup_delay: Duration::from_secs(120),
+            clusters: [("foo".into(), [("circuit-prover-gpu".into(), 10)].into())].into(),
+            replica_hour_cost: [("circuit-prover-gpu".into(), 2.0)].into(),
+        };
+
+        let records = [
+            record(0, 300),
+            record(1, 300),
+            record(2, 300),
+            record(3, 300),
+            record(4, 0),
+            record(5, 0),
+        ];
+        let report = Simulator::new(&scaler_config, &config).run(&records).await;
+
+        assert_eq!(report.steps, 6);
+        let deployment = &report.deployments["circuit-prover-gpu"];
+        assert_eq!(deployment.max_replicas, 3);
+        // 3 replicas from minute 0 to 4.
+        assert_eq!(deployment.replica_hours, 0.2);
+        assert_eq!(report.total_cost, 0.4);
+
+        let job = &report.jobs["prover/circuit-prover-gpu"];
+        assert_eq!(job.scale_ups, 1);
+        // Pods are started at minute 2.
+        assert_eq!(job.underprovisioned_seconds, 120);
+        assert_eq!(job.max_scale_up_latency_seconds, 120);
+    }
+}
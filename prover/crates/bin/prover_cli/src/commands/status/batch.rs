@@ -8,12 +8,13 @@ use zksync_prover_dal::{Connection, ConnectionPool, Prover, ProverDal};
 use zksync_types::{
     basic_fri_types::AggregationRound,
     prover_dal::{
-        BasicWitnessGeneratorJobInfo, ExtendedJobCountStatistics, LeafWitnessGeneratorJobInfo,
-        NodeWitnessGeneratorJobInfo, ProofCompressionJobInfo, ProverJobFriInfo, ProverJobStatus,
-        RecursionTipWitnessGeneratorJobInfo, SchedulerWitnessGeneratorJobInfo,
+        BasicWitnessGeneratorJobInfo, ChainBatch, ExtendedJobCountStatistics,
+        LeafWitnessGeneratorJobInfo, NodeWitnessGeneratorJobInfo, ProofCompressionJobInfo,
+        ProverJobFriInfo, ProverJobStatus, RecursionTipWitnessGeneratorJobInfo,
+        SchedulerWitnessGeneratorJobInfo,
     },
     url::SensitiveUrl,
-    L1BatchNumber,
+    L1BatchNumber, L2ChainId,
 };
 
 use super::utils::{get_prover_job_status, BatchData, StageInfo, Status};
@@ -28,16 +29,26 @@ pub struct Args {
     batches: Vec<L1BatchNumber>,
     #[clap(short, long, default_value("false"))]
     verbose: bool,
+    /// Chain the batches belong to. If set, batch numbers are the chain's ones rather than
+    /// the prover-local ones.
+    #[clap(long)]
+    chain_id: Option<L2ChainId>,
 }
 
 pub(crate) async fn run(args: Args, config: ProverCLIConfig) -> anyhow::Result<()> {
-    let batches_data = get_batches_data(args.batches, config.db_url).await?;
+    let batches_data = get_batches_data(args.batches, args.chain_id, config.db_url).await?;
 
     for batch_data in batches_data {
-        println!(
-            "== {} ==",
-            format!("Batch {} Status", batch_data.batch_number).bold()
-        );
+        let title = match batch_data.chain_batch {
+            Some(chain_batch) => format!(
+                "Batch {} Status (chain {}, batch {})",
+                batch_data.batch_number,
+                chain_batch.chain_id.as_u64(),
+                chain_batch.l1_batch_number
+            ),
+            None => format!("Batch {} Status", batch_data.batch_number),
+        };
+        println!("== {} ==", title.bold());
 
         if let Status::Custom(msg) = batch_data.compressor.witness_generator_jobs_status(10) {
             if msg.contains("Sent to server") {
@@ -66,6 +77,7 @@ pub(crate) async fn run(args: Args, config: ProverCLIConfig) -> anyhow::Result<(
 
 async fn get_batches_data(
     batches: Vec<L1BatchNumber>,
+    chain_id: Option<L2ChainId>,
     db_url: SensitiveUrl,
 ) -> anyhow::Result<Vec<BatchData>> {
     let prover_connection_pool = ConnectionPool::<Prover>::singleton(db_url)
@@ -80,8 +92,34 @@ async fn get_batches_data(
 
     let mut batches_data = Vec::new();
     for batch in batches {
+        let (batch, chain_batch) = match chain_id {
+            Some(chain_id) => {
+                let chain_batch = ChainBatch {
+                    chain_id,
+                    l1_batch_number: batch,
+                };
+                let Some(prover_batch) = conn
+                    .fri_chain_batches_dal()
+                    .get_prover_batch_number(chain_batch)
+                    .await
+                else {
+                    println!(
+                        "== {} ==",
+                        format!("Batch {batch} of chain {} Status", chain_id.as_u64()).bold()
+                    );
+                    println!("> No batch found. 🚫");
+                    continue;
+                };
+                (prover_batch, Some(chain_batch))
+            }
+            None => (
+                batch,
+                conn.fri_chain_batches_dal().get_chain_batch(batch).await,
+            ),
+        };
         let current_batch_data = BatchData {
             batch_number: batch,
+            chain_batch,
             basic_witness_generator: StageInfo::BasicWitnessGenerator {
                 witness_generator_job_info: get_proof_basic_witness_generator_into_for_batch(
                     batch, &mut conn,
@@ -4,10 +4,10 @@ use strum::{Display, EnumString};
 use zksync_types::{
     basic_fri_types::AggregationRound,
     prover_dal::{
-        BasicWitnessGeneratorJobInfo, LeafWitnessGeneratorJobInfo, NodeWitnessGeneratorJobInfo,
-        ProofCompressionJobInfo, ProofCompressionJobStatus, ProverJobFriInfo, ProverJobStatus,
-        RecursionTipWitnessGeneratorJobInfo, SchedulerWitnessGeneratorJobInfo, Stallable,
-        WitnessJobStatus,
+        BasicWitnessGeneratorJobInfo, ChainBatch, LeafWitnessGeneratorJobInfo,
+        NodeWitnessGeneratorJobInfo, ProofCompressionJobInfo, ProofCompressionJobStatus,
+        ProverJobFriInfo, ProverJobStatus, RecursionTipWitnessGeneratorJobInfo,
+        SchedulerWitnessGeneratorJobInfo, Stallable, WitnessJobStatus,
     },
     L1BatchNumber,
 };
@@ -16,6 +16,8 @@ use zksync_types::{
 pub struct BatchData {
     /// The number of the batch.
     pub batch_number: L1BatchNumber,
+    /// The chain batch, if the batch was fetched from one of several chains.
+    pub chain_batch: Option<ChainBatch>,
     /// The basic witness generator data.
     pub basic_witness_generator: StageInfo,
     /// The leaf witness generator data.
@@ -0,0 +1,721 @@
+use std::{collections::BTreeMap, fmt::Write as _, time::Duration};
+
+use anyhow::Context as _;
+use chrono::{NaiveDateTime, NaiveTime};
+use clap::{Args as ClapArgs, ValueEnum};
+use colored::*;
+use strum::Display;
+use zksync_prover_dal::{Connection, ConnectionPool, Prover, ProverDal};
+use zksync_types::{
+    basic_fri_types::AggregationRound,
+    prover_dal::{ProofCompressionJobStatus, ProverJobFriInfo, ProverJobStatus, WitnessJobStatus},
+    L1BatchNumber,
+};
+
+use crate::cli::ProverCLIConfig;
+
+#[derive(ClapArgs)]
+pub struct Args {
+    /// First batch of the range.
+    #[clap(short = 'n', long = "from")]
+    from: L1BatchNumber,
+    /// Last batch of the range, inclusive. Defaults to the first batch.
+    #[clap(long)]
+    to: Option<L1BatchNumber>,
+    /// Break proving and aggregation stages down by circuit.
+    #[clap(long, default_value("false"))]
+    circuits: bool,
+    /// Print percentiles across the batch range instead of per-batch timelines.
+    #[clap(long, default_value("false"))]
+    summary: bool,
+    #[clap(short, long, value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    Table,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Display)]
+#[strum(serialize_all = "snake_case")]
+enum Stage {
+    BasicWitnessGeneration,
+    BasicProving,
+    LeafWitnessGeneration,
+    LeafProving,
+    NodeWitnessGeneration,
+    NodeProving,
+    RecursionTipWitnessGeneration,
+    RecursionTipProving,
+    SchedulerWitnessGeneration,
+    SchedulerProving,
+    Compression,
+    Submission,
+    /// From saving witness inputs of the batch to submitting its proof.
+    Total,
+}
+
+impl Stage {
+    fn proving(round: AggregationRound) -> Self {
+        match round {
+            AggregationRound::BasicCircuits => Self::BasicProving,
+            AggregationRound::LeafAggregation => Self::LeafProving,
+            AggregationRound::NodeAggregation => Self::NodeProving,
+            AggregationRound::RecursionTip => Self::RecursionTipProving,
+            AggregationRound::Scheduler => Self::SchedulerProving,
+        }
+    }
+}
+
+const PROVING_ROUNDS: [AggregationRound; 5] = [
+    AggregationRound::BasicCircuits,
+    AggregationRound::LeafAggregation,
+    AggregationRound::NodeAggregation,
+    AggregationRound::RecursionTip,
+    AggregationRound::Scheduler,
+];
+
+/// Timestamps of a single job.
+#[derive(Debug, Clone)]
+struct Span {
+    stage: Stage,
+    circuit_id: Option<u32>,
+    /// Time the job could be picked: its creation or the completion of the jobs it depends on,
+    /// whichever is later.
+    ready_at: NaiveDateTime,
+    started_at: Option<NaiveDateTime>,
+    /// Time the witness vector was generated, for proving jobs.
+    witness_vector_at: Option<NaiveDateTime>,
+    finished_at: Option<NaiveDateTime>,
+}
+
+impl Span {
+    fn queue_wait(&self) -> Option<Duration> {
+        Some(elapsed(self.ready_at, self.started_at?))
+    }
+
+    fn processing(&self) -> Option<Duration> {
+        Some(elapsed(self.started_at?, self.finished_at?))
+    }
+
+    fn witness_vector_generation(&self) -> Option<Duration> {
+        Some(elapsed(self.started_at?, self.witness_vector_at?))
+    }
+}
+
+/// Timeline of a stage of a batch, aggregated over its jobs.
+#[derive(Debug)]
+struct StageTimeline {
+    l1_batch_number: L1BatchNumber,
+    stage: Stage,
+    circuit_id: Option<u32>,
+    jobs: usize,
+    /// Time from saving witness inputs of the batch until the first job of the stage was ready.
+    start_offset: Duration,
+    /// Time from the first job being ready until the last one finished; `None` if some jobs
+    /// are not finished.
+    wall_time: Option<Duration>,
+    mean_queue_wait: Option<Duration>,
+    max_queue_wait: Option<Duration>,
+    mean_processing: Option<Duration>,
+    total_processing: Option<Duration>,
+    mean_witness_vector_generation: Option<Duration>,
+}
+
+pub(crate) async fn run(args: Args, config: ProverCLIConfig) -> anyhow::Result<()> {
+    let to = args.to.unwrap_or(args.from);
+    anyhow::ensure!(args.from <= to, "empty batch range {}..={to}", args.from);
+
+    let prover_connection_pool = ConnectionPool::<Prover>::singleton(config.db_url)
+        .build()
+        .await
+        .context("failed to build a prover_connection_pool")?;
+    let mut conn = prover_connection_pool
+        .connection()
+        .await
+        .context("failed to get a connection")?;
+
+    let mut timelines = vec![];
+    for batch in args.from.0..=to.0 {
+        let spans = get_batch_spans(L1BatchNumber(batch), &mut conn).await;
+        timelines.extend(stage_timelines(L1BatchNumber(batch), &spans, args.circuits));
+    }
+
+    let table = if args.summary {
+        summary_table(&timelines)
+    } else {
+        timeline_table(&timelines)
+    };
+    table.print(args.format);
+    Ok(())
+}
+
+/// Reconstructs timestamps of all jobs of the batch. Returns nothing for unknown batches.
+async fn get_batch_spans(batch: L1BatchNumber, conn: &mut Connection<'_, Prover>) -> Vec<Span> {
+    let Some(basic) = conn
+        .fri_basic_witness_generator_dal()
+        .get_basic_witness_generator_job_for_batch(batch)
+        .await
+    else {
+        return vec![];
+    };
+    let mut spans = vec![Span {
+        stage: Stage::BasicWitnessGeneration,
+        circuit_id: None,
+        ready_at: basic.created_at,
+        started_at: basic.processing_started_at,
+        witness_vector_at: None,
+        finished_at: finished_at(
+            basic.processing_started_at,
+            basic.time_taken,
+            matches!(basic.status, WitnessJobStatus::Successful(_)),
+            basic.updated_at,
+        ),
+    }];
+
+    let mut prover_jobs = BTreeMap::new();
+    for round in PROVING_ROUNDS {
+        let jobs = conn
+            .fri_prover_jobs_dal()
+            .get_prover_jobs_stats_for_batch(batch, round)
+            .await;
+        spans.extend(jobs.iter().map(|job| Span {
+            stage: Stage::proving(round),
+            circuit_id: Some(job.circuit_id),
+            ready_at: job.created_at,
+            started_at: job.processing_started_at,
+            witness_vector_at: job.witness_vector_generated_at,
+            finished_at: prover_job_finished_at(job),
+        }));
+        prover_jobs.insert(round as u8, jobs);
+    }
+    // Aggregation jobs are created in advance and become ready once proofs they aggregate
+    // are generated.
+    let ready_at = |created_at: NaiveDateTime,
+                    round: AggregationRound,
+                    filter: &dyn Fn(&ProverJobFriInfo) -> bool| {
+        prover_jobs[&(round as u8)]
+            .iter()
+            .filter(|job| filter(job))
+            .filter_map(prover_job_finished_at)
+            .fold(created_at, NaiveDateTime::max)
+    };
+
+    for leaf in conn
+        .fri_leaf_witness_generator_dal()
+        .get_leaf_witness_generator_jobs_for_batch(batch)
+        .await
+    {
+        spans.push(Span {
+            stage: Stage::LeafWitnessGeneration,
+            circuit_id: Some(leaf.circuit_id),
+            ready_at: ready_at(leaf.created_at, AggregationRound::BasicCircuits, &|job| {
+                job.circuit_id == leaf.circuit_id
+            }),
+            started_at: leaf.processing_started_at,
+            witness_vector_at: None,
+            finished_at: finished_at(
+                leaf.processing_started_at,
+                leaf.time_taken,
+                matches!(leaf.status, WitnessJobStatus::Successful(_)),
+                leaf.updated_at,
+            ),
+        });
+    }
+
+    for node in conn
+        .fri_node_witness_generator_dal()
+        .get_node_witness_generator_jobs_for_batch(batch)
+        .await
+    {
+        let round = if node.depth == 0 {
+            AggregationRound::LeafAggregation
+        } else {
+            AggregationRound::NodeAggregation
+        };
+        spans.push(Span {
+            stage: Stage::NodeWitnessGeneration,
+            circuit_id: Some(node.circuit_id),
+            ready_at: ready_at(node.created_at, round, &|job| {
+                job.circuit_id == node.circuit_id && job.depth == node.depth
+            }),
+            started_at: node.processing_started_at,
+            witness_vector_at: None,
+            finished_at: finished_at(
+                node.processing_started_at,
+                node.time_taken,
+                matches!(node.status, WitnessJobStatus::Successful(_)),
+                node.updated_at,
+            ),
+        });
+    }
+
+    if let Some(recursion_tip) = conn
+        .fri_recursion_tip_witness_generator_dal()
+        .get_recursion_tip_witness_generator_jobs_for_batch(batch)
+        .await
+    {
+        spans.push(Span {
+            stage: Stage::RecursionTipWitnessGeneration,
+            circuit_id: None,
+            ready_at: ready_at(
+                recursion_tip.created_at,
+                AggregationRound::NodeAggregation,
+                &|job| job.is_node_final_proof,
+            ),
+            started_at: recursion_tip.processing_started_at,
+            witness_vector_at: None,
+            finished_at: finished_at(
+                recursion_tip.processing_started_at,
+                recursion_tip.time_taken,
+                matches!(recursion_tip.status, WitnessJobStatus::Successful(_)),
+                recursion_tip.updated_at,
+            ),
+        });
+    }
+
+    if let Some(scheduler) = conn
+        .fri_scheduler_witness_generator_dal()
+        .get_scheduler_witness_generator_jobs_for_batch(batch)
+        .await
+    {
+        spans.push(Span {
+            stage: Stage::SchedulerWitnessGeneration,
+            circuit_id: None,
+            ready_at: ready_at(
+                scheduler.created_at,
+                AggregationRound::RecursionTip,
+                &|_| true,
+            ),
+            started_at: scheduler.processing_started_at,
+            witness_vector_at: None,
+            finished_at: finished_at(
+                scheduler.processing_started_at,
+                scheduler.time_taken,
+                matches!(scheduler.status, WitnessJobStatus::Successful(_)),
+                scheduler.updated_at,
+            ),
+        });
+    }
+
+    if let Some(compression) = conn
+        .fri_proof_compressor_dal()
+        .get_proof_compression_job_for_batch(batch)
+        .await
+    {
+        let finished = finished_at(
+            compression.processing_started_at,
+            compression.time_taken,
+            matches!(
+                compression.status,
+                ProofCompressionJobStatus::Successful
+                    | ProofCompressionJobStatus::SentToServer
+                    | ProofCompressionJobStatus::Skipped
+            ),
+            compression.updated_at,
+        );
+        spans.push(Span {
+            stage: Stage::Compression,
+            circuit_id: None,
+            ready_at: ready_at(compression.created_at, AggregationRound::Scheduler, &|_| {
+                true
+            }),
+            started_at: compression.processing_started_at,
+            witness_vector_at: None,
+            finished_at: finished,
+        });
+        // Proofs are marked as sent right after they are submitted, so the time spent on
+        // submission is accounted as queue wait.
+        if matches!(compression.status, ProofCompressionJobStatus::SentToServer) {
+            spans.push(Span {
+                stage: Stage::Submission,
+                circuit_id: None,
+                ready_at: finished.unwrap_or(compression.updated_at),
+                started_at: Some(compression.updated_at),
+                witness_vector_at: None,
+                finished_at: Some(compression.updated_at),
+            });
+        }
+    }
+    spans
+}
+
+fn prover_job_finished_at(job: &ProverJobFriInfo) -> Option<NaiveDateTime> {
+    finished_at(
+        job.processing_started_at,
+        job.time_taken,
+        matches!(job.status, ProverJobStatus::Successful(_)),
+        job.updated_at,
+    )
+}
+
+/// `updated_at` may be bumped after a job is finished (e.g. when a proof is sent to server),
+/// so the processing time is preferred.
+fn finished_at(
+    started_at: Option<NaiveDateTime>,
+    time_taken: Option<NaiveTime>,
+    successful: bool,
+    updated_at: NaiveDateTime,
+) -> Option<NaiveDateTime> {
+    if !successful {
+        return None;
+    }
+    match (started_at, time_taken) {
+        (Some(started_at), Some(time_taken)) => {
+            Some(started_at + time_taken.signed_duration_since(NaiveTime::MIN))
+        }
+        _ => Some(updated_at),
+    }
+}
+
+fn elapsed(from: NaiveDateTime, to: NaiveDateTime) -> Duration {
+    (to - from).to_std().unwrap_or_default()
+}
+
+fn stage_timelines(
+    l1_batch_number: L1BatchNumber,
+    spans: &[Span],
+    by_circuit: bool,
+) -> Vec<StageTimeline> {
+    let Some(batch_start) = spans.first().map(|span| span.ready_at) else {
+        return vec![];
+    };
+    let mut groups: BTreeMap<_, Vec<&Span>> = BTreeMap::new();
+    for span in spans {
+        let circuit_id = span.circuit_id.filter(|_| by_circuit);
+        groups
+            .entry((span.stage, circuit_id))
+            .or_default()
+            .push(span);
+    }
+
+    let mut timelines: Vec<_> = groups
+        .into_iter()
+        .map(|((stage, circuit_id), spans)| {
+            aggregate(l1_batch_number, stage, circuit_id, batch_start, &spans)
+        })
+        .collect();
+    let all_spans: Vec<_> = spans.iter().collect();
+    let mut total = aggregate(l1_batch_number, Stage::Total, None, batch_start, &all_spans);
+    // The batch is done only once its proof is submitted.
+    total.wall_time = spans
+        .iter()
+        .find(|span| span.stage == Stage::Submission)
+        .and_then(|span| span.finished_at)
+        .map(|sent_at| elapsed(batch_start, sent_at));
+    timelines.push(total);
+    timelines
+}
+
+fn aggregate(
+    l1_batch_number: L1BatchNumber,
+    stage: Stage,
+    circuit_id: Option<u32>,
+    batch_start: NaiveDateTime,
+    spans: &[&Span],
+) -> StageTimeline {
+    let first_ready = spans
+        .iter()
+        .map(|span| span.ready_at)
+        .min()
+        .unwrap_or(batch_start);
+    let last_finished = spans
+        .iter()
+        .map(|span| span.finished_at)
+        .collect::<Option<Vec<_>>>()
+        .and_then(|finished| finished.into_iter().max());
+    let queue_waits: Vec<_> = spans.iter().filter_map(|span| span.queue_wait()).collect();
+    let processing: Vec<_> = spans.iter().filter_map(|span| span.processing()).collect();
+    let witness_vector_generation: Vec<_> = spans
+        .iter()
+        .filter_map(|span| span.witness_vector_generation())
+        .collect();
+
+    StageTimeline {
+        l1_batch_number,
+        stage,
+        circuit_id,
+        jobs: spans.len(),
+        start_offset: elapsed(batch_start, first_ready),
+        wall_time: last_finished.map(|finished| elapsed(first_ready, finished)),
+        mean_queue_wait: mean(&queue_waits),
+        max_queue_wait: queue_waits.iter().max().copied(),
+        mean_processing: mean(&processing),
+        total_processing: (!processing.is_empty()).then(|| processing.iter().sum()),
+        mean_witness_vector_generation: mean(&witness_vector_generation),
+    }
+}
+
+fn mean(durations: &[Duration]) -> Option<Duration> {
+    if durations.is_empty() {
+        return None;
+    }
+    Some(durations.iter().sum::<Duration>() / durations.len() as u32)
+}
+
+/// Nearest-rank percentile of sorted values; `sorted` must not be empty.
+fn percentile(sorted: &[Duration], p: usize) -> Duration {
+    let rank = (sorted.len() * p).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+fn timeline_table(timelines: &[StageTimeline]) -> Table {
+    Table {
+        columns: vec![
+            "l1_batch_number",
+            "stage",
+            "circuit_id",
+            "jobs",
+            "start_offset",
+            "wall_time",
+            "mean_queue_wait",
+            "max_queue_wait",
+            "mean_processing",
+            "total_processing",
+            "mean_witness_vector_generation",
+        ],
+        rows: timelines
+            .iter()
+            .map(|timeline| {
+                vec![
+                    Value::Int(timeline.l1_batch_number.0.into()),
+                    Value::Text(timeline.stage.to_string()),
+                    Value::OptInt(timeline.circuit_id.map(u64::from)),
+                    Value::Int(timeline.jobs as u64),
+                    Value::Duration(Some(timeline.start_offset)),
+                    Value::Duration(timeline.wall_time),
+                    Value::Duration(timeline.mean_queue_wait),
+                    Value::Duration(timeline.max_queue_wait),
+                    Value::Duration(timeline.mean_processing),
+                    Value::Duration(timeline.total_processing),
+                    Value::Duration(timeline.mean_witness_vector_generation),
+                ]
+            })
+            .collect(),
+    }
+}
+
+/// Percentiles of per-batch wall time, queue wait and processing time of each stage.
+fn summary_table(timelines: &[StageTimeline]) -> Table {
+    type Metric = (&'static str, fn(&StageTimeline) -> Option<Duration>);
+    const METRICS: [Metric; 3] = [
+        ("wall_time", |t| t.wall_time),
+        ("mean_queue_wait", |t| t.mean_queue_wait),
+        ("mean_processing", |t| t.mean_processing),
+    ];
+
+    let mut groups: BTreeMap<_, Vec<&StageTimeline>> = BTreeMap::new();
+    for timeline in timelines {
+        groups
+            .entry((timeline.stage, timeline.circuit_id))
+            .or_default()
+            .push(timeline);
+    }
+
+    let mut rows = vec![];
+    for ((stage, circuit_id), timelines) in groups {
+        for (metric, value) in METRICS {
+            let mut values: Vec<_> = timelines.iter().filter_map(|t| value(t)).collect();
+            if values.is_empty() {
+                continue;
+            }
+            values.sort();
+            rows.push(vec![
+                Value::Text(stage.to_string()),
+                Value::OptInt(circuit_id.map(u64::from)),
+                Value::Text(metric.to_string()),
+                Value::Int(values.len() as u64),
+                Value::Duration(Some(percentile(&values, 50))),
+                Value::Duration(Some(percentile(&values, 90))),
+                Value::Duration(Some(percentile(&values, 99))),
+                Value::Duration(values.last().copied()),
+            ]);
+        }
+    }
+    Table {
+        columns: vec![
+            "stage",
+            "circuit_id",
+            "metric",
+            "batches",
+            "p50",
+            "p90",
+            "p99",
+            "max",
+        ],
+        rows,
+    }
+}
+
+enum Value {
+    Text(String),
+    Int(u64),
+    OptInt(Option<u64>),
+    Duration(Option<Duration>),
+}
+
+impl Value {
+    /// Human-readable form.
+    fn display(&self) -> String {
+        match self {
+            Value::Text(text) => text.clone(),
+            Value::Int(value) => value.to_string(),
+            Value::OptInt(value) => value.map(|v| v.to_string()).unwrap_or_default(),
+            Value::Duration(None) => "-".to_string(),
+            Value::Duration(Some(duration)) => {
+                let secs = duration.as_secs();
+                if secs < 60 {
+                    format!("{:.1}s", duration.as_secs_f64())
+                } else if secs < 3600 {
+                    format!("{}m {:02}s", secs / 60, secs % 60)
+                } else {
+                    format!("{}h {:02}m {:02}s", secs / 3600, secs / 60 % 60, secs % 60)
+                }
+            }
+        }
+    }
+
+    /// Machine-readable form; durations are in seconds.
+    fn raw(&self) -> String {
+        match self {
+            Value::Duration(duration) => duration
+                .map(|d| format!("{:.3}", d.as_secs_f64()))
+                .unwrap_or_default(),
+            other => other.display(),
+        }
+    }
+
+    fn json(&self) -> serde_json::Value {
+        match self {
+            Value::Text(text) => text.clone().into(),
+            Value::Int(value) => (*value).into(),
+            Value::OptInt(value) => (*value).into(),
+            Value::Duration(duration) => duration.map(|d| d.as_secs_f64()).into(),
+        }
+    }
+}
+
+struct Table {
+    columns: Vec<&'static str>,
+    rows: Vec<Vec<Value>>,
+}
+
+impl Table {
+    fn print(&self, format: OutputFormat) {
+        print!("{}", self.render(format));
+    }
+
+    fn render(&self, format: OutputFormat) -> String {
+        let mut output = String::new();
+        match format {
+            OutputFormat::Table => {
+                let cells: Vec<Vec<String>> = self
+                    .rows
+                    .iter()
+                    .map(|row| row.iter().map(Value::display).collect())
+                    .collect();
+                let widths: Vec<usize> = self
+                    .columns
+                    .iter()
+                    .enumerate()
+                    .map(|(i, column)| {
+                        cells
+                            .iter()
+                            .map(|row| row[i].chars().count())
+                            .chain([column.len()])
+                            .max()
+                            .unwrap()
+                    })
+                    .collect();
+                let header: Vec<_> = self
+                    .columns
+                    .iter()
+                    .zip(&widths)
+                    .map(|(column, width)| format!("{column:<width$}"))
+                    .collect();
+                writeln!(output, "{}", header.join("  ").bold()).unwrap();
+                for row in cells {
+                    let row: Vec<_> = row
+                        .iter()
+                        .zip(&widths)
+                        .map(|(cell, width)| format!("{cell:<width$}"))
+                        .collect();
+                    writeln!(output, "{}", row.join("  ")).unwrap();
+                }
+            }
+            OutputFormat::Csv => {
+                writeln!(output, "{}", self.columns.join(",")).unwrap();
+                for row in &self.rows {
+                    let row: Vec<_> = row.iter().map(Value::raw).collect();
+                    writeln!(output, "{}", row.join(",")).unwrap();
+                }
+            }
+            OutputFormat::Json => {
+                let rows: Vec<_> = self
+                    .rows
+                    .iter()
+                    .map(|row| {
+                        let object: serde_json::Map<_, _> = self
+                            .columns
+                            .iter()
+                            .zip(row)
+                            .map(|(column, value)| (column.to_string(), value.json()))
+                            .collect();
+                        serde_json::Value::Object(object)
+                    })
+                    .collect();
+                writeln!(output, "{}", serde_json::to_string_pretty(&rows).unwrap()).unwrap();
+            }
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(values: impl IntoIterator<Item = u64>) -> Vec<Duration> {
+        values.into_iter().map(Duration::from_secs).collect()
+    }
+
+    #[test]
+    fn percentile_of_single_value() {
+        let values = secs([5]);
+        for p in [0, 1, 50, 99, 100] {
+            assert_eq!(percentile(&values, p), Duration::from_secs(5), "p{p}");
+        }
+    }
+
+    #[test]
+    fn percentile_of_two_values() {
+        let values = secs([1, 2]);
+        assert_eq!(percentile(&values, 50), Duration::from_secs(1));
+        assert_eq!(percentile(&values, 51), Duration::from_secs(2));
+        assert_eq!(percentile(&values, 99), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn percentile_uses_nearest_rank() {
+        let values = secs(1..=10);
+        assert_eq!(percentile(&values, 0), Duration::from_secs(1));
+        assert_eq!(percentile(&values, 10), Duration::from_secs(1));
+        assert_eq!(percentile(&values, 11), Duration::from_secs(2));
+        assert_eq!(percentile(&values, 50), Duration::from_secs(5));
+        assert_eq!(percentile(&values, 90), Duration::from_secs(9));
+        assert_eq!(percentile(&values, 99), Duration::from_secs(10));
+        assert_eq!(percentile(&values, 100), Duration::from_secs(10));
+
+        let values = secs(1..=200);
+        assert_eq!(percentile(&values, 99), Duration::from_secs(198));
+    }
+
+    #[test]
+    #[should_panic]
+    fn percentile_of_no_values_panics() {
+        percentile(&[], 50);
+    }
+}
@@ -40,3 +40,32 @@ async fn pli_config_succeeds() {
         .assert()
         .success();
 }
+
+#[tokio::test]
+#[doc = "prover_cli timeline -n 10000 --to 10001 --format csv"]
+async fn pli_timeline_of_non_existing_batches_succeeds() {
+    let connection_pool = ConnectionPool::<Prover>::prover_test_pool().await;
+
+    Command::cargo_bin("prover_cli")
+        .unwrap()
+        .arg(connection_pool.database_url().expose_str())
+        .arg("timeline")
+        .args(["-n", "10000", "--to", "10001", "--format", "csv"])
+        .assert()
+        .success()
+        .stdout(
+            "l1_batch_number,stage,circuit_id,jobs,start_offset,wall_time,mean_queue_wait,\
+             max_queue_wait,mean_processing,total_processing,mean_witness_vector_generation\n",
+        );
+}
+
+#[test]
+#[doc = "prover_cli timeline -n 2 --to 1"]
+fn pli_timeline_of_empty_range_fails() {
+    Command::cargo_bin("prover_cli")
+        .unwrap()
+        .arg("timeline")
+        .args(["-n", "2", "--to", "1"])
+        .assert()
+        .failure();
+}
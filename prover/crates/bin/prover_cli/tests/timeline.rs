@@ -0,0 +1,202 @@
+use std::time::Duration;
+
+use assert_cmd::Command;
+use chrono::{NaiveDate, NaiveDateTime};
+use zksync_prover_dal::{
+    fri_witness_generator_dal::FriWitnessJobStatus, Connection, ConnectionPool, Prover, ProverDal,
+};
+use zksync_types::{
+    basic_fri_types::AggregationRound,
+    protocol_version::{L1VerifierConfig, ProtocolSemanticVersion},
+    prover_dal::{ProofCompressionJobStatus, ProverJobStatus, ProverJobStatusSuccessful},
+    L1BatchNumber,
+};
+
+const BATCH_STDOUT: &str = "\
+l1_batch_number,stage,circuit_id,jobs,start_offset,wall_time,mean_queue_wait,max_queue_wait,\
+mean_processing,total_processing,mean_witness_vector_generation
+1,basic_witness_generation,,1,0.000,30.000,10.000,10.000,20.000,20.000,
+1,basic_proving,,2,30.000,70.000,10.000,10.000,60.000,120.000,
+1,compression,,1,100.000,40.000,10.000,10.000,30.000,30.000,
+1,submission,,1,140.000,10.000,10.000,10.000,0.000,0.000,
+1,total,,5,0.000,150.000,10.000,10.000,34.000,170.000,
+";
+
+fn at(secs: u64) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2024, 1, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap()
+        + chrono::Duration::seconds(secs as i64)
+}
+
+async fn prepare_db(connection_pool: &ConnectionPool<Prover>) -> Connection<'static, Prover> {
+    let mut connection = connection_pool.connection().await.unwrap();
+    connection
+        .fri_protocol_versions_dal()
+        .save_prover_protocol_version(
+            ProtocolSemanticVersion::default(),
+            L1VerifierConfig::default(),
+        )
+        .await;
+    connection
+}
+
+/// Saves a batch whose basic witness generation waited `queue_wait` and took `processing`.
+async fn insert_basic_witness_job(
+    batch_number: L1BatchNumber,
+    queue_wait: u64,
+    processing: u64,
+    connection: &mut Connection<'_, Prover>,
+) {
+    connection
+        .fri_basic_witness_generator_dal()
+        .save_witness_inputs(batch_number, "", ProtocolSemanticVersion::default())
+        .await;
+    connection
+        .fri_basic_witness_generator_dal()
+        .set_status_for_basic_witness_job(FriWitnessJobStatus::Successful, batch_number)
+        .await;
+    connection
+        .cli_test_dal()
+        .update_job_timestamps(
+            "witness_inputs_fri",
+            batch_number,
+            at(0),
+            at(queue_wait),
+            Duration::from_secs(processing),
+            at(queue_wait + processing),
+        )
+        .await;
+}
+
+#[tokio::test]
+#[doc = "prover_cli timeline -n 1 --format csv"]
+async fn pli_timeline_splits_queue_wait_and_processing() {
+    let connection_pool = ConnectionPool::<Prover>::prover_test_pool().await;
+    let mut connection = prepare_db(&connection_pool).await;
+    let batch_number = L1BatchNumber(1);
+
+    // Basic witness generation: ready at 0s, picked at 10s, finished at 30s.
+    insert_basic_witness_job(batch_number, 10, 20, &mut connection).await;
+    // Basic proving: 2 jobs ready at 30s, picked at 40s, finished at 100s.
+    connection
+        .fri_prover_jobs_dal()
+        .insert_prover_jobs(
+            batch_number,
+            vec![(1, String::new()), (2, String::new())],
+            AggregationRound::BasicCircuits,
+            0,
+            ProtocolSemanticVersion::default(),
+        )
+        .await;
+    for (sequence_number, circuit_id) in [1, 2].into_iter().enumerate() {
+        connection
+            .cli_test_dal()
+            .update_prover_job(
+                ProverJobStatus::Successful(ProverJobStatusSuccessful::default()),
+                circuit_id,
+                AggregationRound::BasicCircuits as i64,
+                batch_number,
+                sequence_number,
+            )
+            .await;
+    }
+    connection
+        .cli_test_dal()
+        .update_job_timestamps(
+            "prover_jobs_fri",
+            batch_number,
+            at(30),
+            at(40),
+            Duration::from_secs(60),
+            at(100),
+        )
+        .await;
+    // Compression: ready at 100s, picked at 110s, finished at 140s; the proof is sent at 150s.
+    connection
+        .cli_test_dal()
+        .insert_compressor_job(ProofCompressionJobStatus::SentToServer, batch_number)
+        .await;
+    connection
+        .cli_test_dal()
+        .update_job_timestamps(
+            "proof_compression_jobs_fri",
+            batch_number,
+            at(100),
+            at(110),
+            Duration::from_secs(30),
+            at(150),
+        )
+        .await;
+
+    Command::cargo_bin("prover_cli")
+        .unwrap()
+        .arg(connection_pool.database_url().expose_str())
+        .arg("timeline")
+        .args(["-n", "1", "--format", "csv"])
+        .assert()
+        .success()
+        .stdout(BATCH_STDOUT);
+}
+
+#[tokio::test]
+#[doc = "prover_cli timeline -n 1 --to 10 --summary --format json"]
+async fn pli_timeline_summary_reports_percentiles() {
+    let connection_pool = ConnectionPool::<Prover>::prover_test_pool().await;
+    let mut connection = prepare_db(&connection_pool).await;
+    // Batch `i` waits `i` seconds in the queue and is processed for `10 * i` seconds.
+    for i in 1..=10 {
+        insert_basic_witness_job(
+            L1BatchNumber(i),
+            i.into(),
+            10 * u64::from(i),
+            &mut connection,
+        )
+        .await;
+    }
+
+    let output = Command::cargo_bin("prover_cli")
+        .unwrap()
+        .arg(connection_pool.database_url().expose_str())
+        .arg("timeline")
+        .args(["-n", "1", "--to", "10", "--summary", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    let rows: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    let row = |stage: &str, metric: &str, p50: f64, p90: f64, p99: f64| {
+        serde_json::json!({
+            "stage": stage,
+            "circuit_id": null,
+            "metric": metric,
+            "batches": 10,
+            "p50": p50,
+            "p90": p90,
+            "p99": p99,
+            "max": p99,
+        })
+    };
+    // Batches are not submitted, so there is no total wall time.
+    let expected = serde_json::json!([
+        row("basic_witness_generation", "wall_time", 55.0, 99.0, 110.0),
+        row(
+            "basic_witness_generation",
+            "mean_queue_wait",
+            5.0,
+            9.0,
+            10.0
+        ),
+        row(
+            "basic_witness_generation",
+            "mean_processing",
+            50.0,
+            90.0,
+            100.0
+        ),
+        row("total", "mean_queue_wait", 5.0, 9.0, 10.0),
+        row("total", "mean_processing", 50.0, 90.0, 100.0),
+    ]);
+    assert_eq!(rows, expected);
+}
@@ -0,0 +1,146 @@
+use zksync_config::configs::FriProverGatewayConfig;
+use zksync_types::L2ChainId;
+
+/// Proof data handler the gateway talks to.
+#[derive(Debug, Clone)]
+pub(crate) struct ChainEndpoint {
+    /// `None` for the server at `api_url`, whose batches are saved without a chain.
+    pub(crate) chain_id: Option<L2ChainId>,
+    pub(crate) base_url: String,
+    pub(crate) weight: u32,
+}
+
+impl ChainEndpoint {
+    /// Label used in logs and metrics.
+    pub(crate) fn label(&self) -> String {
+        match self.chain_id {
+            Some(chain_id) => chain_id.as_u64().to_string(),
+            None => "default".to_string(),
+        }
+    }
+}
+
+/// Returns endpoints to fetch proof generation data from: configured chains, or `api_url` if
+/// there are none.
+pub(crate) fn fetch_endpoints(
+    config: &FriProverGatewayConfig,
+) -> anyhow::Result<Vec<ChainEndpoint>> {
+    if config.chains.is_empty() {
+        return Ok(vec![default_endpoint(config)]);
+    }
+    config
+        .chains
+        .iter()
+        .map(|chain| {
+            anyhow::ensure!(
+                chain.weight > 0,
+                "weight of chain {} must be positive",
+                chain.chain_id.as_u64()
+            );
+            Ok(ChainEndpoint {
+                chain_id: Some(chain.chain_id),
+                base_url: chain.api_url.clone(),
+                weight: chain.weight,
+            })
+        })
+        .collect()
+}
+
+/// Returns endpoints to submit proofs to. `api_url` is always included, since batches fetched
+/// before chains were configured have to be submitted there.
+pub(crate) fn submit_endpoints(
+    config: &FriProverGatewayConfig,
+) -> anyhow::Result<Vec<ChainEndpoint>> {
+    let mut endpoints = fetch_endpoints(config)?;
+    if !config.chains.is_empty() {
+        endpoints.push(default_endpoint(config));
+    }
+    Ok(endpoints)
+}
+
+fn default_endpoint(config: &FriProverGatewayConfig) -> ChainEndpoint {
+    ChainEndpoint {
+        chain_id: None,
+        base_url: config.api_url.clone(),
+        weight: 1,
+    }
+}
+
+/// Smooth weighted round-robin over endpoints. Endpoints which reported no pending batches are
+/// skipped until all endpoints are idle, so that busy chains share fetches by their weights and
+/// don't wait for idle ones.
+#[derive(Debug)]
+pub(crate) struct WeightedScheduler {
+    weights: Vec<i64>,
+    current: Vec<i64>,
+    idle: Vec<bool>,
+}
+
+impl WeightedScheduler {
+    pub(crate) fn new(endpoints: &[ChainEndpoint]) -> Self {
+        Self {
+            weights: endpoints.iter().map(|e| i64::from(e.weight)).collect(),
+            current: vec![0; endpoints.len()],
+            idle: vec![false; endpoints.len()],
+        }
+    }
+
+    /// Returns the index of the endpoint to poll next.
+    pub(crate) fn next(&mut self) -> usize {
+        if self.idle.iter().all(|&idle| idle) {
+            self.idle.fill(false);
+        }
+        let mut total = 0;
+        let mut best = None;
+        for i in (0..self.weights.len()).filter(|&i| !self.idle[i]) {
+            self.current[i] += self.weights[i];
+            total += self.weights[i];
+            if best.map_or(true, |b| self.current[i] > self.current[b]) {
+                best = Some(i);
+            }
+        }
+        let best = best.expect("no endpoints to schedule");
+        self.current[best] -= total;
+        best
+    }
+
+    pub(crate) fn set_idle(&mut self, index: usize, idle: bool) {
+        self.idle[index] = idle;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scheduler(weights: &[u32]) -> WeightedScheduler {
+        let endpoints: Vec<_> = weights
+            .iter()
+            .map(|&weight| ChainEndpoint {
+                chain_id: None,
+                base_url: String::new(),
+                weight,
+            })
+            .collect();
+        WeightedScheduler::new(&endpoints)
+    }
+
+    #[test]
+    fn test_weighted_scheduling() {
+        let mut scheduler = scheduler(&[3, 1]);
+        let picks: Vec<_> = (0..8).map(|_| scheduler.next()).collect();
+        assert_eq!(picks, [0, 0, 1, 0, 0, 0, 1, 0]);
+
+        scheduler.set_idle(0, true);
+        assert_eq!(scheduler.next(), 1);
+        assert_eq!(scheduler.next(), 1, "Idle endpoint is skipped");
+
+        scheduler.set_idle(1, true);
+        let picks: Vec<_> = (0..4).map(|_| scheduler.next()).collect();
+        assert_eq!(
+            picks.iter().filter(|&&i| i == 0).count(),
+            3,
+            "All endpoints are polled again once all of them are idle"
+        );
+    }
+}
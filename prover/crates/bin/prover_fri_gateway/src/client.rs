@@ -4,13 +4,15 @@ use serde::{de::DeserializeOwned, Serialize};
 use zksync_object_store::ObjectStore;
 use zksync_prover_dal::{ConnectionPool, Prover};
 
+use crate::chains::ChainEndpoint;
+
 /// A tiny wrapper over the reqwest client that also stores
 /// the objects commonly needed when interacting with prover API.
 #[derive(Debug)]
 pub(crate) struct ProverApiClient {
     pub(crate) blob_store: Arc<dyn ObjectStore>,
     pub(crate) pool: ConnectionPool<Prover>,
-    pub(crate) api_url: String,
+    pub(crate) endpoints: Vec<ChainEndpoint>,
     pub(crate) client: reqwest::Client,
 }
 
@@ -18,12 +20,12 @@ impl ProverApiClient {
     pub(crate) fn new(
         blob_store: Arc<dyn ObjectStore>,
         pool: ConnectionPool<Prover>,
-        api_url: String,
+        endpoints: Vec<ChainEndpoint>,
     ) -> Self {
         Self {
             blob_store,
             pool,
-            api_url,
+            endpoints,
             client: reqwest::Client::new(),
         }
     }
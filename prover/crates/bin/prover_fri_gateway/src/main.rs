@@ -9,10 +9,11 @@ use traits::PeriodicApi as _;
 use zksync_core_leftovers::temp_config_store::{load_database_secrets, load_general_config};
 use zksync_env_config::object_store::ProverObjectStoreConfig;
 use zksync_object_store::ObjectStoreFactory;
-use zksync_prover_dal::{ConnectionPool, Prover};
+use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
 use zksync_task_management::ManagedTasks;
 use zksync_vlog::prometheus::PrometheusExporterConfig;
 
+mod chains;
 mod client;
 mod metrics;
 mod proof_gen_data_fetcher;
@@ -43,6 +44,16 @@ async fn main() -> anyhow::Result<()> {
     .build()
     .await
     .context("failed to build a connection pool")?;
+    if !config.chains.is_empty() {
+        // Batches saved before chains were configured keep their numbers, so chain batches
+        // have to be numbered after them.
+        pool.connection()
+            .await
+            .context("failed to get a connection")?
+            .fri_chain_batches_dal()
+            .sync_batch_number_sequence()
+            .await;
+    }
     let object_store_config = ProverObjectStoreConfig(
         general_config
             .prover_config
@@ -54,12 +65,12 @@ async fn main() -> anyhow::Result<()> {
 
     let proof_submitter = ProofSubmitter::new(
         store_factory.create_store().await?,
-        config.api_url.clone(),
+        chains::submit_endpoints(&config)?,
         pool.clone(),
     );
     let proof_gen_data_fetcher = ProofGenDataFetcher::new(
         store_factory.create_store().await?,
-        config.api_url.clone(),
+        chains::fetch_endpoints(&config)?,
         pool,
     );
 
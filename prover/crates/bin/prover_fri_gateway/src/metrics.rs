@@ -5,6 +5,11 @@ use vise::{Counter, LabeledFamily, Metrics};
 pub(crate) struct ProverFriGatewayMetrics {
     #[metrics(labels = ["service_name"])]
     pub http_error: LabeledFamily<&'static str, Counter>,
+    /// Batches fetched from a chain, `default` for the server at `api_url`.
+    #[metrics(labels = ["chain"])]
+    pub fetched_batches: LabeledFamily<String, Counter>,
+    #[metrics(labels = ["chain"])]
+    pub submitted_proofs: LabeledFamily<String, Counter>,
 }
 
 #[vise::register]
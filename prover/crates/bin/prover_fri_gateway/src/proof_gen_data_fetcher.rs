@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use async_trait::async_trait;
 use zksync_object_store::ObjectStore;
@@ -6,13 +6,24 @@ use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
 use zksync_prover_interface::api::{
     ProofGenerationData, ProofGenerationDataRequest, ProofGenerationDataResponse,
 };
+use zksync_types::prover_dal::ChainBatch;
 
-use crate::{client::ProverApiClient, traits::PeriodicApi};
+use crate::{
+    chains::{ChainEndpoint, WeightedScheduler},
+    client::ProverApiClient,
+    metrics::METRICS,
+    traits::PeriodicApi,
+};
 
 /// Poller structure that will periodically check the prover API for new proof generation data.
 /// Fetched data is stored to the database/object store for further processing.
+/// If several chains are configured, each poll goes to one of them, chosen by weighted fair
+/// scheduling.
 #[derive(Debug)]
-pub struct ProofGenDataFetcher(ProverApiClient);
+pub struct ProofGenDataFetcher {
+    inner: ProverApiClient,
+    scheduler: Mutex<WeightedScheduler>,
+}
 
 /// The path to the API endpoint that returns the next proof generation data.
 const PROOF_GENERATION_DATA_PATH: &str = "/proof_generation_data";
@@ -20,12 +31,12 @@ const PROOF_GENERATION_DATA_PATH: &str = "/proof_generation_data";
 impl ProofGenDataFetcher {
     pub(crate) fn new(
         blob_store: Arc<dyn ObjectStore>,
-        base_url: String,
+        endpoints: Vec<ChainEndpoint>,
         pool: ConnectionPool<Prover>,
     ) -> Self {
-        let api_url = format!("{base_url}{PROOF_GENERATION_DATA_PATH}");
-        let inner = ProverApiClient::new(blob_store, pool, api_url);
-        Self(inner)
+        let scheduler = Mutex::new(WeightedScheduler::new(&endpoints));
+        let inner = ProverApiClient::new(blob_store, pool, endpoints);
+        Self { inner, scheduler }
     }
 }
 
@@ -33,15 +44,33 @@ impl ProofGenDataFetcher {
     #[tracing::instrument(
         name = "ProofGenDataFetcher::save_proof_gen_data",
         skip_all,
-        fields(l1_batch = %data.l1_batch_number)
+        fields(l1_batch = %data.l1_batch_number, chain = %endpoint.label())
     )]
-    async fn save_proof_gen_data(&self, data: ProofGenerationData) {
-        let store = &*self.0.blob_store;
+    async fn save_proof_gen_data(&self, endpoint: &ChainEndpoint, data: ProofGenerationData) {
+        let mut connection = self.inner.pool.connection().await.unwrap();
+
+        // Batches of different chains may have the same number, so they are saved under
+        // prover-local numbers. The number is allocated before the data is written, so that
+        // concurrent fetchers never write data of different batches under the same number.
+        let l1_batch_number = match endpoint.chain_id {
+            Some(chain_id) => {
+                let chain_batch = ChainBatch {
+                    chain_id,
+                    l1_batch_number: data.l1_batch_number,
+                };
+                connection
+                    .fri_chain_batches_dal()
+                    .allocate_prover_batch_number(chain_batch)
+                    .await
+            }
+            None => data.l1_batch_number,
+        };
+
+        let store = &*self.inner.blob_store;
         let witness_inputs = store
-            .put(data.l1_batch_number, &data.witness_input_data)
+            .put(l1_batch_number, &data.witness_input_data)
             .await
             .expect("Failed to save proof generation data to GCS");
-        let mut connection = self.0.pool.connection().await.unwrap();
 
         connection
             .fri_protocol_versions_dal()
@@ -50,42 +79,61 @@ impl ProofGenDataFetcher {
 
         connection
             .fri_basic_witness_generator_dal()
-            .save_witness_inputs(data.l1_batch_number, &witness_inputs, data.protocol_version)
+            .save_witness_inputs(l1_batch_number, &witness_inputs, data.protocol_version)
             .await;
+        METRICS.fetched_batches[&endpoint.label()].inc();
     }
 }
 
 #[async_trait]
 impl PeriodicApi for ProofGenDataFetcher {
-    type JobId = ();
+    /// Index of the endpoint.
+    type JobId = usize;
     type Request = ProofGenerationDataRequest;
     type Response = ProofGenerationDataResponse;
 
     const SERVICE_NAME: &'static str = "ProofGenDataFetcher";
 
     async fn get_next_request(&self) -> Option<(Self::JobId, ProofGenerationDataRequest)> {
-        Some(((), ProofGenerationDataRequest {}))
+        let index = self.scheduler.lock().unwrap().next();
+        Some((index, ProofGenerationDataRequest {}))
     }
 
     async fn send_request(
         &self,
-        _: (),
+        index: usize,
         request: ProofGenerationDataRequest,
     ) -> reqwest::Result<Self::Response> {
-        self.0.send_http_request(request, &self.0.api_url).await
+        let endpoint = &self.inner.endpoints[index];
+        let url = format!("{}{PROOF_GENERATION_DATA_PATH}", endpoint.base_url);
+        self.inner.send_http_request(request, &url).await
     }
 
-    async fn handle_response(&self, _: (), response: Self::Response) {
+    async fn handle_response(&self, index: usize, response: Self::Response) {
+        let endpoint = &self.inner.endpoints[index];
+        let has_data = matches!(response, ProofGenerationDataResponse::Success(Some(_)));
+        self.scheduler.lock().unwrap().set_idle(index, !has_data);
         match response {
             ProofGenerationDataResponse::Success(Some(data)) => {
-                tracing::info!("Received proof gen data for: {:?}", data.l1_batch_number);
-                self.save_proof_gen_data(*data).await;
+                tracing::info!(
+                    "Received proof gen data for: {:?} (chain {})",
+                    data.l1_batch_number,
+                    endpoint.label()
+                );
+                self.save_proof_gen_data(endpoint, *data).await;
             }
             ProofGenerationDataResponse::Success(None) => {
-                tracing::info!("There are currently no pending batches to be proven");
+                tracing::info!(
+                    "There are currently no pending batches to be proven (chain {})",
+                    endpoint.label()
+                );
             }
             ProofGenerationDataResponse::Error(err) => {
-                tracing::error!("Failed to get proof gen data: {:?}", err);
+                tracing::error!(
+                    "Failed to get proof gen data (chain {}): {:?}",
+                    endpoint.label(),
+                    err
+                );
             }
         }
     }
@@ -6,39 +6,78 @@ use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
 use zksync_prover_interface::api::{SubmitProofRequest, SubmitProofResponse};
 use zksync_types::{prover_dal::ProofCompressionJobStatus, L1BatchNumber};
 
-use crate::{client::ProverApiClient, traits::PeriodicApi};
+use crate::{
+    chains::ChainEndpoint, client::ProverApiClient, metrics::METRICS, traits::PeriodicApi,
+};
 
 /// The path to the API endpoint that submits the proof.
 const SUBMIT_PROOF_PATH: &str = "/submit_proof";
 
 /// Poller structure that will periodically check the database for new proofs to submit.
-/// Once a new proof is detected, it will be sent to the prover API.
+/// Once a new proof is detected, it will be sent to the prover API of the chain the batch
+/// was fetched from.
 #[derive(Debug)]
 pub struct ProofSubmitter(ProverApiClient);
 
+/// Proof to submit.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ProofSubmission {
+    /// Prover-local batch number.
+    l1_batch_number: L1BatchNumber,
+    /// Index of the endpoint to submit to.
+    endpoint: usize,
+    /// Batch number on the chain.
+    chain_l1_batch_number: L1BatchNumber,
+}
+
 impl ProofSubmitter {
     pub(crate) fn new(
         blob_store: Arc<dyn ObjectStore>,
-        base_url: String,
+        endpoints: Vec<ChainEndpoint>,
         pool: ConnectionPool<Prover>,
     ) -> Self {
-        let api_url = format!("{base_url}{SUBMIT_PROOF_PATH}");
-        let inner = ProverApiClient::new(blob_store, pool, api_url);
+        let inner = ProverApiClient::new(blob_store, pool, endpoints);
         Self(inner)
     }
 }
 
 impl ProofSubmitter {
-    async fn next_submit_proof_request(&self) -> Option<(L1BatchNumber, SubmitProofRequest)> {
-        let (l1_batch_number, protocol_version, status) = self
+    async fn next_submit_proof_request(&self) -> Option<(ProofSubmission, SubmitProofRequest)> {
+        let chain_ids: Vec<_> = self
             .0
-            .pool
-            .connection()
-            .await
-            .unwrap()
+            .endpoints
+            .iter()
+            .filter_map(|endpoint| endpoint.chain_id)
+            .collect();
+        let mut connection = self.0.pool.connection().await.unwrap();
+        // Proofs of batches fetched for chains which are no longer configured can't be submitted,
+        // so they are skipped instead of blocking submission of later batches.
+        let (l1_batch_number, protocol_version, status) = connection
             .fri_proof_compressor_dal()
-            .get_least_proven_block_not_sent_to_server()
+            .get_least_proven_block_not_sent_to_server(&chain_ids)
             .await?;
+        let chain_batch = connection
+            .fri_chain_batches_dal()
+            .get_chain_batch(l1_batch_number)
+            .await;
+        drop(connection);
+
+        // Batches saved without a chain are submitted to `api_url`, which is always configured.
+        let chain_id = chain_batch.map(|batch| batch.chain_id);
+        let endpoint = self
+            .0
+            .endpoints
+            .iter()
+            .position(|endpoint| endpoint.chain_id == chain_id)
+            .unwrap_or_else(|| {
+                panic!("chain {chain_id:?} of batch {l1_batch_number} is not configured")
+            });
+        let submission = ProofSubmission {
+            l1_batch_number,
+            endpoint,
+            chain_l1_batch_number: chain_batch
+                .map_or(l1_batch_number, |batch| batch.l1_batch_number),
+        };
 
         let request = match status {
             ProofCompressionJobStatus::Successful => {
@@ -57,7 +96,7 @@ impl ProofSubmitter {
             ),
         };
 
-        Some((l1_batch_number, request))
+        Some((submission, request))
     }
 
     async fn save_successful_sent_proof(&self, l1_batch_number: L1BatchNumber) {
@@ -74,14 +113,14 @@ impl ProofSubmitter {
 
 #[async_trait]
 impl PeriodicApi for ProofSubmitter {
-    type JobId = L1BatchNumber;
+    type JobId = ProofSubmission;
     type Request = SubmitProofRequest;
     type Response = SubmitProofResponse;
     const SERVICE_NAME: &'static str = "ProofSubmitter";
 
     async fn get_next_request(&self) -> Option<(Self::JobId, SubmitProofRequest)> {
-        let (l1_batch_number, request) = self.next_submit_proof_request().await?;
-        Some((l1_batch_number, request))
+        let (submission, request) = self.next_submit_proof_request().await?;
+        Some((submission, request))
     }
 
     async fn send_request(
@@ -89,12 +128,17 @@ impl PeriodicApi for ProofSubmitter {
         job_id: Self::JobId,
         request: SubmitProofRequest,
     ) -> reqwest::Result<Self::Response> {
-        let endpoint = format!("{}/{job_id}", self.0.api_url);
+        let endpoint = format!(
+            "{}{SUBMIT_PROOF_PATH}/{}",
+            self.0.endpoints[job_id.endpoint].base_url, job_id.chain_l1_batch_number
+        );
         self.0.send_http_request(request, &endpoint).await
     }
 
-    async fn handle_response(&self, job_id: L1BatchNumber, response: Self::Response) {
+    async fn handle_response(&self, job_id: ProofSubmission, response: Self::Response) {
         tracing::info!("Received response: {:?}", response);
-        self.save_successful_sent_proof(job_id).await;
+        self.save_successful_sent_proof(job_id.l1_batch_number)
+            .await;
+        METRICS.submitted_proofs[&self.0.endpoints[job_id.endpoint].label()].inc();
     }
 }
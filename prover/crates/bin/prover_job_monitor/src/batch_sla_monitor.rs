@@ -0,0 +1,213 @@
+use std::time::Duration;
+
+use zksync_prover_dal::{Connection, Prover, ProverDal};
+
+use crate::{metrics::PROVER_JOB_MONITOR_METRICS, task_wiring::Task};
+
+/// `BatchSlaMonitor` is a task that keeps track of SLA deadlines of L1 batches.
+/// Unproven batches get a deadline of `sla` after their witness inputs are saved, and jobs of all
+/// rounds are picked in the order of their batch deadline.
+/// Unproven batches without failed jobs which are within `at_risk_threshold` of the deadline are
+/// reported and, if `min_priority` is set, their unfinished jobs are boosted to at least that priority.
+#[derive(Debug)]
+pub struct BatchSlaMonitor {
+    sla: Duration,
+    at_risk_threshold: Duration,
+    /// 0 disables boosting
+    min_priority: u32,
+}
+
+/// Outcome of a single SLA check.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct SlaCheckOutcome {
+    at_risk_batches: usize,
+    missed_batches: usize,
+    boosted_jobs: u64,
+}
+
+impl BatchSlaMonitor {
+    pub fn new(sla: Duration, at_risk_threshold: Duration, min_priority: u32) -> Self {
+        Self {
+            sla,
+            at_risk_threshold,
+            min_priority,
+        }
+    }
+
+    async fn check_batches(&self, connection: &mut Connection<'_, Prover>) -> SlaCheckOutcome {
+        let assigned = connection
+            .fri_batch_sla_dal()
+            .assign_batch_deadlines(self.sla)
+            .await;
+        if assigned > 0 {
+            tracing::debug!("Assigned SLA deadlines to {assigned} batches");
+        }
+
+        let at_risk = connection
+            .fri_batch_sla_dal()
+            .get_sla_at_risk_batches(self.at_risk_threshold)
+            .await;
+        let mut outcome = SlaCheckOutcome {
+            at_risk_batches: at_risk.len(),
+            missed_batches: at_risk.iter().filter(|batch| batch.missed).count(),
+            boosted_jobs: 0,
+        };
+
+        for batch in at_risk {
+            if batch.missed {
+                tracing::warn!(
+                    "Batch {} missed its SLA deadline {}",
+                    batch.l1_batch_number,
+                    batch.deadline
+                );
+            } else {
+                tracing::warn!(
+                    "Batch {} is at risk of missing its SLA deadline {}",
+                    batch.l1_batch_number,
+                    batch.deadline
+                );
+            }
+            if self.min_priority == 0 {
+                continue;
+            }
+            let boosted = connection
+                .fri_batch_sla_dal()
+                .boost_batch_priority(batch.l1_batch_number, self.min_priority)
+                .await;
+            if boosted > 0 {
+                tracing::info!(
+                    "Boosted {boosted} jobs of batch {} to priority {}",
+                    batch.l1_batch_number,
+                    self.min_priority
+                );
+                outcome.boosted_jobs += boosted;
+            }
+        }
+        outcome
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for BatchSlaMonitor {
+    async fn invoke(&self, connection: &mut Connection<Prover>) -> anyhow::Result<()> {
+        let outcome = self.check_batches(connection).await;
+        PROVER_JOB_MONITOR_METRICS
+            .sla_at_risk_batches
+            .set(outcome.at_risk_batches as u64);
+        PROVER_JOB_MONITOR_METRICS
+            .sla_missed_batches
+            .set(outcome.missed_batches as u64);
+        PROVER_JOB_MONITOR_METRICS
+            .sla_boosted_jobs
+            .inc_by(outcome.boosted_jobs);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_prover_dal::ConnectionPool;
+    use zksync_types::{
+        basic_fri_types::AggregationRound,
+        protocol_version::{L1VerifierConfig, ProtocolSemanticVersion},
+        L1BatchNumber,
+    };
+
+    use super::*;
+
+    async fn save_batch(connection: &mut Connection<'_, Prover>, number: u32) {
+        connection
+            .fri_basic_witness_generator_dal()
+            .save_witness_inputs(
+                L1BatchNumber(number),
+                "witness_inputs",
+                ProtocolSemanticVersion::default(),
+            )
+            .await;
+        connection
+            .fri_prover_jobs_dal()
+            .insert_prover_jobs(
+                L1BatchNumber(number),
+                vec![(1, "circuit".to_owned())],
+                AggregationRound::BasicCircuits,
+                0,
+                ProtocolSemanticVersion::default(),
+            )
+            .await;
+    }
+
+    #[tokio::test]
+    async fn at_risk_batches_are_reported_and_boosted() {
+        let pool = ConnectionPool::<Prover>::prover_test_pool().await;
+        let mut connection = pool.connection().await.unwrap();
+        connection
+            .fri_protocol_versions_dal()
+            .save_prover_protocol_version(
+                ProtocolSemanticVersion::default(),
+                L1VerifierConfig::default(),
+            )
+            .await;
+        save_batch(&mut connection, 1).await;
+        save_batch(&mut connection, 2).await;
+        // Batch #1 is proven and must not be reported.
+        connection
+            .fri_proof_compressor_dal()
+            .insert_proof_compression_job(
+                L1BatchNumber(1),
+                "fri_proof",
+                ProtocolSemanticVersion::default(),
+            )
+            .await;
+        connection
+            .fri_proof_compressor_dal()
+            .mark_proof_compression_job_successful(
+                L1BatchNumber(1),
+                Duration::from_secs(1),
+                "l1_proof",
+            )
+            .await;
+
+        let monitor = BatchSlaMonitor::new(Duration::ZERO, Duration::from_secs(3600), 10);
+        let outcome = monitor.check_batches(&mut connection).await;
+        // Witness inputs and the prover job of batch #2 are boosted.
+        assert_eq!(
+            outcome,
+            SlaCheckOutcome {
+                at_risk_batches: 1,
+                missed_batches: 1,
+                boosted_jobs: 2,
+            }
+        );
+
+        // Jobs are boosted only once.
+        let outcome = monitor.check_batches(&mut connection).await;
+        assert_eq!(
+            outcome,
+            SlaCheckOutcome {
+                at_risk_batches: 1,
+                missed_batches: 1,
+                boosted_jobs: 0,
+            }
+        );
+
+        // Once the batch is proven, it's no longer reported.
+        connection
+            .fri_proof_compressor_dal()
+            .insert_proof_compression_job(
+                L1BatchNumber(2),
+                "fri_proof",
+                ProtocolSemanticVersion::default(),
+            )
+            .await;
+        connection
+            .fri_proof_compressor_dal()
+            .mark_proof_compression_job_successful(
+                L1BatchNumber(2),
+                Duration::from_secs(1),
+                "l1_proof",
+            )
+            .await;
+        let outcome = monitor.check_batches(&mut connection).await;
+        assert_eq!(outcome, SlaCheckOutcome::default());
+    }
+}
@@ -1,6 +1,7 @@
 pub mod archiver;
 pub mod attempts_reporter;
 pub mod autoscaler_queue_reporter;
+pub mod batch_sla_monitor;
 pub mod job_requeuer;
 pub(crate) mod metrics;
 pub mod queue_reporter;
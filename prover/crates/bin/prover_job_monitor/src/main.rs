@@ -16,6 +16,7 @@ use zksync_prover_job_monitor::{
     archiver::{GpuProverArchiver, ProverJobsArchiver},
     attempts_reporter::ProverJobAttemptsReporter,
     autoscaler_queue_reporter::get_queue_reporter_router,
+    batch_sla_monitor::BatchSlaMonitor,
     job_requeuer::{ProofCompressorJobRequeuer, ProverJobRequeuer, WitnessGeneratorJobRequeuer},
     queue_reporter::{
         ProofCompressorQueueReporter, ProverQueueReporter, WitnessGeneratorQueueReporter,
@@ -233,5 +234,17 @@ fn get_tasks(
         attempts_reporter,
     );
 
+    // SLA deadlines of batches
+    let batch_sla_monitor = BatchSlaMonitor::new(
+        prover_job_monitor_config.batch_sla(),
+        prover_job_monitor_config.sla_at_risk_threshold(),
+        prover_job_monitor_config.sla_at_risk_min_priority,
+    );
+    task_runner.add(
+        "BatchSlaMonitor",
+        prover_job_monitor_config.sla_monitor_run_interval(),
+        batch_sla_monitor,
+    );
+
     Ok(task_runner.spawn(stop_receiver))
 }
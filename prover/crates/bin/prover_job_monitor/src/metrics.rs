@@ -8,6 +8,11 @@ pub(crate) struct ProverJobMonitorMetrics {
     pub gpu_prover_archived: Counter,
     #[metrics(labels = ["job_type"])]
     pub reached_max_attempts: LabeledFamily<JobType, Gauge>,
+    /// Unproven batches close to or past their SLA deadline.
+    pub sla_at_risk_batches: Gauge<u64>,
+    /// Unproven batches past their SLA deadline.
+    pub sla_missed_batches: Gauge<u64>,
+    pub sla_boosted_jobs: Counter,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
@@ -0,0 +1,891 @@
+//! Local cache of object store artifacts shared between witness generator rounds.
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs::File,
+    io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use fs2::FileExt;
+use zksync_object_store::{Bucket, ObjectStore, ObjectStoreError};
+use zksync_types::{protocol_version::ProtocolSemanticVersion, web3::keccak256, H256};
+
+use crate::metrics::ARTIFACT_CACHE_METRICS;
+
+/// Buckets with artifacts read by witness generators. Other buckets (e.g., circuits) are only written
+/// by witness generators, so they aren't cached.
+const CACHED_BUCKETS: [Bucket; 5] = [
+    Bucket::WitnessInput,
+    Bucket::LeafAggregationWitnessJobsFri,
+    Bucket::NodeAggregationWitnessJobsFri,
+    Bucket::SchedulerWitnessJobsFri,
+    Bucket::ProofsFri,
+];
+
+/// Name of the file locked by the process using the cache directory.
+const LOCK_FILE_NAME: &str = "LOCK";
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct IndexEntry {
+    content_hash: H256,
+    /// Object store latency observed when the object was fetched or stored.
+    remote_latency: Duration,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    /// Size and last access of every cached blob.
+    blobs: HashMap<H256, (u64, u64)>,
+    /// Cached blobs ordered by last access.
+    lru: BTreeMap<u64, H256>,
+    total_size: u64,
+    access_counter: u64,
+    /// Content hash referenced by every index entry, keyed by the hash of bucket and key.
+    index: HashMap<H256, H256>,
+    /// Index entries referencing every blob, so that they are removed together with the blob.
+    index_by_blob: HashMap<H256, HashSet<H256>>,
+}
+
+/// Blob evicted from the cache together with the index entries referencing it.
+#[derive(Debug)]
+struct EvictedBlob {
+    content_hash: H256,
+    index_keys: Vec<H256>,
+}
+
+impl CacheState {
+    fn contains(&self, hash: H256) -> bool {
+        self.blobs.contains_key(&hash)
+    }
+
+    fn touch(&mut self, hash: H256, size: u64) {
+        self.access_counter += 1;
+        let access = self.access_counter;
+        if let Some((prev_size, prev_access)) = self.blobs.insert(hash, (size, access)) {
+            self.lru.remove(&prev_access);
+            self.total_size -= prev_size;
+        }
+        self.lru.insert(access, hash);
+        self.total_size += size;
+    }
+
+    /// Removes the blob, returning index entries referencing it.
+    fn forget(&mut self, hash: H256) -> Vec<H256> {
+        if let Some((size, access)) = self.blobs.remove(&hash) {
+            self.lru.remove(&access);
+            self.total_size -= size;
+        }
+        let index_keys: Vec<_> = self
+            .index_by_blob
+            .remove(&hash)
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        for key in &index_keys {
+            self.index.remove(key);
+        }
+        index_keys
+    }
+
+    fn set_index_entry(&mut self, index_key: H256, content_hash: H256) {
+        if let Some(prev_hash) = self.index.insert(index_key, content_hash) {
+            if prev_hash != content_hash {
+                self.unlink_index_entry(index_key, prev_hash);
+            }
+        }
+        self.index_by_blob
+            .entry(content_hash)
+            .or_default()
+            .insert(index_key);
+    }
+
+    fn remove_index_entry(&mut self, index_key: H256) {
+        if let Some(content_hash) = self.index.remove(&index_key) {
+            self.unlink_index_entry(index_key, content_hash);
+        }
+    }
+
+    fn unlink_index_entry(&mut self, index_key: H256, content_hash: H256) {
+        if let Some(keys) = self.index_by_blob.get_mut(&content_hash) {
+            keys.remove(&index_key);
+            if keys.is_empty() {
+                self.index_by_blob.remove(&content_hash);
+            }
+        }
+    }
+
+    /// Removes least recently used blobs until the cache fits into `max_size`.
+    fn evict(&mut self, max_size: u64) -> Vec<EvictedBlob> {
+        let mut evicted = vec![];
+        while self.total_size > max_size {
+            let Some((_, content_hash)) = self.lru.first_key_value() else {
+                break;
+            };
+            let content_hash = *content_hash;
+            let index_keys = self.forget(content_hash);
+            evicted.push(EvictedBlob {
+                content_hash,
+                index_keys,
+            });
+        }
+        evicted
+    }
+}
+
+/// Content-addressed on-disk cache of object store artifacts with a bounded size.
+///
+/// Blobs are stored under the hash of their content, and the index maps bucket and key to the hash,
+/// so that all witness generator rounds of a process share blobs. The size and LRU order of blobs
+/// are tracked in memory, so a cache directory can be used by a single process only; it is locked
+/// for the lifetime of the cache. Each protocol version gets its own subdirectory; subdirectories
+/// of other versions are removed on startup, since artifacts aren't compatible across protocol
+/// versions. Other entries of the cache directory are never touched. When the size limit is
+/// reached, least recently used blobs are evicted together with index entries referencing them
+/// (after a restart, blobs are ordered by creation time).
+#[derive(Debug)]
+pub struct ArtifactCache {
+    blobs_dir: PathBuf,
+    index_dir: PathBuf,
+    max_size: u64,
+    state: Mutex<CacheState>,
+    /// Holds the exclusive lock of the cache directory; the lock is released when the file is closed.
+    _lock_file: File,
+}
+
+impl ArtifactCache {
+    /// Opens the cache at `path`. Fails if the directory is used by another process.
+    pub async fn new(
+        path: PathBuf,
+        max_size: u64,
+        protocol_version: ProtocolSemanticVersion,
+    ) -> anyhow::Result<Self> {
+        tokio::task::spawn_blocking(move || Self::new_blocking(&path, max_size, protocol_version))
+            .await
+            .context("panicked while initializing artifact cache")?
+    }
+
+    fn new_blocking(
+        path: &Path,
+        max_size: u64,
+        protocol_version: ProtocolSemanticVersion,
+    ) -> anyhow::Result<Self> {
+        let version_dir_name = protocol_version.to_string();
+        std::fs::create_dir_all(path)
+            .with_context(|| format!("cannot create artifact cache directory {path:?}"))?;
+        let lock_file = File::create(path.join(LOCK_FILE_NAME)).with_context(|| {
+            format!("cannot create lock file in artifact cache directory {path:?}")
+        })?;
+        lock_file.try_lock_exclusive().with_context(|| {
+            format!("artifact cache directory {path:?} is used by another process")
+        })?;
+
+        for entry in std::fs::read_dir(path)? {
+            let entry = entry?;
+            let file_name = entry.file_name();
+            let Some(file_name) = file_name.to_str() else {
+                continue;
+            };
+            // The directory may contain unrelated files, so only caches of other versions are removed.
+            if is_version_dir_name(file_name) && file_name != version_dir_name {
+                tracing::info!(
+                    "Removing artifact cache of another protocol version at {:?}",
+                    entry.path()
+                );
+                remove_path(&entry.path())?;
+                ARTIFACT_CACHE_METRICS.invalidated.inc();
+            }
+        }
+
+        let version_dir = path.join(version_dir_name);
+        let blobs_dir = version_dir.join("blobs");
+        let index_dir = version_dir.join("index");
+        std::fs::create_dir_all(&blobs_dir)?;
+        std::fs::create_dir_all(&index_dir)?;
+
+        let mut blobs = vec![];
+        for entry in std::fs::read_dir(&blobs_dir)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            let hash = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.parse::<H256>().ok());
+            match hash {
+                Some(hash) if metadata.is_file() => {
+                    blobs.push((metadata.modified()?, hash, metadata.len()));
+                }
+                // Leftovers of interrupted writes.
+                _ => remove_path(&entry.path())?,
+            }
+        }
+        blobs.sort_unstable_by_key(|(modified, ..)| *modified);
+
+        let mut state = CacheState::default();
+        for (_, hash, size) in blobs {
+            state.touch(hash, size);
+        }
+        let evicted = state.evict(max_size);
+        ARTIFACT_CACHE_METRICS.size.set(state.total_size);
+        tracing::info!(
+            "Initialized artifact cache at {version_dir:?} with {} blobs, {} bytes",
+            state.blobs.len(),
+            state.total_size
+        );
+
+        let mut cache = Self {
+            blobs_dir,
+            index_dir,
+            max_size,
+            state: Mutex::new(state),
+            _lock_file: lock_file,
+        };
+        for blob in evicted {
+            remove_path(&cache.blob_path(blob.content_hash))?;
+            ARTIFACT_CACHE_METRICS.evicted.inc();
+        }
+        cache.load_index()?;
+        Ok(cache)
+    }
+
+    /// Loads index entries, removing entries of blobs which are not cached and leftovers
+    /// of interrupted writes.
+    fn load_index(&mut self) -> anyhow::Result<()> {
+        let state = self.state.get_mut().unwrap();
+        for entry in std::fs::read_dir(&self.index_dir)? {
+            let entry = entry?;
+            let index_key = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.parse::<H256>().ok());
+            let content_hash = match index_key {
+                Some(_) if entry.metadata()?.is_file() => {
+                    let contents = std::fs::read(entry.path())?;
+                    bincode::deserialize::<IndexEntry>(&contents)
+                        .ok()
+                        .map(|entry| entry.content_hash)
+                }
+                _ => None,
+            };
+            match (index_key, content_hash) {
+                (Some(index_key), Some(content_hash)) if state.contains(content_hash) => {
+                    state.set_index_entry(index_key, content_hash);
+                }
+                _ => remove_path(&entry.path())?,
+            }
+        }
+        Ok(())
+    }
+
+    fn blob_path(&self, hash: H256) -> PathBuf {
+        self.blobs_dir.join(format!("{hash:x}"))
+    }
+
+    fn index_key(bucket: Bucket, key: &str) -> H256 {
+        H256(keccak256(format!("{bucket}/{key}").as_bytes()))
+    }
+
+    fn index_path(&self, index_key: H256) -> PathBuf {
+        self.index_dir.join(format!("{index_key:x}"))
+    }
+
+    /// Removes an evicted blob and index entries referencing it.
+    async fn remove_evicted(&self, blob: EvictedBlob) -> anyhow::Result<()> {
+        for index_key in blob.index_keys {
+            remove_if_exists(&self.index_path(index_key)).await?;
+        }
+        remove_if_exists(&self.blob_path(blob.content_hash)).await?;
+        ARTIFACT_CACHE_METRICS.evicted.inc();
+        Ok(())
+    }
+
+    /// Returns the cached object together with the object store latency observed for it.
+    async fn get(&self, bucket: Bucket, key: &str) -> anyhow::Result<Option<(Vec<u8>, Duration)>> {
+        let index_key = Self::index_key(bucket, key);
+        let index_path = self.index_path(index_key);
+        let Some(entry) = read_if_exists(&index_path).await? else {
+            return Ok(None);
+        };
+        let entry: IndexEntry =
+            bincode::deserialize(&entry).context("failed deserializing index entry")?;
+        let blob_path = self.blob_path(entry.content_hash);
+        let blob = match read_if_exists(&blob_path).await? {
+            Some(blob) => {
+                let (blob, hash) = hash_content(blob).await;
+                if hash == entry.content_hash {
+                    Some(blob)
+                } else {
+                    tracing::warn!("Cached blob {blob_path:?} is corrupted, removing it");
+                    remove_if_exists(&blob_path).await?;
+                    None
+                }
+            }
+            None => None,
+        };
+
+        let Some(blob) = blob else {
+            // The blob was evicted or corrupted; index entries referencing it are stale.
+            let index_keys = self.state.lock().unwrap().forget(entry.content_hash);
+            ARTIFACT_CACHE_METRICS.invalidated.inc();
+            for index_key in index_keys {
+                remove_if_exists(&self.index_path(index_key)).await?;
+            }
+            remove_if_exists(&index_path).await?;
+            return Ok(None);
+        };
+        let mut state = self.state.lock().unwrap();
+        state.touch(entry.content_hash, blob.len() as u64);
+        ARTIFACT_CACHE_METRICS.size.set(state.total_size);
+        Ok(Some((blob, entry.remote_latency)))
+    }
+
+    async fn insert(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: &[u8],
+        content_hash: H256,
+        remote_latency: Duration,
+    ) -> anyhow::Result<()> {
+        let size = value.len() as u64;
+        if size > self.max_size {
+            return Ok(());
+        }
+
+        let blob_path = self.blob_path(content_hash);
+        let is_cached = self.state.lock().unwrap().contains(content_hash);
+        if !is_cached || !tokio::fs::try_exists(&blob_path).await? {
+            write_atomically(&blob_path, value).await?;
+        }
+        let entry = IndexEntry {
+            content_hash,
+            remote_latency,
+        };
+        let entry = bincode::serialize(&entry).context("failed serializing index entry")?;
+        let index_key = Self::index_key(bucket, key);
+        write_atomically(&self.index_path(index_key), &entry).await?;
+
+        let evicted = {
+            let mut state = self.state.lock().unwrap();
+            state.touch(content_hash, size);
+            state.set_index_entry(index_key, content_hash);
+            let evicted = state.evict(self.max_size);
+            ARTIFACT_CACHE_METRICS.size.set(state.total_size);
+            evicted
+        };
+        for blob in evicted {
+            self.remove_evicted(blob).await?;
+        }
+        Ok(())
+    }
+
+    async fn remove(&self, bucket: Bucket, key: &str) -> anyhow::Result<()> {
+        let index_key = Self::index_key(bucket, key);
+        self.state.lock().unwrap().remove_index_entry(index_key);
+        remove_if_exists(&self.index_path(index_key)).await?;
+        Ok(())
+    }
+}
+
+async fn hash_content(value: Vec<u8>) -> (Vec<u8>, H256) {
+    tokio::task::spawn_blocking(move || {
+        let hash = H256(keccak256(&value));
+        (value, hash)
+    })
+    .await
+    .expect("panicked while hashing content")
+}
+
+async fn read_if_exists(path: &Path) -> io::Result<Option<Vec<u8>>> {
+    match tokio::fs::read(path).await {
+        Ok(contents) => Ok(Some(contents)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+async fn remove_if_exists(path: &Path) -> io::Result<()> {
+    match tokio::fs::remove_file(path).await {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+/// Writes to a temporary file first, so that concurrent reads never observe partially written files.
+async fn write_atomically(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let tmp_path = path.with_extension(format!("tmp{}", rand::random::<u64>()));
+    tokio::fs::write(&tmp_path, contents).await?;
+    tokio::fs::rename(&tmp_path, path).await
+}
+
+/// Checks whether the name of a cache directory entry is a protocol version, i.e. a subdirectory
+/// with artifacts of some protocol version.
+fn is_version_dir_name(name: &str) -> bool {
+    let parts: Vec<_> = name.split('.').collect();
+    parts.len() == 3
+        && parts
+            .iter()
+            .all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()))
+}
+
+fn remove_path(path: &Path) -> io::Result<()> {
+    if path.is_dir() {
+        std::fs::remove_dir_all(path)
+    } else {
+        std::fs::remove_file(path)
+    }
+}
+
+/// [`ObjectStore`] serving artifacts read by witness generators from an [`ArtifactCache`].
+/// Cache errors are logged and never fail object store operations.
+#[derive(Debug)]
+pub struct CachedObjectStore {
+    inner: Arc<dyn ObjectStore>,
+    cache: Arc<ArtifactCache>,
+}
+
+impl CachedObjectStore {
+    pub fn new(inner: Arc<dyn ObjectStore>, cache: Arc<ArtifactCache>) -> Self {
+        Self { inner, cache }
+    }
+
+    async fn cache_object(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: &[u8],
+        content_hash: H256,
+        remote_latency: Duration,
+    ) {
+        if let Err(err) = self
+            .cache
+            .insert(bucket, key, value, content_hash, remote_latency)
+            .await
+        {
+            tracing::warn!("failed caching object {bucket}/{key}: {err:#}");
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectStore for CachedObjectStore {
+    #[tracing::instrument(name = "CachedObjectStore::get_raw", skip(self))]
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        if !CACHED_BUCKETS.contains(&bucket) {
+            return self.inner.get_raw(bucket, key).await;
+        }
+
+        let started_at = Instant::now();
+        match self.cache.get(bucket, key).await {
+            Ok(Some((object, remote_latency))) => {
+                tracing::trace!("obtained object from artifact cache");
+                ARTIFACT_CACHE_METRICS.hits[&bucket.to_string()].inc();
+                ARTIFACT_CACHE_METRICS.time_saved[&bucket.to_string()]
+                    .observe(remote_latency.saturating_sub(started_at.elapsed()));
+                return Ok(object);
+            }
+            Ok(None) => {}
+            Err(err) => {
+                tracing::warn!("failed reading object {bucket}/{key} from artifact cache: {err:#}");
+            }
+        }
+
+        ARTIFACT_CACHE_METRICS.misses[&bucket.to_string()].inc();
+        let started_at = Instant::now();
+        let object = self.inner.get_raw(bucket, key).await?;
+        let remote_latency = started_at.elapsed();
+        let (object, content_hash) = hash_content(object).await;
+        self.cache_object(bucket, key, &object, content_hash, remote_latency)
+            .await;
+        Ok(object)
+    }
+
+    #[tracing::instrument(
+        name = "CachedObjectStore::put_raw",
+        skip(self, value),
+        fields(value.len = value.len())
+    )]
+    async fn put_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        if !CACHED_BUCKETS.contains(&bucket) {
+            return self.inner.put_raw(bucket, key, value).await;
+        }
+
+        let (value, content_hash) = hash_content(value).await;
+        let started_at = Instant::now();
+        self.inner.put_raw(bucket, key, value.clone()).await?;
+        // Upload latency is used as an estimate of the latency of fetching the object later.
+        let remote_latency = started_at.elapsed();
+        self.cache_object(bucket, key, &value, content_hash, remote_latency)
+            .await;
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "CachedObjectStore::remove_raw", skip(self))]
+    async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
+        self.inner.remove_raw(bucket, key).await?;
+        if CACHED_BUCKETS.contains(&bucket) {
+            if let Err(err) = self.cache.remove(bucket, key).await {
+                tracing::warn!(
+                    "failed removing object {bucket}/{key} from artifact cache: {err:#}"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        self.inner.storage_prefix_raw(bucket)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+    use zksync_object_store::MockObjectStore;
+    use zksync_prover_fri_types::PROVER_PROTOCOL_SEMANTIC_VERSION;
+    use zksync_types::protocol_version::VersionPatch;
+
+    use super::*;
+
+    async fn create_store(
+        inner: &Arc<dyn ObjectStore>,
+        path: &Path,
+        max_size: u64,
+        protocol_version: ProtocolSemanticVersion,
+    ) -> CachedObjectStore {
+        let cache = ArtifactCache::new(path.to_owned(), max_size, protocol_version)
+            .await
+            .unwrap();
+        CachedObjectStore::new(inner.clone(), Arc::new(cache))
+    }
+
+    #[tokio::test]
+    async fn cache_directory_is_locked() {
+        let dir = TempDir::new().unwrap();
+        let cache = ArtifactCache::new(
+            dir.path().to_owned(),
+            1 << 20,
+            PROVER_PROTOCOL_SEMANTIC_VERSION,
+        )
+        .await
+        .unwrap();
+        let err = ArtifactCache::new(
+            dir.path().to_owned(),
+            1 << 20,
+            PROVER_PROTOCOL_SEMANTIC_VERSION,
+        )
+        .await
+        .unwrap_err();
+        assert!(
+            format!("{err:#}").contains("used by another process"),
+            "{err:#}"
+        );
+
+        drop(cache);
+        ArtifactCache::new(
+            dir.path().to_owned(),
+            1 << 20,
+            PROVER_PROTOCOL_SEMANTIC_VERSION,
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn caching_basics() {
+        let dir = TempDir::new().unwrap();
+        let inner = MockObjectStore::arc();
+        inner
+            .put_raw(Bucket::ProofsFri, "proof", vec![1, 2, 3])
+            .await
+            .unwrap();
+        inner
+            .put_raw(Bucket::ProverJobsFri, "circuit", vec![3, 2, 1])
+            .await
+            .unwrap();
+        let store = create_store(
+            &inner,
+            dir.path(),
+            1 << 20,
+            PROVER_PROTOCOL_SEMANTIC_VERSION,
+        )
+        .await;
+
+        let object = store.get_raw(Bucket::ProofsFri, "proof").await.unwrap();
+        assert_eq!(object, [1, 2, 3]);
+        let object = store
+            .get_raw(Bucket::ProverJobsFri, "circuit")
+            .await
+            .unwrap();
+        assert_eq!(object, [3, 2, 1]);
+
+        inner.remove_raw(Bucket::ProofsFri, "proof").await.unwrap();
+        inner
+            .remove_raw(Bucket::ProverJobsFri, "circuit")
+            .await
+            .unwrap();
+        // Only the object from the cached bucket is served from the cache.
+        let object = store.get_raw(Bucket::ProofsFri, "proof").await.unwrap();
+        assert_eq!(object, [1, 2, 3]);
+        let err = store
+            .get_raw(Bucket::ProverJobsFri, "circuit")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ObjectStoreError::KeyNotFound(_)), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn put_objects_are_cached_and_removed() {
+        let dir = TempDir::new().unwrap();
+        let inner = MockObjectStore::arc();
+        let store = create_store(
+            &inner,
+            dir.path(),
+            1 << 20,
+            PROVER_PROTOCOL_SEMANTIC_VERSION,
+        )
+        .await;
+
+        store
+            .put_raw(
+                Bucket::NodeAggregationWitnessJobsFri,
+                "aggregations",
+                vec![1],
+            )
+            .await
+            .unwrap();
+        store
+            .put_raw(
+                Bucket::NodeAggregationWitnessJobsFri,
+                "aggregations",
+                vec![2],
+            )
+            .await
+            .unwrap();
+        inner
+            .remove_raw(Bucket::NodeAggregationWitnessJobsFri, "aggregations")
+            .await
+            .unwrap();
+        let object = store
+            .get_raw(Bucket::NodeAggregationWitnessJobsFri, "aggregations")
+            .await
+            .unwrap();
+        assert_eq!(object, [2]);
+
+        inner
+            .put_raw(
+                Bucket::NodeAggregationWitnessJobsFri,
+                "aggregations",
+                vec![2],
+            )
+            .await
+            .unwrap();
+        store
+            .remove_raw(Bucket::NodeAggregationWitnessJobsFri, "aggregations")
+            .await
+            .unwrap();
+        let err = store
+            .get_raw(Bucket::NodeAggregationWitnessJobsFri, "aggregations")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ObjectStoreError::KeyNotFound(_)), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn least_recently_used_blobs_are_evicted() {
+        let dir = TempDir::new().unwrap();
+        let inner = MockObjectStore::arc();
+        let store = create_store(&inner, dir.path(), 10, PROVER_PROTOCOL_SEMANTIC_VERSION).await;
+
+        store
+            .put_raw(Bucket::ProofsFri, "first", vec![1; 4])
+            .await
+            .unwrap();
+        store
+            .put_raw(Bucket::ProofsFri, "second", vec![2; 4])
+            .await
+            .unwrap();
+        // Makes "second" the least recently used blob.
+        store.get_raw(Bucket::ProofsFri, "first").await.unwrap();
+        store
+            .put_raw(Bucket::ProofsFri, "third", vec![3; 4])
+            .await
+            .unwrap();
+        // Too large to be cached.
+        store
+            .put_raw(Bucket::ProofsFri, "fourth", vec![4; 11])
+            .await
+            .unwrap();
+        assert_eq!(store.cache.state.lock().unwrap().total_size, 8);
+
+        for key in ["first", "second", "third", "fourth"] {
+            inner.remove_raw(Bucket::ProofsFri, key).await.unwrap();
+        }
+        store.get_raw(Bucket::ProofsFri, "first").await.unwrap();
+        store.get_raw(Bucket::ProofsFri, "third").await.unwrap();
+        store
+            .get_raw(Bucket::ProofsFri, "second")
+            .await
+            .unwrap_err();
+        store
+            .get_raw(Bucket::ProofsFri, "fourth")
+            .await
+            .unwrap_err();
+    }
+
+    fn dir_entries(path: &Path) -> Vec<String> {
+        let mut names: Vec<_> = std::fs::read_dir(path)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[tokio::test]
+    async fn filling_cache_past_its_limit() {
+        let dir = TempDir::new().unwrap();
+        let inner = MockObjectStore::arc();
+        let store = create_store(&inner, dir.path(), 10, PROVER_PROTOCOL_SEMANTIC_VERSION).await;
+        for i in 0..10_u8 {
+            store
+                .put_raw(Bucket::ProofsFri, &format!("proof{i}"), vec![i; 3])
+                .await
+                .unwrap();
+        }
+        // Two keys referencing the same blob.
+        store
+            .put_raw(Bucket::WitnessInput, "input", vec![9; 3])
+            .await
+            .unwrap();
+
+        let cache = &store.cache;
+        let cached_blobs: Vec<_> = [7_u8, 8, 9]
+            .map(|i| format!("{:x}", H256(keccak256(&[i; 3]))))
+            .into();
+        let mut expected_blobs = cached_blobs.clone();
+        expected_blobs.sort_unstable();
+        assert_eq!(dir_entries(&cache.blobs_dir), expected_blobs);
+        let mut expected_index: Vec<_> = [
+            (Bucket::ProofsFri, "proof7"),
+            (Bucket::ProofsFri, "proof8"),
+            (Bucket::ProofsFri, "proof9"),
+            (Bucket::WitnessInput, "input"),
+        ]
+        .map(|(bucket, key)| format!("{:x}", ArtifactCache::index_key(bucket, key)))
+        .into();
+        expected_index.sort_unstable();
+        assert_eq!(dir_entries(&cache.index_dir), expected_index);
+        {
+            let state = cache.state.lock().unwrap();
+            assert_eq!(state.total_size, 9);
+            assert_eq!(state.index.len(), 4);
+        }
+
+        // Leftovers of interrupted writes and index entries of missing blobs are removed on restart.
+        let index_dir = cache.index_dir.clone();
+        let blobs_dir = cache.blobs_dir.clone();
+        std::fs::write(index_dir.join("0123.tmp42"), [0]).unwrap();
+        std::fs::write(blobs_dir.join("0123.tmp42"), [0]).unwrap();
+        std::fs::remove_file(blobs_dir.join(&cached_blobs[0])).unwrap();
+        drop(store);
+        let store = create_store(&inner, dir.path(), 10, PROVER_PROTOCOL_SEMANTIC_VERSION).await;
+        let mut expected_blobs = cached_blobs[1..].to_vec();
+        expected_blobs.sort_unstable();
+        assert_eq!(dir_entries(&blobs_dir), expected_blobs);
+        let mut expected_index: Vec<_> = [
+            (Bucket::ProofsFri, "proof8"),
+            (Bucket::ProofsFri, "proof9"),
+            (Bucket::WitnessInput, "input"),
+        ]
+        .map(|(bucket, key)| format!("{:x}", ArtifactCache::index_key(bucket, key)))
+        .into();
+        expected_index.sort_unstable();
+        assert_eq!(dir_entries(&index_dir), expected_index);
+
+        for i in 0..10_u8 {
+            inner
+                .remove_raw(Bucket::ProofsFri, &format!("proof{i}"))
+                .await
+                .unwrap();
+        }
+        let object = store.get_raw(Bucket::ProofsFri, "proof8").await.unwrap();
+        assert_eq!(object, [8; 3]);
+        store
+            .get_raw(Bucket::ProofsFri, "proof7")
+            .await
+            .unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn corrupted_blobs_are_fetched_again() {
+        let dir = TempDir::new().unwrap();
+        let inner = MockObjectStore::arc();
+        let store = create_store(
+            &inner,
+            dir.path(),
+            1 << 20,
+            PROVER_PROTOCOL_SEMANTIC_VERSION,
+        )
+        .await;
+
+        store
+            .put_raw(Bucket::WitnessInput, "input", vec![1, 2, 3])
+            .await
+            .unwrap();
+        let content_hash = H256(keccak256(&[1, 2, 3]));
+        std::fs::write(store.cache.blob_path(content_hash), [0]).unwrap();
+
+        let object = store.get_raw(Bucket::WitnessInput, "input").await.unwrap();
+        assert_eq!(object, [1, 2, 3]);
+        let cached_blob = std::fs::read(store.cache.blob_path(content_hash)).unwrap();
+        assert_eq!(cached_blob, [1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn cache_is_invalidated_across_protocol_versions() {
+        let dir = TempDir::new().unwrap();
+        let inner = MockObjectStore::arc();
+        let version = PROVER_PROTOCOL_SEMANTIC_VERSION;
+        // Unrelated entries of the cache directory must be preserved.
+        std::fs::write(dir.path().join("notes.txt"), "keep me").unwrap();
+        std::fs::create_dir(dir.path().join("other")).unwrap();
+        let store = create_store(&inner, dir.path(), 1 << 20, version).await;
+        store
+            .put_raw(Bucket::SchedulerWitnessJobsFri, "witness", vec![1, 2, 3])
+            .await
+            .unwrap();
+        inner
+            .remove_raw(Bucket::SchedulerWitnessJobsFri, "witness")
+            .await
+            .unwrap();
+
+        // Cache contents survive restarts with the same protocol version.
+        let store = create_store(&inner, dir.path(), 1 << 20, version).await;
+        let object = store
+            .get_raw(Bucket::SchedulerWitnessJobsFri, "witness")
+            .await
+            .unwrap();
+        assert_eq!(object, [1, 2, 3]);
+
+        let next_version =
+            ProtocolSemanticVersion::new(version.minor, VersionPatch(version.patch.0 + 1));
+        let store = create_store(&inner, dir.path(), 1 << 20, next_version).await;
+        store
+            .get_raw(Bucket::SchedulerWitnessJobsFri, "witness")
+            .await
+            .unwrap_err();
+        assert!(!dir.path().join(version.to_string()).exists());
+        assert!(dir.path().join("notes.txt").exists());
+        assert!(dir.path().join("other").exists());
+    }
+}
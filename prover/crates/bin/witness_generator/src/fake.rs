@@ -0,0 +1,678 @@
+//! Fake proof mode. Rounds pick and complete jobs through the same database states as real
+//! witness generation, but exchange [`FakeArtifact`]s instead of witnesses, so neither witness
+//! inputs nor setup keys are needed.
+
+use std::{marker::PhantomData, sync::Arc, time::Instant};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use circuit_definitions::{
+    circuit_definitions::recursion_layer::ZkSyncRecursionLayerStorageType,
+    zkevm_circuits::scheduler::aux::BaseLayerCircuitType,
+};
+use tokio::task::JoinHandle;
+use zksync_config::configs::FriWitnessGeneratorConfig;
+use zksync_object_store::ObjectStore;
+use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
+use zksync_prover_fri_types::{
+    fake::{FakeArtifact, FakeArtifactKind},
+    keys::{AggregationsKey, ClosedFormInputKey, FriCircuitKey},
+    AuxOutputWitnessWrapper, CircuitWrapper, FriProofWrapper,
+};
+use zksync_prover_fri_utils::get_recursive_layer_circuit_id_for_base_layer;
+use zksync_queued_job_processor::JobProcessor;
+use zksync_types::{
+    basic_fri_types::AggregationRound, protocol_version::ProtocolSemanticVersion, L1BatchNumber,
+};
+
+use crate::{
+    rounds::{
+        create_aggregation_jobs, BasicCircuits, JobManager, LeafAggregation, NodeAggregation,
+        RecursionTip, Scheduler,
+    },
+    utils::{AggregationWrapper, ClosedFormInputWrapper, SchedulerPartialInputWrapper},
+};
+
+#[async_trait]
+pub trait FakeJobManager: JobManager {
+    /// Data necessary to save the job outcome to database.
+    type FakeArtifacts: Send + 'static;
+
+    /// Checks fake inputs of the job and stores fake outputs in place of the real ones.
+    async fn process_fake_job(
+        metadata: Self::Metadata,
+        object_store: Arc<dyn ObjectStore>,
+    ) -> anyhow::Result<Self::FakeArtifacts>;
+
+    async fn save_fake_to_database(
+        connection_pool: &ConnectionPool<Prover>,
+        job_id: u32,
+        started_at: Instant,
+        artifacts: Self::FakeArtifacts,
+    ) -> anyhow::Result<()>;
+}
+
+/// Witness generator for a single round in fake proof mode.
+#[derive(Debug)]
+pub struct FakeWitnessGenerator<R> {
+    config: FriWitnessGeneratorConfig,
+    object_store: Arc<dyn ObjectStore>,
+    connection_pool: ConnectionPool<Prover>,
+    protocol_version: ProtocolSemanticVersion,
+    _round: PhantomData<R>,
+}
+
+impl<R: FakeJobManager> FakeWitnessGenerator<R> {
+    pub fn new(
+        config: FriWitnessGeneratorConfig,
+        object_store: Arc<dyn ObjectStore>,
+        connection_pool: ConnectionPool<Prover>,
+        protocol_version: ProtocolSemanticVersion,
+    ) -> Self {
+        Self {
+            config,
+            object_store,
+            connection_pool,
+            protocol_version,
+            _round: PhantomData,
+        }
+    }
+}
+
+#[async_trait]
+impl<R> JobProcessor for FakeWitnessGenerator<R>
+where
+    R: FakeJobManager + Send + Sync,
+{
+    type Job = R::Metadata;
+    type JobId = u32;
+    type JobArtifacts = R::FakeArtifacts;
+
+    const SERVICE_NAME: &'static str = R::SERVICE_NAME;
+
+    async fn get_next_job(&self) -> anyhow::Result<Option<(Self::JobId, Self::Job)>> {
+        let job = R::get_metadata(self.connection_pool.clone(), self.protocol_version)
+            .await
+            .context("get_metadata()")?;
+        if let Some((id, _)) = &job {
+            tracing::info!("Processing fake {:?} job {:?}", R::ROUND, id);
+        }
+        Ok(job)
+    }
+
+    async fn save_failure(&self, job_id: Self::JobId, _started_at: Instant, error: String) {
+        self.connection_pool
+            .connection()
+            .await
+            .unwrap()
+            .fri_witness_generator_dal()
+            .mark_witness_job_failed(&error, job_id, R::ROUND)
+            .await;
+    }
+
+    async fn process_job(
+        &self,
+        _job_id: &Self::JobId,
+        job: Self::Job,
+        _started_at: Instant,
+    ) -> JoinHandle<anyhow::Result<Self::JobArtifacts>> {
+        let object_store = self.object_store.clone();
+        tokio::spawn(async move { R::process_fake_job(job, object_store).await })
+    }
+
+    async fn save_result(
+        &self,
+        job_id: Self::JobId,
+        started_at: Instant,
+        artifacts: Self::JobArtifacts,
+    ) -> anyhow::Result<()> {
+        R::save_fake_to_database(&self.connection_pool, job_id, started_at, artifacts).await?;
+        tracing::info!("Saved fake {:?} artifacts for job {:?}", R::ROUND, job_id);
+        Ok(())
+    }
+
+    fn max_attempts(&self) -> u32 {
+        self.config.max_attempts
+    }
+
+    async fn get_job_attempts(&self, job_id: &Self::JobId) -> anyhow::Result<u32> {
+        let mut prover_storage = self.connection_pool.connection().await.context(format!(
+            "failed to acquire DB connection for {:?}",
+            R::ROUND
+        ))?;
+        prover_storage
+            .fri_witness_generator_dal()
+            .get_witness_job_attempts(*job_id, R::ROUND)
+            .await
+            .map(|attempts| attempts.unwrap_or(0))
+            .context(format!("failed to get job attempts for {:?}", R::ROUND))
+    }
+}
+
+fn fake_artifact(
+    kind: FakeArtifactKind,
+    l1_batch_number: L1BatchNumber,
+    aggregation_round: AggregationRound,
+    circuit_id: u8,
+    depth: u16,
+) -> FakeArtifact {
+    FakeArtifact {
+        kind,
+        l1_batch_number,
+        aggregation_round,
+        circuit_id,
+        depth,
+    }
+}
+
+async fn check_fake_proofs(
+    object_store: &dyn ObjectStore,
+    l1_batch_number: L1BatchNumber,
+    prover_job_ids: impl IntoIterator<Item = u32>,
+) -> anyhow::Result<()> {
+    for job_id in prover_job_ids {
+        FakeArtifact::get::<FriProofWrapper>(
+            object_store,
+            job_id,
+            FakeArtifactKind::Proof,
+            l1_batch_number,
+        )
+        .await
+        .with_context(|| format!("failed to load fake proof of prover job {job_id}"))?;
+    }
+    Ok(())
+}
+
+/// Stores a fake circuit for the single prover job produced by an aggregation round.
+async fn put_fake_circuit(
+    object_store: &dyn ObjectStore,
+    l1_batch_number: L1BatchNumber,
+    aggregation_round: AggregationRound,
+    circuit_id: u8,
+    depth: u16,
+) -> anyhow::Result<String> {
+    let key = FriCircuitKey {
+        block_number: l1_batch_number,
+        sequence_number: 0,
+        circuit_id,
+        aggregation_round,
+        depth,
+    };
+    fake_artifact(
+        FakeArtifactKind::Circuit,
+        l1_batch_number,
+        aggregation_round,
+        circuit_id,
+        depth,
+    )
+    .put::<CircuitWrapper>(object_store, key)
+    .await
+    .context("failed to save fake circuit")
+}
+
+#[derive(Debug)]
+pub struct FakeBasicCircuitArtifacts {
+    l1_batch_number: L1BatchNumber,
+    circuit_urls: Vec<(u8, String)>,
+    queue_urls: Vec<(u8, String, usize)>,
+    scheduler_partial_input_url: String,
+}
+
+/// Every base layer circuit type gets a single circuit, so that all leaf and node aggregations
+/// of a batch are exercised.
+#[async_trait]
+impl FakeJobManager for BasicCircuits {
+    type FakeArtifacts = FakeBasicCircuitArtifacts;
+
+    async fn process_fake_job(
+        l1_batch_number: L1BatchNumber,
+        object_store: Arc<dyn ObjectStore>,
+    ) -> anyhow::Result<FakeBasicCircuitArtifacts> {
+        let round = AggregationRound::BasicCircuits;
+        let mut circuit_urls = vec![];
+        let mut queue_urls = vec![];
+        for (sequence_number, circuit_id) in BaseLayerCircuitType::as_iter_u8().enumerate() {
+            let key = FriCircuitKey {
+                block_number: l1_batch_number,
+                sequence_number,
+                circuit_id,
+                aggregation_round: round,
+                depth: 0,
+            };
+            let circuit_url = fake_artifact(
+                FakeArtifactKind::Circuit,
+                l1_batch_number,
+                round,
+                circuit_id,
+                0,
+            )
+            .put::<CircuitWrapper>(&*object_store, key)
+            .await
+            .context("failed to save fake circuit")?;
+            circuit_urls.push((circuit_id, circuit_url));
+
+            let key = ClosedFormInputKey {
+                block_number: l1_batch_number,
+                circuit_id,
+            };
+            let queue_url = fake_artifact(
+                FakeArtifactKind::ClosedFormInputs,
+                l1_batch_number,
+                round,
+                circuit_id,
+                0,
+            )
+            .put::<ClosedFormInputWrapper>(&*object_store, key)
+            .await
+            .context("failed to save fake closed form inputs")?;
+            queue_urls.push((circuit_id, queue_url, 1));
+        }
+
+        fake_artifact(
+            FakeArtifactKind::AuxOutputWitness,
+            l1_batch_number,
+            round,
+            0,
+            0,
+        )
+        .put::<AuxOutputWitnessWrapper>(&*object_store, l1_batch_number)
+        .await
+        .context("failed to save fake aux output witness")?;
+        let scheduler_partial_input_url = fake_artifact(
+            FakeArtifactKind::SchedulerPartialInput,
+            l1_batch_number,
+            round,
+            0,
+            0,
+        )
+        .put::<SchedulerPartialInputWrapper>(&*object_store, l1_batch_number)
+        .await
+        .context("failed to save fake scheduler partial input")?;
+
+        Ok(FakeBasicCircuitArtifacts {
+            l1_batch_number,
+            circuit_urls,
+            queue_urls,
+            scheduler_partial_input_url,
+        })
+    }
+
+    async fn save_fake_to_database(
+        connection_pool: &ConnectionPool<Prover>,
+        _job_id: u32,
+        started_at: Instant,
+        artifacts: FakeBasicCircuitArtifacts,
+    ) -> anyhow::Result<()> {
+        let l1_batch_number = artifacts.l1_batch_number;
+        let mut connection = connection_pool.connection().await?;
+        let mut transaction = connection.start_transaction().await?;
+        let protocol_version_id = transaction
+            .fri_basic_witness_generator_dal()
+            .protocol_version_for_l1_batch(l1_batch_number)
+            .await;
+        transaction
+            .fri_prover_jobs_dal()
+            .insert_prover_jobs(
+                l1_batch_number,
+                artifacts.circuit_urls,
+                AggregationRound::BasicCircuits,
+                0,
+                protocol_version_id,
+            )
+            .await;
+        create_aggregation_jobs(
+            &mut transaction,
+            l1_batch_number,
+            &artifacts.queue_urls,
+            &artifacts.scheduler_partial_input_url,
+            get_recursive_layer_circuit_id_for_base_layer,
+            protocol_version_id,
+        )
+        .await?;
+        transaction
+            .fri_basic_witness_generator_dal()
+            .mark_witness_job_as_successful(l1_batch_number, started_at.elapsed())
+            .await;
+        transaction.commit().await?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct FakeAggregationArtifacts {
+    l1_batch_number: L1BatchNumber,
+    circuit_id: u8,
+    depth: u16,
+    circuit_url: String,
+    /// Inputs of the next aggregation, if any.
+    aggregation_url: Option<String>,
+}
+
+/// Each leaf aggregation produces a single circuit, so node aggregation needs a single round.
+#[async_trait]
+impl FakeJobManager for LeafAggregation {
+    type FakeArtifacts = FakeAggregationArtifacts;
+
+    async fn process_fake_job(
+        metadata: Self::Metadata,
+        object_store: Arc<dyn ObjectStore>,
+    ) -> anyhow::Result<FakeAggregationArtifacts> {
+        let l1_batch_number = metadata.block_number;
+        let round = AggregationRound::LeafAggregation;
+        let key = ClosedFormInputKey {
+            block_number: l1_batch_number,
+            circuit_id: metadata.circuit_id,
+        };
+        FakeArtifact::get::<ClosedFormInputWrapper>(
+            &*object_store,
+            key,
+            FakeArtifactKind::ClosedFormInputs,
+            l1_batch_number,
+        )
+        .await
+        .context("failed to load fake closed form inputs")?;
+        check_fake_proofs(
+            &*object_store,
+            l1_batch_number,
+            metadata.prover_job_ids_for_proofs,
+        )
+        .await?;
+
+        let circuit_id = get_recursive_layer_circuit_id_for_base_layer(metadata.circuit_id);
+        let circuit_url =
+            put_fake_circuit(&*object_store, l1_batch_number, round, circuit_id, 0).await?;
+        let key = AggregationsKey {
+            block_number: l1_batch_number,
+            circuit_id,
+            depth: 0,
+        };
+        let aggregation_url = fake_artifact(
+            FakeArtifactKind::Aggregations,
+            l1_batch_number,
+            round,
+            circuit_id,
+            0,
+        )
+        .put::<AggregationWrapper>(&*object_store, key)
+        .await
+        .context("failed to save fake aggregations")?;
+
+        Ok(FakeAggregationArtifacts {
+            l1_batch_number,
+            circuit_id,
+            depth: 0,
+            circuit_url,
+            aggregation_url: Some(aggregation_url),
+        })
+    }
+
+    async fn save_fake_to_database(
+        connection_pool: &ConnectionPool<Prover>,
+        job_id: u32,
+        started_at: Instant,
+        artifacts: FakeAggregationArtifacts,
+    ) -> anyhow::Result<()> {
+        let mut connection = connection_pool.connection().await?;
+        let mut transaction = connection.start_transaction().await?;
+        let protocol_version_id = transaction
+            .fri_basic_witness_generator_dal()
+            .protocol_version_for_l1_batch(artifacts.l1_batch_number)
+            .await;
+        transaction
+            .fri_prover_jobs_dal()
+            .insert_prover_jobs(
+                artifacts.l1_batch_number,
+                vec![(artifacts.circuit_id, artifacts.circuit_url)],
+                AggregationRound::LeafAggregation,
+                artifacts.depth,
+                protocol_version_id,
+            )
+            .await;
+        transaction
+            .fri_node_witness_generator_dal()
+            .update_node_aggregation_jobs_url(
+                artifacts.l1_batch_number,
+                artifacts.circuit_id,
+                1,
+                artifacts.depth,
+                artifacts
+                    .aggregation_url
+                    .context("leaf aggregation must produce aggregations")?,
+            )
+            .await;
+        transaction
+            .fri_leaf_witness_generator_dal()
+            .mark_leaf_aggregation_as_successful(job_id, started_at.elapsed())
+            .await;
+        transaction.commit().await?;
+        Ok(())
+    }
+}
+
+/// Node aggregation always aggregates a single proof, which becomes the final node proof.
+#[async_trait]
+impl FakeJobManager for NodeAggregation {
+    type FakeArtifacts = FakeAggregationArtifacts;
+
+    async fn process_fake_job(
+        metadata: Self::Metadata,
+        object_store: Arc<dyn ObjectStore>,
+    ) -> anyhow::Result<FakeAggregationArtifacts> {
+        let l1_batch_number = metadata.block_number;
+        let key = AggregationsKey {
+            block_number: l1_batch_number,
+            circuit_id: metadata.circuit_id,
+            depth: metadata.depth,
+        };
+        FakeArtifact::get::<AggregationWrapper>(
+            &*object_store,
+            key,
+            FakeArtifactKind::Aggregations,
+            l1_batch_number,
+        )
+        .await
+        .context("failed to load fake aggregations")?;
+        check_fake_proofs(
+            &*object_store,
+            l1_batch_number,
+            metadata.prover_job_ids_for_proofs,
+        )
+        .await?;
+
+        let circuit_url = put_fake_circuit(
+            &*object_store,
+            l1_batch_number,
+            AggregationRound::NodeAggregation,
+            metadata.circuit_id,
+            metadata.depth,
+        )
+        .await?;
+        Ok(FakeAggregationArtifacts {
+            l1_batch_number,
+            circuit_id: metadata.circuit_id,
+            depth: metadata.depth,
+            circuit_url,
+            aggregation_url: None,
+        })
+    }
+
+    async fn save_fake_to_database(
+        connection_pool: &ConnectionPool<Prover>,
+        job_id: u32,
+        started_at: Instant,
+        artifacts: FakeAggregationArtifacts,
+    ) -> anyhow::Result<()> {
+        let mut connection = connection_pool.connection().await?;
+        let mut transaction = connection.start_transaction().await?;
+        let protocol_version_id = transaction
+            .fri_basic_witness_generator_dal()
+            .protocol_version_for_l1_batch(artifacts.l1_batch_number)
+            .await;
+        transaction
+            .fri_prover_jobs_dal()
+            .insert_prover_job(
+                artifacts.l1_batch_number,
+                artifacts.circuit_id,
+                artifacts.depth,
+                0,
+                AggregationRound::NodeAggregation,
+                &artifacts.circuit_url,
+                true,
+                protocol_version_id,
+            )
+            .await;
+        transaction
+            .fri_node_witness_generator_dal()
+            .mark_node_aggregation_as_successful(job_id, started_at.elapsed())
+            .await;
+        transaction.commit().await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl FakeJobManager for RecursionTip {
+    type FakeArtifacts = FakeAggregationArtifacts;
+
+    async fn process_fake_job(
+        metadata: Self::Metadata,
+        object_store: Arc<dyn ObjectStore>,
+    ) -> anyhow::Result<FakeAggregationArtifacts> {
+        let l1_batch_number = metadata.l1_batch_number;
+        check_fake_proofs(
+            &*object_store,
+            l1_batch_number,
+            metadata
+                .final_node_proof_job_ids
+                .into_iter()
+                .map(|(_, job_id)| job_id),
+        )
+        .await?;
+
+        let circuit_id = ZkSyncRecursionLayerStorageType::RecursionTipCircuit as u8;
+        let circuit_url = put_fake_circuit(
+            &*object_store,
+            l1_batch_number,
+            AggregationRound::RecursionTip,
+            circuit_id,
+            0,
+        )
+        .await?;
+        Ok(FakeAggregationArtifacts {
+            l1_batch_number,
+            circuit_id,
+            depth: 0,
+            circuit_url,
+            aggregation_url: None,
+        })
+    }
+
+    async fn save_fake_to_database(
+        connection_pool: &ConnectionPool<Prover>,
+        _job_id: u32,
+        started_at: Instant,
+        artifacts: FakeAggregationArtifacts,
+    ) -> anyhow::Result<()> {
+        let mut connection = connection_pool.connection().await?;
+        let mut transaction = connection.start_transaction().await?;
+        let protocol_version_id = transaction
+            .fri_basic_witness_generator_dal()
+            .protocol_version_for_l1_batch(artifacts.l1_batch_number)
+            .await;
+        transaction
+            .fri_prover_jobs_dal()
+            .insert_prover_job(
+                artifacts.l1_batch_number,
+                artifacts.circuit_id,
+                0,
+                0,
+                AggregationRound::RecursionTip,
+                &artifacts.circuit_url,
+                false,
+                protocol_version_id,
+            )
+            .await;
+        transaction
+            .fri_recursion_tip_witness_generator_dal()
+            .mark_recursion_tip_job_as_successful(artifacts.l1_batch_number, started_at.elapsed())
+            .await;
+        transaction.commit().await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl FakeJobManager for Scheduler {
+    type FakeArtifacts = FakeAggregationArtifacts;
+
+    async fn process_fake_job(
+        metadata: Self::Metadata,
+        object_store: Arc<dyn ObjectStore>,
+    ) -> anyhow::Result<FakeAggregationArtifacts> {
+        let l1_batch_number = metadata.l1_batch_number;
+        FakeArtifact::get::<SchedulerPartialInputWrapper>(
+            &*object_store,
+            l1_batch_number,
+            FakeArtifactKind::SchedulerPartialInput,
+            l1_batch_number,
+        )
+        .await
+        .context("failed to load fake scheduler partial input")?;
+        check_fake_proofs(
+            &*object_store,
+            l1_batch_number,
+            [metadata.recursion_tip_job_id],
+        )
+        .await?;
+
+        let circuit_id = ZkSyncRecursionLayerStorageType::SchedulerCircuit as u8;
+        let circuit_url = put_fake_circuit(
+            &*object_store,
+            l1_batch_number,
+            AggregationRound::Scheduler,
+            circuit_id,
+            0,
+        )
+        .await?;
+        Ok(FakeAggregationArtifacts {
+            l1_batch_number,
+            circuit_id,
+            depth: 0,
+            circuit_url,
+            aggregation_url: None,
+        })
+    }
+
+    async fn save_fake_to_database(
+        connection_pool: &ConnectionPool<Prover>,
+        _job_id: u32,
+        started_at: Instant,
+        artifacts: FakeAggregationArtifacts,
+    ) -> anyhow::Result<()> {
+        let mut connection = connection_pool.connection().await?;
+        let mut transaction = connection.start_transaction().await?;
+        let protocol_version_id = transaction
+            .fri_basic_witness_generator_dal()
+            .protocol_version_for_l1_batch(artifacts.l1_batch_number)
+            .await;
+        transaction
+            .fri_prover_jobs_dal()
+            .insert_prover_job(
+                artifacts.l1_batch_number,
+                artifacts.circuit_id,
+                0,
+                0,
+                AggregationRound::Scheduler,
+                &artifacts.circuit_url,
+                false,
+                protocol_version_id,
+            )
+            .await;
+        transaction
+            .fri_scheduler_witness_generator_dal()
+            .mark_scheduler_job_as_successful(artifacts.l1_batch_number, started_at.elapsed())
+            .await;
+        transaction.commit().await?;
+        Ok(())
+    }
+}
@@ -1,7 +1,9 @@
 #![allow(incomplete_features)] // We have to use generic const exprs.
 #![feature(generic_const_exprs)]
 
+pub mod artifact_cache;
 pub mod artifacts;
+pub mod fake;
 pub mod metrics;
 pub mod precalculated_merkle_paths_provider;
 pub mod rounds;
@@ -1,17 +1,21 @@
 #![allow(incomplete_features)] // We have to use generic const exprs.
 #![feature(generic_const_exprs)]
 
-use std::time::{Duration, Instant};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use anyhow::{anyhow, Context as _};
-use futures::{channel::mpsc, executor::block_on, SinkExt, StreamExt};
+use futures::{channel::mpsc, executor::block_on, future::BoxFuture, SinkExt, StreamExt};
 #[cfg(not(target_env = "msvc"))]
 use jemallocator::Jemalloc;
 use structopt::StructOpt;
 use tokio::sync::watch;
+use zksync_config::configs::FriWitnessGeneratorConfig;
 use zksync_core_leftovers::temp_config_store::{load_database_secrets, load_general_config};
 use zksync_env_config::object_store::ProverObjectStoreConfig;
-use zksync_object_store::ObjectStoreFactory;
+use zksync_object_store::{ObjectStore, ObjectStoreFactory};
 use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
 use zksync_prover_fri_types::PROVER_PROTOCOL_SEMANTIC_VERSION;
 use zksync_prover_keystore::keystore::Keystore;
@@ -20,6 +24,8 @@ use zksync_task_management::ManagedTasks;
 use zksync_types::{basic_fri_types::AggregationRound, protocol_version::ProtocolSemanticVersion};
 use zksync_vlog::prometheus::PrometheusExporterConfig;
 use zksync_witness_generator::{
+    artifact_cache::{ArtifactCache, CachedObjectStore},
+    fake::{FakeJobManager, FakeWitnessGenerator},
     metrics::SERVER_METRICS,
     rounds::{
         BasicCircuits, LeafAggregation, NodeAggregation, RecursionTip, Scheduler, WitnessGenerator,
@@ -53,6 +59,18 @@ struct Opt {
     /// Path to the secrets file.
     #[structopt(long)]
     secrets_path: Option<std::path::PathBuf>,
+    /// Exchange placeholder artifacts instead of generating witnesses, so that the pipeline can
+    /// be run without witness inputs and setup keys. For testing only.
+    #[structopt(long = "fake_proofs")]
+    fake_proofs: bool,
+    /// Directory of the local artifact cache shared by all rounds run by this process. The directory
+    /// is locked, so each witness generator process needs its own one.
+    /// If not set, all artifacts are fetched from the object store.
+    #[structopt(long = "artifact_cache_path")]
+    artifact_cache_path: Option<std::path::PathBuf>,
+    /// Maximum size of the local artifact cache in megabytes.
+    #[structopt(long = "artifact_cache_size_mb", default_value = "10240")]
+    artifact_cache_size_mb: u64,
 }
 
 /// Checks if the configuration locally matches the one in the database.
@@ -90,6 +108,88 @@ async fn ensure_protocol_alignment(
     Ok(())
 }
 
+async fn create_object_store(
+    store_factory: &ObjectStoreFactory,
+    artifact_cache: Option<&Arc<ArtifactCache>>,
+) -> anyhow::Result<Arc<dyn ObjectStore>> {
+    let object_store = store_factory.create_store().await?;
+    Ok(match artifact_cache {
+        Some(cache) => Arc::new(CachedObjectStore::new(object_store, cache.clone())),
+        None => object_store,
+    })
+}
+
+fn run_fake_witness_generator(
+    round: AggregationRound,
+    config: FriWitnessGeneratorConfig,
+    object_store: Arc<dyn ObjectStore>,
+    connection_pool: ConnectionPool<Prover>,
+    protocol_version: ProtocolSemanticVersion,
+    stop_receiver: watch::Receiver<bool>,
+    batch_size: Option<usize>,
+) -> BoxFuture<'static, anyhow::Result<()>> {
+    fn run<R: FakeJobManager + Send + Sync + 'static>(
+        generator: FakeWitnessGenerator<R>,
+        stop_receiver: watch::Receiver<bool>,
+        batch_size: Option<usize>,
+    ) -> BoxFuture<'static, anyhow::Result<()>> {
+        generator.run(stop_receiver, batch_size)
+    }
+
+    match round {
+        AggregationRound::BasicCircuits => run(
+            FakeWitnessGenerator::<BasicCircuits>::new(
+                config,
+                object_store,
+                connection_pool,
+                protocol_version,
+            ),
+            stop_receiver,
+            batch_size,
+        ),
+        AggregationRound::LeafAggregation => run(
+            FakeWitnessGenerator::<LeafAggregation>::new(
+                config,
+                object_store,
+                connection_pool,
+                protocol_version,
+            ),
+            stop_receiver,
+            batch_size,
+        ),
+        AggregationRound::NodeAggregation => run(
+            FakeWitnessGenerator::<NodeAggregation>::new(
+                config,
+                object_store,
+                connection_pool,
+                protocol_version,
+            ),
+            stop_receiver,
+            batch_size,
+        ),
+        AggregationRound::RecursionTip => run(
+            FakeWitnessGenerator::<RecursionTip>::new(
+                config,
+                object_store,
+                connection_pool,
+                protocol_version,
+            ),
+            stop_receiver,
+            batch_size,
+        ),
+        AggregationRound::Scheduler => run(
+            FakeWitnessGenerator::<Scheduler>::new(
+                config,
+                object_store,
+                connection_pool,
+                protocol_version,
+            ),
+            stop_receiver,
+            batch_size,
+        ),
+    }
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let opt = Opt::from_args();
@@ -151,9 +251,24 @@ async fn main() -> anyhow::Result<()> {
 
     let protocol_version = PROVER_PROTOCOL_SEMANTIC_VERSION;
 
-    ensure_protocol_alignment(&connection_pool, protocol_version, &keystore)
-        .await
-        .unwrap_or_else(|err| panic!("Protocol alignment check failed: {:?}", err));
+    if opt.fake_proofs {
+        tracing::warn!("Running in fake proof mode, no witnesses will be generated");
+    } else {
+        ensure_protocol_alignment(&connection_pool, protocol_version, &keystore)
+            .await
+            .unwrap_or_else(|err| panic!("Protocol alignment check failed: {:?}", err));
+    }
+
+    let artifact_cache = match (&opt.artifact_cache_path, opt.fake_proofs) {
+        (Some(path), false) => {
+            let max_size = opt.artifact_cache_size_mb << 20;
+            let cache = ArtifactCache::new(path.clone(), max_size, protocol_version)
+                .await
+                .context("failed to initialize artifact cache")?;
+            Some(Arc::new(cache))
+        }
+        _ => None,
+    };
 
     let rounds = match (opt.round, opt.all_rounds) {
         (Some(round), false) => vec![round],
@@ -204,10 +319,22 @@ async fn main() -> anyhow::Result<()> {
         };
 
         let witness_generator_task = match round {
+            _ if opt.fake_proofs => {
+                let object_store = store_factory.create_store().await?;
+                run_fake_witness_generator(
+                    round,
+                    config.clone(),
+                    object_store,
+                    connection_pool.clone(),
+                    protocol_version,
+                    stop_receiver.clone(),
+                    opt.batch_size,
+                )
+            }
             AggregationRound::BasicCircuits => {
                 let generator = WitnessGenerator::<BasicCircuits>::new(
                     config.clone(),
-                    store_factory.create_store().await?,
+                    create_object_store(&store_factory, artifact_cache.as_ref()).await?,
                     public_blob_store,
                     connection_pool.clone(),
                     protocol_version,
@@ -218,7 +345,7 @@ async fn main() -> anyhow::Result<()> {
             AggregationRound::LeafAggregation => {
                 let generator = WitnessGenerator::<LeafAggregation>::new(
                     config.clone(),
-                    store_factory.create_store().await?,
+                    create_object_store(&store_factory, artifact_cache.as_ref()).await?,
                     public_blob_store,
                     connection_pool.clone(),
                     protocol_version,
@@ -229,7 +356,7 @@ async fn main() -> anyhow::Result<()> {
             AggregationRound::NodeAggregation => {
                 let generator = WitnessGenerator::<NodeAggregation>::new(
                     config.clone(),
-                    store_factory.create_store().await?,
+                    create_object_store(&store_factory, artifact_cache.as_ref()).await?,
                     public_blob_store,
                     connection_pool.clone(),
                     protocol_version,
@@ -240,7 +367,7 @@ async fn main() -> anyhow::Result<()> {
             AggregationRound::RecursionTip => {
                 let generator = WitnessGenerator::<RecursionTip>::new(
                     config.clone(),
-                    store_factory.create_store().await?,
+                    create_object_store(&store_factory, artifact_cache.as_ref()).await?,
                     public_blob_store,
                     connection_pool.clone(),
                     protocol_version,
@@ -251,7 +378,7 @@ async fn main() -> anyhow::Result<()> {
             AggregationRound::Scheduler => {
                 let generator = WitnessGenerator::<Scheduler>::new(
                     config.clone(),
-                    store_factory.create_store().await?,
+                    create_object_store(&store_factory, artifact_cache.as_ref()).await?,
                     public_blob_store,
                     connection_pool.clone(),
                     protocol_version,
@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use vise::{Buckets, Family, Gauge, Histogram, LabeledFamily, Metrics};
+use vise::{Buckets, Counter, Family, Gauge, Histogram, LabeledFamily, Metrics, Unit};
 use zksync_prover_fri_utils::metrics::StageLabel;
 
 #[derive(Debug, Metrics)]
@@ -20,6 +20,31 @@ pub(crate) struct WitnessGeneratorMetrics {
 pub(crate) static WITNESS_GENERATOR_METRICS: vise::Global<WitnessGeneratorMetrics> =
     vise::Global::new();
 
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "prover_fri_witness_generator_artifact_cache")]
+pub(crate) struct ArtifactCacheMetrics {
+    /// Number of objects served from the local cache.
+    #[metrics(labels = ["bucket"])]
+    pub hits: LabeledFamily<String, Counter>,
+    /// Number of objects fetched from the object store because they were not cached.
+    #[metrics(labels = ["bucket"])]
+    pub misses: LabeledFamily<String, Counter>,
+    /// Object store latency saved by serving an object from the local cache.
+    #[metrics(buckets = Buckets::LATENCIES, labels = ["bucket"])]
+    pub time_saved: LabeledFamily<String, Histogram<Duration>>,
+    /// Total size of cached blobs.
+    #[metrics(unit = Unit::Bytes)]
+    pub size: Gauge<u64>,
+    /// Number of blobs evicted to keep the cache within its size limit.
+    pub evicted: Counter,
+    /// Number of cache entries dropped because they were stale or corrupted,
+    /// or were cached for another protocol version.
+    pub invalidated: Counter,
+}
+
+#[vise::register]
+pub(crate) static ARTIFACT_CACHE_METRICS: vise::Global<ArtifactCacheMetrics> = vise::Global::new();
+
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "prover")]
 pub struct ServerMetrics {
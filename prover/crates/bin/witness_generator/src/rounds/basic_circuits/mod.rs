@@ -26,6 +26,8 @@ use crate::{
 mod artifacts;
 mod utils;
 
+pub(crate) use utils::create_aggregation_jobs;
+
 #[derive(Clone)]
 pub struct BasicCircuitArtifacts {
     pub(super) circuit_urls: Vec<(u8, String)>,
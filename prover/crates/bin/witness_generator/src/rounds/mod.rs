@@ -18,6 +18,7 @@ mod node_aggregation;
 mod recursion_tip;
 mod scheduler;
 
+pub(crate) use basic_circuits::create_aggregation_jobs;
 pub use basic_circuits::BasicCircuits;
 pub use leaf_aggregation::LeafAggregation;
 pub use node_aggregation::NodeAggregation;
@@ -0,0 +1,239 @@
+use std::{sync::Arc, time::Instant};
+
+use circuit_definitions::zkevm_circuits::scheduler::aux::BaseLayerCircuitType;
+use zksync_object_store::{MockObjectStore, ObjectStore};
+use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
+use zksync_prover_fri_types::{
+    fake::{FakeArtifact, FakeArtifactKind},
+    keys::FriCircuitKey,
+    CircuitWrapper, FriProofWrapper,
+};
+use zksync_types::{
+    basic_fri_types::AggregationRound,
+    protocol_version::{L1VerifierConfig, ProtocolSemanticVersion},
+    prover_dal::{ProofCompressionJobStatus, ProverJobStatus, WitnessJobStatus},
+    L1BatchNumber,
+};
+use zksync_witness_generator::{
+    fake::FakeJobManager,
+    rounds::{BasicCircuits, LeafAggregation, NodeAggregation, RecursionTip, Scheduler},
+};
+
+const BATCH: L1BatchNumber = L1BatchNumber(1);
+
+/// Runs a single job of the fake round `R`. Returns `false` if there is no job to pick.
+async fn run_fake_job<R: FakeJobManager>(
+    pool: &ConnectionPool<Prover>,
+    object_store: &Arc<dyn ObjectStore>,
+) -> bool {
+    let Some((job_id, metadata)) =
+        R::get_metadata(pool.clone(), ProtocolSemanticVersion::default())
+            .await
+            .unwrap()
+    else {
+        return false;
+    };
+    let artifacts = R::process_fake_job(metadata, object_store.clone())
+        .await
+        .unwrap();
+    R::save_fake_to_database(pool, job_id, Instant::now(), artifacts)
+        .await
+        .unwrap();
+    true
+}
+
+/// Proves all queued prover jobs the same way as the fake circuit prover and returns their
+/// number. Afterwards, moves witness jobs with all proofs ready to the queue, as the prover job
+/// monitor does.
+async fn prove_queued_jobs(pool: &ConnectionPool<Prover>, object_store: &dyn ObjectStore) -> usize {
+    let mut conn = pool.connection().await.unwrap();
+    let mut proven = 0;
+    while let Some(metadata) = conn
+        .fri_prover_jobs_dal()
+        .get_next_job(ProtocolSemanticVersion::default(), "test")
+        .await
+    {
+        let circuit = FakeArtifact::get::<CircuitWrapper>(
+            object_store,
+            FriCircuitKey::from(metadata),
+            FakeArtifactKind::Circuit,
+            metadata.block_number,
+        )
+        .await
+        .unwrap();
+        assert_eq!(circuit.aggregation_round, metadata.aggregation_round);
+        assert_eq!(circuit.circuit_id, metadata.circuit_id);
+        let proof = FakeArtifact {
+            kind: FakeArtifactKind::Proof,
+            ..circuit
+        };
+        let blob_url = proof
+            .put::<FriProofWrapper>(object_store, metadata.id)
+            .await
+            .unwrap();
+        conn.fri_prover_jobs_dal()
+            .save_proof(metadata.id, metadata.pick_time.elapsed(), &blob_url)
+            .await;
+        if metadata.is_scheduler_proof().unwrap() {
+            conn.fri_proof_compressor_dal()
+                .insert_proof_compression_job(
+                    metadata.block_number,
+                    &blob_url,
+                    ProtocolSemanticVersion::default(),
+                )
+                .await;
+        }
+        proven += 1;
+    }
+
+    conn.fri_leaf_witness_generator_dal()
+        .move_leaf_aggregation_jobs_from_waiting_to_queued()
+        .await;
+    conn.fri_node_witness_generator_dal()
+        .move_depth_zero_node_aggregation_jobs()
+        .await;
+    conn.fri_recursion_tip_witness_generator_dal()
+        .move_recursion_tip_jobs_from_waiting_to_queued()
+        .await;
+    conn.fri_scheduler_witness_generator_dal()
+        .move_scheduler_jobs_from_waiting_to_queued()
+        .await;
+    proven
+}
+
+async fn assert_prover_jobs_successful(
+    pool: &ConnectionPool<Prover>,
+    round: AggregationRound,
+    expected_count: usize,
+) {
+    let jobs = pool
+        .connection()
+        .await
+        .unwrap()
+        .fri_prover_jobs_dal()
+        .get_prover_jobs_stats_for_batch(BATCH, round)
+        .await;
+    assert_eq!(jobs.len(), expected_count, "{round:?}");
+    for job in jobs {
+        assert!(
+            matches!(job.status, ProverJobStatus::Successful(_)),
+            "{round:?}: {job:?}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn fake_rounds_prove_batch() {
+    let pool = ConnectionPool::<Prover>::prover_test_pool().await;
+    let object_store = MockObjectStore::arc();
+    let mut conn = pool.connection().await.unwrap();
+    conn.fri_protocol_versions_dal()
+        .save_prover_protocol_version(
+            ProtocolSemanticVersion::default(),
+            L1VerifierConfig::default(),
+        )
+        .await;
+    conn.fri_basic_witness_generator_dal()
+        .save_witness_inputs(BATCH, "", ProtocolSemanticVersion::default())
+        .await;
+    let base_circuit_count = BaseLayerCircuitType::as_iter_u8().count();
+
+    assert!(run_fake_job::<BasicCircuits>(&pool, &object_store).await);
+    assert!(!run_fake_job::<BasicCircuits>(&pool, &object_store).await);
+    let basic_job = conn
+        .fri_basic_witness_generator_dal()
+        .get_basic_witness_generator_job_for_batch(BATCH)
+        .await
+        .unwrap();
+    assert!(matches!(basic_job.status, WitnessJobStatus::Successful(_)));
+    // Aggregation jobs wait for proofs of the previous round.
+    assert!(!run_fake_job::<LeafAggregation>(&pool, &object_store).await);
+    assert_eq!(
+        prove_queued_jobs(&pool, &*object_store).await,
+        base_circuit_count
+    );
+    assert_prover_jobs_successful(&pool, AggregationRound::BasicCircuits, base_circuit_count).await;
+
+    for _ in 0..base_circuit_count {
+        assert!(run_fake_job::<LeafAggregation>(&pool, &object_store).await);
+    }
+    assert!(!run_fake_job::<LeafAggregation>(&pool, &object_store).await);
+    let leaf_jobs = conn
+        .fri_leaf_witness_generator_dal()
+        .get_leaf_witness_generator_jobs_for_batch(BATCH)
+        .await;
+    assert_eq!(leaf_jobs.len(), base_circuit_count);
+    for job in leaf_jobs {
+        assert!(
+            matches!(job.status, WitnessJobStatus::Successful(_)),
+            "{job:?}"
+        );
+    }
+    assert!(!run_fake_job::<NodeAggregation>(&pool, &object_store).await);
+    assert_eq!(
+        prove_queued_jobs(&pool, &*object_store).await,
+        base_circuit_count
+    );
+    assert_prover_jobs_successful(&pool, AggregationRound::LeafAggregation, base_circuit_count)
+        .await;
+
+    for _ in 0..base_circuit_count {
+        assert!(run_fake_job::<NodeAggregation>(&pool, &object_store).await);
+    }
+    assert!(!run_fake_job::<NodeAggregation>(&pool, &object_store).await);
+    let node_jobs = conn
+        .fri_node_witness_generator_dal()
+        .get_node_witness_generator_jobs_for_batch(BATCH)
+        .await;
+    assert_eq!(node_jobs.len(), base_circuit_count);
+    for job in node_jobs {
+        assert!(
+            matches!(job.status, WitnessJobStatus::Successful(_)),
+            "{job:?}"
+        );
+    }
+    assert!(!run_fake_job::<RecursionTip>(&pool, &object_store).await);
+    assert_eq!(
+        prove_queued_jobs(&pool, &*object_store).await,
+        base_circuit_count
+    );
+    assert_prover_jobs_successful(&pool, AggregationRound::NodeAggregation, base_circuit_count)
+        .await;
+
+    assert!(run_fake_job::<RecursionTip>(&pool, &object_store).await);
+    let recursion_tip_job = conn
+        .fri_recursion_tip_witness_generator_dal()
+        .get_recursion_tip_witness_generator_jobs_for_batch(BATCH)
+        .await
+        .unwrap();
+    assert!(matches!(
+        recursion_tip_job.status,
+        WitnessJobStatus::Successful(_)
+    ));
+    assert!(!run_fake_job::<Scheduler>(&pool, &object_store).await);
+    assert_eq!(prove_queued_jobs(&pool, &*object_store).await, 1);
+    assert_prover_jobs_successful(&pool, AggregationRound::RecursionTip, 1).await;
+
+    assert!(run_fake_job::<Scheduler>(&pool, &object_store).await);
+    let scheduler_job = conn
+        .fri_scheduler_witness_generator_dal()
+        .get_scheduler_witness_generator_jobs_for_batch(BATCH)
+        .await
+        .unwrap();
+    assert!(matches!(
+        scheduler_job.status,
+        WitnessJobStatus::Successful(_)
+    ));
+    assert_eq!(prove_queued_jobs(&pool, &*object_store).await, 1);
+    assert_prover_jobs_successful(&pool, AggregationRound::Scheduler, 1).await;
+
+    let compression_job = conn
+        .fri_proof_compressor_dal()
+        .get_proof_compression_job_for_batch(BATCH)
+        .await
+        .unwrap();
+    assert!(matches!(
+        compression_job.status,
+        ProofCompressionJobStatus::Queued
+    ));
+}
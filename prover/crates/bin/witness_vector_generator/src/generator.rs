@@ -254,12 +254,15 @@ async fn handle_send_result(
 
             METRICS.blob_sending_time[&blob_size_in_mb.to_string()].observe(*elapsed);
 
-            pool.connection()
-                .await
-                .unwrap()
+            let mut connection = pool.connection().await.unwrap();
+            connection
                 .fri_prover_jobs_dal()
                 .update_status(job_id, "in_gpu_proof")
                 .await;
+            connection
+                .fri_prover_jobs_dal()
+                .mark_witness_vector_generated(job_id)
+                .await;
         }
 
         Err(err) => {
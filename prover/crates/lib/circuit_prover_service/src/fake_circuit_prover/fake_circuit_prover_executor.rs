@@ -0,0 +1,37 @@
+use zksync_prover_fri_types::fake::{FakeArtifact, FakeArtifactKind};
+use zksync_prover_job_processor::Executor;
+use zksync_types::prover_dal::FriProverJobMetadata;
+
+/// FakeCircuitProver executor implementation.
+/// Checks that the fake circuit matches the job & produces a fake proof for it.
+#[derive(Debug)]
+pub struct FakeCircuitProverExecutor;
+
+impl Executor for FakeCircuitProverExecutor {
+    type Input = FakeArtifact;
+    type Output = FakeArtifact;
+    type Metadata = FriProverJobMetadata;
+
+    #[tracing::instrument(
+        name = "fake_circuit_prover_executor",
+        skip_all,
+        fields(l1_batch = % metadata.block_number)
+    )]
+    fn execute(
+        &self,
+        input: Self::Input,
+        metadata: Self::Metadata,
+    ) -> anyhow::Result<Self::Output> {
+        anyhow::ensure!(
+            input.circuit_id == metadata.circuit_id
+                && input.aggregation_round == metadata.aggregation_round
+                && input.depth == metadata.depth,
+            "fake circuit {input:?} doesn't match job {}",
+            metadata.id
+        );
+        Ok(FakeArtifact {
+            kind: FakeArtifactKind::Proof,
+            ..input
+        })
+    }
+}
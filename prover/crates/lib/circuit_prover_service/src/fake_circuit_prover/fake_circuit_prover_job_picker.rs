@@ -0,0 +1,75 @@
+use std::sync::Arc;
+
+use anyhow::Context;
+use async_trait::async_trait;
+use zksync_object_store::ObjectStore;
+use zksync_prover_dal::{ConnectionPool, Prover};
+use zksync_prover_fri_types::{
+    fake::{FakeArtifact, FakeArtifactKind},
+    keys::FriCircuitKey,
+    CircuitWrapper,
+};
+use zksync_prover_job_processor::JobPicker;
+use zksync_types::prover_dal::FriProverJobMetadata;
+
+use crate::{
+    fake_circuit_prover::FakeCircuitProverExecutor,
+    witness_vector_generator::{HeavyWitnessVectorMetadataLoader, WitnessVectorMetadataLoader},
+};
+
+/// FakeCircuitProver job picker implementation.
+/// Picks job from database (prioritizing heavy jobs) and gets the fake circuit from object store.
+#[derive(Debug)]
+pub struct FakeCircuitProverJobPicker {
+    connection_pool: ConnectionPool<Prover>,
+    object_store: Arc<dyn ObjectStore>,
+    metadata_loader: HeavyWitnessVectorMetadataLoader,
+}
+
+impl FakeCircuitProverJobPicker {
+    pub fn new(
+        connection_pool: ConnectionPool<Prover>,
+        object_store: Arc<dyn ObjectStore>,
+        metadata_loader: HeavyWitnessVectorMetadataLoader,
+    ) -> Self {
+        Self {
+            connection_pool,
+            object_store,
+            metadata_loader,
+        }
+    }
+}
+
+#[async_trait]
+impl JobPicker for FakeCircuitProverJobPicker {
+    type ExecutorType = FakeCircuitProverExecutor;
+
+    async fn pick_job(&mut self) -> anyhow::Result<Option<(FakeArtifact, FriProverJobMetadata)>> {
+        let connection = self
+            .connection_pool
+            .connection()
+            .await
+            .context("failed to get db connection")?;
+        let metadata = match self.metadata_loader.load_metadata(connection).await {
+            None => return Ok(None),
+            Some(metadata) => metadata,
+        };
+
+        let circuit = FakeArtifact::get::<CircuitWrapper>(
+            &*self.object_store,
+            FriCircuitKey::from(metadata),
+            FakeArtifactKind::Circuit,
+            metadata.block_number,
+        )
+        .await
+        .context("failed to get fake circuit from object store")?;
+        tracing::info!(
+            "Picked fake circuit prover job {}, on batch {}, for circuit {}, at round {}",
+            metadata.id,
+            metadata.block_number,
+            metadata.circuit_id,
+            metadata.aggregation_round
+        );
+        Ok(Some((circuit, metadata)))
+    }
+}
@@ -0,0 +1,108 @@
+use std::sync::Arc;
+
+use anyhow::Context;
+use async_trait::async_trait;
+use zksync_object_store::ObjectStore;
+use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
+use zksync_prover_fri_types::{fake::FakeArtifact, FriProofWrapper};
+use zksync_prover_job_processor::JobSaver;
+use zksync_types::{protocol_version::ProtocolSemanticVersion, prover_dal::FriProverJobMetadata};
+
+use crate::fake_circuit_prover::FakeCircuitProverExecutor;
+
+/// FakeCircuitProver job saver implementation.
+/// Persists the job execution to database the same way as the GPU circuit prover does, storing
+/// the fake proof in place of the real one.
+#[derive(Debug)]
+pub struct FakeCircuitProverJobSaver {
+    connection_pool: ConnectionPool<Prover>,
+    object_store: Arc<dyn ObjectStore>,
+    protocol_version: ProtocolSemanticVersion,
+}
+
+impl FakeCircuitProverJobSaver {
+    pub fn new(
+        connection_pool: ConnectionPool<Prover>,
+        object_store: Arc<dyn ObjectStore>,
+        protocol_version: ProtocolSemanticVersion,
+    ) -> Self {
+        Self {
+            connection_pool,
+            object_store,
+            protocol_version,
+        }
+    }
+}
+
+#[async_trait]
+impl JobSaver for FakeCircuitProverJobSaver {
+    type ExecutorType = FakeCircuitProverExecutor;
+
+    #[tracing::instrument(
+        name = "fake_circuit_prover_job_saver",
+        skip_all,
+        fields(l1_batch = % data.1.block_number)
+    )]
+    async fn save_job_result(
+        &self,
+        data: (anyhow::Result<FakeArtifact>, FriProverJobMetadata),
+    ) -> anyhow::Result<()> {
+        let (result, metadata) = data;
+        match result {
+            Ok(proof) => {
+                let is_scheduler_proof = metadata.is_scheduler_proof()?;
+
+                let blob_url = proof
+                    .put::<FriProofWrapper>(&*self.object_store, metadata.id)
+                    .await
+                    .context("failed to upload to object store")?;
+
+                let mut connection = self
+                    .connection_pool
+                    .connection()
+                    .await
+                    .context("failed to get db connection")?;
+                let mut transaction = connection
+                    .start_transaction()
+                    .await
+                    .context("failed to start db transaction")?;
+                transaction
+                    .fri_prover_jobs_dal()
+                    .save_proof(metadata.id, metadata.pick_time.elapsed(), &blob_url)
+                    .await;
+                if is_scheduler_proof {
+                    transaction
+                        .fri_proof_compressor_dal()
+                        .insert_proof_compression_job(
+                            metadata.block_number,
+                            &blob_url,
+                            self.protocol_version,
+                        )
+                        .await;
+                }
+                transaction
+                    .commit()
+                    .await
+                    .context("failed to commit db transaction")?;
+            }
+            Err(error) => {
+                tracing::error!("Fake circuit prover failed: {:?}", error);
+                self.connection_pool
+                    .connection()
+                    .await
+                    .context("failed to get db connection")?
+                    .fri_prover_jobs_dal()
+                    .save_proof_error(metadata.id, error.to_string())
+                    .await;
+            }
+        };
+        tracing::info!(
+            "Saved fake proof for job {}, on batch {}, for circuit {}, at round {}",
+            metadata.id,
+            metadata.block_number,
+            metadata.circuit_id,
+            metadata.aggregation_round
+        );
+        Ok(())
+    }
+}
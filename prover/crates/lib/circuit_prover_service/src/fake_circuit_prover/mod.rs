@@ -0,0 +1,7 @@
+pub use fake_circuit_prover_executor::FakeCircuitProverExecutor;
+pub use fake_circuit_prover_job_picker::FakeCircuitProverJobPicker;
+pub use fake_circuit_prover_job_saver::FakeCircuitProverJobSaver;
+
+mod fake_circuit_prover_executor;
+mod fake_circuit_prover_job_picker;
+mod fake_circuit_prover_job_saver;
@@ -13,6 +13,9 @@ use zksync_prover_keystore::GoldilocksGpuProverSetupData;
 use zksync_types::{protocol_version::ProtocolSemanticVersion, prover_dal::FriProverJobMetadata};
 
 use crate::{
+    fake_circuit_prover::{
+        FakeCircuitProverExecutor, FakeCircuitProverJobPicker, FakeCircuitProverJobSaver,
+    },
     gpu_circuit_prover::{
         GpuCircuitProverExecutor, GpuCircuitProverJobPicker, GpuCircuitProverJobSaver,
     },
@@ -142,3 +145,33 @@ pub fn circuit_prover_runner(
     let job_saver = GpuCircuitProverJobSaver::new(connection_pool, object_store, protocol_version);
     JobRunner::new(executor, job_picker, job_saver, 1, None)
 }
+
+/// Fake Circuit Prover runner implementation.
+/// Moves prover jobs through the same states as the circuit prover, exchanging fake artifacts
+/// instead of witness vectors & proofs. Needs neither setup data nor a GPU; meant for testing.
+pub fn fake_circuit_prover_runner(
+    connection_pool: ConnectionPool<Prover>,
+    object_store: Arc<dyn ObjectStore>,
+    protocol_version: ProtocolSemanticVersion,
+    count: usize,
+    cancellation_token: CancellationToken,
+) -> JobRunner<FakeCircuitProverExecutor, FakeCircuitProverJobPicker, FakeCircuitProverJobSaver> {
+    let metadata_loader =
+        HeavyWitnessVectorMetadataLoader::new(get_current_pod_name(), protocol_version);
+    let job_picker = FakeCircuitProverJobPicker::new(
+        connection_pool.clone(),
+        object_store.clone(),
+        metadata_loader,
+    );
+    let job_saver = FakeCircuitProverJobSaver::new(connection_pool, object_store, protocol_version);
+    JobRunner::new(
+        FakeCircuitProverExecutor,
+        job_picker,
+        job_saver,
+        count,
+        Some(BackoffAndCancellable::new(
+            Backoff::default(),
+            cancellation_token,
+        )),
+    )
+}
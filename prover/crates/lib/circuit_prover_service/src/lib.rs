@@ -2,6 +2,7 @@
 // Crypto code uses generic const exprs, allocator_api is needed to use global allocators
 #![feature(generic_const_exprs, allocator_api)]
 
+pub mod fake_circuit_prover;
 pub mod gpu_circuit_prover;
 pub mod job_runner;
 mod metrics;
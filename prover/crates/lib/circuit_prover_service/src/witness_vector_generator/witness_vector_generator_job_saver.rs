@@ -13,7 +13,7 @@ use crate::{
 };
 
 /// WitnessVectorGenerator job saver implementation.
-/// On successful execution, sends data further to gpu circuit prover.
+/// On successful execution, records the generation time and sends data further to gpu circuit prover.
 /// On error, marks the job as failed in database.
 #[derive(Debug)]
 pub struct WitnessVectorGeneratorJobSaver {
@@ -57,6 +57,13 @@ impl JobSaver for WitnessVectorGeneratorJobSaver {
         let (result, metadata) = data;
         match result {
             Ok(payload) => {
+                self.connection_pool
+                    .connection()
+                    .await
+                    .context("failed to get db connection")?
+                    .fri_prover_jobs_dal()
+                    .mark_witness_vector_generated(metadata.id)
+                    .await;
                 tracing::info!(
                     "Started transferring witness vector generator job {}, on batch {}, for circuit {}, at round {}",
                     metadata.id,
@@ -0,0 +1,20 @@
+DROP INDEX IF EXISTS idx_proof_compression_jobs_fri_queued_deadline;
+DROP INDEX IF EXISTS idx_prover_jobs_fri_circuit_queued_deadline;
+DROP INDEX IF EXISTS idx_prover_jobs_fri_queued_deadline;
+DROP INDEX IF EXISTS idx_scheduler_witness_jobs_fri_queued_deadline;
+DROP INDEX IF EXISTS idx_recursion_tip_witness_jobs_fri_queued_deadline;
+DROP INDEX IF EXISTS idx_node_aggregation_witness_jobs_fri_queued_deadline;
+DROP INDEX IF EXISTS idx_leaf_aggregation_witness_jobs_fri_queued_deadline;
+DROP INDEX IF EXISTS idx_witness_inputs_fri_queued_deadline;
+
+ALTER TABLE proof_compression_jobs_fri DROP COLUMN IF EXISTS deadline;
+ALTER TABLE prover_jobs_fri_archive DROP COLUMN IF EXISTS deadline;
+ALTER TABLE prover_jobs_fri DROP COLUMN IF EXISTS deadline;
+ALTER TABLE scheduler_witness_jobs_fri DROP COLUMN IF EXISTS deadline;
+ALTER TABLE recursion_tip_witness_jobs_fri DROP COLUMN IF EXISTS deadline;
+ALTER TABLE node_aggregation_witness_jobs_fri DROP COLUMN IF EXISTS deadline;
+ALTER TABLE leaf_aggregation_witness_jobs_fri DROP COLUMN IF EXISTS deadline;
+
+DROP INDEX IF EXISTS idx_witness_inputs_fri_deadline;
+
+ALTER TABLE witness_inputs_fri DROP COLUMN IF EXISTS deadline;
@@ -0,0 +1,46 @@
+ALTER TABLE witness_inputs_fri ADD COLUMN IF NOT EXISTS deadline TIMESTAMP;
+
+CREATE INDEX IF NOT EXISTS idx_witness_inputs_fri_deadline
+    ON witness_inputs_fri USING btree (deadline)
+    WHERE (deadline IS NOT NULL);
+
+-- Batch SLA deadlines are copied to the jobs of all rounds, so that job pickers don't need to look them up.
+ALTER TABLE leaf_aggregation_witness_jobs_fri ADD COLUMN IF NOT EXISTS deadline TIMESTAMP;
+ALTER TABLE node_aggregation_witness_jobs_fri ADD COLUMN IF NOT EXISTS deadline TIMESTAMP;
+ALTER TABLE recursion_tip_witness_jobs_fri ADD COLUMN IF NOT EXISTS deadline TIMESTAMP;
+ALTER TABLE scheduler_witness_jobs_fri ADD COLUMN IF NOT EXISTS deadline TIMESTAMP;
+ALTER TABLE prover_jobs_fri ADD COLUMN IF NOT EXISTS deadline TIMESTAMP;
+ALTER TABLE prover_jobs_fri_archive ADD COLUMN IF NOT EXISTS deadline TIMESTAMP;
+ALTER TABLE proof_compression_jobs_fri ADD COLUMN IF NOT EXISTS deadline TIMESTAMP;
+
+CREATE INDEX IF NOT EXISTS idx_witness_inputs_fri_queued_deadline
+    ON witness_inputs_fri USING btree (priority DESC, deadline ASC NULLS LAST, created_at ASC)
+    WHERE (status = 'queued'::text);
+
+CREATE INDEX IF NOT EXISTS idx_leaf_aggregation_witness_jobs_fri_queued_deadline
+    ON leaf_aggregation_witness_jobs_fri USING btree (priority DESC, deadline ASC NULLS LAST, created_at ASC)
+    WHERE (status = 'queued'::text);
+
+CREATE INDEX IF NOT EXISTS idx_node_aggregation_witness_jobs_fri_queued_deadline
+    ON node_aggregation_witness_jobs_fri USING btree (priority DESC, deadline ASC NULLS LAST, created_at ASC)
+    WHERE (status = 'queued'::text);
+
+CREATE INDEX IF NOT EXISTS idx_recursion_tip_witness_jobs_fri_queued_deadline
+    ON recursion_tip_witness_jobs_fri USING btree (priority DESC, deadline ASC NULLS LAST, created_at ASC)
+    WHERE (status = 'queued'::text);
+
+CREATE INDEX IF NOT EXISTS idx_scheduler_witness_jobs_fri_queued_deadline
+    ON scheduler_witness_jobs_fri USING btree (priority DESC, deadline ASC NULLS LAST, created_at ASC)
+    WHERE (status = 'queued'::text);
+
+CREATE INDEX IF NOT EXISTS idx_prover_jobs_fri_queued_deadline
+    ON prover_jobs_fri USING btree (aggregation_round, priority DESC, deadline ASC NULLS LAST, created_at ASC)
+    WHERE (status = 'queued'::text);
+
+CREATE INDEX IF NOT EXISTS idx_prover_jobs_fri_circuit_queued_deadline
+    ON prover_jobs_fri USING btree (circuit_id, aggregation_round, priority DESC, deadline ASC NULLS LAST, created_at ASC)
+    WHERE (status = 'queued'::text);
+
+CREATE INDEX IF NOT EXISTS idx_proof_compression_jobs_fri_queued_deadline
+    ON proof_compression_jobs_fri USING btree (priority DESC, deadline ASC NULLS LAST, created_at ASC)
+    WHERE (status = 'queued'::text);
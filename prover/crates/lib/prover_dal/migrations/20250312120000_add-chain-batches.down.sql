@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS chain_batches_fri;
+DROP SEQUENCE IF EXISTS chain_batches_fri_l1_batch_number_seq;
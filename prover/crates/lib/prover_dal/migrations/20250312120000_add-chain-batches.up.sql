@@ -0,0 +1,15 @@
+-- Prover-local numbers of batches fetched from one of several chains. Batches of different chains may have
+-- the same number, so each chain batch is allocated a number from the sequence before its data is saved.
+CREATE SEQUENCE IF NOT EXISTS chain_batches_fri_l1_batch_number_seq;
+
+CREATE TABLE IF NOT EXISTS chain_batches_fri (
+    l1_batch_number BIGINT PRIMARY KEY DEFAULT nextval('chain_batches_fri_l1_batch_number_seq'),
+    chain_id BIGINT NOT NULL,
+    chain_l1_batch_number BIGINT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+ALTER SEQUENCE chain_batches_fri_l1_batch_number_seq OWNED BY chain_batches_fri.l1_batch_number;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_chain_batches_fri_chain_batch
+    ON chain_batches_fri USING btree (chain_id, chain_l1_batch_number);
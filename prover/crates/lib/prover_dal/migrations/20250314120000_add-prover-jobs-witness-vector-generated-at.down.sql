@@ -0,0 +1,2 @@
+ALTER TABLE prover_jobs_fri_archive DROP COLUMN IF EXISTS witness_vector_generated_at;
+ALTER TABLE prover_jobs_fri DROP COLUMN IF EXISTS witness_vector_generated_at;
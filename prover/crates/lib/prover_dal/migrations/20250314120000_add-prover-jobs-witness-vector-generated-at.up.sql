@@ -0,0 +1,2 @@
+ALTER TABLE prover_jobs_fri ADD COLUMN IF NOT EXISTS witness_vector_generated_at TIMESTAMP;
+ALTER TABLE prover_jobs_fri_archive ADD COLUMN IF NOT EXISTS witness_vector_generated_at TIMESTAMP;
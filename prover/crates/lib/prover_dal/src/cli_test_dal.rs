@@ -1,10 +1,13 @@
+use std::time::Duration;
+
+use sqlx::types::chrono::NaiveDateTime;
 use zksync_basic_types::{
     prover_dal::{ProofCompressionJobStatus, ProverJobStatus, WitnessJobStatus},
     L1BatchNumber,
 };
 use zksync_db_connection::connection::Connection;
 
-use crate::Prover;
+use crate::{duration_to_naive_time, Prover};
 
 #[derive(Debug)]
 pub struct CliTestDal<'a, 'c> {
@@ -238,4 +241,38 @@ impl CliTestDal<'_, '_> {
         .await
         .unwrap();
     }
+
+    /// Sets timestamps of all jobs of the batch in `table` (e.g. `prover_jobs_fri`), so that
+    /// time-based reports can be checked.
+    pub async fn update_job_timestamps(
+        &mut self,
+        table: &str,
+        batch_number: L1BatchNumber,
+        created_at: NaiveDateTime,
+        processing_started_at: NaiveDateTime,
+        time_taken: Duration,
+        updated_at: NaiveDateTime,
+    ) {
+        let query = format!(
+            r#"
+            UPDATE {table}
+            SET
+                created_at = $1,
+                processing_started_at = $2,
+                time_taken = $3,
+                updated_at = $4
+            WHERE
+                l1_batch_number = $5
+            "#
+        );
+        sqlx::query(&query)
+            .bind(created_at)
+            .bind(processing_started_at)
+            .bind(duration_to_naive_time(time_taken))
+            .bind(updated_at)
+            .bind(batch_number.0 as i64)
+            .execute(self.storage.conn())
+            .await
+            .unwrap();
+    }
 }
@@ -0,0 +1,492 @@
+use std::time::Duration;
+
+use zksync_basic_types::{prover_dal::BatchSlaStatus, L1BatchNumber};
+use zksync_db_connection::connection::Connection;
+
+use crate::{pg_interval_from_duration, Prover};
+
+/// SLA deadlines of L1 batches. Deadlines are assigned in `witness_inputs_fri` and copied to the jobs
+/// of all rounds, which are picked in the order of their deadline after the job priority.
+///
+/// Only unproven batches are considered: batches with a successful, skipped or sent to server
+/// compression job are proven. Batches with failed jobs are not reported as at risk until the jobs
+/// are requeued.
+#[derive(Debug)]
+pub struct FriBatchSlaDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Prover>,
+}
+
+impl FriBatchSlaDal<'_, '_> {
+    /// Sets the deadline to `sla` after the witness inputs were saved for unproven batches saved after
+    /// the last batch with a deadline, and copies it to the already created jobs of these batches.
+    /// Jobs created afterwards copy the deadline on insertion. Returns the number of updated batches.
+    pub async fn assign_batch_deadlines(&mut self, sla: Duration) -> usize {
+        let sla = pg_interval_from_duration(sla);
+        sqlx::query_scalar!(
+            r#"
+            WITH
+            assigned AS (
+                UPDATE witness_inputs_fri wi
+                SET
+                    deadline = wi.created_at + $1::INTERVAL
+                WHERE
+                    wi.deadline IS NULL
+                    AND wi.l1_batch_number > COALESCE(
+                        (
+                            SELECT
+                                MAX(l1_batch_number)
+                            FROM
+                                witness_inputs_fri
+                            WHERE
+                                deadline IS NOT NULL
+                        ),
+                        -1
+                    )
+                    AND NOT EXISTS (
+                        SELECT
+                            1
+                        FROM
+                            proof_compression_jobs_fri pc
+                        WHERE
+                            pc.l1_batch_number = wi.l1_batch_number
+                            AND pc.status IN ('successful', 'sent_to_server', 'skipped')
+                    )
+                RETURNING
+                    wi.l1_batch_number,
+                    wi.deadline
+            ),
+            
+            leaf AS (
+                UPDATE leaf_aggregation_witness_jobs_fri j
+                SET
+                    deadline = assigned.deadline
+                FROM
+                    assigned
+                WHERE
+                    j.l1_batch_number = assigned.l1_batch_number
+            ),
+            
+            node AS (
+                UPDATE node_aggregation_witness_jobs_fri j
+                SET
+                    deadline = assigned.deadline
+                FROM
+                    assigned
+                WHERE
+                    j.l1_batch_number = assigned.l1_batch_number
+            ),
+            
+            recursion_tip AS (
+                UPDATE recursion_tip_witness_jobs_fri j
+                SET
+                    deadline = assigned.deadline
+                FROM
+                    assigned
+                WHERE
+                    j.l1_batch_number = assigned.l1_batch_number
+            ),
+            
+            scheduler AS (
+                UPDATE scheduler_witness_jobs_fri j
+                SET
+                    deadline = assigned.deadline
+                FROM
+                    assigned
+                WHERE
+                    j.l1_batch_number = assigned.l1_batch_number
+            ),
+            
+            prover AS (
+                UPDATE prover_jobs_fri j
+                SET
+                    deadline = assigned.deadline
+                FROM
+                    assigned
+                WHERE
+                    j.l1_batch_number = assigned.l1_batch_number
+            ),
+            
+            compressor AS (
+                UPDATE proof_compression_jobs_fri j
+                SET
+                    deadline = assigned.deadline
+                FROM
+                    assigned
+                WHERE
+                    j.l1_batch_number = assigned.l1_batch_number
+            )
+            
+            SELECT
+                COUNT(*) AS "count!"
+            FROM
+                assigned
+            "#,
+            &sla
+        )
+        .fetch_one(self.storage.conn())
+        .await
+        .unwrap() as usize
+    }
+
+    /// Returns unproven batches without failed jobs which have the deadline in less than `threshold`
+    /// (or have already missed it), the earliest deadline first.
+    pub async fn get_sla_at_risk_batches(&mut self, threshold: Duration) -> Vec<BatchSlaStatus> {
+        let threshold = pg_interval_from_duration(threshold);
+        sqlx::query!(
+            r#"
+            SELECT
+                wi.l1_batch_number,
+                wi.deadline AS "deadline!",
+                wi.deadline < NOW() AS "missed!"
+            FROM
+                witness_inputs_fri wi
+            WHERE
+                wi.deadline < NOW() + $1::INTERVAL
+                AND wi.status != 'failed'
+                AND NOT EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        proof_compression_jobs_fri pc
+                    WHERE
+                        pc.l1_batch_number = wi.l1_batch_number
+                        AND pc.status IN ('successful', 'sent_to_server', 'skipped', 'failed')
+                )
+                AND NOT EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        leaf_aggregation_witness_jobs_fri
+                    WHERE
+                        l1_batch_number = wi.l1_batch_number
+                        AND status = 'failed'
+                    UNION ALL
+                    SELECT
+                        1
+                    FROM
+                        node_aggregation_witness_jobs_fri
+                    WHERE
+                        l1_batch_number = wi.l1_batch_number
+                        AND status = 'failed'
+                    UNION ALL
+                    SELECT
+                        1
+                    FROM
+                        recursion_tip_witness_jobs_fri
+                    WHERE
+                        l1_batch_number = wi.l1_batch_number
+                        AND status = 'failed'
+                    UNION ALL
+                    SELECT
+                        1
+                    FROM
+                        scheduler_witness_jobs_fri
+                    WHERE
+                        l1_batch_number = wi.l1_batch_number
+                        AND status = 'failed'
+                    UNION ALL
+                    SELECT
+                        1
+                    FROM
+                        prover_jobs_fri
+                    WHERE
+                        l1_batch_number = wi.l1_batch_number
+                        AND status = 'failed'
+                )
+            ORDER BY
+                wi.deadline ASC
+            "#,
+            &threshold
+        )
+        .fetch_all(self.storage.conn())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|row| BatchSlaStatus {
+            l1_batch_number: L1BatchNumber(row.l1_batch_number as u32),
+            deadline: row.deadline,
+            missed: row.missed,
+        })
+        .collect()
+    }
+
+    /// Raises the priority of unfinished jobs of the batch in all rounds to at least `min_priority`.
+    /// Returns the number of boosted jobs.
+    pub async fn boost_batch_priority(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        min_priority: u32,
+    ) -> u64 {
+        sqlx::query_scalar!(
+            r#"
+            WITH
+            basic AS (
+                UPDATE witness_inputs_fri
+                SET
+                    priority = $2
+                WHERE
+                    l1_batch_number = $1
+                    AND priority < $2
+                    AND status NOT IN ('successful', 'skipped')
+                RETURNING
+                    1
+            ),
+            
+            leaf AS (
+                UPDATE leaf_aggregation_witness_jobs_fri
+                SET
+                    priority = $2
+                WHERE
+                    l1_batch_number = $1
+                    AND priority < $2
+                    AND status NOT IN ('successful', 'skipped')
+                RETURNING
+                    1
+            ),
+            
+            node AS (
+                UPDATE node_aggregation_witness_jobs_fri
+                SET
+                    priority = $2
+                WHERE
+                    l1_batch_number = $1
+                    AND priority < $2
+                    AND status NOT IN ('successful', 'skipped')
+                RETURNING
+                    1
+            ),
+            
+            recursion_tip AS (
+                UPDATE recursion_tip_witness_jobs_fri
+                SET
+                    priority = $2
+                WHERE
+                    l1_batch_number = $1
+                    AND priority < $2
+                    AND status NOT IN ('successful', 'skipped')
+                RETURNING
+                    1
+            ),
+            
+            scheduler AS (
+                UPDATE scheduler_witness_jobs_fri
+                SET
+                    priority = $2
+                WHERE
+                    l1_batch_number = $1
+                    AND priority < $2
+                    AND status NOT IN ('successful', 'skipped')
+                RETURNING
+                    1
+            ),
+            
+            prover AS (
+                UPDATE prover_jobs_fri
+                SET
+                    priority = $2
+                WHERE
+                    l1_batch_number = $1
+                    AND priority < $2
+                    AND status NOT IN ('successful', 'skipped')
+                RETURNING
+                    1
+            ),
+            
+            compressor AS (
+                UPDATE proof_compression_jobs_fri
+                SET
+                    priority = $2
+                WHERE
+                    l1_batch_number = $1
+                    AND priority < $2
+                    AND status NOT IN ('successful', 'sent_to_server', 'skipped')
+                RETURNING
+                    1
+            )
+            
+            SELECT
+                (SELECT COUNT(*) FROM basic)
+                + (SELECT COUNT(*) FROM leaf)
+                + (SELECT COUNT(*) FROM node)
+                + (SELECT COUNT(*) FROM recursion_tip)
+                + (SELECT COUNT(*) FROM scheduler)
+                + (SELECT COUNT(*) FROM prover)
+                + (SELECT COUNT(*) FROM compressor) AS "count!"
+            "#,
+            i64::from(l1_batch_number.0),
+            min_priority as i32
+        )
+        .fetch_one(self.storage.conn())
+        .await
+        .unwrap() as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::types::chrono::NaiveDateTime;
+    use zksync_basic_types::{
+        basic_fri_types::AggregationRound,
+        protocol_version::{L1VerifierConfig, ProtocolSemanticVersion},
+    };
+    use zksync_db_connection::connection_pool::ConnectionPool;
+
+    use super::*;
+    use crate::{fri_witness_generator_dal::FriWitnessJobStatus, ProverDal};
+
+    const HOUR: Duration = Duration::from_secs(3600);
+
+    async fn save_batches(conn: &mut Connection<'_, Prover>, numbers: impl Iterator<Item = u32>) {
+        for number in numbers {
+            conn.fri_basic_witness_generator_dal()
+                .save_witness_inputs(
+                    L1BatchNumber(number),
+                    "witness_inputs",
+                    ProtocolSemanticVersion::default(),
+                )
+                .await;
+        }
+    }
+
+    async fn mark_batch_proven(conn: &mut Connection<'_, Prover>, number: u32) {
+        conn.fri_proof_compressor_dal()
+            .insert_proof_compression_job(
+                L1BatchNumber(number),
+                "fri_proof",
+                ProtocolSemanticVersion::default(),
+            )
+            .await;
+        conn.fri_proof_compressor_dal()
+            .mark_proof_compression_job_successful(
+                L1BatchNumber(number),
+                Duration::from_secs(1),
+                "l1_proof",
+            )
+            .await;
+    }
+
+    async fn insert_prover_job(conn: &mut Connection<'_, Prover>, number: u32) {
+        conn.fri_prover_jobs_dal()
+            .insert_prover_jobs(
+                L1BatchNumber(number),
+                vec![(1, "circuit".to_owned())],
+                AggregationRound::BasicCircuits,
+                0,
+                ProtocolSemanticVersion::default(),
+            )
+            .await;
+    }
+
+    async fn deadline(
+        conn: &mut Connection<'_, Prover>,
+        table: &str,
+        number: u32,
+    ) -> Option<NaiveDateTime> {
+        sqlx::query_scalar(&format!(
+            "SELECT deadline FROM {table} WHERE l1_batch_number = $1"
+        ))
+        .bind(i64::from(number))
+        .fetch_one(conn.conn())
+        .await
+        .unwrap()
+    }
+
+    async fn setup(pool: &ConnectionPool<Prover>) -> Connection<'static, Prover> {
+        let mut conn = pool.connection().await.unwrap();
+        conn.fri_protocol_versions_dal()
+            .save_prover_protocol_version(
+                ProtocolSemanticVersion::default(),
+                L1VerifierConfig::default(),
+            )
+            .await;
+        conn
+    }
+
+    #[tokio::test]
+    async fn deadlines_are_assigned_to_unproven_batches_and_their_jobs() {
+        let pool = ConnectionPool::<Prover>::prover_test_pool().await;
+        let mut conn = setup(&pool).await;
+        save_batches(&mut conn, 1..=3).await;
+        mark_batch_proven(&mut conn, 1).await;
+        insert_prover_job(&mut conn, 2).await;
+
+        let assigned = conn.fri_batch_sla_dal().assign_batch_deadlines(HOUR).await;
+        assert_eq!(assigned, 2);
+        assert_eq!(deadline(&mut conn, "witness_inputs_fri", 1).await, None);
+        assert_eq!(
+            deadline(&mut conn, "proof_compression_jobs_fri", 1).await,
+            None
+        );
+        let batch_deadline = deadline(&mut conn, "witness_inputs_fri", 2).await;
+        assert!(batch_deadline.is_some());
+        // Jobs created before the deadline was assigned get it as well...
+        assert_eq!(
+            deadline(&mut conn, "prover_jobs_fri", 2).await,
+            batch_deadline
+        );
+        // ...and jobs created afterwards copy it on insertion.
+        insert_prover_job(&mut conn, 3).await;
+        let batch_deadline = deadline(&mut conn, "witness_inputs_fri", 3).await;
+        assert!(batch_deadline.is_some());
+        assert_eq!(
+            deadline(&mut conn, "prover_jobs_fri", 3).await,
+            batch_deadline
+        );
+
+        let assigned = conn.fri_batch_sla_dal().assign_batch_deadlines(HOUR).await;
+        assert_eq!(assigned, 0);
+        save_batches(&mut conn, 4..=4).await;
+        let assigned = conn.fri_batch_sla_dal().assign_batch_deadlines(HOUR).await;
+        assert_eq!(assigned, 1);
+        // Proven batches are never backfilled.
+        assert_eq!(deadline(&mut conn, "witness_inputs_fri", 1).await, None);
+    }
+
+    #[tokio::test]
+    async fn at_risk_batches_exclude_proven_and_failed_batches() {
+        let pool = ConnectionPool::<Prover>::prover_test_pool().await;
+        let mut conn = setup(&pool).await;
+        save_batches(&mut conn, 1..=4).await;
+        let assigned = conn
+            .fri_batch_sla_dal()
+            .assign_batch_deadlines(Duration::ZERO)
+            .await;
+        assert_eq!(assigned, 4);
+
+        mark_batch_proven(&mut conn, 1).await;
+        insert_prover_job(&mut conn, 2).await;
+        sqlx::query("UPDATE prover_jobs_fri SET status = 'failed' WHERE l1_batch_number = 2")
+            .execute(conn.conn())
+            .await
+            .unwrap();
+        conn.fri_basic_witness_generator_dal()
+            .set_status_for_basic_witness_job(FriWitnessJobStatus::Failed, L1BatchNumber(3))
+            .await;
+
+        let at_risk = conn
+            .fri_batch_sla_dal()
+            .get_sla_at_risk_batches(Duration::ZERO)
+            .await;
+        assert_eq!(at_risk.len(), 1, "{at_risk:?}");
+        assert_eq!(at_risk[0].l1_batch_number, L1BatchNumber(4));
+        assert!(at_risk[0].missed);
+
+        save_batches(&mut conn, 5..=5).await;
+        conn.fri_batch_sla_dal().assign_batch_deadlines(HOUR).await;
+        let at_risk = conn
+            .fri_batch_sla_dal()
+            .get_sla_at_risk_batches(Duration::ZERO)
+            .await;
+        assert_eq!(at_risk.len(), 1, "{at_risk:?}");
+        let at_risk = conn
+            .fri_batch_sla_dal()
+            .get_sla_at_risk_batches(2 * HOUR)
+            .await;
+        let numbers: Vec<_> = at_risk
+            .iter()
+            .map(|batch| batch.l1_batch_number.0)
+            .collect();
+        assert_eq!(numbers, [4, 5]);
+        assert!(!at_risk[1].missed);
+    }
+}
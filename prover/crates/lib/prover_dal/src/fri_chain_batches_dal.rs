@@ -0,0 +1,193 @@
+use zksync_basic_types::{prover_dal::ChainBatch, L1BatchNumber, L2ChainId};
+use zksync_db_connection::connection::Connection;
+
+use crate::Prover;
+
+/// Prover-local numbers of batches fetched from one of several chains. Batches of different chains
+/// may have the same number, so each chain batch is proven under a number allocated from a sequence.
+/// Allocated numbers are never reused, so proofs are always mapped back to the batch they were
+/// generated for, even if the batch data is deleted from other tables.
+#[derive(Debug)]
+pub struct FriChainBatchesDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Prover>,
+}
+
+impl FriChainBatchesDal<'_, '_> {
+    /// Moves the number sequence past all saved batches, so that allocated numbers don't clash with
+    /// batches saved without a chain. Must be called before allocating numbers after batches
+    /// without a chain may have been saved.
+    pub async fn sync_batch_number_sequence(&mut self) {
+        sqlx::query!(
+            r#"
+            SELECT
+                SETVAL(
+                    'chain_batches_fri_l1_batch_number_seq',
+                    GREATEST(
+                        (
+                            SELECT
+                                last_value
+                            FROM
+                                chain_batches_fri_l1_batch_number_seq
+                        ),
+                        (
+                            SELECT
+                                COALESCE(MAX(l1_batch_number), 0)
+                            FROM
+                                witness_inputs_fri
+                        ),
+                        (
+                            SELECT
+                                COALESCE(MAX(l1_batch_number), 0)
+                            FROM
+                                chain_batches_fri
+                        )
+                    )
+                )
+            "#
+        )
+        .fetch_one(self.storage.conn())
+        .await
+        .unwrap();
+    }
+
+    /// Returns the prover-local number of a chain batch, allocating it if the batch is seen
+    /// for the first time. Allocation is atomic, so concurrent callers never get the same number
+    /// for different batches.
+    pub async fn allocate_prover_batch_number(&mut self, chain_batch: ChainBatch) -> L1BatchNumber {
+        let number = sqlx::query_scalar!(
+            r#"
+            INSERT INTO
+            chain_batches_fri (chain_id, chain_l1_batch_number, created_at)
+            VALUES
+            ($1, $2, NOW())
+            ON CONFLICT (chain_id, chain_l1_batch_number) DO
+            UPDATE
+            SET
+            chain_id = excluded.chain_id
+            RETURNING
+            l1_batch_number
+            "#,
+            chain_batch.chain_id.as_u64() as i64,
+            i64::from(chain_batch.l1_batch_number.0)
+        )
+        .fetch_one(self.storage.conn())
+        .await
+        .unwrap();
+        L1BatchNumber(number as u32)
+    }
+
+    /// Returns the prover-local number of an already allocated chain batch.
+    pub async fn get_prover_batch_number(
+        &mut self,
+        chain_batch: ChainBatch,
+    ) -> Option<L1BatchNumber> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT
+                l1_batch_number
+            FROM
+                chain_batches_fri
+            WHERE
+                chain_id = $1
+                AND chain_l1_batch_number = $2
+            "#,
+            chain_batch.chain_id.as_u64() as i64,
+            i64::from(chain_batch.l1_batch_number.0)
+        )
+        .fetch_optional(self.storage.conn())
+        .await
+        .unwrap()
+        .map(|number| L1BatchNumber(number as u32))
+    }
+
+    /// Returns the chain batch a prover-local number was allocated for, or `None` for batches saved
+    /// without a chain.
+    pub async fn get_chain_batch(&mut self, block_number: L1BatchNumber) -> Option<ChainBatch> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                chain_id,
+                chain_l1_batch_number
+            FROM
+                chain_batches_fri
+            WHERE
+                l1_batch_number = $1
+            "#,
+            i64::from(block_number.0)
+        )
+        .fetch_optional(self.storage.conn())
+        .await
+        .unwrap()?;
+        Some(ChainBatch {
+            chain_id: L2ChainId::new(row.chain_id as u64).expect("invalid chain ID in database"),
+            l1_batch_number: L1BatchNumber(row.chain_l1_batch_number as u32),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_basic_types::protocol_version::ProtocolSemanticVersion;
+    use zksync_db_connection::connection_pool::ConnectionPool;
+
+    use super::*;
+    use crate::ProverDal;
+
+    fn chain_batch(chain_id: u32, number: u32) -> ChainBatch {
+        ChainBatch {
+            chain_id: L2ChainId::from(chain_id),
+            l1_batch_number: L1BatchNumber(number),
+        }
+    }
+
+    #[tokio::test]
+    async fn allocating_prover_batch_numbers() {
+        let pool = ConnectionPool::<Prover>::prover_test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        // A batch saved without a chain before chains were configured.
+        conn.fri_basic_witness_generator_dal()
+            .save_witness_inputs(
+                L1BatchNumber(10),
+                "witness_inputs",
+                ProtocolSemanticVersion::default(),
+            )
+            .await;
+        conn.fri_chain_batches_dal()
+            .sync_batch_number_sequence()
+            .await;
+
+        let first = conn
+            .fri_chain_batches_dal()
+            .allocate_prover_batch_number(chain_batch(270, 1))
+            .await;
+        let second = conn
+            .fri_chain_batches_dal()
+            .allocate_prover_batch_number(chain_batch(271, 1))
+            .await;
+        assert!(first > L1BatchNumber(10), "{first}");
+        assert!(second > L1BatchNumber(10), "{second}");
+        assert_ne!(first, second);
+
+        // Allocation is idempotent.
+        let number = conn
+            .fri_chain_batches_dal()
+            .allocate_prover_batch_number(chain_batch(270, 1))
+            .await;
+        assert_eq!(number, first);
+        let number = conn
+            .fri_chain_batches_dal()
+            .get_prover_batch_number(chain_batch(271, 1))
+            .await;
+        assert_eq!(number, Some(second));
+
+        let batch = conn.fri_chain_batches_dal().get_chain_batch(first).await;
+        assert_eq!(batch, Some(chain_batch(270, 1)));
+        let batch = conn.fri_chain_batches_dal().get_chain_batch(second).await;
+        assert_eq!(batch, Some(chain_batch(271, 1)));
+        let batch = conn
+            .fri_chain_batches_dal()
+            .get_chain_batch(L1BatchNumber(10))
+            .await;
+        assert_eq!(batch, None);
+    }
+}
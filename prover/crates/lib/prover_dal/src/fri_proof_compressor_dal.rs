@@ -6,7 +6,7 @@ use zksync_basic_types::{
     prover_dal::{
         JobCountStatistics, ProofCompressionJobInfo, ProofCompressionJobStatus, StuckJobs,
     },
-    L1BatchNumber,
+    L1BatchNumber, L2ChainId,
 };
 use zksync_db_connection::connection::Connection;
 
@@ -34,10 +34,27 @@ impl FriProofCompressorDal<'_, '_> {
                 created_at,
                 updated_at,
                 protocol_version,
-                protocol_version_patch
+                protocol_version_patch,
+                deadline
             )
             VALUES
-            ($1, $2, $3, NOW(), NOW(), $4, $5)
+            (
+                $1,
+                $2,
+                $3,
+                NOW(),
+                NOW(),
+                $4,
+                $5,
+                (
+                    SELECT
+                        deadline
+                    FROM
+                        witness_inputs_fri
+                    WHERE
+                        l1_batch_number = $1
+                )
+            )
             ON CONFLICT (l1_batch_number) DO NOTHING
             "#,
             i64::from(block_number.0),
@@ -77,6 +94,7 @@ impl FriProofCompressorDal<'_, '_> {
                         AND protocol_version_patch = $5
                     ORDER BY
                         priority DESC,
+                        deadline ASC NULLS LAST,
                         created_at ASC
                     LIMIT
                         1
@@ -147,6 +165,53 @@ impl FriProofCompressorDal<'_, '_> {
         .unwrap();
     }
 
+    /// Marks the job as done without a proof, so that the batch is submitted as skipped.
+    pub async fn mark_proof_compression_job_skipped(
+        &mut self,
+        block_number: L1BatchNumber,
+        time_taken: Duration,
+    ) {
+        sqlx::query!(
+            r#"
+            UPDATE proof_compression_jobs_fri
+            SET
+                status = $1,
+                updated_at = NOW(),
+                time_taken = $2
+            WHERE
+                l1_batch_number = $3
+            "#,
+            ProofCompressionJobStatus::Skipped.to_string(),
+            duration_to_naive_time(time_taken),
+            i64::from(block_number.0)
+        )
+        .execute(self.storage.conn())
+        .await
+        .unwrap();
+    }
+
+    /// Checks whether any batch has been compressed into a real L1 proof. Such a database must not
+    /// be used by the prover pipeline in fake proof mode.
+    pub async fn has_compressed_proofs(&mut self) -> bool {
+        sqlx::query!(
+            r#"
+            SELECT
+                EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        proof_compression_jobs_fri
+                    WHERE
+                        l1_proof_blob_url IS NOT NULL
+                ) AS "exists!"
+            "#
+        )
+        .fetch_one(self.storage.conn())
+        .await
+        .unwrap()
+        .exists
+    }
+
     pub async fn mark_proof_compression_job_failed(
         &mut self,
         error: &str,
@@ -175,35 +240,48 @@ impl FriProofCompressorDal<'_, '_> {
         .unwrap();
     }
 
+    /// Returns the least batch with a successful or skipped proof which is not sent to the server yet.
+    /// Batches fetched for chains other than `chain_ids` are skipped, since they can't be submitted;
+    /// batches saved without a chain are always considered.
     pub async fn get_least_proven_block_not_sent_to_server(
         &mut self,
+        chain_ids: &[L2ChainId],
     ) -> Option<(
         L1BatchNumber,
         ProtocolSemanticVersion,
         ProofCompressionJobStatus,
     )> {
+        let chain_ids: Vec<_> = chain_ids
+            .iter()
+            .map(|chain_id| chain_id.as_u64() as i64)
+            .collect();
         let row = sqlx::query!(
             r#"
             SELECT
-                l1_batch_number,
-                status,
-                protocol_version,
-                protocol_version_patch
+                pc.l1_batch_number,
+                pc.status,
+                pc.protocol_version,
+                pc.protocol_version_patch
             FROM
-                proof_compression_jobs_fri
+                proof_compression_jobs_fri pc
+            LEFT JOIN chain_batches_fri cb ON cb.l1_batch_number = pc.l1_batch_number
             WHERE
-                l1_batch_number = (
-                    SELECT
-                        MIN(l1_batch_number)
-                    FROM
-                        proof_compression_jobs_fri
-                    WHERE
-                        status = $1
-                        OR status = $2
+                (
+                    pc.status = $1
+                    OR pc.status = $2
                 )
+                AND (
+                    cb.chain_id IS NULL
+                    OR cb.chain_id = ANY($3)
+                )
+            ORDER BY
+                pc.l1_batch_number ASC
+            LIMIT
+                1
             "#,
             ProofCompressionJobStatus::Successful.to_string(),
-            ProofCompressionJobStatus::Skipped.to_string()
+            ProofCompressionJobStatus::Skipped.to_string(),
+            &chain_ids
         )
         .fetch_optional(self.storage.conn())
         .await
@@ -480,3 +558,177 @@ impl FriProofCompressorDal<'_, '_> {
         .unwrap_or(0) as usize
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use zksync_basic_types::{protocol_version::L1VerifierConfig, prover_dal::ChainBatch};
+    use zksync_db_connection::connection_pool::ConnectionPool;
+
+    use super::*;
+    use crate::ProverDal;
+
+    async fn save_proven_batch(conn: &mut Connection<'_, Prover>, number: L1BatchNumber) {
+        conn.fri_basic_witness_generator_dal()
+            .save_witness_inputs(number, "witness_inputs", ProtocolSemanticVersion::default())
+            .await;
+        conn.fri_proof_compressor_dal()
+            .insert_proof_compression_job(number, "fri_proof", ProtocolSemanticVersion::default())
+            .await;
+        conn.fri_proof_compressor_dal()
+            .mark_proof_compression_job_successful(number, Duration::from_secs(1), "l1_proof")
+            .await;
+    }
+
+    async fn save_proven_chain_batch(
+        conn: &mut Connection<'_, Prover>,
+        chain_batch: ChainBatch,
+    ) -> L1BatchNumber {
+        let number = conn
+            .fri_chain_batches_dal()
+            .allocate_prover_batch_number(chain_batch)
+            .await;
+        save_proven_batch(conn, number).await;
+        number
+    }
+
+    async fn save_protocol_version(conn: &mut Connection<'_, Prover>) {
+        conn.fri_protocol_versions_dal()
+            .save_prover_protocol_version(
+                ProtocolSemanticVersion::default(),
+                L1VerifierConfig::default(),
+            )
+            .await;
+    }
+
+    #[tokio::test]
+    async fn proven_batches_of_not_configured_chains_are_skipped() {
+        let pool = ConnectionPool::<Prover>::prover_test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        save_protocol_version(&mut conn).await;
+        let configured_chain = L2ChainId::from(270);
+        let removed_chain = L2ChainId::from(271);
+        let removed_chain_number = save_proven_chain_batch(
+            &mut conn,
+            ChainBatch {
+                chain_id: removed_chain,
+                l1_batch_number: L1BatchNumber(1),
+            },
+        )
+        .await;
+        let configured_chain_number = save_proven_chain_batch(
+            &mut conn,
+            ChainBatch {
+                chain_id: configured_chain,
+                l1_batch_number: L1BatchNumber(1),
+            },
+        )
+        .await;
+        let number_without_chain = configured_chain_number + 1;
+        save_proven_batch(&mut conn, number_without_chain).await;
+
+        let (number, _, status) = conn
+            .fri_proof_compressor_dal()
+            .get_least_proven_block_not_sent_to_server(&[configured_chain])
+            .await
+            .unwrap();
+        assert_eq!(number, configured_chain_number);
+        assert!(matches!(status, ProofCompressionJobStatus::Successful));
+
+        conn.fri_proof_compressor_dal()
+            .mark_proof_sent_to_server(configured_chain_number)
+            .await;
+        let (number, ..) = conn
+            .fri_proof_compressor_dal()
+            .get_least_proven_block_not_sent_to_server(&[configured_chain])
+            .await
+            .unwrap();
+        assert_eq!(number, number_without_chain);
+
+        let (number, ..) = conn
+            .fri_proof_compressor_dal()
+            .get_least_proven_block_not_sent_to_server(&[configured_chain, removed_chain])
+            .await
+            .unwrap();
+        assert_eq!(number, removed_chain_number);
+    }
+
+    #[tokio::test]
+    async fn proofs_are_mapped_back_to_their_chain_batches() {
+        let pool = ConnectionPool::<Prover>::prover_test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        save_protocol_version(&mut conn).await;
+        let chains = [L2ChainId::from(270), L2ChainId::from(271)];
+        // Both chains have a batch with the same number.
+        let chain_batches = chains.map(|chain_id| ChainBatch {
+            chain_id,
+            l1_batch_number: L1BatchNumber(5),
+        });
+        let mut numbers = vec![];
+        for chain_batch in chain_batches {
+            numbers.push(save_proven_chain_batch(&mut conn, chain_batch).await);
+        }
+        assert_ne!(numbers[0], numbers[1]);
+
+        for (number, chain_batch) in numbers.into_iter().zip(chain_batches) {
+            let (proven_number, ..) = conn
+                .fri_proof_compressor_dal()
+                .get_least_proven_block_not_sent_to_server(&chains)
+                .await
+                .unwrap();
+            assert_eq!(proven_number, number);
+            let proven_batch = conn
+                .fri_chain_batches_dal()
+                .get_chain_batch(proven_number)
+                .await;
+            assert_eq!(proven_batch, Some(chain_batch));
+            conn.fri_proof_compressor_dal()
+                .mark_proof_sent_to_server(proven_number)
+                .await;
+        }
+        let proven = conn
+            .fri_proof_compressor_dal()
+            .get_least_proven_block_not_sent_to_server(&chains)
+            .await;
+        assert!(proven.is_none());
+    }
+
+    #[tokio::test]
+    async fn skipped_proofs_are_not_compressed_proofs() {
+        let pool = ConnectionPool::<Prover>::prover_test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        save_protocol_version(&mut conn).await;
+        assert!(
+            !conn
+                .fri_proof_compressor_dal()
+                .has_compressed_proofs()
+                .await
+        );
+
+        conn.fri_proof_compressor_dal()
+            .insert_proof_compression_job(
+                L1BatchNumber(1),
+                "fri_proof",
+                ProtocolSemanticVersion::default(),
+            )
+            .await;
+        conn.fri_proof_compressor_dal()
+            .mark_proof_compression_job_skipped(L1BatchNumber(1), Duration::from_secs(1))
+            .await;
+        conn.fri_proof_compressor_dal()
+            .mark_proof_sent_to_server(L1BatchNumber(1))
+            .await;
+        assert!(
+            !conn
+                .fri_proof_compressor_dal()
+                .has_compressed_proofs()
+                .await
+        );
+
+        save_proven_batch(&mut conn, L1BatchNumber(2)).await;
+        assert!(
+            conn.fri_proof_compressor_dal()
+                .has_compressed_proofs()
+                .await
+        );
+    }
+}
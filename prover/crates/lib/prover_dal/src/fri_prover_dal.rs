@@ -32,9 +32,9 @@ pub struct FriProverDal<'a, 'c> {
 impl FriProverDal<'_, '_> {
     // Postgres has a limit of 65535 push_bind parameters per query.
     // We need to split the insert into chunks to avoid hitting this limit.
-    // A single row in insert_prover_jobs push_binds 10 parameters, therefore
-    // the limit is 65k / 10 ~ 6500 jobs chunk.
-    const INSERT_JOBS_CHUNK_SIZE: usize = 6500;
+    // A single row in insert_prover_jobs push_binds 11 parameters, therefore
+    // the limit is 65k / 11 ~ 5900 jobs chunk.
+    const INSERT_JOBS_CHUNK_SIZE: usize = 5900;
 
     pub async fn insert_prover_jobs(
         &mut self,
@@ -68,7 +68,8 @@ impl FriProverDal<'_, '_> {
                     status,
                     created_at,
                     updated_at,
-                    protocol_version_patch
+                    protocol_version_patch,
+                    deadline
                 )
                 "#,
             );
@@ -87,7 +88,10 @@ impl FriProverDal<'_, '_> {
                         .push_bind("queued") // status
                         .push("NOW()") // created_at
                         .push("NOW()") // updated_at
-                        .push_bind(protocol_version_id.patch.0 as i32);
+                        .push_bind(protocol_version_id.patch.0 as i32)
+                        .push("(SELECT deadline FROM witness_inputs_fri WHERE l1_batch_number = ")
+                        .push_bind_unseparated(l1_batch_number.0 as i64)
+                        .push_unseparated(")"); // deadline
                 },
             );
 
@@ -111,7 +115,8 @@ impl FriProverDal<'_, '_> {
     /// Prover jobs must be thought of as ordered.
     /// Prover must prioritize proving such jobs that will make the chain move forward the fastest.
     /// Current ordering:
-    /// - pick the lowest batch
+    /// - pick the highest priority (requeued jobs and jobs of boosted batches go first)
+    /// - pick the job with the earliest batch SLA deadline, then the oldest job
     /// - within the lowest batch, look at the lowest aggregation level (move up the proof tree)
     /// - pick the same type of circuit for as long as possible, this maximizes GPU cache reuse
     ///
@@ -146,6 +151,7 @@ impl FriProverDal<'_, '_> {
                         AND aggregation_round = $4
                     ORDER BY
                         priority DESC,
+                        deadline ASC NULLS LAST,
                         created_at ASC,
                         circuit_id ASC,
                         id ASC
@@ -189,7 +195,8 @@ impl FriProverDal<'_, '_> {
     /// Prover jobs must be thought of as ordered.
     /// Prover must prioritize proving such jobs that will make the chain move forward the fastest.
     /// Current ordering:
-    /// - pick the lowest batch
+    /// - pick the highest priority (requeued jobs and jobs of boosted batches go first)
+    /// - pick the job with the earliest batch SLA deadline, then the oldest job
     /// - within the lowest batch, look at the lowest aggregation level (move up the proof tree)
     /// - pick the same type of circuit for as long as possible, this maximizes GPU cache reuse
     ///
@@ -224,6 +231,7 @@ impl FriProverDal<'_, '_> {
                         AND aggregation_round != $4
                     ORDER BY
                         priority DESC,
+                        deadline ASC NULLS LAST,
                         created_at ASC,
                         aggregation_round ASC,
                         circuit_id ASC
@@ -288,6 +296,7 @@ impl FriProverDal<'_, '_> {
                         AND protocol_version_patch = $2
                     ORDER BY
                         priority DESC,
+                        deadline ASC NULLS LAST,
                         created_at ASC,
                         aggregation_round DESC
                     LIMIT
@@ -370,12 +379,14 @@ impl FriProverDal<'_, '_> {
                             AND pj.aggregation_round = tuple.round
                         ORDER BY
                             pj.priority DESC,
+                            pj.deadline ASC NULLS LAST,
                             pj.created_at ASC
                         LIMIT
                             1
                     ) AS pj ON TRUE
                     ORDER BY
                         pj.priority DESC,
+                        pj.deadline ASC NULLS LAST,
                         pj.created_at ASC,
                         pj.aggregation_round DESC
                     LIMIT
@@ -592,10 +603,32 @@ impl FriProverDal<'_, '_> {
                 status,
                 created_at,
                 updated_at,
-                protocol_version_patch
+                protocol_version_patch,
+                deadline
             )
             VALUES
-            ($1, $2, $3, $4, $5, $6, $7, $8, 'queued', NOW(), NOW(), $9)
+            (
+                $1,
+                $2,
+                $3,
+                $4,
+                $5,
+                $6,
+                $7,
+                $8,
+                'queued',
+                NOW(),
+                NOW(),
+                $9,
+                (
+                    SELECT
+                        deadline
+                    FROM
+                        witness_inputs_fri
+                    WHERE
+                        l1_batch_number = $1
+                )
+            )
             ON CONFLICT (
                 l1_batch_number, aggregation_round, circuit_id, depth, sequence_number
             ) DO
@@ -760,6 +793,24 @@ impl FriProverDal<'_, '_> {
         .unwrap();
     }
 
+    /// Records that the witness vector of the job is generated and the job is handed over to
+    /// the GPU prover.
+    pub async fn mark_witness_vector_generated(&mut self, id: u32) {
+        sqlx::query!(
+            r#"
+            UPDATE prover_jobs_fri
+            SET
+                witness_vector_generated_at = NOW()
+            WHERE
+                id = $1
+            "#,
+            i64::from(id)
+        )
+        .execute(self.storage.conn())
+        .await
+        .unwrap();
+    }
+
     pub async fn get_scheduler_proof_job_id(
         &mut self,
         l1_batch_number: L1BatchNumber,
@@ -907,6 +958,7 @@ impl FriProverDal<'_, '_> {
                 ProtocolVersionId::try_from(protocol_version as u16).unwrap()
             }),
             picked_by: row.picked_by.clone(),
+            witness_vector_generated_at: row.witness_vector_generated_at,
         })
         .collect()
     }
@@ -78,6 +78,7 @@ impl FriBasicWitnessGeneratorDal<'_, '_> {
                         AND protocol_version_patch = $3
                     ORDER BY
                         priority DESC,
+                        deadline ASC NULLS LAST,
                         created_at ASC
                     LIMIT
                         1
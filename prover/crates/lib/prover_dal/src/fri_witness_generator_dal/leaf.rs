@@ -66,6 +66,7 @@ impl FriLeafWitnessGeneratorDal<'_, '_> {
                         AND protocol_version_patch = $2
                     ORDER BY
                         priority DESC,
+                        deadline ASC NULLS LAST,
                         created_at ASC
                     LIMIT
                         1
@@ -251,10 +252,29 @@ impl FriLeafWitnessGeneratorDal<'_, '_> {
                 status,
                 created_at,
                 updated_at,
-                protocol_version_patch
+                protocol_version_patch,
+                deadline
             )
             VALUES
-            ($1, $2, $3, $4, $5, 'waiting_for_proofs', NOW(), NOW(), $6)
+            (
+                $1,
+                $2,
+                $3,
+                $4,
+                $5,
+                'waiting_for_proofs',
+                NOW(),
+                NOW(),
+                $6,
+                (
+                    SELECT
+                        deadline
+                    FROM
+                        witness_inputs_fri
+                    WHERE
+                        l1_batch_number = $1
+                )
+            )
             ON CONFLICT (l1_batch_number, circuit_id) DO
             UPDATE
             SET
@@ -78,6 +78,7 @@ impl FriNodeWitnessGeneratorDal<'_, '_> {
                         AND protocol_version_patch = $2
                     ORDER BY
                         priority DESC,
+                        deadline ASC NULLS LAST,
                         created_at ASC
                     LIMIT
                         1
@@ -159,10 +160,30 @@ impl FriNodeWitnessGeneratorDal<'_, '_> {
                 status,
                 created_at,
                 updated_at,
-                protocol_version_patch
+                protocol_version_patch,
+                deadline
             )
             VALUES
-            ($1, $2, $3, $4, $5, $6, 'waiting_for_proofs', NOW(), NOW(), $7)
+            (
+                $1,
+                $2,
+                $3,
+                $4,
+                $5,
+                $6,
+                'waiting_for_proofs',
+                NOW(),
+                NOW(),
+                $7,
+                (
+                    SELECT
+                        deadline
+                    FROM
+                        witness_inputs_fri
+                    WHERE
+                        l1_batch_number = $1
+                )
+            )
             ON CONFLICT (l1_batch_number, circuit_id, depth) DO
             UPDATE
             SET
@@ -133,6 +133,7 @@ impl FriRecursionTipWitnessGeneratorDal<'_, '_> {
                         AND protocol_version_patch = $2
                     ORDER BY
                         priority DESC,
+                        deadline ASC NULLS LAST,
                         created_at ASC
                     LIMIT
                         1
@@ -275,10 +276,27 @@ impl FriRecursionTipWitnessGeneratorDal<'_, '_> {
                 protocol_version,
                 created_at,
                 updated_at,
-                protocol_version_patch
+                protocol_version_patch,
+                deadline
             )
             VALUES
-            ($1, 'waiting_for_proofs', $2, $3, NOW(), NOW(), $4)
+            (
+                $1,
+                'waiting_for_proofs',
+                $2,
+                $3,
+                NOW(),
+                NOW(),
+                $4,
+                (
+                    SELECT
+                        deadline
+                    FROM
+                        witness_inputs_fri
+                    WHERE
+                        l1_batch_number = $1
+                )
+            )
             ON CONFLICT (l1_batch_number) DO
             UPDATE
             SET
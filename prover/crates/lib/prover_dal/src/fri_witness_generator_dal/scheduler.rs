@@ -145,6 +145,7 @@ impl FriSchedulerWitnessGeneratorDal<'_, '_> {
                         AND protocol_version_patch = $3
                     ORDER BY
                         priority DESC,
+                        deadline ASC NULLS LAST,
                         created_at ASC
                     LIMIT
                         1
@@ -281,10 +282,27 @@ impl FriSchedulerWitnessGeneratorDal<'_, '_> {
                 status,
                 created_at,
                 updated_at,
-                protocol_version_patch
+                protocol_version_patch,
+                deadline
             )
             VALUES
-            ($1, $2, $3, 'waiting_for_proofs', NOW(), NOW(), $4)
+            (
+                $1,
+                $2,
+                $3,
+                'waiting_for_proofs',
+                NOW(),
+                NOW(),
+                $4,
+                (
+                    SELECT
+                        deadline
+                    FROM
+                        witness_inputs_fri
+                    WHERE
+                        l1_batch_number = $1
+                )
+            )
             ON CONFLICT (l1_batch_number) DO
             UPDATE
             SET
@@ -7,6 +7,8 @@ pub use zksync_db_connection::{
 
 use crate::{
     cli_test_dal::CliTestDal,
+    fri_batch_sla_dal::FriBatchSlaDal,
+    fri_chain_batches_dal::FriChainBatchesDal,
     fri_gpu_prover_queue_dal::FriGpuProverQueueDal,
     fri_proof_compressor_dal::FriProofCompressorDal,
     fri_protocol_versions_dal::FriProtocolVersionsDal,
@@ -19,6 +21,8 @@ use crate::{
 };
 
 pub mod cli_test_dal;
+pub mod fri_batch_sla_dal;
+pub mod fri_chain_batches_dal;
 pub mod fri_gpu_prover_queue_dal;
 pub mod fri_proof_compressor_dal;
 pub mod fri_protocol_versions_dal;
@@ -59,6 +63,10 @@ where
     fn fri_protocol_versions_dal(&mut self) -> FriProtocolVersionsDal<'_, 'a>;
 
     fn fri_proof_compressor_dal(&mut self) -> FriProofCompressorDal<'_, 'a>;
+
+    fn fri_batch_sla_dal(&mut self) -> FriBatchSlaDal<'_, 'a>;
+
+    fn fri_chain_batches_dal(&mut self) -> FriChainBatchesDal<'_, 'a>;
 }
 
 #[derive(Clone, Debug)]
@@ -114,4 +122,12 @@ impl<'a> ProverDal<'a> for Connection<'a, Prover> {
     fn fri_proof_compressor_dal(&mut self) -> FriProofCompressorDal<'_, 'a> {
         FriProofCompressorDal { storage: self }
     }
+
+    fn fri_batch_sla_dal(&mut self) -> FriBatchSlaDal<'_, 'a> {
+        FriBatchSlaDal { storage: self }
+    }
+
+    fn fri_chain_batches_dal(&mut self) -> FriChainBatchesDal<'_, 'a> {
+        FriChainBatchesDal { storage: self }
+    }
 }
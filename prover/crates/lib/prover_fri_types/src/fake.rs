@@ -0,0 +1,112 @@
+//! Placeholder artifacts for running the prover pipeline in fake proof mode.
+
+use zksync_object_store::{bincode, ObjectStore, ObjectStoreError, StoredObject};
+use zksync_types::{basic_fri_types::AggregationRound, L1BatchNumber};
+
+/// Kind of the real artifact a [`FakeArtifact`] stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum FakeArtifactKind {
+    Circuit,
+    Proof,
+    ClosedFormInputs,
+    Aggregations,
+    SchedulerPartialInput,
+    AuxOutputWitness,
+}
+
+/// Placeholder exchanged by witness generators, circuit provers and proof compressors running
+/// in fake proof mode. It is saved in the bucket and under the key of the artifact it stands for,
+/// so jobs go through the same database states and object store locations as with real proofs,
+/// without witness inputs, setup keys or GPUs.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FakeArtifact {
+    pub kind: FakeArtifactKind,
+    pub l1_batch_number: L1BatchNumber,
+    pub aggregation_round: AggregationRound,
+    pub circuit_id: u8,
+    pub depth: u16,
+}
+
+impl FakeArtifact {
+    /// Stores the artifact in place of a `V`. Returns the key, same as [`ObjectStore::put()`].
+    pub async fn put<V: StoredObject>(
+        &self,
+        store: &dyn ObjectStore,
+        key: V::Key<'_>,
+    ) -> Result<String, ObjectStoreError> {
+        let key = V::encode_key(key);
+        let bytes =
+            bincode::serialize(self).map_err(|err| ObjectStoreError::Serialization(err.into()))?;
+        store.put_raw(V::BUCKET, &key, bytes).await?;
+        Ok(key)
+    }
+
+    /// Loads the artifact stored in place of a `V`, checking that it is of the expected kind and
+    /// batch.
+    pub async fn get<V: StoredObject>(
+        store: &dyn ObjectStore,
+        key: V::Key<'_>,
+        kind: FakeArtifactKind,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<Self, ObjectStoreError> {
+        let bytes = store.get_raw(V::BUCKET, &V::encode_key(key)).await?;
+        let artifact: Self = bincode::deserialize(&bytes)
+            .map_err(|err| ObjectStoreError::Serialization(err.into()))?;
+        if artifact.kind != kind || artifact.l1_batch_number != l1_batch_number {
+            let err =
+                format!("expected fake {kind:?} for batch {l1_batch_number}, got {artifact:?}");
+            return Err(ObjectStoreError::Serialization(err.into()));
+        }
+        Ok(artifact)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_object_store::MockObjectStore;
+
+    use super::*;
+    use crate::FriProofWrapper;
+
+    #[tokio::test]
+    async fn fake_artifact_kind_and_batch_are_checked() {
+        let store = MockObjectStore::arc();
+        let artifact = FakeArtifact {
+            kind: FakeArtifactKind::Proof,
+            l1_batch_number: L1BatchNumber(1),
+            aggregation_round: AggregationRound::Scheduler,
+            circuit_id: 1,
+            depth: 0,
+        };
+        artifact.put::<FriProofWrapper>(&*store, 1).await.unwrap();
+
+        let loaded = FakeArtifact::get::<FriProofWrapper>(
+            &*store,
+            1,
+            FakeArtifactKind::Proof,
+            L1BatchNumber(1),
+        )
+        .await
+        .unwrap();
+        assert_eq!(loaded, artifact);
+
+        let err = FakeArtifact::get::<FriProofWrapper>(
+            &*store,
+            1,
+            FakeArtifactKind::Circuit,
+            L1BatchNumber(1),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, ObjectStoreError::Serialization(_)), "{err}");
+        let err = FakeArtifact::get::<FriProofWrapper>(
+            &*store,
+            1,
+            FakeArtifactKind::Proof,
+            L1BatchNumber(2),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, ObjectStoreError::Serialization(_)), "{err}");
+    }
+}
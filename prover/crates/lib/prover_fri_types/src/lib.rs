@@ -22,6 +22,7 @@ use zksync_types::{
 
 use crate::keys::FriCircuitKey;
 
+pub mod fake;
 pub mod keys;
 pub mod queue;
 
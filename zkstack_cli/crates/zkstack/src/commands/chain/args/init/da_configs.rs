@@ -99,6 +99,7 @@ impl ValidiumType {
                                         })
                                     })
                                     .ask(),
+                                inclusion_delay_ms: None,
                             })
                         }
                     };